@@ -1,5 +1,10 @@
+// versions of the embedded codes (reported by backend_info)
+const TRIANGLE_VERSION: &str = "1.6";
+const TETGEN_VERSION: &str = "1.4.3";
+
 fn main() {
     cc::Build::new()
+        .define("TRIANGLE_VERSION", format!("\"{}\"", TRIANGLE_VERSION).as_str())
         .file("c_code/triangle.c")
         .file("c_code/tricall_report.c")
         .file("c_code/interface_triangle.c")
@@ -9,6 +14,7 @@ fn main() {
         .compile("c_code_interface_triangle");
     cc::Build::new()
         .cpp(true)
+        .define("TETGEN_VERSION", format!("\"{}\"", TETGEN_VERSION).as_str())
         .file("c_code/predicates.cxx")
         .file("c_code/tetgen.cxx")
         .file("c_code/interface_tetgen.cpp")
@@ -16,4 +22,8 @@ fn main() {
         .flag("-Wno-unused-parameter")
         .flag("-Wno-unused-but-set-variable")
         .compile("c_code_interface_tetgen");
+    // the console is compiled last because it is used by both libraries above
+    cc::Build::new()
+        .file("c_code/console.c")
+        .compile("c_code_console");
 }
@@ -0,0 +1,42 @@
+#include <stdarg.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "console.h"
+
+#undef printf
+
+static _Thread_local void (*console_callback)(void *, const char *, int32_t) = NULL;
+static _Thread_local void *console_data = NULL;
+
+void tritet_set_console_callback(void (*callback)(void *, const char *, int32_t), void *data) {
+    console_callback = callback;
+    console_data = data;
+}
+
+int tritet_printf(const char *format, ...) {
+    va_list args;
+    va_start(args, format);
+    if (console_callback == NULL) {
+        int n = vprintf(format, args);
+        va_end(args);
+        return n;
+    }
+    char buffer[512];
+    va_list copy;
+    va_copy(copy, args);
+    int n = vsnprintf(buffer, sizeof(buffer), format, copy);
+    va_end(copy);
+    if (n >= 0 && (size_t)n < sizeof(buffer)) {
+        console_callback(console_data, buffer, n);
+    } else if (n >= 0) {
+        char *text = (char *)malloc((size_t)n + 1);
+        if (text != NULL) {
+            vsnprintf(text, (size_t)n + 1, format, args);
+            console_callback(console_data, text, n);
+            free(text);
+        }
+    }
+    va_end(args);
+    return n;
+}
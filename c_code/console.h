@@ -0,0 +1,24 @@
+#ifndef TRITET_CONSOLE_H
+#define TRITET_CONSOLE_H
+
+#include <inttypes.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// Prints to stdout or passes the formatted text to the console callback (if set)
+int tritet_printf(const char *format, ...);
+
+// Sets the callback receiving the text printed by the c-code (NULL restores stdout)
+// The callback is thread-local; thus, it must be set by the thread calling the run functions
+void tritet_set_console_callback(void (*callback)(void *, const char *, int32_t), void *data);
+
+#ifdef __cplusplus
+}
+#endif
+
+// Redirects the calls to printf in the files including this header (after the system headers)
+#define printf tritet_printf
+
+#endif  // TRITET_CONSOLE_H
@@ -10,6 +10,9 @@ const int32_t TRITET_SUCCESS = 0;
 
 const int32_t TRITET_ERROR_NULL_DATA = 10;
 const int32_t TRITET_ERROR_STRING_CONCAT = 20;
+const int32_t TRITET_ERROR_ALLOC = 30;
+const int32_t TRITET_ERROR_CANCELLED = 40;
+const int32_t TRITET_ERROR_TETGEN_FAILED = 50;
 
 const int32_t TRITET_ERROR_NULL_POINT_LIST = 100;
 const int32_t TRITET_ERROR_NULL_SEGMENT_LIST = 200;
@@ -17,6 +20,7 @@ const int32_t TRITET_ERROR_NULL_FACET_LIST = 300;
 const int32_t TRITET_ERROR_NULL_FACET_POLYGON_LIST = 400;
 const int32_t TRITET_ERROR_NULL_REGION_LIST = 500;
 const int32_t TRITET_ERROR_NULL_HOLE_LIST = 600;
+const int32_t TRITET_ERROR_NULL_MESH = 700;
 
 const int32_t TRITET_ERROR_INVALID_POINT_INDEX = 1000;
 const int32_t TRITET_ERROR_INVALID_SEGMENT_INDEX = 2000;
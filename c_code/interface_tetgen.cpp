@@ -15,6 +15,7 @@ void drop_tetgen(struct ExtTetgen *tetgen) {
     if (tetgen == NULL) {
         return;
     }
+    delete[] tetgen->original_index;
     delete tetgen;
 }
 
@@ -28,9 +29,11 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
     if (tetgen == NULL) {
         return NULL;
     }
+    tetgen->original_index = NULL;
     try {
         tetgen->input.initialize();
         tetgen->output.initialize();
+        tetgen->addin.initialize();
     } catch (...) {
         drop_tetgen(tetgen);
         return NULL;
@@ -53,6 +56,11 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
             drop_tetgen(tetgen);
             return NULL;
         }
+        tetgen->input.facetmarkerlist = new (std::nothrow) int32_t[nfacet];
+        if (tetgen->input.facetmarkerlist == NULL) {
+            drop_tetgen(tetgen);
+            return NULL;
+        }
         const int32_t NUM_POLY = 1;
         for (int32_t index = 0; index < nfacet; index++) {
             // facet polygon
@@ -74,6 +82,8 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
                 return NULL;
             }
             gon->numberofvertices = nvertex;
+            // facet marker
+            tetgen->input.facetmarkerlist[index] = 0;
         }
     }
 
@@ -100,6 +110,16 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
     return tetgen;
 }
 
+void tet_clear_outputs(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return;
+    }
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    delete[] tetgen->original_index;
+    tetgen->original_index = NULL;
+}
+
 int32_t tet_set_point(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -117,6 +137,41 @@ int32_t tet_set_point(struct ExtTetgen *tetgen, int32_t index, double x, double
     return TRITET_SUCCESS;
 }
 
+int32_t tet_set_points(struct ExtTetgen *tetgen, int32_t npoint, double const *coordinates) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+    if (npoint != tetgen->input.numberofpoints) {
+        return TRITET_ERROR_INVALID_POINT_INDEX;
+    }
+    memcpy(tetgen->input.pointlist, coordinates, npoint * 3 * sizeof(double));
+
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_set_additional_points(struct ExtTetgen *tetgen, int32_t npoint, double const *coordinates) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    double *pointlist = NULL;
+    if (npoint > 0) {
+        pointlist = new (std::nothrow) double[npoint * 3];
+        if (pointlist == NULL) {
+            return TRITET_ERROR_ALLOC;
+        }
+        memcpy(pointlist, coordinates, npoint * 3 * sizeof(double));
+    }
+    delete[] tetgen->addin.pointlist;
+    tetgen->addin.firstnumber = 0;
+    tetgen->addin.pointlist = pointlist;
+    tetgen->addin.numberofpoints = npoint;
+
+    return TRITET_SUCCESS;
+}
+
 int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m, int32_t p) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -132,7 +187,7 @@ int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m,
     if (fac->polygonlist == NULL) {
         return TRITET_ERROR_NULL_FACET_POLYGON_LIST;
     }
-    if (fac->numberofpolygons != 1) {
+    if (fac->numberofpolygons < 1) {
         return TRITET_ERROR_INVALID_FACET_NUM_POLYGON;
     }
 
@@ -148,7 +203,113 @@ int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m,
     return TRITET_SUCCESS;
 }
 
-int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, int32_t attribute, double max_volume) {
+int32_t tet_set_facet_polygon(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t npoint, int32_t const *points) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.facetlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
+    }
+    if (index >= tetgen->input.numberoffacets) {
+        return TRITET_ERROR_INVALID_FACET_INDEX;
+    }
+
+    tetgenio::facet *fac = &tetgen->input.facetlist[index];
+    if (fac->polygonlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_POLYGON_LIST;
+    }
+    if (polygon < 1 || polygon > fac->numberofpolygons) {
+        return TRITET_ERROR_INVALID_FACET_NUM_POLYGON;
+    }
+    for (int32_t m = 0; m < npoint; m++) {
+        if (points[m] < 0 || points[m] >= tetgen->input.numberofpoints) {
+            return TRITET_ERROR_INVALID_FACET_POINT_ID;
+        }
+    }
+    int32_t *vertexlist = new (std::nothrow) int32_t[npoint];
+    if (vertexlist == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    for (int32_t m = 0; m < npoint; m++) {
+        vertexlist[m] = points[m];
+    }
+
+    // append a new polygon or replace an existing one
+    if (polygon == fac->numberofpolygons) {
+        tetgenio::polygon *polygonlist = new (std::nothrow) tetgenio::polygon[fac->numberofpolygons + 1];
+        if (polygonlist == NULL) {
+            delete[] vertexlist;
+            return TRITET_ERROR_ALLOC;
+        }
+        for (int32_t i = 0; i < fac->numberofpolygons; i++) {
+            polygonlist[i] = fac->polygonlist[i];
+        }
+        delete[] fac->polygonlist;
+        fac->polygonlist = polygonlist;
+        fac->numberofpolygons += 1;
+    } else {
+        delete[] fac->polygonlist[polygon].vertexlist;
+    }
+    fac->polygonlist[polygon].vertexlist = vertexlist;
+    fac->polygonlist[polygon].numberofvertices = npoint;
+
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_set_facet_hole(struct ExtTetgen *tetgen, int32_t index, int32_t hole, double x, double y, double z) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.facetlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
+    }
+    if (index >= tetgen->input.numberoffacets) {
+        return TRITET_ERROR_INVALID_FACET_INDEX;
+    }
+
+    tetgenio::facet *fac = &tetgen->input.facetlist[index];
+    if (hole < 0 || hole > fac->numberofholes) {
+        return TRITET_ERROR_INVALID_HOLE_INDEX;
+    }
+
+    // append a new hole or replace an existing one
+    if (hole == fac->numberofholes) {
+        double *holelist = new (std::nothrow) double[(fac->numberofholes + 1) * 3];
+        if (holelist == NULL) {
+            return TRITET_ERROR_ALLOC;
+        }
+        for (int32_t i = 0; i < fac->numberofholes * 3; i++) {
+            holelist[i] = fac->holelist[i];
+        }
+        if (fac->holelist != NULL) {
+            delete[] fac->holelist;
+        }
+        fac->holelist = holelist;
+        fac->numberofholes += 1;
+    }
+    fac->holelist[hole * 3] = x;
+    fac->holelist[hole * 3 + 1] = y;
+    fac->holelist[hole * 3 + 2] = z;
+
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_set_facet_marker(struct ExtTetgen *tetgen, int32_t index, int32_t marker) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.facetlist == NULL || tetgen->input.facetmarkerlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
+    }
+    if (index >= tetgen->input.numberoffacets) {
+        return TRITET_ERROR_INVALID_FACET_INDEX;
+    }
+    tetgen->input.facetmarkerlist[index] = marker;
+
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, double attribute, double max_volume) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
     }
@@ -184,6 +345,74 @@ int32_t tet_set_hole(struct ExtTetgen *tetgen, int32_t index, double x, double y
     return TRITET_SUCCESS;
 }
 
+// Calls tetrahedralize, maps the exceptions thrown by Tetgen to the error codes, and records the
+// index of the input point corresponding to each output point
+//
+// Exceptions:
+// * `TETGEN_CANCELLED` -- the generation has been stopped by the cancellation callback
+// * `1` or `std::bad_alloc` -- out of memory
+// * any other -- Tetgen could not generate the mesh (e.g., the facets intersect each other)
+//
+// The input points are numbered by temporary markers starting at 1 (Tetgen replaces the zero
+// markers of the boundary points by 1). Tetgen keeps the input points as the first output points,
+// but removes the duplicated and unused ones and compacts the markers accordingly (see
+// jettisonnodes in tetgen.cxx). Thus, the compacted markers give the input point of the first
+// output points and the other output points receive -1. If not NULL, `input_original` gives the
+// indices in tetgen->input of the points of `input` (e.g., when refining the previous output).
+static int32_t run_tetrahedralize(struct ExtTetgen *tetgen, char *command, tetgenio *input, tetgenio *addin, int32_t const *input_original) {
+    int32_t ninput = input->numberofpoints;
+    int *markers = new (std::nothrow) int[ninput];
+    if (markers == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    for (int32_t i = 0; i < ninput; i++) {
+        markers[i] = i + 1;
+    }
+    int *input_markers = input->pointmarkerlist;
+    input->pointmarkerlist = markers;
+
+    int32_t status = TRITET_SUCCESS;
+    try {
+        tetrahedralize(command, input, &tetgen->output, addin, NULL);
+    } catch (int32_t code) {
+        if (code == TETGEN_CANCELLED) {
+            status = TRITET_ERROR_CANCELLED;
+        } else if (code == 1) {
+            status = TRITET_ERROR_ALLOC;
+        } else {
+            status = TRITET_ERROR_TETGEN_FAILED;
+        }
+    } catch (std::bad_alloc &) {
+        status = TRITET_ERROR_ALLOC;
+    } catch (...) {
+        status = TRITET_ERROR_TETGEN_FAILED;
+    }
+    int32_t nkept = input->numberofpoints;
+    input->pointmarkerlist = input_markers;
+
+    int32_t *original = NULL;
+    int32_t npoint = tetgen->output.numberofpoints;
+    if (status == TRITET_SUCCESS && npoint > 0) {
+        original = new (std::nothrow) int32_t[npoint];
+        if (original == NULL) {
+            status = TRITET_ERROR_ALLOC;
+        } else {
+            for (int32_t i = 0; i < npoint; i++) {
+                if (i < nkept) {
+                    int32_t m = markers[i] - 1;
+                    original[i] = input_original == NULL ? m : input_original[m];
+                } else {
+                    original[i] = -1;
+                }
+            }
+        }
+    }
+    delete[] markers;
+    delete[] tetgen->original_index;
+    tetgen->original_index = original;
+    return status;
+}
+
 int32_t tet_run_delaunay(struct ExtTetgen *tetgen, int32_t verbose) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -200,18 +429,99 @@ int32_t tet_run_delaunay(struct ExtTetgen *tetgen, int32_t verbose) {
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
-    try {
-        tetrahedralize(command, &tetgen->input, &tetgen->output, NULL, NULL);
-    } catch (int32_t status) {
-        printf("status = %d\n", status);  // TODO
-    } catch (...) {
-        return 1;  // TODO
+    // The lists of a previous call are not freed by tetrahedralize (and some of them, e.g., the
+    // face markers, are not overwritten); thus, the output is reset
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    // Tetgen decreases input.numberofpoints when jettisoning duplicated points; thus, it is restored
+    // after the call to allow running the generator again with the same input
+    int32_t npoint = tetgen->input.numberofpoints;
+    int32_t status = run_tetrahedralize(tetgen, command, &tetgen->input, NULL, NULL);
+    tetgen->input.numberofpoints = npoint;
+    return status;
+}
+
+int32_t tet_run_cdt(struct ExtTetgen *tetgen, int32_t verbose) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+    if (tetgen->input.facetlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
     }
 
+    // Tetrahedralize the PLC without quality constraints (boundary recovery only)
+    // Switches:
+    // * `p` -- tetrahedralize a piecewise linear complex (PLC)
+    // * `z` -- number everything from zero (z)
+    // * `A` -- assign a regional attribute to each element (A)
+    char command[10];
+    strcpy(command, "pzA");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    // The lists of a previous call are not freed by tetrahedralize; thus, the output is reset
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    // The A switch increments input.numberoftetrahedronattributes; thus, it is restored after the call
+    int32_t npoint = tetgen->input.numberofpoints;
+    int32_t nattribute = tetgen->input.numberoftetrahedronattributes;
+    int32_t status = run_tetrahedralize(tetgen, command, &tetgen->input, NULL, NULL);
+    tetgen->input.numberofpoints = npoint;
+    tetgen->input.numberoftetrahedronattributes = nattribute;
+    return status;
+}
+
+int32_t tet_run_voronoi(struct ExtTetgen *tetgen, int32_t verbose) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+
+    // Tetrahedralize the points and generate the Voronoi diagram
+    // Switches:
+    // * `z` -- number everything from zero (z)
+    // * `v` -- generate the Voronoi diagram (v)
+    char command[10];
+    strcpy(command, "zv");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    // The Voronoi lists of a previous call are not freed by tetrahedralize; thus, the output is reset
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    int32_t npoint = tetgen->input.numberofpoints;
+    int32_t status = run_tetrahedralize(tetgen, command, &tetgen->input, NULL, NULL);
+    tetgen->input.numberofpoints = npoint;
+    return status;
+}
+
+// Appends the quality switches to the command
+// * `q<ratio>` -- maximum radius-edge ratio (the default is 2.0)
+// * `q<angle>` -- minimum dihedral angle in degrees, given by a second `q` (the default is 5.0)
+static int32_t append_quality(char *command, double max_radius_edge_ratio, double min_dihedral_angle) {
+    char buf[64];
+    int32_t n = 0;
+    if (min_dihedral_angle > 0.0) {
+        double ratio = max_radius_edge_ratio > 0.0 ? max_radius_edge_ratio : 2.0;
+        n = snprintf(buf, 64, "q%.15fq%.15f", ratio, min_dihedral_angle);
+    } else if (max_radius_edge_ratio > 0.0) {
+        n = snprintf(buf, 64, "q%.15f", max_radius_edge_ratio);
+    } else {
+        n = snprintf(buf, 64, "q");
+    }
+    if (n >= 64) {
+        return TRITET_ERROR_STRING_CONCAT;
+    }
+    strcat(command, buf);
     return TRITET_SUCCESS;
 }
 
-int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double global_max_volume, double global_min_angle) {
+int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double global_max_volume, double max_radius_edge_ratio, double min_dihedral_angle, int32_t max_steiner_points, int32_t no_bisect) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
     }
@@ -227,6 +537,10 @@ int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_
     // * `p` -- tetrahedralize a piecewise linear complex (PLC)
     // * `z` -- number everything from zero (z)
     // * `A` -- assign a regional attribute to each element (A)
+    // * `q` -- quality mesh generation (q)
+    // * `S` -- maximum number of Steiner points (S)
+    // * `Y` -- prohibit Steiner points on the boundary (Y) or on all facets and segments (YY)
+    // * `i` -- insert the additional points (i)
     char command[128];
     strcpy(command, "pzA");
     if (verbose == TRITET_FALSE) {
@@ -235,6 +549,11 @@ int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_
     if (o2 == TRITET_TRUE) {
         strcat(command, "o2");
     }
+    tetgenio *addin = NULL;
+    if (tetgen->addin.numberofpoints > 0) {
+        strcat(command, "i");
+        addin = &tetgen->addin;
+    }
     if (global_max_volume > 0.0) {
         char buf[32];
         int32_t n = snprintf(buf, 32, "a%.15f", global_max_volume);
@@ -243,25 +562,199 @@ int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_
         }
         strcat(command, buf);
     }
-    if (global_min_angle > 0.0) {
+    if (append_quality(command, max_radius_edge_ratio, min_dihedral_angle) != TRITET_SUCCESS) {
+        return TRITET_ERROR_STRING_CONCAT;
+    }
+    if (max_steiner_points >= 0) {
         char buf[32];
-        int32_t n = snprintf(buf, 32, "q%.15f", global_min_angle);
+        int32_t n = snprintf(buf, 32, "S%d", max_steiner_points);
         if (n >= 32) {
             return TRITET_ERROR_STRING_CONCAT;
         }
         strcat(command, buf);
-    } else {
-        strcat(command, "q");
     }
-    try {
-        tetrahedralize(command, &tetgen->input, &tetgen->output, NULL, NULL);
-    } catch (int32_t status) {
-        printf("status = %d\n", status);  // TODO
-    } catch (...) {
-        return 1;  // TODO
+    if (no_bisect == 1) {
+        strcat(command, "Y");
+    } else if (no_bisect > 1) {
+        strcat(command, "YY");
     }
+    // The lists of a previous call are not freed by tetrahedralize (and some of them, e.g., the
+    // face markers, are not overwritten); thus, the output is reset
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    // Tetgen decreases input.numberofpoints when jettisoning duplicated points; thus, it is restored
+    // after the call to allow running the generator again with the same input. Likewise, the A switch
+    // increments input.numberoftetrahedronattributes (the regional attribute must remain the first one)
+    int32_t npoint = tetgen->input.numberofpoints;
+    int32_t nattribute = tetgen->input.numberoftetrahedronattributes;
+    int32_t status = run_tetrahedralize(tetgen, command, &tetgen->input, addin, NULL);
+    tetgen->input.numberofpoints = npoint;
+    tetgen->input.numberoftetrahedronattributes = nattribute;
+    return status;
+}
 
-    return TRITET_SUCCESS;
+int32_t tet_run_refine(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double const *max_volumes, double global_max_volume, double max_radius_edge_ratio, double min_dihedral_angle, int32_t max_steiner_points, int32_t no_bisect) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->output.pointlist == NULL || tetgen->output.tetrahedronlist == NULL) {
+        return TRITET_ERROR_NULL_MESH;
+    }
+
+    // Feed the current output back as input
+    // (the unused middle nodes of quadratic tetrahedra are jettisoned by Tetgen)
+    tetgenio input;
+    tetgenio *out = &tetgen->output;
+    int32_t npoint = out->numberofpoints;
+    int32_t ntet = out->numberoftetrahedra;
+    int32_t ncorner = out->numberofcorners;
+    int32_t nattribute = out->numberoftetrahedronattributes;
+    int32_t nface = out->numberoftrifaces;
+    input.firstnumber = 0;
+    input.pointlist = new (std::nothrow) double[npoint * 3];
+    input.tetrahedronlist = new (std::nothrow) int32_t[ntet * ncorner];
+    input.tetrahedronvolumelist = new (std::nothrow) double[ntet];
+    if (input.pointlist == NULL || input.tetrahedronlist == NULL || input.tetrahedronvolumelist == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    input.numberofpoints = npoint;
+    for (int32_t i = 0; i < npoint * 3; i++) {
+        input.pointlist[i] = out->pointlist[i];
+    }
+    input.numberoftetrahedra = ntet;
+    input.numberofcorners = ncorner;
+    for (int32_t i = 0; i < ntet * ncorner; i++) {
+        input.tetrahedronlist[i] = out->tetrahedronlist[i];
+    }
+    for (int32_t i = 0; i < ntet; i++) {
+        input.tetrahedronvolumelist[i] = max_volumes[i] > 0.0 ? max_volumes[i] : -1.0;
+    }
+    if (nattribute > 0 && out->tetrahedronattributelist != NULL) {
+        input.tetrahedronattributelist = new (std::nothrow) double[ntet * nattribute];
+        if (input.tetrahedronattributelist == NULL) {
+            return TRITET_ERROR_ALLOC;
+        }
+        for (int32_t i = 0; i < ntet * nattribute; i++) {
+            input.tetrahedronattributelist[i] = out->tetrahedronattributelist[i];
+        }
+        input.numberoftetrahedronattributes = nattribute;
+    }
+    if (nface > 0 && out->trifacelist != NULL) {
+        input.trifacelist = new (std::nothrow) int32_t[nface * 3];
+        input.trifacemarkerlist = new (std::nothrow) int32_t[nface];
+        if (input.trifacelist == NULL || input.trifacemarkerlist == NULL) {
+            return TRITET_ERROR_ALLOC;
+        }
+        for (int32_t i = 0; i < nface * 3; i++) {
+            input.trifacelist[i] = out->trifacelist[i];
+        }
+        for (int32_t i = 0; i < nface; i++) {
+            input.trifacemarkerlist[i] = out->trifacemarkerlist == NULL ? 0 : out->trifacemarkerlist[i];
+        }
+        input.numberoftrifaces = nface;
+    }
+
+    // Refine mesh
+    // Switches:
+    // * `r` -- refine a previously generated mesh (r)
+    // * `z` -- number everything from zero (z)
+    // * `a` -- use the volume constraint of each tetrahedron (a)
+    // * `q` -- quality mesh generation (q)
+    // * `S` -- maximum number of Steiner points (S)
+    // * `Y` -- prohibit Steiner points on the boundary (Y) or on all facets and segments (YY)
+    char command[128];
+    strcpy(command, "rza");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (o2 == TRITET_TRUE) {
+        strcat(command, "o2");
+    }
+    if (global_max_volume > 0.0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "a%.15f", global_max_volume);
+        if (n >= 32) {
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    }
+    if (append_quality(command, max_radius_edge_ratio, min_dihedral_angle) != TRITET_SUCCESS) {
+        return TRITET_ERROR_STRING_CONCAT;
+    }
+    if (max_steiner_points >= 0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "S%d", max_steiner_points);
+        if (n >= 32) {
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    }
+    if (no_bisect == 1) {
+        strcat(command, "Y");
+    } else if (no_bisect > 1) {
+        strcat(command, "YY");
+    }
+
+    // The refined mesh replaces the previous output
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    // The points of the input are the points of the previous output
+    return run_tetrahedralize(tetgen, command, &input, NULL, tetgen->original_index);
+}
+
+int32_t tet_run_given_mesh(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, int32_t ntet, int32_t const *corners, int32_t nface, int32_t const *faces, int32_t const *face_markers) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+
+    // The tetrahedra and the marked faces are given to Tetgen as a previously generated mesh
+    tetgenio input;
+    int32_t npoint = tetgen->input.numberofpoints;
+    input.firstnumber = 0;
+    input.pointlist = new (std::nothrow) double[npoint * 3];
+    input.tetrahedronlist = new (std::nothrow) int32_t[ntet * 4];
+    input.trifacelist = new (std::nothrow) int32_t[nface * 3];
+    input.trifacemarkerlist = new (std::nothrow) int32_t[nface];
+    if (input.pointlist == NULL || input.tetrahedronlist == NULL || input.trifacelist == NULL || input.trifacemarkerlist == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    input.numberofpoints = npoint;
+    for (int32_t i = 0; i < npoint * 3; i++) {
+        input.pointlist[i] = tetgen->input.pointlist[i];
+    }
+    input.numberoftetrahedra = ntet;
+    input.numberofcorners = 4;
+    for (int32_t i = 0; i < ntet * 4; i++) {
+        input.tetrahedronlist[i] = corners[i];
+    }
+    input.numberoftrifaces = nface;
+    for (int32_t i = 0; i < nface * 3; i++) {
+        input.trifacelist[i] = faces[i];
+    }
+    for (int32_t i = 0; i < nface; i++) {
+        input.trifacemarkerlist[i] = face_markers[i];
+    }
+
+    // Reconstruct the mesh (without refinement)
+    // Switches:
+    // * `r` -- read a previously generated mesh (r)
+    // * `z` -- number everything from zero (z)
+    char command[16];
+    strcpy(command, "rz");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (o2 == TRITET_TRUE) {
+        strcat(command, "o2");
+    }
+
+    // The given mesh replaces the previous output
+    tetgen->output.deinitialize();
+    tetgen->output.initialize();
+    return run_tetrahedralize(tetgen, command, &input, NULL, NULL);
 }
 
 int32_t tet_get_npoint(struct ExtTetgen *tetgen) {
@@ -329,3 +822,257 @@ int32_t tet_get_tetrahedron_attribute(struct ExtTetgen *tetgen, int32_t index) {
 
     return 0;
 }
+
+double tet_get_tetrahedron_attribute_f64(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->output.numberoftetrahedra && tetgen->output.numberoftetrahedronattributes > 0) {
+        return tetgen->output.tetrahedronattributelist[index * tetgen->output.numberoftetrahedronattributes];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t tet_get_nmarked_face(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberoftrifaces;
+}
+
+int32_t tet_get_marked_face_corner(struct ExtTetgen *tetgen, int32_t index, int32_t corner) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberoftrifaces && (corner == 0 || corner == 1 || corner == 2)) {
+        return tetgen->output.trifacelist[index * 3 + corner];
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_point_original_index(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return -1;
+    }
+    if (index < tetgen->output.numberofpoints && tetgen->original_index != NULL) {
+        return tetgen->original_index[index];
+    } else {
+        return -1;
+    }
+}
+
+int32_t tet_get_marked_face_marker(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberoftrifaces && tetgen->output.trifacemarkerlist != NULL) {
+        return tetgen->output.trifacemarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_voronoi_npoint(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberofvpoints;
+}
+
+double tet_get_voronoi_point(struct ExtTetgen *tetgen, int32_t index, int32_t dim) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->output.numberofvpoints && (dim == 0 || dim == 1 || dim == 2)) {
+        return tetgen->output.vpointlist[index * 3 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t tet_get_voronoi_nedge(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberofvedges;
+}
+
+int32_t tet_get_voronoi_edge_point(struct ExtTetgen *tetgen, int32_t index, int32_t side) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberofvedges && (side == 0 || side == 1)) {
+        if (side == 0) {
+            return tetgen->output.vedgelist[index].v1;
+        } else {
+            return tetgen->output.vedgelist[index].v2;
+        }
+    } else {
+        return 0;
+    }
+}
+
+double tet_get_voronoi_edge_point_b_direction(struct ExtTetgen *tetgen, int32_t index, int32_t dim) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->output.numberofvedges && (dim == 0 || dim == 1 || dim == 2)) {
+        if (tetgen->output.vedgelist[index].v2 == -1) {
+            return tetgen->output.vedgelist[index].vnormal[dim];
+        } else {
+            return 0.0;
+        }
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t tet_get_voronoi_nface(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberofvfacets;
+}
+
+int32_t tet_get_voronoi_face_cell(struct ExtTetgen *tetgen, int32_t index, int32_t side) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberofvfacets && (side == 0 || side == 1)) {
+        if (side == 0) {
+            return tetgen->output.vfacetlist[index].c1;
+        } else {
+            return tetgen->output.vfacetlist[index].c2;
+        }
+    } else {
+        return 0;
+    }
+}
+
+// Returns the number of edges of a Voronoi face (stored in elist[0])
+int32_t tet_get_voronoi_face_nedge(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberofvfacets && tetgen->output.vfacetlist[index].elist != NULL) {
+        return tetgen->output.vfacetlist[index].elist[0];
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_voronoi_face_edge(struct ExtTetgen *tetgen, int32_t index, int32_t m) {
+    if (m < 0 || m >= tet_get_voronoi_face_nedge(tetgen, index)) {
+        return 0;
+    }
+    return tetgen->output.vfacetlist[index].elist[1 + m];
+}
+
+int32_t tet_get_voronoi_ncell(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberofvcells;
+}
+
+// Returns the number of faces of a Voronoi cell (stored in the 0th entry)
+int32_t tet_get_voronoi_cell_nface(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberofvcells && tetgen->output.vcelllist[index] != NULL) {
+        return tetgen->output.vcelllist[index][0];
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_voronoi_cell_face(struct ExtTetgen *tetgen, int32_t index, int32_t m) {
+    if (m < 0 || m >= tet_get_voronoi_cell_nface(tetgen, index)) {
+        return 0;
+    }
+    return tetgen->output.vcelllist[index][1 + m];
+}
+
+double tet_get_input_point(struct ExtTetgen *tetgen, int32_t index, int32_t dim) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->input.numberofpoints && (dim == 0 || dim == 1 || dim == 2)) {
+        return tetgen->input.pointlist[index * 3 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+double tet_get_input_hole(struct ExtTetgen *tetgen, int32_t index, int32_t dim) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->input.numberofholes && (dim == 0 || dim == 1 || dim == 2)) {
+        return tetgen->input.holelist[index * 3 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+double tet_get_input_region(struct ExtTetgen *tetgen, int32_t index, int32_t dim) {
+    if (tetgen == NULL) {
+        return 0.0;
+    }
+    if (index < tetgen->input.numberofregions && (dim == 0 || dim == 1 || dim == 2)) {
+        return tetgen->input.regionlist[index * 5 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t tet_get_input_region_attribute(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->input.numberofregions) {
+        return tetgen->input.regionlist[index * 5 + 3];
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_input_facet_npolygon(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->input.numberoffacets && tetgen->input.facetlist[index].polygonlist != NULL) {
+        return tetgen->input.facetlist[index].numberofpolygons;
+    } else {
+        return 0;
+    }
+}
+
+int32_t tet_get_input_facet_polygon_npoint(struct ExtTetgen *tetgen, int32_t index, int32_t polygon) {
+    if (polygon >= tet_get_input_facet_npolygon(tetgen, index)) {
+        return 0;
+    }
+    return tetgen->input.facetlist[index].polygonlist[polygon].numberofvertices;
+}
+
+int32_t tet_get_input_facet_polygon_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m) {
+    if (m >= tet_get_input_facet_polygon_npoint(tetgen, index, polygon)) {
+        return 0;
+    }
+    return tetgen->input.facetlist[index].polygonlist[polygon].vertexlist[m];
+}
+
+// Sets the callback called periodically by tetrahedralize to check whether the generation must stop
+// The run functions return TRITET_ERROR_CANCELLED if the callback returns nonzero (NULL disables it)
+void tet_set_cancel_callback(int32_t (*callback)(void *), void *data) {
+    tetgen_set_cancel_callback(callback, data);
+}
+
+// Sets the callback called at the end of each phase of tetrahedralize (NULL disables it)
+// The callback is thread-local; thus, it must be set by the thread calling the run functions
+void tet_set_progress_callback(void (*callback)(void *, int32_t), void *data) {
+    tetgen_set_progress_callback(callback, data);
+}
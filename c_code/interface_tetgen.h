@@ -8,23 +8,45 @@
 struct ExtTetgen {
     struct tetgenio input;
     struct tetgenio output;
+    struct tetgenio addin;
+    int32_t *original_index;  // index of the input point corresponding to each output point (or -1)
 };
 
 struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *facet_npoint, int32_t nregion, int32_t nhole);
 
 void drop_tetgen(struct ExtTetgen *tetgen);
 
+void tet_clear_outputs(struct ExtTetgen *tetgen);
+
 int32_t tet_set_point(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z);
 
+int32_t tet_set_points(struct ExtTetgen *tetgen, int32_t npoint, double const *coordinates);
+
+int32_t tet_set_additional_points(struct ExtTetgen *tetgen, int32_t npoint, double const *coordinates);
+
 int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m, int32_t p);
 
-int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, int32_t attribute, double max_volume);
+int32_t tet_set_facet_polygon(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t npoint, int32_t const *points);
+
+int32_t tet_set_facet_hole(struct ExtTetgen *tetgen, int32_t index, int32_t hole, double x, double y, double z);
+
+int32_t tet_set_facet_marker(struct ExtTetgen *tetgen, int32_t index, int32_t marker);
+
+int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, double attribute, double max_volume);
 
 int32_t tet_set_hole(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z);
 
 int32_t tet_run_delaunay(struct ExtTetgen *tetgen, int32_t verbose);
 
-int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double global_max_volume, double global_min_angle);
+int32_t tet_run_cdt(struct ExtTetgen *tetgen, int32_t verbose);
+
+int32_t tet_run_voronoi(struct ExtTetgen *tetgen, int32_t verbose);
+
+int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double global_max_volume, double max_radius_edge_ratio, double min_dihedral_angle, int32_t max_steiner_points, int32_t no_bisect);
+
+int32_t tet_run_refine(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double const *max_volumes, double global_max_volume, double max_radius_edge_ratio, double min_dihedral_angle, int32_t max_steiner_points, int32_t no_bisect);
+
+int32_t tet_run_given_mesh(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, int32_t ntet, int32_t const *corners, int32_t nface, int32_t const *faces, int32_t const *face_markers);
 
 int32_t tet_get_npoint(struct ExtTetgen *tetgen);
 
@@ -38,4 +60,56 @@ int32_t tet_get_tetrahedron_corner(struct ExtTetgen *tetgen, int32_t index, int3
 
 int32_t tet_get_tetrahedron_attribute(struct ExtTetgen *tetgen, int32_t index);
 
+double tet_get_tetrahedron_attribute_f64(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_nmarked_face(struct ExtTetgen *tetgen);
+
+int32_t tet_get_marked_face_corner(struct ExtTetgen *tetgen, int32_t index, int32_t corner);
+
+int32_t tet_get_point_original_index(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_marked_face_marker(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_voronoi_npoint(struct ExtTetgen *tetgen);
+
+double tet_get_voronoi_point(struct ExtTetgen *tetgen, int32_t index, int32_t dim);
+
+int32_t tet_get_voronoi_nedge(struct ExtTetgen *tetgen);
+
+int32_t tet_get_voronoi_edge_point(struct ExtTetgen *tetgen, int32_t index, int32_t side);
+
+double tet_get_voronoi_edge_point_b_direction(struct ExtTetgen *tetgen, int32_t index, int32_t dim);
+
+int32_t tet_get_voronoi_nface(struct ExtTetgen *tetgen);
+
+int32_t tet_get_voronoi_face_cell(struct ExtTetgen *tetgen, int32_t index, int32_t side);
+
+int32_t tet_get_voronoi_face_nedge(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_voronoi_face_edge(struct ExtTetgen *tetgen, int32_t index, int32_t m);
+
+int32_t tet_get_voronoi_ncell(struct ExtTetgen *tetgen);
+
+int32_t tet_get_voronoi_cell_nface(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_voronoi_cell_face(struct ExtTetgen *tetgen, int32_t index, int32_t m);
+
+double tet_get_input_point(struct ExtTetgen *tetgen, int32_t index, int32_t dim);
+
+double tet_get_input_hole(struct ExtTetgen *tetgen, int32_t index, int32_t dim);
+
+double tet_get_input_region(struct ExtTetgen *tetgen, int32_t index, int32_t dim);
+
+int32_t tet_get_input_region_attribute(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_input_facet_npolygon(struct ExtTetgen *tetgen, int32_t index);
+
+int32_t tet_get_input_facet_polygon_npoint(struct ExtTetgen *tetgen, int32_t index, int32_t polygon);
+
+int32_t tet_get_input_facet_polygon_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m);
+
+void tet_set_cancel_callback(int32_t (*callback)(void *), void *data);
+
+void tet_set_progress_callback(void (*callback)(void *, int32_t), void *data);
+
 #endif  // INTERFACE_TETGEN_H
\ No newline at end of file
@@ -124,6 +124,7 @@ struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregi
     zero_triangle_data(&triangle->input);
     zero_triangle_data(&triangle->output);
     zero_triangle_data(&triangle->voronoi);
+    triangle->original_index = NULL;
 
     // points
     triangle->input.pointlist = (double *)malloc(npoint * 2 * sizeof(double));
@@ -136,7 +137,8 @@ struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregi
     // segments
     if (nsegment > 0) {
         triangle->input.segmentlist = (int32_t *)malloc(nsegment * 2 * sizeof(int32_t));
-        if (triangle->input.segmentlist == NULL) {
+        triangle->input.segmentmarkerlist = (int32_t *)calloc(nsegment, sizeof(int32_t));
+        if (triangle->input.segmentlist == NULL || triangle->input.segmentmarkerlist == NULL) {
             free_triangle_data(&triangle->input);
             free(triangle);
             return NULL;
@@ -169,6 +171,57 @@ struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregi
     return triangle;
 }
 
+// Resizes an array allocated by malloc (the array is freed if nbytes is zero)
+static int32_t resize_array(void **array, size_t nbytes) {
+    if (nbytes == 0) {
+        free(*array);
+        *array = NULL;
+        return TRITET_SUCCESS;
+    }
+    void *resized = realloc(*array, nbytes);
+    if (resized == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    *array = resized;
+    return TRITET_SUCCESS;
+}
+
+int32_t reset_triangle(struct ExtTriangle *triangle, int32_t npoint, int32_t nsegment, int32_t nregion, int32_t nhole) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    clear_triangle_outputs(triangle);
+
+    // the arrays are reallocated (thus, the allocations are reused if the sizes do not increase)
+    struct triangulateio *input = &triangle->input;
+    if (resize_array((void **)&input->pointlist, npoint * 2 * sizeof(double)) != TRITET_SUCCESS ||
+        resize_array((void **)&input->segmentlist, nsegment * 2 * sizeof(int32_t)) != TRITET_SUCCESS ||
+        resize_array((void **)&input->segmentmarkerlist, nsegment * sizeof(int32_t)) != TRITET_SUCCESS ||
+        resize_array((void **)&input->regionlist, nregion * 4 * sizeof(double)) != TRITET_SUCCESS ||
+        resize_array((void **)&input->holelist, nhole * 2 * sizeof(double)) != TRITET_SUCCESS) {
+        return TRITET_ERROR_ALLOC;
+    }
+    if (nsegment > 0) {
+        memset(input->segmentmarkerlist, 0, nsegment * sizeof(int32_t));
+    }
+    input->numberofpoints = npoint;
+    input->numberofsegments = nsegment;
+    input->numberofregions = nregion;
+    input->numberofholes = nhole;
+
+    return TRITET_SUCCESS;
+}
+
+void clear_triangle_outputs(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return;
+    }
+    free_triangle_data(&triangle->output);
+    free_triangle_data(&triangle->voronoi);
+    free(triangle->original_index);
+    triangle->original_index = NULL;
+}
+
 void drop_triangle(struct ExtTriangle *triangle) {
     if (triangle == NULL) {
         return;
@@ -176,9 +229,37 @@ void drop_triangle(struct ExtTriangle *triangle) {
     free_triangle_data(&triangle->input);
     free_triangle_data(&triangle->output);
     free_triangle_data(&triangle->voronoi);
+    free(triangle->original_index);
     free(triangle);
 }
 
+// Sets the index of the input point corresponding to each output point
+//
+// Triangle keeps the points of its input as the first output points, with the same numbering.
+// Thus, the first `ninput` output points correspond to the input points; `input_original` gives
+// their indices in triangle->input if the input was not triangle->input (e.g., when refining the
+// previous output) or is NULL otherwise. The points generated by Triangle receive -1.
+static int32_t set_original_index(struct ExtTriangle *triangle, int32_t ninput, int32_t const *input_original) {
+    free(triangle->original_index);
+    triangle->original_index = NULL;
+    int32_t npoint = triangle->output.numberofpoints;
+    if (npoint == 0) {
+        return TRITET_SUCCESS;
+    }
+    triangle->original_index = (int32_t *)malloc(npoint * sizeof(int32_t));
+    if (triangle->original_index == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    for (int32_t i = 0; i < npoint; i++) {
+        if (i < ninput) {
+            triangle->original_index[i] = input_original == NULL ? i : input_original[i];
+        } else {
+            triangle->original_index[i] = -1;
+        }
+    }
+    return TRITET_SUCCESS;
+}
+
 int32_t set_point(struct ExtTriangle *triangle, int32_t index, double x, double y) {
     if (triangle == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -194,6 +275,20 @@ int32_t set_point(struct ExtTriangle *triangle, int32_t index, double x, double
     return TRITET_SUCCESS;
 }
 
+int32_t set_points(struct ExtTriangle *triangle, int32_t npoint, double const *coordinates) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+    if (npoint != triangle->input.numberofpoints) {
+        return TRITET_ERROR_INVALID_POINT_INDEX;
+    }
+    memcpy(triangle->input.pointlist, coordinates, npoint * 2 * sizeof(double));
+    return TRITET_SUCCESS;
+}
+
 int32_t set_segment(struct ExtTriangle *triangle, int32_t index, int32_t a, int32_t b) {
     if (triangle == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -212,7 +307,21 @@ int32_t set_segment(struct ExtTriangle *triangle, int32_t index, int32_t a, int3
     return TRITET_SUCCESS;
 }
 
-int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, int32_t attribute, double max_area) {
+int32_t set_segment_marker(struct ExtTriangle *triangle, int32_t index, int32_t marker) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->input.segmentmarkerlist == NULL) {
+        return TRITET_ERROR_NULL_SEGMENT_LIST;
+    }
+    if (index >= triangle->input.numberofsegments) {
+        return TRITET_ERROR_INVALID_SEGMENT_INDEX;
+    }
+    triangle->input.segmentmarkerlist[index] = marker;
+    return TRITET_SUCCESS;
+}
+
+int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, double attribute, double max_area) {
     // Shewchuk: If you are using the -A and -a switches simultaneously and wish to assign an attribute
     // to some region without imposing an area constraint, use a negative maximum area.
     if (triangle == NULL) {
@@ -246,6 +355,39 @@ int32_t set_hole(struct ExtTriangle *triangle, int32_t index, double x, double y
     return TRITET_SUCCESS;
 }
 
+// Assigns the marker of the segments to the points lying on them
+//
+// Triangle gives the default boundary marker (1) to the points on the boundary of the holes
+// if they are marked before the segments are inserted or when the holes are carved. Moreover,
+// Triangle gives a point shared by segments with different markers the marker of the segment
+// inserted first. Thus, the points lying on edges with markers other than 0 and 1 receive the
+// smallest of these markers, regardless of the order of the segments.
+static void fix_point_markers(struct triangulateio *out) {
+    if (out->pointmarkerlist == NULL || out->edgelist == NULL || out->edgemarkerlist == NULL) {
+        return;
+    }
+    // the points on marked edges are reset first (INT32_MAX is the identity of the minimum)
+    for (int32_t e = 0; e < out->numberofedges; e++) {
+        int32_t marker = out->edgemarkerlist[e];
+        if (marker != 0 && marker != 1) {
+            out->pointmarkerlist[out->edgelist[e * 2]] = INT32_MAX;
+            out->pointmarkerlist[out->edgelist[e * 2 + 1]] = INT32_MAX;
+        }
+    }
+    for (int32_t e = 0; e < out->numberofedges; e++) {
+        int32_t marker = out->edgemarkerlist[e];
+        if (marker == 0 || marker == 1) {
+            continue;
+        }
+        for (int32_t side = 0; side < 2; side++) {
+            int32_t p = out->edgelist[e * 2 + side];
+            if (marker < out->pointmarkerlist[p]) {
+                out->pointmarkerlist[p] = marker;
+            }
+        }
+    }
+}
+
 int32_t run_delaunay(struct ExtTriangle *triangle, int32_t verbose) {
     if (triangle == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -257,11 +399,16 @@ int32_t run_delaunay(struct ExtTriangle *triangle, int32_t verbose) {
     // Triangulate the points
     // Switches:
     // * `z` -- number everything from zero (z)
+    // * `e` -- output the edges (e)
+    // * `n` -- output the neighbors of each triangle (n)
     char command[10];
-    strcpy(command, "z");
+    strcpy(command, "zen");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
+    // Release the previous output because Triangle reuses the non-NULL arrays
+    free_triangle_data(&triangle->output);
+    free_triangle_data(&triangle->voronoi);
     triangulate(command, &triangle->input, &triangle->output, NULL);
 
     // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
@@ -269,6 +416,9 @@ int32_t run_delaunay(struct ExtTriangle *triangle, int32_t verbose) {
     // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
     triangle->output.regionlist = NULL;
     triangle->output.holelist = NULL;
+    if (set_original_index(triangle, triangle->input.numberofpoints, NULL) != TRITET_SUCCESS) {
+        return TRITET_ERROR_ALLOC;
+    }
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->output, 1, 1, 0, 0, 0, 0);
@@ -288,11 +438,16 @@ int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose) {
     // Switches:
     // * `z` -- number everything from zero (z)
     // * `v` -- Voronoi diagram
+    // * `e` -- output the edges (e)
+    // * `n` -- output the neighbors of each triangle (n)
     char command[10];
-    strcpy(command, "zv");
+    strcpy(command, "zven");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
+    // Release the previous output because Triangle reuses the non-NULL arrays
+    free_triangle_data(&triangle->output);
+    free_triangle_data(&triangle->voronoi);
     triangulate(command, &triangle->input, &triangle->output, &triangle->voronoi);
 
     // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
@@ -300,6 +455,9 @@ int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose) {
     // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
     triangle->output.regionlist = NULL;
     triangle->output.holelist = NULL;
+    if (set_original_index(triangle, triangle->input.numberofpoints, NULL) != TRITET_SUCCESS) {
+        return TRITET_ERROR_ALLOC;
+    }
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->voronoi, 0, 0, 0, 0, 1, 1);
@@ -307,7 +465,7 @@ int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose) {
     return TRITET_SUCCESS;
 }
 
-int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double global_max_area, double global_min_angle) {
+int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double global_max_area, double global_min_angle, int32_t max_steiner_points, int32_t no_bisect, int32_t conforming_delaunay) {
     if (triangle == NULL) {
         return TRITET_ERROR_NULL_DATA;
     }
@@ -323,8 +481,13 @@ int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t q
     // * `p` -- write a PSLG (p)
     // * `z` -- number everything from zero (z)
     // * `A` -- assign a regional attribute to each element (A)
+    // * `e` -- output the edges (e)
+    // * `n` -- output the neighbors of each triangle (n)
+    // * `S` -- maximum number of Steiner points (S)
+    // * `Y` -- prohibit Steiner points on the boundary (Y) or on all segments (YY)
+    // * `D` -- conforming Delaunay: all triangles are truly Delaunay (D)
     char command[128];
-    strcpy(command, "pzA");
+    strcpy(command, "pzAen");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
@@ -349,6 +512,25 @@ int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t q
     } else {
         strcat(command, "q");
     }
+    if (max_steiner_points >= 0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "S%d", max_steiner_points);
+        if (n >= 32) {
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    }
+    if (no_bisect == 1) {
+        strcat(command, "Y");
+    } else if (no_bisect > 1) {
+        strcat(command, "YY");
+    }
+    if (conforming_delaunay == TRITET_TRUE) {
+        strcat(command, "D");
+    }
+    // Release the previous output because Triangle reuses the non-NULL arrays
+    free_triangle_data(&triangle->output);
+    free_triangle_data(&triangle->voronoi);
     triangulate(command, &triangle->input, &triangle->output, NULL);
 
     // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
@@ -356,6 +538,254 @@ int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t q
     // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
     triangle->output.regionlist = NULL;
     triangle->output.holelist = NULL;
+    fix_point_markers(&triangle->output);
+    if (set_original_index(triangle, triangle->input.numberofpoints, NULL) != TRITET_SUCCESS) {
+        return TRITET_ERROR_ALLOC;
+    }
+
+    if (verbose == TRITET_TRUE) {
+        report(&triangle->output, 1, 1, 0, 0, 0, 0);
+    }
+    return TRITET_SUCCESS;
+}
+
+int32_t run_refine(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double const *max_areas, double global_max_area, double global_min_angle, int32_t max_steiner_points, int32_t no_bisect, int32_t conforming_delaunay) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->output.pointlist == NULL || triangle->output.trianglelist == NULL) {
+        return TRITET_ERROR_NULL_MESH;
+    }
+
+    // Feed the current output (corners only) back as input
+    // The middle nodes of quadratic triangles are removed and the points are renumbered
+    struct triangulateio *out = &triangle->output;
+    int32_t *map = (int32_t *)malloc(out->numberofpoints * sizeof(int32_t));
+    if (map == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    for (int32_t i = 0; i < out->numberofpoints; i++) {
+        map[i] = -1;
+    }
+    for (int32_t t = 0; t < out->numberoftriangles; t++) {
+        for (int32_t m = 0; m < 3; m++) {
+            map[out->trianglelist[t * out->numberofcorners + m]] = 0;
+        }
+    }
+    // Number the corners in increasing order to keep the original points first
+    int32_t npoint = 0;
+    for (int32_t i = 0; i < out->numberofpoints; i++) {
+        if (map[i] == 0) {
+            map[i] = npoint;
+            npoint++;
+        }
+    }
+    struct triangulateio input;
+    zero_triangle_data(&input);
+    input.pointlist = (double *)malloc(npoint * 2 * sizeof(double));
+    input.pointmarkerlist = (int32_t *)malloc(npoint * sizeof(int32_t));
+    input.trianglelist = (int32_t *)malloc(out->numberoftriangles * 3 * sizeof(int32_t));
+    input.trianglearealist = (double *)malloc(out->numberoftriangles * sizeof(double));
+    if (input.pointlist == NULL || input.pointmarkerlist == NULL || input.trianglelist == NULL || input.trianglearealist == NULL) {
+        free(map);
+        free_triangle_data(&input);
+        return TRITET_ERROR_ALLOC;
+    }
+    // The input point corresponding to each point fed back is kept to number the refined output
+    int32_t *input_original = (int32_t *)malloc(npoint * sizeof(int32_t));
+    if (input_original == NULL) {
+        free(map);
+        free_triangle_data(&input);
+        return TRITET_ERROR_ALLOC;
+    }
+    input.numberofpoints = npoint;
+    for (int32_t i = 0; i < out->numberofpoints; i++) {
+        if (map[i] >= 0) {
+            input.pointlist[map[i] * 2] = out->pointlist[i * 2];
+            input.pointlist[map[i] * 2 + 1] = out->pointlist[i * 2 + 1];
+            input.pointmarkerlist[map[i]] = out->pointmarkerlist == NULL ? 0 : out->pointmarkerlist[i];
+            input_original[map[i]] = triangle->original_index == NULL ? -1 : triangle->original_index[i];
+        }
+    }
+    input.numberoftriangles = out->numberoftriangles;
+    input.numberofcorners = 3;
+    for (int32_t t = 0; t < out->numberoftriangles; t++) {
+        for (int32_t m = 0; m < 3; m++) {
+            input.trianglelist[t * 3 + m] = map[out->trianglelist[t * out->numberofcorners + m]];
+        }
+        input.trianglearealist[t] = max_areas[t] > 0.0 ? max_areas[t] : -1.0;
+    }
+    if (out->numberoftriangleattributes > 0 && out->triangleattributelist != NULL) {
+        int32_t n = out->numberoftriangles * out->numberoftriangleattributes;
+        input.triangleattributelist = (double *)malloc(n * sizeof(double));
+        if (input.triangleattributelist == NULL) {
+            free(map);
+            free(input_original);
+            free_triangle_data(&input);
+            return TRITET_ERROR_ALLOC;
+        }
+        memcpy(input.triangleattributelist, out->triangleattributelist, n * sizeof(double));
+        input.numberoftriangleattributes = out->numberoftriangleattributes;
+    }
+    int32_t with_segments = out->segmentlist != NULL && out->numberofsegments > 0;
+    if (with_segments) {
+        input.segmentlist = (int32_t *)malloc(out->numberofsegments * 2 * sizeof(int32_t));
+        input.segmentmarkerlist = (int32_t *)malloc(out->numberofsegments * sizeof(int32_t));
+        if (input.segmentlist == NULL || input.segmentmarkerlist == NULL) {
+            free(map);
+            free(input_original);
+            free_triangle_data(&input);
+            return TRITET_ERROR_ALLOC;
+        }
+        for (int32_t s = 0; s < out->numberofsegments; s++) {
+            input.segmentlist[s * 2] = map[out->segmentlist[s * 2]];
+            input.segmentlist[s * 2 + 1] = map[out->segmentlist[s * 2 + 1]];
+            input.segmentmarkerlist[s] = out->segmentmarkerlist == NULL ? 0 : out->segmentmarkerlist[s];
+        }
+        input.numberofsegments = out->numberofsegments;
+    }
+    free(map);
+
+    // Refine mesh
+    // Switches:
+    // * `r` -- refine a previously generated mesh (r)
+    // * `p` -- keep the segments of the previous mesh (p)
+    // * `z` -- number everything from zero (z)
+    // * `e` -- output the edges (e)
+    // * `n` -- output the neighbors of each triangle (n)
+    // * `a` -- use the area constraint of each triangle (a)
+    // * `S` -- maximum number of Steiner points (S)
+    // * `Y` -- prohibit Steiner points on the boundary (Y) or on all segments (YY)
+    // * `D` -- conforming Delaunay: all triangles are truly Delaunay (D)
+    char command[128];
+    strcpy(command, with_segments ? "rpzena" : "rzena");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (quadratic == TRITET_TRUE) {
+        strcat(command, "o2");
+    }
+    if (global_max_area > 0.0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "a%.15f", global_max_area);
+        if (n >= 32) {
+            free(input_original);
+            free_triangle_data(&input);
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    }
+    if (global_min_angle > 0.0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "q%.15f", global_min_angle);
+        if (n >= 32) {
+            free(input_original);
+            free_triangle_data(&input);
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    } else {
+        strcat(command, "q");
+    }
+    if (max_steiner_points >= 0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "S%d", max_steiner_points);
+        if (n >= 32) {
+            free(input_original);
+            free_triangle_data(&input);
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    }
+    if (no_bisect == 1) {
+        strcat(command, "Y");
+    } else if (no_bisect > 1) {
+        strcat(command, "YY");
+    }
+    if (conforming_delaunay == TRITET_TRUE) {
+        strcat(command, "D");
+    }
+    struct triangulateio refined;
+    zero_triangle_data(&refined);
+    triangulate(command, &input, &refined, NULL);
+
+    // The refined mesh replaces the previous output
+    // (see the note in run_triangulate about regionlist and holelist)
+    refined.regionlist = NULL;
+    refined.holelist = NULL;
+    free_triangle_data(&input);
+    free_triangle_data(&triangle->output);
+    triangle->output = refined;
+    fix_point_markers(&triangle->output);
+    int32_t status = set_original_index(triangle, npoint, input_original);
+    free(input_original);
+    if (status != TRITET_SUCCESS) {
+        return status;
+    }
+
+    if (verbose == TRITET_TRUE) {
+        report(&triangle->output, 1, 1, 0, 0, 0, 0);
+    }
+    return TRITET_SUCCESS;
+}
+
+int32_t run_given_mesh(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, int32_t ntriangle, int32_t const *corners) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+    if (triangle->input.segmentlist == NULL) {
+        return TRITET_ERROR_NULL_SEGMENT_LIST;
+    }
+
+    // The triangles are given to Triangle as a previously generated mesh
+    // (the input points and segments are shared; the regions and holes are ignored)
+    struct triangulateio input = triangle->input;
+    input.trianglelist = (int32_t *)malloc(ntriangle * 3 * sizeof(int32_t));
+    if (input.trianglelist == NULL) {
+        return TRITET_ERROR_ALLOC;
+    }
+    memcpy(input.trianglelist, corners, ntriangle * 3 * sizeof(int32_t));
+    input.numberoftriangles = ntriangle;
+    input.numberofcorners = 3;
+    input.triangleattributelist = NULL;
+    input.trianglearealist = NULL;
+    input.numberoftriangleattributes = 0;
+    input.regionlist = NULL;
+    input.numberofregions = 0;
+    input.holelist = NULL;
+    input.numberofholes = 0;
+
+    // Reconstruct the mesh (without refinement)
+    // Switches:
+    // * `r` -- read a previously generated mesh (r)
+    // * `p` -- keep the segments (p)
+    // * `z` -- number everything from zero (z)
+    // * `e` -- output the edges (e)
+    // * `n` -- output the neighbors of each triangle (n)
+    char command[16];
+    strcpy(command, "rpzen");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (quadratic == TRITET_TRUE) {
+        strcat(command, "o2");
+    }
+    // Release the previous output because Triangle reuses the non-NULL arrays
+    free_triangle_data(&triangle->output);
+    free_triangle_data(&triangle->voronoi);
+    triangulate(command, &input, &triangle->output, NULL);
+    free(input.trianglelist);
+
+    // See the note in run_triangulate about regionlist and holelist
+    triangle->output.regionlist = NULL;
+    triangle->output.holelist = NULL;
+    fix_point_markers(&triangle->output);
+    if (set_original_index(triangle, triangle->input.numberofpoints, NULL) != TRITET_SUCCESS) {
+        return TRITET_ERROR_ALLOC;
+    }
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->output, 1, 1, 0, 0, 0, 0);
@@ -417,6 +847,79 @@ int32_t get_triangle_attribute(struct ExtTriangle *triangle, int32_t index) {
     }
 }
 
+double get_triangle_attribute_f64(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0.0;
+    }
+    if (index < triangle->output.numberoftriangles && triangle->output.numberoftriangleattributes > 0) {
+        return triangle->output.triangleattributelist[index * triangle->output.numberoftriangleattributes];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t get_triangle_neighbor(struct ExtTriangle *triangle, int32_t index, int32_t corner) {
+    if (triangle == NULL) {
+        return -1;
+    }
+    if (index < triangle->output.numberoftriangles && corner >= 0 && corner < 3 && triangle->output.neighborlist != NULL) {
+        return triangle->output.neighborlist[index * 3 + corner];
+    } else {
+        return -1;
+    }
+}
+
+int32_t get_nedge(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    return triangle->output.numberofedges;
+}
+
+int32_t get_edge_point(struct ExtTriangle *triangle, int32_t index, int32_t side) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofedges && (side == 0 || side == 1)) {
+        return triangle->output.edgelist[index * 2 + side];
+    } else {
+        return 0;
+    }
+}
+
+int32_t get_point_marker(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofpoints && triangle->output.pointmarkerlist != NULL) {
+        return triangle->output.pointmarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
+int32_t get_point_original_index(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return -1;
+    }
+    if (index < triangle->output.numberofpoints && triangle->original_index != NULL) {
+        return triangle->original_index[index];
+    } else {
+        return -1;
+    }
+}
+
+int32_t get_edge_marker(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofedges && triangle->output.edgemarkerlist != NULL) {
+        return triangle->output.edgemarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
 int32_t get_voronoi_npoint(struct ExtTriangle *triangle) {
     if (triangle == NULL) {
         return 0;
@@ -467,3 +970,58 @@ double get_voronoi_edge_point_b_direction(struct ExtTriangle *triangle, int32_t
         return 0.0;
     }
 }
+
+double get_input_point(struct ExtTriangle *triangle, int32_t index, int32_t dim) {
+    if (triangle == NULL) {
+        return 0.0;
+    }
+    if (index < triangle->input.numberofpoints && (dim == 0 || dim == 1)) {
+        return triangle->input.pointlist[index * 2 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t get_input_segment(struct ExtTriangle *triangle, int32_t index, int32_t side) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->input.numberofsegments && (side == 0 || side == 1)) {
+        return triangle->input.segmentlist[index * 2 + side];
+    } else {
+        return 0;
+    }
+}
+
+double get_input_hole(struct ExtTriangle *triangle, int32_t index, int32_t dim) {
+    if (triangle == NULL) {
+        return 0.0;
+    }
+    if (index < triangle->input.numberofholes && (dim == 0 || dim == 1)) {
+        return triangle->input.holelist[index * 2 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+double get_input_region(struct ExtTriangle *triangle, int32_t index, int32_t dim) {
+    if (triangle == NULL) {
+        return 0.0;
+    }
+    if (index < triangle->input.numberofregions && (dim == 0 || dim == 1)) {
+        return triangle->input.regionlist[index * 4 + dim];
+    } else {
+        return 0.0;
+    }
+}
+
+int32_t get_input_region_attribute(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->input.numberofregions) {
+        return triangle->input.regionlist[index * 4 + 2];
+    } else {
+        return 0;
+    }
+}
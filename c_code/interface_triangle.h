@@ -15,17 +15,26 @@ struct ExtTriangle {
     struct triangulateio input;
     struct triangulateio output;
     struct triangulateio voronoi;
+    int32_t *original_index;  // index of the input point corresponding to each output point (or -1)
 };
 
 struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregion, int32_t nhole);
 
 void drop_triangle(struct ExtTriangle *triangle);
 
+int32_t reset_triangle(struct ExtTriangle *triangle, int32_t npoint, int32_t nsegment, int32_t nregion, int32_t nhole);
+
+void clear_triangle_outputs(struct ExtTriangle *triangle);
+
 int32_t set_point(struct ExtTriangle *triangle, int32_t index, double x, double y);
 
+int32_t set_points(struct ExtTriangle *triangle, int32_t npoint, double const *coordinates);
+
 int32_t set_segment(struct ExtTriangle *triangle, int32_t index, int32_t a, int32_t b);
 
-int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, int32_t attribute, double max_area);
+int32_t set_segment_marker(struct ExtTriangle *triangle, int32_t index, int32_t marker);
+
+int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, double attribute, double max_area);
 
 int32_t set_hole(struct ExtTriangle *triangle, int32_t index, double x, double y);
 
@@ -33,7 +42,11 @@ int32_t run_delaunay(struct ExtTriangle *triangle, int32_t verbose);
 
 int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose);
 
-int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double global_max_area, double global_min_angle);
+int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double global_max_area, double global_min_angle, int32_t max_steiner_points, int32_t no_bisect, int32_t conforming_delaunay);
+
+int32_t run_refine(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double const *max_areas, double global_max_area, double global_min_angle, int32_t max_steiner_points, int32_t no_bisect, int32_t conforming_delaunay);
+
+int32_t run_given_mesh(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, int32_t ntriangle, int32_t const *corners);
 
 int32_t get_npoint(struct ExtTriangle *triangle);
 
@@ -47,6 +60,20 @@ int32_t get_triangle_corner(struct ExtTriangle *triangle, int32_t index, int32_t
 
 int32_t get_triangle_attribute(struct ExtTriangle *triangle, int32_t index);
 
+double get_triangle_attribute_f64(struct ExtTriangle *triangle, int32_t index);
+
+int32_t get_triangle_neighbor(struct ExtTriangle *triangle, int32_t index, int32_t corner);
+
+int32_t get_nedge(struct ExtTriangle *triangle);
+
+int32_t get_edge_point(struct ExtTriangle *triangle, int32_t index, int32_t side);
+
+int32_t get_point_marker(struct ExtTriangle *triangle, int32_t index);
+
+int32_t get_point_original_index(struct ExtTriangle *triangle, int32_t index);
+
+int32_t get_edge_marker(struct ExtTriangle *triangle, int32_t index);
+
 int32_t get_voronoi_npoint(struct ExtTriangle *triangle);
 
 int32_t get_voronoi_point(struct ExtTriangle *triangle, int32_t index, int32_t dim);
@@ -57,4 +84,14 @@ int32_t get_voronoi_edge_point(struct ExtTriangle *triangle, int32_t index, int3
 
 double get_voronoi_edge_point_b_direction(struct ExtTriangle *triangle, int32_t index, int32_t dim);
 
+double get_input_point(struct ExtTriangle *triangle, int32_t index, int32_t dim);
+
+int32_t get_input_segment(struct ExtTriangle *triangle, int32_t index, int32_t side);
+
+double get_input_hole(struct ExtTriangle *triangle, int32_t index, int32_t dim);
+
+double get_input_region(struct ExtTriangle *triangle, int32_t index, int32_t dim);
+
+int32_t get_input_region_attribute(struct ExtTriangle *triangle, int32_t index);
+
 #endif  // INTERFACE_TRIANGLE_H
@@ -31,6 +31,34 @@
 
 #include "tetgen.h"
 
+// tritet: redirects printf to tritet_printf (see console.h)
+#include "console.h"
+
+// tritet: the cancellation callback is called periodically by the functions
+// creating tetrahedra, subfaces, and points (see tetgen_set_cancel_callback in
+// tetgen.h). If it returns nonzero, the generation is stopped by throwing
+// TETGEN_CANCELLED. It is thread-local as the progress callback.
+
+static thread_local int (*tetgen_cancel_callback)(void *) = NULL;
+static thread_local void *tetgen_cancel_data = NULL;
+static thread_local unsigned int tetgen_cancel_counter = 0;
+
+void tetgen_set_cancel_callback(int (*callback)(void *), void *data)
+{
+  tetgen_cancel_callback = callback;
+  tetgen_cancel_data = data;
+  tetgen_cancel_counter = 0;
+}
+
+static void tetgen_check_cancelled()
+{
+  if (tetgen_cancel_callback != NULL && (++tetgen_cancel_counter % 1024) == 0) {
+    if (tetgen_cancel_callback(tetgen_cancel_data) != 0) {
+      terminatetetgen(TETGEN_CANCELLED);
+    }
+  }
+}
+
 //// io_cxx ///////////////////////////////////////////////////////////////////
 ////                                                                       ////
 ////                                                                       ////
@@ -5253,6 +5281,7 @@ tetgenmesh::point tetgenmesh::pointtraverse()
 
 void tetgenmesh::maketetrahedron(triface *newtet)
 {
+  tetgen_check_cancelled();
   newtet->tet = (tetrahedron *) tetrahedrons->alloc();
   // Initialize the four adjoining tetrahedra to be "outer space".
   newtet->tet[0] = (tetrahedron) dummytet;
@@ -5291,6 +5320,7 @@ void tetgenmesh::maketetrahedron(triface *newtet)
 
 void tetgenmesh::makeshellface(memorypool *pool, face *newface)
 {
+  tetgen_check_cancelled();
   newface->sh = (shellface *) pool->alloc();
   //Initialize the three adjoining subfaces to be the omnipresent subface.
   newface->sh[0] = (shellface) dummysh;
@@ -5339,6 +5369,7 @@ void tetgenmesh::makepoint(point* pnewpoint)
 {
   int ptmark, i;
 
+  tetgen_check_cancelled();
   *pnewpoint = (point) points->alloc();
   // Initialize three coordinates.
   (*pnewpoint)[0] = 0.0;
@@ -32448,12 +32479,12 @@ void tetgenmesh::outvoronoi(tetgenio* out)
       out->vpointlist[index++] = ccent[2];
     }
     // Remember the index of this element.
-    * (int *) (tetloop.tet + elemmarkerindex) = vpointcount;
+    setelemmarker(tetloop.tet, vpointcount);
     vpointcount++;
     tetloop.tet = tetrahedrontraverse();
   }
   // Set the outside element marker.
-  * (int *) (dummytet + elemmarkerindex) = -1;
+  setelemmarker(dummytet, -1);
 
   if (out == (tetgenio *) NULL) {
     fprintf(outfile, "# Generated by %s\n", b->commandline);
@@ -32502,7 +32533,7 @@ void tetgenmesh::outvoronoi(tetgenio* out)
     // Count the number of Voronoi edges. Look at the four faces of each
     //   tetrahedron. Count the face if the tetrahedron's pointer is
     //   smaller than its neighbor's or the neighbor is outside.
-    end1 = * (int *) (tetloop.tet + elemmarkerindex);
+    end1 = getelemmarker(tetloop.tet);
     for (i = 0; i < 4; i++) {
       decode(tetloop.tet[i], worktet);
       if ((worktet.tet == dummytet) || (tetloop.tet < worktet.tet)) {
@@ -32512,7 +32543,7 @@ void tetgenmesh::outvoronoi(tetgenio* out)
           vedge = &(out->vedgelist[index++]);
           vedge->v1 = end1 + shift;
         }
-        end2 = * (int *) (worktet.tet + elemmarkerindex);
+        end2 = getelemmarker(worktet.tet);
         // Note that end2 may be -1 (worktet.tet is outside).
         if (end2 == -1) {
           // Calculate the out normal of this hull face.
@@ -32650,7 +32681,7 @@ void tetgenmesh::outvoronoi(tetgenio* out)
         // If hitbdry > 0, then spintet is a hull face.
         if (hitbdry > 0) {
           // The edge list starts with a ray.
-          vpointcount = * (int *) (spintet.tet + elemmarkerindex);
+          vpointcount = getelemmarker(spintet.tet);
           vedgecount = tetfaceindexarray[vpointcount * 4 + spintet.loc];
           if (out == (tetgenio *) NULL) {
             fprintf(outfile, " %d", vedgecount + shift);
@@ -32665,7 +32696,7 @@ void tetgenmesh::outvoronoi(tetgenio* out)
         }
         // Output internal Voronoi edges.
         for (j = 0; j < tcount; j++) {
-          vpointcount = * (int *) (spintet.tet + elemmarkerindex);
+          vpointcount = getelemmarker(spintet.tet);
           vedgecount = tetfaceindexarray[vpointcount * 4 + spintet.loc];
           if (out == (tetgenio *) NULL) {
             fprintf(outfile, " %d", vedgecount + shift);
@@ -32763,7 +32794,7 @@ void tetgenmesh::outvoronoi(tetgenio* out)
         }
         assert(j < tetlist->len());
         // k is the right edge number.        
-        end1 = * (int *) (tetloop.tet + elemmarkerindex);
+        end1 = getelemmarker(tetloop.tet);
         vfacecount = tetedgeindexarray[end1 * 6 + k];
         if (out == (tetgenio *) NULL) {
           fprintf(outfile, " %d", vfacecount + shift);
@@ -34495,6 +34526,26 @@ void tetgenmesh::statistics()
 //                                                                           //
 ///////////////////////////////////////////////////////////////////////////////
 
+// tritet: the progress callback is called at the end of each phase of
+// tetrahedralize() (see tetgen_set_progress_callback in tetgen.h). It is
+// thread-local so that independent instances may run in parallel.
+
+static thread_local void (*tetgen_progress_callback)(void *, int) = NULL;
+static thread_local void *tetgen_progress_data = NULL;
+
+void tetgen_set_progress_callback(void (*callback)(void *, int), void *data)
+{
+  tetgen_progress_callback = callback;
+  tetgen_progress_data = data;
+}
+
+static void tetgen_notify_progress(int phase)
+{
+  if (tetgen_progress_callback != NULL) {
+    tetgen_progress_callback(tetgen_progress_data, phase);
+  }
+}
+
 void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   tetgenio *addin, tetgenio *bgmin)
 {
@@ -34518,6 +34569,7 @@ void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   m.transfernodes();
 
   tv[1] = clock();
+  tetgen_notify_progress(TETGEN_PHASE_INPUT);
 
   if (b->refine) {
     m.reconstructmesh();
@@ -34553,6 +34605,7 @@ void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   }
 
   tv[3] = clock();
+  tetgen_notify_progress(TETGEN_PHASE_DELAUNAY);
 
   if (!b->quiet) {
     if (b->metric) {
@@ -34610,6 +34663,9 @@ void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   }
 
   tv[6] = clock();
+  if (b->plc) {
+    tetgen_notify_progress(TETGEN_PHASE_BOUNDARY);
+  }
 
   if (!b->quiet) {
     if ((b->plc || b->refine) && !(b->diagnose == 1)) {
@@ -34689,6 +34745,9 @@ void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   }
 
   tv[12] = clock();
+  if (b->quality) {
+    tetgen_notify_progress(TETGEN_PHASE_REFINEMENT);
+  }
 
   if (!b->quiet) {
     if (b->quality && (b->optlevel > 0)) {
@@ -34815,6 +34874,7 @@ void tetrahedralize(tetgenbehavior *b, tetgenio *in, tetgenio *out,
   }
 
   tv[13] = clock();
+  tetgen_notify_progress(TETGEN_PHASE_OUTPUT);
 
   if (!b->quiet) {
     printf("\nOutput seconds:  %g\n",
@@ -34924,3 +34984,39 @@ void tetrahedralize(char *switches, tetgenio *in, tetgenio *out,
 ////                                                                       ////
 //// main_cxx /////////////////////////////////////////////////////////////////
 
+///////////////////////////////////////////////////////////////////////////////
+//                                                                           //
+// tritet: version (TETGEN_VERSION is defined by build.rs), symbols defined  //
+// when compiling this file, and size of REAL. These are defined here (not   //
+// in the interface) so that they report the configuration actually used to  //
+// compile TetGen.                                                           //
+//                                                                           //
+///////////////////////////////////////////////////////////////////////////////
+
+char const *tet_get_version()
+{
+  return TETGEN_VERSION;
+}
+
+char const *tet_get_compiled_flags()
+{
+  return ""
+#ifdef TETLIBRARY
+         " TETLIBRARY"
+#endif
+#ifdef SINGLE
+         " SINGLE"
+#endif
+#ifdef SELF_CHECK
+         " SELF_CHECK"
+#endif
+#ifdef NDEBUG
+         " NDEBUG"
+#endif
+         ;
+}
+
+int tet_get_real_size()
+{
+  return (int) sizeof(REAL);
+}
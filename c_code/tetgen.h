@@ -2361,6 +2361,25 @@ void tetrahedralize(char *switches, tetgenio *in, tetgenio *out,
                     tetgenio *addin = NULL, tetgenio *bgmin = NULL);
 #endif // #ifdef TETLIBRARY
 
+// tritet: phases reported by the progress callback (see tetrahedralize())
+#define TETGEN_PHASE_INPUT 0
+#define TETGEN_PHASE_DELAUNAY 1
+#define TETGEN_PHASE_BOUNDARY 2
+#define TETGEN_PHASE_REFINEMENT 3
+#define TETGEN_PHASE_OUTPUT 4
+
+void tetgen_set_progress_callback(void (*callback)(void *, int), void *data);
+
+// tritet: value thrown by terminatetetgen() when the cancellation callback returns nonzero
+#define TETGEN_CANCELLED 40
+
+void tetgen_set_cancel_callback(int (*callback)(void *), void *data);
+
+// tritet: version, symbols defined when compiling tetgen.cxx, and size of REAL
+extern "C" char const *tet_get_version();
+extern "C" char const *tet_get_compiled_flags();
+extern "C" int tet_get_real_size();
+
 ///////////////////////////////////////////////////////////////////////////////
 //                                                                           //
 // terminatetetgen()    Terminate TetGen with a given exit code.             //
@@ -357,6 +357,9 @@
 #include "triangle.h"
 #endif /* TRILIBRARY */
 
+/* tritet: redirects printf to tritet_printf (see console.h)                  */
+#include "console.h"
+
 /* A few forward declarations.                                               */
 
 #ifndef TRILIBRARY
@@ -640,17 +643,19 @@ struct memorypool {
 
 
 /* Global constants.                                                         */
+/*   (tritet: thread-local because exactinit() writes them at each call)     */
 
-REAL splitter;       /* Used to split REAL factors for exact multiplication. */
-REAL epsilon;                             /* Floating-point machine epsilon. */
-REAL resulterrbound;
-REAL ccwerrboundA, ccwerrboundB, ccwerrboundC;
-REAL iccerrboundA, iccerrboundB, iccerrboundC;
-REAL o3derrboundA, o3derrboundB, o3derrboundC;
+_Thread_local REAL splitter; /* Used to split REAL factors for exact mult. */
+_Thread_local REAL epsilon;               /* Floating-point machine epsilon. */
+_Thread_local REAL resulterrbound;
+_Thread_local REAL ccwerrboundA, ccwerrboundB, ccwerrboundC;
+_Thread_local REAL iccerrboundA, iccerrboundB, iccerrboundC;
+_Thread_local REAL o3derrboundA, o3derrboundB, o3derrboundC;
 
 /* Random number seed is not constant, but I've made it global anyway.       */
+/*   (tritet: thread-local so that meshes can be generated in parallel)     */
 
-unsigned long randomseed;                     /* Current random number seed. */
+_Thread_local unsigned long randomseed;       /* Current random number seed. */
 
 
 /* Mesh data structure.  Triangle operates on only one mesh, but the mesh    */
@@ -16004,3 +16009,142 @@ char **argv;
   return 0;
 #endif /* not TRILIBRARY */
 }
+
+/*****************************************************************************/
+/*                                                                           */
+/*  triangle_orient2d()   Exact orientation test for external callers.       */
+/*  triangle_indiametral()   Exact diametral circle test.                    */
+/*  triangle_distancecompare()   Exact comparison of squared distances.      */
+/*                                                                           */
+/*  These use the exact arithmetic of counterclockwise() and incircle(),     */
+/*  after calling exactinit() once per thread.                               */
+/*                                                                           */
+/*****************************************************************************/
+
+static _Thread_local int exactinitialized = 0;
+
+static void exactinitonce()
+{
+  if (!exactinitialized) {
+    exactinit();
+    exactinitialized = 1;
+  }
+}
+
+/* Returns the sum of the products x[i] * y[i] for 0 <= i < n <= 12; the    */
+/*   sign is exact and the magnitude is an approximation.                   */
+
+static REAL productsum(int n, REAL *x, REAL *y)
+{
+  INEXACT REAL c;
+  INEXACT REAL abig;
+  REAL ahi, alo, bhi, blo;
+  REAL err1, err2, err3;
+  REAL product[3];
+  REAL sum[2][32];
+  int sumlength, which, i;
+
+  sum[0][0] = 0.0;
+  sumlength = 1;
+  which = 0;
+  for (i = 0; i < n; i++) {
+    Two_Product(x[i], y[i], product[1], product[0]);
+    sumlength = fast_expansion_sum_zeroelim(sumlength, sum[which], 2, product,
+                                            sum[1 - which]);
+    which = 1 - which;
+  }
+  return sum[which][sumlength - 1];
+}
+
+REAL triangle_orient2d(REAL *pa, REAL *pb, REAL *pc)
+{
+  struct mesh m;
+  struct behavior b;
+
+  exactinitonce();
+  m.counterclockcount = 0;
+  b.noexact = 0;
+  return counterclockwise(&m, &b, pa, pb, pc);
+}
+
+/* Returns a positive value if pc lies strictly inside the circle whose     */
+/*   diameter is the segment from pa to pb, a negative value if it lies     */
+/*   outside, and zero if it lies on the circle; i.e., the sign of          */
+/*   -(pa - pc) . (pb - pc).                                                */
+
+REAL triangle_indiametral(REAL *pa, REAL *pb, REAL *pc)
+{
+  REAL x[8] = {-pa[0], -pa[1], pa[0], pa[1], pb[0], pb[1], -pc[0], -pc[1]};
+  REAL y[8] = {pb[0], pb[1], pc[0], pc[1], pc[0], pc[1], pc[0], pc[1]};
+
+  exactinitonce();
+  return productsum(8, x, y);
+}
+
+/* Returns a positive value if the distance from pa to pb is greater than   */
+/*   the distance from pc to pd, a negative value if it is smaller, and     */
+/*   zero if they are equal.                                                */
+
+REAL triangle_distancecompare(REAL *pa, REAL *pb, REAL *pc, REAL *pd)
+{
+  REAL x[12] = {pa[0], pa[1], pb[0], pb[1], -2.0 * pa[0], -2.0 * pa[1],
+                -pc[0], -pc[1], -pd[0], -pd[1], 2.0 * pc[0], 2.0 * pc[1]};
+  REAL y[12] = {pa[0], pa[1], pb[0], pb[1], pb[0], pb[1],
+                pc[0], pc[1], pd[0], pd[1], pd[0], pd[1]};
+
+  exactinitonce();
+  return productsum(12, x, y);
+}
+
+/*****************************************************************************/
+/*                                                                           */
+/*  get_triangle_version()   Returns the version (TRIANGLE_VERSION is        */
+/*                           defined by build.rs).                           */
+/*  get_triangle_compiled_flags()   Returns the symbols defined when         */
+/*                                  compiling this file.                     */
+/*  get_triangle_real_size()   Returns the size of REAL.                     */
+/*                                                                           */
+/*  These are defined here (not in the interface) so that they report the   */
+/*  configuration actually used to compile Triangle.                         */
+/*                                                                           */
+/*****************************************************************************/
+
+char const *get_triangle_version()
+{
+  return TRIANGLE_VERSION;
+}
+
+char const *get_triangle_compiled_flags()
+{
+  return ""
+#ifdef TRILIBRARY
+         " TRILIBRARY"
+#endif /* TRILIBRARY */
+#ifdef SINGLE
+         " SINGLE"
+#endif /* SINGLE */
+#ifdef REDUCED
+         " REDUCED"
+#endif /* REDUCED */
+#ifdef CDT_ONLY
+         " CDT_ONLY"
+#endif /* CDT_ONLY */
+#ifdef NO_TIMER
+         " NO_TIMER"
+#endif /* NO_TIMER */
+#ifdef SELF_CHECK
+         " SELF_CHECK"
+#endif /* SELF_CHECK */
+#ifdef LINUX
+         " LINUX"
+#endif /* LINUX */
+#ifdef CPU86
+         " CPU86"
+#endif /* CPU86 */
+         ;
+}
+
+int get_triangle_real_size()
+{
+  return (int) sizeof(REAL);
+}
@@ -283,7 +283,19 @@ struct triangulateio {
 void triangulate(char *, struct triangulateio *, struct triangulateio *,
                  struct triangulateio *);
 void trifree(VOID *memptr);
+REAL triangle_orient2d(REAL *pa, REAL *pb, REAL *pc);
+REAL triangle_indiametral(REAL *pa, REAL *pb, REAL *pc);
+REAL triangle_distancecompare(REAL *pa, REAL *pb, REAL *pc, REAL *pd);
+char const *get_triangle_version(void);
+char const *get_triangle_compiled_flags(void);
+int get_triangle_real_size(void);
 #else /* not ANSI_DECLARATORS */
 void triangulate();
 void trifree();
+REAL triangle_orient2d();
+REAL triangle_indiametral();
+REAL triangle_distancecompare();
+char const *get_triangle_version();
+char const *get_triangle_compiled_flags();
+int get_triangle_real_size();
 #endif /* not ANSI_DECLARATORS */
@@ -5,6 +5,7 @@
 #define ANSI_DECLARATORS
 #define VOID int
 #include "triangle.h"
+#include "console.h"
 #undef REAL
 #undef ANSI_DECLARATORS
 #undef VOID
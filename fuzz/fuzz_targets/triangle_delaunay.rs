@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use tritet_fuzz::PointSet2d;
+
+fuzz_target!(|input: PointSet2d| {
+    // the generation must either succeed or return an error (never abort the process)
+    let _ = input.generate();
+});
@@ -0,0 +1,151 @@
+//! Random inputs for the fuzz targets
+//!
+//! The coordinates are taken from a coarse grid (a signed byte divided by 8), thus the random
+//! inputs often have repeated points, collinear or coplanar points, and segments or facets
+//! touching each other; i.e., the degenerate cases that used to crash the process. The point
+//! indices are taken modulo the number of points and the quality constraints are bounded, so
+//! the generation always terminates in a reasonable time.
+
+use arbitrary::Arbitrary;
+use tritet::{StrError, Tetgen, Triangle};
+
+/// Holds the maximum number of points (to keep the runs fast)
+const MAX_NPOINT: usize = 64;
+
+/// Converts a grid coordinate to a real coordinate
+fn coord(value: i8) -> f64 {
+    value as f64 / 8.0
+}
+
+/// Returns None if n is zero
+fn option(n: usize) -> Option<usize> {
+    if n > 0 {
+        Some(n)
+    } else {
+        None
+    }
+}
+
+/// Holds the points of a 2D Delaunay triangulation
+#[derive(Arbitrary, Debug)]
+pub struct PointSet2d {
+    pub points: Vec<(i8, i8)>,
+}
+
+/// Holds the points of a 3D Delaunay triangulation
+#[derive(Arbitrary, Debug)]
+pub struct PointSet3d {
+    pub points: Vec<(i8, i8, i8)>,
+}
+
+/// Holds a Planar Straight Line Graph (PSLG) and the mesh constraints
+#[derive(Arbitrary, Debug)]
+pub struct Pslg {
+    pub points: Vec<(i8, i8)>,
+    pub segments: Vec<(u8, u8)>,
+    pub holes: Vec<(i8, i8)>,
+    pub regions: Vec<(i8, i8, u8, Option<u8>)>,
+    pub quadratic: bool,
+    pub max_area: Option<u8>,
+    pub min_angle: Option<u8>,
+}
+
+/// Holds a Piecewise Linear Complex (PLC) and the mesh constraints
+#[derive(Arbitrary, Debug)]
+pub struct Plc {
+    pub points: Vec<(i8, i8, i8)>,
+    pub facets: Vec<(Vec<u8>, i8)>,
+    pub holes: Vec<(i8, i8, i8)>,
+    pub regions: Vec<(i8, i8, i8, u8, Option<u8>)>,
+    pub o2: bool,
+    pub max_volume: Option<u8>,
+    pub min_angle: Option<u8>,
+}
+
+impl PointSet2d {
+    /// Generates the Delaunay triangulation
+    pub fn generate(&self) -> Result<(), StrError> {
+        let points = &self.points[..usize::min(self.points.len(), MAX_NPOINT)];
+        let mut triangle = Triangle::new(points.len(), None, None, None)?;
+        for (i, (x, y)) in points.iter().enumerate() {
+            triangle.set_point(i, coord(*x), coord(*y))?;
+        }
+        triangle.generate_delaunay(false)
+    }
+}
+
+impl PointSet3d {
+    /// Generates the Delaunay triangulation
+    pub fn generate(&self) -> Result<(), StrError> {
+        let points = &self.points[..usize::min(self.points.len(), MAX_NPOINT)];
+        let mut tetgen = Tetgen::new(points.len(), None, None, None)?;
+        for (i, (x, y, z)) in points.iter().enumerate() {
+            tetgen.set_point(i, coord(*x), coord(*y), coord(*z))?;
+        }
+        tetgen.generate_delaunay(false)
+    }
+}
+
+impl Pslg {
+    /// Generates the mesh
+    pub fn generate(&self) -> Result<(), StrError> {
+        let points = &self.points[..usize::min(self.points.len(), MAX_NPOINT)];
+        let npoint = usize::max(points.len(), 1);
+        let mut triangle = Triangle::new(
+            points.len(),
+            option(self.segments.len()),
+            option(self.regions.len()),
+            option(self.holes.len()),
+        )?;
+        for (i, (x, y)) in points.iter().enumerate() {
+            triangle.set_point(i, coord(*x), coord(*y))?;
+        }
+        for (i, (a, b)) in self.segments.iter().enumerate() {
+            triangle.set_segment(i, *a as usize % npoint, *b as usize % npoint)?;
+        }
+        for (i, (x, y)) in self.holes.iter().enumerate() {
+            triangle.set_hole(i, coord(*x), coord(*y))?;
+        }
+        for (i, (x, y, attribute, max_area)) in self.regions.iter().enumerate() {
+            let max_area = max_area.map(|a| (1 + a as usize) as f64 / 64.0);
+            triangle.set_region(i, coord(*x), coord(*y), *attribute as i32, max_area)?;
+        }
+        let max_area = self.max_area.map(|a| (1 + a as usize) as f64 / 64.0);
+        let min_angle = self.min_angle.map(|a| (a % 30) as f64);
+        triangle.generate_mesh(false, self.quadratic, max_area, min_angle)
+    }
+}
+
+impl Plc {
+    /// Generates the mesh
+    pub fn generate(&self) -> Result<(), StrError> {
+        let points = &self.points[..usize::min(self.points.len(), MAX_NPOINT)];
+        let npoint = usize::max(points.len(), 1);
+        let facet_npoint = self.facets.iter().map(|(polygon, _)| polygon.len()).collect();
+        let mut tetgen = Tetgen::new(
+            points.len(),
+            Some(facet_npoint),
+            option(self.regions.len()),
+            option(self.holes.len()),
+        )?;
+        for (i, (x, y, z)) in points.iter().enumerate() {
+            tetgen.set_point(i, coord(*x), coord(*y), coord(*z))?;
+        }
+        for (i, (polygon, marker)) in self.facets.iter().enumerate() {
+            for (m, p) in polygon.iter().enumerate() {
+                tetgen.set_facet_point(i, m, *p as usize % npoint)?;
+            }
+            tetgen.set_facet_marker(i, *marker as i32)?;
+        }
+        for (i, (x, y, z)) in self.holes.iter().enumerate() {
+            tetgen.set_hole(i, coord(*x), coord(*y), coord(*z))?;
+        }
+        for (i, (x, y, z, attribute, max_volume)) in self.regions.iter().enumerate() {
+            let max_volume = max_volume.map(|v| (1 + v as usize) as f64 / 64.0);
+            tetgen.set_region(i, coord(*x), coord(*y), coord(*z), *attribute as i32, max_volume)?;
+        }
+        let max_volume = self.max_volume.map(|v| (1 + v as usize) as f64 / 64.0);
+        let min_angle = self.min_angle.map(|a| (a % 20) as f64);
+        tetgen.generate_mesh(false, self.o2, max_volume, min_angle)
+    }
+}
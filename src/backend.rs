@@ -0,0 +1,104 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+
+extern "C" {
+    fn get_triangle_version() -> *const c_char;
+    fn get_triangle_compiled_flags() -> *const c_char;
+    fn get_triangle_real_size() -> i32;
+    fn tet_get_version() -> *const c_char;
+    fn tet_get_compiled_flags() -> *const c_char;
+    fn tet_get_real_size() -> i32;
+}
+
+/// Holds information about the embedded Triangle and Tetgen codes
+///
+/// # Examples
+///
+/// ```
+/// use tritet::backend_info;
+///
+/// let info = backend_info();
+/// assert_eq!(info.triangle_version, "1.6");
+/// assert_eq!(info.precision, "double");
+/// println!("{}", info);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackendInfo {
+    /// Holds the version of Triangle
+    pub triangle_version: String,
+
+    /// Holds the version of Tetgen
+    pub tetgen_version: String,
+
+    /// Holds the symbols defined when compiling the codes, prefixed by "triangle:" or "tetgen:"
+    pub compiled_flags: Vec<String>,
+
+    /// Holds the floating-point precision of the codes ("double", "single", or "mixed")
+    pub precision: String,
+}
+
+/// Returns information about the embedded Triangle and Tetgen codes
+pub fn backend_info() -> BackendInfo {
+    unsafe {
+        let triangle_flags = c_string(get_triangle_compiled_flags());
+        let tetgen_flags = c_string(tet_get_compiled_flags());
+        let compiled_flags = triangle_flags
+            .split_whitespace()
+            .map(|flag| format!("triangle:{}", flag))
+            .chain(tetgen_flags.split_whitespace().map(|flag| format!("tetgen:{}", flag)))
+            .collect();
+        let precision = match (get_triangle_real_size(), tet_get_real_size()) {
+            (8, 8) => "double",
+            (4, 4) => "single",
+            _ => "mixed",
+        };
+        BackendInfo {
+            triangle_version: c_string(get_triangle_version()),
+            tetgen_version: c_string(tet_get_version()),
+            compiled_flags,
+            precision: precision.to_string(),
+        }
+    }
+}
+
+/// Converts a static string from the c-code
+unsafe fn c_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(ptr).to_string_lossy().into_owned()
+}
+
+impl fmt::Display for BackendInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Triangle {}, Tetgen {}, {} precision, flags: {}",
+            self.triangle_version,
+            self.tetgen_version,
+            self.precision,
+            self.compiled_flags.join(" ")
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::backend_info;
+
+    #[test]
+    fn backend_info_works() {
+        let info = backend_info();
+        assert_eq!(info.triangle_version, "1.6");
+        assert_eq!(info.tetgen_version, "1.4.3");
+        assert_eq!(info.precision, "double");
+        assert!(info.compiled_flags.contains(&"triangle:TRILIBRARY".to_string()));
+        assert!(info.compiled_flags.contains(&"tetgen:TETLIBRARY".to_string()));
+        assert!(
+            format!("{}", info).starts_with("Triangle 1.6, Tetgen 1.4.3, double precision, flags: triangle:TRILIBRARY")
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use tritet::{write_mesh_vtu, StrError, Tetgen, TetgenOptions, Triangle, TriangleOptions};
+
+const USAGE: &str = "\
+Usage: tritet tri|tet INPUT OUTPUT [SWITCHES]
+
+COMMANDS:
+    tri          generates a 2D mesh with Triangle
+    tet          generates a 3D mesh with TetGen
+
+INPUT (tri):
+    file.poly    Triangle's PSLG (points, segments, holes, and regions)
+    file.node    Triangle's points; generates a mesh of the convex hull
+    file.csv     points with x,y per line; generates a Delaunay triangulation
+
+INPUT (tet):
+    file.poly    TetGen's PLC (points, facets, holes, and regions)
+    file.smesh
+    file.stl     closed surface with triangles (ASCII or binary STL)
+    file.off     closed surface with polygons (OFF or PLY)
+    file.ply
+    file.csv     points with x,y,z per line; generates a Delaunay tetrahedralization
+
+OUTPUT:
+    file.vtu     Paraview's VTU file (cells and attributes)
+    file.msh     Gmsh's MSH 4.1 file (attributes and markers as physical groups)
+    file.obj     Wavefront OBJ file with the boundary (edges with markers in 2D)
+    prefix       Triangle's .node, .ele, .poly, and .edge files (tri) or
+                 TetGen's .node, .ele, and .face files (tet) (no extension)
+
+SWITCHES:
+    --max-area VALUE        maximum area of the triangles (tri)
+    --min-angle VALUE       minimum angle of the triangles in degrees (tri)
+    --conforming            generates a conforming Delaunay triangulation (tri)
+    --max-volume VALUE      maximum volume of the tetrahedra (tet)
+    --max-ratio VALUE       maximum radius-edge ratio of the tetrahedra (tet)
+    --min-dihedral VALUE    minimum dihedral angle of the tetrahedra in degrees (tet)
+    --o2                    generates the middle nodes (quadratic elements)
+    --verbose               prints Triangle's or TetGen's messages
+
+The crate must be compiled with the cli feature; e.g.:
+    cargo run --features cli --bin tritet -- tri data/input/square_with_hole.poly /tmp/square.vtu --max-area 0.1
+";
+
+/// Holds the mesh options of each command
+enum Command {
+    Tri(TriangleOptions),
+    Tet(TetgenOptions),
+}
+
+/// Holds the command line arguments
+struct Arguments {
+    input: String,
+    output: String,
+    command: Command,
+}
+
+/// Parses the command line arguments
+fn parse_arguments() -> Result<Arguments, StrError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 3 {
+        return Err("COMMAND, INPUT, and OUTPUT must be given");
+    }
+    let mut command = match args[0].as_str() {
+        "tri" => Command::Tri(TriangleOptions::new()),
+        "tet" => Command::Tet(TetgenOptions::new()),
+        _ => return Err("the COMMAND must be tri or tet"),
+    };
+    let mut words = args[3..].iter();
+    while let Some(word) = words.next() {
+        let mut value = || -> Result<f64, StrError> {
+            let text = words.next().ok_or("the switch requires a value")?;
+            text.parse().map_err(|_| "the value of the switch is invalid")
+        };
+        match (&mut command, word.as_str()) {
+            (Command::Tri(options), "--max-area") => {
+                options.set_max_area(value()?);
+            }
+            (Command::Tri(options), "--min-angle") => {
+                options.set_min_angle(value()?);
+            }
+            (Command::Tri(options), "--conforming") => {
+                options.set_conforming_delaunay(true);
+            }
+            (Command::Tri(options), "--o2") => {
+                options.set_quadratic(true);
+            }
+            (Command::Tri(options), "--verbose") => {
+                options.set_verbose(true);
+            }
+            (Command::Tet(options), "--max-volume") => {
+                options.set_max_volume(value()?);
+            }
+            (Command::Tet(options), "--max-ratio") => {
+                options.set_max_radius_edge_ratio(value()?);
+            }
+            (Command::Tet(options), "--min-dihedral") => {
+                options.set_min_dihedral_angle(value()?);
+            }
+            (Command::Tet(options), "--o2") => {
+                options.set_quadratic(true);
+            }
+            (Command::Tet(options), "--verbose") => {
+                options.set_verbose(true);
+            }
+            _ => return Err("the switch is unknown or not available for this COMMAND"),
+        }
+    }
+    Ok(Arguments {
+        input: args[1].clone(),
+        output: args[2].clone(),
+        command,
+    })
+}
+
+/// Reads the points of a CSV file (2 or 3 columns; lines starting with # are ignored)
+fn read_csv(path: &str) -> Result<Vec<Vec<f64>>, StrError> {
+    let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+    let mut rows = Vec::new();
+    for line in contents.lines().map(|l| l.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let row = line
+            .split(',')
+            .map(|w| w.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "the CSV file has an invalid number")?;
+        if row.len() != 2 && row.len() != 3 {
+            return Err("the CSV file must have 2 or 3 columns");
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        return Err("the CSV file has no points");
+    }
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err("the CSV file must have the same number of columns in all lines");
+    }
+    Ok(rows)
+}
+
+/// Writes a 2D mesh
+fn write_triangle(triangle: &Triangle, output: &str) -> Result<(), StrError> {
+    match Path::new(output).extension().and_then(|e| e.to_str()) {
+        Some("vtu") => write_mesh_vtu(&triangle.to_mesh(), output),
+        Some("msh") => triangle.write_msh(output),
+        Some("obj") => triangle.write_boundary_obj(output),
+        Some(_) => Err("the OUTPUT extension is not supported"),
+        None => triangle.write_node_ele(output),
+    }
+}
+
+/// Writes a 3D mesh
+fn write_tetgen(tetgen: &Tetgen, output: &str) -> Result<(), StrError> {
+    match Path::new(output).extension().and_then(|e| e.to_str()) {
+        Some("vtu") => write_mesh_vtu(&tetgen.to_mesh(), output),
+        Some("msh") => tetgen.write_msh(output),
+        Some("obj") => tetgen.write_boundary_obj(output),
+        Some(_) => Err("the OUTPUT extension is not supported"),
+        None => tetgen.write_tetgen_files(output),
+    }
+}
+
+/// Generates a 2D mesh
+fn run_tri(input: &str, output: &str, options: &TriangleOptions) -> Result<(), StrError> {
+    let triangle = match Path::new(input).extension().and_then(|e| e.to_str()) {
+        Some("poly") => Triangle::from_poly_file(input)?,
+        Some("node") => Triangle::from_node_file(input)?,
+        Some("csv") => {
+            let rows = read_csv(input)?;
+            if rows[0].len() != 2 {
+                return Err("the CSV file must have 2 columns");
+            }
+            let mut triangle = Triangle::new(rows.len(), None, None, None)?;
+            for (p, row) in rows.iter().enumerate() {
+                triangle.set_point(p, row[0], row[1])?;
+            }
+            triangle.generate_delaunay(options.verbose)?;
+            println!(
+                "generated {} triangles with {} points",
+                triangle.ntriangle(),
+                triangle.npoint()
+            );
+            return write_triangle(&triangle, output);
+        }
+        _ => return Err("the INPUT extension is not supported"),
+    };
+    triangle.generate_mesh_with(options)?;
+    println!(
+        "generated {} triangles with {} points (minimum angle = {:.2})",
+        triangle.ntriangle(),
+        triangle.npoint(),
+        triangle.min_angle().unwrap_or(0.0)
+    );
+    write_triangle(&triangle, output)
+}
+
+/// Generates a 3D mesh
+fn run_tet(input: &str, output: &str, options: &TetgenOptions) -> Result<(), StrError> {
+    let tetgen = match Path::new(input).extension().and_then(|e| e.to_str()) {
+        Some("poly") => Tetgen::from_poly_file(input)?,
+        Some("smesh") => Tetgen::from_smesh_file(input)?,
+        Some("stl") => Tetgen::from_stl(input)?,
+        Some("off") => Tetgen::from_off(input)?,
+        Some("ply") => Tetgen::from_ply(input)?,
+        Some("csv") => {
+            let rows = read_csv(input)?;
+            if rows[0].len() != 3 {
+                return Err("the CSV file must have 3 columns");
+            }
+            let mut tetgen = Tetgen::new(rows.len(), None, None, None)?;
+            for (p, row) in rows.iter().enumerate() {
+                tetgen.set_point(p, row[0], row[1], row[2])?;
+            }
+            tetgen.generate_delaunay(options.verbose)?;
+            println!("generated {} tetrahedra with {} points", tetgen.ntet(), tetgen.npoint());
+            return write_tetgen(&tetgen, output);
+        }
+        _ => return Err("the INPUT extension is not supported"),
+    };
+    tetgen.generate_mesh_with(options)?;
+    println!("generated {} tetrahedra with {} points", tetgen.ntet(), tetgen.npoint());
+    write_tetgen(&tetgen, output)
+}
+
+fn run(args: &Arguments) -> Result<(), StrError> {
+    match &args.command {
+        Command::Tri(options) => run_tri(&args.input, &args.output, options),
+        Command::Tet(options) => run_tet(&args.input, &args.output, options),
+    }
+}
+
+fn main() {
+    let result = parse_arguments().and_then(|args| run(&args));
+    if let Err(message) = result {
+        eprintln!("ERROR: {}\n\n{}", message, USAGE);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,138 @@
+/// Implements a uniform grid over axis-aligned bounding boxes to speed up region queries
+///
+/// Each item (given by its bounding box) is registered in all grid cells that it overlaps.
+/// In 2D, the z-coordinates of the boxes should be set to zero.
+pub(crate) struct BoxGrid {
+    min: [f64; 3],                    // minimum corner of the grid
+    max: [f64; 3],                    // maximum corner of the grid
+    size: [f64; 3],                   // size of the grid cells
+    ndiv: [usize; 3],                 // number of divisions along each direction
+    boxes: Vec<([f64; 3], [f64; 3])>, // bounding box of each item
+    cells: Vec<Vec<usize>>,           // items registered in each grid cell
+}
+
+impl BoxGrid {
+    /// Allocates a new instance given the (min, max) bounding box of each item
+    pub(crate) fn new(boxes: &[([f64; 3], [f64; 3])]) -> Self {
+        let mut min = [f64::MAX; 3];
+        let mut max = [f64::MIN; 3];
+        for (lo, hi) in boxes {
+            for i in 0..3 {
+                min[i] = f64::min(min[i], lo[i]);
+                max[i] = f64::max(max[i], hi[i]);
+            }
+        }
+        if boxes.is_empty() {
+            min = [0.0; 3];
+            max = [0.0; 3];
+        }
+        // about one item per grid cell along each (non-flat) direction
+        let nflat = (0..3).filter(|i| max[*i] - min[*i] <= 0.0).count();
+        let nactive = f64::max(1.0, (3 - nflat) as f64);
+        let n = f64::max(1.0, f64::powf(boxes.len() as f64, 1.0 / nactive).round());
+        let mut ndiv = [1; 3];
+        let mut size = [1.0; 3];
+        for i in 0..3 {
+            let width = max[i] - min[i];
+            if width > 0.0 {
+                ndiv[i] = n as usize;
+                size[i] = width / (ndiv[i] as f64);
+            }
+        }
+        let mut grid = BoxGrid {
+            min,
+            max,
+            size,
+            ndiv,
+            boxes: boxes.to_vec(),
+            cells: vec![Vec::new(); ndiv[0] * ndiv[1] * ndiv[2]],
+        };
+        for (item, (lo, hi)) in boxes.iter().enumerate() {
+            let (a, b) = grid.range(lo, hi);
+            for k in a[2]..=b[2] {
+                for j in a[1]..=b[1] {
+                    for i in a[0]..=b[0] {
+                        let c = i + j * ndiv[0] + k * ndiv[0] * ndiv[1];
+                        grid.cells[c].push(item);
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    /// Returns the sorted items whose bounding boxes overlap the (min, max) box
+    pub(crate) fn candidates(&self, min: &[f64; 3], max: &[f64; 3]) -> Vec<usize> {
+        let mut items = Vec::new();
+        if self.boxes.is_empty() || (0..3).any(|i| min[i] > max[i]) {
+            return items;
+        }
+        let (a, b) = self.range(min, max);
+        for k in a[2]..=b[2] {
+            for j in a[1]..=b[1] {
+                for i in a[0]..=b[0] {
+                    let c = i + j * self.ndiv[0] + k * self.ndiv[0] * self.ndiv[1];
+                    for item in &self.cells[c] {
+                        let (lo, hi) = &self.boxes[*item];
+                        if (0..3).all(|d| lo[d] <= max[d] && hi[d] >= min[d]) {
+                            items.push(*item);
+                        }
+                    }
+                }
+            }
+        }
+        items.sort();
+        items.dedup();
+        items
+    }
+
+    /// Returns the (min, max) corners of the grid
+    pub(crate) fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        (self.min, self.max)
+    }
+
+    /// Returns the size of the grid cells (1.0 along flat directions)
+    pub(crate) fn cell_size(&self) -> [f64; 3] {
+        self.size
+    }
+
+    /// Returns the range of grid cell indices overlapped by the (min, max) box (clamped to the grid)
+    fn range(&self, min: &[f64; 3], max: &[f64; 3]) -> ([usize; 3], [usize; 3]) {
+        let mut a = [0; 3];
+        let mut b = [0; 3];
+        for i in 0..3 {
+            let last = (self.ndiv[i] - 1) as f64;
+            a[i] = f64::floor((min[i] - self.min[i]) / self.size[i]).clamp(0.0, last) as usize;
+            b[i] = f64::floor((max[i] - self.min[i]) / self.size[i]).clamp(0.0, last) as usize;
+        }
+        (a, b)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::BoxGrid;
+
+    #[test]
+    fn box_grid_works() {
+        let boxes = vec![
+            ([0.0, 0.0, 0.0], [1.0, 1.0, 0.0]),
+            ([1.0, 0.0, 0.0], [2.0, 1.0, 0.0]),
+            ([2.0, 2.0, 0.0], [3.0, 3.0, 0.0]),
+            ([0.5, 0.5, 0.0], [2.5, 2.5, 0.0]),
+        ];
+        let grid = BoxGrid::new(&boxes);
+        assert_eq!(grid.ndiv, [2, 2, 1]);
+        assert_eq!(grid.bounds(), ([0.0; 3], [3.0, 3.0, 0.0]));
+        assert_eq!(grid.cell_size(), [1.5, 1.5, 1.0]);
+        assert_eq!(grid.candidates(&[0.1, 0.1, -1.0], &[0.2, 0.2, 1.0]), &[0]);
+        assert_eq!(grid.candidates(&[0.9, 0.9, 0.0], &[1.1, 0.95, 0.0]), &[0, 1, 3]);
+        assert_eq!(grid.candidates(&[2.8, 2.8, 0.0], &[5.0, 5.0, 0.0]), &[2]);
+        assert_eq!(grid.candidates(&[5.0, 5.0, 0.0], &[6.0, 6.0, 0.0]), &[] as &[usize]);
+        assert_eq!(grid.candidates(&[1.0, 1.0, 0.0], &[0.0, 0.0, 0.0]), &[] as &[usize]);
+        let empty = BoxGrid::new(&[]);
+        assert_eq!(empty.candidates(&[0.0; 3], &[1.0; 3]), &[] as &[usize]);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::StrError;
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+extern "C" {
+    fn tet_set_cancel_callback(callback: Option<extern "C" fn(*mut c_void) -> i32>, data: *mut c_void);
+}
+
+/// Requests the cancellation of a generation running in another thread (see `Tetgen::set_cancellation_token`)
+///
+/// The token may be cloned; all clones share the same state. Thus, one clone is given to the
+/// generator and the other is used to cancel the generation.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Allocates a new (not cancelled) token
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Requests the cancellation (the generation stops at the next check)
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Clears the cancellation request (e.g., to reuse the token)
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns true if the cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Holds the state of the cancellation during a call to the c-code
+struct CancelState<'a> {
+    token: Option<&'a CancellationToken>,
+    deadline: Option<Instant>,
+    reason: Option<StrError>,
+}
+
+/// Returns 1 if the c-code must stop the generation
+extern "C" fn check_cancelled(data: *mut c_void) -> i32 {
+    let state = unsafe { &mut *(data as *mut CancelState) };
+    if state.token.map(|t| t.is_cancelled()) == Some(true) {
+        state.reason = Some("mesh generation cancelled");
+        return 1;
+    }
+    if let Some(deadline) = state.deadline {
+        if Instant::now() >= deadline {
+            state.reason = Some("mesh generation timed out");
+            return 1;
+        }
+    }
+    0
+}
+
+/// Clears the callback when dropped (also if a panic of a handler is resumed by `run`)
+struct CallbackGuard;
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        unsafe {
+            tet_set_cancel_callback(None, ptr::null_mut());
+        }
+    }
+}
+
+/// Runs a call to the c-code that is stopped if the token is cancelled or the deadline is reached
+///
+/// Returns the status given by the c-code or an error if the generation has been stopped.
+pub(crate) fn run_with_cancellation<F>(
+    token: Option<&CancellationToken>,
+    deadline: Option<Instant>,
+    run: F,
+) -> Result<i32, StrError>
+where
+    F: FnOnce() -> i32,
+{
+    if token.is_none() && deadline.is_none() {
+        return Ok(run());
+    }
+    let mut state = CancelState {
+        token,
+        deadline,
+        reason: None,
+    };
+    unsafe {
+        tet_set_cancel_callback(Some(check_cancelled), &mut state as *mut CancelState as *mut c_void);
+    }
+    let guard = CallbackGuard;
+    let status = run();
+    drop(guard);
+    match state.reason {
+        Some(reason) => Err(reason),
+        None => Ok(status),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+    use crate::fixtures::cube;
+    use crate::{StrError, TetgenOptions};
+    use std::time::Duration;
+
+    #[test]
+    fn cancellation_token_works() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+        token.reset();
+        assert!(!clone.is_cancelled());
+    }
+
+    #[test]
+    fn cancelled_generation_returns_error() -> Result<(), StrError> {
+        let mut tetgen = cube()?;
+        let mut options = TetgenOptions::new();
+        options.set_max_volume(1e-4);
+        let token = CancellationToken::new();
+        tetgen.set_cancellation_token(Some(token.clone()));
+        token.cancel();
+        assert_eq!(
+            tetgen.generate_mesh_with(&options).err(),
+            Some("mesh generation cancelled")
+        );
+        assert_eq!(tetgen.ntet(), 0);
+
+        // the instance may be used again
+        token.reset();
+        tetgen.generate_mesh_with(&options)?;
+        assert!(tetgen.ntet() > 1000);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_with_timeout_works() -> Result<(), StrError> {
+        let tetgen = cube()?;
+        let mut options = TetgenOptions::new();
+        options.set_max_volume(1e-5);
+        assert_eq!(
+            tetgen.generate_mesh_with_timeout(&options, Duration::ZERO).err(),
+            Some("mesh generation timed out")
+        );
+        assert_eq!(tetgen.ntet(), 0);
+        options.set_max_volume(0.01);
+        tetgen.generate_mesh_with_timeout(&options, Duration::from_secs(60))?;
+        assert!(tetgen.ntet() > 100);
+        Ok(())
+    }
+}
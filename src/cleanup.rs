@@ -0,0 +1,184 @@
+use crate::mesh::simplex_measure;
+use crate::triangle_graphs::find_root;
+use crate::{Mesh, Renumbering};
+
+/// Holds the report of the cleanup of degenerate cells (see `Mesh::remove_degenerate_cells`)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CleanupReport {
+    /// Holds the original IDs of the removed cells (sorted)
+    pub removed_cells: Vec<usize>,
+
+    /// Holds the original IDs of the merged points as (merged, kept) pairs, in the order of merging
+    pub merged_points: Vec<(usize, usize)>,
+}
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Removes the cells whose measure (area in 2D or volume in 3D) is practically zero
+    ///
+    /// A cell is degenerate if the absolute value of its measure is smaller than `eps`. Some
+    /// refinements produce such cells, which may crash solvers using the mesh.
+    ///
+    /// If `collapse` is false, the degenerate cells are simply removed; thus, the mesh may have
+    /// gaps where they were. Otherwise, the two corners of the shortest edge of each degenerate
+    /// cell are merged into the one with the smallest ID (which keeps its coordinates), and the
+    /// cells with repeated corners are removed. The collapse is repeated until no degenerate cell
+    /// remains. The middle nodes of quadratic cells are not moved.
+    ///
+    /// The facets are updated as well; those with repeated points are removed.
+    ///
+    /// # Output
+    ///
+    /// Returns the cleaned mesh (extracted as in `extract_cells_in`), the maps to the original
+    /// points and cells, and the report of the removed cells and merged points.
+    pub fn remove_degenerate_cells(&self, eps: f64, collapse: bool) -> (Mesh<DIM>, Renumbering, CleanupReport) {
+        let ncell = self.cells.len();
+        let mut parent: Vec<usize> = (0..self.points.len()).collect();
+        let mut alive = vec![true; ncell];
+        let mut report = CleanupReport::default();
+        loop {
+            let mut merged = false;
+            for (cell, cell_alive) in alive.iter_mut().enumerate() {
+                if !*cell_alive {
+                    continue;
+                }
+                let corners: Vec<usize> = self.cells[cell][..DIM + 1]
+                    .iter()
+                    .map(|p| find_root(&mut parent, *p))
+                    .collect();
+                let repeated = (0..DIM).any(|i| corners[(i + 1)..].contains(&corners[i]));
+                if !repeated && f64::abs(simplex_measure(&self.points, &corners)) >= eps {
+                    continue;
+                }
+                if repeated || !collapse {
+                    *cell_alive = false;
+                    report.removed_cells.push(cell);
+                    continue;
+                }
+                let mut shortest = (f64::MAX, 0, 0);
+                for i in 0..DIM {
+                    for j in (i + 1)..(DIM + 1) {
+                        let (a, b) = (corners[i], corners[j]);
+                        let d: f64 = (0..DIM).map(|k| (self.points[a][k] - self.points[b][k]).powi(2)).sum();
+                        if d < shortest.0 {
+                            shortest = (d, usize::min(a, b), usize::max(a, b));
+                        }
+                    }
+                }
+                let (_, kept, removed) = shortest;
+                parent[removed] = kept;
+                report.merged_points.push((removed, kept));
+                merged = true;
+            }
+            if !merged {
+                break;
+            }
+        }
+        report.removed_cells.sort();
+
+        // apply the merges and extract the remaining cells
+        let npoint = self.points.len();
+        let roots: Vec<usize> = (0..npoint).map(|p| find_root(&mut parent, p)).collect();
+        let mut facets = Vec::new();
+        let mut facet_markers = Vec::new();
+        for (facet, marker) in self.facets.iter().zip(&self.facet_markers) {
+            let nodes: Vec<usize> = facet.iter().map(|p| roots[*p]).collect();
+            if (0..nodes.len()).all(|i| !nodes[(i + 1)..].contains(&nodes[i])) {
+                facets.push(nodes);
+                facet_markers.push(*marker);
+            }
+        }
+        let merged = Mesh {
+            points: self.points.clone(),
+            cells: self
+                .cells
+                .iter()
+                .map(|nodes| nodes.iter().map(|p| roots[*p]).collect())
+                .collect(),
+            attributes: self.attributes.clone(),
+            facets,
+            facet_markers,
+            tags: self.tags.clone(),
+        };
+        let selected = (0..ncell).filter(|cell| alive[*cell]).collect();
+        let (mesh, renumbering) = merged.extract_cells(selected);
+        (mesh, renumbering, report)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{CellTags, TetMesh, TriMesh};
+
+    /// Returns a unit square with a zero-area cell (cell 3) along the left edge
+    fn square() -> TriMesh {
+        TriMesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.5]],
+            cells: vec![vec![0, 1, 4], vec![1, 2, 4], vec![2, 3, 4], vec![3, 0, 4]],
+            attributes: vec![1, 2, 3, 4],
+            facets: vec![vec![0, 1], vec![3, 4], vec![4, 0]],
+            facet_markers: vec![-1, -2, -3],
+            tags: CellTags::default(),
+        }
+    }
+
+    #[test]
+    fn remove_degenerate_cells_works() {
+        let mesh = square();
+        let (clean, renumbering, report) = mesh.remove_degenerate_cells(1e-12, false);
+        assert_eq!(clean.cells, &[vec![0, 1, 4], vec![1, 2, 4], vec![2, 3, 4]]);
+        assert_eq!(clean.attributes, &[1, 2, 3]);
+        assert_eq!(clean.facets, mesh.facets);
+        assert_eq!(renumbering.points, &[0, 1, 2, 3, 4]);
+        assert_eq!(renumbering.cells, &[0, 1, 2]);
+        assert_eq!(report.removed_cells, &[3]);
+        assert_eq!(report.merged_points, &[]);
+
+        // nothing to do
+        let (same, _, report) = clean.remove_degenerate_cells(1e-12, true);
+        assert_eq!(same, clean);
+        assert_eq!(report.removed_cells, &[]);
+    }
+
+    #[test]
+    fn collapse_degenerate_cells_works() {
+        let (clean, renumbering, report) = square().remove_degenerate_cells(1e-12, true);
+        assert_eq!(clean.points, &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+        assert_eq!(clean.cells, &[vec![0, 1, 3], vec![1, 2, 3]]);
+        assert_eq!(clean.attributes, &[1, 2]);
+        assert_eq!(clean.facets, &[vec![0, 1], vec![3, 0]]);
+        assert_eq!(clean.facet_markers, &[-1, -3]);
+        assert_eq!(clean.measures().iter().sum::<f64>(), 1.0);
+        assert_eq!(renumbering.points, &[0, 1, 2, 3]);
+        assert_eq!(renumbering.cells, &[0, 1]);
+        assert_eq!(report.removed_cells, &[2, 3]);
+        assert_eq!(report.merged_points, &[(4, 3)]);
+    }
+
+    #[test]
+    fn remove_degenerate_cells_works_in_3d() {
+        let mesh = TetMesh {
+            points: vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.3, 0.3, 0.0],
+            ],
+            cells: vec![vec![0, 1, 2, 3], vec![0, 1, 2, 4]],
+            attributes: vec![1, 2],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        let (clean, _, report) = mesh.remove_degenerate_cells(1e-12, false);
+        assert_eq!(clean.cells, &[vec![0, 1, 2, 3]]);
+        assert_eq!(clean.points.len(), 4);
+        assert_eq!(report.removed_cells, &[1]);
+        let (clean, _, report) = mesh.remove_degenerate_cells(1e-12, true);
+        assert_eq!(clean.cells, &[vec![0, 1, 2, 3]]);
+        assert_eq!(report.removed_cells, &[1]);
+        assert_eq!(report.merged_points, &[(4, 0)]);
+    }
+}
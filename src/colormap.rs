@@ -0,0 +1,107 @@
+use crate::constants;
+use crate::StrError;
+use std::collections::HashMap;
+
+/// Maps attributes (or markers) to colors
+///
+/// The color of a key is deterministic: unless overridden by `set_color`, the key `k` gets the
+/// color `palette[k mod palette.len()]`. Thus, the same attribute is drawn with the same color
+/// in all drawings and exported files, regardless of the order of the cells.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{ColorMap, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut colormap = ColorMap::new(&["red", "green", "blue"])?;
+///     colormap.set_color(10, "black");
+///     assert_eq!(colormap.color(1), "green");
+///     assert_eq!(colormap.color(4), "green");
+///     assert_eq!(colormap.color(-1), "blue");
+///     assert_eq!(colormap.color(10), "black");
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ColorMap {
+    palette: Vec<String>,            // colors assigned by key
+    overrides: HashMap<i64, String>, // colors given by the user
+}
+
+impl ColorMap {
+    /// Allocates a new instance with a palette of colors (e.g., "#cbe4f9", "red")
+    pub fn new(palette: &[&str]) -> Result<Self, StrError> {
+        if palette.is_empty() {
+            return Err("the palette must have at least one color");
+        }
+        Ok(ColorMap {
+            palette: palette.iter().map(|c| c.to_string()).collect(),
+            overrides: HashMap::new(),
+        })
+    }
+
+    /// Allocates a new instance with the light palette (used to fill cells)
+    pub fn light() -> Self {
+        ColorMap::new(&constants::LIGHT_COLORS).unwrap()
+    }
+
+    /// Allocates a new instance with the dark palette (used to draw edges)
+    pub fn dark() -> Self {
+        ColorMap::new(&constants::DARK_COLORS).unwrap()
+    }
+
+    /// Overrides the color of a key
+    pub fn set_color(&mut self, key: i64, color: &str) -> &mut Self {
+        self.overrides.insert(key, color.to_string());
+        self
+    }
+
+    /// Returns the color of a key
+    pub fn color(&self, key: i64) -> &str {
+        match self.overrides.get(&key) {
+            Some(color) => color,
+            None => &self.palette[key.rem_euclid(self.palette.len() as i64) as usize],
+        }
+    }
+
+    /// Returns the legend entries (key, color) for a list of keys (sorted and without duplicates)
+    pub fn legend(&self, keys: &[i64]) -> Vec<(i64, String)> {
+        let mut sorted = keys.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        sorted.into_iter().map(|k| (k, self.color(k).to_string())).collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::ColorMap;
+    use crate::constants;
+
+    #[test]
+    fn new_captures_errors() {
+        assert_eq!(
+            ColorMap::new(&[]).err(),
+            Some("the palette must have at least one color")
+        );
+    }
+
+    #[test]
+    fn colormap_works() {
+        let mut colormap = ColorMap::light();
+        assert_eq!(colormap.color(0), constants::LIGHT_COLORS[0]);
+        assert_eq!(colormap.color(18), constants::LIGHT_COLORS[1]);
+        colormap.set_color(18, "red");
+        assert_eq!(colormap.color(18), "red");
+        assert_eq!(
+            colormap.legend(&[18, 0, 18]),
+            &[(0, constants::LIGHT_COLORS[0].to_string()), (18, "red".to_string())]
+        );
+        let colormap = ColorMap::dark().clone();
+        assert_eq!(colormap.color(-1), constants::DARK_COLORS[11]);
+        assert!(!format!("{:?}", ColorMap::new(&["red"]).unwrap()).is_empty());
+    }
+}
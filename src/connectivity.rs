@@ -0,0 +1,560 @@
+use crate::{Tetgen, Triangle};
+use std::collections::HashMap;
+
+/// Holds the local corners and the local middle node of the six edges of a quadratic tetrahedron
+///
+/// Each entry is `(corner_a, corner_b, middle)` with the local indices of `Tetgen::tet_node`.
+pub const TET_EDGE_LOCAL_NODES: [(usize, usize, usize); 6] =
+    [(0, 1, 4), (1, 2, 5), (0, 2, 6), (0, 3, 7), (1, 3, 8), (2, 3, 9)];
+
+/// Holds a graph in compressed sparse row (CSR) format
+///
+/// The neighbors of vertex `i` are `adjncy[xadj[i]..xadj[i + 1]]`. This is the format
+/// used by graph partitioners such as METIS and by sparse matrix symbolic analysis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrGraph {
+    /// Holds the offsets of the neighbor lists (length = number of vertices + 1)
+    pub xadj: Vec<usize>,
+
+    /// Holds the neighbors of all vertices, sorted within each list
+    pub adjncy: Vec<usize>,
+}
+
+impl CsrGraph {
+    /// Returns the number of vertices of the graph
+    pub fn nvertex(&self) -> usize {
+        self.xadj.len() - 1
+    }
+
+    /// Returns the neighbors of a vertex
+    pub fn neighbors(&self, vertex: usize) -> &[usize] {
+        &self.adjncy[self.xadj[vertex]..self.xadj[vertex + 1]]
+    }
+
+    /// Returns the connected components of the graph
+    ///
+    /// # Output
+    ///
+    /// Returns `(ncomponent, component)` where `component` holds the component ID of each vertex.
+    /// The components are numbered in the order of their smallest vertex.
+    pub fn components(&self) -> (usize, Vec<usize>) {
+        let nvertex = self.nvertex();
+        let mut component = vec![usize::MAX; nvertex];
+        let mut ncomponent = 0;
+        let mut stack = Vec::new();
+        for start in 0..nvertex {
+            if component[start] != usize::MAX {
+                continue;
+            }
+            component[start] = ncomponent;
+            stack.push(start);
+            while let Some(vertex) = stack.pop() {
+                for other in self.neighbors(vertex) {
+                    if component[*other] == usize::MAX {
+                        component[*other] = ncomponent;
+                        stack.push(*other);
+                    }
+                }
+            }
+            ncomponent += 1;
+        }
+        (ncomponent, component)
+    }
+
+    /// Allocates a new instance from the (unsorted, possibly repeated) neighbors of each vertex
+    pub(crate) fn from_lists(lists: Vec<Vec<usize>>) -> Self {
+        let mut xadj = Vec::with_capacity(lists.len() + 1);
+        let mut adjncy = Vec::new();
+        xadj.push(0);
+        for mut list in lists {
+            list.sort();
+            list.dedup();
+            adjncy.extend(list);
+            xadj.push(adjncy.len());
+        }
+        CsrGraph { xadj, adjncy }
+    }
+}
+
+impl Triangle {
+    /// Returns the element-to-node connectivity (the node IDs of each triangle)
+    ///
+    /// All nodes are included; e.g., the middle nodes of quadratic triangles.
+    pub fn cell_to_nodes(&self) -> Vec<Vec<usize>> {
+        let nnode = self.nnode();
+        (0..self.ntriangle())
+            .map(|cell| (0..nnode).map(|m| self.triangle_node(cell, m)).collect())
+            .collect()
+    }
+
+    /// Returns the node-to-element connectivity (the IDs of the triangles sharing each point)
+    ///
+    /// The outer vector has length equal to `npoint` and the triangle IDs are sorted.
+    /// Points that do not belong to any triangle have an empty list.
+    pub fn node_to_cells(&self) -> Vec<Vec<usize>> {
+        invert_connectivity(self.npoint(), &self.cell_to_nodes())
+    }
+
+    /// Returns the dual graph of the mesh; i.e., the adjacency of triangles sharing an edge
+    ///
+    /// Only the corner nodes are considered to find the shared edges. See also
+    /// `cell_to_cell_adjacency` for the cached version.
+    pub fn cell_adjacency_csr(&self) -> CsrGraph {
+        let corners: Vec<Vec<usize>> = (0..self.ntriangle())
+            .map(|cell| (0..3).map(|m| self.triangle_node(cell, m)).collect())
+            .collect();
+        dual_graph(&corners, &[&[0, 1], &[1, 2], &[2, 0]])
+    }
+
+    /// Returns the nodal graph of the mesh; i.e., the adjacency of points sharing a triangle
+    ///
+    /// All nodes are considered (e.g., middle nodes); thus, the graph yields the sparsity
+    /// pattern of the (off-diagonal) entries of the global matrices of the finite element method.
+    pub fn nodal_graph_csr(&self) -> CsrGraph {
+        nodal_graph(self.npoint(), &self.cell_to_nodes())
+    }
+
+    /// Returns the connected components of the mesh (e.g., the meshes of disjoint domains)
+    ///
+    /// Two triangles belong to the same component if they are connected by shared edges.
+    /// See `CsrGraph::components` for the output.
+    pub fn cell_components(&self) -> (usize, Vec<usize>) {
+        self.cell_to_cell_adjacency().components()
+    }
+
+    /// Returns the two corner nodes of the edge holding each middle node of quadratic triangles
+    ///
+    /// The outer vector has length equal to `npoint` and the corner IDs are sorted. Corner
+    /// points (and all points of linear triangles) have `None`. For example, the node 3 of a
+    /// triangle (see `triangle_node`) lies between the nodes 0 and 1.
+    pub fn midside_parents(&self) -> Vec<Option<(usize, usize)>> {
+        if self.nnode() != 6 {
+            return vec![None; self.npoint()];
+        }
+        midside_parents(self.npoint(), &self.cell_to_nodes(), &[(3, 0, 1), (4, 1, 2), (5, 2, 0)])
+    }
+}
+
+impl Tetgen {
+    /// Returns the element-to-node connectivity (the node IDs of each tetrahedron)
+    ///
+    /// All nodes are included; e.g., the middle nodes of quadratic tetrahedra.
+    pub fn cell_to_nodes(&self) -> Vec<Vec<usize>> {
+        let nnode = self.nnode();
+        (0..self.ntet())
+            .map(|cell| (0..nnode).map(|m| self.tet_node(cell, m)).collect())
+            .collect()
+    }
+
+    /// Returns the node-to-element connectivity (the IDs of the tetrahedra sharing each point)
+    ///
+    /// The outer vector has length equal to `npoint` and the tetrahedron IDs are sorted.
+    /// Points that do not belong to any tetrahedron have an empty list.
+    pub fn node_to_cells(&self) -> Vec<Vec<usize>> {
+        invert_connectivity(self.npoint(), &self.cell_to_nodes())
+    }
+
+    /// Returns the dual graph of the mesh; i.e., the adjacency of tetrahedra sharing a face
+    ///
+    /// Only the corner nodes are considered to find the shared faces. See also
+    /// `cell_to_cell_adjacency` for the cached version.
+    pub fn cell_adjacency_csr(&self) -> CsrGraph {
+        let corners: Vec<Vec<usize>> = (0..self.ntet())
+            .map(|cell| (0..4).map(|m| self.tet_node(cell, m)).collect())
+            .collect();
+        dual_graph(&corners, &[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]])
+    }
+
+    /// Returns the nodal graph of the mesh; i.e., the adjacency of points sharing a tetrahedron
+    ///
+    /// All nodes are considered (e.g., middle nodes); thus, the graph yields the sparsity
+    /// pattern of the (off-diagonal) entries of the global matrices of the finite element method.
+    pub fn nodal_graph_csr(&self) -> CsrGraph {
+        nodal_graph(self.npoint(), &self.cell_to_nodes())
+    }
+
+    /// Returns the connected components of the mesh (e.g., the meshes of disjoint domains)
+    ///
+    /// Two tetrahedra belong to the same component if they are connected by shared faces.
+    /// See `CsrGraph::components` for the output.
+    pub fn cell_components(&self) -> (usize, Vec<usize>) {
+        self.cell_to_cell_adjacency().components()
+    }
+
+    /// Returns the two corner nodes of the edge holding each middle node of quadratic tetrahedra
+    ///
+    /// The outer vector has length equal to `npoint` and the corner IDs are sorted. Corner
+    /// points (and all points of linear tetrahedra) have `None`. For example, the node 9 of a
+    /// tetrahedron (see `tet_node`) lies between the nodes 2 and 3.
+    pub fn midside_parents(&self) -> Vec<Option<(usize, usize)>> {
+        if self.nnode() != 10 {
+            return vec![None; self.npoint()];
+        }
+        let edges = TET_EDGE_LOCAL_NODES.map(|(a, b, mid)| (mid, a, b));
+        midside_parents(self.npoint(), &self.cell_to_nodes(), &edges)
+    }
+
+    /// Returns the corners and the middle node of each edge of a quadratic tetrahedron
+    ///
+    /// The edges follow `TET_EDGE_LOCAL_NODES`; i.e., the k-th entry holds the point IDs
+    /// `(corner_a, corner_b, middle)` of the edge with middle node `4 + k` (see `tet_node`).
+    ///
+    /// Returns None if `index` is out of range or the tetrahedra are linear.
+    pub fn tet_edge_nodes(&self, index: usize) -> Option<[(usize, usize, usize); 6]> {
+        if self.nnode() != 10 || index >= self.ntet() {
+            return None;
+        }
+        Some(TET_EDGE_LOCAL_NODES.map(|(a, b, mid)| {
+            (
+                self.tet_node(index, a),
+                self.tet_node(index, b),
+                self.tet_node(index, mid),
+            )
+        }))
+    }
+
+    /// Returns the nodes of each marked face, including the middle nodes of quadratic tetrahedra
+    ///
+    /// The first three nodes are the corners (see `marked_face_node`). If the tetrahedra are
+    /// quadratic, the nodes 3, 4, and 5 lie between the corners (0,1), (1,2), and (2,0),
+    /// respectively (as in `Triangle::triangle_node`); these nodes are found from the edges of
+    /// the tetrahedra because Tetgen does not output the middle nodes of the faces.
+    pub fn marked_face_nodes(&self) -> Vec<Vec<usize>> {
+        let corners = |face: usize| (0..3).map(|m| self.marked_face_node(face, m)).collect::<Vec<_>>();
+        if self.nnode() != 10 {
+            return (0..self.nmarked_face()).map(corners).collect();
+        }
+        let middle: HashMap<(usize, usize), usize> = self
+            .midside_parents()
+            .iter()
+            .enumerate()
+            .filter_map(|(p, parents)| parents.map(|edge| (edge, p)))
+            .collect();
+        (0..self.nmarked_face())
+            .map(|face| {
+                let mut nodes = corners(face);
+                for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+                    let (a, b) = (nodes[i], nodes[j]);
+                    nodes.push(middle[&(usize::min(a, b), usize::max(a, b))]);
+                }
+                nodes
+            })
+            .collect()
+    }
+}
+
+/// Finds the corner nodes of each middle node given the local (middle, corner, corner) nodes of the edges
+fn midside_parents(
+    npoint: usize,
+    cell_to_nodes: &[Vec<usize>],
+    edges: &[(usize, usize, usize)],
+) -> Vec<Option<(usize, usize)>> {
+    let mut parents = vec![None; npoint];
+    for nodes in cell_to_nodes {
+        for (mid, a, b) in edges {
+            let (a, b) = (nodes[*a], nodes[*b]);
+            parents[nodes[*mid]] = Some((usize::min(a, b), usize::max(a, b)));
+        }
+    }
+    parents
+}
+
+/// Computes the dual graph given the corners of each cell and the local corners of each facet
+pub(crate) fn dual_graph(corners: &[Vec<usize>], facets: &[&[usize]]) -> CsrGraph {
+    let mut facet_to_cells: HashMap<Vec<usize>, Vec<usize>> = HashMap::new();
+    for (cell, nodes) in corners.iter().enumerate() {
+        for facet in facets {
+            let mut key: Vec<usize> = facet.iter().map(|m| nodes[*m]).collect();
+            key.sort();
+            facet_to_cells.entry(key).or_default().push(cell);
+        }
+    }
+    let mut lists = vec![Vec::new(); corners.len()];
+    for cells in facet_to_cells.values() {
+        for a in cells {
+            for b in cells {
+                if a != b {
+                    lists[*a].push(*b);
+                }
+            }
+        }
+    }
+    CsrGraph::from_lists(lists)
+}
+
+/// Computes the nodal graph given the nodes of each cell
+pub(crate) fn nodal_graph(npoint: usize, cell_to_nodes: &[Vec<usize>]) -> CsrGraph {
+    let mut lists = vec![Vec::new(); npoint];
+    for nodes in cell_to_nodes {
+        for a in nodes {
+            for b in nodes {
+                if a != b {
+                    lists[*a].push(*b);
+                }
+            }
+        }
+    }
+    CsrGraph::from_lists(lists)
+}
+
+/// Inverts the element-to-node connectivity
+fn invert_connectivity(npoint: usize, cell_to_nodes: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut node_to_cells = vec![Vec::new(); npoint];
+    for (cell, nodes) in cell_to_nodes.iter().enumerate() {
+        for node in nodes {
+            if node_to_cells[*node].last() != Some(&cell) {
+                node_to_cells[*node].push(cell);
+            }
+        }
+    }
+    node_to_cells
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::CsrGraph;
+    use crate::{StrError, Tetgen, Triangle};
+    use std::sync::Arc;
+
+    #[test]
+    fn csr_graph_works() {
+        let graph = CsrGraph::from_lists(vec![vec![2, 1, 2], vec![0], vec![0]]);
+        assert_eq!(graph.nvertex(), 3);
+        assert_eq!(graph.xadj, &[0, 2, 3, 4]);
+        assert_eq!(graph.adjncy, &[1, 2, 0, 0]);
+        assert_eq!(graph.neighbors(0), &[1, 2]);
+        assert_eq!(graph.neighbors(2), &[0]);
+        assert_eq!(
+            format!("{:?}", graph.clone()),
+            "CsrGraph { xadj: [0, 2, 3, 4], adjncy: [1, 2, 0, 0] }"
+        );
+    }
+
+    #[test]
+    fn triangle_connectivity_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.1)?;
+        triangle.generate_delaunay(false)?;
+        let cell_to_nodes = triangle.cell_to_nodes();
+        assert_eq!(cell_to_nodes.len(), 2);
+        let node_to_cells = triangle.node_to_cells();
+        assert_eq!(node_to_cells.len(), 4);
+        for (cell, nodes) in cell_to_nodes.iter().enumerate() {
+            assert_eq!(nodes.len(), 3);
+            for node in nodes {
+                assert!(node_to_cells[*node].contains(&cell));
+            }
+        }
+        let nshared = node_to_cells.iter().filter(|cells| cells.len() == 2).count();
+        assert_eq!(nshared, 2);
+
+        let dual = triangle.cell_adjacency_csr();
+        assert_eq!(dual.xadj, &[0, 1, 2]);
+        assert_eq!(dual.adjncy, &[1, 0]);
+        let nodal = triangle.nodal_graph_csr();
+        assert_eq!(nodal.nvertex(), 4);
+        assert_eq!(nodal.adjncy.len(), 2 * 5); // 5 edges
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_connectivity_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 1.0, 1.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let cell_to_nodes = tetgen.cell_to_nodes();
+        let node_to_cells = tetgen.node_to_cells();
+        assert_eq!(cell_to_nodes.len(), tetgen.ntet());
+        assert_eq!(node_to_cells.len(), 5);
+        let total: usize = node_to_cells.iter().map(|cells| cells.len()).sum();
+        assert_eq!(total, 4 * tetgen.ntet());
+        for (node, cells) in node_to_cells.iter().enumerate() {
+            assert!(cells.windows(2).all(|w| w[0] < w[1]));
+            for cell in cells {
+                assert!(cell_to_nodes[*cell].contains(&node));
+            }
+        }
+
+        let dual = tetgen.cell_adjacency_csr();
+        assert_eq!(dual.nvertex(), tetgen.ntet());
+        for cell in 0..dual.nvertex() {
+            for other in dual.neighbors(cell) {
+                assert!(dual.neighbors(*other).contains(&cell));
+                let shared = cell_to_nodes[cell]
+                    .iter()
+                    .filter(|n| cell_to_nodes[*other].contains(n))
+                    .count();
+                assert_eq!(shared, 3);
+            }
+        }
+        let nodal = tetgen.nodal_graph_csr();
+        assert_eq!(nodal.nvertex(), 5);
+        assert!((0..5).all(|node| !nodal.neighbors(node).contains(&node)));
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_cached_connectivity_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 1, (0.0, 0.0), (2.0, 1.0), [0; 4], false)?;
+        let map = triangle.vertex_to_cell_map();
+        assert!(Arc::ptr_eq(&map, &triangle.vertex_to_cell_map()));
+        assert_eq!(map.nvertex(), 6);
+        for (p, cells) in triangle.node_to_cells().iter().enumerate() {
+            assert_eq!(map.neighbors(p), cells);
+        }
+        assert_eq!(map.neighbors(0), &[0, 1]);
+        assert_eq!(map.neighbors(1), &[0, 2, 3]);
+        let adjacency = triangle.cell_to_cell_adjacency();
+        assert_eq!(*adjacency, triangle.cell_adjacency_csr());
+        assert!(Arc::ptr_eq(&adjacency, &triangle.cell_to_cell_adjacency()));
+        assert_eq!(adjacency.neighbors(0), &[1, 3]);
+        assert_eq!(adjacency.neighbors(1), &[0]);
+
+        // the cached maps are rebuilt after generating a new mesh
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 4);
+        assert_eq!(triangle.vertex_to_cell_map().adjncy.len(), 3 * 4);
+        assert_eq!(*triangle.cell_to_cell_adjacency(), triangle.cell_adjacency_csr());
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_cached_connectivity_works() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], false)?;
+        let map = tetgen.vertex_to_cell_map();
+        assert!(Arc::ptr_eq(&map, &tetgen.vertex_to_cell_map()));
+        assert_eq!(map.nvertex(), 8);
+        for (p, cells) in tetgen.node_to_cells().iter().enumerate() {
+            assert_eq!(map.neighbors(p), cells);
+        }
+        // the main diagonal is shared by all tetrahedra
+        assert_eq!(map.neighbors(0).len(), 6);
+        assert_eq!(map.neighbors(7).len(), 6);
+        let adjacency = tetgen.cell_to_cell_adjacency();
+        assert_eq!(*adjacency, tetgen.cell_adjacency_csr());
+        assert!(Arc::ptr_eq(&adjacency, &tetgen.cell_to_cell_adjacency()));
+        assert!((0..6).all(|cell| adjacency.neighbors(cell).len() == 2));
+
+        // the cached maps are rebuilt after generating a new mesh
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.vertex_to_cell_map().adjncy.len(), 4 * tetgen.ntet());
+        assert_eq!(*tetgen.cell_to_cell_adjacency(), tetgen.cell_adjacency_csr());
+        Ok(())
+    }
+
+    #[test]
+    fn components_work() {
+        let graph = CsrGraph::from_lists(vec![vec![3], vec![], vec![4], vec![0], vec![2]]);
+        assert_eq!(graph.components(), (3, vec![0, 1, 2, 0, 2]));
+        let graph = CsrGraph::from_lists(Vec::new());
+        assert_eq!(graph.components(), (0, vec![]));
+    }
+
+    #[test]
+    fn triangle_cell_components_work() -> Result<(), StrError> {
+        // two disjoint squares
+        let mut triangle = Triangle::new(8, Some(8), None, None)?;
+        for (offset, x0) in [(0, 0.0), (4, 3.0)] {
+            triangle
+                .set_point(offset, x0, 0.0)?
+                .set_point(offset + 1, x0 + 1.0, 0.0)?
+                .set_point(offset + 2, x0 + 1.0, 1.0)?
+                .set_point(offset + 3, x0, 1.0)?;
+            for i in 0..4 {
+                triangle.set_segment(offset + i, offset + i, offset + (i + 1) % 4)?;
+            }
+        }
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        let (ncomponent, component) = triangle.cell_components();
+        assert_eq!(ncomponent, 2);
+        assert_eq!(component.len(), triangle.ntriangle());
+        let left = |cell: usize| triangle.point(triangle.triangle_node(cell, 0), 0) < 2.0;
+        for (cell, c) in component.iter().enumerate() {
+            assert_eq!(*c == component[0], left(cell) == left(0));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_midside_parents_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        assert!(triangle.midside_parents().iter().all(|p| p.is_none()));
+
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let parents = triangle.midside_parents();
+        assert_eq!(parents.len(), triangle.npoint());
+        for cell in 0..triangle.ntriangle() {
+            for m in 0..3 {
+                assert_eq!(parents[triangle.triangle_node(cell, m)], None);
+            }
+            for m in 3..6 {
+                let mid = triangle.triangle_node(cell, m);
+                let (a, b) = parents[mid].unwrap();
+                assert!(a < b);
+                for dim in 0..2 {
+                    let x = (triangle.point(a, dim) + triangle.point(b, dim)) / 2.0;
+                    assert!(f64::abs(triangle.point(mid, dim) - x) < 1e-15);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_midside_parents_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.tet_edge_nodes(0), None);
+        tetgen.generate_mesh(false, true, Some(0.01), None)?;
+        assert_eq!(tetgen.tet_edge_nodes(tetgen.ntet()), None);
+        let parents = tetgen.midside_parents();
+        assert_eq!(parents.len(), tetgen.npoint());
+        let nmid = parents.iter().filter(|p| p.is_some()).count();
+        assert!(nmid > 6);
+        for cell in 0..tetgen.ntet() {
+            for m in 4..10 {
+                let mid = tetgen.tet_node(cell, m);
+                let (a, b) = parents[mid].unwrap();
+                for dim in 0..3 {
+                    let x = (tetgen.point(a, dim) + tetgen.point(b, dim)) / 2.0;
+                    assert!(f64::abs(tetgen.point(mid, dim) - x) < 1e-15);
+                }
+            }
+            let edges = tetgen.tet_edge_nodes(cell).unwrap();
+            for (k, (a, b, mid)) in edges.iter().enumerate() {
+                assert_eq!(*mid, tetgen.tet_node(cell, 4 + k));
+                assert_eq!(parents[*mid], Some((usize::min(*a, *b), usize::max(*a, *b))));
+            }
+        }
+        Ok(())
+    }
+}
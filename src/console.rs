@@ -0,0 +1,187 @@
+use std::any::Any;
+use std::ffi::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+extern "C" {
+    fn tritet_set_console_callback(callback: Option<extern "C" fn(*mut c_void, *const c_char, i32)>, data: *mut c_void);
+}
+
+/// Receives the console output of Triangle or Tetgen (see `Triangle::set_console_handler`)
+///
+/// The handler is implemented for closures; e.g., `|line: &str| log::info!("{}", line)`.
+pub trait ConsoleHandler: Send {
+    /// Handles a line of text (without the newline character)
+    fn on_line(&mut self, line: &str);
+}
+
+impl<F> ConsoleHandler for F
+where
+    F: FnMut(&str) + Send,
+{
+    fn on_line(&mut self, line: &str) {
+        self(line)
+    }
+}
+
+/// Holds the state of the handler during a call to the c-code
+struct ConsoleState<'a> {
+    handler: &'a mut dyn ConsoleHandler,
+    line: Vec<u8>,
+    panic: Option<Box<dyn Any + Send>>, // payload of a panic of the handler (resumed after the c-code returns)
+}
+
+/// Receives the text printed by the c-code
+///
+/// A panic of the handler must not unwind into the c-code; thus, it is caught and stored in the
+/// state, and the handler is not called again.
+extern "C" fn print_to_console(data: *mut c_void, text: *const c_char, length: i32) {
+    let state = unsafe { &mut *(data as *mut ConsoleState) };
+    if state.panic.is_some() {
+        return;
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(text as *const u8, length as usize) };
+    for byte in bytes {
+        if *byte == b'\n' {
+            let (handler, line) = (&mut state.handler, &state.line);
+            if let Err(payload) =
+                panic::catch_unwind(AssertUnwindSafe(|| handler.on_line(&String::from_utf8_lossy(line))))
+            {
+                state.panic = Some(payload);
+                return;
+            }
+            state.line.clear();
+        } else {
+            state.line.push(*byte);
+        }
+    }
+}
+
+/// Clears the callback when dropped (also if a panic of a handler is resumed by `run`)
+struct CallbackGuard;
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        unsafe {
+            tritet_set_console_callback(None, ptr::null_mut());
+        }
+    }
+}
+
+/// Runs a call to the c-code passing the console output to the handler (if any)
+///
+/// The last line is passed to the handler even if it has no newline character. If the handler
+/// panics, the panic is resumed after the c-code returns.
+pub(crate) fn run_with_console<T, F>(handler: Option<&mut dyn ConsoleHandler>, run: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let handler = match handler {
+        Some(h) => h,
+        None => return run(),
+    };
+    let mut state = ConsoleState {
+        handler,
+        line: Vec::new(),
+        panic: None,
+    };
+    unsafe {
+        tritet_set_console_callback(Some(print_to_console), &mut state as *mut ConsoleState as *mut c_void);
+    }
+    let guard = CallbackGuard;
+    let result = run();
+    drop(guard);
+    if let Some(payload) = state.panic {
+        panic::resume_unwind(payload);
+    }
+    if !state.line.is_empty() {
+        state.handler.on_line(&String::from_utf8_lossy(&state.line));
+    }
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn console_handler_works_with_triangle() -> Result<(), StrError> {
+        let lines: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = lines.clone();
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.set_console_handler(move |line: &str| sink.lock().unwrap().push(line.to_string()));
+        triangle.generate_delaunay(true)?;
+        let captured = lines.lock().unwrap().clone();
+        assert!(captured
+            .iter()
+            .any(|l| l.contains("Constructing Delaunay triangulation")));
+        assert!(captured.iter().all(|l| !l.contains('\n')));
+
+        lines.lock().unwrap().clear();
+        triangle.generate_delaunay(false)?;
+        assert_eq!(lines.lock().unwrap().len(), 0);
+
+        triangle.clear_console_handler();
+        triangle.generate_delaunay(true)?;
+        assert_eq!(lines.lock().unwrap().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn console_handler_works_with_tetgen() -> Result<(), StrError> {
+        let lines: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = lines.clone();
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.set_console_handler(move |line: &str| sink.lock().unwrap().push(line.to_string()));
+        tetgen.generate_delaunay(true)?;
+        let captured = lines.lock().unwrap().clone();
+        assert!(captured
+            .iter()
+            .any(|l| l.contains("Constructing Delaunay tetrahedralization")));
+
+        lines.lock().unwrap().clear();
+        tetgen.clear_console_handler();
+        tetgen.generate_delaunay(true)?;
+        assert_eq!(lines.lock().unwrap().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn panicking_console_handler_is_resumed() -> Result<(), StrError> {
+        let count = Arc::new(Mutex::new(0));
+        let sink = count.clone();
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.set_console_handler(move |_: &str| {
+            *sink.lock().unwrap() += 1;
+            panic!("stop");
+        });
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| triangle.generate_delaunay(true)));
+        assert_eq!(*res.err().unwrap().downcast::<&str>().unwrap(), "stop");
+        assert_eq!(*count.lock().unwrap(), 1);
+
+        // the generator may be used again
+        let lines = Arc::new(Mutex::new(0));
+        let sink = lines.clone();
+        triangle.set_console_handler(move |_: &str| *sink.lock().unwrap() += 1);
+        triangle.generate_delaunay(true)?;
+        assert_eq!(triangle.ntriangle(), 1);
+        assert!(*lines.lock().unwrap() > 0);
+        Ok(())
+    }
+}
@@ -2,6 +2,9 @@ pub(crate) const TRITET_SUCCESS: i32 = 0;
 
 pub(crate) const TRITET_ERROR_NULL_DATA: i32 = 10;
 pub(crate) const TRITET_ERROR_STRING_CONCAT: i32 = 20;
+pub(crate) const TRITET_ERROR_ALLOC: i32 = 30;
+pub(crate) const TRITET_ERROR_CANCELLED: i32 = 40;
+pub(crate) const TRITET_ERROR_TETGEN_FAILED: i32 = 50;
 
 pub(crate) const TRITET_ERROR_NULL_POINT_LIST: i32 = 100;
 pub(crate) const TRITET_ERROR_NULL_SEGMENT_LIST: i32 = 200;
@@ -9,6 +12,7 @@ pub(crate) const TRITET_ERROR_NULL_FACET_LIST: i32 = 300;
 pub(crate) const TRITET_ERROR_NULL_FACET_POLYGON_LIST: i32 = 400;
 pub(crate) const TRITET_ERROR_NULL_REGION_LIST: i32 = 500;
 pub(crate) const TRITET_ERROR_NULL_HOLE_LIST: i32 = 600;
+pub(crate) const TRITET_ERROR_NULL_MESH: i32 = 700;
 
 pub(crate) const TRITET_ERROR_INVALID_POINT_INDEX: i32 = 1000;
 pub(crate) const TRITET_ERROR_INVALID_SEGMENT_INDEX: i32 = 2000;
@@ -37,6 +41,8 @@ pub(crate) const TRITET_TO_TRIANGLE: [usize; 6] = [0, 1, 2, 5, 3, 4];
 
 /// Maps indices used in this library (tritet) to indices used in Tetgen
 ///
+/// The embedded Tetgen (version 1.4.3) numbers the middle nodes as this library does.
+///
 /// ```text
 ///       This library (tritet)                          Tetgen
 ///               NODES                                  CORNERS
@@ -48,25 +54,25 @@ pub(crate) const TRITET_TO_TRIANGLE: [usize; 6] = [0, 1, 2, 5, 3, 4];
 ///           | |      \                                | |      \
 ///          /  |       `.                             /  |       `.
 ///          |  |         `,                           |  |         `,
-///         /   7            9                        /   5            4
+///         /   7            9                        /   7            9
 ///         |   |             \                       |   |             \
 ///        /    |              `.                    /    |              `.
 ///        |    |                ',                  |    |                ',
 ///       8     |                  \                8     |                  \
 ///       |     0 ,,_               `.              |     0 ,,_               `.
-///      |     /     ``'-., 6         `.           |     /     ``'-., 9         `.
+///      |     /     ``'-., 6         `.           |     /     ``'-., 6         `.
 ///      |    /               `''-.,,_  ',         |    /               `''-.,,_  ',
 ///     |    /                        ``'2 ,,     |    /                        ``'2 ,,
 ///     |   '                       ,.-``         |   '                       ,.-``
-///    |   4                   _,-'`             |   6                   _,-'`
+///    |   4                   _,-'`             |   4                   _,-'`
 ///    ' /                 ,.'`                  ' /                 ,.'`
-///   | /             _ 5 `                     | /             _ 7 `
+///   | /             _ 5 `                     | /             _ 5 `
 ///   '/          ,-'`                          '/          ,-'`
 ///  |/      ,.-``                             |/      ,.-``
 ///  /  _,-``                                  /  _,-``
 /// 1 '`                                      1 '`
 /// ```
-pub(crate) const TRITET_TO_TETGEN: [usize; 10] = [0, 1, 2, 3, 6, 7, 9, 5, 8, 4];
+pub(crate) const TRITET_TO_TETGEN: [usize; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
 
 /// Defines a set of "light" colors
 pub(crate) const LIGHT_COLORS: [&'static str; 17] = [
@@ -80,5 +86,10 @@ pub(crate) const DARK_COLORS: [&'static str; 12] = [
     "#2f3b22", "#152d32",
 ];
 
+pub(crate) const VTK_LINE: i32 = 3;
+pub(crate) const VTK_POLYGON: i32 = 7;
+pub(crate) const VTK_TRIANGLE: i32 = 5;
+pub(crate) const VTK_QUADRATIC_TRIANGLE: i32 = 22;
 pub(crate) const VTK_TETRA: i32 = 10;
 pub(crate) const VTK_QUADRATIC_TETRA: i32 = 24;
+pub(crate) const VTK_POLYHEDRON: i32 = 42;
@@ -0,0 +1,337 @@
+use crate::vector::{dot, norm, triangle_normal};
+use crate::TriMesh3D;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Holds the coefficients of a symmetric 4×4 error quadric (upper triangle, row by row)
+type Quadric = [f64; 10];
+
+impl TriMesh3D {
+    /// Returns a coarser surface with at most `target_ntri` triangles (if possible)
+    ///
+    /// Uses the quadric error metric of Garland and Heckbert: the edge whose collapse causes the
+    /// smallest error is collapsed first and the merged point is placed where the sum of squared
+    /// distances to the planes of the original triangles is minimum. Collapses that would make
+    /// the surface non-manifold or flip triangles are skipped; thus, the resulting number of
+    /// triangles may be greater than `target_ntri`.
+    ///
+    /// The unused points are removed; the order of the remaining points and triangles is kept.
+    pub fn decimate(&self, target_ntri: usize) -> TriMesh3D {
+        let npoint = self.points.len();
+        let mut points = self.points.clone();
+        let mut triangles = self.triangles.clone();
+        let mut alive = vec![true; triangles.len()];
+        let mut ntri = triangles.len();
+
+        // triangles around each point and initial quadrics
+        let mut point_tris: Vec<Vec<usize>> = vec![Vec::new(); npoint];
+        let mut quadrics: Vec<Quadric> = vec![[0.0; 10]; npoint];
+        for (t, tri) in triangles.iter().enumerate() {
+            let q = plane_quadric(&points[tri[0]], &points[tri[1]], &points[tri[2]]);
+            for p in tri {
+                point_tris[*p].push(t);
+                for (a, b) in quadrics[*p].iter_mut().zip(q.iter()) {
+                    *a += b;
+                }
+            }
+        }
+
+        // candidate edges (cost bits, a, b, stamp of a, stamp of b); costs are non-negative
+        let mut stamps = vec![0_usize; npoint];
+        let mut heap = BinaryHeap::new();
+        for tri in &triangles {
+            for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+                if a < b {
+                    let (cost, _) = collapse_target(&quadrics, &points, a, b);
+                    heap.push(Reverse((cost.to_bits(), a, b, 0, 0)));
+                }
+            }
+        }
+
+        while ntri > target_ntri {
+            let Reverse((_, a, b, stamp_a, stamp_b)) = match heap.pop() {
+                Some(entry) => entry,
+                None => break,
+            };
+            if stamps[a] != stamp_a || stamps[b] != stamp_b {
+                continue; // outdated
+            }
+            let shared: Vec<usize> = point_tris[a]
+                .iter()
+                .copied()
+                .filter(|t| triangles[*t].contains(&b))
+                .collect();
+            if shared.is_empty() {
+                continue; // the edge does not exist anymore
+            }
+
+            // link condition: the common neighbors must be the opposite corners of the shared triangles
+            let neighbors_a = neighbors(&triangles, &point_tris[a], a);
+            let neighbors_b = neighbors(&triangles, &point_tris[b], b);
+            let ncommon = neighbors_a.iter().filter(|p| neighbors_b.contains(p)).count();
+            let nunion = neighbors_a.len() + neighbors_b.len() - ncommon;
+            if ncommon != shared.len() || nunion <= 4 {
+                continue;
+            }
+
+            // reject flipped or degenerate triangles
+            let (_, x) = collapse_target(&quadrics, &points, a, b);
+            let flips = point_tris[a].iter().chain(point_tris[b].iter()).any(|t| {
+                if shared.contains(t) {
+                    return false;
+                }
+                let old = triangles[*t].map(|p| points[p]);
+                let new = triangles[*t].map(|p| if p == a || p == b { x } else { points[p] });
+                let n_old = triangle_normal(&old[0], &old[1], &old[2]);
+                let n_new = triangle_normal(&new[0], &new[1], &new[2]);
+                dot(&n_old, &n_new) <= 0.0
+            });
+            if flips {
+                continue;
+            }
+
+            // collapse b into a
+            for t in &shared {
+                alive[*t] = false;
+                ntri -= 1;
+                for p in triangles[*t] {
+                    point_tris[p].retain(|s| s != t);
+                }
+            }
+            let moved = std::mem::take(&mut point_tris[b]);
+            for t in moved {
+                for p in triangles[t].iter_mut() {
+                    if *p == b {
+                        *p = a;
+                    }
+                }
+                point_tris[a].push(t);
+            }
+            points[a] = x;
+            let qb = quadrics[b];
+            for (qa, qb) in quadrics[a].iter_mut().zip(qb.iter()) {
+                *qa += qb;
+            }
+            stamps[a] += 1;
+            stamps[b] += 1;
+
+            // new candidate edges around a
+            for p in neighbors(&triangles, &point_tris[a], a) {
+                let (i, j) = if a < p { (a, p) } else { (p, a) };
+                let (cost, _) = collapse_target(&quadrics, &points, i, j);
+                heap.push(Reverse((cost.to_bits(), i, j, stamps[i], stamps[j])));
+            }
+        }
+
+        // remove the unused points
+        let mut used = vec![false; npoint];
+        for (t, tri) in triangles.iter().enumerate() {
+            if alive[t] {
+                for p in tri {
+                    used[*p] = true;
+                }
+            }
+        }
+        let mut new_ids = vec![0; npoint];
+        let mut new_points = Vec::new();
+        for p in (0..npoint).filter(|p| used[*p]) {
+            new_ids[p] = new_points.len();
+            new_points.push(points[p]);
+        }
+        TriMesh3D {
+            points: new_points,
+            triangles: triangles
+                .iter()
+                .enumerate()
+                .filter(|(t, _)| alive[*t])
+                .map(|(_, tri)| tri.map(|p| new_ids[p]))
+                .collect(),
+        }
+    }
+}
+
+/// Returns the points connected to `p` by the edges of the given triangles
+fn neighbors(triangles: &[[usize; 3]], tris: &[usize], p: usize) -> Vec<usize> {
+    let mut result: Vec<usize> = tris.iter().flat_map(|t| triangles[*t]).filter(|q| *q != p).collect();
+    result.sort();
+    result.dedup();
+    result
+}
+
+/// Returns the determinant of a 3×3 matrix
+fn det3(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Returns the quadric of the plane of a triangle weighted by its area
+fn plane_quadric(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Quadric {
+    let n = triangle_normal(a, b, c);
+    let len = norm(&n);
+    if len == 0.0 {
+        return [0.0; 10];
+    }
+    let area = len / 2.0;
+    let (nx, ny, nz) = (n[0] / len, n[1] / len, n[2] / len);
+    let d = -(nx * a[0] + ny * a[1] + nz * a[2]);
+    [
+        area * nx * nx,
+        area * nx * ny,
+        area * nx * nz,
+        area * nx * d,
+        area * ny * ny,
+        area * ny * nz,
+        area * ny * d,
+        area * nz * nz,
+        area * nz * d,
+        area * d * d,
+    ]
+}
+
+/// Evaluates the error xᵀ Q x (with homogeneous coordinates)
+fn quadric_error(q: &Quadric, x: &[f64; 3]) -> f64 {
+    let [a2, ab, ac, ad, b2, bc, bd, c2, cd, d2] = *q;
+    let (x, y, z) = (x[0], x[1], x[2]);
+    let error = a2 * x * x
+        + 2.0 * ab * x * y
+        + 2.0 * ac * x * z
+        + 2.0 * ad * x
+        + b2 * y * y
+        + 2.0 * bc * y * z
+        + 2.0 * bd * y
+        + c2 * z * z
+        + 2.0 * cd * z
+        + d2;
+    f64::max(error, 0.0)
+}
+
+/// Returns the cost and the position of the point resulting from collapsing the edge (a, b)
+fn collapse_target(quadrics: &[Quadric], points: &[[f64; 3]], a: usize, b: usize) -> (f64, [f64; 3]) {
+    let mut q = quadrics[a];
+    for (qi, qb) in q.iter_mut().zip(quadrics[b].iter()) {
+        *qi += qb;
+    }
+
+    // solve the 3×3 system for the optimal position (Cramer's rule)
+    let [a2, ab, ac, ad, b2, bc, bd, c2, cd, _] = q;
+    let m = [[a2, ab, ac], [ab, b2, bc], [ac, bc, c2]];
+    let r = [-ad, -bd, -cd];
+    let det = det3(&m);
+    if f64::abs(det) > 1e-10 * f64::max(a2 * b2 * c2, f64::MIN_POSITIVE) {
+        let mut x = [0.0; 3];
+        for (j, xj) in x.iter_mut().enumerate() {
+            let mut mj = m;
+            for i in 0..3 {
+                mj[i][j] = r[i];
+            }
+            *xj = det3(&mj) / det;
+        }
+        return (quadric_error(&q, &x), x);
+    }
+
+    // fallback: best of the endpoints and the midpoint
+    let (xa, xb) = (points[a], points[b]);
+    let mid = [(xa[0] + xb[0]) / 2.0, (xa[1] + xb[1]) / 2.0, (xa[2] + xb[2]) / 2.0];
+    [mid, xa, xb]
+        .into_iter()
+        .map(|x| (quadric_error(&q, &x), x))
+        .fold(
+            (f64::MAX, mid),
+            |best, candidate| {
+                if candidate.0 < best.0 {
+                    candidate
+                } else {
+                    best
+                }
+            },
+        )
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{plane_quadric, quadric_error};
+    use crate::{StrError, Tetgen, TriMesh3D};
+
+    // Returns the signed volume enclosed by a closed surface
+    fn volume(mesh: &TriMesh3D) -> f64 {
+        mesh.triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let (a, b, c) = (mesh.points[*a], mesh.points[*b], mesh.points[*c]);
+                (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0]))
+                    / 6.0
+            })
+            .sum()
+    }
+
+    // Generates a unit cube subdivided into n×n×n blocks
+    fn cube(n: usize) -> Result<Tetgen, StrError> {
+        let np = n + 1;
+        let mut tetgen = Tetgen::new(np * np * np, None, None, None)?;
+        let mut index = 0;
+        for k in 0..np {
+            for j in 0..np {
+                for i in 0..np {
+                    let h = 1.0 / (n as f64);
+                    tetgen.set_point(index, i as f64 * h, j as f64 * h, k as f64 * h)?;
+                    index += 1;
+                }
+            }
+        }
+        tetgen.generate_delaunay(false)?;
+        Ok(tetgen)
+    }
+
+    #[test]
+    fn quadric_works() {
+        let q = plane_quadric(&[0.0, 0.0, 1.0], &[2.0, 0.0, 1.0], &[0.0, 1.0, 1.0]);
+        assert_eq!(quadric_error(&q, &[5.0, 5.0, 1.0]), 0.0);
+        assert_eq!(quadric_error(&q, &[5.0, 5.0, 3.0]), 4.0); // area × distance²
+        assert_eq!(plane_quadric(&[0.0; 3], &[1.0, 0.0, 0.0], &[2.0, 0.0, 0.0]), [0.0; 10]);
+    }
+
+    #[test]
+    fn boundary_surface_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 0.1, 0.1, 0.1)?;
+        tetgen.generate_delaunay(false)?;
+        let surface = tetgen.boundary_surface();
+        assert_eq!(surface.points.len(), 4);
+        assert_eq!(surface.triangles.len(), 4);
+        assert_eq!(surface.points[3], [0.0, 0.0, 1.0]);
+        assert!(f64::abs(volume(&surface) - 1.0 / 6.0) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn decimate_works() -> Result<(), StrError> {
+        let tetgen = cube(4)?;
+        let surface = tetgen.boundary_surface();
+        assert_eq!(surface.triangles.len(), 6 * 4 * 4 * 2);
+        assert!(f64::abs(volume(&surface) - 1.0) < 1e-14);
+
+        // flat faces can be collapsed without changing the shape
+        let coarse = surface.decimate(12);
+        assert!(coarse.triangles.len() < 40);
+        assert!(coarse.points.len() < surface.points.len());
+        assert!(f64::abs(volume(&coarse) - 1.0) < 1e-10);
+        for p in coarse.points.iter().flatten() {
+            assert!(*p > -1e-10 && *p < 1.0 + 1e-10);
+        }
+        for tri in &coarse.triangles {
+            assert!(tri.iter().all(|p| *p < coarse.points.len()));
+        }
+
+        // nothing to do
+        assert_eq!(surface.decimate(1000), surface);
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{TetMesh, Tetgen, TriMesh, Triangle};
+
+/// Defines a region of space used to select cells
+///
+/// **Note:** The z-coordinates are ignored in 2D.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Region {
+    /// Axis-aligned bounding box given by the minimum and maximum corners
+    BBox {
+        /// The (x, y, z) coordinates of the minimum corner
+        min: [f64; 3],
+        /// The (x, y, z) coordinates of the maximum corner
+        max: [f64; 3],
+    },
+
+    /// Sphere (or circle in 2D) given by its center and radius
+    Sphere {
+        /// The (x, y, z) coordinates of the center
+        center: [f64; 3],
+        /// The radius
+        radius: f64,
+    },
+}
+
+impl Region {
+    /// Indicates whether a point is inside (or on the boundary of) the region
+    pub fn contains(&self, x: &[f64]) -> bool {
+        match self {
+            Region::BBox { min, max } => x.iter().enumerate().all(|(i, v)| *v >= min[i] && *v <= max[i]),
+            Region::Sphere { center, radius } => {
+                let d2: f64 = x
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (v - center[i]) * (v - center[i]))
+                    .sum();
+                d2 <= radius * radius
+            }
+        }
+    }
+}
+
+/// Holds the maps from the entities of an extracted mesh to the entities of the original mesh
+#[derive(Clone, Debug, PartialEq)]
+pub struct Renumbering {
+    /// Holds the original ID of each point of the extracted mesh (new → old)
+    pub points: Vec<usize>,
+
+    /// Holds the original ID of each cell of the extracted mesh (new → old)
+    pub cells: Vec<usize>,
+}
+
+impl Triangle {
+    /// Extracts the triangles whose centroids are inside a region (see `Mesh::extract_cells_in`)
+    pub fn extract_cells_in(&self, region: &Region) -> (TriMesh, Renumbering) {
+        self.to_mesh().extract_cells_in(region)
+    }
+}
+
+impl Tetgen {
+    /// Extracts the tetrahedra whose centroids are inside a region (see `Mesh::extract_cells_in`)
+    pub fn extract_cells_in(&self, region: &Region) -> (TetMesh, Renumbering) {
+        self.to_mesh().extract_cells_in(region)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Region, Renumbering};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn region_contains_works() {
+        let bbox = Region::BBox {
+            min: [0.0, 0.0, 0.0],
+            max: [1.0, 1.0, 1.0],
+        };
+        assert!(bbox.contains(&[0.5, 1.0]));
+        assert!(!bbox.contains(&[0.5, 1.1]));
+        assert!(bbox.contains(&[0.5, 0.5, 0.0]));
+        assert!(!bbox.contains(&[0.5, 0.5, -0.1]));
+        let sphere = Region::Sphere {
+            center: [0.0, 0.0, 0.0],
+            radius: 1.0,
+        };
+        assert!(sphere.contains(&[0.6, 0.8]));
+        assert!(!sphere.contains(&[0.6, 0.8, 0.1]));
+        assert_eq!(
+            format!("{:?}", sphere),
+            "Sphere { center: [0.0, 0.0, 0.0], radius: 1.0 }"
+        );
+    }
+
+    #[test]
+    fn triangle_extract_cells_in_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(5, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_point(4, 0.5, 0.5)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 4);
+
+        // the bottom triangle (0, 1, 4)
+        let region = Region::BBox {
+            min: [0.0, 0.0, 0.0],
+            max: [1.0, 0.3, 0.0],
+        };
+        let (mesh, renumbering) = triangle.extract_cells_in(&region);
+        assert_eq!(mesh.cells.len(), 1);
+        assert_eq!(renumbering.points, &[0, 1, 4]);
+        assert_eq!(mesh.points, &[[0.0, 0.0], [1.0, 0.0], [0.5, 0.5]]);
+        let mut nodes = mesh.cells[0].clone();
+        nodes.sort();
+        assert_eq!(nodes, &[0, 1, 2]);
+        assert_eq!(mesh.attributes, &[0]);
+
+        // everything
+        let region = Region::Sphere {
+            center: [0.5, 0.5, 0.0],
+            radius: 1.0,
+        };
+        let (mesh, renumbering) = triangle.extract_cells_in(&region);
+        assert_eq!(mesh.cells.len(), 4);
+        assert_eq!(
+            renumbering.clone(),
+            Renumbering {
+                points: vec![0, 1, 2, 3, 4],
+                cells: vec![0, 1, 2, 3],
+            }
+        );
+
+        // nothing
+        let region = Region::Sphere {
+            center: [5.0, 5.0, 0.0],
+            radius: 1.0,
+        };
+        let (mesh, renumbering) = triangle.extract_cells_in(&region);
+        assert_eq!(mesh.cells.len(), 0);
+        assert_eq!(mesh.points.len(), 0);
+        assert_eq!(renumbering.cells.len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_extract_cells_in_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(8, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 5.0, 0.0, 0.0)?
+            .set_point(5, 6.0, 0.0, 0.0)?
+            .set_point(6, 5.0, 1.0, 0.0)?
+            .set_point(7, 5.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let region = Region::BBox {
+            min: [-1.0, -1.0, -1.0],
+            max: [2.0, 2.0, 2.0],
+        };
+        let (mesh, renumbering) = tetgen.extract_cells_in(&region);
+        assert!(!mesh.cells.is_empty());
+        for (new, old) in renumbering.points.iter().enumerate() {
+            assert_eq!(mesh.points[new][0], tetgen.point(*old, 0));
+        }
+        assert!(mesh.cells.len() < tetgen.ntet());
+        for (new, old) in renumbering.cells.iter().enumerate() {
+            let xc: f64 = (0..4).map(|m| tetgen.point(tetgen.tet_node(*old, m), 0)).sum::<f64>() / 4.0;
+            assert!(xc <= 2.0);
+            for m in 0..4 {
+                assert_eq!(renumbering.points[mesh.cells[new][m]], tetgen.tet_node(*old, m));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{StrError, Tetgen, Triangle};
+
+/// Holds the corners of the unit cube
+pub(crate) const CUBE_POINTS: [[f64; 3]; 8] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 1.0, 1.0],
+    [0.0, 1.0, 1.0],
+];
+
+/// Holds the facets of the unit cube (counterclockwise when seen from outside)
+pub(crate) const CUBE_FACETS: [[usize; 4]; 6] = [
+    [0, 4, 7, 3],
+    [1, 2, 6, 5],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+];
+
+/// Returns the unit square given by four points and four segments
+pub(crate) fn square() -> Result<Triangle, StrError> {
+    let mut triangle = Triangle::new(4, Some(4), None, None)?;
+    triangle
+        .set_point(0, 0.0, 0.0)?
+        .set_point(1, 1.0, 0.0)?
+        .set_point(2, 1.0, 1.0)?
+        .set_point(3, 0.0, 1.0)?
+        .set_segment(0, 0, 1)?
+        .set_segment(1, 1, 2)?
+        .set_segment(2, 2, 3)?
+        .set_segment(3, 3, 0)?;
+    Ok(triangle)
+}
+
+/// Returns the unit cube with the points set but the six facets (four points each) not set yet
+pub(crate) fn cube_points() -> Result<Tetgen, StrError> {
+    let mut tetgen = Tetgen::new(8, Some(vec![4; 6]), None, None)?;
+    for (p, x) in CUBE_POINTS.iter().enumerate() {
+        tetgen.set_point(p, x[0], x[1], x[2])?;
+    }
+    Ok(tetgen)
+}
+
+/// Returns the unit cube given by eight points and six facets
+pub(crate) fn cube() -> Result<Tetgen, StrError> {
+    let mut tetgen = cube_points()?;
+    for (index, facet) in CUBE_FACETS.iter().enumerate() {
+        for (m, p) in facet.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+    }
+    Ok(tetgen)
+}
@@ -0,0 +1,559 @@
+use crate::triangle_shapes::point_in_polygon;
+use crate::{Geometry2d, StrError, Triangle};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+/// Holds the marker of the segments on the exterior rings of GeoJSON polygons
+pub const GEOJSON_EXTERIOR_MARKER: i32 = 1;
+
+/// Holds the marker of the segments on the interior rings (holes) of GeoJSON polygons
+pub const GEOJSON_INTERIOR_MARKER: i32 = 2;
+
+/// Holds the vertices of a ring (the first vertex is not repeated at the end)
+type Ring = Vec<(f64, f64)>;
+
+/// Holds a JSON value
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Returns the value of a key if this is an object
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+impl Triangle {
+    /// Allocates a new instance with the polygons of a GeoJSON text
+    ///
+    /// The format is described in [RFC 7946](https://datatracker.ietf.org/doc/html/rfc7946).
+    /// The Polygon and MultiPolygon geometries are read from a FeatureCollection, a Feature,
+    /// a GeometryCollection, or a bare geometry; the other geometries are not supported and
+    /// the properties are ignored. The third coordinate (altitude), if any, is ignored.
+    ///
+    /// The segments on the exterior rings have the marker [GEOJSON_EXTERIOR_MARKER] and the
+    /// segments on the interior rings have the marker [GEOJSON_INTERIOR_MARKER]. A hole seed
+    /// is computed automatically for each interior ring. The points closer to each other than
+    /// 1e-10 are merged (see [Geometry2d]). The returned Triangle is ready for `generate_mesh`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let text = r#"{
+    ///         "type": "Polygon",
+    ///         "coordinates": [
+    ///             [[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]],
+    ///             [[1, 1], [1, 3], [3, 3], [3, 1], [1, 1]]
+    ///         ]
+    ///     }"#;
+    ///     let triangle = Triangle::from_geojson(text)?;
+    ///     triangle.generate_mesh(false, false, Some(0.5), None)?;
+    ///     let area: f64 = triangle.to_mesh().measures().iter().sum();
+    ///     assert!(f64::abs(area - 12.0) < 1e-14);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_geojson(text: &str) -> Result<Triangle, StrError> {
+        let mut parser = Parser::new(text.as_bytes());
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err("the GeoJSON text has unexpected characters after the value");
+        }
+        let mut polygons = Vec::new();
+        collect_polygons(&value, &mut polygons)?;
+        if polygons.is_empty() {
+            return Err("the GeoJSON text has no polygons");
+        }
+        let mut geo = Geometry2d::new();
+        for polygon in &polygons {
+            geo.add_polygon(&polygon[0], GEOJSON_EXTERIOR_MARKER)?;
+            for ring in &polygon[1..] {
+                let (x, y) = hole_seed(ring, &polygons)?;
+                geo.add_polygon(ring, GEOJSON_INTERIOR_MARKER)?.add_hole(x, y);
+            }
+        }
+        geo.build()
+    }
+
+    /// Allocates a new instance with the polygons of a GeoJSON file
+    ///
+    /// See [Triangle::from_geojson] for the supported geometries and the markers.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_geojson_file<P>(full_path: &P) -> Result<Triangle, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let contents = fs::read_to_string(Path::new(full_path)).map_err(|_| "cannot read file")?;
+        Triangle::from_geojson(&contents)
+    }
+}
+
+/// Collects the polygons (exterior ring followed by the interior rings) of a GeoJSON object
+fn collect_polygons(value: &Json, polygons: &mut Vec<Vec<Ring>>) -> Result<(), StrError> {
+    let members = |key: &str| match value.get(key) {
+        Some(Json::Array(items)) => Ok(items),
+        _ => Err("the GeoJSON object has a missing or invalid member"),
+    };
+    match value.get("type") {
+        Some(Json::String(kind)) => match kind.as_str() {
+            "FeatureCollection" => {
+                for feature in members("features")? {
+                    collect_polygons(feature, polygons)?;
+                }
+            }
+            "Feature" => match value.get("geometry") {
+                Some(Json::Null) => (),
+                Some(geometry) => collect_polygons(geometry, polygons)?,
+                None => return Err("the GeoJSON object has a missing or invalid member"),
+            },
+            "GeometryCollection" => {
+                for geometry in members("geometries")? {
+                    collect_polygons(geometry, polygons)?;
+                }
+            }
+            "Polygon" => polygons.push(parse_polygon(value.get("coordinates"))?),
+            "MultiPolygon" => {
+                for coordinates in members("coordinates")? {
+                    polygons.push(parse_polygon(Some(coordinates))?);
+                }
+            }
+            _ => return Err("only Polygon and MultiPolygon geometries are supported"),
+        },
+        _ => return Err("the GeoJSON object must have a type"),
+    }
+    Ok(())
+}
+
+/// Parses the coordinates of a polygon
+fn parse_polygon(coordinates: Option<&Json>) -> Result<Vec<Ring>, StrError> {
+    let rings = match coordinates {
+        Some(Json::Array(rings)) if !rings.is_empty() => rings,
+        _ => return Err("the coordinates of a polygon must be a non-empty array of rings"),
+    };
+    let mut polygon = Vec::with_capacity(rings.len());
+    for ring in rings {
+        let positions = match ring {
+            Json::Array(positions) => positions,
+            _ => return Err("the coordinates of a polygon must be a non-empty array of rings"),
+        };
+        let mut vertices = Vec::with_capacity(positions.len());
+        for position in positions {
+            match position {
+                Json::Array(values) if values.len() >= 2 => match (&values[0], &values[1]) {
+                    (Json::Number(x), Json::Number(y)) => vertices.push((*x, *y)),
+                    _ => return Err("a position must have at least two numbers"),
+                },
+                _ => return Err("a position must have at least two numbers"),
+            }
+        }
+        if vertices.len() < 4 || vertices.first() != vertices.last() {
+            return Err("a ring must be closed and have at least four positions");
+        }
+        vertices.pop();
+        polygon.push(vertices);
+    }
+    Ok(polygon)
+}
+
+/// Computes a point inside a ring and outside the given polygons (e.g., inside an interior ring and outside the domain)
+///
+/// The horizontal line between two y-coordinates of the ring (thus not crossing its vertices)
+/// is intersected with the ring and all polygons; then, the midpoint of the widest interval
+/// that is inside the ring but outside all polygons (e.g., islands within the hole) is selected.
+pub(crate) fn hole_seed(ring: &[(f64, f64)], polygons: &[Vec<Ring>]) -> Result<(f64, f64), StrError> {
+    let mut ys: Vec<f64> = ring.iter().map(|(_, y)| *y).collect();
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+    if ys.len() < 2 {
+        return Err("cannot find a seed point inside an interior ring");
+    }
+    let k = ys.len() / 2;
+    let y = (ys[k - 1] + ys[k]) / 2.0;
+    let mut xs = Vec::new();
+    for other in std::iter::once(ring).chain(polygons.iter().flatten().map(|other| other.as_slice())) {
+        for i in 0..other.len() {
+            let (a, b) = (other[i], other[(i + 1) % other.len()]);
+            if (a.1 < y) != (b.1 < y) {
+                xs.push(a.0 + (y - a.1) * (b.0 - a.0) / (b.1 - a.1));
+            }
+        }
+    }
+    xs.sort_by(f64::total_cmp);
+    let in_domain = |x: f64| {
+        polygons.iter().any(|polygon| {
+            point_in_polygon(&polygon[0], x, y) && !polygon[1..].iter().any(|hole| point_in_polygon(hole, x, y))
+        })
+    };
+    let mut best: Option<(f64, f64)> = None; // (width, x)
+    for pair in xs.windows(2) {
+        let (width, x) = (pair[1] - pair[0], (pair[0] + pair[1]) / 2.0);
+        if width > 0.0 && point_in_polygon(ring, x, y) && !in_domain(x) && best.is_none_or(|(w, _)| width > w) {
+            best = Some((width, x));
+        }
+    }
+    match best {
+        Some((_, x)) => Ok((x, y)),
+        None => Err("cannot find a seed point inside an interior ring"),
+    }
+}
+
+/// Defines the maximum nesting depth of arrays and objects in a GeoJSON text
+///
+/// GeoJSON needs about five levels (e.g., a FeatureCollection of MultiPolygons); the limit
+/// prevents deeply nested (malicious) texts from overflowing the stack of the recursive parser.
+const GEOJSON_MAX_DEPTH: usize = 64;
+
+/// Implements a parser of JSON texts
+struct Parser<'a> {
+    bytes: &'a [u8], // the text
+    pos: usize,      // the current position
+    depth: usize,    // the current nesting depth of arrays and objects
+}
+
+impl<'a> Parser<'a> {
+    /// Allocates a new instance
+    fn new(bytes: &'a [u8]) -> Self {
+        Parser {
+            bytes,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    /// Skips the whitespace characters
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && matches!(self.bytes[self.pos], b' ' | b'\t' | b'\n' | b'\r') {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes the expected literal
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, StrError> {
+        if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err("the GeoJSON text is not valid JSON")
+        }
+    }
+
+    /// Parses a value
+    fn value(&mut self) -> Result<Json, StrError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{' | b'[') => {
+                if self.depth >= GEOJSON_MAX_DEPTH {
+                    return Err("the GeoJSON text is nested too deeply");
+                }
+                self.depth += 1;
+                let value = if self.bytes[self.pos] == b'{' {
+                    self.object()
+                } else {
+                    self.array()
+                };
+                self.depth -= 1;
+                value
+            }
+            Some(b'"') => Ok(Json::String(self.string()?)),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err("the GeoJSON text is not valid JSON"),
+        }
+    }
+
+    /// Parses an object
+    fn object(&mut self) -> Result<Json, StrError> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) != Some(&b'"') {
+                return Err("the GeoJSON text is not valid JSON");
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) != Some(&b':') {
+                return Err("the GeoJSON text is not valid JSON");
+            }
+            self.pos += 1;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(members));
+                }
+                _ => return Err("the GeoJSON text is not valid JSON"),
+            }
+        }
+    }
+
+    /// Parses an array
+    fn array(&mut self) -> Result<Json, StrError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err("the GeoJSON text is not valid JSON"),
+            }
+        }
+    }
+
+    /// Parses a string (the current character is the opening quote)
+    fn string(&mut self) -> Result<String, StrError> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return String::from_utf8(bytes).map_err(|_| "the GeoJSON text is not valid JSON");
+                }
+                Some(b'\\') => {
+                    let escaped = match self.bytes.get(self.pos + 1) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let hex = self.bytes.get(self.pos + 2..self.pos + 6).unwrap_or_default();
+                            let code = std::str::from_utf8(hex)
+                                .ok()
+                                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                                .ok_or("the GeoJSON text is not valid JSON")?;
+                            self.pos += 4;
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => return Err("the GeoJSON text is not valid JSON"),
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                    self.pos += 2;
+                }
+                Some(byte) => {
+                    bytes.push(*byte);
+                    self.pos += 1;
+                }
+                None => return Err("the GeoJSON text is not valid JSON"),
+            }
+        }
+    }
+
+    /// Parses a number
+    fn number(&mut self) -> Result<Json, StrError> {
+        let start = self.pos;
+        while self.pos < self.bytes.len()
+            && matches!(self.bytes[self.pos], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        {
+            self.pos += 1;
+        }
+        let text =
+            std::str::from_utf8(&self.bytes[start..self.pos]).map_err(|_| "the GeoJSON text is not valid JSON")?;
+        let value: f64 = text.parse().map_err(|_| "the GeoJSON text is not valid JSON")?;
+        Ok(Json::Number(value))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Json, Parser, GEOJSON_EXTERIOR_MARKER, GEOJSON_INTERIOR_MARKER, GEOJSON_MAX_DEPTH};
+    use crate::{StrError, Triangle};
+    use std::fs;
+
+    fn area(triangle: &Triangle) -> f64 {
+        triangle.to_mesh().measures().iter().sum()
+    }
+
+    #[test]
+    fn parser_works() -> Result<(), StrError> {
+        let mut parser = Parser::new(br#" {"a": [1, -2.5e1, true, false, null], "b\nA": {}} "#);
+        assert_eq!(
+            parser.value()?,
+            Json::Object(vec![
+                (
+                    "a".to_string(),
+                    Json::Array(vec![
+                        Json::Number(1.0),
+                        Json::Number(-25.0),
+                        Json::Bool(true),
+                        Json::Bool(false),
+                        Json::Null
+                    ])
+                ),
+                ("b\nA".to_string(), Json::Object(Vec::new())),
+            ])
+        );
+        for text in ["", "[1,", "{\"a\" 1}", "[1 2]", "tru", "\"abc", "{1: 2}", "-"] {
+            let mut parser = Parser::new(text.as_bytes());
+            assert_eq!(parser.value().err(), Some("the GeoJSON text is not valid JSON"));
+        }
+
+        // deeply nested texts are rejected instead of overflowing the stack
+        let nested = format!("{}{}", "[".repeat(GEOJSON_MAX_DEPTH), "]".repeat(GEOJSON_MAX_DEPTH));
+        assert!(Parser::new(nested.as_bytes()).value().is_ok());
+        let nested = format!(
+            "{}{}",
+            "[".repeat(GEOJSON_MAX_DEPTH + 1),
+            "]".repeat(GEOJSON_MAX_DEPTH + 1)
+        );
+        assert_eq!(
+            Parser::new(nested.as_bytes()).value().err(),
+            Some("the GeoJSON text is nested too deeply")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_geojson_captures_errors() {
+        assert_eq!(
+            Triangle::from_geojson(&"[".repeat(200000)).err(),
+            Some("the GeoJSON text is nested too deeply")
+        );
+        assert_eq!(
+            Triangle::from_geojson(&"{\"a\":".repeat(200000)).err(),
+            Some("the GeoJSON text is nested too deeply")
+        );
+        assert_eq!(
+            Triangle::from_geojson("[] 1").err(),
+            Some("the GeoJSON text has unexpected characters after the value")
+        );
+        assert_eq!(
+            Triangle::from_geojson("[]").err(),
+            Some("the GeoJSON object must have a type")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "Point", "coordinates": [0, 0]}"#).err(),
+            Some("only Polygon and MultiPolygon geometries are supported")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "FeatureCollection"}"#).err(),
+            Some("the GeoJSON object has a missing or invalid member")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "FeatureCollection", "features": []}"#).err(),
+            Some("the GeoJSON text has no polygons")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "Polygon", "coordinates": []}"#).err(),
+            Some("the coordinates of a polygon must be a non-empty array of rings")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "Polygon", "coordinates": [[[0, 0], [1], [1, 1], [0, 0]]]}"#).err(),
+            Some("a position must have at least two numbers")
+        );
+        assert_eq!(
+            Triangle::from_geojson(r#"{"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]]}"#).err(),
+            Some("a ring must be closed and have at least four positions")
+        );
+    }
+
+    #[test]
+    fn from_geojson_works() -> Result<(), StrError> {
+        // an L-shaped polygon with a triangular hole and a square polygon with a square hole
+        // and an island inside the hole
+        let text = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {"name": "L-shape"},
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [
+                            [[0, 0], [4, 0], [4, 2], [2, 2], [2, 4], [0, 4], [0, 0]],
+                            [[0.5, 0.5], [1.5, 0.5], [0.5, 1.5], [0.5, 0.5]]
+                        ]
+                    }
+                },
+                {"type": "Feature", "properties": null, "geometry": null},
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": {
+                        "type": "MultiPolygon",
+                        "coordinates": [
+                            [
+                                [[10, 0, 5], [16, 0, 5], [16, 6, 5], [10, 6, 5], [10, 0, 5]],
+                                [[11, 1], [11, 5], [15, 5], [15, 1], [11, 1]]
+                            ],
+                            [
+                                [[12, 2], [14, 2], [14, 4], [12, 4], [12, 2]]
+                            ]
+                        ]
+                    }
+                }
+            ]
+        }"#;
+        let triangle = Triangle::from_geojson(text)?;
+        triangle.generate_mesh(false, false, Some(0.25), Some(20.0))?;
+        let expected = (12.0 - 0.5) + (36.0 - 16.0) + 4.0;
+        assert!(f64::abs(area(&triangle) - expected) < 1e-13);
+        let mut markers: Vec<i32> = (0..triangle.nedge())
+            .map(|e| triangle.edge_marker(e))
+            .filter(|m| *m != 0)
+            .collect();
+        markers.sort();
+        markers.dedup();
+        assert_eq!(markers, &[GEOJSON_EXTERIOR_MARKER, GEOJSON_INTERIOR_MARKER]);
+        Ok(())
+    }
+
+    #[test]
+    fn from_geojson_file_works() -> Result<(), StrError> {
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        let text = r#"{"type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 1], [0, 1], [0, 0]]]}"#;
+        fs::write("/tmp/tritet/test_from_geojson.geojson", text).map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Triangle::from_geojson_file("/tmp/tritet/__does_not_exist__.geojson").err(),
+            Some("cannot read file")
+        );
+        let triangle = Triangle::from_geojson_file("/tmp/tritet/test_from_geojson.geojson")?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert!(f64::abs(area(&triangle) - 2.0) < 1e-15);
+        Ok(())
+    }
+}
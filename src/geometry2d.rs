@@ -0,0 +1,504 @@
+use crate::simplify::simplify_paths;
+use crate::{StrError, Triangle};
+use std::f64::consts::PI;
+
+/// Assists in the definition of a Planar Straight Line Graph (PSLG) from polygons, circles, and arcs
+///
+/// The points are numbered automatically and the points closer to each other than a tolerance
+/// (see `set_tolerance`) are merged; thus, shapes touching each other (e.g., an arc ending at the
+/// corner of a polygon) share their points. The function `build` allocates a [Triangle] with the
+/// number of points, segments, regions, and holes, and sets all of them.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{Geometry2d, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut geo = Geometry2d::new();
+///     geo.add_polygon(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)], -1)?
+///         .add_circle((2.0, 2.0), 1.0, 16, -2)?
+///         .add_hole(2.0, 2.0)
+///         .add_region(0.5, 0.5, 1, None);
+///     assert_eq!(geo.npoint(), 20);
+///     assert_eq!(geo.nsegment(), 20);
+///     let triangle = geo.build()?;
+///     triangle.generate_mesh(false, false, Some(0.5), None)?;
+///     assert!((0..triangle.ntriangle()).all(|t| triangle.triangle_attribute(t) == 1));
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Geometry2d {
+    points: Vec<(f64, f64)>,                    // coordinates of the points
+    segments: Vec<(usize, usize, i32)>,         // points and marker of the segments
+    regions: Vec<(f64, f64, i32, Option<f64>)>, // coordinates, attribute, and maximum area of the regions
+    holes: Vec<(f64, f64)>,                     // coordinates of the holes
+    tolerance: f64,                             // tolerance to merge points
+}
+
+impl Geometry2d {
+    /// Allocates a new (empty) instance
+    pub fn new() -> Self {
+        Geometry2d {
+            points: Vec::new(),
+            segments: Vec::new(),
+            regions: Vec::new(),
+            holes: Vec::new(),
+            tolerance: 1e-10,
+        }
+    }
+
+    /// Sets the tolerance to merge points (the default is 1e-10)
+    ///
+    /// Only the points added afterwards are affected.
+    pub fn set_tolerance(&mut self, value: f64) -> &mut Self {
+        self.tolerance = value;
+        self
+    }
+
+    /// Returns the number of points
+    pub fn npoint(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the number of segments
+    pub fn nsegment(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Returns the number of regions
+    pub fn nregion(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns the number of holes
+    pub fn nhole(&self) -> usize {
+        self.holes.len()
+    }
+
+    /// Adds a closed polygon
+    ///
+    /// # Input
+    ///
+    /// * `points` -- the coordinates of the vertices (at least three; the first one is not repeated at the end)
+    /// * `marker` -- the marker of the segments (see `Triangle::set_segment_marker`)
+    pub fn add_polygon(&mut self, points: &[(f64, f64)], marker: i32) -> Result<&mut Self, StrError> {
+        if points.len() < 3 {
+            return Err("the polygon must have at least 3 points");
+        }
+        self.add_path(points, true, marker);
+        Ok(self)
+    }
+
+    /// Adds a circle discretized by a regular polygon
+    ///
+    /// # Input
+    ///
+    /// * `center` -- the coordinates of the center
+    /// * `radius` -- the radius (> 0)
+    /// * `n` -- the number of segments (≥ 3); the first point is at angle zero
+    /// * `marker` -- the marker of the segments (see `Triangle::set_segment_marker`)
+    pub fn add_circle(
+        &mut self,
+        center: (f64, f64),
+        radius: f64,
+        n: usize,
+        marker: i32,
+    ) -> Result<&mut Self, StrError> {
+        if radius <= 0.0 {
+            return Err("the radius must be positive");
+        }
+        if n < 3 {
+            return Err("the circle must have at least 3 segments");
+        }
+        let points: Vec<_> = (0..n)
+            .map(|i| polar(center, radius, 2.0 * PI * (i as f64) / (n as f64)))
+            .collect();
+        self.add_path(&points, true, marker);
+        Ok(self)
+    }
+
+    /// Adds an arc of circle discretized by segments
+    ///
+    /// The arc goes counterclockwise from `start_angle` to `end_angle` if `end_angle > start_angle`
+    /// and clockwise otherwise. The arc is open; thus, it is usually combined with other shapes
+    /// sharing its end points (e.g., `add_polyline`).
+    ///
+    /// # Input
+    ///
+    /// * `center` -- the coordinates of the center
+    /// * `radius` -- the radius (> 0)
+    /// * `start_angle` -- the angle (in radians) of the first point
+    /// * `end_angle` -- the angle (in radians) of the last point
+    /// * `n` -- the number of segments (≥ 1)
+    /// * `marker` -- the marker of the segments (see `Triangle::set_segment_marker`)
+    pub fn add_arc(
+        &mut self,
+        center: (f64, f64),
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        n: usize,
+        marker: i32,
+    ) -> Result<&mut Self, StrError> {
+        if radius <= 0.0 {
+            return Err("the radius must be positive");
+        }
+        if n < 1 {
+            return Err("the arc must have at least 1 segment");
+        }
+        if start_angle == end_angle {
+            return Err("the start and end angles of the arc must be different");
+        }
+        let points: Vec<_> = (0..(n + 1))
+            .map(|i| {
+                polar(
+                    center,
+                    radius,
+                    start_angle + (end_angle - start_angle) * (i as f64) / (n as f64),
+                )
+            })
+            .collect();
+        self.add_path(&points, false, marker);
+        Ok(self)
+    }
+
+    /// Adds an open polyline
+    ///
+    /// # Input
+    ///
+    /// * `points` -- the coordinates of the vertices (at least two)
+    /// * `marker` -- the marker of the segments (see `Triangle::set_segment_marker`)
+    pub fn add_polyline(&mut self, points: &[(f64, f64)], marker: i32) -> Result<&mut Self, StrError> {
+        if points.len() < 2 {
+            return Err("the polyline must have at least 2 points");
+        }
+        self.add_path(points, false, marker);
+        Ok(self)
+    }
+
+    /// Adds a hole (see `Triangle::set_hole`)
+    pub fn add_hole(&mut self, x: f64, y: f64) -> &mut Self {
+        self.holes.push((x, y));
+        self
+    }
+
+    /// Adds a region (see `Triangle::set_region`)
+    pub fn add_region(&mut self, x: f64, y: f64, attribute: i32, max_area: Option<f64>) -> &mut Self {
+        self.regions.push((x, y, attribute, max_area));
+        self
+    }
+
+    /// Simplifies the polylines formed by the segments using the Douglas-Peucker algorithm
+    ///
+    /// The segments are split into chains at the points shared by more than two segments, at the
+    /// ends of open paths, and where the marker changes; these points are kept. The other points
+    /// farther than `tolerance` from the simplified chains are kept as well. The topology is
+    /// preserved: the simplified segments do not intersect each other and no point is moved to
+    /// the other side of a segment (see `simplify_polylines`).
+    ///
+    /// Returns the removed point IDs, as numbered before the simplification. The remaining points
+    /// are renumbered in their original order.
+    pub fn simplify_segments(&mut self, tolerance: f64) -> Result<Vec<usize>, StrError> {
+        // segments of each point
+        let npoint = self.points.len();
+        let mut point_segments = vec![Vec::new(); npoint];
+        for (index, (a, b, _)) in self.segments.iter().enumerate() {
+            point_segments[*a].push(index);
+            point_segments[*b].push(index);
+        }
+        let other = |segment: usize, point: usize| {
+            let (a, b, _) = self.segments[segment];
+            if a == point {
+                b
+            } else {
+                a
+            }
+        };
+
+        // points in the interior of chains
+        let interior: Vec<bool> = (0..npoint)
+            .map(|p| match point_segments[p].as_slice() {
+                [s, t] => {
+                    self.segments[*s].2 == self.segments[*t].2 && other(*s, p) != other(*t, p) && other(*s, p) != p
+                }
+                _ => false,
+            })
+            .collect();
+
+        // chains of point IDs (open chains start at a fixed point; the others are rings)
+        let mut chains: Vec<(Vec<usize>, bool, i32)> = Vec::new();
+        let mut visited = vec![false; self.segments.len()];
+        let starts = (0..npoint).filter(|p| !interior[*p]).chain(0..npoint);
+        for start in starts {
+            for first in point_segments[start].clone() {
+                if visited[first] {
+                    continue;
+                }
+                let mut chain = vec![start];
+                let (mut point, mut segment) = (start, first);
+                loop {
+                    visited[segment] = true;
+                    point = other(segment, point);
+                    if point == start {
+                        break;
+                    }
+                    chain.push(point);
+                    if !interior[point] {
+                        break;
+                    }
+                    let [s, t] = [point_segments[point][0], point_segments[point][1]];
+                    segment = if s == segment { t } else { s };
+                }
+                let closed = point == start;
+                chains.push((chain, closed, self.segments[first].2));
+            }
+        }
+
+        // simplify
+        let polylines: Vec<Vec<(f64, f64)>> = chains
+            .iter()
+            .map(|(chain, _, _)| chain.iter().map(|p| self.points[*p]).collect())
+            .collect();
+        let closed: Vec<bool> = chains.iter().map(|(_, closed, _)| *closed).collect();
+        let simplified = simplify_paths(&polylines, &closed, tolerance)?;
+
+        // renumber the points and rebuild the segments
+        let mut removed = vec![false; npoint];
+        for ((chain, _, _), res) in chains.iter().zip(&simplified) {
+            for i in &res.removed {
+                removed[chain[*i]] = true;
+            }
+        }
+        let mut new_id = vec![usize::MAX; npoint];
+        let mut points = Vec::new();
+        for p in 0..npoint {
+            if !removed[p] {
+                new_id[p] = points.len();
+                points.push(self.points[p]);
+            }
+        }
+        let mut segments = Vec::new();
+        for ((chain, closed, marker), res) in chains.iter().zip(&simplified) {
+            let ids: Vec<usize> = res.kept.iter().map(|i| new_id[chain[*i]]).collect();
+            let nsegment = if *closed { ids.len() } else { ids.len() - 1 };
+            for i in 0..nsegment {
+                segments.push((ids[i], ids[(i + 1) % ids.len()], *marker));
+            }
+        }
+        self.points = points;
+        self.segments = segments;
+        Ok((0..npoint).filter(|p| removed[*p]).collect())
+    }
+
+    /// Allocates a new Triangle with the points, segments, regions, and holes
+    ///
+    /// The returned Triangle is ready for `generate_mesh`.
+    pub fn build(&self) -> Result<Triangle, StrError> {
+        let option = |n: usize| if n > 0 { Some(n) } else { None };
+        let mut triangle = Triangle::new(
+            self.points.len(),
+            option(self.segments.len()),
+            option(self.regions.len()),
+            option(self.holes.len()),
+        )?;
+        for (index, (x, y)) in self.points.iter().enumerate() {
+            triangle.set_point(index, *x, *y)?;
+        }
+        for (index, (a, b, marker)) in self.segments.iter().enumerate() {
+            triangle.set_segment(index, *a, *b)?;
+            if *marker != 0 {
+                triangle.set_segment_marker(index, *marker)?;
+            }
+        }
+        for (index, (x, y, attribute, max_area)) in self.regions.iter().enumerate() {
+            triangle.set_region(index, *x, *y, *attribute, *max_area)?;
+        }
+        for (index, (x, y)) in self.holes.iter().enumerate() {
+            triangle.set_hole(index, *x, *y)?;
+        }
+        Ok(triangle)
+    }
+
+    /// Adds the points and the segments connecting consecutive points
+    fn add_path(&mut self, points: &[(f64, f64)], closed: bool, marker: i32) {
+        let ids: Vec<_> = points.iter().map(|(x, y)| self.point_id(*x, *y)).collect();
+        let nsegment = if closed { ids.len() } else { ids.len() - 1 };
+        for i in 0..nsegment {
+            let (a, b) = (ids[i], ids[(i + 1) % ids.len()]);
+            if a != b {
+                self.segments.push((a, b, marker));
+            }
+        }
+    }
+
+    /// Returns the ID of a point, adding it if not present yet
+    fn point_id(&mut self, x: f64, y: f64) -> usize {
+        let tol = self.tolerance;
+        match self
+            .points
+            .iter()
+            .position(|(a, b)| f64::abs(a - x) <= tol && f64::abs(b - y) <= tol)
+        {
+            Some(id) => id,
+            None => {
+                self.points.push((x, y));
+                self.points.len() - 1
+            }
+        }
+    }
+}
+
+impl Default for Geometry2d {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the coordinates of a point given in polar coordinates
+fn polar(center: (f64, f64), radius: f64, angle: f64) -> (f64, f64) {
+    (center.0 + radius * f64::cos(angle), center.1 + radius * f64::sin(angle))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Geometry2d;
+    use crate::StrError;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn add_functions_capture_errors() {
+        let mut geo = Geometry2d::new();
+        assert_eq!(
+            geo.add_polygon(&[(0.0, 0.0), (1.0, 0.0)], 0).err(),
+            Some("the polygon must have at least 3 points")
+        );
+        assert_eq!(
+            geo.add_circle((0.0, 0.0), 0.0, 8, 0).err(),
+            Some("the radius must be positive")
+        );
+        assert_eq!(
+            geo.add_circle((0.0, 0.0), 1.0, 2, 0).err(),
+            Some("the circle must have at least 3 segments")
+        );
+        assert_eq!(
+            geo.add_arc((0.0, 0.0), -1.0, 0.0, PI, 4, 0).err(),
+            Some("the radius must be positive")
+        );
+        assert_eq!(
+            geo.add_arc((0.0, 0.0), 1.0, 0.0, PI, 0, 0).err(),
+            Some("the arc must have at least 1 segment")
+        );
+        assert_eq!(
+            geo.add_arc((0.0, 0.0), 1.0, PI, PI, 4, 0).err(),
+            Some("the start and end angles of the arc must be different")
+        );
+        assert_eq!(
+            geo.add_polyline(&[(0.0, 0.0)], 0).err(),
+            Some("the polyline must have at least 2 points")
+        );
+        assert_eq!(geo.npoint(), 0);
+        assert_eq!(geo.build().err(), Some("npoint must be ≥ 3"));
+    }
+
+    #[test]
+    fn build_works() -> Result<(), StrError> {
+        let mut geo = Geometry2d::new();
+        geo.add_polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)], -10)?
+            .add_polyline(&[(1.0, 0.0), (1.0, 1.0)], -20)?
+            .add_region(0.5, 0.5, 1, None)
+            .add_region(1.5, 0.5, -2, None);
+        assert_eq!(geo.npoint(), 6);
+        assert_eq!(geo.nsegment(), 5);
+        assert_eq!(geo.nregion(), 2);
+        assert_eq!(geo.nhole(), 0);
+        let triangle = geo.build()?;
+        assert_eq!(triangle.input_segment(4), (4, 5));
+        triangle.generate_mesh(false, false, None, None)?;
+        let mut n_left = 0;
+        for t in 0..triangle.ntriangle() {
+            let xc: f64 = (0..3)
+                .map(|m| triangle.point(triangle.triangle_node(t, m), 0))
+                .sum::<f64>()
+                / 3.0;
+            if xc < 1.0 {
+                n_left += 1;
+                assert_eq!(triangle.triangle_attribute(t), 1);
+            } else {
+                assert_eq!(triangle.triangle_attribute(t), -2);
+            }
+        }
+        assert_eq!(n_left, 2);
+        assert_eq!(triangle.ntriangle(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn arcs_share_points() -> Result<(), StrError> {
+        // half disk: the ends of the diameter are merged with the ends of the arc
+        let mut geo = Geometry2d::new();
+        geo.add_arc((0.0, 0.0), 1.0, 0.0, PI, 8, -1)?
+            .add_polyline(&[(-1.0, 0.0), (1.0, 0.0)], -2)?;
+        assert_eq!(geo.npoint(), 9);
+        assert_eq!(geo.nsegment(), 9);
+        let triangle = geo.build()?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let area: f64 = (0..triangle.ntriangle())
+            .map(|t| triangle.triangle_quality(t).unwrap().area)
+            .sum();
+        let polygon_area = 8.0 * 0.5 * f64::sin(PI / 8.0);
+        assert!(f64::abs(area - polygon_area) < 1e-14);
+
+        // with zero tolerance, the rounding errors of the arc prevent the merging
+        let mut geo = Geometry2d::new();
+        geo.set_tolerance(0.0)
+            .add_arc((0.0, 0.0), 1.0, 0.0, PI, 8, -1)?
+            .add_polyline(&[(-1.0, 0.0), (1.0, 0.0)], -2)?;
+        assert_eq!(geo.npoint(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn simplify_segments_works() -> Result<(), StrError> {
+        // dense square boundary split by a dense vertical line with another marker
+        let bump = |i: usize| if i % 2 == 1 && i != 5 { 0.001 } else { 0.0 };
+        let mut ring: Vec<_> = (0..=10).map(|i| (0.2 * (i as f64), bump(i))).collect();
+        ring.extend((0..=10).map(|i| (2.0 - 0.2 * (i as f64), 1.0)));
+        let line: Vec<_> = (0..=10).map(|i| (1.0, 0.1 * (i as f64))).collect();
+        let mut geo = Geometry2d::new();
+        geo.add_polygon(&ring, -1)?.add_polyline(&line, -2)?;
+        assert_eq!(geo.npoint(), 31);
+        assert_eq!(geo.nsegment(), 32);
+        assert_eq!(
+            geo.simplify_segments(-1.0).err(),
+            Some("the tolerance must be non-negative")
+        );
+        let removed = geo.simplify_segments(0.01)?;
+        assert_eq!(removed.len(), 25);
+        assert!(!removed.contains(&0) && !removed.contains(&5) && !removed.contains(&10));
+        assert_eq!(geo.npoint(), 6);
+        assert_eq!(geo.nsegment(), 7);
+        let triangle = geo.build()?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let area: f64 = (0..triangle.ntriangle())
+            .map(|t| triangle.triangle_quality(t).unwrap().area)
+            .sum();
+        assert!(f64::abs(area - 2.0) < 1e-14);
+        assert_eq!(
+            (0..triangle.nedge()).filter(|e| triangle.edge_marker(*e) == -2).count(),
+            1
+        );
+
+        // a dense circle keeps at least three points
+        let mut geo = Geometry2d::new();
+        geo.add_circle((0.0, 0.0), 1.0, 64, -1)?;
+        let removed = geo.simplify_segments(10.0)?;
+        assert_eq!(removed.len(), 61);
+        assert_eq!(geo.npoint(), 3);
+        assert_eq!(geo.nsegment(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,560 @@
+use crate::vector::{dot, norm, triangle_normal};
+use crate::{StrError, Tetgen};
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+/// Assists in the definition of a Piecewise Linear Complex (PLC) from boxes, cylinders, spheres, and extrusions
+///
+/// The points are numbered automatically and the points closer to each other than a tolerance
+/// (see `set_tolerance`) are merged. Moreover, a facet with the same points as a previous facet
+/// is skipped (the marker of the first one is kept); thus, solids touching each other (e.g., two
+/// boxes sharing a face) can be combined to define regions with different attributes.
+///
+/// The facets of the primitives are ordered counterclockwise when seen from the outside; i.e.,
+/// with the normal vectors pointing outward. The function `build` allocates a [Tetgen] with the
+/// number of points, facets, regions, and holes, and sets all of them.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{Geometry3d, StrError};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut geo = Geometry3d::new();
+///     geo.add_box((0.0, 0.0, 0.0), (1.0, 1.0, 1.0), -1)?
+///         .add_box((1.0, 0.0, 0.0), (2.0, 1.0, 1.0), -2)?
+///         .add_region(0.5, 0.5, 0.5, 1, None)
+///         .add_region(1.5, 0.5, 0.5, 2, None);
+///     assert_eq!(geo.npoint(), 12);
+///     assert_eq!(geo.nfacet(), 11);
+///     let tetgen = geo.build()?;
+///     tetgen.generate_mesh(false, false, None, None)?;
+///     assert!((0..tetgen.ntet()).all(|t| tetgen.tet_attribute(t) > 0));
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Geometry3d {
+    points: Vec<[f64; 3]>,                      // coordinates of the points
+    facets: Vec<(Vec<usize>, i32)>,             // points and marker of the facets
+    regions: Vec<([f64; 3], i32, Option<f64>)>, // coordinates, attribute, and maximum volume of the regions
+    holes: Vec<[f64; 3]>,                       // coordinates of the holes
+    facet_keys: HashSet<Vec<usize>>,            // sorted points of the facets (to skip repeated facets)
+    tolerance: f64,                             // tolerance to merge points
+}
+
+impl Geometry3d {
+    /// Allocates a new (empty) instance
+    pub fn new() -> Self {
+        Geometry3d {
+            points: Vec::new(),
+            facets: Vec::new(),
+            regions: Vec::new(),
+            holes: Vec::new(),
+            facet_keys: HashSet::new(),
+            tolerance: 1e-10,
+        }
+    }
+
+    /// Sets the tolerance to merge points (the default is 1e-10)
+    ///
+    /// Only the points added afterwards are affected.
+    pub fn set_tolerance(&mut self, value: f64) -> &mut Self {
+        self.tolerance = value;
+        self
+    }
+
+    /// Returns the number of points
+    pub fn npoint(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the number of facets
+    pub fn nfacet(&self) -> usize {
+        self.facets.len()
+    }
+
+    /// Returns the number of regions
+    pub fn nregion(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns the number of holes
+    pub fn nhole(&self) -> usize {
+        self.holes.len()
+    }
+
+    /// Adds a planar facet
+    ///
+    /// # Input
+    ///
+    /// * `points` -- the coordinates of the vertices (at least three; the first one is not repeated at the end)
+    /// * `marker` -- the marker of the facet (see `Tetgen::set_facet_marker`)
+    pub fn add_facet(&mut self, points: &[(f64, f64, f64)], marker: i32) -> Result<&mut Self, StrError> {
+        if points.len() < 3 {
+            return Err("the facet must have at least 3 points");
+        }
+        let points: Vec<_> = points.iter().map(|(x, y, z)| [*x, *y, *z]).collect();
+        self.push_facet(&points, marker);
+        Ok(self)
+    }
+
+    /// Adds an axis-aligned box
+    ///
+    /// # Input
+    ///
+    /// * `min` -- the coordinates of the corner with the smallest coordinates
+    /// * `max` -- the coordinates of the corner with the largest coordinates
+    /// * `marker` -- the marker of the six facets (see `Tetgen::set_facet_marker`)
+    pub fn add_box(&mut self, min: (f64, f64, f64), max: (f64, f64, f64), marker: i32) -> Result<&mut Self, StrError> {
+        if max.0 <= min.0 || max.1 <= min.1 || max.2 <= min.2 {
+            return Err("the max coordinates of the box must be greater than the min coordinates");
+        }
+        let bottom = [(min.0, min.1), (max.0, min.1), (max.0, max.1), (min.0, max.1)];
+        self.push_prism(&bottom, min.2, max.2, marker);
+        Ok(self)
+    }
+
+    /// Adds a cylinder with axis parallel to z, discretized by a prism with a regular polygonal base
+    ///
+    /// # Input
+    ///
+    /// * `center` -- the coordinates of the center of the bottom face
+    /// * `radius` -- the radius (> 0)
+    /// * `height` -- the height (> 0)
+    /// * `n_segments` -- the number of sides of the polygonal base (≥ 3)
+    /// * `marker` -- the marker of all facets (see `Tetgen::set_facet_marker`)
+    pub fn add_cylinder(
+        &mut self,
+        center: (f64, f64, f64),
+        radius: f64,
+        height: f64,
+        n_segments: usize,
+        marker: i32,
+    ) -> Result<&mut Self, StrError> {
+        if radius <= 0.0 {
+            return Err("the radius must be positive");
+        }
+        if height <= 0.0 {
+            return Err("the height must be positive");
+        }
+        if n_segments < 3 {
+            return Err("the cylinder must have at least 3 segments");
+        }
+        let bottom: Vec<_> = (0..n_segments)
+            .map(|i| {
+                let angle = 2.0 * PI * (i as f64) / (n_segments as f64);
+                (center.0 + radius * f64::cos(angle), center.1 + radius * f64::sin(angle))
+            })
+            .collect();
+        self.push_prism(&bottom, center.2, center.2 + height, marker);
+        Ok(self)
+    }
+
+    /// Adds a sphere discretized by a subdivided icosahedron
+    ///
+    /// Each subdivision splits each triangle into four; thus, the sphere has `20 × 4ⁿ` triangular
+    /// facets, where `n = n_subdiv`. The points are on the surface of the sphere.
+    ///
+    /// # Input
+    ///
+    /// * `center` -- the coordinates of the center
+    /// * `radius` -- the radius (> 0)
+    /// * `n_subdiv` -- the number of subdivisions of the icosahedron
+    /// * `marker` -- the marker of all facets (see `Tetgen::set_facet_marker`)
+    pub fn add_sphere(
+        &mut self,
+        center: (f64, f64, f64),
+        radius: f64,
+        n_subdiv: usize,
+        marker: i32,
+    ) -> Result<&mut Self, StrError> {
+        if radius <= 0.0 {
+            return Err("the radius must be positive");
+        }
+        // unit icosahedron
+        let phi = (1.0 + f64::sqrt(5.0)) / 2.0;
+        let mut vertices: Vec<[f64; 3]> = vec![
+            [-1.0, phi, 0.0],
+            [1.0, phi, 0.0],
+            [-1.0, -phi, 0.0],
+            [1.0, -phi, 0.0],
+            [0.0, -1.0, phi],
+            [0.0, 1.0, phi],
+            [0.0, -1.0, -phi],
+            [0.0, 1.0, -phi],
+            [phi, 0.0, -1.0],
+            [phi, 0.0, 1.0],
+            [-phi, 0.0, -1.0],
+            [-phi, 0.0, 1.0],
+        ];
+        for v in &mut vertices {
+            *v = normalize(*v);
+        }
+        let mut triangles: Vec<[[f64; 3]; 3]> = [
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ]
+        .iter()
+        .map(|t| [vertices[t[0]], vertices[t[1]], vertices[t[2]]])
+        .collect();
+        for _ in 0..n_subdiv {
+            let mut refined = Vec::with_capacity(4 * triangles.len());
+            for [a, b, c] in triangles {
+                let ab = normalize(midpoint(a, b));
+                let bc = normalize(midpoint(b, c));
+                let ca = normalize(midpoint(c, a));
+                refined.push([a, ab, ca]);
+                refined.push([ab, b, bc]);
+                refined.push([ca, bc, c]);
+                refined.push([ab, bc, ca]);
+            }
+            triangles = refined;
+        }
+        for [a, b, c] in triangles {
+            // orient the facet outward (the unit vector of a corner is the outward normal)
+            let n = triangle_normal(&a, &b, &c);
+            let (b, c) = if dot(&n, &a) > 0.0 { (b, c) } else { (c, b) };
+            let points: Vec<_> = [a, b, c]
+                .iter()
+                .map(|x| {
+                    [
+                        center.0 + radius * x[0],
+                        center.1 + radius * x[1],
+                        center.2 + radius * x[2],
+                    ]
+                })
+                .collect();
+            self.push_facet(&points, marker);
+        }
+        Ok(self)
+    }
+
+    /// Adds a solid obtained by extruding a polygon of the x-y plane along z
+    ///
+    /// # Input
+    ///
+    /// * `polygon` -- the x-y coordinates of the vertices (at least three; clockwise or counterclockwise;
+    ///   the first one is not repeated at the end)
+    /// * `z_min` -- the z-coordinate of the bottom face
+    /// * `z_max` -- the z-coordinate of the top face
+    /// * `marker` -- the marker of all facets (see `Tetgen::set_facet_marker`)
+    pub fn extrude_polygon(
+        &mut self,
+        polygon: &[(f64, f64)],
+        z_min: f64,
+        z_max: f64,
+        marker: i32,
+    ) -> Result<&mut Self, StrError> {
+        if polygon.len() < 3 {
+            return Err("the polygon must have at least 3 points");
+        }
+        if z_max <= z_min {
+            return Err("z_max must be greater than z_min");
+        }
+        let n = polygon.len();
+        let twice_area: f64 = (0..n)
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum();
+        if twice_area == 0.0 {
+            return Err("the polygon must have a non-zero area");
+        }
+        if twice_area > 0.0 {
+            self.push_prism(polygon, z_min, z_max, marker);
+        } else {
+            let reversed: Vec<_> = polygon.iter().rev().copied().collect();
+            self.push_prism(&reversed, z_min, z_max, marker);
+        }
+        Ok(self)
+    }
+
+    /// Adds a hole (see `Tetgen::set_hole`)
+    pub fn add_hole(&mut self, x: f64, y: f64, z: f64) -> &mut Self {
+        self.holes.push([x, y, z]);
+        self
+    }
+
+    /// Adds a region (see `Tetgen::set_region`)
+    pub fn add_region(&mut self, x: f64, y: f64, z: f64, attribute: i32, max_volume: Option<f64>) -> &mut Self {
+        self.regions.push(([x, y, z], attribute, max_volume));
+        self
+    }
+
+    /// Allocates a new Tetgen with the points, facets, regions, and holes
+    ///
+    /// The returned Tetgen is ready for `generate_mesh`.
+    pub fn build(&self) -> Result<Tetgen, StrError> {
+        let option = |n: usize| if n > 0 { Some(n) } else { None };
+        let facet_npoint = self.facets.iter().map(|(points, _)| points.len()).collect();
+        let mut tetgen = Tetgen::new(
+            self.points.len(),
+            Some(facet_npoint),
+            option(self.regions.len()),
+            option(self.holes.len()),
+        )?;
+        for (index, x) in self.points.iter().enumerate() {
+            tetgen.set_point(index, x[0], x[1], x[2])?;
+        }
+        for (index, (points, marker)) in self.facets.iter().enumerate() {
+            for (m, p) in points.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, *marker)?;
+        }
+        for (index, (x, attribute, max_volume)) in self.regions.iter().enumerate() {
+            tetgen.set_region(index, x[0], x[1], x[2], *attribute, *max_volume)?;
+        }
+        for (index, x) in self.holes.iter().enumerate() {
+            tetgen.set_hole(index, x[0], x[1], x[2])?;
+        }
+        Ok(tetgen)
+    }
+
+    /// Adds the facets of a prism given its counterclockwise base
+    fn push_prism(&mut self, base: &[(f64, f64)], z_min: f64, z_max: f64, marker: i32) {
+        let n = base.len();
+        let bottom: Vec<_> = base.iter().rev().map(|(x, y)| [*x, *y, z_min]).collect();
+        let top: Vec<_> = base.iter().map(|(x, y)| [*x, *y, z_max]).collect();
+        self.push_facet(&bottom, marker);
+        self.push_facet(&top, marker);
+        for i in 0..n {
+            let (a, b) = (base[i], base[(i + 1) % n]);
+            let side = [
+                [a.0, a.1, z_min],
+                [b.0, b.1, z_min],
+                [b.0, b.1, z_max],
+                [a.0, a.1, z_max],
+            ];
+            self.push_facet(&side, marker);
+        }
+    }
+
+    /// Adds a facet, unless a facet with the same points exists already
+    fn push_facet(&mut self, points: &[[f64; 3]], marker: i32) {
+        let ids: Vec<_> = points.iter().map(|x| self.point_id(*x)).collect();
+        let mut key = ids.clone();
+        key.sort();
+        if self.facet_keys.insert(key) {
+            self.facets.push((ids, marker));
+        }
+    }
+
+    /// Returns the ID of a point, adding it if not present yet
+    fn point_id(&mut self, x: [f64; 3]) -> usize {
+        let tol = self.tolerance;
+        match self
+            .points
+            .iter()
+            .position(|y| (0..3).all(|i| f64::abs(y[i] - x[i]) <= tol))
+        {
+            Some(id) => id,
+            None => {
+                self.points.push(x);
+                self.points.len() - 1
+            }
+        }
+    }
+}
+
+impl Default for Geometry3d {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the midpoint between u and v
+fn midpoint(u: [f64; 3], v: [f64; 3]) -> [f64; 3] {
+    [(u[0] + v[0]) / 2.0, (u[1] + v[1]) / 2.0, (u[2] + v[2]) / 2.0]
+}
+
+/// Returns the unit vector parallel to u
+fn normalize(u: [f64; 3]) -> [f64; 3] {
+    let length = norm(&u);
+    [u[0] / length, u[1] / length, u[2] / length]
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::Geometry3d;
+    use crate::vector::{dot, norm, sub, triangle_normal};
+    use crate::{StrError, Tetgen};
+    use std::f64::consts::PI;
+
+    /// Returns the total volume of the tetrahedra
+    fn volume(tetgen: &Tetgen) -> f64 {
+        (0..tetgen.ntet()).map(|t| tetgen.tet_quality(t).unwrap().volume).sum()
+    }
+
+    /// Checks that the facets are oriented outward with respect to a point inside a convex solid
+    fn check_outward(geo: &Geometry3d, inside: [f64; 3]) {
+        for (points, _) in &geo.facets {
+            let x: Vec<_> = points.iter().map(|p| geo.points[*p]).collect();
+            let n = triangle_normal(&x[0], &x[1], &x[2]);
+            assert!(dot(&n, &sub(&x[0], &inside)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn add_functions_capture_errors() {
+        let mut geo = Geometry3d::new();
+        assert_eq!(
+            geo.add_facet(&[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0)], 0).err(),
+            Some("the facet must have at least 3 points")
+        );
+        assert_eq!(
+            geo.add_box((0.0, 0.0, 0.0), (1.0, 0.0, 1.0), 0).err(),
+            Some("the max coordinates of the box must be greater than the min coordinates")
+        );
+        assert_eq!(
+            geo.add_cylinder((0.0, 0.0, 0.0), 0.0, 1.0, 8, 0).err(),
+            Some("the radius must be positive")
+        );
+        assert_eq!(
+            geo.add_cylinder((0.0, 0.0, 0.0), 1.0, 0.0, 8, 0).err(),
+            Some("the height must be positive")
+        );
+        assert_eq!(
+            geo.add_cylinder((0.0, 0.0, 0.0), 1.0, 1.0, 2, 0).err(),
+            Some("the cylinder must have at least 3 segments")
+        );
+        assert_eq!(
+            geo.add_sphere((0.0, 0.0, 0.0), -1.0, 1, 0).err(),
+            Some("the radius must be positive")
+        );
+        assert_eq!(
+            geo.extrude_polygon(&[(0.0, 0.0), (1.0, 0.0)], 0.0, 1.0, 0).err(),
+            Some("the polygon must have at least 3 points")
+        );
+        assert_eq!(
+            geo.extrude_polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)], 1.0, 1.0, 0)
+                .err(),
+            Some("z_max must be greater than z_min")
+        );
+        assert_eq!(
+            geo.extrude_polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)], 0.0, 1.0, 0)
+                .err(),
+            Some("the polygon must have a non-zero area")
+        );
+        assert_eq!(geo.npoint(), 0);
+        assert_eq!(geo.build().err(), Some("npoint must be ≥ 4"));
+    }
+
+    #[test]
+    fn add_box_works() -> Result<(), StrError> {
+        let mut geo = Geometry3d::new();
+        geo.add_box((1.0, 2.0, 3.0), (2.0, 4.0, 6.0), -1)?;
+        assert_eq!(geo.npoint(), 8);
+        assert_eq!(geo.nfacet(), 6);
+        check_outward(&geo, [1.5, 3.0, 4.5]);
+        let tetgen = geo.build()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(f64::abs(volume(&tetgen) - 6.0) < 1e-14);
+        assert!((0..tetgen.nmarked_face()).all(|f| tetgen.marked_face_marker(f) == -1));
+        Ok(())
+    }
+
+    #[test]
+    fn boxes_with_regions_and_holes_work() -> Result<(), StrError> {
+        // three boxes in a row; the middle one is a hole and the shared faces are not repeated
+        let mut geo = Geometry3d::new();
+        geo.add_box((0.0, 0.0, 0.0), (1.0, 1.0, 1.0), -1)?
+            .add_box((1.0, 0.0, 0.0), (2.0, 1.0, 1.0), -2)?
+            .add_box((2.0, 0.0, 0.0), (3.0, 1.0, 1.0), -3)?
+            .add_region(0.5, 0.5, 0.5, 1, None)
+            .add_region(2.5, 0.5, 0.5, -1, None)
+            .add_hole(1.5, 0.5, 0.5);
+        assert_eq!(geo.npoint(), 16);
+        assert_eq!(geo.nfacet(), 16);
+        assert_eq!(geo.nregion(), 2);
+        assert_eq!(geo.nhole(), 1);
+        let tetgen = geo.build()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(f64::abs(volume(&tetgen) - 2.0) < 1e-14);
+        for t in 0..tetgen.ntet() {
+            let xc: f64 = (0..4).map(|m| tetgen.point(tetgen.tet_node(t, m), 0)).sum::<f64>() / 4.0;
+            let correct = if xc < 1.0 { 1 } else { -1 };
+            assert_eq!(tetgen.tet_attribute(t), correct);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn add_cylinder_works() -> Result<(), StrError> {
+        let mut geo = Geometry3d::new();
+        geo.add_cylinder((1.0, 1.0, -1.0), 0.5, 2.0, 12, -5)?;
+        assert_eq!(geo.npoint(), 24);
+        assert_eq!(geo.nfacet(), 14);
+        check_outward(&geo, [1.0, 1.0, 0.0]);
+        let tetgen = geo.build()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let base_area = 12.0 * 0.5 * 0.25 * f64::sin(2.0 * PI / 12.0);
+        assert!(f64::abs(volume(&tetgen) - base_area * 2.0) < 1e-14);
+        Ok(())
+    }
+
+    #[test]
+    fn add_sphere_works() -> Result<(), StrError> {
+        let mut geo = Geometry3d::new();
+        geo.add_sphere((0.0, 0.0, 0.0), 1.0, 0, -1)?;
+        assert_eq!(geo.npoint(), 12);
+        assert_eq!(geo.nfacet(), 20);
+        check_outward(&geo, [0.0, 0.0, 0.0]);
+
+        let mut geo = Geometry3d::new();
+        geo.add_sphere((1.0, 2.0, 3.0), 2.0, 2, -1)?;
+        assert_eq!(geo.npoint(), 162);
+        assert_eq!(geo.nfacet(), 320);
+        check_outward(&geo, [1.0, 2.0, 3.0]);
+        assert!(geo
+            .points
+            .iter()
+            .all(|x| f64::abs(norm(&sub(x, &[1.0, 2.0, 3.0])) - 2.0) < 1e-14));
+        let tetgen = geo.build()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let exact = 4.0 * PI * 8.0 / 3.0;
+        let vol = volume(&tetgen);
+        assert!(vol < exact && vol > 0.95 * exact);
+        Ok(())
+    }
+
+    #[test]
+    fn extrude_polygon_works() -> Result<(), StrError> {
+        // L-shaped polygon given clockwise
+        let polygon = [(0.0, 0.0), (0.0, 2.0), (1.0, 2.0), (1.0, 1.0), (2.0, 1.0), (2.0, 0.0)];
+        let mut geo = Geometry3d::new();
+        geo.extrude_polygon(&polygon, 0.0, 0.5, -1)?;
+        assert_eq!(geo.npoint(), 12);
+        assert_eq!(geo.nfacet(), 8);
+        let tetgen = geo.build()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(f64::abs(volume(&tetgen) - 1.5) < 1e-14);
+        // the bottom facet points downward
+        let (bottom, _) = &geo.facets[0];
+        let x: Vec<_> = bottom.iter().map(|p| geo.points[*p]).collect();
+        assert!(triangle_normal(&x[0], &x[1], &x[2])[2] < 0.0);
+        Ok(())
+    }
+}
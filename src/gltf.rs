@@ -0,0 +1,249 @@
+use crate::vector::{dot, sub, triangle_normal};
+use crate::ColorMap;
+use crate::StrError;
+use crate::Tetgen;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Writes the boundary surface of tetrahedra as a binary glTF file (.glb)
+///
+/// The boundary faces (faces shared by only one tetrahedron) are written as triangles with
+/// outward normals. Each triangle holds its own vertices; thus, the colors are constant per
+/// face. The file can be loaded into web viewers (e.g., three.js) without Paraview.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+/// * `color_by_marker` -- colors the faces by the marker of the marked faces (see `set_facet_marker`)
+///   instead of the attribute of the tetrahedra. Faces not found in the list of marked
+///   faces get the color of marker zero. The colors are given by `ColorMap::light()`.
+pub fn write_tet_gltf<P>(tetgen: &Tetgen, full_path: &P, color_by_marker: bool) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntet = tetgen.ntet();
+    if ntet < 1 {
+        return Err("there are no tetrahedra to write");
+    }
+
+    // find the boundary faces (sorted corners => (tet, opposite corner))
+    let mut faces: HashMap<[usize; 3], Vec<(usize, usize)>> = HashMap::new();
+    for tet in 0..ntet {
+        let v: Vec<usize> = (0..4).map(|m| tetgen.tet_node(tet, m)).collect();
+        for opposite in 0..4 {
+            let mut key = [0; 3];
+            for (k, m) in (0..4).filter(|m| *m != opposite).enumerate() {
+                key[k] = v[m];
+            }
+            key.sort();
+            faces.entry(key).or_default().push((tet, v[opposite]));
+        }
+    }
+    let mut boundary: Vec<([usize; 3], usize, usize)> = faces
+        .into_iter()
+        .filter(|(_, owners)| owners.len() == 1)
+        .map(|(key, owners)| (key, owners[0].0, owners[0].1))
+        .collect();
+    boundary.sort();
+
+    // markers of the marked faces
+    let mut markers: HashMap<[usize; 3], i32> = HashMap::new();
+    if color_by_marker {
+        for face in 0..tetgen.nmarked_face() {
+            let mut key = [
+                tetgen.marked_face_node(face, 0),
+                tetgen.marked_face_node(face, 1),
+                tetgen.marked_face_node(face, 2),
+            ];
+            key.sort();
+            markers.insert(key, tetgen.marked_face_marker(face));
+        }
+    }
+
+    // vertices and colors
+    let coords = |p: usize| [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)];
+    let colormap = ColorMap::light();
+    let mut positions: Vec<f32> = Vec::with_capacity(boundary.len() * 9);
+    let mut vertex_colors: Vec<f32> = Vec::with_capacity(boundary.len() * 9);
+    let mut min = [f32::MAX; 3];
+    let mut max = [f32::MIN; 3];
+    for (key, tet, opposite) in &boundary {
+        let (a, b, c) = (coords(key[0]), coords(key[1]), coords(key[2]));
+        let d = coords(*opposite);
+        let inward = dot(&triangle_normal(&a, &b, &c), &sub(&d, &a)) > 0.0;
+        let corners = if inward { [a, c, b] } else { [a, b, c] };
+        let group = if color_by_marker {
+            *markers.get(key).unwrap_or(&0) as i64
+        } else {
+            tetgen.tet_attribute(*tet) as i64
+        };
+        let color = hex_to_rgb(colormap.color(group));
+        for x in &corners {
+            for dim in 0..3 {
+                let value = x[dim] as f32;
+                min[dim] = f32::min(min[dim], value);
+                max[dim] = f32::max(max[dim], value);
+                positions.push(value);
+            }
+            vertex_colors.extend_from_slice(&color);
+        }
+    }
+
+    // binary buffer: positions followed by colors
+    let nvertex = boundary.len() * 3;
+    let nbyte_positions = positions.len() * 4;
+    let mut bin: Vec<u8> = Vec::with_capacity(nbyte_positions * 2);
+    for value in positions.iter().chain(vertex_colors.iter()) {
+        bin.extend_from_slice(&value.to_le_bytes());
+    }
+
+    // json
+    let mut json = format!(
+        "{{\"asset\":{{\"version\":\"2.0\",\"generator\":\"tritet\"}},\
+         \"scene\":0,\"scenes\":[{{\"nodes\":[0]}}],\"nodes\":[{{\"mesh\":0}}],\
+         \"meshes\":[{{\"primitives\":[{{\"attributes\":{{\"POSITION\":0,\"COLOR_0\":1}},\"mode\":4}}]}}],\
+         \"buffers\":[{{\"byteLength\":{}}}],\
+         \"bufferViews\":[{{\"buffer\":0,\"byteOffset\":0,\"byteLength\":{},\"target\":34962}},\
+         {{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":34962}}],\
+         \"accessors\":[{{\"bufferView\":0,\"componentType\":5126,\"count\":{},\"type\":\"VEC3\",\
+         \"min\":[{},{},{}],\"max\":[{},{},{}]}},\
+         {{\"bufferView\":1,\"componentType\":5126,\"count\":{},\"type\":\"VEC3\"}}]}}",
+        bin.len(),
+        nbyte_positions,
+        nbyte_positions,
+        nbyte_positions,
+        nvertex,
+        min[0],
+        min[1],
+        min[2],
+        max[0],
+        max[1],
+        max[2],
+        nvertex,
+    );
+    while json.len() % 4 != 0 {
+        json.push(' ');
+    }
+
+    // glb container: header, json chunk, and binary chunk
+    let total = 12 + 8 + json.len() + 8 + bin.len();
+    let mut buffer: Vec<u8> = Vec::with_capacity(total);
+    buffer.extend_from_slice(b"glTF");
+    buffer.extend_from_slice(&2_u32.to_le_bytes());
+    buffer.extend_from_slice(&(total as u32).to_le_bytes());
+    buffer.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(b"JSON");
+    buffer.extend_from_slice(json.as_bytes());
+    buffer.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(b"BIN\0");
+    buffer.extend_from_slice(&bin);
+
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(&buffer).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Converts a color such as "#cbe4f9" to RGB components in [0, 1]
+fn hex_to_rgb(color: &str) -> [f32; 3] {
+    let component = |i: usize| u8::from_str_radix(&color[i..i + 2], 16).unwrap_or(0) as f32 / 255.0;
+    [component(1), component(3), component(5)]
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{hex_to_rgb, write_tet_gltf};
+    use crate::vector::{dot, sub, triangle_normal};
+    use crate::StrError;
+    use crate::Tetgen;
+    use std::fs;
+
+    fn read_u32(bytes: &[u8], start: usize) -> usize {
+        u32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]]) as usize
+    }
+
+    fn read_f32(bytes: &[u8], start: usize) -> f32 {
+        f32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], bytes[start + 3]])
+    }
+
+    #[test]
+    fn hex_to_rgb_works() {
+        assert_eq!(hex_to_rgb("#ff0000"), [1.0, 0.0, 0.0]);
+        assert_eq!(hex_to_rgb("#00ff33"), [0.0, 1.0, 0.2]);
+    }
+
+    #[test]
+    fn write_tet_gltf_captures_errors() -> Result<(), StrError> {
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            write_tet_gltf(&tetgen, "/tmp/tritet/test_write_tet_gltf_empty.glb", false).err(),
+            Some("there are no tetrahedra to write")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_gltf_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let file_path = "/tmp/tritet/test_write_tet_gltf.glb";
+        write_tet_gltf(&tetgen, file_path, false)?;
+        let bytes = fs::read(file_path).map_err(|_| "cannot open file")?;
+
+        // header
+        assert_eq!(&bytes[0..4], b"glTF");
+        assert_eq!(read_u32(&bytes, 4), 2);
+        assert_eq!(read_u32(&bytes, 8), bytes.len());
+
+        // json chunk
+        let json_len = read_u32(&bytes, 12);
+        assert_eq!(json_len % 4, 0);
+        assert_eq!(&bytes[16..20], b"JSON");
+        let json = std::str::from_utf8(&bytes[20..20 + json_len]).unwrap();
+        assert!(json.contains("\"count\":12"));
+        assert!(json.contains("\"min\":[0,0,0],\"max\":[1,1,1]"));
+
+        // binary chunk: 4 faces × 3 vertices × (position + color)
+        let bin_start = 20 + json_len;
+        assert_eq!(read_u32(&bytes, bin_start), 4 * 3 * 3 * 4 * 2);
+        assert_eq!(&bytes[bin_start + 4..bin_start + 8], b"BIN\0");
+
+        // the normals point outward (away from the centroid)
+        let data = bin_start + 8;
+        for face in 0..4 {
+            let x: Vec<[f64; 3]> = (0..3)
+                .map(|v| {
+                    let start = data + (face * 9 + v * 3) * 4;
+                    [0, 4, 8].map(|k| read_f32(&bytes, start + k) as f64)
+                })
+                .collect();
+            let n = triangle_normal(&x[0], &x[1], &x[2]);
+            assert!(dot(&n, &sub(&[0.25, 0.25, 0.25], &x[0])) < 0.0);
+        }
+
+        // by marker
+        write_tet_gltf(&tetgen, file_path, true)?;
+        let bytes_marker = fs::read(file_path).map_err(|_| "cannot open file")?;
+        assert_eq!(bytes_marker, bytes);
+        Ok(())
+    }
+}
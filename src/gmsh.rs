@@ -0,0 +1,352 @@
+use crate::Mesh;
+use crate::StrError;
+use crate::{Tetgen, Triangle};
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Maps the local indices of the nodes used in this library (tritet) to the ones used in Gmsh
+///
+/// Only the middle nodes of the tetrahedron differ: Gmsh numbers the nodes on the edges
+/// (1,3) and (2,3) as 9 and 8, respectively.
+const TRITET_TO_GMSH_TET10: [usize; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 9, 8];
+
+impl Triangle {
+    /// Writes the generated mesh as a Gmsh's MSH file (version 4.1)
+    ///
+    /// See `write_mesh_msh`.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn write_msh<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        write_mesh_msh(&self.to_mesh(), full_path)
+    }
+}
+
+impl Tetgen {
+    /// Writes the generated mesh as a Gmsh's MSH file (version 4.1)
+    ///
+    /// See `write_mesh_msh`.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn write_msh<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        write_mesh_msh(&self.to_mesh(), full_path)
+    }
+}
+
+/// Writes a mesh as a Gmsh's MSH file (version 4.1, ASCII)
+///
+/// The format is described in [Gmsh's manual](https://gmsh.info/doc/texinfo/gmsh.html#MSH-file-format).
+/// The cells with the same attribute are written as an entity of dimension DIM (a surface in
+/// 2D or a volume in 3D) and the facets with the same marker as an entity of dimension DIM-1
+/// (a curve in 2D or a surface in 3D). Each entity belongs to a physical group named
+/// "attribute A" or "marker M", where A and M are the values in the mesh; the tags of the
+/// entities and physical groups are numbered consecutively from 1 (in increasing order of
+/// attributes and markers) because Gmsh requires positive tags. The nodes and elements are
+/// numbered from 1 and the z-coordinates are zero in 2D.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_mesh_msh<const DIM: usize, P>(mesh: &Mesh<DIM>, full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ncell = mesh.cells.len();
+    if ncell < 1 {
+        return Err("there are no cells to write");
+    }
+
+    // group the cells by attribute and the facets by marker
+    let mut cell_groups: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    for (cell, attribute) in mesh.attributes.iter().enumerate() {
+        cell_groups.entry(*attribute).or_default().push(cell);
+    }
+    let mut facet_groups: BTreeMap<i32, Vec<usize>> = BTreeMap::new();
+    for (facet, marker) in mesh.facet_markers.iter().enumerate() {
+        facet_groups.entry(*marker).or_default().push(facet);
+    }
+    let coords = |p: usize| -> [f64; 3] {
+        let mut x = [0.0; 3];
+        x[..DIM].copy_from_slice(&mesh.points[p]);
+        x
+    };
+    let bounding_box = |elements: &[Vec<usize>], ids: &[usize]| -> String {
+        let (mut min, mut max) = ([f64::MAX; 3], [f64::MIN; 3]);
+        for nodes in ids.iter().map(|i| &elements[*i]) {
+            for x in nodes.iter().map(|p| coords(*p)) {
+                for k in 0..3 {
+                    min[k] = f64::min(min[k], x[k]);
+                    max[k] = f64::max(max[k], x[k]);
+                }
+            }
+        }
+        format!("{} {} {} {} {} {}", min[0], min[1], min[2], max[0], max[1], max[2])
+    };
+
+    let mut buffer = String::new();
+    buffer.push_str("$MeshFormat\n4.1 0 8\n$EndMeshFormat\n");
+
+    // physical groups
+    writeln!(
+        &mut buffer,
+        "$PhysicalNames\n{}",
+        cell_groups.len() + facet_groups.len()
+    )
+    .unwrap();
+    for (tag, attribute) in cell_groups.keys().enumerate() {
+        writeln!(&mut buffer, "{} {} \"attribute {}\"", DIM, tag + 1, attribute).unwrap();
+    }
+    for (tag, marker) in facet_groups.keys().enumerate() {
+        writeln!(&mut buffer, "{} {} \"marker {}\"", DIM - 1, tag + 1, marker).unwrap();
+    }
+    buffer.push_str("$EndPhysicalNames\n");
+
+    // entities (the tag of each entity equals the tag of its physical group)
+    let (ncurve, nsurface, nvolume) = if DIM == 2 {
+        (facet_groups.len(), cell_groups.len(), 0)
+    } else {
+        (0, facet_groups.len(), cell_groups.len())
+    };
+    writeln!(&mut buffer, "$Entities\n0 {} {} {}", ncurve, nsurface, nvolume).unwrap();
+    for (tag, ids) in facet_groups.values().enumerate() {
+        let bbox = bounding_box(&mesh.facets, ids);
+        writeln!(&mut buffer, "{} {} 1 {} 0", tag + 1, bbox, tag + 1).unwrap();
+    }
+    for (tag, ids) in cell_groups.values().enumerate() {
+        let bbox = bounding_box(&mesh.cells, ids);
+        writeln!(&mut buffer, "{} {} 1 {} 0", tag + 1, bbox, tag + 1).unwrap();
+    }
+    buffer.push_str("$EndEntities\n");
+
+    // nodes (all of them in the first entity of dimension DIM)
+    let npoint = mesh.points.len();
+    writeln!(&mut buffer, "$Nodes\n1 {} 1 {}\n{} 1 0 {}", npoint, npoint, DIM, npoint).unwrap();
+    for p in 0..npoint {
+        writeln!(&mut buffer, "{}", p + 1).unwrap();
+    }
+    for p in 0..npoint {
+        let x = coords(p);
+        writeln!(&mut buffer, "{} {} {}", x[0], x[1], x[2]).unwrap();
+    }
+    buffer.push_str("$EndNodes\n");
+
+    // elements (the cells first, then the facets)
+    let nelement = ncell + mesh.facets.len();
+    writeln!(
+        &mut buffer,
+        "$Elements\n{} {} 1 {}",
+        cell_groups.len() + facet_groups.len(),
+        nelement,
+        nelement
+    )
+    .unwrap();
+    let mut write_block = |dim: usize, tag: usize, elements: &[Vec<usize>], ids: &[usize], offset: usize| {
+        let nnode = elements[ids[0]].len();
+        let element_type = element_type(dim, nnode).ok_or("the number of nodes of an element is not supported")?;
+        if ids.iter().any(|i| elements[*i].len() != nnode) {
+            return Err("the elements with the same attribute or marker must have the same number of nodes");
+        }
+        writeln!(&mut buffer, "{} {} {} {}", dim, tag, element_type, ids.len()).unwrap();
+        for i in ids {
+            write!(&mut buffer, "{}", offset + i + 1).unwrap();
+            for (m, p) in elements[*i].iter().enumerate() {
+                let p = if element_type == 11 {
+                    elements[*i][TRITET_TO_GMSH_TET10[m]]
+                } else {
+                    *p
+                };
+                write!(&mut buffer, " {}", p + 1).unwrap();
+            }
+            buffer.push('\n');
+        }
+        Ok(())
+    };
+    for (tag, ids) in cell_groups.values().enumerate() {
+        write_block(DIM, tag + 1, &mesh.cells, ids, 0)?;
+    }
+    for (tag, ids) in facet_groups.values().enumerate() {
+        write_block(DIM - 1, tag + 1, &mesh.facets, ids, ncell)?;
+    }
+    buffer.push_str("$EndElements\n");
+
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Returns the Gmsh's element type given the dimension and the number of nodes
+fn element_type(dim: usize, nnode: usize) -> Option<usize> {
+    match (dim, nnode) {
+        (1, 2) => Some(1),   // 2-node line
+        (1, 3) => Some(8),   // 3-node line
+        (2, 3) => Some(2),   // 3-node triangle
+        (2, 6) => Some(9),   // 6-node triangle
+        (3, 4) => Some(4),   // 4-node tetrahedron
+        (3, 10) => Some(11), // 10-node tetrahedron
+        _ => None,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::write_mesh_msh;
+    use crate::{CellTags, StrError, Tetgen, TriMesh, Triangle};
+    use std::fs;
+
+    #[test]
+    fn write_mesh_msh_captures_errors() {
+        let mut mesh = TriMesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]],
+            cells: Vec::new(),
+            attributes: Vec::new(),
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        let path = "/tmp/tritet/test_write_mesh_msh_errors.msh";
+        assert_eq!(write_mesh_msh(&mesh, path).err(), Some("there are no cells to write"));
+        mesh.cells = vec![vec![0, 1, 2, 3]];
+        mesh.attributes = vec![0];
+        assert_eq!(
+            write_mesh_msh(&mesh, path).err(),
+            Some("the number of nodes of an element is not supported")
+        );
+        mesh.cells = vec![vec![0, 1, 2], vec![1, 3, 2, 0]];
+        mesh.attributes = vec![0, 0];
+        assert_eq!(
+            write_mesh_msh(&mesh, path).err(),
+            Some("the elements with the same attribute or marker must have the same number of nodes")
+        );
+    }
+
+    #[test]
+    fn write_mesh_msh_works() -> Result<(), StrError> {
+        let mesh = TriMesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![7, 0],
+            facets: vec![vec![0, 1], vec![2, 3]],
+            facet_markers: vec![-1, -2],
+            tags: CellTags::default(),
+        };
+        let path = "/tmp/tritet/test_write_mesh_msh.msh";
+        write_mesh_msh(&mesh, path)?;
+        let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        assert_eq!(
+            contents,
+            "$MeshFormat\n\
+             4.1 0 8\n\
+             $EndMeshFormat\n\
+             $PhysicalNames\n\
+             4\n\
+             2 1 \"attribute 0\"\n\
+             2 2 \"attribute 7\"\n\
+             1 1 \"marker -2\"\n\
+             1 2 \"marker -1\"\n\
+             $EndPhysicalNames\n\
+             $Entities\n\
+             0 2 2 0\n\
+             1 0 1 0 1 1 0 1 1 0\n\
+             2 0 0 0 1 0 0 1 2 0\n\
+             1 0 0 0 1 1 0 1 1 0\n\
+             2 0 0 0 1 1 0 1 2 0\n\
+             $EndEntities\n\
+             $Nodes\n\
+             1 4 1 4\n\
+             2 1 0 4\n\
+             1\n2\n3\n4\n\
+             0 0 0\n\
+             1 0 0\n\
+             1 1 0\n\
+             0 1 0\n\
+             $EndNodes\n\
+             $Elements\n\
+             4 4 1 4\n\
+             2 1 2 1\n\
+             2 1 3 4\n\
+             2 2 2 1\n\
+             1 1 2 3\n\
+             1 1 1 1\n\
+             4 3 4\n\
+             1 2 1 1\n\
+             3 1 2\n\
+             $EndElements\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_msh_works_with_generators() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?
+            .set_segment_marker(0, -10)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        let path = "/tmp/tritet/test_triangle_write_msh.msh";
+        triangle.write_msh(path)?;
+        let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        assert!(contents.contains("\"marker -10\""));
+        assert!(contents.contains("\n2 1 9 ")); // 6-node triangles
+
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, -(index as i32) - 1)?;
+        }
+        tetgen.generate_mesh(false, true, None, None)?;
+        let path = "/tmp/tritet/test_tetgen_write_msh.msh";
+        tetgen.write_msh(path)?;
+        let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        assert!(contents.contains("\"marker -4\""));
+        assert!(contents.contains("\n3 1 11 ")); // 10-node tetrahedra
+
+        // the middle nodes 8 and 9 are swapped
+        let mesh = tetgen.to_mesh();
+        let line = contents
+            .lines()
+            .skip_while(|line| !line.starts_with("3 1 11 "))
+            .nth(1)
+            .unwrap();
+        let nodes: Vec<usize> = line.split_whitespace().skip(1).map(|w| w.parse().unwrap()).collect();
+        assert_eq!(nodes[8], mesh.cells[0][9] + 1);
+        assert_eq!(nodes[9], mesh.cells[0][8] + 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use crate::geojson::hole_seed;
+use crate::vector::{cross, dot, norm, sub, triangle_normal};
+use crate::{StrError, Tetgen, Triangle};
+
+impl Triangle {
+    /// Marks a hole given by a closed polygon of input points (the seed point is computed automatically)
+    ///
+    /// A point strictly inside the polygon is found by intersecting the polygon with a horizontal
+    /// line (not crossing its vertices) and selecting the midpoint of the widest interval inside
+    /// the polygon; thus, non-convex polygons are supported. Then, the hole is set with `set_hole`.
+    ///
+    /// **Note:** The points of the polygon must be set first (see `set_point`). The polygon must
+    /// not cross itself and must not contain other parts of the domain (e.g., islands).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the hole and goes from 0 to `nhole` (passed down to `new`)
+    /// * `points` -- are the IDs of the points of the polygon (the last point connects to the first one)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(8, Some(8), None, Some(1))?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 3.0, 0.0)?
+    ///         .set_point(2, 3.0, 3.0)?
+    ///         .set_point(3, 0.0, 3.0)?
+    ///         // a non-convex (C-shaped) hole whose centroid is outside
+    ///         .set_point(4, 1.0, 1.0)?
+    ///         .set_point(5, 2.0, 1.0)?
+    ///         .set_point(6, 1.2, 1.5)?
+    ///         .set_point(7, 2.0, 2.0)?;
+    ///     for m in 0..4 {
+    ///         triangle.set_segment(m, m, (m + 1) % 4)?;
+    ///     }
+    ///     for m in 0..4 {
+    ///         triangle.set_segment(4 + m, 4 + m, 4 + (m + 1) % 4)?;
+    ///     }
+    ///     triangle.set_hole_from_polygon(0, &[4, 5, 6, 7])?;
+    ///     triangle.generate_mesh(false, false, None, None)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_hole_from_polygon(&mut self, index: usize, points: &[usize]) -> Result<&mut Self, StrError> {
+        if points.len() < 3 {
+            return Err("the polygon must have at least 3 points");
+        }
+        let (npoint, _) = self.input_npoint();
+        if points.iter().any(|p| *p >= npoint) {
+            return Err("id of polygon point is out of bounds");
+        }
+        let ring: Vec<(f64, f64)> = points
+            .iter()
+            .map(|p| {
+                let x = self.input_point(*p);
+                (x[0], x[1])
+            })
+            .collect();
+        let (x, y) = hole_seed(&ring, &[]).map_err(|_| "cannot find a point inside the polygon")?;
+        self.set_hole(index, x, y)
+    }
+}
+
+impl Tetgen {
+    /// Marks a hole given by a closed shell of input facets (the seed point is computed automatically)
+    ///
+    /// A ray is cast from a point on the first facet along its normal (in both directions); the
+    /// direction whose ray crosses the shell an odd number of times points inward, and the seed
+    /// is the midpoint between the facet and the nearest crossing. Then, the hole is set with
+    /// `set_hole`. Only the first polygon of each facet is considered (see `set_facet_polygon`).
+    ///
+    /// **Note:** The facets must be set first (see `set_facet_point`). The shell must be closed,
+    /// must not cross itself, and must not contain other parts of the domain.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the hole and goes from 0 to `nhole` (passed down to `new`)
+    /// * `facets` -- are the IDs of the facets of the shell
+    pub fn set_hole_from_shell(&mut self, index: usize, facets: &[usize]) -> Result<&mut Self, StrError> {
+        if facets.len() < 4 {
+            return Err("the shell must have at least 4 facets");
+        }
+        let (nfacet, _) = self.input_nfacet();
+        if facets.iter().any(|f| *f >= nfacet) {
+            return Err("index of facet is out of bounds");
+        }
+
+        // fan triangulation of the facets (the first triangle is the largest one of the first facet)
+        let normal = |[a, b, c]: &[[f64; 3]; 3]| triangle_normal(a, b, c);
+        let mut triangles: Vec<[[f64; 3]; 3]> = Vec::new();
+        for (k, facet) in facets.iter().enumerate() {
+            let polygons = self.input_facet_polygons(*facet);
+            let x: Vec<[f64; 3]> = polygons[0].iter().map(|p| self.input_point(*p)).collect();
+            let start = triangles.len();
+            for i in 1..(usize::max(x.len(), 2) - 1) {
+                triangles.push([x[0], x[i], x[i + 1]]);
+            }
+            if k == 0 {
+                let largest = (start..triangles.len())
+                    .max_by(|a, b| norm(&normal(&triangles[*a])).total_cmp(&norm(&normal(&triangles[*b]))));
+                match largest {
+                    Some(t) => triangles.swap(0, t),
+                    None => return Err("the facet must have at least 3 points"),
+                }
+            }
+        }
+        let n = normal(&triangles[0]);
+        let length = norm(&n);
+        if length <= 0.0 {
+            return Err("cannot find a point inside the shell");
+        }
+        let [a, b, c] = triangles[0];
+        let origin = [0, 1, 2].map(|i| (a[i] + b[i] + c[i]) / 3.0);
+
+        // cast the rays
+        for sign in [1.0, -1.0] {
+            let direction = [0, 1, 2].map(|i| sign * n[i] / length);
+            let mut hits: Vec<f64> = triangles[1..]
+                .iter()
+                .filter_map(|triangle| ray_triangle(&origin, &direction, triangle))
+                .collect();
+            hits.sort_by(f64::total_cmp);
+            let tolerance = 1e-10 * length.sqrt();
+            hits.dedup_by(|b, a| *b - *a <= tolerance);
+            hits.retain(|t| *t > tolerance);
+            if hits.len() % 2 == 1 {
+                let t = hits[0] / 2.0;
+                let x = [0, 1, 2].map(|i| origin[i] + t * direction[i]);
+                return self.set_hole(index, x[0], x[1], x[2]);
+            }
+        }
+        Err("cannot find a point inside the shell")
+    }
+}
+
+/// Returns the distance along the ray to the intersection with a triangle (Möller–Trumbore), if any
+///
+/// The intersections on the edges of the triangle are included.
+fn ray_triangle(origin: &[f64; 3], direction: &[f64; 3], triangle: &[[f64; 3]; 3]) -> Option<f64> {
+    let [a, b, c] = triangle;
+    let (e1, e2) = (sub(b, a), sub(c, a));
+    let p = cross(direction, &e2);
+    let det = dot(&e1, &p);
+    if f64::abs(det) < 1e-14 * norm(&e1) * norm(&e2) {
+        return None; // parallel
+    }
+    let s = sub(origin, a);
+    let u = dot(&s, &p) / det;
+    let q = cross(&s, &e1);
+    let v = dot(direction, &q) / det;
+    let eps = 1e-12;
+    if u < -eps || v < -eps || u + v > 1.0 + eps {
+        return None;
+    }
+    Some(dot(&e2, &q) / det)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::CUBE_FACETS;
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn set_hole_from_polygon_captures_errors() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, Some(1))?;
+        assert_eq!(
+            triangle.set_hole_from_polygon(0, &[0, 1]).err(),
+            Some("the polygon must have at least 3 points")
+        );
+        assert_eq!(
+            triangle.set_hole_from_polygon(0, &[0, 1, 3]).err(),
+            Some("id of polygon point is out of bounds")
+        );
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?;
+        assert_eq!(
+            triangle.set_hole_from_polygon(0, &[0, 1, 2]).err(),
+            Some("cannot find a point inside the polygon")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_hole_from_polygon_works() -> Result<(), StrError> {
+        // square with a C-shaped hole
+        let mut triangle = Triangle::new(12, Some(12), None, Some(1))?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 4.0, 0.0)?
+            .set_point(2, 4.0, 4.0)?
+            .set_point(3, 0.0, 4.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 3.0, 1.0)?
+            .set_point(6, 3.0, 1.5)?
+            .set_point(7, 1.5, 1.5)?
+            .set_point(8, 1.5, 2.5)?
+            .set_point(9, 3.0, 2.5)?
+            .set_point(10, 3.0, 3.0)?
+            .set_point(11, 1.0, 3.0)?;
+        for m in 0..4 {
+            triangle.set_segment(m, m, (m + 1) % 4)?;
+        }
+        for m in 0..8 {
+            triangle.set_segment(4 + m, 4 + m, 4 + (m + 1) % 8)?;
+        }
+        triangle.set_hole_from_polygon(0, &[4, 5, 6, 7, 8, 9, 10, 11])?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let area: f64 = (0..triangle.ntriangle()).map(|t| triangle.triangle_area(t)).sum();
+        // 16 - (2 × 2 - 1.5 × 1)
+        assert!(f64::abs(area - 13.5) < 1e-14);
+        Ok(())
+    }
+
+    #[test]
+    fn set_hole_from_shell_captures_errors() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, Some(1))?;
+        assert_eq!(
+            tetgen.set_hole_from_shell(0, &[0, 1, 2]).err(),
+            Some("the shell must have at least 4 facets")
+        );
+        assert_eq!(
+            tetgen.set_hole_from_shell(0, &[0, 1, 2, 4]).err(),
+            Some("index of facet is out of bounds")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_hole_from_shell_works() -> Result<(), StrError> {
+        // cube [0, 3]³ with a cubic hole [1, 2]³
+        let mut tetgen = Tetgen::new(16, Some(vec![4; 12]), None, Some(1))?;
+        for (offset, min, max) in [(0, 0.0, 3.0), (8, 1.0, 2.0)] {
+            tetgen
+                .set_point(offset, min, min, min)?
+                .set_point(offset + 1, max, min, min)?
+                .set_point(offset + 2, max, max, min)?
+                .set_point(offset + 3, min, max, min)?
+                .set_point(offset + 4, min, min, max)?
+                .set_point(offset + 5, max, min, max)?
+                .set_point(offset + 6, max, max, max)?
+                .set_point(offset + 7, min, max, max)?;
+            for (f, face) in CUBE_FACETS.iter().enumerate() {
+                for (m, p) in face.iter().enumerate() {
+                    tetgen.set_facet_point(offset / 8 * 6 + f, m, offset + p)?;
+                }
+            }
+        }
+        tetgen.set_hole_from_shell(0, &[6, 7, 8, 9, 10, 11])?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(f64::abs(tetgen.total_volume() - 26.0) < 1e-13);
+        Ok(())
+    }
+}
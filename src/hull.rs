@@ -0,0 +1,198 @@
+use crate::vector::{dot, sub, triangle_normal};
+use crate::{StrError, Tetgen, Triangle};
+use std::collections::HashMap;
+
+impl Triangle {
+    /// Generates the convex hull of the points
+    ///
+    /// The hull is given by the boundary edges (marked by Triangle) of the Delaunay triangulation
+    /// generated by `generate_delaunay`; thus, the output data (e.g., `ntriangle`) is also available
+    /// after this call. Collinear points on the hull are kept as vertices of the hull.
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Triangle's messages to the console
+    ///
+    /// # Output
+    ///
+    /// Returns the edges `(a, b)` of the hull (IDs of output points) forming a counterclockwise
+    /// loop that starts at the point with the smallest ID.
+    ///
+    /// **Note:** The orientation is computed with the coordinates mapped by the output transform, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(5, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 0.0, 1.0)?
+    ///         .set_point(2, 0.5, 0.5)?
+    ///         .set_point(3, 1.0, 1.0)?
+    ///         .set_point(4, 1.0, 0.0)?;
+    ///     let hull = triangle.generate_hull(false)?;
+    ///     assert_eq!(hull, &[(0, 4), (4, 3), (3, 1), (1, 0)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_hull(&self, verbose: bool) -> Result<Vec<(usize, usize)>, StrError> {
+        self.generate_delaunay(verbose)?;
+        let npoint = self.npoint();
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1)];
+        let mut center = [0.0, 0.0];
+        for p in 0..npoint {
+            let x = coords(p);
+            center[0] += x[0] / (npoint as f64);
+            center[1] += x[1] / (npoint as f64);
+        }
+        let mut next: HashMap<usize, usize> = HashMap::new();
+        for e in 0..self.nedge() {
+            if self.edge_marker(e) == 0 {
+                continue;
+            }
+            let (a, b) = (self.edge_point(e, 0), self.edge_point(e, 1));
+            let (xa, xb) = (coords(a), coords(b));
+            let area = (xb[0] - xa[0]) * (center[1] - xa[1]) - (xb[1] - xa[1]) * (center[0] - xa[0]);
+            if area > 0.0 {
+                next.insert(a, b);
+            } else {
+                next.insert(b, a);
+            }
+        }
+        let start = match next.keys().min() {
+            Some(p) => *p,
+            None => return Err("cannot find the edges of the convex hull"),
+        };
+        let mut hull = Vec::with_capacity(next.len());
+        let mut a = start;
+        loop {
+            let b = match next.get(&a) {
+                Some(b) => *b,
+                None => return Err("cannot find the edges of the convex hull"),
+            };
+            hull.push((a, b));
+            if b == start || hull.len() > next.len() {
+                break;
+            }
+            a = b;
+        }
+        if hull.len() != next.len() {
+            return Err("cannot find the edges of the convex hull");
+        }
+        Ok(hull)
+    }
+}
+
+impl Tetgen {
+    /// Generates the convex hull of the points
+    ///
+    /// The hull is given by the convex hull faces output by Tetgen along with the Delaunay
+    /// tetrahedralization generated by `generate_delaunay`; thus, the output data (e.g., `ntet`
+    /// and `nmarked_face`) is also available after this call.
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Tetgen's messages to the console
+    ///
+    /// # Output
+    ///
+    /// Returns the faces of the hull (IDs of output points) ordered counterclockwise when seen
+    /// from the outside; i.e., with the normal vectors pointing outward.
+    ///
+    /// **Note:** The orientation is computed with the coordinates mapped by the output transform, if any.
+    pub fn generate_hull(&self, verbose: bool) -> Result<Vec<[usize; 3]>, StrError> {
+        self.generate_delaunay(verbose)?;
+        let npoint = self.npoint();
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let mut center = [0.0, 0.0, 0.0];
+        for p in 0..npoint {
+            let x = coords(p);
+            for i in 0..3 {
+                center[i] += x[i] / (npoint as f64);
+            }
+        }
+        let hull: Vec<_> = (0..self.nmarked_face())
+            .map(|f| {
+                let [a, b, c] = [0, 1, 2].map(|m| self.marked_face_node(f, m));
+                let (xa, xb, xc) = (coords(a), coords(b), coords(c));
+                if dot(&triangle_normal(&xa, &xb, &xc), &sub(&xa, &center)) > 0.0 {
+                    [a, b, c]
+                } else {
+                    [a, c, b]
+                }
+            })
+            .collect();
+        if hull.is_empty() {
+            return Err("cannot find the faces of the convex hull");
+        }
+        Ok(hull)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::vector::{dot, sub, triangle_normal};
+    use crate::{StrError, Tetgen, Triangle};
+    use std::collections::HashMap;
+
+    #[test]
+    fn triangle_generate_hull_works() -> Result<(), StrError> {
+        // points on the hull (including a collinear one) and in the interior
+        let mut triangle = Triangle::new(7, None, None, None)?;
+        triangle
+            .set_point(0, 1.0, 1.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 0.5, 0.5)?
+            .set_point(3, 2.0, 2.0)?
+            .set_point(4, 0.0, 2.0)?
+            .set_point(5, 0.0, 0.0)?
+            .set_point(6, 1.0, 0.0)?;
+        let hull = triangle.generate_hull(false)?;
+        assert_eq!(hull, &[(1, 3), (3, 4), (4, 5), (5, 6), (6, 1)]);
+        assert_eq!(triangle.ntriangle(), 7);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_generate_hull_works() -> Result<(), StrError> {
+        // cube corners and one interior point
+        let mut tetgen = Tetgen::new(9, None, None, None)?;
+        tetgen
+            .set_point(0, 0.5, 0.5, 0.5)?
+            .set_point(1, 0.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 0.0, 0.0)?
+            .set_point(3, 1.0, 1.0, 0.0)?
+            .set_point(4, 0.0, 1.0, 0.0)?
+            .set_point(5, 0.0, 0.0, 1.0)?
+            .set_point(6, 1.0, 0.0, 1.0)?
+            .set_point(7, 1.0, 1.0, 1.0)?
+            .set_point(8, 0.0, 1.0, 1.0)?;
+        let hull = tetgen.generate_hull(false)?;
+        assert_eq!(hull.len(), 12);
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for face in &hull {
+            assert!(!face.contains(&0));
+            let x: Vec<_> = face
+                .iter()
+                .map(|p| [tetgen.point(*p, 0), tetgen.point(*p, 1), tetgen.point(*p, 2)])
+                .collect();
+            // the normal points outward (away from the center of the cube)
+            let normal = triangle_normal(&x[0], &x[1], &x[2]);
+            assert!(dot(&normal, &sub(&x[0], &[0.5, 0.5, 0.5])) > 0.0);
+            // consistently oriented faces traverse each edge once in each direction
+            for m in 0..3 {
+                *edges.entry((face[m], face[(m + 1) % 3])).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(edges.len(), 36);
+        assert!(edges
+            .iter()
+            .all(|((a, b), count)| *count == 1 && edges.contains_key(&(*b, *a))));
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+use crate::{Mesh, StrError, Tetgen, Triangle};
+use std::fmt;
+
+/// Implements a newtype index wrapper
+///
+/// The index is private and there is no conversion from `usize`; thus, an ID is either returned
+/// by the typed API or created explicitly with `new`.
+macro_rules! typed_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(usize);
+
+        impl $name {
+            /// Allocates a new ID with the given index
+            pub fn new(index: usize) -> Self {
+                $name(index)
+            }
+
+            /// Returns the underlying index
+            pub fn index(self) -> usize {
+                self.0
+            }
+        }
+
+        impl From<$name> for usize {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+typed_id!(
+    /// Holds the index of a point
+    ///
+    /// A plain index is not converted to a point ID implicitly; e.g., the following does not compile:
+    ///
+    /// ```compile_fail
+    /// use tritet::{CellId, PointId};
+    /// let cell = CellId::new(0);
+    /// let point: PointId = PointId::from(cell.index());
+    /// ```
+    PointId
+);
+
+typed_id!(
+    /// Holds the index of a cell (triangle or tetrahedron)
+    CellId
+);
+
+typed_id!(
+    /// Holds the index of a segment (input of Triangle)
+    SegmentId
+);
+
+/// Implements the typed API
+///
+/// These functions call the functions with `usize` indices; thus, the typed and untyped APIs may be mixed.
+impl Triangle {
+    /// Sets a point with a typed index (see `set_point`)
+    pub fn set_point_by_id(&mut self, point: PointId, x: f64, y: f64) -> Result<&mut Self, StrError> {
+        self.set_point(point.0, x, y)
+    }
+
+    /// Sets the segment with typed indices (see `set_segment`)
+    pub fn set_segment_by_id(&mut self, segment: SegmentId, a: PointId, b: PointId) -> Result<&mut Self, StrError> {
+        self.set_segment(segment.0, a.0, b.0)
+    }
+
+    /// Sets the marker of a segment with a typed index (see `set_segment_marker`)
+    pub fn set_segment_marker_by_id(&mut self, segment: SegmentId, marker: i32) -> Result<&mut Self, StrError> {
+        self.set_segment_marker(segment.0, marker)
+    }
+
+    /// Returns an iterator over the IDs of the points of the output
+    pub fn point_ids(&self) -> impl Iterator<Item = PointId> {
+        (0..self.npoint()).map(PointId)
+    }
+
+    /// Returns an iterator over the IDs of the triangles of the output
+    pub fn cell_ids(&self) -> impl Iterator<Item = CellId> {
+        (0..self.ntriangle()).map(CellId)
+    }
+
+    /// Returns the x-y coordinates of a point (zero if `point` is out of range)
+    pub fn point_coords(&self, point: PointId) -> [f64; 2] {
+        [self.point(point.0, 0), self.point(point.0, 1)]
+    }
+
+    /// Returns the marker of a point (see `point_marker`)
+    pub fn point_marker_by_id(&self, point: PointId) -> i32 {
+        self.point_marker(point.0)
+    }
+
+    /// Returns the ID of a triangle's node (see `triangle_node`)
+    pub fn cell_point(&self, cell: CellId, m: usize) -> PointId {
+        PointId(self.triangle_node(cell.0, m))
+    }
+
+    /// Returns the attribute ID of a triangle (see `triangle_attribute`)
+    pub fn cell_attribute(&self, cell: CellId) -> i32 {
+        self.triangle_attribute(cell.0)
+    }
+
+    /// Returns the neighbor of a triangle across a side (see `triangle_neighbor`)
+    pub fn cell_neighbor(&self, cell: CellId, side: usize) -> Option<CellId> {
+        self.triangle_neighbor(cell.0, side).map(CellId)
+    }
+
+    /// Returns the ID of an edge's endpoint (see `edge_point`)
+    pub fn edge_point_id(&self, edge: usize, side: usize) -> PointId {
+        PointId(self.edge_point(edge, side))
+    }
+}
+
+/// Implements the typed API
+///
+/// These functions call the functions with `usize` indices; thus, the typed and untyped APIs may be mixed.
+impl Tetgen {
+    /// Sets a point with a typed index (see `set_point`)
+    pub fn set_point_by_id(&mut self, point: PointId, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+        self.set_point(point.0, x, y, z)
+    }
+
+    /// Sets a point of a facet with a typed point index (see `set_facet_point`)
+    pub fn set_facet_point_by_id(&mut self, facet: usize, m: usize, point: PointId) -> Result<&mut Self, StrError> {
+        self.set_facet_point(facet, m, point.0)
+    }
+
+    /// Returns an iterator over the IDs of the points of the output
+    pub fn point_ids(&self) -> impl Iterator<Item = PointId> {
+        (0..self.npoint()).map(PointId)
+    }
+
+    /// Returns an iterator over the IDs of the tetrahedra of the output
+    pub fn cell_ids(&self) -> impl Iterator<Item = CellId> {
+        (0..self.ntet()).map(CellId)
+    }
+
+    /// Returns the x-y-z coordinates of a point (zero if `point` is out of range)
+    pub fn point_coords(&self, point: PointId) -> [f64; 3] {
+        [self.point(point.0, 0), self.point(point.0, 1), self.point(point.0, 2)]
+    }
+
+    /// Returns the ID of a tetrahedron's node (see `tet_node`)
+    pub fn cell_point(&self, cell: CellId, m: usize) -> PointId {
+        PointId(self.tet_node(cell.0, m))
+    }
+
+    /// Returns the attribute ID of a tetrahedron (see `tet_attribute`)
+    pub fn cell_attribute(&self, cell: CellId) -> i32 {
+        self.tet_attribute(cell.0)
+    }
+
+    /// Returns the neighbor of a tetrahedron across a face (see `tet_neighbor`)
+    pub fn cell_neighbor(&self, cell: CellId, m: usize) -> Option<CellId> {
+        self.tet_neighbor(cell.0, m).map(CellId)
+    }
+
+    /// Returns the ID of a marked face's node (see `marked_face_node`)
+    pub fn marked_face_point(&self, face: usize, m: usize) -> PointId {
+        PointId(self.marked_face_node(face, m))
+    }
+}
+
+/// Implements the typed API
+///
+/// These functions call the functions with `usize` indices; thus, the typed and untyped APIs may be mixed.
+impl<const DIM: usize> Mesh<DIM> {
+    /// Returns an iterator over the IDs of the points
+    pub fn point_ids(&self) -> impl Iterator<Item = PointId> {
+        (0..self.points.len()).map(PointId)
+    }
+
+    /// Returns an iterator over the IDs of the cells
+    pub fn cell_ids(&self) -> impl Iterator<Item = CellId> {
+        (0..self.cells.len()).map(CellId)
+    }
+
+    /// Returns the coordinates of a point (panics if `point` is out of range)
+    pub fn point_coords(&self, point: PointId) -> [f64; DIM] {
+        self.points[point.0]
+    }
+
+    /// Returns the ID of a cell's node (panics if `cell` or `m` is out of range)
+    pub fn cell_point(&self, cell: CellId, m: usize) -> PointId {
+        PointId(self.cells[cell.0][m])
+    }
+
+    /// Returns the attribute ID of a cell (panics if `cell` is out of range)
+    pub fn cell_attribute(&self, cell: CellId) -> i32 {
+        self.attributes[cell.0]
+    }
+
+    /// Returns the centroid of a cell (see `centroid`)
+    pub fn cell_centroid(&self, cell: CellId) -> [f64; DIM] {
+        self.centroid(cell.0)
+    }
+
+    /// Returns the measure of a cell (see `measure`)
+    pub fn cell_measure(&self, cell: CellId) -> f64 {
+        self.measure(cell.0)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{CellId, PointId, SegmentId};
+    use crate::fixtures::{square, CUBE_FACETS, CUBE_POINTS};
+    use crate::{Mesh, StrError, Tetgen, Triangle};
+
+    #[test]
+    fn ids_work() {
+        let p = PointId::new(3);
+        let c = CellId::new(4);
+        assert_eq!(p.index(), 3);
+        assert_eq!(usize::from(c), 4);
+        assert_eq!(format!("{}", SegmentId::new(5)), "5");
+        assert_eq!(format!("{:?}", p), "PointId(3)");
+        assert!(PointId::new(1) < PointId::new(2));
+    }
+
+    #[test]
+    fn triangle_typed_api_works() -> Result<(), StrError> {
+        let p: Vec<PointId> = (0..4).map(PointId::new).collect();
+        let s: Vec<SegmentId> = (0..4).map(SegmentId::new).collect();
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point_by_id(p[0], 0.0, 0.0)?
+            .set_point_by_id(p[1], 1.0, 0.0)?
+            .set_point_by_id(p[2], 1.0, 1.0)?
+            .set_point_by_id(p[3], 0.0, 1.0)?;
+        for i in 0..4 {
+            triangle
+                .set_segment_by_id(s[i], p[i], p[(i + 1) % 4])?
+                .set_segment_marker_by_id(s[i], -10 - (i as i32))?;
+        }
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.point_ids().collect::<Vec<_>>(), p);
+        let cells: Vec<CellId> = triangle.cell_ids().collect();
+        assert_eq!(cells, &[CellId::new(0), CellId::new(1)]);
+        assert_eq!(triangle.point_coords(p[2]), [1.0, 1.0]);
+        assert_eq!(triangle.cell_attribute(cells[0]), 0);
+        let neighbors: Vec<_> = (0..3)
+            .filter_map(|side| triangle.cell_neighbor(cells[0], side))
+            .collect();
+        assert_eq!(neighbors, &[cells[1]]);
+        assert_eq!(triangle.point_marker_by_id(p[1]), triangle.point_marker(1));
+        assert_eq!(triangle.point_marker_by_id(p[1]), -11);
+        for edge in 0..triangle.nedge() {
+            for side in 0..2 {
+                assert_eq!(
+                    triangle.edge_point_id(edge, side).index(),
+                    triangle.edge_point(edge, side)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_typed_api_works() -> Result<(), StrError> {
+        let p: Vec<PointId> = (0..8).map(PointId::new).collect();
+        let mut tetgen = Tetgen::new(8, Some(vec![4; 6]), None, None)?;
+        for (point, x) in p.iter().zip(CUBE_POINTS) {
+            tetgen.set_point_by_id(*point, x[0], x[1], x[2])?;
+        }
+        for (facet, corners) in CUBE_FACETS.iter().enumerate() {
+            for (m, corner) in corners.iter().enumerate() {
+                tetgen.set_facet_point_by_id(facet, m, p[*corner])?;
+            }
+        }
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.point_ids().collect::<Vec<_>>(), p);
+        let cells: Vec<CellId> = tetgen.cell_ids().collect();
+        assert_eq!(cells.len(), tetgen.ntet());
+        assert_eq!(tetgen.point_coords(p[6]), [1.0, 1.0, 1.0]);
+        for cell in &cells {
+            for m in 0..4 {
+                assert_eq!(tetgen.cell_point(*cell, m).index(), tetgen.tet_node(cell.index(), m));
+                assert_eq!(
+                    tetgen.cell_neighbor(*cell, m).map(|c| c.index()),
+                    tetgen.tet_neighbor(cell.index(), m)
+                );
+            }
+            assert_eq!(tetgen.cell_attribute(*cell), 0);
+        }
+        assert!(tetgen.nmarked_face() > 0);
+        for face in 0..tetgen.nmarked_face() {
+            for m in 0..3 {
+                assert_eq!(
+                    tetgen.marked_face_point(face, m).index(),
+                    tetgen.marked_face_node(face, m)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn mesh_typed_api_works() -> Result<(), StrError> {
+        let triangle = square()?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let mesh: Mesh<2> = triangle.to_mesh();
+        assert_eq!(mesh.point_ids().count(), 4);
+        let cells: Vec<CellId> = mesh.cell_ids().collect();
+        assert_eq!(cells.len(), 2);
+        for cell in cells {
+            let nodes: Vec<PointId> = (0..3).map(|m| mesh.cell_point(cell, m)).collect();
+            assert_eq!(nodes, (0..3).map(|m| triangle.cell_point(cell, m)).collect::<Vec<_>>());
+            assert_eq!(mesh.point_coords(nodes[0]), triangle.point_coords(nodes[0]));
+            assert_eq!(mesh.cell_attribute(cell), 0);
+            assert_eq!(mesh.cell_centroid(cell), mesh.centroid(cell.index()));
+            assert!(f64::abs(mesh.cell_measure(cell) - 0.5) < 1e-15);
+        }
+        Ok(())
+    }
+}
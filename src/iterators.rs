@@ -0,0 +1,394 @@
+use crate::vector::{dot, sub, triangle_normal};
+use crate::{CsrGraph, Tetgen, Triangle};
+use std::collections::HashMap;
+
+/// Defines entities with a marker (e.g., boundary edges and faces)
+pub trait Marked {
+    /// Returns the marker
+    fn marker(&self) -> i32;
+}
+
+/// Holds an edge of the output of Triangle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Edge {
+    /// Holds the index of the edge (see `Triangle::edge_point`)
+    pub index: usize,
+
+    /// Holds the IDs of the endpoints
+    pub points: [usize; 2],
+
+    /// Holds the marker
+    pub marker: i32,
+}
+
+/// Holds a marked face of the output of Tetgen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Face {
+    /// Holds the index of the marked face (see `Tetgen::marked_face_node`)
+    pub index: usize,
+
+    /// Holds the IDs of the corners
+    pub points: [usize; 3],
+
+    /// Holds the marker
+    pub marker: i32,
+}
+
+impl Marked for Edge {
+    fn marker(&self) -> i32 {
+        self.marker
+    }
+}
+
+impl Marked for Face {
+    fn marker(&self) -> i32 {
+        self.marker
+    }
+}
+
+/// Iterates over the edges of the output of Triangle (see `Triangle::edges`)
+pub struct Edges<'a> {
+    triangle: &'a Triangle,
+    next: usize,
+    nedge: usize,
+}
+
+impl<'a> Iterator for Edges<'a> {
+    type Item = Edge;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.nedge {
+            return None;
+        }
+        let index = self.next;
+        self.next += 1;
+        Some(Edge {
+            index,
+            points: [self.triangle.edge_point(index, 0), self.triangle.edge_point(index, 1)],
+            marker: self.triangle.edge_marker(index),
+        })
+    }
+}
+
+/// Iterates over the marked faces of the output of Tetgen (see `Tetgen::marked_faces`)
+pub struct Faces<'a> {
+    tetgen: &'a Tetgen,
+    next: usize,
+    nface: usize,
+}
+
+impl<'a> Iterator for Faces<'a> {
+    type Item = Face;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.nface {
+            return None;
+        }
+        let index = self.next;
+        self.next += 1;
+        Some(Face {
+            index,
+            points: [
+                self.tetgen.marked_face_node(index, 0),
+                self.tetgen.marked_face_node(index, 1),
+                self.tetgen.marked_face_node(index, 2),
+            ],
+            marker: self.tetgen.marked_face_marker(index),
+        })
+    }
+}
+
+/// Iterates over the entities with a given marker (see `MarkerFilter::with_marker`)
+pub struct WithMarker<I> {
+    iter: I,
+    marker: i32,
+}
+
+impl<I> Iterator for WithMarker<I>
+where
+    I: Iterator,
+    I::Item: Marked,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let marker = self.marker;
+        self.iter.find(|item| item.marker() == marker)
+    }
+}
+
+/// Adds marker filters to iterators over marked entities
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{MarkerFilter, StrError, Triangle};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut triangle = Triangle::new(4, None, None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 1.0, 1.0)?
+///         .set_point(3, 0.0, 1.0)?;
+///     triangle.generate_delaunay(false)?;
+///     assert_eq!(triangle.edges().count(), 5);
+///     assert_eq!(triangle.edges().with_marker(1).count(), 4);
+///     Ok(())
+/// }
+/// ```
+pub trait MarkerFilter: Iterator + Sized
+where
+    Self::Item: Marked,
+{
+    /// Returns a lazy iterator over the entities with the given marker
+    fn with_marker(self, marker: i32) -> WithMarker<Self> {
+        WithMarker { iter: self, marker }
+    }
+}
+
+impl<I> MarkerFilter for I
+where
+    I: Iterator,
+    I::Item: Marked,
+{
+}
+
+impl Triangle {
+    /// Returns an iterator over the edges of the triangulation (see `nedge`)
+    pub fn edges(&self) -> Edges<'_> {
+        Edges {
+            triangle: self,
+            next: 0,
+            nedge: self.nedge(),
+        }
+    }
+
+    /// Returns the endpoints of the marked edges grouped by marker
+    ///
+    /// The interior edges (marker 0) are not included. The boundary edges without an explicit
+    /// marker, including the ones on the boundary of holes, have the marker 1 (see `edge_marker`).
+    /// The edges of each marker are given in the order of their indices.
+    pub fn edges_by_marker(&self) -> HashMap<i32, Vec<(usize, usize)>> {
+        let mut groups: HashMap<i32, Vec<(usize, usize)>> = HashMap::new();
+        for edge in self.edges().filter(|edge| edge.marker != 0) {
+            groups
+                .entry(edge.marker)
+                .or_default()
+                .push((edge.points[0], edge.points[1]));
+        }
+        groups
+    }
+}
+
+impl Tetgen {
+    /// Returns an iterator over the marked (boundary) faces (see `nmarked_face`)
+    pub fn marked_faces(&self) -> Faces<'_> {
+        Faces {
+            tetgen: self,
+            next: 0,
+            nface: self.nmarked_face(),
+        }
+    }
+
+    /// Returns the corners of the marked faces grouped by marker with outward normals
+    ///
+    /// The corners of each face are ordered counterclockwise when seen from outside of the
+    /// attached tetrahedron; i.e., the normal given by the right-hand rule points outward.
+    /// The faces shared by two tetrahedra (e.g., between regions) are oriented outward from the
+    /// tetrahedron with the smallest index. The faces of each marker are given in the order of
+    /// their indices (see `marked_face_node`).
+    pub fn marked_faces_by_marker(&self) -> HashMap<i32, Vec<[usize; 3]>> {
+        let map = self.vertex_to_cell_map();
+        let mut groups: HashMap<i32, Vec<[usize; 3]>> = HashMap::new();
+        for face in self.marked_faces() {
+            let corners = self.outward_face(&map, face.points).unwrap_or(face.points);
+            groups.entry(face.marker).or_default().push(corners);
+        }
+        groups
+    }
+
+    /// Returns the corners of a face ordered such that the normal points out of the attached tetrahedron
+    ///
+    /// The attached tetrahedron is the one with the smallest index among the tetrahedra sharing
+    /// the face; `map` is the point-to-cell map (see `vertex_to_cell_map`). Returns None if no
+    /// tetrahedron has the three corners.
+    pub(crate) fn outward_face(&self, map: &CsrGraph, corners: [usize; 3]) -> Option<[usize; 3]> {
+        let [a, b, c] = corners;
+        let cell = *map
+            .neighbors(a)
+            .iter()
+            .find(|cell| map.neighbors(b).contains(cell) && map.neighbors(c).contains(cell))?;
+        let d = (0..4).map(|m| self.tet_node(cell, m)).find(|p| !corners.contains(p))?;
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let (xa, xb, xc, xd) = (coords(a), coords(b), coords(c), coords(d));
+        if dot(&triangle_normal(&xa, &xb, &xc), &sub(&xd, &xa)) > 0.0 {
+            Some([a, c, b])
+        } else {
+            Some([a, b, c])
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, MarkerFilter};
+    use crate::fixtures::cube_points;
+    use crate::vector::{dot, triangle_normal};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn edges_by_marker_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(8, Some(8), None, Some(1))?;
+        assert_eq!(triangle.edges_by_marker().len(), 0);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 3.0, 0.0)?
+            .set_point(2, 3.0, 3.0)?
+            .set_point(3, 0.0, 3.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 2.0, 1.0)?
+            .set_point(6, 2.0, 2.0)?
+            .set_point(7, 1.0, 2.0)?;
+        for i in 0..4 {
+            triangle
+                .set_segment(i, i, (i + 1) % 4)?
+                .set_segment(4 + i, 4 + i, 4 + (i + 1) % 4)?
+                .set_segment_marker(i, -10 - (i as i32))?;
+        }
+        triangle.set_hole(0, 1.5, 1.5)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let groups = triangle.edges_by_marker();
+        let mut markers: Vec<_> = groups.keys().copied().collect();
+        markers.sort();
+        assert_eq!(markers, &[-13, -12, -11, -10, 1]);
+        for (a, b) in &groups[&-10] {
+            assert_eq!(triangle.point(*a, 1), 0.0);
+            assert_eq!(triangle.point(*b, 1), 0.0);
+        }
+        let length: f64 = groups[&-10]
+            .iter()
+            .map(|(a, b)| f64::abs(triangle.point(*b, 0) - triangle.point(*a, 0)))
+            .sum();
+        assert_eq!(length, 3.0);
+
+        // the boundary of the hole has the implicit marker 1
+        for (a, b) in &groups[&1] {
+            for p in [*a, *b] {
+                let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+                assert!(x == 1.0 || x == 2.0 || y == 1.0 || y == 2.0);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn marked_faces_by_marker_works() -> Result<(), StrError> {
+        let mut tetgen = cube_points()?;
+        assert_eq!(tetgen.marked_faces_by_marker().len(), 0);
+        // the facets are given with random orientations
+        let faces = [
+            [0, 4, 7, 3],
+            [1, 5, 6, 2],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 3, 2, 1],
+            [4, 7, 6, 5],
+        ];
+        for (index, face) in faces.iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, -1 - (index as i32))?;
+        }
+        tetgen.generate_mesh(false, false, Some(0.1), None)?;
+        let groups = tetgen.marked_faces_by_marker();
+        assert_eq!(groups.len(), 6);
+        assert_eq!(
+            groups.values().map(|faces| faces.len()).sum::<usize>(),
+            tetgen.nmarked_face()
+        );
+        let outward = [
+            [-1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0],
+        ];
+        let x = |p: usize| [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)];
+        for (index, n) in outward.iter().enumerate() {
+            for [a, b, c] in &groups[&(-1 - (index as i32))] {
+                let normal = triangle_normal(&x(*a), &x(*b), &x(*c));
+                assert!(dot(&normal, n) > 0.0);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn edges_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        assert_eq!(triangle.edges().count(), 0);
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        let edges: Vec<Edge> = triangle.edges().collect();
+        assert_eq!(edges.len(), triangle.nedge());
+        assert_eq!(edges[2].index, 2);
+        assert_eq!(edges[2].points[1], triangle.edge_point(2, 1));
+        let boundary: Vec<Edge> = triangle.edges().with_marker(1).collect();
+        assert!(!boundary.is_empty());
+        assert!(boundary.len() < edges.len());
+        for edge in &boundary {
+            for p in edge.points {
+                let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+                assert!(x < 1e-15 || y < 1e-15 || f64::abs(x + y - 1.0) < 1e-15);
+            }
+        }
+        assert_eq!(triangle.edges().with_marker(-1).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn marked_faces_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen
+            .set_facet_point(0, 0, 0)?
+            .set_facet_point(0, 1, 2)?
+            .set_facet_point(0, 2, 1)?
+            .set_facet_point(1, 0, 0)?
+            .set_facet_point(1, 1, 1)?
+            .set_facet_point(1, 2, 3)?
+            .set_facet_point(2, 0, 0)?
+            .set_facet_point(2, 1, 3)?
+            .set_facet_point(2, 2, 2)?
+            .set_facet_point(3, 0, 1)?
+            .set_facet_point(3, 1, 2)?
+            .set_facet_point(3, 2, 3)?;
+        tetgen.set_facet_marker(0, -10)?.set_facet_marker(3, -20)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.marked_faces().count(), 4);
+        let bottom: Vec<[usize; 3]> = tetgen.marked_faces().with_marker(-10).map(|f| f.points).collect();
+        assert_eq!(bottom.len(), 1);
+        let mut points = bottom[0];
+        points.sort();
+        assert_eq!(points, [0, 1, 2]);
+        assert_eq!(tetgen.marked_faces().with_marker(-20).count(), 1);
+        assert_eq!(tetgen.marked_faces().with_marker(0).count(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+/// Defines the maximum number of attempts to remove a degeneracy by jittering the points
+pub(crate) const JITTER_MAX_ATTEMPTS: usize = 5;
+
+/// Returns a pseudo-random number in [-1, 1] for a coordinate of a point
+///
+/// The numbers are deterministic (splitmix64 hash); thus, the runs (and replays) are reproducible.
+pub(crate) fn jitter_offset(point: usize, dim: usize, attempt: usize) -> f64 {
+    let mut z = (point as u64)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add((dim as u64) << 32)
+        .wrapping_add(attempt as u64);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    2.0 * ((z >> 11) as f64 / (1u64 << 53) as f64) - 1.0
+}
+
+/// Returns the input points missing from the output cells (e.g., duplicates or degenerate sets)
+///
+/// All points are returned if there are no cells. Otherwise, the coordinates of the input points are
+/// matched to the coordinates of the output points used by the cells, allowing each output point
+/// to match a single input point; hence, the second copy of a duplicated point is returned.
+pub(crate) fn find_degenerate_points<const N: usize>(input: &[[f64; N]], used_output: &[[f64; N]]) -> Vec<usize> {
+    if used_output.is_empty() {
+        return (0..input.len()).collect();
+    }
+    let key = |x: &[f64; N]| x.map(|v| v.to_bits());
+    let mut available: HashMap<[u64; N], usize> = HashMap::new();
+    for x in used_output {
+        *available.entry(key(x)).or_insert(0) += 1;
+    }
+    let mut points = Vec::new();
+    for (p, x) in input.iter().enumerate() {
+        match available.get_mut(&key(x)) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => points.push(p),
+        }
+    }
+    points
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{find_degenerate_points, jitter_offset};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn jitter_offset_works() {
+        let mut sum = 0.0;
+        for p in 0..1000 {
+            let r = jitter_offset(p, 1, 0);
+            assert!((-1.0..=1.0).contains(&r));
+            sum += r;
+        }
+        assert!(f64::abs(sum / 1000.0) < 0.1);
+        assert_eq!(jitter_offset(3, 0, 2), jitter_offset(3, 0, 2));
+        assert_ne!(jitter_offset(3, 0, 2), jitter_offset(3, 1, 2));
+        assert_ne!(jitter_offset(3, 0, 2), jitter_offset(3, 0, 3));
+    }
+
+    #[test]
+    fn find_degenerate_points_works() {
+        let input = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+        assert_eq!(find_degenerate_points(&input, &[]), &[0, 1, 2, 3]);
+        let used = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+        assert_eq!(find_degenerate_points(&input, &used), &[3]);
+        assert_eq!(find_degenerate_points(&input[..3], &used), &[] as &[usize]);
+    }
+
+    #[test]
+    fn triangle_jitter_works() -> Result<(), StrError> {
+        // collinear points
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 0);
+        assert_eq!(triangle.jittered_points(), &[] as &[usize]);
+        triangle.set_jitter_on_degeneracy(Some(1e-8));
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 1);
+        assert_eq!(triangle.jittered_points(), &[0, 1, 2]);
+        assert!(f64::abs(triangle.point(2, 0) - 2.0) <= 1e-8);
+        assert!(f64::abs(triangle.point(2, 1)) <= 1e-8);
+
+        // the input is not modified by the jitter
+        assert_eq!(triangle.input_point(2), [2.0, 0.0]);
+        triangle.set_jitter_on_degeneracy(None);
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 0);
+        assert_eq!(triangle.jittered_points(), &[] as &[usize]);
+
+        // duplicated point
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_point(3, 1.0, 0.0)?;
+        triangle.set_jitter_on_degeneracy(Some(1e-6));
+        triangle.generate_voronoi(false)?;
+        assert_eq!(triangle.jittered_points(), &[3]);
+        assert!(triangle.ntriangle() >= 2);
+        assert_eq!(triangle.point(0, 0), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_jitter_works() -> Result<(), StrError> {
+        // coplanar points
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 1.0, 1.0, 0.0)?;
+        tetgen.set_jitter_on_degeneracy(Some(1e-6));
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.jittered_points(), &[0, 1, 2, 3]);
+        assert_eq!(tetgen.input_point(3), [1.0, 1.0, 0.0]);
+        tetgen.set_jitter_on_degeneracy(None);
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 0);
+
+        // duplicated point
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 1.0, 0.0, 0.0)?;
+        tetgen.set_jitter_on_degeneracy(Some(1e-6));
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.jittered_points(), &[4]);
+        assert_eq!(tetgen.npoint(), 5);
+        assert!(tetgen.ntet() > 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{StrError, TriMesh};
+use std::collections::{HashMap, HashSet};
+
+/// Defines the name of the tag holding the side of each cell with respect to the level-set
+///
+/// The values are 1 (positive side), -1 (negative side), or 0 (all nodes on the level-set).
+pub const LEVELSET_SIDE_TAG: &str = "levelset_side";
+
+/// Defines the maximum number of iterations to find the zero of the level-set along an edge
+const ROOT_MAX_ITERATIONS: usize = 50;
+
+impl TriMesh {
+    /// Splits the cells crossed by the zero level-set of a function (experimental)
+    ///
+    /// The crossed edges are cut at the zero of `phi` (found by the Illinois variant of the
+    /// regula falsi method); thus, the new nodes are snapped onto the level-set. Each crossed
+    /// triangle is split into two or three triangles that inherit its attribute and tags. The
+    /// crossed facets are split into two facets that inherit the marker. The side of each
+    /// cell is saved in the `LEVELSET_SIDE_TAG` tag.
+    ///
+    /// Only linear triangles (3 nodes) are supported.
+    ///
+    /// # Output
+    ///
+    /// Returns `(mesh, interface)` where `interface` holds the edges (pairs of point IDs in the
+    /// new mesh) lying on the level-set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle, LEVELSET_SIDE_TAG};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?;
+    ///     triangle.generate_delaunay(false)?;
+    ///
+    ///     // the vertical line x = 0.3
+    ///     let (mesh, interface) = triangle.to_mesh().conform_to_levelset(|x| x[0] - 0.3)?;
+    ///     assert_eq!(mesh.ncell(), 6);
+    ///     assert_eq!(interface.len(), 2);
+    ///     for [a, b] in interface {
+    ///         assert!(f64::abs(mesh.points[a][0] - 0.3) < 1e-15);
+    ///         assert!(f64::abs(mesh.points[b][0] - 0.3) < 1e-15);
+    ///     }
+    ///     assert_eq!(mesh.tags.values(LEVELSET_SIDE_TAG).unwrap().len(), 6);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn conform_to_levelset<F>(&self, phi: F) -> Result<(TriMesh, Vec<[usize; 2]>), StrError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        if self.cells.iter().any(|cell| cell.len() != 3) {
+            return Err("conform_to_levelset supports linear triangles only");
+        }
+        let mut points = self.points.clone();
+        let mut values: Vec<f64> = points.iter().map(|x| phi(x)).collect();
+        let mut cut: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut cells = Vec::new();
+        let mut parents = Vec::new();
+        let mut interface = HashSet::new();
+
+        // returns the new point on the edge (a, b) or None if the edge is not crossed
+        let mut cut_edge = |a: usize, b: usize, points: &mut Vec<[f64; 2]>, values: &mut Vec<f64>| {
+            if values[a] * values[b] >= 0.0 {
+                return None;
+            }
+            let key = (usize::min(a, b), usize::max(a, b));
+            let p = *cut.entry(key).or_insert_with(|| {
+                let x = find_root(&phi, &points[key.0], &points[key.1], values[key.0], values[key.1]);
+                points.push(x);
+                values.push(0.0);
+                points.len() - 1
+            });
+            Some(p)
+        };
+
+        for (c, cell) in self.cells.iter().enumerate() {
+            let crossing: Vec<Option<usize>> = (0..3)
+                .map(|s| cut_edge(cell[s], cell[(s + 1) % 3], &mut points, &mut values))
+                .collect();
+            let ncrossing = crossing.iter().filter(|p| p.is_some()).count();
+            let mut push = |nodes: [usize; 3]| {
+                cells.push(nodes.to_vec());
+                parents.push(c);
+            };
+            if ncrossing == 0 {
+                // the cell is not crossed, but some of its edges may lie on the level-set
+                for s in 0..3 {
+                    let (a, b) = (cell[s], cell[(s + 1) % 3]);
+                    if values[a] == 0.0 && values[b] == 0.0 {
+                        interface.insert((usize::min(a, b), usize::max(a, b)));
+                    }
+                }
+                push([cell[0], cell[1], cell[2]]);
+            } else if ncrossing == 1 {
+                // one node is on the level-set and the opposite side is crossed
+                let s = crossing.iter().position(|p| p.is_some()).unwrap();
+                let p = crossing[s].unwrap();
+                let (b, c, a) = (cell[s], cell[(s + 1) % 3], cell[(s + 2) % 3]);
+                push([a, b, p]);
+                push([a, p, c]);
+                interface.insert((usize::min(a, p), usize::max(a, p)));
+            } else {
+                // node a is alone on one side; nodes b and c are on the other side
+                let s = (0..3).find(|s| crossing[*s].is_none()).unwrap();
+                let (b, c, a) = (cell[s], cell[(s + 1) % 3], cell[(s + 2) % 3]);
+                let p_ca = crossing[(s + 1) % 3].unwrap();
+                let p_ab = crossing[(s + 2) % 3].unwrap();
+                push([a, p_ab, p_ca]);
+                if distance(&points[p_ab], &points[c]) < distance(&points[b], &points[p_ca]) {
+                    push([p_ab, b, c]);
+                    push([p_ab, c, p_ca]);
+                } else {
+                    push([p_ab, b, p_ca]);
+                    push([b, c, p_ca]);
+                }
+                interface.insert((usize::min(p_ab, p_ca), usize::max(p_ab, p_ca)));
+            }
+        }
+
+        // split the crossed facets
+        let mut facets = Vec::new();
+        let mut facet_markers = Vec::new();
+        for (facet, marker) in self.facets.iter().zip(&self.facet_markers) {
+            let (a, b) = (facet[0], facet[1]);
+            match cut.get(&(usize::min(a, b), usize::max(a, b))) {
+                Some(p) => {
+                    facets.push(vec![a, *p]);
+                    facets.push(vec![*p, b]);
+                    facet_markers.extend_from_slice(&[*marker, *marker]);
+                }
+                None => {
+                    facets.push(facet.clone());
+                    facet_markers.push(*marker);
+                }
+            }
+        }
+
+        let mut mesh = TriMesh {
+            points,
+            attributes: parents.iter().map(|c| self.attributes[*c]).collect(),
+            facets,
+            facet_markers,
+            tags: self.tags.select(&parents),
+            cells,
+        };
+        if mesh.tags.values(LEVELSET_SIDE_TAG).is_none() {
+            mesh.add_tag(LEVELSET_SIDE_TAG, 0)?;
+        }
+        for c in 0..mesh.cells.len() {
+            let sum: f64 = mesh.cells[c].iter().map(|p| values[*p]).sum();
+            let side = if sum > 0.0 {
+                1
+            } else if sum < 0.0 {
+                -1
+            } else {
+                0
+            };
+            mesh.set_tag(c, LEVELSET_SIDE_TAG, side)?;
+        }
+        let mut interface: Vec<[usize; 2]> = interface.into_iter().map(|(a, b)| [a, b]).collect();
+        interface.sort();
+        Ok((mesh, interface))
+    }
+}
+
+/// Returns the distance between two points
+fn distance(a: &[f64; 2], b: &[f64; 2]) -> f64 {
+    f64::sqrt((b[0] - a[0]) * (b[0] - a[0]) + (b[1] - a[1]) * (b[1] - a[1]))
+}
+
+/// Finds the zero of phi along the segment (xa, xb), given phi(xa) * phi(xb) < 0
+fn find_root<F>(phi: &F, xa: &[f64; 2], xb: &[f64; 2], fa: f64, fb: f64) -> [f64; 2]
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let at = |t: f64| [xa[0] + t * (xb[0] - xa[0]), xa[1] + t * (xb[1] - xa[1])];
+    let (mut ta, mut tb, mut fa, mut fb) = (0.0, 1.0, fa, fb);
+    let tolerance = 1e-14 * f64::max(fa.abs(), fb.abs());
+    for _ in 0..ROOT_MAX_ITERATIONS {
+        let t = tb - fb * (tb - ta) / (fb - fa);
+        let f = phi(&at(t));
+        if f * fb < 0.0 {
+            ta = tb;
+            fa = fb;
+        } else {
+            fa /= 2.0; // Illinois modification
+        }
+        tb = t;
+        fb = f;
+        if f.abs() <= tolerance || f64::abs(tb - ta) < f64::EPSILON {
+            break;
+        }
+    }
+    at(tb)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::LEVELSET_SIDE_TAG;
+    use crate::{CellTags, Mesh, TriMesh};
+
+    fn square() -> TriMesh {
+        Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![1, 2],
+            facets: vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]],
+            facet_markers: vec![-1, -2, -3, -4],
+            tags: CellTags::default(),
+        }
+    }
+
+    #[test]
+    fn conform_to_levelset_captures_errors() {
+        let mut mesh = square();
+        mesh.cells[0].push(3);
+        assert_eq!(
+            mesh.conform_to_levelset(|x| x[0]).err(),
+            Some("conform_to_levelset supports linear triangles only")
+        );
+    }
+
+    #[test]
+    fn conform_to_levelset_works_with_straight_line() {
+        let mesh = square();
+        let (sub, interface) = mesh.conform_to_levelset(|x| x[1] - 0.25).unwrap();
+        assert_eq!(sub.points.len(), 7);
+        assert_eq!(sub.ncell(), 6);
+        assert_eq!(interface.len(), 2);
+        let area: f64 = sub.measures().iter().sum();
+        assert!(f64::abs(area - 1.0) < 1e-15);
+        let sides = sub.tags.values(LEVELSET_SIDE_TAG).unwrap();
+        let mut below = 0.0;
+        for (c, side) in sides.iter().enumerate() {
+            assert!(sub.measure(c) > 0.0);
+            if *side < 0 {
+                below += sub.measure(c);
+            }
+        }
+        assert!(f64::abs(below - 0.25) < 1e-15);
+        assert_eq!(sub.attributes.iter().filter(|a| **a == 1).count(), 3);
+        assert_eq!(sub.facets.len(), 6);
+        assert_eq!(sub.facet_markers, &[-1, -2, -2, -3, -4, -4]);
+        for (facet, marker) in sub.facets.iter().zip(&sub.facet_markers) {
+            let x = if *marker == -2 { 1.0 } else { 0.0 };
+            if *marker == -2 || *marker == -4 {
+                assert!(facet.iter().all(|p| sub.points[*p][0] == x));
+            }
+        }
+    }
+
+    #[test]
+    fn conform_to_levelset_works_through_nodes() {
+        // the diagonal x = y passes through nodes 0 and 2
+        let mesh = square();
+        let (sub, interface) = mesh.conform_to_levelset(|x| x[0] - x[1]).unwrap();
+        assert_eq!(sub.ncell(), 2);
+        assert_eq!(interface, &[[0, 2]]);
+        assert_eq!(sub.tags.values(LEVELSET_SIDE_TAG), Some(&[1, -1][..]));
+
+        // the anti-diagonal x + y = 1 passes through nodes 1 and 3
+        let (sub, interface) = mesh.conform_to_levelset(|x| x[0] + x[1] - 1.0).unwrap();
+        assert_eq!(sub.ncell(), 4);
+        assert_eq!(interface.len(), 2);
+        for [a, b] in interface {
+            assert!(a == 4 || b == 4);
+        }
+        assert_eq!(sub.points[4], [0.5, 0.5]);
+    }
+
+    #[test]
+    fn conform_to_levelset_snaps_nodes_onto_circle() {
+        let mesh = square();
+        let phi = |x: &[f64]| x[0] * x[0] + x[1] * x[1] - 0.5;
+        let (sub, interface) = mesh.conform_to_levelset(phi).unwrap();
+        assert_eq!(interface.len(), 2);
+        for [a, b] in interface {
+            assert!(phi(&sub.points[a]).abs() < 1e-14);
+            assert!(phi(&sub.points[b]).abs() < 1e-14);
+        }
+    }
+}
@@ -3,17 +3,107 @@
 /// Defines a type alias for the error type as a static string
 pub type StrError = &'static str;
 
+mod backend;
+mod box_grid;
+mod cancellation;
+mod cleanup;
+mod colormap;
+mod connectivity;
+mod console;
 mod constants;
 mod conversion;
+mod decimate;
+mod extract;
+#[cfg(test)]
+mod fixtures;
+mod geojson;
+mod geometry2d;
+mod geometry3d;
+mod gltf;
+mod gmsh;
+mod hole_seeds;
+mod hull;
+mod ids;
+mod iterators;
+mod jitter;
+mod levelset;
+mod locate;
+mod measures;
+mod mesh;
+mod normals;
+mod obj;
+mod options;
+mod orientation;
 mod paraview;
+mod partition;
+mod periodic;
+#[cfg(feature = "plot")]
+mod plotting;
+mod poly_file;
+mod predicates;
+mod progress;
+mod provenance;
+mod pvd;
+mod quality;
+mod recorder;
+mod renumber;
+mod seeds;
+mod simplify;
+mod sizing;
+mod spatial_index;
+mod statistics;
+mod split_segments;
+mod structured;
+mod surface_files;
+mod svg;
+mod tags;
 mod tetgen;
+mod tetgen_files;
+mod transform;
 mod triangle;
+mod triangle_graphs;
+mod triangle_shapes;
+mod validation;
+mod vector;
+mod voronoi_cells;
+pub use crate::backend::*;
+pub use crate::cancellation::*;
+pub use crate::cleanup::*;
+pub use crate::colormap::*;
+pub use crate::connectivity::*;
+pub use crate::console::*;
+pub use crate::extract::*;
+pub use crate::geojson::*;
+pub use crate::geometry2d::*;
+pub use crate::geometry3d::*;
+pub use crate::gltf::*;
+pub use crate::gmsh::*;
+pub use crate::ids::*;
+pub use crate::iterators::*;
+pub use crate::levelset::*;
+pub use crate::mesh::*;
+pub use crate::options::*;
 pub use crate::paraview::*;
+pub use crate::partition::*;
+pub use crate::progress::*;
+pub use crate::provenance::*;
+pub use crate::pvd::*;
+pub use crate::quality::*;
+pub use crate::recorder::{replay, replay_script, Replay};
+pub use crate::seeds::*;
+pub use crate::simplify::*;
+pub use crate::spatial_index::*;
+pub use crate::statistics::*;
+pub use crate::split_segments::*;
+pub use crate::svg::*;
+pub use crate::tags::*;
 pub use crate::tetgen::*;
+pub use crate::transform::*;
 pub use crate::triangle::*;
+pub use crate::voronoi_cells::*;
 
-// run code from README file
-#[cfg(doctest)]
+// run code from README file (the examples draw figures)
+#[cfg(all(doctest, feature = "plot"))]
 mod test_readme {
     macro_rules! external_doc_test {
         ($x:expr) => {
@@ -0,0 +1,462 @@
+use crate::{StrError, Tetgen, Triangle};
+
+/// Holds the tolerance on the barycentric coordinates to accept a point on the boundary of a cell
+const LOCATE_TOLERANCE: f64 = 1e-12;
+
+impl Triangle {
+    /// Finds the triangle containing a point
+    ///
+    /// The search walks from triangle to triangle towards the point using the neighbors of the
+    /// triangles (see `triangle_neighbor`); if the walk leaves the mesh (e.g., in non-convex
+    /// domains) or takes too many steps, all triangles are checked (brute-force mode).
+    ///
+    /// Points on the boundary of a triangle are considered inside (up to a small tolerance);
+    /// thus, the index of any of the triangles sharing the point may be returned.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if the point is outside the mesh.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?;
+    ///     triangle.generate_delaunay(false)?;
+    ///     let cell = triangle.find_cell(0.9, 0.1).unwrap();
+    ///     assert!((0..3).any(|m| triangle.triangle_node(cell, m) == 1));
+    ///     assert_eq!(triangle.find_cell(2.0, 0.5), None);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find_cell(&self, x: f64, y: f64) -> Option<usize> {
+        let ntriangle = self.ntriangle();
+        let lambda = |cell: usize| {
+            let corners: Vec<[f64; 2]> = (0..3)
+                .map(|m| {
+                    let p = self.triangle_node(cell, m);
+                    [self.point(p, 0), self.point(p, 1)]
+                })
+                .collect();
+            triangle_barycentric(&corners, &[x, y])
+        };
+        // the side s goes from node s to node (s + 1) % 3; thus, it is opposite to node (s + 2) % 3
+        let opposite_side = [1, 2, 0];
+        find_cell(ntriangle, lambda, |cell, m| {
+            self.triangle_neighbor(cell, opposite_side[m])
+        })
+    }
+
+    /// Returns the barycentric coordinates of a point with respect to a triangle
+    ///
+    /// The coordinates correspond to the corners of the triangle (see `triangle_node`) and sum
+    /// up to one. All coordinates are in [0, 1] if the point is inside the triangle.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if `index` is out of range or the triangle is degenerate (zero area).
+    pub fn barycentric(&self, index: usize, x: f64, y: f64) -> Option<[f64; 3]> {
+        if index >= self.ntriangle() {
+            return None;
+        }
+        let corners: Vec<[f64; 2]> = (0..3)
+            .map(|m| {
+                let p = self.triangle_node(index, m);
+                [self.point(p, 0), self.point(p, 1)]
+            })
+            .collect();
+        triangle_barycentric(&corners, &[x, y])
+    }
+
+    /// Interpolates a field given at the points of the mesh
+    ///
+    /// The triangle containing the point is found with `find_cell` and the field is interpolated
+    /// with the shape functions of the triangle; i.e., linear or quadratic (with middle nodes).
+    ///
+    /// # Input
+    ///
+    /// * `values_at_points` -- the value of the field at each point (len = npoint)
+    /// * `x` -- the x-coordinate of the point
+    /// * `y` -- the y-coordinate of the point
+    ///
+    /// # Output
+    ///
+    /// Returns None if the point is outside the mesh.
+    pub fn interpolate(&self, values_at_points: &[f64], x: f64, y: f64) -> Result<Option<f64>, StrError> {
+        if values_at_points.len() != self.npoint() {
+            return Err("values_at_points.len() must be equal to npoint");
+        }
+        let cell = match self.find_cell(x, y) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        let l = match self.barycentric(cell, x, y) {
+            Some(l) => l,
+            None => return Ok(None),
+        };
+        let values: Vec<f64> = (0..self.nnode())
+            .map(|m| values_at_points[self.triangle_node(cell, m)])
+            .collect();
+        Ok(Some(interpolate(&l, &values, &[(0, 1), (1, 2), (2, 0)])))
+    }
+}
+
+impl Tetgen {
+    /// Finds the tetrahedron containing a point
+    ///
+    /// The search walks from tetrahedron to tetrahedron towards the point using the neighbors
+    /// of the tetrahedra (see `tet_neighbor`); if the walk leaves the mesh (e.g., in non-convex
+    /// domains) or takes too many steps, all tetrahedra are checked (brute-force mode).
+    ///
+    /// Points on the boundary of a tetrahedron are considered inside (up to a small tolerance);
+    /// thus, the index of any of the tetrahedra sharing the point may be returned.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if the point is outside the mesh.
+    pub fn find_cell(&self, x: f64, y: f64, z: f64) -> Option<usize> {
+        let ntet = self.ntet();
+        let lambda = |cell: usize| {
+            let corners: Vec<[f64; 3]> = (0..4)
+                .map(|m| {
+                    let p = self.tet_node(cell, m);
+                    [self.point(p, 0), self.point(p, 1), self.point(p, 2)]
+                })
+                .collect();
+            tet_barycentric(&corners, &[x, y, z])
+        };
+        find_cell(ntet, lambda, |cell, m| self.tet_neighbor(cell, m))
+    }
+
+    /// Returns the barycentric coordinates of a point with respect to a tetrahedron
+    ///
+    /// The coordinates correspond to the corners of the tetrahedron (see `tet_node`) and sum
+    /// up to one. All coordinates are in [0, 1] if the point is inside the tetrahedron.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if `index` is out of range or the tetrahedron is degenerate (zero volume).
+    pub fn barycentric(&self, index: usize, x: f64, y: f64, z: f64) -> Option<[f64; 4]> {
+        if index >= self.ntet() {
+            return None;
+        }
+        let corners: Vec<[f64; 3]> = (0..4)
+            .map(|m| {
+                let p = self.tet_node(index, m);
+                [self.point(p, 0), self.point(p, 1), self.point(p, 2)]
+            })
+            .collect();
+        tet_barycentric(&corners, &[x, y, z])
+    }
+
+    /// Interpolates a field given at the points of the mesh
+    ///
+    /// The tetrahedron containing the point is found with `find_cell` and the field is
+    /// interpolated with the shape functions of the tetrahedron; i.e., linear or quadratic
+    /// (with middle nodes).
+    ///
+    /// # Input
+    ///
+    /// * `values_at_points` -- the value of the field at each point (len = npoint)
+    /// * `x` -- the x-coordinate of the point
+    /// * `y` -- the y-coordinate of the point
+    /// * `z` -- the z-coordinate of the point
+    ///
+    /// # Output
+    ///
+    /// Returns None if the point is outside the mesh.
+    pub fn interpolate(&self, values_at_points: &[f64], x: f64, y: f64, z: f64) -> Result<Option<f64>, StrError> {
+        if values_at_points.len() != self.npoint() {
+            return Err("values_at_points.len() must be equal to npoint");
+        }
+        let cell = match self.find_cell(x, y, z) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        let l = match self.barycentric(cell, x, y, z) {
+            Some(l) => l,
+            None => return Ok(None),
+        };
+        let values: Vec<f64> = (0..self.nnode())
+            .map(|m| values_at_points[self.tet_node(cell, m)])
+            .collect();
+        let edges = [(0, 1), (1, 2), (0, 2), (0, 3), (1, 3), (2, 3)];
+        Ok(Some(interpolate(&l, &values, &edges)))
+    }
+}
+
+/// Interpolates the values at the nodes of a simplex given the barycentric coordinates
+///
+/// The first N values are at the corners. If there are more values, they are at the middle
+/// nodes of the given edges (in order) and quadratic shape functions are used.
+fn interpolate<const N: usize>(l: &[f64; N], values: &[f64], edges: &[(usize, usize)]) -> f64 {
+    if values.len() == N {
+        return (0..N).map(|i| l[i] * values[i]).sum();
+    }
+    let corners: f64 = (0..N).map(|i| l[i] * (2.0 * l[i] - 1.0) * values[i]).sum();
+    let middles: f64 = edges
+        .iter()
+        .enumerate()
+        .map(|(k, (i, j))| 4.0 * l[*i] * l[*j] * values[N + k])
+        .sum();
+    corners + middles
+}
+
+/// Finds the cell containing a point given the barycentric coordinates and the neighbors of the cells
+///
+/// The neighbor `m` of a cell is opposite to the node `m`. The barycentric coordinates are
+/// None for degenerate cells.
+fn find_cell<const N: usize, F, G>(ncell: usize, lambda: F, neighbor: G) -> Option<usize>
+where
+    F: Fn(usize) -> Option<[f64; N]>,
+    G: Fn(usize, usize) -> Option<usize>,
+{
+    if ncell == 0 {
+        return None;
+    }
+
+    // walk towards the point by crossing the facet opposite to the most negative coordinate
+    let mut cell = 0;
+    for _ in 0..ncell {
+        let l = match lambda(cell) {
+            Some(l) => l,
+            None => break,
+        };
+        let m = (0..N).min_by(|a, b| l[*a].total_cmp(&l[*b])).unwrap();
+        if l[m] >= -LOCATE_TOLERANCE {
+            return Some(cell);
+        }
+        match neighbor(cell, m) {
+            Some(next) => cell = next,
+            None => break,
+        }
+    }
+
+    // brute-force mode
+    (0..ncell).find(|cell| match lambda(*cell) {
+        Some(l) => l.iter().all(|v| *v >= -LOCATE_TOLERANCE),
+        None => false,
+    })
+}
+
+/// Returns the barycentric coordinates of a point with respect to a triangle
+///
+/// Returns None if the triangle is degenerate (zero area).
+pub(crate) fn triangle_barycentric(corners: &[[f64; 2]], x: &[f64; 2]) -> Option<[f64; 3]> {
+    let (a, b, c) = (corners[0], corners[1], corners[2]);
+    let det = (b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]);
+    if det == 0.0 {
+        return None;
+    }
+    let l1 = ((x[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (x[1] - a[1])) / det;
+    let l2 = ((b[0] - a[0]) * (x[1] - a[1]) - (x[0] - a[0]) * (b[1] - a[1])) / det;
+    Some([1.0 - l1 - l2, l1, l2])
+}
+
+/// Returns the barycentric coordinates of a point with respect to a tetrahedron
+///
+/// Returns None if the tetrahedron is degenerate (zero volume).
+pub(crate) fn tet_barycentric(corners: &[[f64; 3]], x: &[f64; 3]) -> Option<[f64; 4]> {
+    let sub = |u: &[f64; 3], v: &[f64; 3]| [u[0] - v[0], u[1] - v[1], u[2] - v[2]];
+    let triple = |u: &[f64; 3], v: &[f64; 3], w: &[f64; 3]| {
+        u[0] * (v[1] * w[2] - v[2] * w[1]) + u[1] * (v[2] * w[0] - v[0] * w[2]) + u[2] * (v[0] * w[1] - v[1] * w[0])
+    };
+    let a = sub(&corners[1], &corners[0]);
+    let b = sub(&corners[2], &corners[0]);
+    let c = sub(&corners[3], &corners[0]);
+    let d = sub(x, &corners[0]);
+    let det = triple(&a, &b, &c);
+    if det == 0.0 {
+        return None;
+    }
+    let l1 = triple(&d, &b, &c) / det;
+    let l2 = triple(&a, &d, &c) / det;
+    let l3 = triple(&a, &b, &d) / det;
+    Some([1.0 - l1 - l2 - l3, l1, l2, l3])
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate, tet_barycentric, triangle_barycentric};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn barycentric_works() {
+        let corners = [[0.0, 0.0], [2.0, 0.0], [0.0, 2.0]];
+        assert_eq!(triangle_barycentric(&corners, &[0.0, 0.0]), Some([1.0, 0.0, 0.0]));
+        assert_eq!(triangle_barycentric(&corners, &[1.0, 1.0]), Some([0.0, 0.5, 0.5]));
+        assert_eq!(triangle_barycentric(&corners, &[-1.0, 0.0]), Some([1.5, -0.5, 0.0]));
+        assert_eq!(
+            triangle_barycentric(&[[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]], &[0.0, 0.0]),
+            None
+        );
+
+        let corners = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert_eq!(tet_barycentric(&corners, &[0.0, 0.0, 1.0]), Some([0.0, 0.0, 0.0, 1.0]));
+        assert_eq!(
+            tet_barycentric(&corners, &[0.25, 0.25, 0.25]),
+            Some([0.25, 0.25, 0.25, 0.25])
+        );
+        let flat = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]];
+        assert_eq!(tet_barycentric(&flat, &[0.0, 0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn interpolate_works() {
+        // linear
+        let l = [0.2, 0.3, 0.5];
+        assert!((interpolate(&l, &[1.0, 2.0, 3.0], &[]) - 2.3).abs() < 1e-15);
+        // quadratic: the shape functions reproduce a constant field
+        let edges = [(0, 1), (1, 2), (2, 0)];
+        assert!((interpolate(&l, &[7.0; 6], &edges) - 7.0).abs() < 1e-14);
+        // quadratic: the value at a middle node
+        let l = [0.5, 0.5, 0.0];
+        assert_eq!(interpolate(&l, &[0.0, 0.0, 0.0, 9.0, 0.0, 0.0], &edges), 9.0);
+    }
+
+    #[test]
+    fn triangle_interpolate_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 0.0, 2.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        assert_eq!(triangle.barycentric(0, 0.0, 0.0), None);
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let ntriangle = triangle.ntriangle();
+        assert_eq!(triangle.barycentric(ntriangle, 0.0, 0.0), None);
+        let l = triangle.barycentric(0, 0.5, 0.5).unwrap();
+        assert!((l.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+
+        // quadratic field (exactly reproduced by quadratic triangles)
+        let f = |x: f64, y: f64| 1.0 + x + 2.0 * y + x * y - y * y;
+        let values: Vec<f64> = (0..triangle.npoint())
+            .map(|p| f(triangle.point(p, 0), triangle.point(p, 1)))
+            .collect();
+        assert_eq!(
+            triangle.interpolate(&[0.0], 0.5, 0.5).err(),
+            Some("values_at_points.len() must be equal to npoint")
+        );
+        for (x, y) in [(0.1, 0.2), (0.5, 0.5), (1.2, 0.7), (0.0, 1.9)] {
+            let v = triangle.interpolate(&values, x, y)?.unwrap();
+            assert!((v - f(x, y)).abs() < 1e-13);
+        }
+        assert_eq!(triangle.interpolate(&values, 1.5, 1.5)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_interpolate_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 2.0, 2.0, 2.0)?;
+        assert_eq!(tetgen.barycentric(0, 0.0, 0.0, 0.0), None);
+        tetgen.generate_delaunay(false)?;
+        let l = tetgen.barycentric(0, 0.3, 0.3, 0.3).unwrap();
+        assert!((l.iter().sum::<f64>() - 1.0).abs() < 1e-14);
+
+        // linear field (exactly reproduced by linear tetrahedra)
+        let f = |x: f64, y: f64, z: f64| 1.0 + x - 2.0 * y + 3.0 * z;
+        let values: Vec<f64> = (0..tetgen.npoint())
+            .map(|p| f(tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)))
+            .collect();
+        for (x, y, z) in [(0.1, 0.2, 0.3), (0.5, 0.5, 0.5), (1.0, 1.0, 1.0)] {
+            let v = tetgen.interpolate(&values, x, y, z)?.unwrap();
+            assert!((v - f(x, y, z)).abs() < 1e-13);
+        }
+        assert_eq!(tetgen.interpolate(&values, -0.1, 0.0, 0.0)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_find_cell_works() -> Result<(), StrError> {
+        // L-shaped (non-convex) domain
+        let mut triangle = Triangle::new(6, Some(6), None, None)?;
+        assert_eq!(triangle.find_cell(0.5, 0.5), None);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 2.0, 1.0)?
+            .set_point(3, 1.0, 1.0)?
+            .set_point(4, 1.0, 2.0)?
+            .set_point(5, 0.0, 2.0)?;
+        for i in 0..6 {
+            triangle.set_segment(i, i, (i + 1) % 6)?;
+        }
+        triangle.generate_mesh(false, false, Some(0.01), None)?;
+        let ntriangle = triangle.ntriangle();
+        for cell in 0..ntriangle {
+            // the centroid is found in its own triangle
+            let mut c = [0.0, 0.0];
+            for m in 0..3 {
+                let p = triangle.triangle_node(cell, m);
+                c[0] += triangle.point(p, 0) / 3.0;
+                c[1] += triangle.point(p, 1) / 3.0;
+            }
+            assert_eq!(triangle.find_cell(c[0], c[1]), Some(cell));
+        }
+        assert!(triangle.find_cell(1.9, 0.9).is_some());
+        assert!(triangle.find_cell(0.1, 1.9).is_some());
+        assert!(triangle.find_cell(2.0, 1.0).is_some()); // corner
+        assert_eq!(triangle.find_cell(1.5, 1.5), None); // in the notch
+        assert_eq!(triangle.find_cell(-0.1, 0.5), None);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_find_cell_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(8, None, None, None)?;
+        assert_eq!(tetgen.find_cell(0.5, 0.5, 0.5), None);
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 0.0)?
+            .set_point(4, 0.0, 0.0, 1.0)?
+            .set_point(5, 1.0, 0.0, 1.0)?
+            .set_point(6, 1.0, 1.0, 1.0)?
+            .set_point(7, 0.0, 1.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let ntet = tetgen.ntet();
+        for cell in 0..ntet {
+            let mut c = [0.0, 0.0, 0.0];
+            for m in 0..4 {
+                let p = tetgen.tet_node(cell, m);
+                for (i, v) in c.iter_mut().enumerate() {
+                    *v += tetgen.point(p, i) / 4.0;
+                }
+            }
+            assert_eq!(tetgen.find_cell(c[0], c[1], c[2]), Some(cell));
+        }
+        assert!(tetgen.find_cell(1.0, 1.0, 1.0).is_some());
+        assert_eq!(tetgen.find_cell(1.5, 0.5, 0.5), None);
+
+        // the neighbors are symmetric
+        for cell in 0..ntet {
+            for m in 0..4 {
+                if let Some(other) = tetgen.tet_neighbor(cell, m) {
+                    assert!((0..4).any(|k| tetgen.tet_neighbor(other, k) == Some(cell)));
+                }
+            }
+        }
+        assert_eq!(tetgen.tet_neighbor(ntet, 0), None);
+        assert_eq!(tetgen.tet_neighbor(0, 4), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use crate::mesh::simplex_measure;
+use crate::{Tetgen, Triangle};
+
+impl Triangle {
+    /// Returns the area of a triangle (computed with the corners)
+    ///
+    /// The area is positive because Triangle orders the corners counterclockwise.
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if `index` is out of range.
+    pub fn triangle_area(&self, index: usize) -> f64 {
+        let x = self.triangle_corners(index);
+        simplex_measure(&x, &[0, 1, 2])
+    }
+
+    /// Returns the centroid of a triangle (computed with the corners)
+    ///
+    /// # Warning
+    ///
+    /// This function will return the coordinates of the point 0 if `index` is out of range.
+    pub fn triangle_centroid(&self, index: usize) -> [f64; 2] {
+        let x = self.triangle_corners(index);
+        [(x[0][0] + x[1][0] + x[2][0]) / 3.0, (x[0][1] + x[1][1] + x[2][1]) / 3.0]
+    }
+
+    /// Returns the total area of the triangles
+    pub fn total_area(&self) -> f64 {
+        (0..self.ntriangle()).map(|index| self.triangle_area(index)).sum()
+    }
+
+    /// Returns the area-weighted centroid of the triangles (the center of mass of the domain)
+    ///
+    /// Returns `None` if there are no triangles or the total area is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(4, 2, (1.0, 0.0), (3.0, 1.0), [0; 4], false)?;
+    ///     assert_eq!(triangle.total_area(), 2.0);
+    ///     assert_eq!(triangle.domain_centroid(), Some([2.0, 0.5]));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn domain_centroid(&self) -> Option<[f64; 2]> {
+        let mut total = 0.0;
+        let mut moment = [0.0; 2];
+        for index in 0..self.ntriangle() {
+            let area = self.triangle_area(index);
+            let centroid = self.triangle_centroid(index);
+            total += area;
+            moment[0] += area * centroid[0];
+            moment[1] += area * centroid[1];
+        }
+        if total == 0.0 {
+            return None;
+        }
+        Some([moment[0] / total, moment[1] / total])
+    }
+
+    /// Returns the coordinates of the corners of a triangle
+    fn triangle_corners(&self, index: usize) -> [[f64; 2]; 3] {
+        let mut x = [[0.0; 2]; 3];
+        for (m, xm) in x.iter_mut().enumerate() {
+            let p = self.triangle_node(index, m);
+            *xm = [self.point(p, 0), self.point(p, 1)];
+        }
+        x
+    }
+}
+
+impl Tetgen {
+    /// Returns the volume of a tetrahedron (computed with the corners)
+    ///
+    /// The volume is positive if the fourth corner is on the positive side of the first three,
+    /// which is the ordering given by Tetgen.
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if `index` is out of range.
+    pub fn tet_volume(&self, index: usize) -> f64 {
+        let x = self.tet_corners(index);
+        simplex_measure(&x, &[0, 1, 2, 3])
+    }
+
+    /// Returns the centroid of a tetrahedron (computed with the corners)
+    ///
+    /// # Warning
+    ///
+    /// This function will return the coordinates of the point 0 if `index` is out of range.
+    pub fn tet_centroid(&self, index: usize) -> [f64; 3] {
+        let x = self.tet_corners(index);
+        let mut centroid = [0.0; 3];
+        for (dim, c) in centroid.iter_mut().enumerate() {
+            *c = (x[0][dim] + x[1][dim] + x[2][dim] + x[3][dim]) / 4.0;
+        }
+        centroid
+    }
+
+    /// Returns the total volume of the tetrahedra
+    pub fn total_volume(&self) -> f64 {
+        (0..self.ntet()).map(|index| self.tet_volume(index)).sum()
+    }
+
+    /// Returns the volume-weighted centroid of the tetrahedra (the center of mass of the domain)
+    ///
+    /// Returns `None` if there are no tetrahedra or the total volume is zero.
+    pub fn domain_centroid(&self) -> Option<[f64; 3]> {
+        let mut total = 0.0;
+        let mut moment = [0.0; 3];
+        for index in 0..self.ntet() {
+            let volume = self.tet_volume(index);
+            let centroid = self.tet_centroid(index);
+            total += volume;
+            for dim in 0..3 {
+                moment[dim] += volume * centroid[dim];
+            }
+        }
+        if total == 0.0 {
+            return None;
+        }
+        Some([moment[0] / total, moment[1] / total, moment[2] / total])
+    }
+
+    /// Returns the coordinates of the corners of a tetrahedron
+    fn tet_corners(&self, index: usize) -> [[f64; 3]; 4] {
+        let mut x = [[0.0; 3]; 4];
+        for (m, xm) in x.iter_mut().enumerate() {
+            let p = self.tet_node(index, m);
+            *xm = [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        }
+        x
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn triangle_measures_work() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(triangle.total_area(), 0.0);
+        assert_eq!(triangle.domain_centroid(), None);
+
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 3.0, 0.0)?
+            .set_point(2, 3.0, 3.0)?
+            .set_point(3, 0.0, 6.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        let mesh = triangle.to_mesh();
+        for index in 0..2 {
+            assert_eq!(triangle.triangle_area(index), mesh.measure(index));
+            let (centroid, correct) = (triangle.triangle_centroid(index), mesh.centroid(index));
+            assert!((0..2).all(|dim| f64::abs(centroid[dim] - correct[dim]) < 1e-15));
+        }
+        assert_eq!(triangle.total_area(), 13.5);
+        // square (area 9, centroid (1.5, 1.5)) plus triangle (area 4.5, centroid (1, 4))
+        let centroid = triangle.domain_centroid().unwrap();
+        assert!(f64::abs(centroid[0] - 4.0 / 3.0) < 1e-15);
+        assert!(f64::abs(centroid[1] - 7.0 / 3.0) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_measures_work() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(2, 1, 3, (0.0, 0.0, 0.0), (2.0, 1.0, 1.5), [0; 6], false)?;
+        let mesh = tetgen.to_mesh();
+        for index in 0..tetgen.ntet() {
+            assert!(f64::abs(tetgen.tet_volume(index) - mesh.measure(index)) < 1e-15);
+            let (centroid, correct) = (tetgen.tet_centroid(index), mesh.centroid(index));
+            assert!((0..3).all(|dim| f64::abs(centroid[dim] - correct[dim]) < 1e-15));
+        }
+        assert!(f64::abs(tetgen.total_volume() - 3.0) < 1e-15);
+        let centroid = tetgen.domain_centroid().unwrap();
+        for (c, correct) in centroid.iter().zip(&[1.0, 0.5, 0.75]) {
+            assert!(f64::abs(c - correct) < 1e-15);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,593 @@
+use crate::connectivity::dual_graph;
+use crate::vector::{dot, sub, triangle_normal};
+use crate::{CellTags, Region, Renumbering, StrError, Tetgen, Triangle};
+use std::collections::HashMap;
+
+/// Holds a mesh of simplices owned by Rust (decoupled from Triangle's and Tetgen's data)
+///
+/// The cells are triangles if `DIM = 2` or tetrahedra if `DIM = 3`. The first `DIM + 1` nodes
+/// of each cell are the corners; the other (optional) nodes are the middle nodes. The
+/// algorithms implemented here are available in both dimensions.
+///
+/// The mesh holds plain vectors only; thus, it can be sent to other threads and stored after
+/// Triangle or Tetgen is dropped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mesh<const DIM: usize> {
+    /// Holds the coordinates of the points
+    pub points: Vec<[f64; DIM]>,
+
+    /// Holds the node IDs of each cell (see `Triangle::triangle_node` and `Tetgen::tet_node`)
+    pub cells: Vec<Vec<usize>>,
+
+    /// Holds the attribute ID of each cell
+    pub attributes: Vec<i32>,
+
+    /// Holds the point IDs of the marked facets (the marked edges in 2D or the marked faces in 3D)
+    pub facets: Vec<Vec<usize>>,
+
+    /// Holds the marker of each facet
+    pub facet_markers: Vec<i32>,
+
+    /// Holds the user-defined tags of the cells (see `add_tag`)
+    pub tags: CellTags,
+}
+
+/// Holds a triangle mesh (3 or 6 nodes per cell)
+pub type TriMesh = Mesh<2>;
+
+/// Holds a tetrahedron mesh (4 or 10 nodes per cell)
+pub type TetMesh = Mesh<3>;
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Returns the number of cells
+    pub fn ncell(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the centroid of a cell (computed with the corners)
+    pub fn centroid(&self, cell: usize) -> [f64; DIM] {
+        let mut centroid = [0.0; DIM];
+        for p in &self.cells[cell][..DIM + 1] {
+            for (c, x) in centroid.iter_mut().zip(self.points[*p].iter()) {
+                *c += x / ((DIM + 1) as f64);
+            }
+        }
+        centroid
+    }
+
+    /// Returns the measure of a cell (area in 2D or volume in 3D)
+    ///
+    /// The measure is positive if the corners are ordered counterclockwise (2D) or if the
+    /// fourth corner is on the positive side of the first three (3D).
+    pub fn measure(&self, cell: usize) -> f64 {
+        simplex_measure(&self.points, &self.cells[cell][..DIM + 1])
+    }
+
+    /// Returns the measures of all cells
+    pub fn measures(&self) -> Vec<f64> {
+        (0..self.cells.len()).map(|cell| self.measure(cell)).collect()
+    }
+
+    /// Returns the measure-weighted average of a field over the cells of each attribute
+    ///
+    /// The field is interpolated within each cell with the shape functions of the cell (linear
+    /// or quadratic) and integrated exactly; then, the integral over the cells of an attribute is
+    /// divided by their total measure. The absolute values of the measures are used; thus,
+    /// the result does not depend on the orientation of the cells. The attributes whose cells
+    /// have zero total measure are not included.
+    ///
+    /// # Input
+    ///
+    /// * `values_at_points` -- the value of the field at each point
+    pub fn average_field_by_region(&self, values_at_points: &[f64]) -> Result<HashMap<i32, f64>, StrError> {
+        if values_at_points.len() != self.points.len() {
+            return Err("the number of values must be equal to the number of points");
+        }
+        // integration weights of the corners and middle nodes (as fractions of the measure)
+        let weights = |nnode: usize| -> Result<(f64, f64), StrError> {
+            match (DIM, nnode) {
+                (2, 3) => Ok((1.0 / 3.0, 0.0)),
+                (2, 6) => Ok((0.0, 1.0 / 3.0)),
+                (3, 4) => Ok((1.0 / 4.0, 0.0)),
+                (3, 10) => Ok((-1.0 / 20.0, 1.0 / 5.0)),
+                _ => Err("the number of nodes of a cell is not supported"),
+            }
+        };
+        let mut sums: HashMap<i32, (f64, f64)> = HashMap::new();
+        for (cell, nodes) in self.cells.iter().enumerate() {
+            let (w_corner, w_middle) = weights(nodes.len())?;
+            let measure = f64::abs(self.measure(cell));
+            let mean: f64 = nodes
+                .iter()
+                .enumerate()
+                .map(|(m, p)| if m <= DIM { w_corner } else { w_middle } * values_at_points[*p])
+                .sum();
+            let sum = sums.entry(self.attributes[cell]).or_insert((0.0, 0.0));
+            sum.0 += mean * measure;
+            sum.1 += measure;
+        }
+        Ok(sums
+            .into_iter()
+            .filter(|(_, (_, measure))| *measure > 0.0)
+            .map(|(attribute, (integral, measure))| (attribute, integral / measure))
+            .collect())
+    }
+
+    /// Returns the cells sharing each point (sorted)
+    pub fn node_to_cells(&self) -> Vec<Vec<usize>> {
+        let mut result = vec![Vec::new(); self.points.len()];
+        for (cell, nodes) in self.cells.iter().enumerate() {
+            for p in nodes {
+                result[*p].push(cell);
+            }
+        }
+        result
+    }
+
+    /// Extracts the cells whose centroids are inside a region
+    ///
+    /// Only the points used by the selected cells are copied; they are renumbered in the
+    /// order of the original IDs. The cells keep the original order. Only the facets whose
+    /// points are all copied are kept.
+    ///
+    /// # Output
+    ///
+    /// Returns the extracted mesh and the maps to the original points and cells.
+    pub fn extract_cells_in(&self, region: &Region) -> (Mesh<DIM>, Renumbering) {
+        let selected: Vec<usize> = (0..self.cells.len())
+            .filter(|cell| region.contains(&self.centroid(*cell)))
+            .collect();
+        self.extract_cells(selected)
+    }
+
+    /// Returns the connected components of the mesh (e.g., the meshes of disjoint domains)
+    ///
+    /// Two cells belong to the same component if they are connected by shared edges (2D) or
+    /// faces (3D). See `CsrGraph::components` for the output.
+    pub fn cell_components(&self) -> (usize, Vec<usize>) {
+        let corners: Vec<Vec<usize>> = self.cells.iter().map(|nodes| nodes[..DIM + 1].to_vec()).collect();
+        let facets: &[&[usize]] = if DIM == 2 {
+            &[&[0, 1], &[1, 2], &[2, 0]]
+        } else {
+            &[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]
+        };
+        dual_graph(&corners, facets).components()
+    }
+
+    /// Splits the mesh into its connected components (see `cell_components`)
+    ///
+    /// Each component is extracted as in `extract_cells_in`.
+    pub fn split_components(&self) -> Vec<(Mesh<DIM>, Renumbering)> {
+        let (ncomponent, component) = self.cell_components();
+        let mut selected = vec![Vec::new(); ncomponent];
+        for (cell, c) in component.iter().enumerate() {
+            selected[*c].push(cell);
+        }
+        selected.into_iter().map(|cells| self.extract_cells(cells)).collect()
+    }
+
+    /// Extracts the selected cells and the points used by them
+    pub(crate) fn extract_cells(&self, selected: Vec<usize>) -> (Mesh<DIM>, Renumbering) {
+        let connectivity: Vec<Vec<usize>> = selected.iter().map(|cell| self.cells[*cell].clone()).collect();
+        let (old_points, cells) = renumber(self.points.len(), connectivity);
+        let mut new_ids = vec![usize::MAX; self.points.len()];
+        for (new, old) in old_points.iter().enumerate() {
+            new_ids[*old] = new;
+        }
+        let mut facets = Vec::new();
+        let mut facet_markers = Vec::new();
+        for (facet, marker) in self.facets.iter().zip(&self.facet_markers) {
+            if facet.iter().all(|p| new_ids[*p] != usize::MAX) {
+                facets.push(facet.iter().map(|p| new_ids[*p]).collect());
+                facet_markers.push(*marker);
+            }
+        }
+        let mesh = Mesh {
+            points: old_points.iter().map(|p| self.points[*p]).collect(),
+            cells,
+            attributes: selected.iter().map(|cell| self.attributes[*cell]).collect(),
+            facets,
+            facet_markers,
+            tags: self.tags.select(&selected),
+        };
+        let renumbering = Renumbering {
+            points: old_points,
+            cells: selected,
+        };
+        (mesh, renumbering)
+    }
+
+    /// Appends the points and cells of another mesh
+    ///
+    /// The points are not merged; thus, the two parts are not connected. The tags are matched
+    /// by name; the cells without a tag get the default value of the tag.
+    pub fn append(&mut self, other: &Mesh<DIM>) {
+        let offset = self.points.len();
+        let ncell = self.cells.len();
+        self.points.extend_from_slice(&other.points);
+        self.cells.extend(
+            other
+                .cells
+                .iter()
+                .map(|nodes| nodes.iter().map(|p| p + offset).collect()),
+        );
+        self.attributes.extend_from_slice(&other.attributes);
+        self.facets.extend(
+            other
+                .facets
+                .iter()
+                .map(|nodes| nodes.iter().map(|p| p + offset).collect()),
+        );
+        self.facet_markers.extend_from_slice(&other.facet_markers);
+        self.tags.append(ncell, &other.tags, other.cells.len());
+    }
+}
+
+impl Triangle {
+    /// Copies the output of Triangle into a new mesh
+    ///
+    /// The facets are the edges with a non-zero marker (see `edge_marker`).
+    pub fn to_mesh(&self) -> TriMesh {
+        let nnode = self.nnode();
+        let (facets, facet_markers) = self
+            .edges()
+            .filter(|edge| edge.marker != 0)
+            .map(|edge| (edge.points.to_vec(), edge.marker))
+            .unzip();
+        Mesh {
+            points: (0..self.npoint())
+                .map(|p| [self.point(p, 0), self.point(p, 1)])
+                .collect(),
+            cells: (0..self.ntriangle())
+                .map(|cell| (0..nnode).map(|m| self.triangle_node(cell, m)).collect())
+                .collect(),
+            attributes: (0..self.ntriangle())
+                .map(|cell| self.triangle_attribute(cell))
+                .collect(),
+            facets,
+            facet_markers,
+            tags: CellTags::default(),
+        }
+    }
+}
+
+impl Tetgen {
+    /// Copies the output of Tetgen into a new mesh
+    ///
+    /// The facets are the marked faces with their middle nodes, if any (see `marked_face_nodes`).
+    pub fn to_mesh(&self) -> TetMesh {
+        let nnode = self.nnode();
+        let facets = self.marked_face_nodes();
+        let facet_markers = self.marked_faces().map(|face| face.marker).collect();
+        Mesh {
+            points: (0..self.npoint())
+                .map(|p| [self.point(p, 0), self.point(p, 1), self.point(p, 2)])
+                .collect(),
+            cells: (0..self.ntet())
+                .map(|cell| (0..nnode).map(|m| self.tet_node(cell, m)).collect())
+                .collect(),
+            attributes: (0..self.ntet()).map(|cell| self.tet_attribute(cell)).collect(),
+            facets,
+            facet_markers,
+            tags: CellTags::default(),
+        }
+    }
+}
+
+/// Returns the signed measure of the simplex given by DIM + 1 corners (see `Mesh::measure`)
+pub(crate) fn simplex_measure<const DIM: usize>(points: &[[f64; DIM]], corners: &[usize]) -> f64 {
+    let x0 = points[corners[0]];
+    let mut jac = [[0.0; DIM]; DIM];
+    for (i, row) in jac.iter_mut().enumerate() {
+        let x = points[corners[i + 1]];
+        for (j, v) in row.iter_mut().enumerate() {
+            *v = x[j] - x0[j];
+        }
+    }
+    let factorial: f64 = (1..=DIM).map(|k| k as f64).product();
+    determinant(jac) / factorial
+}
+
+/// Computes the determinant of a small matrix using Gaussian elimination with partial pivoting
+fn determinant<const N: usize>(mut a: [[f64; N]; N]) -> f64 {
+    let mut det = 1.0;
+    for k in 0..N {
+        let pivot = (k..N)
+            .max_by(|i, j| f64::abs(a[*i][k]).total_cmp(&f64::abs(a[*j][k])))
+            .unwrap();
+        if a[pivot][k] == 0.0 {
+            return 0.0;
+        }
+        if pivot != k {
+            a.swap(pivot, k);
+            det = -det;
+        }
+        det *= a[k][k];
+        let row_k = a[k];
+        for row in a.iter_mut().skip(k + 1) {
+            let factor = row[k] / row_k[k];
+            for (v, vk) in row.iter_mut().zip(row_k.iter()).skip(k) {
+                *v -= factor * vk;
+            }
+        }
+    }
+    det
+}
+
+/// Renumbers the points used by the cells; returns the old IDs of the new points and the new connectivity
+fn renumber(npoint: usize, mut connectivity: Vec<Vec<usize>>) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let mut used = vec![false; npoint];
+    for nodes in &connectivity {
+        for p in nodes {
+            used[*p] = true;
+        }
+    }
+    let old_points: Vec<usize> = (0..npoint).filter(|p| used[*p]).collect();
+    let mut new_ids = vec![0; npoint];
+    for (new, old) in old_points.iter().enumerate() {
+        new_ids[*old] = new;
+    }
+    for nodes in connectivity.iter_mut() {
+        for p in nodes.iter_mut() {
+            *p = new_ids[*p];
+        }
+    }
+    (old_points, connectivity)
+}
+
+/// Holds a triangulated surface in 3D owned by Rust (e.g., the boundary of a tetrahedron mesh)
+#[derive(Clone, Debug, PartialEq)]
+pub struct TriMesh3D {
+    /// Holds the (x, y, z) coordinates of the points
+    pub points: Vec<[f64; 3]>,
+
+    /// Holds the point IDs of each triangle (counterclockwise when seen from outside)
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Tetgen {
+    /// Returns the boundary surface of the tetrahedra
+    ///
+    /// The boundary faces (shared by only one tetrahedron) are oriented with outward normals.
+    /// Only the corner nodes are considered; the points are renumbered in the order of the
+    /// original IDs.
+    pub fn boundary_surface(&self) -> TriMesh3D {
+        // sorted corners => (corners, opposite corner)
+        let mut faces: HashMap<[usize; 3], Vec<([usize; 3], usize)>> = HashMap::new();
+        for tet in 0..self.ntet() {
+            let v: Vec<usize> = (0..4).map(|m| self.tet_node(tet, m)).collect();
+            for opposite in 0..4 {
+                let mut corners = [0; 3];
+                for (k, m) in (0..4).filter(|m| *m != opposite).enumerate() {
+                    corners[k] = v[m];
+                }
+                let mut key = corners;
+                key.sort();
+                faces.entry(key).or_default().push((corners, v[opposite]));
+            }
+        }
+
+        // orient the boundary faces outward
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let mut boundary: Vec<[usize; 3]> = Vec::new();
+        for owners in faces.values().filter(|owners| owners.len() == 1) {
+            let ([a, b, c], d) = owners[0];
+            let (xa, xb, xc, xd) = (coords(a), coords(b), coords(c), coords(d));
+            if dot(&triangle_normal(&xa, &xb, &xc), &sub(&xd, &xa)) > 0.0 {
+                boundary.push([a, c, b]);
+            } else {
+                boundary.push([a, b, c]);
+            }
+        }
+        boundary.sort();
+
+        // renumber the points
+        let npoint = self.npoint();
+        let mut used = vec![false; npoint];
+        for p in boundary.iter().flatten() {
+            used[*p] = true;
+        }
+        let old_points: Vec<usize> = (0..npoint).filter(|p| used[*p]).collect();
+        let mut new_ids = vec![0; npoint];
+        for (new, old) in old_points.iter().enumerate() {
+            new_ids[*old] = new;
+        }
+        TriMesh3D {
+            points: old_points.iter().map(|p| coords(*p)).collect(),
+            triangles: boundary
+                .iter()
+                .map(|[a, b, c]| [new_ids[*a], new_ids[*b], new_ids[*c]])
+                .collect(),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{determinant, Mesh, TetMesh, TriMesh};
+    use crate::fixtures::square;
+    use crate::{CellTags, Region, StrError, Tetgen, Triangle};
+
+    #[test]
+    fn determinant_works() {
+        assert_eq!(determinant([[2.0]]), 2.0);
+        assert_eq!(determinant([[0.0, 1.0], [1.0, 0.0]]), -1.0);
+        assert_eq!(determinant([[1.0, 2.0], [2.0, 4.0]]), 0.0);
+        assert_eq!(determinant([[2.0, 0.0, 0.0], [0.0, 3.0, 0.0], [0.0, 0.0, 4.0]]), 24.0);
+    }
+
+    #[test]
+    fn mesh_functions_work_in_2d() {
+        let mesh: TriMesh = Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![1, 2],
+            facets: vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]],
+            facet_markers: vec![-1, -2, -3, -4],
+            tags: CellTags::default(),
+        };
+        assert_eq!(mesh.ncell(), 2);
+        assert_eq!(mesh.measures(), &[0.5, 0.5]);
+        assert_eq!(mesh.centroid(0), [2.0 / 3.0, 1.0 / 3.0]);
+        assert_eq!(mesh.node_to_cells(), &[vec![0, 1], vec![0], vec![0, 1], vec![1]]);
+        let region = Region::BBox {
+            min: [0.0, 0.5, 0.0],
+            max: [1.0, 1.0, 0.0],
+        };
+        let (sub, renumbering) = mesh.extract_cells_in(&region);
+        assert_eq!(sub.cells, &[vec![0, 1, 2]]);
+        assert_eq!(sub.attributes, &[2]);
+        assert_eq!(sub.facets, &[vec![1, 2], vec![2, 0]]);
+        assert_eq!(sub.facet_markers, &[-3, -4]);
+        assert_eq!(renumbering.points, &[0, 2, 3]);
+
+        let mut twice = sub.clone();
+        twice.append(&sub);
+        assert_eq!(twice.facets, &[vec![1, 2], vec![2, 0], vec![4, 5], vec![5, 3]]);
+        assert_eq!(twice.facet_markers, &[-3, -4, -3, -4]);
+    }
+
+    #[test]
+    fn average_field_by_region_works() -> Result<(), StrError> {
+        let mut mesh: TriMesh = Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![1, 2],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        assert_eq!(
+            mesh.average_field_by_region(&[0.0]).err(),
+            Some("the number of values must be equal to the number of points")
+        );
+        let x: Vec<f64> = mesh.points.iter().map(|p| p[0]).collect();
+        let averages = mesh.average_field_by_region(&x)?;
+        assert_eq!(averages.len(), 2);
+        assert!(f64::abs(averages[&1] - 2.0 / 3.0) < 1e-15);
+        assert!(f64::abs(averages[&2] - 1.0 / 3.0) < 1e-15);
+        mesh.cells[1] = vec![0, 3, 2]; // clockwise
+        mesh.attributes[1] = 1;
+        assert_eq!(mesh.average_field_by_region(&x)?[&1], 0.5);
+
+        // quadratic field on quadratic triangles (exact)
+        let triangle = square()?;
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let mesh = triangle.to_mesh();
+        let xx: Vec<f64> = mesh.points.iter().map(|p| p[0] * p[0]).collect();
+        let averages = mesh.average_field_by_region(&xx)?;
+        assert!(f64::abs(averages[&0] - 1.0 / 3.0) < 1e-14);
+
+        // quadratic field on a quadratic tetrahedron (exact)
+        let mesh: TetMesh = Mesh {
+            points: vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.5, 0.0, 0.0],
+                [0.5, 0.5, 0.0],
+                [0.0, 0.5, 0.0],
+                [0.0, 0.0, 0.5],
+                [0.5, 0.0, 0.5],
+                [0.0, 0.5, 0.5],
+            ],
+            cells: vec![(0..10).collect()],
+            attributes: vec![3],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        let xx: Vec<f64> = mesh.points.iter().map(|p| p[0] * p[0]).collect();
+        let averages = mesh.average_field_by_region(&xx)?;
+        assert!(f64::abs(averages[&3] - 0.1) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn split_components_works() {
+        // two squares touching at a corner (thus, not connected by an edge)
+        let mesh: TriMesh = Mesh {
+            points: vec![
+                [0.0, 0.0],
+                [1.0, 0.0],
+                [1.0, 1.0],
+                [0.0, 1.0],
+                [2.0, 1.0],
+                [2.0, 2.0],
+                [1.0, 2.0],
+            ],
+            cells: vec![vec![0, 1, 2], vec![2, 4, 5], vec![0, 2, 3], vec![2, 5, 6]],
+            attributes: vec![1, 2, 3, 4],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        assert_eq!(mesh.cell_components(), (2, vec![0, 1, 0, 1]));
+        let parts = mesh.split_components();
+        assert_eq!(parts.len(), 2);
+        let (first, renumbering) = &parts[0];
+        assert_eq!(first.cells, &[vec![0, 1, 2], vec![0, 2, 3]]);
+        assert_eq!(first.attributes, &[1, 3]);
+        assert_eq!(renumbering.points, &[0, 1, 2, 3]);
+        let (second, renumbering) = &parts[1];
+        assert_eq!(second.points, &[[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 2.0]]);
+        assert_eq!(second.attributes, &[2, 4]);
+        assert_eq!(renumbering.cells, &[1, 3]);
+    }
+
+    #[test]
+    fn mesh_functions_work_in_3d() {
+        let mesh: TetMesh = Mesh {
+            points: vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]],
+            cells: vec![vec![0, 1, 2, 3], vec![0, 2, 1, 3]],
+            attributes: vec![0, 0],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        assert_eq!(mesh.measures(), &[8.0 / 6.0, -8.0 / 6.0]);
+        assert_eq!(mesh.centroid(0), [0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn to_mesh_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let mesh = triangle.to_mesh();
+        assert_eq!(mesh.points.len(), 4);
+        assert_eq!(mesh.ncell(), 2);
+        assert_eq!(mesh.measures(), &[0.5, 0.5]);
+        assert_eq!(mesh.facets.len(), 4);
+        assert_eq!(mesh.facet_markers, &[1, 1, 1, 1]);
+
+        // the mesh outlives Triangle and can be moved to another thread
+        drop(triangle);
+        let area = std::thread::spawn(move || mesh.measures().iter().sum::<f64>())
+            .join()
+            .unwrap();
+        assert_eq!(area, 1.0);
+
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let mesh = tetgen.to_mesh();
+        assert_eq!(mesh.facets.len(), tetgen.nmarked_face());
+        assert_eq!(mesh.facet_markers.len(), tetgen.nmarked_face());
+        assert_eq!(mesh.points[3], [0.0, 0.0, 1.0]);
+        assert_eq!(mesh.cells.len(), 1);
+        assert!(f64::abs(mesh.measure(0) - 1.0 / 6.0) < 1e-15);
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use crate::vector::{norm, triangle_normal};
+use crate::{Tetgen, Triangle};
+
+impl Triangle {
+    /// Returns the outward unit normal of an edge (e.g., for Neumann boundary conditions)
+    ///
+    /// The normal points out of the triangle attached to the edge; i.e., away from its centroid.
+    /// Thus, the normal of a boundary edge points out of the domain. The normal of an interior
+    /// edge (e.g., on a segment between two regions) points out of the attached triangle with
+    /// the smallest ID.
+    /// The attached triangle is found with the cached `vertex_to_cell_map`; thus, this
+    /// function can be called for all edges without rebuilding the map.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `nedge`
+    ///
+    /// # Warning
+    ///
+    /// This function will return `[0.0, 0.0]` if `index` is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(2, 2, (0.0, 0.0), (1.0, 1.0), [-1, -2, -3, -4], false)?;
+    ///     for e in 0..triangle.nedge() {
+    ///         if triangle.edge_marker(e) == -4 {
+    ///             assert_eq!(triangle.edge_out_normal(e), [0.0, 1.0]);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn edge_out_normal(&self, index: usize) -> [f64; 2] {
+        let (a, b) = (self.edge_point(index, 0), self.edge_point(index, 1));
+        let (dx, dy) = (self.point(b, 0) - self.point(a, 0), self.point(b, 1) - self.point(a, 1));
+        let length = f64::sqrt(dx * dx + dy * dy);
+        if length <= 0.0 || index >= self.nedge() {
+            return [0.0, 0.0];
+        }
+        let map = self.vertex_to_cell_map();
+        let cell = match map.neighbors(a).iter().find(|cell| map.neighbors(b).contains(cell)) {
+            Some(cell) => *cell,
+            None => return [0.0, 0.0],
+        };
+        let mut normal = [dy / length, -dx / length];
+        let mut side = 0.0;
+        for m in 0..3 {
+            let p = self.triangle_node(cell, m);
+            for (dim, n) in normal.iter().enumerate() {
+                side += n * (self.point(a, dim) - self.point(p, dim)) / 3.0;
+            }
+        }
+        if side < 0.0 {
+            normal = [-normal[0], -normal[1]];
+        }
+        normal
+    }
+}
+
+impl Tetgen {
+    /// Returns the outward unit normal of a marked face (e.g., for Neumann boundary conditions)
+    ///
+    /// The normal points out of the tetrahedron attached to the face; i.e., away from its
+    /// centroid. Thus, the normal of a boundary face points out of the domain. The normal of
+    /// an interior face (e.g., on a facet between two regions) points out of the attached
+    /// tetrahedron with the smallest ID.
+    /// This is the orientation of the faces given by `marked_faces_by_marker`. The attached
+    /// tetrahedron is found with the cached `vertex_to_cell_map`; thus, this function can be
+    /// called for all marked faces without rebuilding the map.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the marked face and goes from 0 to `nmarked_face`
+    ///
+    /// # Warning
+    ///
+    /// This function will return `[0.0, 0.0, 0.0]` if `index` is out of range.
+    pub fn marked_face_out_normal(&self, index: usize) -> [f64; 3] {
+        if index >= self.nmarked_face() {
+            return [0.0, 0.0, 0.0];
+        }
+        let corners = [0, 1, 2].map(|m| self.marked_face_node(index, m));
+        let [a, b, c] = match self.outward_face(&self.vertex_to_cell_map(), corners) {
+            Some(corners) => corners,
+            None => return [0.0, 0.0, 0.0],
+        };
+        let x = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let normal = triangle_normal(&x(a), &x(b), &x(c));
+        let length = norm(&normal);
+        if length <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+        [normal[0] / length, normal[1] / length, normal[2] / length]
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn edge_out_normal_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(3, 2, (0.0, 0.0), (3.0, 2.0), [-1, -2, -3, -4], false)?;
+        assert_eq!(triangle.edge_out_normal(triangle.nedge()), [0.0, 0.0]);
+        let expected = [[-1.0, 0.0], [1.0, 0.0], [0.0, -1.0], [0.0, 1.0]];
+        let mut count = 0;
+        for e in 0..triangle.nedge() {
+            let marker = triangle.edge_marker(e);
+            let normal = triangle.edge_out_normal(e);
+            if marker < 0 {
+                assert_eq!(normal, expected[(-marker - 1) as usize]);
+                count += 1;
+            }
+            assert!(f64::abs(normal[0] * normal[0] + normal[1] * normal[1] - 1.0) < 1e-15);
+        }
+        assert_eq!(count, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn edge_out_normal_works_with_unstructured_mesh() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, false, Some(0.05), None)?;
+        let s = f64::sqrt(0.5);
+        for e in 0..triangle.nedge() {
+            let (a, b) = (triangle.edge_point(e, 0), triangle.edge_point(e, 1));
+            let (ya, yb) = (triangle.point(a, 1), triangle.point(b, 1));
+            let (xa, xb) = (triangle.point(a, 0), triangle.point(b, 0));
+            let normal = triangle.edge_out_normal(e);
+            if ya == 0.0 && yb == 0.0 {
+                assert_eq!(normal, [0.0, -1.0]);
+            } else if xa == 0.0 && xb == 0.0 {
+                assert_eq!(normal, [-1.0, 0.0]);
+            } else if f64::abs(xa + ya - 1.0) < 1e-14 && f64::abs(xb + yb - 1.0) < 1e-14 {
+                assert!(f64::abs(normal[0] - s) < 1e-14 && f64::abs(normal[1] - s) < 1e-14);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn marked_face_out_normal_works() -> Result<(), StrError> {
+        let markers = [-1, -2, -3, -4, -5, -6];
+        let tetgen = Tetgen::generate_structured_box(2, 1, 2, (0.0, 0.0, 0.0), (2.0, 1.0, 2.0), markers, false)?;
+        assert_eq!(tetgen.marked_face_out_normal(tetgen.nmarked_face()), [0.0, 0.0, 0.0]);
+        let expected = [
+            [-1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0],
+        ];
+        assert_eq!(tetgen.nmarked_face(), 2 * (2 * 2 + 2 * 4 + 2 * 2));
+        for f in 0..tetgen.nmarked_face() {
+            let marker = tetgen.marked_face_marker(f);
+            let normal = tetgen.marked_face_out_normal(f);
+            let correct = expected[(-marker - 1) as usize];
+            for dim in 0..3 {
+                assert!(f64::abs(normal[dim] - correct[dim]) < 1e-15);
+            }
+        }
+        Ok(())
+    }
+}
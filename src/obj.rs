@@ -0,0 +1,255 @@
+use crate::StrError;
+use crate::{Tetgen, Triangle};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+impl Triangle {
+    /// Writes the boundary polylines as a Wavefront OBJ file
+    ///
+    /// The boundary edges (edges with a non-zero marker) are chained into polylines and written
+    /// as line elements (`l`) in groups named `marker_<marker>`. Only the points on the boundary
+    /// are written and the z-coordinates are zero.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn write_boundary_obj<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let mut groups: BTreeMap<i32, Vec<[usize; 2]>> = BTreeMap::new();
+        for edge in self.edges().filter(|e| e.marker != 0) {
+            groups.entry(edge.marker).or_default().push(edge.points);
+        }
+        if groups.is_empty() {
+            return Err("there are no boundary edges to write");
+        }
+        let mut points: Vec<usize> = groups.values().flatten().flatten().copied().collect();
+        points.sort();
+        points.dedup();
+        let mut buffer = String::new();
+        let local = write_vertices(&mut buffer, &points, |p| [self.point(p, 0), self.point(p, 1), 0.0]);
+        for (marker, edges) in &groups {
+            writeln!(&mut buffer, "g marker_{}", marker).unwrap();
+            for polyline in chain_edges(edges) {
+                buffer.push('l');
+                for p in polyline {
+                    write!(&mut buffer, " {}", local[&p]).unwrap();
+                }
+                buffer.push('\n');
+            }
+        }
+        write_obj_file(&buffer, full_path)
+    }
+}
+
+impl Tetgen {
+    /// Writes the marked faces as a Wavefront OBJ file
+    ///
+    /// The marked faces (see `marked_faces`) are written as triangular faces (`f`) in groups
+    /// named `marker_<marker>`. Only the points on the marked faces are written.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn write_boundary_obj<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let mut groups: BTreeMap<i32, Vec<[usize; 3]>> = BTreeMap::new();
+        for face in self.marked_faces() {
+            groups.entry(face.marker).or_default().push(face.points);
+        }
+        if groups.is_empty() {
+            return Err("there are no marked faces to write");
+        }
+        let mut points: Vec<usize> = groups.values().flatten().flatten().copied().collect();
+        points.sort();
+        points.dedup();
+        let mut buffer = String::new();
+        let local = write_vertices(&mut buffer, &points, |p| {
+            [self.point(p, 0), self.point(p, 1), self.point(p, 2)]
+        });
+        for (marker, faces) in &groups {
+            writeln!(&mut buffer, "g marker_{}", marker).unwrap();
+            for face in faces {
+                writeln!(
+                    &mut buffer,
+                    "f {} {} {}",
+                    local[&face[0]], local[&face[1]], local[&face[2]]
+                )
+                .unwrap();
+            }
+        }
+        write_obj_file(&buffer, full_path)
+    }
+}
+
+/// Writes the vertices and returns the map from point ID to (one-based) OBJ vertex index
+fn write_vertices<F>(buffer: &mut String, points: &[usize], coords: F) -> HashMap<usize, usize>
+where
+    F: Fn(usize) -> [f64; 3],
+{
+    let mut local = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        let x = coords(*p);
+        writeln!(buffer, "v {} {} {}", x[0], x[1], x[2]).unwrap();
+        local.insert(*p, i + 1);
+    }
+    local
+}
+
+/// Chains edges into polylines
+///
+/// The open polylines start at points not shared by exactly two edges; the remaining edges
+/// form closed polylines whose first point is repeated at the end.
+fn chain_edges(edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut incident: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (e, edge) in edges.iter().enumerate() {
+        incident.entry(edge[0]).or_default().push(e);
+        incident.entry(edge[1]).or_default().push(e);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut polylines = Vec::new();
+    let walk = |start: usize, used: &mut Vec<bool>| {
+        let mut polyline = vec![start];
+        let mut current = start;
+        while let Some(&e) = incident[&current].iter().find(|e| !used[**e]) {
+            used[e] = true;
+            current = if edges[e][0] == current {
+                edges[e][1]
+            } else {
+                edges[e][0]
+            };
+            polyline.push(current);
+        }
+        polyline
+    };
+    let mut starts: Vec<usize> = incident
+        .iter()
+        .filter(|(_, es)| es.len() != 2)
+        .map(|(p, _)| *p)
+        .collect();
+    starts.sort();
+    for start in starts {
+        while incident[&start].iter().any(|e| !used[*e]) {
+            polylines.push(walk(start, &mut used));
+        }
+    }
+    for (e, edge) in edges.iter().enumerate() {
+        if !used[e] {
+            polylines.push(walk(edge[0], &mut used));
+        }
+    }
+    polylines
+}
+
+/// Writes the buffer to a file, creating the directory if needed
+fn write_obj_file<P>(buffer: &str, full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::chain_edges;
+    use crate::{StrError, Tetgen, Triangle};
+    use std::fs;
+
+    #[test]
+    fn chain_edges_works() {
+        assert_eq!(chain_edges(&[[0, 1], [2, 1], [2, 3]]), vec![vec![0, 1, 2, 3]]);
+        assert_eq!(chain_edges(&[[0, 1], [1, 2], [2, 0]]), vec![vec![0, 1, 2, 0]]);
+        assert_eq!(chain_edges(&[[5, 6], [7, 8]]), vec![vec![5, 6], vec![7, 8]]);
+    }
+
+    #[test]
+    fn write_boundary_obj_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle
+                .write_boundary_obj("/tmp/tritet/test_boundary_obj_empty.obj")
+                .err(),
+            Some("there are no boundary edges to write")
+        );
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen
+                .write_boundary_obj("/tmp/tritet/test_boundary_obj_empty.obj")
+                .err(),
+            Some("there are no marked faces to write")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_write_boundary_obj_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let full_path = "/tmp/tritet/test_triangle_boundary.obj";
+        triangle.write_boundary_obj(full_path)?;
+        let contents = fs::read_to_string(full_path).map_err(|_| "cannot read file")?;
+        assert_eq!(
+            contents,
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 0 1 0\n\
+             g marker_1\n\
+             l 1 2 3 1\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_write_boundary_obj_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_facet_marker(0, -10)?.set_facet_marker(3, -20)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let full_path = "/tmp/tritet/test_tetgen_boundary.obj";
+        tetgen.write_boundary_obj(full_path)?;
+        let contents = fs::read_to_string(full_path).map_err(|_| "cannot read file")?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.iter().filter(|l| l.starts_with("v ")).count(), 4);
+        assert_eq!(lines.iter().filter(|l| l.starts_with("f ")).count(), 4);
+        let groups: Vec<&str> = lines.iter().filter(|l| l.starts_with("g ")).copied().collect();
+        assert_eq!(groups, &["g marker_-20", "g marker_-10", "g marker_0"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+use crate::conversion::to_i32;
+
+/// Holds the constraints on the Steiner points inserted by `generate_mesh`
+///
+/// Steiner points are the points added by Triangle or Tetgen to satisfy the quality constraints
+/// (e.g., maximum area or volume, minimum angle) and to recover the segments and facets.
+///
+/// **Note:** Prohibiting Steiner points may yield a mesh that does not satisfy the quality constraints.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SteinerOptions {
+    /// Maximum number of Steiner points (None means unlimited) (switch `S`)
+    pub max_steiner_points: Option<usize>,
+
+    /// Prohibits Steiner points on the segments or facets of the boundary (switch `Y`)
+    pub prohibit_steiner_on_boundary: bool,
+
+    /// Also prohibits Steiner points on the interior segments or facets (switch `YY`)
+    ///
+    /// This implies `prohibit_steiner_on_boundary`.
+    pub prohibit_steiner_in_interior: bool,
+}
+
+impl SteinerOptions {
+    /// Returns the maximum number of Steiner points (-1 means unlimited) and the count of `Y` switches for the c-code
+    pub(crate) fn to_c(self) -> (i32, i32) {
+        let max = match self.max_steiner_points {
+            Some(n) => to_i32(n),
+            None => -1,
+        };
+        let no_bisect = if self.prohibit_steiner_in_interior {
+            2
+        } else if self.prohibit_steiner_on_boundary {
+            1
+        } else {
+            0
+        };
+        (max, no_bisect)
+    }
+}
+
+/// Holds the options of `Triangle::generate_mesh_with`
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{StrError, Triangle, TriangleOptions};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut triangle = Triangle::new(3, Some(3), None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 0.0, 1.0)?
+///         .set_segment(0, 0, 1)?
+///         .set_segment(1, 1, 2)?
+///         .set_segment(2, 2, 0)?;
+///     let mut options = TriangleOptions::new();
+///     options.set_quadratic(true).set_max_area(0.1).set_min_angle(25.0);
+///     triangle.generate_mesh_with(&options)?;
+///     assert_eq!(triangle.nnode(), 6);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TriangleOptions {
+    /// Prints Triangle's messages to the console
+    pub verbose: bool,
+
+    /// Generates the middle nodes; e.g., nnode = 6
+    pub quadratic: bool,
+
+    /// The maximum area constraint for all generated triangles
+    pub max_area: Option<f64>,
+
+    /// The minimum angle constraint in degrees (the default minimum angle is twenty degrees)
+    pub min_angle: Option<f64>,
+
+    /// The constraints on the Steiner points
+    pub steiner: SteinerOptions,
+
+    /// Generates a conforming Delaunay triangulation; i.e., all triangles are truly Delaunay,
+    /// not only constrained Delaunay (switch `D`)
+    ///
+    /// The circumcenter of each triangle lies inside the triangle or on the boundary; thus, the
+    /// dual Voronoi edges are orthogonal to the triangle edges, as required by some finite volume
+    /// schemes. More Steiner points are usually inserted on the segments.
+    pub conforming_delaunay: bool,
+
+    /// Returns an error if the generated triangles do not satisfy the minimum angle constraint
+    ///
+    /// Triangle may give up near small angles of the input segments; thus, the smallest angle
+    /// of the mesh (see `Triangle::min_angle`) is compared with `min_angle` (or twenty degrees).
+    pub enforce_min_angle: bool,
+}
+
+/// Holds the options of `Tetgen::generate_mesh_with`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TetgenOptions {
+    /// Prints Tetgen's messages to the console
+    pub verbose: bool,
+
+    /// Generates the middle nodes; e.g., nnode = 10 (switch `o2`)
+    pub quadratic: bool,
+
+    /// The maximum volume constraint for all generated tetrahedra
+    pub max_volume: Option<f64>,
+
+    /// The maximum radius-edge ratio of the tetrahedra (the default is 2.0) (switch `q`)
+    pub max_radius_edge_ratio: Option<f64>,
+
+    /// The minimum dihedral angle constraint in degrees (the default is five degrees) (second switch `q`)
+    pub min_dihedral_angle: Option<f64>,
+
+    /// The constraints on the Steiner points
+    pub steiner: SteinerOptions,
+
+    /// Returns an error if the generated tetrahedra do not satisfy the minimum dihedral angle constraint
+    ///
+    /// Tetgen does not guarantee this constraint; thus, the smallest dihedral angle of the mesh
+    /// (see `Tetgen::min_dihedral_angle`) is compared with `min_dihedral_angle` (or five degrees).
+    pub enforce_min_dihedral_angle: bool,
+}
+
+/// Implements the setters shared by the options of Triangle and Tetgen
+macro_rules! impl_common_setters {
+    ($name:ident) => {
+        impl $name {
+            /// Allocates a new instance with the default values
+            pub fn new() -> Self {
+                $name::default()
+            }
+
+            /// Prints the messages of the mesh generator to the console
+            pub fn set_verbose(&mut self, flag: bool) -> &mut Self {
+                self.verbose = flag;
+                self
+            }
+
+            /// Generates the middle nodes
+            pub fn set_quadratic(&mut self, flag: bool) -> &mut Self {
+                self.quadratic = flag;
+                self
+            }
+
+            /// Sets the maximum number of Steiner points
+            pub fn set_max_steiner_points(&mut self, value: usize) -> &mut Self {
+                self.steiner.max_steiner_points = Some(value);
+                self
+            }
+
+            /// Prohibits Steiner points on the boundary
+            pub fn set_prohibit_steiner_on_boundary(&mut self, flag: bool) -> &mut Self {
+                self.steiner.prohibit_steiner_on_boundary = flag;
+                self
+            }
+
+            /// Also prohibits Steiner points on the interior segments or facets
+            pub fn set_prohibit_steiner_in_interior(&mut self, flag: bool) -> &mut Self {
+                self.steiner.prohibit_steiner_in_interior = flag;
+                self
+            }
+        }
+    };
+}
+
+impl_common_setters!(TriangleOptions);
+impl_common_setters!(TetgenOptions);
+
+impl TriangleOptions {
+    /// Sets the maximum area constraint
+    pub fn set_max_area(&mut self, value: f64) -> &mut Self {
+        self.max_area = Some(value);
+        self
+    }
+
+    /// Sets the minimum angle constraint in degrees
+    pub fn set_min_angle(&mut self, value: f64) -> &mut Self {
+        self.min_angle = Some(value);
+        self
+    }
+
+    /// Generates a conforming Delaunay triangulation
+    pub fn set_conforming_delaunay(&mut self, flag: bool) -> &mut Self {
+        self.conforming_delaunay = flag;
+        self
+    }
+
+    /// Returns an error if the minimum angle constraint is not satisfied
+    pub fn set_enforce_min_angle(&mut self, flag: bool) -> &mut Self {
+        self.enforce_min_angle = flag;
+        self
+    }
+}
+
+impl TetgenOptions {
+    /// Sets the maximum volume constraint
+    pub fn set_max_volume(&mut self, value: f64) -> &mut Self {
+        self.max_volume = Some(value);
+        self
+    }
+
+    /// Sets the maximum radius-edge ratio of the tetrahedra
+    pub fn set_max_radius_edge_ratio(&mut self, value: f64) -> &mut Self {
+        self.max_radius_edge_ratio = Some(value);
+        self
+    }
+
+    /// Sets the minimum dihedral angle constraint in degrees
+    pub fn set_min_dihedral_angle(&mut self, value: f64) -> &mut Self {
+        self.min_dihedral_angle = Some(value);
+        self
+    }
+
+    /// Returns an error if the minimum dihedral angle constraint is not satisfied
+    pub fn set_enforce_min_dihedral_angle(&mut self, flag: bool) -> &mut Self {
+        self.enforce_min_dihedral_angle = flag;
+        self
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{SteinerOptions, TetgenOptions, TriangleOptions};
+    use crate::fixtures::square;
+    use crate::{StrError, Tetgen, Triangle};
+    use std::collections::HashMap;
+
+    #[test]
+    fn to_c_works() {
+        let mut options = SteinerOptions::default();
+        assert_eq!(options.to_c(), (-1, 0));
+        options.max_steiner_points = Some(10);
+        options.prohibit_steiner_on_boundary = true;
+        assert_eq!(options.to_c(), (10, 1));
+        options.prohibit_steiner_in_interior = true;
+        assert_eq!(options.to_c(), (10, 2));
+        assert_eq!(
+            format!("{:?}", SteinerOptions::default()),
+            "SteinerOptions { max_steiner_points: None, prohibit_steiner_on_boundary: false, prohibit_steiner_in_interior: false }"
+        );
+    }
+
+    #[test]
+    fn triangle_steiner_options_work() -> Result<(), StrError> {
+        let mut triangle = square()?;
+        triangle.generate_mesh(false, false, Some(0.01), None)?;
+        let npoint_unlimited = triangle.npoint();
+        assert!(npoint_unlimited > 50);
+
+        triangle.set_steiner_options(SteinerOptions {
+            max_steiner_points: Some(5),
+            ..Default::default()
+        });
+        triangle.generate_mesh(false, false, Some(0.01), None)?;
+        assert_eq!(triangle.npoint(), 4 + 5);
+
+        triangle.set_steiner_options(SteinerOptions {
+            prohibit_steiner_on_boundary: true,
+            ..Default::default()
+        });
+        triangle.generate_mesh(false, false, Some(0.01), None)?;
+        assert!(triangle.npoint() > 4);
+        for p in 4..triangle.npoint() {
+            let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+            assert!(x > 0.0 && x < 1.0 && y > 0.0 && y < 1.0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_steiner_options_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.generate_mesh(false, false, Some(0.001), None)?;
+        assert!(tetgen.npoint() > 10);
+
+        tetgen.set_steiner_options(SteinerOptions {
+            max_steiner_points: Some(3),
+            ..Default::default()
+        });
+        tetgen.generate_mesh(false, false, Some(0.001), None)?;
+        assert!(tetgen.npoint() <= 4 + 3);
+        Ok(())
+    }
+
+    #[test]
+    fn options_builders_work() {
+        let mut options = TriangleOptions::new();
+        options
+            .set_verbose(true)
+            .set_quadratic(true)
+            .set_max_area(0.5)
+            .set_min_angle(30.0)
+            .set_max_steiner_points(7)
+            .set_prohibit_steiner_on_boundary(true)
+            .set_prohibit_steiner_in_interior(true)
+            .set_conforming_delaunay(true)
+            .set_enforce_min_angle(true);
+        assert_eq!(
+            options,
+            TriangleOptions {
+                verbose: true,
+                quadratic: true,
+                max_area: Some(0.5),
+                min_angle: Some(30.0),
+                steiner: SteinerOptions {
+                    max_steiner_points: Some(7),
+                    prohibit_steiner_on_boundary: true,
+                    prohibit_steiner_in_interior: true,
+                },
+                conforming_delaunay: true,
+                enforce_min_angle: true,
+            }
+        );
+        let mut options = TetgenOptions::new();
+        options
+            .set_max_volume(0.1)
+            .set_quadratic(true)
+            .set_max_radius_edge_ratio(1.5)
+            .set_min_dihedral_angle(12.0)
+            .set_enforce_min_dihedral_angle(true);
+        assert_eq!(options.max_volume, Some(0.1));
+        assert_eq!(options.max_radius_edge_ratio, Some(1.5));
+        assert_eq!(options.min_dihedral_angle, Some(12.0));
+        assert!(options.enforce_min_dihedral_angle);
+        assert!(options.quadratic);
+        assert!(!options.verbose);
+        assert_eq!(options.steiner, SteinerOptions::default());
+    }
+
+    /// Returns the largest angle opposite to a boundary edge and the largest sum of the two angles
+    /// opposite to an interior edge (in degrees)
+    fn opposite_angles(triangle: &Triangle) -> (f64, f64) {
+        let mut angles: HashMap<(usize, usize), Vec<f64>> = HashMap::new();
+        for tri in 0..triangle.ntriangle() {
+            let x: Vec<_> = (0..3)
+                .map(|m| {
+                    let p = triangle.triangle_node(tri, m);
+                    (triangle.point(p, 0), triangle.point(p, 1))
+                })
+                .collect();
+            for m in 0..3 {
+                let (o, a, b) = (x[m], x[(m + 1) % 3], x[(m + 2) % 3]);
+                let (u, v) = ((a.0 - o.0, a.1 - o.1), (b.0 - o.0, b.1 - o.1));
+                let angle = f64::atan2(u.0 * v.1 - u.1 * v.0, u.0 * v.0 + u.1 * v.1)
+                    .abs()
+                    .to_degrees();
+                let (pa, pb) = (
+                    triangle.triangle_node(tri, (m + 1) % 3),
+                    triangle.triangle_node(tri, (m + 2) % 3),
+                );
+                angles
+                    .entry((usize::min(pa, pb), usize::max(pa, pb)))
+                    .or_default()
+                    .push(angle);
+            }
+        }
+        let mut max = (0.0, 0.0);
+        for values in angles.values() {
+            if values.len() == 1 {
+                max.0 = f64::max(max.0, values[0]);
+            } else {
+                max.1 = f64::max(max.1, values.iter().sum());
+            }
+        }
+        max
+    }
+
+    #[test]
+    fn conforming_delaunay_works() -> Result<(), StrError> {
+        // obtuse triangle with an interior segment
+        let mut triangle = Triangle::new(5, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 4.0, 0.0)?
+            .set_point(2, 2.0, 0.6)?
+            .set_point(3, 1.0, 0.1)?
+            .set_point(4, 3.0, 0.1)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?
+            .set_segment(3, 3, 4)?;
+        let mut options = TriangleOptions::new();
+        triangle.generate_mesh_with(&options)?;
+        let (boundary, interior) = opposite_angles(&triangle);
+        assert!(boundary > 90.0 || interior > 180.0);
+
+        options.set_conforming_delaunay(true);
+        triangle.generate_mesh_with(&options)?;
+        let (boundary, interior) = opposite_angles(&triangle);
+        assert!(boundary <= 90.0 + 1e-10);
+        assert!(interior <= 180.0 + 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_with_works() -> Result<(), StrError> {
+        let triangle = square()?;
+        triangle.generate_mesh(false, true, Some(0.01), None)?;
+        let npoint = triangle.npoint();
+        let mut options = TriangleOptions::new();
+        options.set_quadratic(true).set_max_area(0.01);
+        triangle.generate_mesh_with(&options)?;
+        assert_eq!(triangle.npoint(), npoint);
+        assert_eq!(triangle.nnode(), 6);
+        options.set_max_steiner_points(2).set_quadratic(false);
+        triangle.generate_mesh_with(&options)?;
+        assert_eq!(triangle.npoint(), 4 + 2);
+
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        let mut options = TetgenOptions::new();
+        options.set_quadratic(true).set_max_volume(0.01);
+        tetgen.generate_mesh_with(&options)?;
+        assert_eq!(tetgen.nnode(), 10);
+        assert!(tetgen.ntet() > 1);
+        Ok(())
+    }
+}
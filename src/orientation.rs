@@ -0,0 +1,157 @@
+use crate::{Mesh, Tetgen, Triangle};
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Returns the cells with non-positive measure (inverted or degenerate cells)
+    ///
+    /// A cell has positive measure if its corners are ordered counterclockwise (2D) or if the
+    /// fourth corner is on the positive side of the first three (3D); i.e., the Jacobian of the
+    /// mapping from the reference cell is positive. See `Mesh::measure`.
+    ///
+    /// # Output
+    ///
+    /// Returns the sorted IDs of the cells with non-positive measure.
+    pub fn check_orientation(&self) -> Vec<usize> {
+        (0..self.cells.len())
+            .filter(|cell| self.measure(*cell) <= 0.0)
+            .collect()
+    }
+
+    /// Reorders the nodes of the cells with negative measure to make their measure positive
+    ///
+    /// The corners 1 and 2 are swapped; the middle nodes of quadratic cells are swapped
+    /// accordingly (see `Triangle::midside_parents` and `TET_EDGE_LOCAL_NODES`). The cells with
+    /// zero measure cannot be fixed (see `remove_degenerate_cells`); thus, they are not changed.
+    ///
+    /// # Output
+    ///
+    /// Returns the sorted IDs of the reoriented cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::TriMesh;
+    ///
+    /// let mut mesh = TriMesh {
+    ///     points: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+    ///     cells: vec![vec![0, 2, 1]],
+    ///     attributes: vec![1],
+    ///     facets: Vec::new(),
+    ///     facet_markers: Vec::new(),
+    ///     tags: Default::default(),
+    /// };
+    /// assert_eq!(mesh.check_orientation(), &[0]);
+    /// assert_eq!(mesh.fix_orientation(), &[0]);
+    /// assert_eq!(mesh.cells[0], &[0, 1, 2]);
+    /// assert_eq!(mesh.check_orientation().len(), 0);
+    /// ```
+    pub fn fix_orientation(&mut self) -> Vec<usize> {
+        let swaps: &[(usize, usize)] = if DIM == 2 {
+            &[(1, 2), (3, 5)]
+        } else {
+            &[(1, 2), (4, 6), (8, 9)]
+        };
+        let mut fixed = Vec::new();
+        for cell in 0..self.cells.len() {
+            if self.measure(cell) < 0.0 {
+                let nodes = &mut self.cells[cell];
+                for (a, b) in swaps {
+                    if *b < nodes.len() {
+                        nodes.swap(*a, *b);
+                    }
+                }
+                fixed.push(cell);
+            }
+        }
+        fixed
+    }
+}
+
+impl Triangle {
+    /// Returns the triangles with non-positive area (see `Mesh::check_orientation`)
+    ///
+    /// Triangle orders the corners counterclockwise; thus, only degenerate triangles are
+    /// expected here. Use `to_mesh` and `Mesh::fix_orientation` to reorient the triangles.
+    pub fn check_orientation(&self) -> Vec<usize> {
+        (0..self.ntriangle())
+            .filter(|index| self.triangle_area(*index) <= 0.0)
+            .collect()
+    }
+}
+
+impl Tetgen {
+    /// Returns the tetrahedra with non-positive volume (see `Mesh::check_orientation`)
+    ///
+    /// Use `to_mesh` and `Mesh::fix_orientation` to reorient the tetrahedra; the output of
+    /// Tetgen is not modified.
+    pub fn check_orientation(&self) -> Vec<usize> {
+        (0..self.ntet())
+            .filter(|index| self.tet_volume(*index) <= 0.0)
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle, TET_EDGE_LOCAL_NODES};
+
+    #[test]
+    fn check_orientation_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 2, (0.0, 0.0), (1.0, 1.0), [0; 4], true)?;
+        assert_eq!(triangle.check_orientation().len(), 0);
+        assert_eq!(triangle.to_mesh().check_orientation().len(), 0);
+        let tetgen = Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], true)?;
+        assert_eq!(tetgen.check_orientation().len(), 0);
+        assert_eq!(tetgen.to_mesh().check_orientation().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn fix_orientation_works_2d() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 2, (0.0, 0.0), (1.0, 1.0), [0; 4], true)?;
+        let original = triangle.to_mesh();
+        let mut mesh = original.clone();
+        mesh.cells[1].swap(0, 1);
+        mesh.cells[1].swap(4, 5);
+        mesh.cells[6].swap(0, 2);
+        mesh.cells[6].swap(3, 4);
+        assert_eq!(mesh.check_orientation(), &[1, 6]);
+        assert_eq!(mesh.fix_orientation(), &[1, 6]);
+        assert_eq!(mesh.check_orientation().len(), 0);
+        assert_eq!(mesh.fix_orientation().len(), 0);
+        for cell in [1, 6] {
+            assert_eq!(mesh.measure(cell), original.measure(cell));
+            let nodes = &mesh.cells[cell];
+            for (mid, a, b) in [(3, 0, 1), (4, 1, 2), (5, 2, 0)] {
+                let xm = mesh.points[nodes[mid]];
+                let (xa, xb) = (mesh.points[nodes[a]], mesh.points[nodes[b]]);
+                assert_eq!(xm, [(xa[0] + xb[0]) / 2.0, (xa[1] + xb[1]) / 2.0]);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn fix_orientation_works_3d() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], true)?;
+        let original = tetgen.to_mesh();
+        let mut mesh = original.clone();
+        // swap the corners 0 and 3 and the middle nodes accordingly
+        for (a, b) in [(0, 3), (4, 8), (6, 9)] {
+            mesh.cells[2].swap(a, b);
+        }
+        assert_eq!(mesh.check_orientation(), &[2]);
+        assert_eq!(mesh.fix_orientation(), &[2]);
+        assert_eq!(mesh.check_orientation().len(), 0);
+        assert!(f64::abs(mesh.measure(2) - original.measure(2)) < 1e-15);
+        let nodes = &mesh.cells[2];
+        for (a, b, mid) in TET_EDGE_LOCAL_NODES {
+            let (xa, xb, xm) = (mesh.points[nodes[a]], mesh.points[nodes[b]], mesh.points[nodes[mid]]);
+            for dim in 0..3 {
+                assert_eq!(xm[dim], (xa[dim] + xb[dim]) / 2.0);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use crate::constants;
+use crate::Mesh;
 use crate::StrError;
-use crate::Tetgen;
+use crate::{Tetgen, Triangle, VoronoiEdgePoint};
 use std::ffi::OsStr;
 use std::fmt::Write;
 use std::fs::{self, File};
@@ -127,13 +128,711 @@ where
     Ok(())
 }
 
+/// Writes a mesh as a Paraview's VTU file, including the attributes and tags of the cells
+///
+/// The attributes are written as the cell data "attribute" and each tag as the cell data with
+/// the name of the tag. In 2D, the z-coordinates are written as zero.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_mesh_vtu<const DIM: usize, P>(mesh: &Mesh<DIM>, full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ncell = mesh.cells.len();
+    if ncell < 1 {
+        return Err("there are no cells to write");
+    }
+
+    let mut buffer = String::new();
+
+    // header
+    write!(
+        &mut buffer,
+        "<?xml version=\"1.0\"?>\n\
+         <VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+         <UnstructuredGrid>\n\
+         <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
+        mesh.points.len(),
+        ncell
+    )
+    .unwrap();
+
+    // nodes: coordinates
+    write!(
+        &mut buffer,
+        "<Points>\n\
+         <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for x in &mesh.points {
+        for v in x.iter().chain([0.0; 3].iter()).take(3) {
+            write!(&mut buffer, "{} ", v).unwrap();
+        }
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </Points>\n"
+    )
+    .unwrap();
+
+    // elements: connectivity
+    write!(
+        &mut buffer,
+        "<Cells>\n\
+         <DataArray type=\"Int32\" Name=\"connectivity\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for nodes in &mesh.cells {
+        for p in nodes {
+            write!(&mut buffer, "{} ", p).unwrap();
+        }
+    }
+
+    // elements: offsets
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    let mut offset = 0;
+    for nodes in &mesh.cells {
+        offset += nodes.len();
+        write!(&mut buffer, "{} ", offset).unwrap();
+    }
+
+    // elements: types
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for nodes in &mesh.cells {
+        let vtk_type = match (DIM, nodes.len()) {
+            (2, 3) => constants::VTK_TRIANGLE,
+            (2, 6) => constants::VTK_QUADRATIC_TRIANGLE,
+            (3, 4) => constants::VTK_TETRA,
+            (3, 10) => constants::VTK_QUADRATIC_TETRA,
+            _ => return Err("the number of nodes of a cell is not supported"),
+        };
+        write!(&mut buffer, "{} ", vtk_type).unwrap();
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </Cells>\n"
+    )
+    .unwrap();
+
+    // elements: attributes and tags
+    write!(
+        &mut buffer,
+        "<CellData Scalars=\"attribute\">\n\
+         <DataArray type=\"Int32\" Name=\"attribute\" NumberOfComponents=\"1\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for attribute in &mesh.attributes {
+        write!(&mut buffer, "{} ", attribute).unwrap();
+    }
+    write!(&mut buffer, "\n</DataArray>\n").unwrap();
+    for name in mesh.tags.names() {
+        writeln!(
+            &mut buffer,
+            "<DataArray type=\"Int64\" Name=\"{}\" NumberOfComponents=\"1\" format=\"ascii\">",
+            name
+        )
+        .unwrap();
+        for cell in 0..ncell {
+            write!(&mut buffer, "{} ", mesh.tag(cell, name).unwrap()).unwrap();
+        }
+        write!(&mut buffer, "\n</DataArray>\n").unwrap();
+    }
+    buffer.push_str("</CellData>\n");
+
+    write!(
+        &mut buffer,
+        "</Piece>\n\
+         </UnstructuredGrid>\n\
+         </VTKFile>\n"
+    )
+    .unwrap();
+
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Writes the marked faces of a Tetgen as a Paraview's VTU file
+///
+/// The faces are written as triangles (VTK_TRIANGLE) or, if the tetrahedra are quadratic,
+/// as quadratic triangles with their middle nodes (VTK_QUADRATIC_TRIANGLE); see
+/// `Tetgen::marked_face_nodes`. The markers are written as the cell data "marker".
+/// All points are written; thus, the file can be loaded together with `write_tet_vtu`.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_marked_faces_vtu<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let nface = tetgen.nmarked_face();
+    if nface < 1 {
+        return Err("there are no marked faces to write");
+    }
+
+    let npoint = tetgen.npoint();
+    let faces = tetgen.marked_face_nodes();
+    let vtk_type = if tetgen.nnode() == 10 {
+        constants::VTK_QUADRATIC_TRIANGLE
+    } else {
+        constants::VTK_TRIANGLE
+    };
+
+    let mut buffer = String::new();
+
+    // header
+    write!(
+        &mut buffer,
+        "<?xml version=\"1.0\"?>\n\
+         <VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+         <UnstructuredGrid>\n\
+         <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
+        npoint, nface
+    )
+    .unwrap();
+
+    // nodes: coordinates
+    write!(
+        &mut buffer,
+        "<Points>\n\
+         <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for index in 0..npoint {
+        for dim in 0..3 {
+            write!(&mut buffer, "{} ", tetgen.point(index, dim)).unwrap();
+        }
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </Points>\n"
+    )
+    .unwrap();
+
+    // elements: connectivity
+    write!(
+        &mut buffer,
+        "<Cells>\n\
+         <DataArray type=\"Int32\" Name=\"connectivity\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for nodes in &faces {
+        for p in nodes {
+            write!(&mut buffer, "{} ", p).unwrap();
+        }
+    }
+
+    // elements: offsets
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    let mut offset = 0;
+    for nodes in &faces {
+        offset += nodes.len();
+        write!(&mut buffer, "{} ", offset).unwrap();
+    }
+
+    // elements: types
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for _ in 0..nface {
+        write!(&mut buffer, "{} ", vtk_type).unwrap();
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </Cells>\n"
+    )
+    .unwrap();
+
+    // elements: markers
+    write!(
+        &mut buffer,
+        "<CellData Scalars=\"marker\">\n\
+         <DataArray type=\"Int32\" Name=\"marker\" NumberOfComponents=\"1\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for index in 0..nface {
+        write!(&mut buffer, "{} ", tetgen.marked_face_marker(index)).unwrap();
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </CellData>\n"
+    )
+    .unwrap();
+
+    write!(
+        &mut buffer,
+        "</Piece>\n\
+         </UnstructuredGrid>\n\
+         </VTKFile>\n"
+    )
+    .unwrap();
+
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Writes the Voronoi cells of a Triangle as a Paraview's VTU file
+///
+/// The cells are clipped by an axis-aligned bounding box (see `Triangle::voronoi_cells`) and
+/// written as polygons with the IDs of their sites as the cell data "site". The cells that do
+/// not intersect the box are skipped. The z-coordinates are written as zero.
+///
+/// # Input
+///
+/// * `min` -- the (x, y) coordinates of the minimum corner of the box
+/// * `max` -- the (x, y) coordinates of the maximum corner of the box
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tri_voronoi_vtu<P>(
+    triangle: &Triangle,
+    min: &[f64; 2],
+    max: &[f64; 2],
+    full_path: &P,
+) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let mut points = Vec::new();
+    let mut cells = Vec::new();
+    let mut sites = Vec::new();
+    for (site, polygon) in triangle.voronoi_cells(min, max)?.iter().enumerate() {
+        if polygon.is_empty() {
+            continue;
+        }
+        cells.push((points.len()..(points.len() + polygon.len())).collect());
+        points.extend(polygon.iter().map(|x| [x[0], x[1], 0.0]));
+        sites.push(site);
+    }
+    write_voronoi_vtu(&points, &cells, None, &sites, full_path)
+}
+
+/// Writes the Voronoi cells of a Tetgen as a Paraview's VTU file
+///
+/// The cells are clipped by an axis-aligned bounding box (see `Tetgen::voronoi_cells`) and
+/// written as polyhedra with the IDs of their sites as the cell data "site". The cells that do
+/// not intersect the box are skipped.
+///
+/// # Input
+///
+/// * `min` -- the (x, y, z) coordinates of the minimum corner of the box
+/// * `max` -- the (x, y, z) coordinates of the maximum corner of the box
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tet_voronoi_vtu<P>(tetgen: &Tetgen, min: &[f64; 3], max: &[f64; 3], full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let mut points = Vec::new();
+    let mut cells = Vec::new();
+    let mut faces = Vec::new();
+    let mut sites = Vec::new();
+    for (site, polyhedron) in tetgen.voronoi_cells(min, max)?.iter().enumerate() {
+        if polyhedron.faces.is_empty() {
+            continue;
+        }
+        let first = points.len();
+        cells.push((first..(first + polyhedron.points.len())).collect());
+        faces.push(
+            polyhedron
+                .faces
+                .iter()
+                .map(|face| face.iter().map(|p| first + p).collect())
+                .collect(),
+        );
+        points.extend(&polyhedron.points);
+        sites.push(site);
+    }
+    write_voronoi_vtu(&points, &cells, Some(&faces), &sites, full_path)
+}
+
+impl Triangle {
+    /// Writes the Voronoi diagram as a Paraview's VTU file
+    ///
+    /// The Voronoi vertices are written as points and the Voronoi edges as lines. The infinite
+    /// rays are clipped by the bounding box of the input points and Voronoi vertices, enlarged by
+    /// 10% of its largest side; the cell data "ray" is 1 for the clipped rays and 0 for the finite
+    /// edges. The z-coordinates are written as zero.
+    ///
+    /// **Note:** `generate_voronoi` must be called first.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn write_voronoi_vtu<P>(&self, full_path: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let nedge = self.voronoi_nedge();
+        if self.voronoi_npoint() < 1 || nedge < 1 {
+            return Err("the Voronoi tessellation must be generated first");
+        }
+
+        // bounding box
+        let mut points: Vec<[f64; 2]> = (0..self.voronoi_npoint())
+            .map(|q| [self.voronoi_point(q, 0), self.voronoi_point(q, 1)])
+            .collect();
+        let (mut min, mut max) = ([f64::MAX; 2], [f64::MIN; 2]);
+        let sites = (0..self.npoint()).map(|p| [self.point(p, 0), self.point(p, 1)]);
+        for x in points.iter().copied().chain(sites) {
+            for dim in 0..2 {
+                min[dim] = f64::min(min[dim], x[dim]);
+                max[dim] = f64::max(max[dim], x[dim]);
+            }
+        }
+        let pad = 0.1 * f64::max(f64::max(max[0] - min[0], max[1] - min[1]), f64::EPSILON);
+        for dim in 0..2 {
+            min[dim] -= pad;
+            max[dim] += pad;
+        }
+
+        // edges and clipped rays
+        let mut edges = Vec::with_capacity(nedge);
+        let mut rays = Vec::with_capacity(nedge);
+        for e in 0..nedge {
+            let a = self.voronoi_edge_point_a(e);
+            match self.voronoi_edge_point_b(e) {
+                VoronoiEdgePoint::Index(b) => {
+                    edges.push([a, b]);
+                    rays.push(0);
+                }
+                VoronoiEdgePoint::Direction(dx, dy) => {
+                    let xa = points[a];
+                    let mut m = f64::MAX;
+                    for (dim, d) in [dx, dy].iter().enumerate() {
+                        if *d > 0.0 {
+                            m = f64::min(m, (max[dim] - xa[dim]) / d);
+                        } else if *d < 0.0 {
+                            m = f64::min(m, (min[dim] - xa[dim]) / d);
+                        }
+                    }
+                    if m == f64::MAX {
+                        continue; // zero direction
+                    }
+                    edges.push([a, points.len()]);
+                    rays.push(1);
+                    points.push([xa[0] + m * dx, xa[1] + m * dy]);
+                }
+            }
+        }
+
+        let mut buffer = String::new();
+
+        // header
+        write!(
+            &mut buffer,
+            "<?xml version=\"1.0\"?>\n\
+             <VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+             <UnstructuredGrid>\n\
+             <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
+            points.len(),
+            edges.len()
+        )
+        .unwrap();
+
+        // nodes: coordinates
+        write!(
+            &mut buffer,
+            "<Points>\n\
+             <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for x in &points {
+            write!(&mut buffer, "{} {} 0 ", x[0], x[1]).unwrap();
+        }
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             </Points>\n"
+        )
+        .unwrap();
+
+        // elements: connectivity
+        write!(
+            &mut buffer,
+            "<Cells>\n\
+             <DataArray type=\"Int32\" Name=\"connectivity\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for [a, b] in &edges {
+            write!(&mut buffer, "{} {} ", a, b).unwrap();
+        }
+
+        // elements: offsets
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             <DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for i in 0..edges.len() {
+            write!(&mut buffer, "{} ", 2 * (i + 1)).unwrap();
+        }
+
+        // elements: types
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for _ in 0..edges.len() {
+            write!(&mut buffer, "{} ", constants::VTK_LINE).unwrap();
+        }
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             </Cells>\n"
+        )
+        .unwrap();
+
+        // elements: rays
+        write!(
+            &mut buffer,
+            "<CellData Scalars=\"ray\">\n\
+             <DataArray type=\"Int32\" Name=\"ray\" NumberOfComponents=\"1\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for ray in &rays {
+            write!(&mut buffer, "{} ", ray).unwrap();
+        }
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             </CellData>\n\
+             </Piece>\n\
+             </UnstructuredGrid>\n\
+             </VTKFile>\n"
+        )
+        .unwrap();
+
+        // create directory
+        let path = Path::new(full_path);
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+
+        // write file
+        let mut file = File::create(path).map_err(|_| "cannot create file")?;
+        file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+        // force sync
+        file.sync_all().map_err(|_| "cannot sync file")?;
+        Ok(())
+    }
+}
+
+/// Writes polygons (no faces given) or polyhedra (faces given) as a Paraview's VTU file
+fn write_voronoi_vtu<P>(
+    points: &[[f64; 3]],
+    cells: &[Vec<usize>],
+    faces: Option<&[Vec<Vec<usize>>]>,
+    sites: &[usize],
+    full_path: &P,
+) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ncell = cells.len();
+    if ncell < 1 {
+        return Err("there are no Voronoi cells to write");
+    }
+
+    let mut buffer = String::new();
+
+    // header
+    write!(
+        &mut buffer,
+        "<?xml version=\"1.0\"?>\n\
+         <VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+         <UnstructuredGrid>\n\
+         <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
+        points.len(),
+        ncell
+    )
+    .unwrap();
+
+    // nodes: coordinates
+    write!(
+        &mut buffer,
+        "<Points>\n\
+         <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for x in points {
+        write!(&mut buffer, "{} {} {} ", x[0], x[1], x[2]).unwrap();
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </Points>\n"
+    )
+    .unwrap();
+
+    // elements: connectivity
+    write!(
+        &mut buffer,
+        "<Cells>\n\
+         <DataArray type=\"Int32\" Name=\"connectivity\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for nodes in cells {
+        for p in nodes {
+            write!(&mut buffer, "{} ", p).unwrap();
+        }
+    }
+
+    // elements: offsets
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    let mut offset = 0;
+    for nodes in cells {
+        offset += nodes.len();
+        write!(&mut buffer, "{} ", offset).unwrap();
+    }
+
+    // elements: types
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    let vtk_type = if faces.is_some() {
+        constants::VTK_POLYHEDRON
+    } else {
+        constants::VTK_POLYGON
+    };
+    for _ in 0..ncell {
+        write!(&mut buffer, "{} ", vtk_type).unwrap();
+    }
+    write!(&mut buffer, "\n</DataArray>\n").unwrap();
+
+    // elements: faces of polyhedra (number of faces, then number of points and points of each face)
+    if let Some(faces) = faces {
+        buffer.push_str("<DataArray type=\"Int32\" Name=\"faces\" format=\"ascii\">\n");
+        let mut face_offsets = Vec::with_capacity(ncell);
+        let mut offset = 0;
+        for cell_faces in faces {
+            write!(&mut buffer, "{} ", cell_faces.len()).unwrap();
+            offset += 1;
+            for face in cell_faces {
+                write!(&mut buffer, "{} ", face.len()).unwrap();
+                for p in face {
+                    write!(&mut buffer, "{} ", p).unwrap();
+                }
+                offset += 1 + face.len();
+            }
+            face_offsets.push(offset);
+        }
+        write!(
+            &mut buffer,
+            "\n</DataArray>\n\
+             <DataArray type=\"Int32\" Name=\"faceoffsets\" format=\"ascii\">\n"
+        )
+        .unwrap();
+        for offset in face_offsets {
+            write!(&mut buffer, "{} ", offset).unwrap();
+        }
+        write!(&mut buffer, "\n</DataArray>\n").unwrap();
+    }
+    buffer.push_str("</Cells>\n");
+
+    // elements: sites
+    write!(
+        &mut buffer,
+        "<CellData Scalars=\"site\">\n\
+         <DataArray type=\"Int32\" Name=\"site\" NumberOfComponents=\"1\" format=\"ascii\">\n"
+    )
+    .unwrap();
+    for site in sites {
+        write!(&mut buffer, "{} ", site).unwrap();
+    }
+    write!(
+        &mut buffer,
+        "\n</DataArray>\n\
+         </CellData>\n"
+    )
+    .unwrap();
+
+    write!(
+        &mut buffer,
+        "</Piece>\n\
+         </UnstructuredGrid>\n\
+         </VTKFile>\n"
+    )
+    .unwrap();
+
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::write_tet_vtu;
-    use crate::StrError;
-    use crate::Tetgen;
+    use super::{write_marked_faces_vtu, write_mesh_vtu, write_tet_voronoi_vtu, write_tet_vtu, write_tri_voronoi_vtu};
+    use crate::{CellTags, Mesh, StrError, TriMesh};
+    use crate::{Tetgen, Triangle};
     use std::fs;
 
     #[test]
@@ -177,4 +876,254 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn write_marked_faces_vtu_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        let file_path = "/tmp/tritet/test_write_marked_faces_vtu.vtu";
+        assert_eq!(
+            write_marked_faces_vtu(&tetgen, file_path).err(),
+            Some("there are no marked faces to write")
+        );
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, -10 - (index as i32))?;
+        }
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(tetgen.ntet(), 1);
+        write_marked_faces_vtu(&tetgen, file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        assert!(contents.contains("<Piece NumberOfPoints=\"10\" NumberOfCells=\"4\">"));
+        assert!(contents.contains("<DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n6 12 18 24 \n"));
+        assert!(contents.contains("<DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n22 22 22 22 \n"));
+
+        // the middle nodes lie between the corners
+        for nodes in tetgen.marked_face_nodes() {
+            assert_eq!(nodes.len(), 6);
+            for (mid, (i, j)) in [(3, (0, 1)), (4, (1, 2)), (5, (2, 0))] {
+                for dim in 0..3 {
+                    let x = (tetgen.point(nodes[i], dim) + tetgen.point(nodes[j], dim)) / 2.0;
+                    assert_eq!(tetgen.point(nodes[mid], dim), x);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_mesh_vtu_captures_errors() {
+        let mut mesh: TriMesh = Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+            cells: Vec::new(),
+            attributes: Vec::new(),
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        let file_path = "/tmp/tritet/test_write_mesh_vtu_errors.vtu";
+        assert_eq!(
+            write_mesh_vtu(&mesh, file_path).err(),
+            Some("there are no cells to write")
+        );
+        mesh.cells.push(vec![0, 1]);
+        mesh.attributes.push(0);
+        assert_eq!(
+            write_mesh_vtu(&mesh, file_path).err(),
+            Some("the number of nodes of a cell is not supported")
+        );
+    }
+
+    #[test]
+    fn write_mesh_vtu_works() -> Result<(), StrError> {
+        let mut mesh: TriMesh = Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![1, 2],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        mesh.add_tag("material", 7)?.set_tag(1, "material", -8)?;
+        let file_path = "/tmp/tritet/test_write_mesh_vtu.vtu";
+        write_mesh_vtu(&mesh, file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        assert_eq!(
+            contents,
+            r#"<?xml version="1.0"?>
+<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">
+<UnstructuredGrid>
+<Piece NumberOfPoints="4" NumberOfCells="2">
+<Points>
+<DataArray type="Float64" NumberOfComponents="3" format="ascii">
+0 0 0 1 0 0 1 1 0 0 1 0 
+</DataArray>
+</Points>
+<Cells>
+<DataArray type="Int32" Name="connectivity" format="ascii">
+0 1 2 0 2 3 
+</DataArray>
+<DataArray type="Int32" Name="offsets" format="ascii">
+3 6 
+</DataArray>
+<DataArray type="UInt8" Name="types" format="ascii">
+5 5 
+</DataArray>
+</Cells>
+<CellData Scalars="attribute">
+<DataArray type="Int32" Name="attribute" NumberOfComponents="1" format="ascii">
+1 2 
+</DataArray>
+<DataArray type="Int64" Name="material" NumberOfComponents="1" format="ascii">
+7 -8 
+</DataArray>
+</CellData>
+</Piece>
+</UnstructuredGrid>
+</VTKFile>
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_voronoi_vtu_works() -> Result<(), StrError> {
+        let file_path = "/tmp/tritet/test_write_tri_voronoi_vtu.vtu";
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            write_tri_voronoi_vtu(&triangle, &[0.0, 0.0], &[1.0, 1.0], file_path).err(),
+            Some("the Delaunay triangulation must be generated first")
+        );
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        write_tri_voronoi_vtu(&triangle, &[0.0, 0.0], &[1.0, 1.0], file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        assert_eq!(
+            contents,
+            r#"<?xml version="1.0"?>
+<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">
+<UnstructuredGrid>
+<Piece NumberOfPoints="12" NumberOfCells="3">
+<Points>
+<DataArray type="Float64" NumberOfComponents="3" format="ascii">
+0 0 0 0.5 0 0 0.5 0.5 0 0 0.5 0 0.5 0 0 1 0 0 1 1 0 0.5 0.5 0 1 1 0 0 1 0 0 0.5 0 0.5 0.5 0 
+</DataArray>
+</Points>
+<Cells>
+<DataArray type="Int32" Name="connectivity" format="ascii">
+0 1 2 3 4 5 6 7 8 9 10 11 
+</DataArray>
+<DataArray type="Int32" Name="offsets" format="ascii">
+4 8 12 
+</DataArray>
+<DataArray type="UInt8" Name="types" format="ascii">
+7 7 7 
+</DataArray>
+</Cells>
+<CellData Scalars="site">
+<DataArray type="Int32" Name="site" NumberOfComponents="1" format="ascii">
+0 1 2 
+</DataArray>
+</CellData>
+</Piece>
+</UnstructuredGrid>
+</VTKFile>
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_voronoi_vtu_works() -> Result<(), StrError> {
+        let file_path = "/tmp/tritet/test_triangle_write_voronoi_vtu.vtu";
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(
+            triangle.write_voronoi_vtu(file_path).err(),
+            Some("the Voronoi tessellation must be generated first")
+        );
+        triangle.generate_voronoi(false)?;
+        triangle.write_voronoi_vtu(file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        assert_eq!(
+            contents,
+            r#"<?xml version="1.0"?>
+<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">
+<UnstructuredGrid>
+<Piece NumberOfPoints="4" NumberOfCells="3">
+<Points>
+<DataArray type="Float64" NumberOfComponents="3" format="ascii">
+0.5 0.5 0 0.5 -0.09999999999999998 0 1.1 1.1 0 -0.09999999999999998 0.5 0 
+</DataArray>
+</Points>
+<Cells>
+<DataArray type="Int32" Name="connectivity" format="ascii">
+0 1 0 2 0 3 
+</DataArray>
+<DataArray type="Int32" Name="offsets" format="ascii">
+2 4 6 
+</DataArray>
+<DataArray type="UInt8" Name="types" format="ascii">
+3 3 3 
+</DataArray>
+</Cells>
+<CellData Scalars="ray">
+<DataArray type="Int32" Name="ray" NumberOfComponents="1" format="ascii">
+1 1 1 
+</DataArray>
+</CellData>
+</Piece>
+</UnstructuredGrid>
+</VTKFile>
+"#
+        );
+
+        // two triangles sharing one finite Voronoi edge
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_point(3, 2.0, 2.0)?;
+        triangle.generate_voronoi(false)?;
+        triangle.write_voronoi_vtu(file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        let rays = contents.lines().rev().nth(5).unwrap();
+        assert_eq!(rays.split_whitespace().filter(|r| *r == "0").count(), 1);
+        assert_eq!(rays.split_whitespace().filter(|r| *r == "1").count(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_voronoi_vtu_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let file_path = "/tmp/tritet/test_write_tet_voronoi_vtu.vtu";
+        write_tet_voronoi_vtu(&tetgen, &[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], file_path)?;
+        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        let sites = contents.split("Name=\"site\"").nth(1).unwrap();
+        assert!(sites.contains("\n0 1 2 3 \n"));
+        assert!(contents.contains("<Piece NumberOfPoints=\"32\" NumberOfCells=\"4\">"));
+        assert!(contents.contains("Name=\"faceoffsets\""));
+        assert!(contents.contains("\n42 42 42 42 \n"));
+        Ok(())
+    }
 }
@@ -0,0 +1,210 @@
+use crate::{Mesh, StrError};
+use std::collections::BTreeMap;
+
+/// Holds the partition of a mesh into balanced parts (see `Mesh::partition`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Partition {
+    /// Holds the number of parts
+    pub nparts: usize,
+
+    /// Holds the part ID of each cell
+    pub cell_part: Vec<usize>,
+
+    /// Holds the sorted corner IDs of the facets (edges in 2D or faces in 3D) shared by cells in different parts
+    ///
+    /// The facets are sorted by their corner IDs.
+    pub interface_facets: Vec<Vec<usize>>,
+
+    /// Holds the two cells sharing each interface facet (the first cell has the smaller part ID)
+    pub interface_cells: Vec<(usize, usize)>,
+}
+
+impl Partition {
+    /// Returns the number of cells in each part
+    pub fn part_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.nparts];
+        for part in &self.cell_part {
+            sizes[*part] += 1;
+        }
+        sizes
+    }
+
+    /// Returns the cells of each part (sorted)
+    pub fn part_cells(&self) -> Vec<Vec<usize>> {
+        let mut cells = vec![Vec::new(); self.nparts];
+        for (cell, part) in self.cell_part.iter().enumerate() {
+            cells[*part].push(cell);
+        }
+        cells
+    }
+}
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Partitions the cells into balanced parts for domain decomposition (e.g., with MPI)
+    ///
+    /// The partition is computed by recursive coordinate bisection of the cell centroids: each
+    /// group of cells is sorted along the direction of the largest extent of its centroids and
+    /// split into two groups whose sizes are proportional to the number of parts assigned to
+    /// each side. Thus, the sizes of the parts differ by at most one cell per bisection level.
+    ///
+    /// # Input
+    ///
+    /// * `nparts` -- is the number of parts (1 ≤ nparts ≤ ncell)
+    ///
+    /// # Output
+    ///
+    /// Returns the part ID of each cell and the facets on the interfaces between parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(4, 2, (0.0, 0.0), (4.0, 2.0), [0; 4], false)?;
+    ///     let partition = triangle.to_mesh().partition(2)?;
+    ///     assert_eq!(partition.part_sizes(), &[8, 8]);
+    ///     // the interface is the vertical line at x = 2
+    ///     assert_eq!(partition.interface_facets.len(), 2);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn partition(&self, nparts: usize) -> Result<Partition, StrError> {
+        let ncell = self.cells.len();
+        if nparts < 1 {
+            return Err("nparts must be ≥ 1");
+        }
+        if nparts > ncell {
+            return Err("nparts must be ≤ ncell");
+        }
+
+        // recursive coordinate bisection
+        let centroids: Vec<[f64; DIM]> = (0..ncell).map(|cell| self.centroid(cell)).collect();
+        let mut cell_part = vec![0; ncell];
+        let mut stack = vec![((0..ncell).collect::<Vec<_>>(), 0, nparts)];
+        while let Some((mut cells, first, count)) = stack.pop() {
+            if count == 1 {
+                for cell in cells {
+                    cell_part[cell] = first;
+                }
+                continue;
+            }
+            let (mut min, mut max) = ([f64::MAX; DIM], [f64::MIN; DIM]);
+            for cell in &cells {
+                for dim in 0..DIM {
+                    min[dim] = f64::min(min[dim], centroids[*cell][dim]);
+                    max[dim] = f64::max(max[dim], centroids[*cell][dim]);
+                }
+            }
+            let mut axis = 0;
+            for dim in 1..DIM {
+                if max[dim] - min[dim] > max[axis] - min[axis] {
+                    axis = dim;
+                }
+            }
+            cells.sort_by(|a, b| centroids[*a][axis].total_cmp(&centroids[*b][axis]).then(a.cmp(b)));
+            let count_left = count / 2;
+            let nleft = cells.len() * count_left / count;
+            let right = cells.split_off(nleft);
+            stack.push((right, first + count_left, count - count_left));
+            stack.push((cells, first, count_left));
+        }
+
+        // facets shared by cells in different parts
+        let facets: &[&[usize]] = if DIM == 2 {
+            &[&[0, 1], &[1, 2], &[2, 0]]
+        } else {
+            &[&[0, 1, 2], &[0, 1, 3], &[0, 2, 3], &[1, 2, 3]]
+        };
+        let mut owners: BTreeMap<Vec<usize>, Vec<usize>> = BTreeMap::new();
+        for (cell, nodes) in self.cells.iter().enumerate() {
+            for facet in facets {
+                let mut key: Vec<usize> = facet.iter().map(|m| nodes[*m]).collect();
+                key.sort();
+                owners.entry(key).or_default().push(cell);
+            }
+        }
+        let mut interface_facets = Vec::new();
+        let mut interface_cells = Vec::new();
+        for (key, cells) in owners {
+            if cells.len() == 2 && cell_part[cells[0]] != cell_part[cells[1]] {
+                let (a, b) = if cell_part[cells[0]] < cell_part[cells[1]] {
+                    (cells[0], cells[1])
+                } else {
+                    (cells[1], cells[0])
+                };
+                interface_facets.push(key);
+                interface_cells.push((a, b));
+            }
+        }
+        Ok(Partition {
+            nparts,
+            cell_part,
+            interface_facets,
+            interface_cells,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn partition_captures_errors() -> Result<(), StrError> {
+        let mesh = Triangle::generate_structured_rectangle(1, 1, (0.0, 0.0), (1.0, 1.0), [0; 4], false)?.to_mesh();
+        assert_eq!(mesh.partition(0).err(), Some("nparts must be ≥ 1"));
+        assert_eq!(mesh.partition(3).err(), Some("nparts must be ≤ ncell"));
+        Ok(())
+    }
+
+    #[test]
+    fn partition_works_2d() -> Result<(), StrError> {
+        let mesh = Triangle::generate_structured_rectangle(4, 2, (0.0, 0.0), (4.0, 2.0), [0; 4], true)?.to_mesh();
+        let single = mesh.partition(1)?;
+        assert_eq!(single.cell_part, vec![0; 16]);
+        assert_eq!(single.interface_facets.len(), 0);
+
+        let partition = mesh.partition(2)?;
+        assert_eq!(partition.part_sizes(), &[8, 8]);
+        for (cell, part) in partition.cell_part.iter().enumerate() {
+            let x = mesh.centroid(cell)[0];
+            assert_eq!(*part, if x < 2.0 { 0 } else { 1 });
+        }
+        assert_eq!(partition.interface_facets.len(), 2);
+        for (facet, (a, b)) in partition.interface_facets.iter().zip(&partition.interface_cells) {
+            assert_eq!(facet.len(), 2);
+            assert!(facet.iter().all(|p| mesh.points[*p][0] == 2.0));
+            assert_eq!((partition.cell_part[*a], partition.cell_part[*b]), (0, 1));
+        }
+
+        let partition = mesh.partition(3)?;
+        let sizes = partition.part_sizes();
+        assert_eq!(sizes.iter().sum::<usize>(), 16);
+        assert!(sizes.iter().all(|size| *size == 5 || *size == 6));
+        let cells = partition.part_cells();
+        assert_eq!(cells.iter().map(|c| c.len()).collect::<Vec<_>>(), sizes);
+        for (a, b) in &partition.interface_cells {
+            assert!(partition.cell_part[*a] < partition.cell_part[*b]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn partition_works_3d() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(1, 1, 4, (0.0, 0.0, 0.0), (1.0, 1.0, 4.0), [0; 6], false)?;
+        let mesh = tetgen.to_mesh();
+        let partition = mesh.partition(4)?;
+        assert_eq!(partition.part_sizes(), &[6, 6, 6, 6]);
+        // the box is bisected along z twice: the interfaces are the planes z = 1, 2, and 3
+        assert_eq!(partition.interface_facets.len(), 3 * 2);
+        for facet in &partition.interface_facets {
+            let z = mesh.points[facet[0]][2];
+            assert!(z == 1.0 || z == 2.0 || z == 3.0);
+            assert!(facet.iter().all(|p| mesh.points[*p][2] == z));
+        }
+        Ok(())
+    }
+}
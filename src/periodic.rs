@@ -0,0 +1,233 @@
+use crate::{StrError, Tetgen, Triangle};
+use std::collections::{BTreeSet, HashSet};
+
+impl Triangle {
+    /// Matches the points on two opposite boundaries of a periodic domain
+    ///
+    /// The points on a boundary are the nodes of the edges with the given marker, including
+    /// the middle nodes of quadratic triangles. Each point `a` on the first boundary is paired
+    /// with the point `b` on the second boundary such that `x(b) = x(a) + translation`, within
+    /// the tolerance.
+    ///
+    /// # Input
+    ///
+    /// * `marker_a` -- is the marker of the edges on the first boundary
+    /// * `marker_b` -- is the marker of the edges on the second boundary
+    /// * `translation` -- is the vector mapping the first boundary onto the second one
+    /// * `tolerance` -- is the maximum distance between a translated point and its match
+    ///
+    /// # Output
+    ///
+    /// Returns the pairs `(a, b)` of output point IDs sorted by `a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(2, 1, (0.0, 0.0), (2.0, 1.0), [-1, -2, -3, -4], false)?;
+    ///     let pairs = triangle.match_periodic_points(-1, -2, &[2.0, 0.0], 1e-10)?;
+    ///     assert_eq!(pairs, &[(0, 2), (3, 5)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn match_periodic_points(
+        &self,
+        marker_a: i32,
+        marker_b: i32,
+        translation: &[f64; 2],
+        tolerance: f64,
+    ) -> Result<Vec<(usize, usize)>, StrError> {
+        let boundary = |marker: i32| {
+            let edges: HashSet<(usize, usize)> = (0..self.nedge())
+                .filter(|index| self.edge_marker(*index) == marker)
+                .map(|index| {
+                    let (a, b) = (self.edge_point(index, 0), self.edge_point(index, 1));
+                    (usize::min(a, b), usize::max(a, b))
+                })
+                .collect();
+            let mut points: BTreeSet<usize> = edges.iter().flat_map(|(a, b)| [*a, *b]).collect();
+            for (p, parents) in self.midside_parents().iter().enumerate() {
+                if let Some(edge) = parents {
+                    if edges.contains(edge) {
+                        points.insert(p);
+                    }
+                }
+            }
+            points.into_iter().collect::<Vec<_>>()
+        };
+        let coords = |p: usize| (0..2).map(|dim| self.point(p, dim)).collect::<Vec<_>>();
+        match_points(&boundary(marker_a), &boundary(marker_b), translation, tolerance, coords)
+    }
+}
+
+impl Tetgen {
+    /// Matches the points on two opposite boundaries of a periodic domain
+    ///
+    /// The points on a boundary are the nodes of the marked faces with the given marker, including
+    /// the middle nodes of quadratic tetrahedra (see `marked_face_nodes`). Each point `a` on the
+    /// first boundary is paired with the point `b` on the second boundary such that
+    /// `x(b) = x(a) + translation`, within the tolerance.
+    ///
+    /// # Input
+    ///
+    /// * `marker_a` -- is the marker of the faces on the first boundary
+    /// * `marker_b` -- is the marker of the faces on the second boundary
+    /// * `translation` -- is the vector mapping the first boundary onto the second one
+    /// * `tolerance` -- is the maximum distance between a translated point and its match
+    ///
+    /// # Output
+    ///
+    /// Returns the pairs `(a, b)` of output point IDs sorted by `a`.
+    pub fn match_periodic_points(
+        &self,
+        marker_a: i32,
+        marker_b: i32,
+        translation: &[f64; 3],
+        tolerance: f64,
+    ) -> Result<Vec<(usize, usize)>, StrError> {
+        let face_nodes = self.marked_face_nodes();
+        let boundary = |marker: i32| {
+            let points: BTreeSet<usize> = face_nodes
+                .iter()
+                .enumerate()
+                .filter(|(face, _)| self.marked_face_marker(*face) == marker)
+                .flat_map(|(_, nodes)| nodes.iter().copied())
+                .collect();
+            points.into_iter().collect::<Vec<_>>()
+        };
+        let coords = |p: usize| (0..3).map(|dim| self.point(p, dim)).collect::<Vec<_>>();
+        match_points(&boundary(marker_a), &boundary(marker_b), translation, tolerance, coords)
+    }
+}
+
+/// Pairs each point in `points_a` with the point in `points_b` at the translated position
+fn match_points<F>(
+    points_a: &[usize],
+    points_b: &[usize],
+    translation: &[f64],
+    tolerance: f64,
+    coords: F,
+) -> Result<Vec<(usize, usize)>, StrError>
+where
+    F: Fn(usize) -> Vec<f64>,
+{
+    if tolerance <= 0.0 {
+        return Err("the tolerance must be positive");
+    }
+    if points_a.is_empty() {
+        return Err("cannot find points on the first boundary");
+    }
+    if points_a.len() != points_b.len() {
+        return Err("the boundaries have different numbers of points");
+    }
+    let xb: Vec<Vec<f64>> = points_b.iter().map(|b| coords(*b)).collect();
+    let mut taken = vec![false; points_b.len()];
+    let mut pairs = Vec::with_capacity(points_a.len());
+    for a in points_a {
+        let target: Vec<f64> = coords(*a).iter().zip(translation).map(|(x, t)| x + t).collect();
+        let found = xb.iter().enumerate().position(|(k, x)| {
+            let distance = x
+                .iter()
+                .zip(&target)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f64>()
+                .sqrt();
+            !taken[k] && distance <= tolerance
+        });
+        match found {
+            Some(k) => {
+                taken[k] = true;
+                pairs.push((*a, points_b[k]));
+            }
+            None => return Err("cannot find the periodic match of a point on the first boundary"),
+        }
+    }
+    Ok(pairs)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn match_periodic_points_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 1, (0.0, 0.0), (2.0, 1.0), [-1, -2, -3, -4], false)?;
+        assert_eq!(
+            triangle.match_periodic_points(-1, -2, &[2.0, 0.0], 0.0).err(),
+            Some("the tolerance must be positive")
+        );
+        assert_eq!(
+            triangle.match_periodic_points(-5, -2, &[2.0, 0.0], 1e-10).err(),
+            Some("cannot find points on the first boundary")
+        );
+        assert_eq!(
+            triangle.match_periodic_points(-1, -3, &[2.0, 0.0], 1e-10).err(),
+            Some("the boundaries have different numbers of points")
+        );
+        assert_eq!(
+            triangle.match_periodic_points(-1, -2, &[1.0, 0.0], 1e-10).err(),
+            Some("cannot find the periodic match of a point on the first boundary")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn match_periodic_points_works_2d() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 3, (0.0, 0.0), (2.0, 3.0), [-1, -2, -3, -4], false)?;
+        let pairs = triangle.match_periodic_points(-3, -4, &[0.0, 3.0], 1e-10)?;
+        assert_eq!(pairs, &[(0, 9), (1, 10), (2, 11)]);
+        let pairs = triangle.match_periodic_points(-1, -2, &[2.0, 0.0], 1e-10)?;
+        assert_eq!(pairs, &[(0, 2), (3, 5), (6, 8), (9, 11)]);
+        Ok(())
+    }
+
+    #[test]
+    fn match_periodic_points_includes_middle_nodes_2d() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 2, (0.0, 0.0), (1.0, 1.0), [-1, -2, -3, -4], true)?;
+        let pairs = triangle.match_periodic_points(-1, -2, &[1.0, 0.0], 1e-10)?;
+        assert_eq!(pairs.len(), 5);
+        for (a, b) in pairs {
+            assert_eq!(triangle.point(a, 0), 0.0);
+            assert_eq!(triangle.point(b, 0), 1.0);
+            assert_eq!(triangle.point(a, 1), triangle.point(b, 1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn match_periodic_points_works_3d() -> Result<(), StrError> {
+        let markers = [-1, -2, -3, -4, -5, -6];
+        let tetgen = Tetgen::generate_structured_box(2, 2, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), markers, false)?;
+        let pairs = tetgen.match_periodic_points(-5, -6, &[0.0, 0.0, 1.0], 1e-10)?;
+        assert_eq!(pairs.len(), 9);
+        for (a, b) in pairs {
+            assert_eq!(b, a + 9);
+        }
+        let pairs = tetgen.match_periodic_points(-1, -2, &[1.0, 0.0, 0.0], 1e-10)?;
+        assert_eq!(pairs.len(), 6);
+        for (a, b) in pairs {
+            assert_eq!(b, a + 2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn match_periodic_points_includes_middle_nodes_3d() -> Result<(), StrError> {
+        let markers = [-1, -2, -3, -4, -5, -6];
+        let tetgen = Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), markers, true)?;
+        let pairs = tetgen.match_periodic_points(-3, -4, &[0.0, 1.0, 0.0], 1e-10)?;
+        // 4 corners + 4 edges + 1 face diagonal
+        assert_eq!(pairs.len(), 9);
+        for (a, b) in pairs {
+            assert_eq!(tetgen.point(a, 1), 0.0);
+            assert_eq!(tetgen.point(b, 1), 1.0);
+            assert_eq!(tetgen.point(a, 0), tetgen.point(b, 0));
+            assert_eq!(tetgen.point(a, 2), tetgen.point(b, 2));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,451 @@
+use crate::{ColorMap, Tetgen, Triangle, VoronoiEdgePoint};
+use plotpy::{Canvas, Curve, Legend, Plot, PolyCode, Text};
+
+impl ColorMap {
+    /// Draws the legend for a list of keys
+    ///
+    /// Each entry is drawn as a square marker with the color of the key and labeled by `prefix` followed by the key.
+    pub fn draw_legend(&self, plot: &mut Plot, keys: &[i64], prefix: &str) {
+        let empty: [f64; 0] = [];
+        for (key, color) in self.legend(keys) {
+            let mut curve = Curve::new();
+            curve
+                .set_label(&format!("{}{}", prefix, key))
+                .set_line_style("None")
+                .set_marker_style("s")
+                .set_marker_size(10.0)
+                .set_marker_color(&color)
+                .set_marker_line_color("black");
+            curve.draw(&empty, &empty);
+            plot.add(&curve);
+        }
+        let mut legend = Legend::new();
+        legend.set_outside(true).set_show_frame(false);
+        legend.draw();
+        plot.add(&legend);
+    }
+}
+
+impl Triangle {
+    /// Draw triangles
+    pub fn draw_triangles(
+        &self,
+        plot: &mut Plot,
+        set_range: bool,
+        with_point_ids: bool,
+        with_triangle_ids: bool,
+        with_attribute_ids: bool,
+        fontsize_point_ids: Option<f64>,
+        fontsize_triangle_ids: Option<f64>,
+        fontsize_attribute_ids: Option<f64>,
+    ) {
+        let n_triangle = self.ntriangle();
+        if n_triangle < 1 {
+            return;
+        }
+        let mut canvas = Canvas::new();
+        let mut point_ids = Text::new();
+        let mut triangle_ids = Text::new();
+        let mut attribute_ids = Text::new();
+        if with_point_ids {
+            point_ids
+                .set_color("red")
+                .set_align_horizontal("center")
+                .set_align_vertical("center")
+                .set_bbox(true)
+                .set_bbox_facecolor("white")
+                .set_bbox_alpha(0.8)
+                .set_bbox_style("circle");
+            if let Some(fsz) = fontsize_point_ids {
+                point_ids.set_fontsize(fsz);
+            }
+        }
+        if with_triangle_ids {
+            triangle_ids
+                .set_color("blue")
+                .set_align_horizontal("center")
+                .set_align_vertical("center");
+            if let Some(fsz) = fontsize_triangle_ids {
+                triangle_ids.set_fontsize(fsz);
+            }
+        }
+        if with_attribute_ids {
+            attribute_ids
+                .set_color("black")
+                .set_align_horizontal("center")
+                .set_align_vertical("center");
+            if let Some(fsz) = fontsize_attribute_ids {
+                attribute_ids.set_fontsize(fsz);
+            }
+        }
+        canvas.set_edge_color("black");
+        let mut x = vec![0.0; 2];
+        let mut xmid = vec![0.0; 2];
+        let mut xatt = vec![0.0; 2];
+        let mut min = vec![f64::MAX; 2];
+        let mut max = vec![f64::MIN; 2];
+        for tri in 0..n_triangle {
+            let attribute = self.triangle_attribute(tri);
+            let color = self.colormap().color(attribute as i64);
+            canvas.set_face_color(color);
+            canvas.polycurve_begin();
+            for dim in 0..2 {
+                xmid[dim] = 0.0;
+            }
+            for m in 0..3 {
+                let p = self.triangle_node(tri, m);
+                for dim in 0..2 {
+                    x[dim] = self.point(p, dim);
+                    min[dim] = f64::min(min[dim], x[dim]);
+                    max[dim] = f64::max(max[dim], x[dim]);
+                    xmid[dim] += x[dim] / 3.0;
+                }
+                if m == 0 {
+                    canvas.polycurve_add(x[0], x[1], PolyCode::MoveTo);
+                } else {
+                    canvas.polycurve_add(x[0], x[1], PolyCode::LineTo);
+                }
+            }
+            canvas.polycurve_end(true);
+            if with_triangle_ids {
+                triangle_ids.draw(xmid[0], xmid[1], format!("{}", tri).as_str());
+            }
+            if with_attribute_ids {
+                for dim in 0..2 {
+                    x[dim] = self.point(self.triangle_node(tri, 0), dim);
+                    xatt[dim] = (x[dim] + xmid[dim]) / 2.0;
+                }
+                attribute_ids.draw(xatt[0], xatt[1], format!("[{}]", attribute).as_str());
+            }
+        }
+        if with_point_ids {
+            for p in 0..self.npoint() {
+                let x = self.point(p, 0);
+                let y = self.point(p, 1);
+                point_ids.draw(x, y, format!("{}", p).as_str());
+            }
+        }
+        plot.add(&canvas);
+        if with_triangle_ids {
+            plot.add(&triangle_ids);
+        }
+        if with_point_ids {
+            plot.add(&point_ids);
+        }
+        if with_attribute_ids {
+            plot.add(&attribute_ids);
+        }
+        if set_range {
+            plot.set_range(min[0], max[0], min[1], max[1]);
+        }
+    }
+
+    /// Draws Voronoi diagram
+    pub fn draw_voronoi(&self, plot: &mut Plot) {
+        if self.voronoi_npoint() < 1 || self.voronoi_nedge() < 1 {
+            return;
+        }
+        let mut x = vec![0.0; 2];
+        let mut min = vec![f64::MAX; 2];
+        let mut max = vec![f64::MIN; 2];
+        let mut markers = Curve::new();
+        markers
+            .set_marker_color("gold")
+            .set_marker_line_color("gold")
+            .set_marker_style("o")
+            .set_stop_clip(true);
+        for p in 0..self.npoint() {
+            for dim in 0..2 {
+                x[dim] = self.point(p, dim);
+                min[dim] = f64::min(min[dim], x[dim]);
+                max[dim] = f64::max(max[dim], x[dim]);
+            }
+            markers.draw(&[x[0]], &[x[1]]);
+        }
+        for q in 0..self.voronoi_npoint() {
+            for dim in 0..2 {
+                x[dim] = self.voronoi_point(q, dim);
+                min[dim] = f64::min(min[dim], x[dim]);
+                max[dim] = f64::max(max[dim], x[dim]);
+            }
+        }
+        let mut canvas = Canvas::new();
+        canvas.polycurve_begin();
+        for e in 0..self.voronoi_nedge() {
+            let a = self.voronoi_edge_point_a(e);
+            let xa = self.voronoi_point(a, 0);
+            let ya = self.voronoi_point(a, 1);
+            let b_or_direction = self.voronoi_edge_point_b(e);
+            match b_or_direction {
+                VoronoiEdgePoint::Index(b) => {
+                    let xb = self.voronoi_point(b, 0);
+                    let yb = self.voronoi_point(b, 1);
+                    canvas.polycurve_add(xa, ya, PolyCode::MoveTo);
+                    canvas.polycurve_add(xb, yb, PolyCode::LineTo);
+                }
+                VoronoiEdgePoint::Direction(dx, dy) => {
+                    let mx = if dx > 0.0 {
+                        (max[0] - xa) / dx
+                    } else if dx < 0.0 {
+                        (min[0] - xa) / dx
+                    } else {
+                        0.0
+                    };
+                    let my = if dy > 0.0 {
+                        (max[1] - ya) / dy
+                    } else if dy < 0.0 {
+                        (min[1] - ya) / dy
+                    } else {
+                        0.0
+                    };
+                    let m = if mx < my { mx } else { my };
+                    if m > 0.0 {
+                        let xb = xa + m * dx;
+                        let yb = ya + m * dy;
+                        min[0] = f64::min(min[0], xb);
+                        max[0] = f64::max(max[0], xb);
+                        min[1] = f64::min(min[1], yb);
+                        max[1] = f64::max(max[1], yb);
+                        canvas.polycurve_add(xa, ya, PolyCode::MoveTo);
+                        canvas.polycurve_add(xb, yb, PolyCode::LineTo);
+                    }
+                }
+            }
+        }
+        canvas.polycurve_end(false);
+        plot.set_range(min[0], max[0], min[1], max[1]);
+        plot.add(&canvas).add(&markers);
+    }
+}
+
+impl Tetgen {
+    /// Draws wireframe representing the edges of tetrahedra
+    pub fn draw_wireframe(
+        &self,
+        plot: &mut Plot,
+        set_range: bool,
+        with_point_ids: bool,
+        with_triangle_ids: bool,
+        with_attribute_ids: bool,
+        fontsize_point_ids: Option<f64>,
+        fontsize_triangle_ids: Option<f64>,
+        fontsize_attribute_ids: Option<f64>,
+    ) {
+        let ntet = self.ntet();
+        if ntet < 1 {
+            return;
+        }
+        let mut canvas = Canvas::new();
+        let mut point_ids = Text::new();
+        let mut tetrahedron_ids = Text::new();
+        let mut attribute_ids = Text::new();
+        if with_point_ids {
+            point_ids
+                .set_color("red")
+                .set_align_horizontal("center")
+                .set_align_vertical("center")
+                .set_bbox(true)
+                .set_bbox_facecolor("white")
+                .set_bbox_alpha(0.8)
+                .set_bbox_style("circle");
+            if let Some(fsz) = fontsize_point_ids {
+                point_ids.set_fontsize(fsz);
+            }
+        }
+        if with_triangle_ids {
+            tetrahedron_ids
+                .set_color("blue")
+                .set_align_horizontal("center")
+                .set_align_vertical("center");
+            if let Some(fsz) = fontsize_triangle_ids {
+                tetrahedron_ids.set_fontsize(fsz);
+            }
+        }
+        if with_attribute_ids {
+            attribute_ids
+                .set_color("black")
+                .set_align_horizontal("center")
+                .set_align_vertical("center");
+            if let Some(fsz) = fontsize_attribute_ids {
+                attribute_ids.set_fontsize(fsz);
+            }
+        }
+        const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        let mut x = vec![0.0; 3];
+        let mut xa = vec![0.0; 3];
+        let mut xb = vec![0.0; 3];
+        let mut xcen = vec![0.0; 3];
+        let mut xatt = vec![0.0; 3];
+        let mut min = vec![f64::MAX; 3];
+        let mut max = vec![f64::MIN; 3];
+        for tet in 0..ntet {
+            let attribute = self.tet_attribute(tet);
+            let color = self.colormap().color(attribute as i64);
+            canvas.set_edge_color(color);
+            for dim in 0..3 {
+                xcen[dim] = 0.0;
+            }
+            for m in 0..4 {
+                let p = self.tet_node(tet, m);
+                for dim in 0..3 {
+                    x[dim] = self.point(p, dim);
+                    min[dim] = f64::min(min[dim], x[dim]);
+                    max[dim] = f64::max(max[dim], x[dim]);
+                    xcen[dim] += x[dim] / 4.0;
+                }
+            }
+            for (ma, mb) in &EDGES {
+                let a = self.tet_node(tet, *ma);
+                let b = self.tet_node(tet, *mb);
+                for dim in 0..3 {
+                    xa[dim] = self.point(a, dim);
+                    xb[dim] = self.point(b, dim);
+                }
+                canvas.polyline_3d_begin();
+                canvas.polyline_3d_add(xa[0], xa[1], xa[2]);
+                canvas.polyline_3d_add(xb[0], xb[1], xb[2]);
+                canvas.polyline_3d_end();
+            }
+            if with_triangle_ids {
+                tetrahedron_ids.draw_3d(xcen[0], xcen[1], xcen[2], format!("{}", tet).as_str());
+            }
+            if with_attribute_ids {
+                for dim in 0..3 {
+                    x[dim] = self.point(self.tet_node(tet, 0), dim);
+                    xatt[dim] = (x[dim] + xcen[dim]) / 2.0;
+                }
+                attribute_ids.draw_3d(xatt[0], xatt[1], xatt[2], format!("[{}]", attribute).as_str());
+            }
+        }
+        if with_point_ids {
+            for p in 0..self.npoint() {
+                let x = self.point(p, 0);
+                let y = self.point(p, 1);
+                let z = self.point(p, 2);
+                point_ids.draw_3d(x, y, z, format!("{}", p).as_str());
+            }
+        }
+        plot.add(&canvas);
+        if with_triangle_ids {
+            plot.add(&tetrahedron_ids);
+        }
+        if with_point_ids {
+            plot.add(&point_ids);
+        }
+        if with_attribute_ids {
+            plot.add(&attribute_ids);
+        }
+        if set_range {
+            plot.set_range_3d(min[0], max[0], min[1], max[1], min[2], max[2]);
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{ColorMap, StrError, Tetgen, Triangle};
+    use plotpy::Plot;
+
+    #[test]
+    fn draw_legend_works() {
+        let colormap = ColorMap::light();
+        let mut plot = Plot::new();
+        colormap.draw_legend(&mut plot, &[1, 2], "attribute ");
+        if false {
+            plot.save("/tmp/tritet/colormap_draw_legend.svg").unwrap();
+        }
+    }
+
+    #[test]
+    fn set_colormap_works() -> Result<(), StrError> {
+        let mut colormap = ColorMap::new(&["red", "green"])?;
+        colormap.set_color(0, "blue");
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_colormap(colormap.clone());
+        triangle.generate_delaunay(false)?;
+        let mut plot = Plot::new();
+        triangle.draw_triangles(&mut plot, true, false, false, false, None, None, None);
+        colormap.draw_legend(&mut plot, &[0], "");
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_colormap(colormap);
+        tetgen.generate_delaunay(false)?;
+        let mut plot = Plot::new();
+        tetgen.draw_wireframe(&mut plot, true, false, false, false, None, None, None);
+        Ok(())
+    }
+
+    #[test]
+    fn draw_triangles_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, true, Some(0.25), None)?;
+        let mut plot = Plot::new();
+        triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
+        if false {
+            plot.set_equal_axes(true)
+                .set_figure_size_points(600.0, 600.0)
+                .save("/tmp/tritet/triangle_draw_triangles_works.svg")?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn draw_voronoi_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(5, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_point(4, 0.5, 0.5)?;
+        triangle.generate_voronoi(false)?;
+        assert_eq!(triangle.voronoi_npoint(), 4);
+        let mut plot = Plot::new();
+        triangle.draw_voronoi(&mut plot);
+        if false {
+            plot.set_equal_axes(true)
+                .set_figure_size_points(600.0, 600.0)
+                .save("/tmp/tritet/triangle_draw_voronoi_works.svg")?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn draw_wireframe_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.npoint(), 4);
+        let mut plot = Plot::new();
+        tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
+        if false {
+            plot.set_equal_axes(true)
+                .set_figure_size_points(600.0, 600.0)
+                .save("/tmp/tritet/tetgen_draw_wireframe_works.svg")?;
+        }
+        Ok(())
+    }
+}
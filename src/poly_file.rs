@@ -0,0 +1,526 @@
+use crate::constants;
+use crate::StrError;
+use crate::Triangle;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Holds the contents of a .node file
+pub(crate) struct NodeData<const DIM: usize> {
+    pub(crate) first: usize,                 // number of the first vertex (0 or 1)
+    pub(crate) coordinates: Vec<[f64; DIM]>, // coordinates of the vertices
+}
+
+/// Holds the contents of a .poly file
+struct PolyData {
+    nodes: Option<NodeData<2>>,       // vertices (None if they are in a separate .node file)
+    segments: Vec<([usize; 2], i32)>, // vertex numbers of the segments (as in the file) and markers
+    holes: Vec<[f64; 2]>,             // coordinates of the holes
+    regions: Vec<([f64; 2], i32, Option<f64>)>, // coordinates, attribute, and maximum area of the regions
+}
+
+impl Triangle {
+    /// Allocates a new instance with the points of a .node file
+    ///
+    /// The format is described in [Triangle's website](https://www.cs.cmu.edu/~quake/triangle.node.html).
+    /// The vertices may be numbered from 0 or 1. The attributes and boundary markers of the
+    /// vertices are ignored.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_node_file<P>(full_path: &P) -> Result<Triangle, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let contents = fs::read_to_string(Path::new(full_path)).map_err(|_| "cannot read file")?;
+        let nodes: NodeData<2> = parse_node(&mut lines(&contents))?;
+        let mut triangle = Triangle::new(nodes.coordinates.len(), None, None, None)?;
+        triangle.set_points(&nodes.coordinates)?;
+        Ok(triangle)
+    }
+
+    /// Allocates a new instance with the points, segments, holes, and regions of a .poly file
+    ///
+    /// The format is described in [Triangle's website](https://www.cs.cmu.edu/~quake/triangle.poly.html).
+    /// If the .poly file has no vertices, they are read from the .node file with the same
+    /// name (e.g., `box.node` for `box.poly`). The vertices may be numbered from 0 or 1.
+    /// The attributes and boundary markers of the vertices are ignored; the boundary markers of
+    /// the segments are set with `set_segment_marker`. The regional attributes must be
+    /// integers and the negative maximum areas mean no constraint.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_poly_file<P>(full_path: &P) -> Result<Triangle, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let path = Path::new(full_path);
+        let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        let poly = parse_poly(&contents)?;
+        let nodes = match poly.nodes {
+            Some(nodes) => nodes,
+            None => {
+                let node_path = path.with_extension("node");
+                let contents = fs::read_to_string(node_path).map_err(|_| "cannot read the corresponding .node file")?;
+                let nodes: NodeData<2> = parse_node(&mut lines(&contents))?;
+                nodes
+            }
+        };
+        let npoint = nodes.coordinates.len();
+        let option = |n: usize| if n > 0 { Some(n) } else { None };
+        let mut triangle = Triangle::new(
+            npoint,
+            option(poly.segments.len()),
+            option(poly.regions.len()),
+            option(poly.holes.len()),
+        )?;
+        triangle.set_points(&nodes.coordinates)?;
+        for (index, (segment, marker)) in poly.segments.iter().enumerate() {
+            let mut ids = [0; 2];
+            for (id, number) in ids.iter_mut().zip(segment) {
+                if *number < nodes.first || *number - nodes.first >= npoint {
+                    return Err("the vertex number of a segment is out of bounds");
+                }
+                *id = number - nodes.first;
+            }
+            triangle
+                .set_segment(index, ids[0], ids[1])?
+                .set_segment_marker(index, *marker)?;
+        }
+        for (index, x) in poly.holes.iter().enumerate() {
+            triangle.set_hole(index, x[0], x[1])?;
+        }
+        for (index, (x, attribute, max_area)) in poly.regions.iter().enumerate() {
+            triangle.set_region(index, x[0], x[1], *attribute, *max_area)?;
+        }
+        Ok(triangle)
+    }
+
+    /// Writes the generated mesh as .node, .ele, .poly, and .edge files
+    ///
+    /// The formats are described in [Triangle's website](https://www.cs.cmu.edu/~quake/triangle.html)
+    /// and the files can be read by showme and other tools based on Triangle. The points and
+    /// triangles are numbered from 0. The nodes of quadratic triangles are written in Triangle's
+    /// order (the middle nodes are opposite to the corners). The .poly file has no points (they
+    /// are in the .node file) and no holes; its segments are the edges with a non-zero marker.
+    ///
+    /// # Input
+    ///
+    /// * `prefix` -- the path without extension (e.g., "/tmp/tritet/mesh" for "/tmp/tritet/mesh.node")
+    pub fn write_node_ele<P>(&self, prefix: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let ntriangle = self.ntriangle();
+        if ntriangle < 1 {
+            return Err("there are no triangles to write");
+        }
+        let nnode = self.nnode();
+        let npoint = self.npoint();
+        let path = |extension: &str| {
+            let mut path = prefix.as_ref().to_os_string();
+            path.push(extension);
+            path
+        };
+
+        // points
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} 2 0 0", npoint).unwrap();
+        for p in 0..npoint {
+            writeln!(&mut buffer, "{} {} {}", p, self.point(p, 0), self.point(p, 1)).unwrap();
+        }
+        write_file(&buffer, &path(".node"))?;
+
+        // triangles
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} {} 1", ntriangle, nnode).unwrap();
+        let mut order = vec![0; nnode];
+        for (m, k) in constants::TRITET_TO_TRIANGLE.iter().take(nnode).enumerate() {
+            order[*k] = m;
+        }
+        for tri in 0..ntriangle {
+            write!(&mut buffer, "{}", tri).unwrap();
+            for m in &order {
+                write!(&mut buffer, " {}", self.triangle_node(tri, *m)).unwrap();
+            }
+            writeln!(&mut buffer, " {}", self.triangle_attribute(tri)).unwrap();
+        }
+        write_file(&buffer, &path(".ele"))?;
+
+        // segments
+        let segments: Vec<_> = self.edges().filter(|edge| edge.marker != 0).collect();
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "0 2 0 0\n{} 1", segments.len()).unwrap();
+        for (index, edge) in segments.iter().enumerate() {
+            writeln!(
+                &mut buffer,
+                "{} {} {} {}",
+                index, edge.points[0], edge.points[1], edge.marker
+            )
+            .unwrap();
+        }
+        buffer.push_str("0\n");
+        write_file(&buffer, &path(".poly"))?;
+
+        // edges
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} 1", self.nedge()).unwrap();
+        for edge in self.edges() {
+            writeln!(
+                &mut buffer,
+                "{} {} {} {}",
+                edge.index, edge.points[0], edge.points[1], edge.marker
+            )
+            .unwrap();
+        }
+        write_file(&buffer, &path(".edge"))
+    }
+}
+
+/// Writes the buffer to a file, creating the directory if needed
+pub(crate) fn write_file<P>(buffer: &str, full_path: &P) -> Result<(), StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    }
+
+    // write file
+    let mut file = File::create(path).map_err(|_| "cannot create file")?;
+    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+    // force sync
+    file.sync_all().map_err(|_| "cannot sync file")?;
+    Ok(())
+}
+
+/// Returns the words of the non-empty lines without comments
+pub(crate) fn lines(contents: &str) -> impl Iterator<Item = Vec<&str>> {
+    contents
+        .lines()
+        .map(|line| {
+            line.split('#')
+                .next()
+                .unwrap_or("")
+                .split_whitespace()
+                .collect::<Vec<_>>()
+        })
+        .filter(|words| !words.is_empty())
+}
+
+/// Returns the words of the next line with at least `n` words
+pub(crate) fn next_line<'a, I>(lines: &mut I, n: usize) -> Result<Vec<&'a str>, StrError>
+where
+    I: Iterator<Item = Vec<&'a str>>,
+{
+    match lines.next() {
+        Some(words) if words.len() >= n => Ok(words),
+        Some(_) => Err("the file has a line with missing values"),
+        None => Err("the file ended unexpectedly"),
+    }
+}
+
+/// Parses a number
+pub(crate) fn parse<T: std::str::FromStr>(word: &str) -> Result<T, StrError> {
+    word.parse().map_err(|_| "the file has an invalid number")
+}
+
+/// Parses the header and the vertices of a .node file (or the first section of a .poly file)
+pub(crate) fn parse_node<'a, I, const DIM: usize>(lines: &mut I) -> Result<NodeData<DIM>, StrError>
+where
+    I: Iterator<Item = Vec<&'a str>>,
+{
+    let header = next_line(lines, 2)?;
+    let npoint: usize = parse(header[0])?;
+    if parse::<usize>(header[1])? != DIM {
+        return Err(if DIM == 2 {
+            "the dimension must be 2"
+        } else {
+            "the dimension must be 3"
+        });
+    }
+    let mut first = 0;
+    let mut coordinates = Vec::new();
+    for i in 0..npoint {
+        let words = next_line(lines, 1 + DIM)?;
+        let number: usize = parse(words[0])?;
+        if i == 0 {
+            if number > 1 {
+                return Err("the vertices must be numbered from 0 or 1");
+            }
+            first = number;
+        } else if number != first + i {
+            return Err("the vertices must be numbered consecutively");
+        }
+        let mut x = [0.0; DIM];
+        for (k, value) in x.iter_mut().enumerate() {
+            *value = parse(words[1 + k])?;
+        }
+        coordinates.push(x);
+    }
+    Ok(NodeData { first, coordinates })
+}
+
+/// Parses a .poly file
+fn parse_poly(contents: &str) -> Result<PolyData, StrError> {
+    let mut lines = lines(contents).peekable();
+    let nodes: NodeData<2> = parse_node(&mut lines)?;
+    let nodes = if nodes.coordinates.is_empty() {
+        None
+    } else {
+        Some(nodes)
+    };
+
+    // segments
+    let header = next_line(&mut lines, 1)?;
+    let nsegment: usize = parse(header[0])?;
+    let with_markers = match header.get(1) {
+        Some(word) => parse::<usize>(word)? != 0,
+        None => false,
+    };
+    let mut segments = Vec::new();
+    for _ in 0..nsegment {
+        let words = next_line(&mut lines, 3)?;
+        let marker = match words.get(3) {
+            Some(word) if with_markers => parse(word)?,
+            _ => 0,
+        };
+        segments.push(([parse(words[1])?, parse(words[2])?], marker));
+    }
+
+    // holes (optional)
+    let mut holes = Vec::new();
+    if lines.peek().is_some() {
+        let nhole: usize = parse(next_line(&mut lines, 1)?[0])?;
+        for _ in 0..nhole {
+            let words = next_line(&mut lines, 3)?;
+            holes.push([parse(words[1])?, parse(words[2])?]);
+        }
+    }
+
+    // regional attributes and area constraints (optional)
+    let mut regions = Vec::new();
+    if lines.peek().is_some() {
+        let nregion: usize = parse(next_line(&mut lines, 1)?[0])?;
+        for _ in 0..nregion {
+            let words = next_line(&mut lines, 4)?;
+            let attribute: f64 = parse(words[3])?;
+            if attribute.fract() != 0.0 || attribute.abs() > i32::MAX as f64 {
+                return Err("the regional attribute must be an integer");
+            }
+            let max_area = match words.get(4) {
+                Some(word) => Some(parse::<f64>(word)?).filter(|area| *area > 0.0),
+                None => None,
+            };
+            regions.push(([parse(words[1])?, parse(words[2])?], attribute as i32, max_area));
+        }
+    }
+    Ok(PolyData {
+        nodes,
+        segments,
+        holes,
+        regions,
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::parse_poly;
+    use crate::{StrError, Triangle};
+    use std::fs;
+
+    #[test]
+    fn parse_poly_captures_errors() {
+        assert_eq!(parse_poly("").err(), Some("the file ended unexpectedly"));
+        assert_eq!(parse_poly("3 3 0 0").err(), Some("the dimension must be 2"));
+        assert_eq!(
+            parse_poly("1 2 0 0\n0 0.0").err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_poly("1 2 0 0\n0 0.0 x").err(),
+            Some("the file has an invalid number")
+        );
+        assert_eq!(
+            parse_poly("1 2 0 0\n2 0.0 0.0").err(),
+            Some("the vertices must be numbered from 0 or 1")
+        );
+        assert_eq!(
+            parse_poly("2 2 0 0\n1 0.0 0.0\n3 1.0 0.0").err(),
+            Some("the vertices must be numbered consecutively")
+        );
+        assert_eq!(
+            parse_poly("1 2 0 0\n0 0.0 0.0").err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_poly("0 2 0 0\n0 0\n0\n1\n1 0.5 0.5 -1.5 0.1").err(),
+            Some("the regional attribute must be an integer")
+        );
+
+        // the counts in the headers are not trusted to allocate memory
+        assert_eq!(
+            parse_poly("99999999999999999 2 0 0").err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_poly("0 2 0 0\n99999999999999999 0").err(),
+            Some("the file ended unexpectedly")
+        );
+    }
+
+    #[test]
+    fn parse_poly_works() -> Result<(), StrError> {
+        let poly = parse_poly(
+            "# vertices are in another file\n\
+             0 2 0 1\n\
+             4 1 # segments\n\
+             1 1 2 5\n\
+             2 2 3 5\n\
+             3 3 4 5\n\
+             4 4 1 5\n\
+             0\n\
+             2\n\
+             1 0.25 0.25 1 -1\n\
+             2 0.75 0.75 2 0.1\n",
+        )?;
+        assert!(poly.nodes.is_none());
+        assert_eq!(poly.segments, &[([1, 2], 5), ([2, 3], 5), ([3, 4], 5), ([4, 1], 5)]);
+        assert_eq!(poly.holes.len(), 0);
+        assert_eq!(poly.regions, &[([0.25, 0.25], 1, None), ([0.75, 0.75], 2, Some(0.1))]);
+        Ok(())
+    }
+
+    #[test]
+    fn from_node_file_works() -> Result<(), StrError> {
+        let full_path = "/tmp/tritet/test_from_node_file.node";
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        fs::write(
+            full_path,
+            "# a triangle\n\
+             3 2 1 1\n\
+             1 0.0 0.0 10.0 1\n\
+             2 1.0 0.0 20.0 1\n\
+             3 0.0 1.0 30.0 1\n",
+        )
+        .map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Triangle::from_node_file("/tmp/tritet/__does_not_exist__.node").err(),
+            Some("cannot read file")
+        );
+        let triangle = Triangle::from_node_file(full_path)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.npoint(), 3);
+        assert_eq!(triangle.ntriangle(), 1);
+        assert_eq!(triangle.point(2, 1), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn from_poly_file_works() -> Result<(), StrError> {
+        // square with a square hole (numbered from 1; vertices in the .poly file)
+        let full_path = "/tmp/tritet/test_from_poly_file.poly";
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        fs::write(
+            full_path,
+            "8 2 0 0\n\
+             1 0.0 0.0\n2 3.0 0.0\n3 3.0 3.0\n4 0.0 3.0\n\
+             5 1.0 1.0\n6 2.0 1.0\n7 2.0 2.0\n8 1.0 2.0\n\
+             8 0\n\
+             1 1 2\n2 2 3\n3 3 4\n4 4 1\n\
+             5 5 6\n6 6 7\n7 7 8\n8 8 5\n\
+             1\n\
+             1 1.5 1.5\n\
+             1\n\
+             1 0.5 0.5 7 -1\n",
+        )
+        .map_err(|_| "cannot write file")?;
+        let triangle = Triangle::from_poly_file(full_path)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let area: f64 = triangle.to_mesh().measures().iter().sum();
+        assert!(f64::abs(area - 8.0) < 1e-14);
+        for tri in 0..triangle.ntriangle() {
+            assert_eq!(triangle.triangle_attribute(tri), 7);
+        }
+
+        // vertices in the .node file (numbered from 0)
+        let full_path = "/tmp/tritet/test_from_poly_file_with_node.poly";
+        fs::write(
+            "/tmp/tritet/test_from_poly_file_with_node.node",
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n",
+        )
+        .map_err(|_| "cannot write file")?;
+        fs::write(full_path, "0 2 0 0\n3 0\n0 0 1\n1 1 2\n2 2 3\n").map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Triangle::from_poly_file(full_path).err(),
+            Some("the vertex number of a segment is out of bounds")
+        );
+        fs::write(full_path, "0 2 0 0\n3 0\n0 0 1\n1 1 2\n2 2 0\n").map_err(|_| "cannot write file")?;
+        let triangle = Triangle::from_poly_file(full_path)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.ntriangle(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn write_node_ele_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        assert_eq!(
+            triangle.write_node_ele("/tmp/tritet/test_write_node_ele_empty").err(),
+            Some("there are no triangles to write")
+        );
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        let prefix = "/tmp/tritet/test_write_node_ele";
+        triangle.write_node_ele(prefix)?;
+        let read =
+            |extension: &str| fs::read_to_string(format!("{}{}", prefix, extension)).map_err(|_| "cannot read file");
+        assert_eq!(
+            read(".node")?,
+            "6 2 0 0\n\
+             0 0 0\n\
+             1 1 0\n\
+             2 0 1\n\
+             3 0.5 0\n\
+             4 0.5 0.5\n\
+             5 0 0.5\n"
+        );
+        // nodes 3, 4, 5 are opposite to the corners 0, 1, 2, respectively
+        let ele = read(".ele")?;
+        let words: Vec<usize> = ele.split_whitespace().map(|w| w.parse().unwrap()).collect();
+        assert_eq!(&words[..3], &[1, 6, 1]);
+        let nodes = &words[4..10];
+        for k in 0..3 {
+            let (a, b) = (nodes[(k + 1) % 3], nodes[(k + 2) % 3]);
+            for dim in 0..2 {
+                let mid = (triangle.point(a, dim) + triangle.point(b, dim)) / 2.0;
+                assert_eq!(triangle.point(nodes[3 + k], dim), mid);
+            }
+        }
+        assert_eq!(read(".edge")?.lines().count(), 4);
+
+        // read back
+        let copy = Triangle::from_poly_file(&format!("{}.poly", prefix))?;
+        copy.generate_mesh(false, false, None, None)?;
+        let area: f64 = copy.to_mesh().measures().iter().sum();
+        assert!(f64::abs(area - 0.5) < 1e-15);
+        assert!(copy.edges().filter(|e| e.marker != 0).all(|e| e.marker == 1));
+        assert!((0..copy.npoint()).all(|p| copy.point_marker(p) == 1));
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+extern "C" {
+    fn triangle_orient2d(pa: *const f64, pb: *const f64, pc: *const f64) -> f64;
+    fn triangle_indiametral(pa: *const f64, pb: *const f64, pc: *const f64) -> f64;
+    fn triangle_distancecompare(pa: *const f64, pb: *const f64, pc: *const f64, pd: *const f64) -> f64;
+}
+
+/// Returns a positive value if (a, b, c) are counterclockwise, a negative value if clockwise, and zero if collinear
+///
+/// The sign is exact (see the exact arithmetic of Triangle).
+pub(crate) fn orient2d(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let (pa, pb, pc) = ([a.0, a.1], [b.0, b.1], [c.0, c.1]);
+    unsafe { triangle_orient2d(pa.as_ptr(), pb.as_ptr(), pc.as_ptr()) }
+}
+
+/// Returns a positive value if c is strictly inside the circle with diameter (a, b), a negative value if outside,
+/// and zero if on the circle
+///
+/// The sign is exact (see the exact arithmetic of Triangle).
+pub(crate) fn in_diametral(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    let (pa, pb, pc) = ([a.0, a.1], [b.0, b.1], [c.0, c.1]);
+    unsafe { triangle_indiametral(pa.as_ptr(), pb.as_ptr(), pc.as_ptr()) }
+}
+
+/// Returns a positive value if |b - a| > |d - c|, a negative value if |b - a| < |d - c|, and zero if equal
+///
+/// The sign is exact (see the exact arithmetic of Triangle).
+pub(crate) fn compare_distances(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> f64 {
+    let (pa, pb, pc, pd) = ([a.0, a.1], [b.0, b.1], [c.0, c.1], [d.0, d.1]);
+    unsafe { triangle_distancecompare(pa.as_ptr(), pb.as_ptr(), pc.as_ptr(), pd.as_ptr()) }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_distances, in_diametral, orient2d};
+
+    #[test]
+    fn orient2d_works() {
+        assert!(orient2d((0.0, 0.0), (1.0, 0.0), (0.0, 1.0)) > 0.0);
+        assert!(orient2d((0.0, 0.0), (0.0, 1.0), (1.0, 0.0)) < 0.0);
+        assert_eq!(orient2d((0.0, 0.0), (1.0, 1.0), (2.0, 2.0)), 0.0);
+
+        // nearly collinear points
+        let (a, b) = ((0.5, 0.5), (12.0, 12.0));
+        let x = 0.5 + f64::EPSILON / 2.0;
+        assert!(orient2d(a, b, (x, 0.5)) < 0.0);
+        assert!(orient2d(a, b, (0.5, x)) > 0.0);
+        assert_eq!(orient2d(a, b, (24.0, 24.0)), 0.0);
+    }
+
+    #[test]
+    fn in_diametral_works() {
+        let (a, b) = ((0.0, 0.0), (2.0, 0.0));
+        assert!(in_diametral(a, b, (1.0, 0.5)) > 0.0);
+        assert!(in_diametral(a, b, (1.0, 1.5)) < 0.0);
+        assert_eq!(in_diametral(a, b, (1.0, 1.0)), 0.0);
+
+        // slightly outside (the naive dot product rounds to zero)
+        let (a, b, c) = ((-1.0, 0.0), (1.0, 0.0), (1e-20, 1.0));
+        assert_eq!((a.0 - c.0) * (b.0 - c.0) + (a.1 - c.1) * (b.1 - c.1), 0.0);
+        assert!(in_diametral(a, b, c) < 0.0);
+    }
+
+    #[test]
+    fn compare_distances_works() {
+        let o = (0.0, 0.0);
+        assert!(compare_distances(o, (3.0, 4.0), o, (4.0, 2.0)) > 0.0);
+        assert!(compare_distances(o, (3.0, 4.0), o, (4.0, 3.5)) < 0.0);
+        assert_eq!(compare_distances(o, (3.0, 4.0), (1.0, 1.0), (6.0, 1.0)), 0.0);
+
+        // distances differing beyond the precision of their squares
+        assert!(compare_distances((1e-30, 0.0), (1.0, 0.0), (0.0, 0.0), (1.0, 0.0)) < 0.0);
+    }
+}
@@ -0,0 +1,202 @@
+use std::any::Any;
+use std::ffi::c_void;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::time::{Duration, Instant};
+
+extern "C" {
+    fn tet_set_progress_callback(callback: Option<extern "C" fn(*mut c_void, i32)>, data: *mut c_void);
+}
+
+/// Defines the phases of a Tetgen generation reported to the progress handler
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationPhase {
+    /// Conversion of the input (points, facets, regions, and holes) to Tetgen's data structures
+    InputConversion,
+
+    /// Delaunay tetrahedralization of the points (or reconstruction of the mesh to be refined)
+    Delaunay,
+
+    /// Recovery of the facets, removal of the holes, and repair of the mesh (only with facets)
+    BoundaryRecovery,
+
+    /// Insertion of Steiner points to satisfy the quality constraints (only with generate_mesh and refine_mesh)
+    Refinement,
+
+    /// Conversion of the mesh to the output (points, tetrahedra, and marked faces)
+    OutputConversion,
+}
+
+/// Holds a progress notification given at the end of a phase of the generation
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    /// Holds the phase that has just finished
+    pub phase: GenerationPhase,
+
+    /// Holds the wall-clock time spent in the phase
+    pub phase_time: Duration,
+
+    /// Holds the wall-clock time since the beginning of the generation
+    pub total_time: Duration,
+}
+
+/// Receives the progress notifications of a generation (see `Tetgen::set_progress_handler`)
+///
+/// The handler is implemented for closures; e.g., `|p: &Progress| println!("{:?}", p)`.
+pub trait ProgressHandler: Send {
+    /// Handles the notification given at the end of a phase
+    fn on_progress(&mut self, progress: &Progress);
+}
+
+impl<F> ProgressHandler for F
+where
+    F: FnMut(&Progress) + Send,
+{
+    fn on_progress(&mut self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+/// Holds the state of the handler during a call to the c-code
+struct ProgressState<'a> {
+    handler: &'a mut dyn ProgressHandler,
+    start: Instant,
+    last: Instant,
+    panic: Option<Box<dyn Any + Send>>, // payload of a panic of the handler (resumed after the c-code returns)
+}
+
+/// Receives the phases from the c-code
+///
+/// A panic of the handler must not unwind into the c-code; thus, it is caught and stored in the
+/// state, and the handler is not called again.
+extern "C" fn notify_progress(data: *mut c_void, phase: i32) {
+    let state = unsafe { &mut *(data as *mut ProgressState) };
+    if state.panic.is_some() {
+        return;
+    }
+    let phase = match phase {
+        0 => GenerationPhase::InputConversion,
+        1 => GenerationPhase::Delaunay,
+        2 => GenerationPhase::BoundaryRecovery,
+        3 => GenerationPhase::Refinement,
+        4 => GenerationPhase::OutputConversion,
+        _ => return,
+    };
+    let now = Instant::now();
+    let progress = Progress {
+        phase,
+        phase_time: now - state.last,
+        total_time: now - state.start,
+    };
+    state.last = now;
+    let handler = &mut state.handler;
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| handler.on_progress(&progress))) {
+        state.panic = Some(payload);
+    }
+}
+
+/// Runs a call to the c-code reporting the phases to the handler (if any)
+///
+/// If the handler panics, the panic is resumed after the c-code returns.
+pub(crate) fn run_with_progress<T, F>(handler: Option<&mut dyn ProgressHandler>, run: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let handler = match handler {
+        Some(h) => h,
+        None => return run(),
+    };
+    let start = Instant::now();
+    let mut state = ProgressState {
+        handler,
+        start,
+        last: start,
+        panic: None,
+    };
+    unsafe {
+        tet_set_progress_callback(Some(notify_progress), &mut state as *mut ProgressState as *mut c_void);
+    }
+    let result = run();
+    unsafe {
+        tet_set_progress_callback(None, ptr::null_mut());
+    }
+    if let Some(payload) = state.panic {
+        panic::resume_unwind(payload);
+    }
+    result
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{GenerationPhase, Progress};
+    use crate::fixtures::cube;
+    use crate::StrError;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn progress_handler_works() -> Result<(), StrError> {
+        use GenerationPhase::*;
+        let reports: Arc<Mutex<Vec<Progress>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+        let mut tetgen = cube()?;
+        tetgen.set_progress_handler(move |p: &Progress| sink.lock().unwrap().push(*p));
+
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        let phases: Vec<_> = reports.lock().unwrap().iter().map(|p| p.phase).collect();
+        assert_eq!(
+            phases,
+            &[
+                InputConversion,
+                Delaunay,
+                BoundaryRecovery,
+                Refinement,
+                OutputConversion
+            ]
+        );
+        let mut sum = std::time::Duration::ZERO;
+        for p in reports.lock().unwrap().iter() {
+            sum += p.phase_time;
+            assert_eq!(p.total_time, sum);
+        }
+
+        reports.lock().unwrap().clear();
+        tetgen.generate_delaunay(false)?;
+        let phases: Vec<_> = reports.lock().unwrap().iter().map(|p| p.phase).collect();
+        assert_eq!(phases, &[InputConversion, Delaunay, OutputConversion]);
+
+        reports.lock().unwrap().clear();
+        tetgen.clear_progress_handler();
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert_eq!(reports.lock().unwrap().len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn panicking_progress_handler_is_resumed() -> Result<(), StrError> {
+        let count = Arc::new(Mutex::new(0));
+        let sink = count.clone();
+        let lines = Arc::new(Mutex::new(0));
+        let line_sink = lines.clone();
+        let mut tetgen = cube()?;
+        tetgen.set_console_handler(move |_: &str| *line_sink.lock().unwrap() += 1);
+        tetgen.set_progress_handler(move |_: &Progress| {
+            *sink.lock().unwrap() += 1;
+            panic!("stop");
+        });
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            tetgen.generate_mesh(false, false, Some(0.01), None)
+        }));
+        assert_eq!(*res.err().unwrap().downcast::<&str>().unwrap(), "stop");
+        assert_eq!(*count.lock().unwrap(), 1);
+
+        // the generator may be used again (with the other handlers)
+        tetgen.clear_progress_handler();
+        *lines.lock().unwrap() = 0;
+        tetgen.generate_mesh(true, false, Some(0.01), None)?;
+        assert!(tetgen.ntet() > 0);
+        assert!(*lines.lock().unwrap() > 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{Tetgen, Triangle};
+
+/// Defines the origin of an output point
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointOrigin {
+    /// The point is an input point (see `point_original_index`)
+    Input,
+
+    /// The point is a Steiner point inserted on the boundary or on a constraint (segment or facet)
+    SteinerBoundary,
+
+    /// The point is a Steiner point inserted in the interior
+    SteinerInterior,
+
+    /// The point is a middle node of a quadratic cell (see `midside_parents`)
+    MiddleNode,
+
+    /// The point is created at the crossing of input segments (see `Triangle::segment_intersections`)
+    SegmentIntersection,
+}
+
+impl Triangle {
+    /// Returns the origin of each output point
+    ///
+    /// The Steiner points with a non-zero marker (see `point_marker`) are on the boundary or on a
+    /// segment; thus, a Steiner point on an interior segment with marker zero is considered
+    /// to be in the interior. The points at the crossings of segments are not Steiner points.
+    pub fn point_origins(&self) -> Vec<PointOrigin> {
+        let parents = self.midside_parents();
+        let mut crossing = vec![false; self.npoint()];
+        for inter in self.segment_intersections() {
+            crossing[inter.point] = true;
+        }
+        (0..self.npoint())
+            .map(|p| {
+                if parents[p].is_some() {
+                    PointOrigin::MiddleNode
+                } else if self.point_original_index(p).is_some() {
+                    PointOrigin::Input
+                } else if crossing[p] {
+                    PointOrigin::SegmentIntersection
+                } else if self.point_marker(p) != 0 {
+                    PointOrigin::SteinerBoundary
+                } else {
+                    PointOrigin::SteinerInterior
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of Steiner points inserted on the boundary or on segments (see `point_origins`)
+    pub fn nsteiner_boundary(&self) -> usize {
+        count(&self.point_origins(), PointOrigin::SteinerBoundary)
+    }
+
+    /// Returns the number of Steiner points inserted in the interior (see `point_origins`)
+    pub fn nsteiner_interior(&self) -> usize {
+        count(&self.point_origins(), PointOrigin::SteinerInterior)
+    }
+}
+
+impl Tetgen {
+    /// Returns the origin of each output point
+    ///
+    /// The Steiner points on the corners of marked faces (see `marked_face_node`) are on the
+    /// boundary or on a facet.
+    pub fn point_origins(&self) -> Vec<PointOrigin> {
+        let parents = self.midside_parents();
+        let mut on_face = vec![false; self.npoint()];
+        for face in 0..self.nmarked_face() {
+            for m in 0..3 {
+                on_face[self.marked_face_node(face, m)] = true;
+            }
+        }
+        (0..self.npoint())
+            .map(|p| {
+                if parents[p].is_some() {
+                    PointOrigin::MiddleNode
+                } else if self.point_original_index(p).is_some() {
+                    PointOrigin::Input
+                } else if on_face[p] {
+                    PointOrigin::SteinerBoundary
+                } else {
+                    PointOrigin::SteinerInterior
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the number of Steiner points inserted on the boundary or on facets (see `point_origins`)
+    pub fn nsteiner_boundary(&self) -> usize {
+        count(&self.point_origins(), PointOrigin::SteinerBoundary)
+    }
+
+    /// Returns the number of Steiner points inserted in the interior (see `point_origins`)
+    pub fn nsteiner_interior(&self) -> usize {
+        count(&self.point_origins(), PointOrigin::SteinerInterior)
+    }
+}
+
+/// Counts the points with a given origin
+fn count(origins: &[PointOrigin], origin: PointOrigin) -> usize {
+    origins.iter().filter(|o| **o == origin).count()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PointOrigin;
+    use crate::fixtures::square;
+    use crate::{StrError, Tetgen};
+
+    #[test]
+    fn triangle_point_origins_works() -> Result<(), StrError> {
+        let triangle = square()?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.point_origins(), &[PointOrigin::Input; 4]);
+        assert_eq!(triangle.nsteiner_boundary(), 0);
+        assert_eq!(triangle.nsteiner_interior(), 0);
+
+        triangle.generate_mesh(false, true, Some(0.01), None)?;
+        let origins = triangle.point_origins();
+        assert_eq!(&origins[..4], &[PointOrigin::Input; 4]);
+        let nboundary = triangle.nsteiner_boundary();
+        let ninterior = triangle.nsteiner_interior();
+        assert!(nboundary > 0 && ninterior > 0);
+        let nmiddle = origins.iter().filter(|o| **o == PointOrigin::MiddleNode).count();
+        assert_eq!(4 + nboundary + ninterior + nmiddle, triangle.npoint());
+        for (p, origin) in origins.iter().enumerate() {
+            let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+            let on_boundary = x == 0.0 || x == 1.0 || y == 0.0 || y == 1.0;
+            match origin {
+                PointOrigin::SteinerBoundary => assert!(on_boundary),
+                PointOrigin::SteinerInterior => assert!(!on_boundary),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_point_origins_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.generate_mesh(false, true, Some(0.001), None)?;
+        let origins = tetgen.point_origins();
+        assert_eq!(&origins[..4], &[PointOrigin::Input; 4]);
+        let nboundary = tetgen.nsteiner_boundary();
+        let ninterior = tetgen.nsteiner_interior();
+        assert!(nboundary > 0 && ninterior > 0);
+        let nmiddle = origins.iter().filter(|o| **o == PointOrigin::MiddleNode).count();
+        assert_eq!(4 + nboundary + ninterior + nmiddle, tetgen.npoint());
+        for (p, origin) in origins.iter().enumerate() {
+            let x = [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)];
+            let on_boundary = x.contains(&0.0) || f64::abs(x[0] + x[1] + x[2] - 1.0) < 1e-14;
+            match origin {
+                PointOrigin::SteinerBoundary => assert!(on_boundary),
+                PointOrigin::SteinerInterior => assert!(!on_boundary),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
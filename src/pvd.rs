@@ -0,0 +1,176 @@
+use crate::{write_mesh_vtu, Mesh, StrError};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::PathBuf;
+
+/// Writes a sequence of meshes as a Paraview's PVD collection (time series)
+///
+/// Each call to `add_mesh` writes the VTU file `{stem}_{step}.vtu` (with six digits) in the
+/// directory and rewrites `{stem}.pvd` referencing all VTU files written so far; thus, the
+/// collection is valid even if the iterations are interrupted. Open the PVD file in Paraview
+/// to animate the evolution of the mesh; e.g., during Lloyd iterations or adaptive refinement.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{PvdWriter, StrError, Triangle, TriangleOptions};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut triangle = Triangle::new(4, Some(4), None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 1.0, 1.0)?
+///         .set_point(3, 0.0, 1.0)?
+///         .set_segment(0, 0, 1)?
+///         .set_segment(1, 1, 2)?
+///         .set_segment(2, 2, 3)?
+///         .set_segment(3, 3, 0)?;
+///     triangle.generate_mesh(false, false, None, None)?;
+///
+///     let mut writer = PvdWriter::new("/tmp/tritet", "doc_pvd_writer");
+///     writer.add_mesh(0.0, &triangle.to_mesh())?;
+///     for i in 1..4 {
+///         let areas = vec![0.5 / (i as f64); triangle.ntriangle()];
+///         triangle.refine_mesh(&areas, &TriangleOptions::new())?;
+///         writer.add_mesh(i as f64, &triangle.to_mesh())?;
+///     }
+///     assert_eq!(writer.nstep(), 4);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PvdWriter {
+    /// Holds the directory of the PVD and VTU files
+    directory: PathBuf,
+
+    /// Holds the stem of the file names
+    stem: String,
+
+    /// Holds the time and VTU file name of each step
+    steps: Vec<(f64, String)>,
+}
+
+impl PvdWriter {
+    /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `directory` -- may be a String, &str, or Path (created if needed)
+    /// * `stem` -- is the name of the PVD file without extension
+    pub fn new<P>(directory: &P, stem: &str) -> Self
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        PvdWriter {
+            directory: PathBuf::from(directory),
+            stem: stem.to_string(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Writes the VTU file of the next step and updates the PVD file
+    ///
+    /// # Input
+    ///
+    /// * `time` -- is the time (or iteration) of the step shown by Paraview
+    /// * `mesh` -- is the mesh at this step (see `write_mesh_vtu`)
+    pub fn add_mesh<const DIM: usize>(&mut self, time: f64, mesh: &Mesh<DIM>) -> Result<(), StrError> {
+        let filename = format!("{}_{:06}.vtu", self.stem, self.steps.len());
+        write_mesh_vtu(mesh, &self.directory.join(&filename))?;
+        self.steps.push((time, filename));
+        self.write_pvd()
+    }
+
+    /// Returns the number of steps written so far
+    pub fn nstep(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns the full path of the PVD file
+    pub fn pvd_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.pvd", self.stem))
+    }
+
+    /// Writes the PVD file referencing the VTU files written so far
+    fn write_pvd(&self) -> Result<(), StrError> {
+        let mut buffer = String::new();
+        buffer.push_str(
+            "<?xml version=\"1.0\"?>\n\
+             <VTKFile type=\"Collection\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+             <Collection>\n",
+        );
+        for (time, filename) in &self.steps {
+            writeln!(
+                &mut buffer,
+                "<DataSet timestep=\"{:?}\" group=\"\" part=\"0\" file=\"{}\" />",
+                time, filename
+            )
+            .unwrap();
+        }
+        buffer.push_str("</Collection>\n</VTKFile>\n");
+
+        // create directory
+        let path = self.pvd_path();
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+
+        // write file
+        let mut file = File::create(&path).map_err(|_| "cannot create file")?;
+        file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+        // force sync
+        file.sync_all().map_err(|_| "cannot sync file")?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::PvdWriter;
+    use crate::{CellTags, StrError, TriMesh};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn pvd_writer_works() -> Result<(), StrError> {
+        let mut mesh = TriMesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+            cells: Vec::new(),
+            attributes: Vec::new(),
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        };
+        let mut writer = PvdWriter::new("/tmp/tritet/test_pvd_writer", "mesh");
+        assert_eq!(writer.add_mesh(0.0, &mesh).err(), Some("there are no cells to write"));
+        assert_eq!(writer.nstep(), 0);
+
+        mesh.cells = vec![vec![0, 1, 2]];
+        mesh.attributes = vec![1];
+        writer.add_mesh(0.0, &mesh)?;
+        mesh.points[2] = [0.5, 1.0];
+        writer.add_mesh(0.5, &mesh)?;
+        assert_eq!(writer.nstep(), 2);
+        assert!(Path::new("/tmp/tritet/test_pvd_writer/mesh_000000.vtu").exists());
+        assert!(Path::new("/tmp/tritet/test_pvd_writer/mesh_000001.vtu").exists());
+
+        let contents = fs::read_to_string(writer.pvd_path()).map_err(|_| "cannot read file")?;
+        assert_eq!(
+            contents,
+            "<?xml version=\"1.0\"?>\n\
+             <VTKFile type=\"Collection\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
+             <Collection>\n\
+             <DataSet timestep=\"0.0\" group=\"\" part=\"0\" file=\"mesh_000000.vtu\" />\n\
+             <DataSet timestep=\"0.5\" group=\"\" part=\"0\" file=\"mesh_000001.vtu\" />\n\
+             </Collection>\n\
+             </VTKFile>\n"
+        );
+        Ok(())
+    }
+}
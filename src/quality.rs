@@ -0,0 +1,538 @@
+use crate::vector::{cross, dot, norm, sub};
+use crate::{StrError, Tetgen, Triangle};
+
+/// Holds the number of bins of the angle histograms in `QualitySummary`
+pub const QUALITY_HISTOGRAM_NBIN: usize = 18;
+
+/// Holds the minimum angle (degrees) used by Triangle when `min_angle` is None
+const DEFAULT_MIN_ANGLE: f64 = 20.0;
+
+/// Holds the minimum dihedral angle (degrees) used by Tetgen when `min_dihedral_angle` is None
+const DEFAULT_MIN_DIHEDRAL_ANGLE: f64 = 5.0;
+
+/// Holds the tolerance (degrees) used to compare the achieved angles with the requested bounds
+const ANGLE_TOLERANCE: f64 = 1e-8;
+
+/// Holds the quality metrics of a triangle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriQuality {
+    /// Holds the area
+    pub area: f64,
+
+    /// Holds the smallest interior angle (degrees)
+    pub min_angle: f64,
+
+    /// Holds the largest interior angle (degrees)
+    pub max_angle: f64,
+
+    /// Holds the longest edge divided by the shortest altitude, scaled to be 1 for the equilateral triangle
+    ///
+    /// The aspect ratio is infinite for degenerate (zero-area) triangles.
+    pub aspect_ratio: f64,
+}
+
+/// Holds the quality metrics of a tetrahedron
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TetQuality {
+    /// Holds the volume
+    pub volume: f64,
+
+    /// Holds the smallest dihedral angle (degrees)
+    pub min_dihedral: f64,
+
+    /// Holds the largest dihedral angle (degrees)
+    pub max_dihedral: f64,
+
+    /// Holds the circumradius divided by the shortest edge (as in Tetgen's `-q` switch)
+    ///
+    /// The ratio equals √6/4 ≈ 0.612 for the regular tetrahedron and is infinite for
+    /// degenerate (zero-volume) tetrahedra.
+    pub radius_edge_ratio: f64,
+}
+
+/// Holds a summary of the quality of all cells in a mesh
+///
+/// For triangles, the angles are the interior angles and the ratio is the aspect ratio.
+/// For tetrahedra, the angles are the dihedral angles and the ratio is the radius-edge ratio.
+///
+/// The histograms split the range from 0 to 180 degrees into `QUALITY_HISTOGRAM_NBIN` bins of
+/// 10 degrees; i.e., the bin `i` counts the cells with angle in `[10 i, 10 (i + 1))`, and the
+/// angle of 180 degrees is counted in the last bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualitySummary {
+    /// Holds the number of cells
+    pub ncell: usize,
+
+    /// Holds the total area (2D) or volume (3D)
+    pub total_size: f64,
+
+    /// Holds the smallest angle (degrees) of all cells
+    pub min_angle: f64,
+
+    /// Holds the largest angle (degrees) of all cells
+    pub max_angle: f64,
+
+    /// Holds the largest (worst) aspect ratio (2D) or radius-edge ratio (3D)
+    pub max_ratio: f64,
+
+    /// Holds the number of cells according to their smallest angle
+    pub min_angle_histogram: [usize; QUALITY_HISTOGRAM_NBIN],
+
+    /// Holds the number of cells according to their largest angle
+    pub max_angle_histogram: [usize; QUALITY_HISTOGRAM_NBIN],
+}
+
+impl QualitySummary {
+    /// Returns a summary computed from the size, smallest angle, largest angle, and ratio of each cell
+    fn new<I>(cells: I) -> Option<Self>
+    where
+        I: Iterator<Item = (f64, f64, f64, f64)>,
+    {
+        let mut summary: Option<QualitySummary> = None;
+        for (size, min_angle, max_angle, ratio) in cells {
+            let s = summary.get_or_insert(QualitySummary {
+                ncell: 0,
+                total_size: 0.0,
+                min_angle,
+                max_angle,
+                max_ratio: ratio,
+                min_angle_histogram: [0; QUALITY_HISTOGRAM_NBIN],
+                max_angle_histogram: [0; QUALITY_HISTOGRAM_NBIN],
+            });
+            s.ncell += 1;
+            s.total_size += size;
+            s.min_angle = f64::min(s.min_angle, min_angle);
+            s.max_angle = f64::max(s.max_angle, max_angle);
+            s.max_ratio = f64::max(s.max_ratio, ratio);
+            s.min_angle_histogram[histogram_bin(min_angle)] += 1;
+            s.max_angle_histogram[histogram_bin(max_angle)] += 1;
+        }
+        summary
+    }
+}
+
+impl Triangle {
+    /// Returns the smallest angle (in degrees) of all triangles in the current mesh
+    ///
+    /// Only the corners of the triangles are considered. The minimum angle constraint of
+    /// `generate_mesh` may be violated near small angles of the input segments; thus, this
+    /// function reports the value actually achieved. See also `TriangleOptions::enforce_min_angle`.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if there are no triangles.
+    pub fn min_angle(&self) -> Option<f64> {
+        (0..self.ntriangle())
+            .filter_map(|t| self.triangle_quality(t))
+            .map(|q| q.min_angle)
+            .reduce(f64::min)
+    }
+
+    /// Returns the quality metrics of a triangle
+    ///
+    /// Only the corners of the triangle are considered.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the triangle and goes from 0 to `ntriangle`
+    ///
+    /// Returns None if `index` is out of range.
+    pub fn triangle_quality(&self, index: usize) -> Option<TriQuality> {
+        if index >= self.ntriangle() {
+            return None;
+        }
+        let x: Vec<[f64; 2]> = (0..3)
+            .map(|m| {
+                let p = self.triangle_node(index, m);
+                [self.point(p, 0), self.point(p, 1)]
+            })
+            .collect();
+        let (mut min_angle, mut max_angle, mut max_length) = (f64::MAX, 0.0, 0.0);
+        for i in 0..3 {
+            let (a, b) = (x[(i + 1) % 3], x[(i + 2) % 3]);
+            let u = [a[0] - x[i][0], a[1] - x[i][1]];
+            let v = [b[0] - x[i][0], b[1] - x[i][1]];
+            let angle = vector_angle(&u, &v);
+            min_angle = f64::min(min_angle, angle);
+            max_angle = f64::max(max_angle, angle);
+            max_length = f64::max(max_length, norm(&u));
+        }
+        let u = [x[1][0] - x[0][0], x[1][1] - x[0][1]];
+        let v = [x[2][0] - x[0][0], x[2][1] - x[0][1]];
+        let area = f64::abs(u[0] * v[1] - u[1] * v[0]) / 2.0;
+        let aspect_ratio = if area > 0.0 {
+            max_length * max_length * f64::sqrt(3.0) / (4.0 * area)
+        } else {
+            f64::INFINITY
+        };
+        Some(TriQuality {
+            area,
+            min_angle,
+            max_angle,
+            aspect_ratio,
+        })
+    }
+
+    /// Returns a summary of the quality of all triangles in the current mesh
+    ///
+    /// See `triangle_quality` and `QualitySummary`.
+    ///
+    /// Returns None if there are no triangles.
+    pub fn quality_summary(&self) -> Option<QualitySummary> {
+        QualitySummary::new((0..self.ntriangle()).filter_map(|t| {
+            self.triangle_quality(t)
+                .map(|q| (q.area, q.min_angle, q.max_angle, q.aspect_ratio))
+        }))
+    }
+
+    /// Returns an error if the minimum angle of the mesh is smaller than the requested one
+    pub(crate) fn check_min_angle(&self, min_angle: Option<f64>) -> Result<(), StrError> {
+        let bound = min_angle.unwrap_or(DEFAULT_MIN_ANGLE);
+        match self.min_angle() {
+            Some(achieved) if achieved + ANGLE_TOLERANCE < bound => {
+                Err("the minimum angle of the triangles is smaller than the requested one")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Tetgen {
+    /// Returns the smallest dihedral angle (in degrees) of all tetrahedra in the current mesh
+    ///
+    /// Only the corners of the tetrahedra are considered. Tetgen does not guarantee the minimum
+    /// dihedral angle constraint; thus, this function reports the value actually achieved.
+    /// See also `TetgenOptions::enforce_min_dihedral_angle`.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if there are no tetrahedra.
+    pub fn min_dihedral_angle(&self) -> Option<f64> {
+        (0..self.ntet())
+            .filter_map(|t| self.tet_quality(t))
+            .map(|q| q.min_dihedral)
+            .reduce(f64::min)
+    }
+
+    /// Returns the quality metrics of a tetrahedron
+    ///
+    /// Only the corners of the tetrahedron are considered.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the tetrahedron and goes from 0 to `ntet`
+    ///
+    /// Returns None if `index` is out of range.
+    pub fn tet_quality(&self, index: usize) -> Option<TetQuality> {
+        const EDGES: [[usize; 4]; 6] = [
+            [0, 1, 2, 3],
+            [0, 2, 1, 3],
+            [0, 3, 1, 2],
+            [1, 2, 0, 3],
+            [1, 3, 0, 2],
+            [2, 3, 0, 1],
+        ];
+        if index >= self.ntet() {
+            return None;
+        }
+        let x: Vec<[f64; 3]> = (0..4)
+            .map(|m| {
+                let p = self.tet_node(index, m);
+                [self.point(p, 0), self.point(p, 1), self.point(p, 2)]
+            })
+            .collect();
+        let (mut min_dihedral, mut max_dihedral, mut min_length) = (f64::MAX, 0.0, f64::MAX);
+        for [i, j, k, l] in EDGES {
+            // the normals of the two faces sharing the edge (i,j) are perpendicular to the edge
+            // and rotated by the same angle; thus, the angle between them is the dihedral angle
+            let n1 = cross(&sub(&x[j], &x[i]), &sub(&x[k], &x[i]));
+            let n2 = cross(&sub(&x[j], &x[i]), &sub(&x[l], &x[i]));
+            let angle = vector_angle(&n1, &n2);
+            min_dihedral = f64::min(min_dihedral, angle);
+            max_dihedral = f64::max(max_dihedral, angle);
+            min_length = f64::min(min_length, norm(&sub(&x[j], &x[i])));
+        }
+        let (a, b, c) = (sub(&x[1], &x[0]), sub(&x[2], &x[0]), sub(&x[3], &x[0]));
+        let (bc, ca, ab) = (cross(&b, &c), cross(&c, &a), cross(&a, &b));
+        let det = dot(&a, &bc);
+        let volume = f64::abs(det) / 6.0;
+        let radius_edge_ratio = if det != 0.0 && min_length > 0.0 {
+            // the circumcenter relative to the first corner
+            let (aa, bb, cc) = (dot(&a, &a), dot(&b, &b), dot(&c, &c));
+            let center = [0, 1, 2].map(|k| (aa * bc[k] + bb * ca[k] + cc * ab[k]) / (2.0 * det));
+            norm(&center) / min_length
+        } else {
+            f64::INFINITY
+        };
+        Some(TetQuality {
+            volume,
+            min_dihedral,
+            max_dihedral,
+            radius_edge_ratio,
+        })
+    }
+
+    /// Returns a summary of the quality of all tetrahedra in the current mesh
+    ///
+    /// See `tet_quality` and `QualitySummary`.
+    ///
+    /// Returns None if there are no tetrahedra.
+    pub fn quality_summary(&self) -> Option<QualitySummary> {
+        QualitySummary::new((0..self.ntet()).filter_map(|t| {
+            self.tet_quality(t)
+                .map(|q| (q.volume, q.min_dihedral, q.max_dihedral, q.radius_edge_ratio))
+        }))
+    }
+
+    /// Returns an error if the minimum dihedral angle of the mesh is smaller than the requested one
+    pub(crate) fn check_min_dihedral_angle(&self, min_dihedral_angle: Option<f64>) -> Result<(), StrError> {
+        let bound = min_dihedral_angle.unwrap_or(DEFAULT_MIN_DIHEDRAL_ANGLE);
+        match self.min_dihedral_angle() {
+            Some(achieved) if achieved + ANGLE_TOLERANCE < bound => {
+                Err("the minimum dihedral angle of the tetrahedra is smaller than the requested one")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns the angle (in degrees) between two vectors
+fn vector_angle<const N: usize>(u: &[f64; N], v: &[f64; N]) -> f64 {
+    let (nu, nv) = (norm(u), norm(v));
+    if nu == 0.0 || nv == 0.0 {
+        return 0.0;
+    }
+    f64::clamp(dot(u, v) / (nu * nv), -1.0, 1.0).acos().to_degrees()
+}
+
+/// Returns the index of the histogram bin of an angle (degrees)
+fn histogram_bin(angle: f64) -> usize {
+    usize::min((angle / 10.0) as usize, QUALITY_HISTOGRAM_NBIN - 1)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{histogram_bin, vector_angle, QUALITY_HISTOGRAM_NBIN};
+    use crate::fixtures::{cube, square};
+    use crate::{StrError, Tetgen, TetgenOptions, Triangle, TriangleOptions};
+
+    #[test]
+    fn vector_angle_works() {
+        assert_eq!(vector_angle(&[1.0, 0.0], &[0.0, 2.0]), 90.0);
+        assert!((vector_angle(&[1.0, 0.0, 0.0], &[1.0, 1.0, 0.0]) - 45.0).abs() < 1e-13);
+        assert_eq!(vector_angle(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn min_angle_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(triangle.min_angle(), None);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert!((triangle.min_angle().unwrap() - 45.0).abs() < 1e-13);
+        Ok(())
+    }
+
+    #[test]
+    fn histogram_bin_works() {
+        assert_eq!(histogram_bin(0.0), 0);
+        assert_eq!(histogram_bin(9.99), 0);
+        assert_eq!(histogram_bin(10.0), 1);
+        assert_eq!(histogram_bin(179.0), QUALITY_HISTOGRAM_NBIN - 1);
+        assert_eq!(histogram_bin(180.0), QUALITY_HISTOGRAM_NBIN - 1);
+    }
+
+    #[test]
+    fn triangle_quality_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        assert_eq!(triangle.triangle_quality(0), None);
+        assert_eq!(triangle.quality_summary(), None);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 1.0, f64::sqrt(3.0))?
+            .set_point(3, 1.0, -1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        assert_eq!(triangle.triangle_quality(2), None);
+        let (mut equilateral, mut right) = (None, None);
+        for t in 0..2 {
+            let q = triangle.triangle_quality(t).unwrap();
+            if q.max_angle > 80.0 {
+                right = Some(q);
+            } else {
+                equilateral = Some(q);
+            }
+        }
+        let q = equilateral.unwrap();
+        assert!((q.area - f64::sqrt(3.0)).abs() < 1e-14);
+        assert!((q.min_angle - 60.0).abs() < 1e-12);
+        assert!((q.max_angle - 60.0).abs() < 1e-12);
+        assert!((q.aspect_ratio - 1.0).abs() < 1e-14);
+        let q = right.unwrap();
+        assert!((q.area - 1.0).abs() < 1e-14);
+        assert!((q.min_angle - 45.0).abs() < 1e-12);
+        assert!((q.max_angle - 90.0).abs() < 1e-12);
+        assert!((q.aspect_ratio - f64::sqrt(3.0)).abs() < 1e-14);
+
+        let summary = triangle.quality_summary().unwrap();
+        assert_eq!(summary.ncell, 2);
+        assert!((summary.total_size - (1.0 + f64::sqrt(3.0))).abs() < 1e-14);
+        assert!((summary.min_angle - 45.0).abs() < 1e-12);
+        assert!((summary.max_angle - 90.0).abs() < 1e-12);
+        assert!((summary.max_ratio - f64::sqrt(3.0)).abs() < 1e-14);
+        // 45 and 60 degrees (the latter may fall into the previous bin due to round-off)
+        let counts = &summary.min_angle_histogram;
+        assert_eq!(counts[4] + counts[5], 2);
+        assert_eq!(counts.iter().sum::<usize>(), 2);
+        assert_eq!(summary.max_angle_histogram.iter().sum::<usize>(), 2);
+        assert_eq!(summary.max_angle_histogram[9], 1); // 90
+        Ok(())
+    }
+
+    #[test]
+    fn enforce_min_angle_works() -> Result<(), StrError> {
+        // wedge with an input angle of about 5.7 degrees
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 0.1)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        let mut options = TriangleOptions::new();
+        options.set_min_angle(20.0);
+        triangle.generate_mesh_with(&options)?;
+        assert!(triangle.min_angle().unwrap() < 20.0);
+        options.set_enforce_min_angle(true);
+        assert_eq!(
+            triangle.generate_mesh_with(&options).err(),
+            Some("the minimum angle of the triangles is smaller than the requested one")
+        );
+        let areas = vec![0.0; triangle.ntriangle()];
+        assert_eq!(
+            triangle.refine_mesh(&areas, &options).err(),
+            Some("the minimum angle of the triangles is smaller than the requested one")
+        );
+
+        // the bound is satisfied away from small input angles
+        let triangle = square()?;
+        let mut options = TriangleOptions::new();
+        options
+            .set_max_area(0.01)
+            .set_min_angle(30.0)
+            .set_enforce_min_angle(true);
+        triangle.generate_mesh_with(&options)?;
+        assert!(triangle.min_angle().unwrap() >= 30.0 - 1e-8);
+        Ok(())
+    }
+
+    #[test]
+    fn min_dihedral_angle_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(tetgen.min_dihedral_angle(), None);
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        // the dihedral angles at the edges (1,2), (1,3), and (2,3) are acos(1/√3)
+        let correct = f64::acos(1.0 / f64::sqrt(3.0)).to_degrees();
+        assert!((tetgen.min_dihedral_angle().unwrap() - correct).abs() < 1e-13);
+        Ok(())
+    }
+
+    #[test]
+    fn tet_quality_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(tetgen.tet_quality(0), None);
+        assert_eq!(tetgen.quality_summary(), None);
+        tetgen
+            .set_point(0, 1.0, 1.0, 1.0)?
+            .set_point(1, 1.0, -1.0, -1.0)?
+            .set_point(2, -1.0, 1.0, -1.0)?
+            .set_point(3, -1.0, -1.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.tet_quality(1), None);
+
+        // regular tetrahedron with edge 2√2
+        let q = tetgen.tet_quality(0).unwrap();
+        let dihedral = f64::acos(1.0 / 3.0).to_degrees();
+        assert!((q.volume - 8.0 / 3.0).abs() < 1e-14);
+        assert!((q.min_dihedral - dihedral).abs() < 1e-12);
+        assert!((q.max_dihedral - dihedral).abs() < 1e-12);
+        assert!((q.radius_edge_ratio - f64::sqrt(6.0) / 4.0).abs() < 1e-14);
+
+        let summary = tetgen.quality_summary().unwrap();
+        assert_eq!(summary.ncell, 1);
+        assert!((summary.total_size - 8.0 / 3.0).abs() < 1e-14);
+        assert_eq!(summary.min_angle, q.min_dihedral);
+        assert_eq!(summary.max_angle, q.max_dihedral);
+        assert_eq!(summary.max_ratio, q.radius_edge_ratio);
+        let mut correct = [0; QUALITY_HISTOGRAM_NBIN];
+        correct[7] = 1; // 70.53
+        assert_eq!(summary.min_angle_histogram, correct);
+        assert_eq!(summary.max_angle_histogram, correct);
+
+        // the summary of a refined cube
+        let tetgen = cube()?;
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        let summary = tetgen.quality_summary().unwrap();
+        assert_eq!(summary.ncell, tetgen.ntet());
+        assert!((summary.total_size - 1.0).abs() < 1e-13);
+        assert_eq!(summary.min_angle, tetgen.min_dihedral_angle().unwrap());
+        assert_eq!(summary.min_angle_histogram.iter().sum::<usize>(), tetgen.ntet());
+        assert!(summary.max_ratio >= f64::sqrt(6.0) / 4.0);
+        Ok(())
+    }
+
+    #[test]
+    fn enforce_min_dihedral_angle_works() -> Result<(), StrError> {
+        let tetgen = cube()?;
+        let mut options = TetgenOptions::new();
+        options.set_max_volume(0.01).set_enforce_min_dihedral_angle(true);
+        tetgen.generate_mesh_with(&options)?;
+        assert!(tetgen.min_dihedral_angle().unwrap() >= 5.0);
+
+        // prism with a wedge of about 2.9 degrees; thus, the default bound of five degrees is never met
+        let mut tetgen = Tetgen::new(6, Some(vec![3, 3, 4, 4, 4]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 0.05, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 1.0, 0.0, 1.0)?
+            .set_point(5, 1.0, 0.05, 1.0)?;
+        let faces: [&[usize]; 5] = [&[0, 2, 1], &[3, 4, 5], &[0, 1, 4, 3], &[1, 2, 5, 4], &[2, 0, 3, 5]];
+        for (index, face) in faces.iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        let mut options = TetgenOptions::new();
+        tetgen.generate_mesh_with(&options)?;
+        assert!(tetgen.min_dihedral_angle().unwrap() < 5.0);
+        options.set_enforce_min_dihedral_angle(true);
+        assert_eq!(
+            tetgen.generate_mesh_with(&options).err(),
+            Some("the minimum dihedral angle of the tetrahedra is smaller than the requested one")
+        );
+        let volumes = vec![0.0; tetgen.ntet()];
+        assert_eq!(
+            tetgen.refine_mesh(&volumes, &options).err(),
+            Some("the minimum dihedral angle of the tetrahedra is smaller than the requested one")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,590 @@
+use crate::{SteinerOptions, StrError, Tetgen, TetgenOptions, Triangle, TriangleOptions};
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Writes the calls to Triangle or Tetgen to a script file, one call per line
+///
+/// Each line is written immediately; thus, the script is available even if the process is
+/// aborted by the c-code.
+pub(crate) struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    /// Creates the script file and writes the call to `new`
+    pub(crate) fn new<P>(full_path: &P, first_line: String) -> Result<Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let path = Path::new(full_path);
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+        let file = File::create(path).map_err(|_| "cannot create file")?;
+        let recorder = Recorder { file };
+        recorder.write(first_line)?;
+        Ok(recorder)
+    }
+
+    /// Writes a line
+    pub(crate) fn write(&self, line: String) -> Result<(), StrError> {
+        let mut file = &self.file;
+        file.write_all(line.as_bytes()).map_err(|_| "cannot write file")?;
+        file.write_all(b"\n").map_err(|_| "cannot write file")?;
+        Ok(())
+    }
+}
+
+/// Formats an optional value as written in the script
+pub(crate) fn option_to_string<T: ToString>(value: &Option<T>) -> String {
+    match value {
+        Some(v) => v.to_string(),
+        None => "None".to_string(),
+    }
+}
+
+/// Holds the generator rebuilt by `replay`
+pub enum Replay {
+    /// Holds the replayed Triangle
+    Triangle(Box<Triangle>),
+
+    /// Holds the replayed Tetgen
+    Tetgen(Box<Tetgen>),
+}
+
+/// Replays a script written by `Triangle::start_recording` or `Tetgen::start_recording`
+///
+/// All recorded calls are executed again, including the calls to the generate functions.
+/// Thus, a crash observed while recording should happen again while replaying.
+///
+/// # Output
+///
+/// Returns the generator after the last call of the script.
+pub fn replay<P>(full_path: &P) -> Result<Replay, StrError>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let path = Path::new(full_path);
+    let script = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+    replay_script(&script)
+}
+
+/// Replays the contents of a script (see `replay`)
+pub fn replay_script(script: &str) -> Result<Replay, StrError> {
+    let mut lines = script.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+    let first: Vec<&str> = match lines.next() {
+        Some(line) => line.split_whitespace().collect(),
+        None => return Err("the script is empty"),
+    };
+    match first[0] {
+        "new_triangle" => {
+            let [npoint, nsegment, nregion, nhole] = args::<4>(&first)?;
+            let mut triangle = Triangle::new(
+                parse(npoint)?,
+                parse_option(nsegment)?,
+                parse_option(nregion)?,
+                parse_option(nhole)?,
+            )?;
+            for line in lines {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                match words[0] {
+                    "reset" => {
+                        let [npoint, nsegment, nregion, nhole] = args::<4>(&words)?;
+                        triangle.reset(
+                            parse(npoint)?,
+                            parse_option(nsegment)?,
+                            parse_option(nregion)?,
+                            parse_option(nhole)?,
+                        )?;
+                    }
+                    "clear_outputs" => {
+                        triangle.clear_outputs()?;
+                    }
+                    "set_point" => {
+                        let [index, x, y] = args::<3>(&words)?;
+                        triangle.set_point(parse(index)?, parse(x)?, parse(y)?)?;
+                    }
+                    "set_point_tag" => {
+                        let [index, tag] = args::<2>(&words)?;
+                        triangle.set_point_tag(parse(index)?, parse(tag)?)?;
+                    }
+                    "set_segment" => {
+                        let [index, a, b] = args::<3>(&words)?;
+                        triangle.set_segment(parse(index)?, parse(a)?, parse(b)?)?;
+                    }
+                    "set_segment_marker" => {
+                        let [index, marker] = args::<2>(&words)?;
+                        triangle.set_segment_marker(parse(index)?, parse(marker)?)?;
+                    }
+                    "set_region" => {
+                        let [index, x, y, attribute, max_area] = args::<5>(&words)?;
+                        triangle.set_region(
+                            parse(index)?,
+                            parse(x)?,
+                            parse(y)?,
+                            parse(attribute)?,
+                            parse_option(max_area)?,
+                        )?;
+                    }
+                    "set_region_f64" => {
+                        let [index, x, y, attribute, max_area] = args::<5>(&words)?;
+                        triangle.set_region_f64(
+                            parse(index)?,
+                            parse(x)?,
+                            parse(y)?,
+                            parse(attribute)?,
+                            parse_option(max_area)?,
+                        )?;
+                    }
+                    "set_hole" => {
+                        let [index, x, y] = args::<3>(&words)?;
+                        triangle.set_hole(parse(index)?, parse(x)?, parse(y)?)?;
+                    }
+                    "generate_delaunay" => {
+                        let [verbose] = args::<1>(&words)?;
+                        triangle.generate_delaunay(parse(verbose)?)?;
+                    }
+                    "generate_voronoi" => {
+                        let [verbose] = args::<1>(&words)?;
+                        triangle.generate_voronoi(parse(verbose)?)?;
+                    }
+                    "set_jitter_on_degeneracy" => {
+                        let [eps] = args::<1>(&words)?;
+                        triangle.set_jitter_on_degeneracy(parse_option(eps)?);
+                    }
+                    "set_steiner_options" => {
+                        let [max_steiner_points, on_boundary, in_interior] = args::<3>(&words)?;
+                        triangle.set_steiner_options(SteinerOptions {
+                            max_steiner_points: parse_option(max_steiner_points)?,
+                            prohibit_steiner_on_boundary: parse(on_boundary)?,
+                            prohibit_steiner_in_interior: parse(in_interior)?,
+                        });
+                    }
+                    "generate_mesh" => {
+                        let [verbose, quadratic, max_area, min_angle] = args::<4>(&words)?;
+                        triangle.generate_mesh(
+                            parse(verbose)?,
+                            parse(quadratic)?,
+                            parse_option(max_area)?,
+                            parse_option(min_angle)?,
+                        )?;
+                    }
+                    "generate_mesh_with" => {
+                        let [verbose, quadratic, max_area, min_angle, max, on_boundary, in_interior, conforming, enforce] =
+                            args::<9>(&words)?;
+                        triangle.generate_mesh_with(&TriangleOptions {
+                            verbose: parse(verbose)?,
+                            quadratic: parse(quadratic)?,
+                            max_area: parse_option(max_area)?,
+                            min_angle: parse_option(min_angle)?,
+                            steiner: SteinerOptions {
+                                max_steiner_points: parse_option(max)?,
+                                prohibit_steiner_on_boundary: parse(on_boundary)?,
+                                prohibit_steiner_in_interior: parse(in_interior)?,
+                            },
+                            conforming_delaunay: parse(conforming)?,
+                            enforce_min_angle: parse(enforce)?,
+                        })?;
+                    }
+                    "refine_mesh" => {
+                        let [areas, verbose, quadratic, max_area, min_angle, max, on_boundary, in_interior, conforming, enforce] =
+                            args::<10>(&words)?;
+                        let areas = areas.split(',').map(parse).collect::<Result<Vec<f64>, _>>()?;
+                        triangle.refine_mesh(
+                            &areas,
+                            &TriangleOptions {
+                                verbose: parse(verbose)?,
+                                quadratic: parse(quadratic)?,
+                                max_area: parse_option(max_area)?,
+                                min_angle: parse_option(min_angle)?,
+                                steiner: SteinerOptions {
+                                    max_steiner_points: parse_option(max)?,
+                                    prohibit_steiner_on_boundary: parse(on_boundary)?,
+                                    prohibit_steiner_in_interior: parse(in_interior)?,
+                                },
+                                conforming_delaunay: parse(conforming)?,
+                                enforce_min_angle: parse(enforce)?,
+                            },
+                        )?;
+                    }
+                    _ => return Err("the script has an unknown call"),
+                }
+            }
+            Ok(Replay::Triangle(Box::new(triangle)))
+        }
+        "new_tetgen" => {
+            let [npoint, facet_npoint, nregion, nhole] = args::<4>(&first)?;
+            let facet_npoint = if facet_npoint == "None" {
+                None
+            } else {
+                Some(facet_npoint.split(',').map(parse).collect::<Result<Vec<usize>, _>>()?)
+            };
+            let mut tetgen = Tetgen::new(
+                parse(npoint)?,
+                facet_npoint,
+                parse_option(nregion)?,
+                parse_option(nhole)?,
+            )?;
+            for line in lines {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                match words[0] {
+                    "set_point" => {
+                        let [index, x, y, z] = args::<4>(&words)?;
+                        tetgen.set_point(parse(index)?, parse(x)?, parse(y)?, parse(z)?)?;
+                    }
+                    "set_point_tag" => {
+                        let [index, tag] = args::<2>(&words)?;
+                        tetgen.set_point_tag(parse(index)?, parse(tag)?)?;
+                    }
+                    "set_facet_point" => {
+                        let [index, m, p] = args::<3>(&words)?;
+                        tetgen.set_facet_point(parse(index)?, parse(m)?, parse(p)?)?;
+                    }
+                    "set_facet_polygon" => {
+                        let [index, polygon, points] = args::<3>(&words)?;
+                        let points = points.split(',').map(parse).collect::<Result<Vec<usize>, _>>()?;
+                        tetgen.set_facet_polygon(parse(index)?, parse(polygon)?, &points)?;
+                    }
+                    "set_facet_hole" => {
+                        let [index, hole, x, y, z] = args::<5>(&words)?;
+                        tetgen.set_facet_hole(parse(index)?, parse(hole)?, parse(x)?, parse(y)?, parse(z)?)?;
+                    }
+                    "set_facet_marker" => {
+                        let [index, marker] = args::<2>(&words)?;
+                        tetgen.set_facet_marker(parse(index)?, parse(marker)?)?;
+                    }
+                    "insert_point" => {
+                        let [x, y, z] = args::<3>(&words)?;
+                        tetgen.insert_points(&[(parse(x)?, parse(y)?, parse(z)?)])?;
+                    }
+                    "clear_inserted_points" => {
+                        tetgen.clear_inserted_points()?;
+                    }
+                    "clear_outputs" => {
+                        tetgen.clear_outputs()?;
+                    }
+                    "set_region" => {
+                        let [index, x, y, z, attribute, max_volume] = args::<6>(&words)?;
+                        tetgen.set_region(
+                            parse(index)?,
+                            parse(x)?,
+                            parse(y)?,
+                            parse(z)?,
+                            parse(attribute)?,
+                            parse_option(max_volume)?,
+                        )?;
+                    }
+                    "set_region_f64" => {
+                        let [index, x, y, z, attribute, max_volume] = args::<6>(&words)?;
+                        tetgen.set_region_f64(
+                            parse(index)?,
+                            parse(x)?,
+                            parse(y)?,
+                            parse(z)?,
+                            parse(attribute)?,
+                            parse_option(max_volume)?,
+                        )?;
+                    }
+                    "set_hole" => {
+                        let [index, x, y, z] = args::<4>(&words)?;
+                        tetgen.set_hole(parse(index)?, parse(x)?, parse(y)?, parse(z)?)?;
+                    }
+                    "generate_delaunay" => {
+                        let [verbose] = args::<1>(&words)?;
+                        tetgen.generate_delaunay(parse(verbose)?)?;
+                    }
+                    "generate_cdt" => {
+                        let [verbose] = args::<1>(&words)?;
+                        tetgen.generate_cdt(parse(verbose)?)?;
+                    }
+                    "generate_voronoi" => {
+                        let [verbose] = args::<1>(&words)?;
+                        tetgen.generate_voronoi(parse(verbose)?)?;
+                    }
+                    "set_jitter_on_degeneracy" => {
+                        let [eps] = args::<1>(&words)?;
+                        tetgen.set_jitter_on_degeneracy(parse_option(eps)?);
+                    }
+                    "set_steiner_options" => {
+                        let [max_steiner_points, on_boundary, in_interior] = args::<3>(&words)?;
+                        tetgen.set_steiner_options(SteinerOptions {
+                            max_steiner_points: parse_option(max_steiner_points)?,
+                            prohibit_steiner_on_boundary: parse(on_boundary)?,
+                            prohibit_steiner_in_interior: parse(in_interior)?,
+                        });
+                    }
+                    "generate_mesh" => {
+                        let [verbose, o2, max_volume, min_angle] = args::<4>(&words)?;
+                        tetgen.generate_mesh(
+                            parse(verbose)?,
+                            parse(o2)?,
+                            parse_option(max_volume)?,
+                            parse_option(min_angle)?,
+                        )?;
+                    }
+                    "generate_mesh_with" => {
+                        let [verbose, quadratic, max_volume, ratio, dihedral, max, on_boundary, in_interior, enforce] =
+                            args::<9>(&words)?;
+                        tetgen.generate_mesh_with(&TetgenOptions {
+                            verbose: parse(verbose)?,
+                            quadratic: parse(quadratic)?,
+                            max_volume: parse_option(max_volume)?,
+                            max_radius_edge_ratio: parse_option(ratio)?,
+                            min_dihedral_angle: parse_option(dihedral)?,
+                            steiner: SteinerOptions {
+                                max_steiner_points: parse_option(max)?,
+                                prohibit_steiner_on_boundary: parse(on_boundary)?,
+                                prohibit_steiner_in_interior: parse(in_interior)?,
+                            },
+                            enforce_min_dihedral_angle: parse(enforce)?,
+                        })?;
+                    }
+                    "refine_mesh" => {
+                        let [volumes, verbose, quadratic, max_volume, ratio, dihedral, max, on_boundary, in_interior, enforce] =
+                            args::<10>(&words)?;
+                        let volumes = volumes.split(',').map(parse).collect::<Result<Vec<f64>, _>>()?;
+                        tetgen.refine_mesh(
+                            &volumes,
+                            &TetgenOptions {
+                                verbose: parse(verbose)?,
+                                quadratic: parse(quadratic)?,
+                                max_volume: parse_option(max_volume)?,
+                                max_radius_edge_ratio: parse_option(ratio)?,
+                                min_dihedral_angle: parse_option(dihedral)?,
+                                steiner: SteinerOptions {
+                                    max_steiner_points: parse_option(max)?,
+                                    prohibit_steiner_on_boundary: parse(on_boundary)?,
+                                    prohibit_steiner_in_interior: parse(in_interior)?,
+                                },
+                                enforce_min_dihedral_angle: parse(enforce)?,
+                            },
+                        )?;
+                    }
+                    _ => return Err("the script has an unknown call"),
+                }
+            }
+            Ok(Replay::Tetgen(Box::new(tetgen)))
+        }
+        _ => Err("the script must start with new_triangle or new_tetgen"),
+    }
+}
+
+/// Returns the arguments of a call (the words after the name of the function)
+fn args<'a, const N: usize>(words: &[&'a str]) -> Result<[&'a str; N], StrError> {
+    if words.len() != N + 1 {
+        return Err("the script has a call with the wrong number of arguments");
+    }
+    let mut result = [""; N];
+    result.copy_from_slice(&words[1..]);
+    Ok(result)
+}
+
+/// Parses an argument
+fn parse<T: std::str::FromStr>(word: &str) -> Result<T, StrError> {
+    word.parse().map_err(|_| "the script has an invalid argument")
+}
+
+/// Parses an optional argument
+fn parse_option<T: std::str::FromStr>(word: &str) -> Result<Option<T>, StrError> {
+    if word == "None" {
+        Ok(None)
+    } else {
+        Ok(Some(parse(word)?))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{replay, replay_script, Replay};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn replay_script_captures_errors() {
+        assert_eq!(replay_script("").err(), Some("the script is empty"));
+        assert_eq!(
+            replay_script("new_mesh 3").err(),
+            Some("the script must start with new_triangle or new_tetgen")
+        );
+        assert_eq!(
+            replay_script("new_triangle 3 None").err(),
+            Some("the script has a call with the wrong number of arguments")
+        );
+        assert_eq!(
+            replay_script("new_triangle 3 None None None\nset_point 0 a 0.0").err(),
+            Some("the script has an invalid argument")
+        );
+        assert_eq!(
+            replay_script("new_tetgen 4 None None None\ndraw").err(),
+            Some("the script has an unknown call")
+        );
+        assert_eq!(
+            replay_script("new_triangle 2 None None None").err(),
+            Some("npoint must be ≥ 3")
+        );
+        assert_eq!(replay("/tmp/tritet/__not_found__.txt").err(), Some("cannot read file"));
+    }
+
+    #[test]
+    fn triangle_recording_works() -> Result<(), StrError> {
+        let path = "/tmp/tritet/test_triangle_recording.txt";
+        let mut triangle = Triangle::new(4, Some(4), Some(1), None)?;
+        triangle
+            .start_recording(path)?
+            .set_points(&[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]])?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?
+            .set_region(0, 0.5, 0.5, 7, Some(0.1))?;
+        triangle.generate_mesh(false, false, None, Some(25.0))?;
+        let script = std::fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        assert_eq!(
+            script,
+            "new_triangle 4 4 1 None\n\
+             set_point 0 0 0\n\
+             set_point 1 1 0\n\
+             set_point 2 1 1\n\
+             set_point 3 0 1\n\
+             set_segment 0 0 1\n\
+             set_segment 1 1 2\n\
+             set_segment 2 2 3\n\
+             set_segment 3 3 0\n\
+             set_region 0 0.5 0.5 7 0.1\n\
+             generate_mesh false false None 25\n"
+        );
+        match replay(path)? {
+            Replay::Triangle(replayed) => {
+                assert_eq!(replayed.npoint(), triangle.npoint());
+                assert_eq!(replayed.ntriangle(), triangle.ntriangle());
+                assert_eq!(replayed.triangle_attribute(0), 7);
+            }
+            Replay::Tetgen(_) => panic!("Triangle expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_recording_works() -> Result<(), StrError> {
+        let path = "/tmp/tritet/test_tetgen_recording.txt";
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .start_recording(path)?
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_facet_marker(0, -10)?;
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        let script = std::fs::read_to_string(path).map_err(|_| "cannot read file")?;
+        assert!(script.starts_with("new_tetgen 4 3,3,3,3 None None\nset_point 0 0 0 0\n"));
+        assert!(script.ends_with("set_facet_marker 0 -10\ngenerate_mesh false false 0.01 None\n"));
+        match replay(path)? {
+            Replay::Tetgen(replayed) => {
+                assert_eq!(replayed.npoint(), tetgen.npoint());
+                assert_eq!(replayed.ntet(), tetgen.ntet());
+                assert_eq!(replayed.nmarked_face(), tetgen.nmarked_face());
+            }
+            Replay::Triangle(_) => panic!("Tetgen expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_script_handles_reset() -> Result<(), StrError> {
+        let script = "new_triangle 3 None None None\n\
+                      set_point 0 0 0\n\
+                      set_point 1 1 0\n\
+                      set_point 2 0 1\n\
+                      generate_delaunay false\n\
+                      clear_outputs\n\
+                      reset 4 None None None\n\
+                      set_point 0 0 0\n\
+                      set_point 1 1 0\n\
+                      set_point 2 1 1\n\
+                      set_point 3 0 1\n\
+                      generate_delaunay false\n";
+        match replay_script(script)? {
+            Replay::Triangle(triangle) => {
+                assert_eq!(triangle.npoint(), 4);
+                assert_eq!(triangle.ntriangle(), 2);
+            }
+            Replay::Tetgen(_) => panic!("Triangle expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_script_handles_insert_points() -> Result<(), StrError> {
+        let script = "new_tetgen 4 3,3,3,3 None None\n\
+                      set_point 0 0 0 0\n\
+                      set_point 1 1 0 0\n\
+                      set_point 2 0 1 0\n\
+                      set_point 3 0 0 1\n\
+                      set_facet_point 0 0 0\nset_facet_point 0 1 2\nset_facet_point 0 2 1\n\
+                      set_facet_point 1 0 0\nset_facet_point 1 1 1\nset_facet_point 1 2 3\n\
+                      set_facet_point 2 0 0\nset_facet_point 2 1 3\nset_facet_point 2 2 2\n\
+                      set_facet_point 3 0 1\nset_facet_point 3 1 2\nset_facet_point 3 2 3\n\
+                      insert_point 0.5 0.5 0.5\n\
+                      clear_inserted_points\n\
+                      insert_point 0.2 0.2 0.2\n\
+                      generate_mesh false false None None\n";
+        match replay_script(script)? {
+            Replay::Tetgen(tetgen) => {
+                assert_eq!(tetgen.inserted_points(), &[[0.2, 0.2, 0.2]]);
+                assert_eq!(tetgen.npoint(), 5);
+            }
+            Replay::Triangle(_) => panic!("Tetgen expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_script_handles_generate_mesh_with() -> Result<(), StrError> {
+        let script = "new_triangle 3 3 None None\n\
+                      set_point 0 0 0\n\
+                      set_point 1 1 0\n\
+                      set_point 2 0 1\n\
+                      set_segment 0 0 1\n\
+                      set_segment 1 1 2\n\
+                      set_segment 2 2 0\n\
+                      generate_mesh_with false true 0.1 None 2 false false false false\n";
+        match replay_script(script)? {
+            Replay::Triangle(replayed) => {
+                assert_eq!(replayed.nnode(), 6);
+                assert!(replayed.ntriangle() > 1);
+            }
+            Replay::Tetgen(_) => panic!("Triangle expected"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_script_handles_set_region_f64() -> Result<(), StrError> {
+        let script = "new_triangle 3 3 1 None\n\
+                      set_point 0 0 0\n\
+                      set_point 1 1 0\n\
+                      set_point 2 0 1\n\
+                      set_segment 0 0 1\n\
+                      set_segment 1 1 2\n\
+                      set_segment 2 2 0\n\
+                      set_region_f64 0 0.2 0.2 -0.5 None\n\
+                      generate_mesh false false None None\n";
+        match replay_script(script)? {
+            Replay::Triangle(triangle) => {
+                assert_eq!(triangle.ntriangle(), 1);
+                assert_eq!(triangle.triangle_attribute_f64(0), -0.5);
+            }
+            Replay::Tetgen(_) => panic!("Triangle expected"),
+        }
+        Ok(())
+    }
+}
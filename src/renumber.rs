@@ -0,0 +1,229 @@
+use crate::connectivity::nodal_graph;
+use crate::{CsrGraph, Mesh, TetMesh, Tetgen, TriMesh, Triangle};
+
+impl CsrGraph {
+    /// Returns the bandwidth of the graph; i.e., the largest difference between adjacent vertices
+    pub fn bandwidth(&self) -> usize {
+        (0..self.nvertex())
+            .flat_map(|a| self.neighbors(a).iter().map(move |b| a.abs_diff(*b)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Computes the reverse Cuthill–McKee ordering of the vertices
+    ///
+    /// Each connected component is traversed in breadth-first order starting at a
+    /// pseudo-peripheral vertex (found with the George–Liu algorithm); the neighbors of each
+    /// vertex are visited in increasing order of degree. The final ordering is reversed, which
+    /// reduces the fill-in of the factorization of the sparse matrices with this graph.
+    ///
+    /// # Output
+    ///
+    /// Returns the old ID of each vertex in the new order (new → old).
+    pub fn reverse_cuthill_mckee(&self) -> Vec<usize> {
+        let nvertex = self.nvertex();
+        let degree = |v: usize| self.xadj[v + 1] - self.xadj[v];
+        let mut visited = vec![false; nvertex];
+        let mut order = Vec::with_capacity(nvertex);
+        let mut candidates: Vec<usize> = (0..nvertex).collect();
+        candidates.sort_by_key(|v| (degree(*v), *v));
+        for seed in candidates {
+            if visited[seed] {
+                continue;
+            }
+            let start = self.pseudo_peripheral_vertex(seed);
+            let first = order.len();
+            visited[start] = true;
+            order.push(start);
+            let mut k = first;
+            while k < order.len() {
+                let mut next: Vec<usize> = self
+                    .neighbors(order[k])
+                    .iter()
+                    .copied()
+                    .filter(|v| !visited[*v])
+                    .collect();
+                next.sort_by_key(|v| (degree(*v), *v));
+                for v in next {
+                    visited[v] = true;
+                    order.push(v);
+                }
+                k += 1;
+            }
+        }
+        order.reverse();
+        order
+    }
+
+    /// Finds a pseudo-peripheral vertex in the component of the seed (George–Liu algorithm)
+    fn pseudo_peripheral_vertex(&self, seed: usize) -> usize {
+        let degree = |v: usize| self.xadj[v + 1] - self.xadj[v];
+        let mut vertex = seed;
+        let mut levels = self.level_structure(vertex);
+        loop {
+            let last = levels.last().unwrap();
+            let candidate = *last.iter().min_by_key(|v| (degree(**v), **v)).unwrap();
+            let candidate_levels = self.level_structure(candidate);
+            if candidate_levels.len() <= levels.len() {
+                return vertex;
+            }
+            vertex = candidate;
+            levels = candidate_levels;
+        }
+    }
+
+    /// Returns the vertices of the component of the root grouped by their distance to the root
+    fn level_structure(&self, root: usize) -> Vec<Vec<usize>> {
+        let mut distance = vec![usize::MAX; self.nvertex()];
+        distance[root] = 0;
+        let mut levels = vec![vec![root]];
+        loop {
+            let mut next = Vec::new();
+            for v in levels.last().unwrap() {
+                for w in self.neighbors(*v) {
+                    if distance[*w] == usize::MAX {
+                        distance[*w] = levels.len();
+                        next.push(*w);
+                    }
+                }
+            }
+            if next.is_empty() {
+                return levels;
+            }
+            levels.push(next);
+        }
+    }
+}
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Returns the bandwidth of the nodal graph; i.e., the largest difference between the IDs of two points of a cell
+    pub fn bandwidth(&self) -> usize {
+        nodal_graph(self.points.len(), &self.cells).bandwidth()
+    }
+
+    /// Renumbers the points with the reverse Cuthill–McKee algorithm to reduce the bandwidth
+    ///
+    /// The ordering is computed with the nodal graph (all nodes of the cells are considered;
+    /// see `CsrGraph::reverse_cuthill_mckee`). The coordinates, cells, and facets are updated;
+    /// the order of the cells and the local order of their nodes are not changed.
+    ///
+    /// # Output
+    ///
+    /// Returns the original ID of each point (new → old), as in `Renumbering::points`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(8, 1, (0.0, 0.0), (8.0, 1.0), [0; 4], false)?;
+    ///     let mut mesh = triangle.to_mesh();
+    ///     assert_eq!(mesh.bandwidth(), 10);
+    ///     let permutation = mesh.renumber_rcm();
+    ///     assert_eq!(permutation.len(), 18);
+    ///     assert_eq!(mesh.bandwidth(), 2);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn renumber_rcm(&mut self) -> Vec<usize> {
+        let permutation = nodal_graph(self.points.len(), &self.cells).reverse_cuthill_mckee();
+        let mut new_ids = vec![0; self.points.len()];
+        for (new, old) in permutation.iter().enumerate() {
+            new_ids[*old] = new;
+        }
+        self.points = permutation.iter().map(|old| self.points[*old]).collect();
+        for nodes in self.cells.iter_mut().chain(self.facets.iter_mut()) {
+            for p in nodes.iter_mut() {
+                *p = new_ids[*p];
+            }
+        }
+        permutation
+    }
+}
+
+impl Triangle {
+    /// Returns the mesh with the points renumbered by reverse Cuthill–McKee (see `Mesh::renumber_rcm`)
+    ///
+    /// The output points of Triangle are not changed; the permutation maps the new point IDs
+    /// to the output point IDs (new → old).
+    pub fn renumber_rcm(&self) -> (TriMesh, Vec<usize>) {
+        let mut mesh = self.to_mesh();
+        let permutation = mesh.renumber_rcm();
+        (mesh, permutation)
+    }
+}
+
+impl Tetgen {
+    /// Returns the mesh with the points renumbered by reverse Cuthill–McKee (see `Mesh::renumber_rcm`)
+    ///
+    /// The output points of Tetgen are not changed; the permutation maps the new point IDs
+    /// to the output point IDs (new → old).
+    pub fn renumber_rcm(&self) -> (TetMesh, Vec<usize>) {
+        let mut mesh = self.to_mesh();
+        let permutation = mesh.renumber_rcm();
+        (mesh, permutation)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{CsrGraph, StrError, Tetgen, Triangle};
+
+    #[test]
+    fn reverse_cuthill_mckee_works() {
+        // path 0-3-1-4-2 plus the isolated vertex 5
+        let graph = CsrGraph::from_lists(vec![vec![3], vec![3, 4], vec![4], vec![0, 1], vec![1, 2], vec![]]);
+        assert_eq!(graph.bandwidth(), 3);
+        let order = graph.reverse_cuthill_mckee();
+        assert_eq!(order, &[2, 4, 1, 3, 0, 5]);
+        let mut new_ids = [0; 6];
+        for (new, old) in order.iter().enumerate() {
+            new_ids[*old] = new;
+        }
+        let lists = (0..6)
+            .map(|new| graph.neighbors(order[new]).iter().map(|old| new_ids[*old]).collect())
+            .collect();
+        assert_eq!(CsrGraph::from_lists(lists).bandwidth(), 1);
+    }
+
+    #[test]
+    fn renumber_rcm_works_2d() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(10, 2, (0.0, 0.0), (10.0, 2.0), [-1, -2, -3, -4], true)?;
+        let original = triangle.to_mesh();
+        let (mesh, permutation) = triangle.renumber_rcm();
+        assert!(mesh.bandwidth() < original.bandwidth());
+        let mut sorted = permutation.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..triangle.npoint()).collect::<Vec<_>>());
+        for (new, old) in permutation.iter().enumerate() {
+            assert_eq!(mesh.points[new], original.points[*old]);
+        }
+        for (cell, nodes) in mesh.cells.iter().enumerate() {
+            let old: Vec<usize> = nodes.iter().map(|p| permutation[*p]).collect();
+            assert_eq!(old, original.cells[cell]);
+            assert_eq!(mesh.measure(cell), original.measure(cell));
+        }
+        for (facet, nodes) in mesh.facets.iter().enumerate() {
+            let old: Vec<usize> = nodes.iter().map(|p| permutation[*p]).collect();
+            assert_eq!(old, original.facets[facet]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn renumber_rcm_works_3d() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(1, 1, 8, (0.0, 0.0, 0.0), (1.0, 1.0, 8.0), [0; 6], false)?;
+        let original = tetgen.to_mesh();
+        let (mesh, permutation) = tetgen.renumber_rcm();
+        assert_eq!(permutation.len(), 36);
+        assert!(mesh.bandwidth() <= original.bandwidth());
+        for (cell, nodes) in mesh.cells.iter().enumerate() {
+            let old: Vec<usize> = nodes.iter().map(|p| permutation[*p]).collect();
+            assert_eq!(old, original.cells[cell]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{StrError, Tetgen, Triangle};
+
+/// Holds a problem found when validating the hole and region seeds against the generated mesh
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeedIssue {
+    /// The seed of a hole lies inside a cell (the hole has not been carved out)
+    HoleCovered {
+        /// The index of the hole
+        hole: usize,
+        /// The index of the cell containing the seed
+        cell: usize,
+    },
+
+    /// The seed of a region does not lie inside any cell (e.g., it is within a hole or outside the domain)
+    RegionOutside {
+        /// The index of the region
+        region: usize,
+    },
+
+    /// The seed of a region lies inside a cell with another attribute (e.g., overwritten by another seed)
+    RegionWrongAttribute {
+        /// The index of the region
+        region: usize,
+        /// The index of the cell containing the seed
+        cell: usize,
+        /// The attribute found in the cell
+        attribute: i32,
+    },
+}
+
+impl Triangle {
+    /// Validates the hole and region seeds against the generated mesh
+    ///
+    /// Checks that each hole seed lies outside all triangles and that each region seed lies
+    /// inside a triangle with the attribute given to `set_region`. Misplaced seeds are a common
+    /// mistake that goes unnoticed because Triangle does not report them.
+    ///
+    /// **Note:** Regional attributes are only assigned by `generate_mesh`.
+    ///
+    /// # Output
+    ///
+    /// Returns the list of issues; an empty list means that all seeds are consistent with the mesh.
+    pub fn validate_seeds(&self) -> Result<Vec<SeedIssue>, StrError> {
+        let ntriangle = self.ntriangle();
+        if ntriangle < 1 {
+            return Err("cannot validate seeds because there are no triangles");
+        }
+        let find_cell = |x: f64, y: f64| {
+            (0..ntriangle).find(|cell| {
+                let a = self.triangle_node(*cell, 0);
+                let b = self.triangle_node(*cell, 1);
+                let c = self.triangle_node(*cell, 2);
+                in_triangle(
+                    (x, y),
+                    (self.point(a, 0), self.point(a, 1)),
+                    (self.point(b, 0), self.point(b, 1)),
+                    (self.point(c, 0), self.point(c, 1)),
+                )
+            })
+        };
+        let mut issues = Vec::new();
+        for hole in 0..self.input_nhole() {
+            let (x, y) = self.input_hole(hole);
+            if let Some(cell) = find_cell(x, y) {
+                issues.push(SeedIssue::HoleCovered { hole, cell });
+            }
+        }
+        for region in 0..self.input_nregion() {
+            let (x, y, expected) = self.input_region(region);
+            match find_cell(x, y) {
+                Some(cell) => {
+                    let attribute = self.triangle_attribute(cell);
+                    if attribute != expected {
+                        issues.push(SeedIssue::RegionWrongAttribute {
+                            region,
+                            cell,
+                            attribute,
+                        });
+                    }
+                }
+                None => issues.push(SeedIssue::RegionOutside { region }),
+            }
+        }
+        Ok(issues)
+    }
+}
+
+impl Tetgen {
+    /// Validates the hole and region seeds against the generated mesh
+    ///
+    /// Checks that each hole seed lies outside all tetrahedra and that each region seed lies
+    /// inside a tetrahedron with the attribute given to `set_region`. Misplaced seeds are a common
+    /// mistake that goes unnoticed because Tetgen does not report them.
+    ///
+    /// **Note:** Regional attributes are only assigned by `generate_mesh`.
+    ///
+    /// # Output
+    ///
+    /// Returns the list of issues; an empty list means that all seeds are consistent with the mesh.
+    pub fn validate_seeds(&self) -> Result<Vec<SeedIssue>, StrError> {
+        let ntet = self.ntet();
+        if ntet < 1 {
+            return Err("cannot validate seeds because there are no tetrahedra");
+        }
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let find_cell = |x: f64, y: f64, z: f64| {
+            (0..ntet).find(|cell| {
+                let a = coords(self.tet_node(*cell, 0));
+                let b = coords(self.tet_node(*cell, 1));
+                let c = coords(self.tet_node(*cell, 2));
+                let d = coords(self.tet_node(*cell, 3));
+                in_tetrahedron([x, y, z], a, b, c, d)
+            })
+        };
+        let mut issues = Vec::new();
+        for hole in 0..self.input_nhole() {
+            let (x, y, z) = self.input_hole(hole);
+            if let Some(cell) = find_cell(x, y, z) {
+                issues.push(SeedIssue::HoleCovered { hole, cell });
+            }
+        }
+        for region in 0..self.input_nregion() {
+            let (x, y, z, expected) = self.input_region(region);
+            match find_cell(x, y, z) {
+                Some(cell) => {
+                    let attribute = self.tet_attribute(cell);
+                    if attribute != expected {
+                        issues.push(SeedIssue::RegionWrongAttribute {
+                            region,
+                            cell,
+                            attribute,
+                        });
+                    }
+                }
+                None => issues.push(SeedIssue::RegionOutside { region }),
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// Indicates whether point p is inside or on the boundary of the triangle (a, b, c)
+fn in_triangle(p: (f64, f64), a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> bool {
+    let orient = |u: (f64, f64), v: (f64, f64), w: (f64, f64)| (v.0 - u.0) * (w.1 - u.1) - (v.1 - u.1) * (w.0 - u.0);
+    let d1 = orient(a, b, p);
+    let d2 = orient(b, c, p);
+    let d3 = orient(c, a, p);
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+/// Indicates whether point p is inside or on the boundary of the tetrahedron (a, b, c, d)
+fn in_tetrahedron(p: [f64; 3], a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> bool {
+    let orient = |u: [f64; 3], v: [f64; 3], w: [f64; 3], q: [f64; 3]| {
+        let (ux, uy, uz) = (v[0] - u[0], v[1] - u[1], v[2] - u[2]);
+        let (vx, vy, vz) = (w[0] - u[0], w[1] - u[1], w[2] - u[2]);
+        let (wx, wy, wz) = (q[0] - u[0], q[1] - u[1], q[2] - u[2]);
+        ux * (vy * wz - vz * wy) - uy * (vx * wz - vz * wx) + uz * (vx * wy - vy * wx)
+    };
+    let vol = orient(a, b, c, d);
+    let signs = [
+        orient(p, b, c, d),
+        orient(a, p, c, d),
+        orient(a, b, p, d),
+        orient(a, b, c, p),
+    ];
+    signs.iter().all(|s| *s * vol >= 0.0)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::SeedIssue;
+    use crate::{StrError, Tetgen, Triangle};
+
+    fn square_with_hole(regions: &[(f64, f64, i32)], hole: (f64, f64)) -> Result<Triangle, StrError> {
+        let mut triangle = Triangle::new(8, Some(8), Some(regions.len()), Some(1))?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_point(4, 0.2, 0.2)?
+            .set_point(5, 0.8, 0.2)?
+            .set_point(6, 0.8, 0.8)?
+            .set_point(7, 0.2, 0.8)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?
+            .set_segment(4, 4, 5)?
+            .set_segment(5, 5, 6)?
+            .set_segment(6, 6, 7)?
+            .set_segment(7, 7, 4)?;
+        for (i, (x, y, attribute)) in regions.iter().enumerate() {
+            triangle.set_region(i, *x, *y, *attribute, None)?;
+        }
+        triangle.set_hole(0, hole.0, hole.1)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        Ok(triangle)
+    }
+
+    #[test]
+    fn validate_seeds_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.validate_seeds().err(),
+            Some("cannot validate seeds because there are no triangles")
+        );
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.validate_seeds().err(),
+            Some("cannot validate seeds because there are no tetrahedra")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_validate_seeds_works() -> Result<(), StrError> {
+        let triangle = square_with_hole(&[(0.1, 0.1, 1)], (0.5, 0.5))?;
+        assert_eq!(triangle.validate_seeds()?, &[]);
+
+        // region seed inside the hole
+        let triangle = square_with_hole(&[(0.1, 0.1, 1), (0.5, 0.5, 2)], (0.5, 0.5))?;
+        assert_eq!(triangle.validate_seeds()?, &[SeedIssue::RegionOutside { region: 1 }]);
+
+        // two region seeds in the same area: the last one wins
+        let triangle = square_with_hole(&[(0.1, 0.1, 1), (0.9, 0.9, 2)], (0.5, 0.5))?;
+        let issues = triangle.validate_seeds()?;
+        assert_eq!(issues.len(), 1);
+        match issues[0] {
+            SeedIssue::RegionWrongAttribute { region, attribute, .. } => {
+                assert_eq!(region, 0);
+                assert_eq!(attribute, 2);
+            }
+            _ => panic!("wrong issue"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_validate_seeds_detects_covered_hole() -> Result<(), StrError> {
+        // the Delaunay triangulation ignores holes
+        let mut triangle = Triangle::new(3, None, None, Some(1))?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_hole(0, 0.2, 0.2)?;
+        triangle.generate_delaunay(false)?;
+        let issues = triangle.validate_seeds()?;
+        assert_eq!(issues, &[SeedIssue::HoleCovered { hole: 0, cell: 0 }]);
+        assert_eq!(format!("{:?}", issues[0].clone()), "HoleCovered { hole: 0, cell: 0 }");
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_validate_seeds_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(2), None)?;
+        tetgen
+            .set_point(0, 0.0, 1.0, 0.0)?
+            .set_point(1, 0.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 1.0)?;
+        tetgen
+            .set_facet_point(0, 0, 0)?
+            .set_facet_point(0, 1, 2)?
+            .set_facet_point(0, 2, 1)?;
+        tetgen
+            .set_facet_point(1, 0, 0)?
+            .set_facet_point(1, 1, 1)?
+            .set_facet_point(1, 2, 3)?;
+        tetgen
+            .set_facet_point(2, 0, 0)?
+            .set_facet_point(2, 1, 3)?
+            .set_facet_point(2, 2, 2)?;
+        tetgen
+            .set_facet_point(3, 0, 1)?
+            .set_facet_point(3, 1, 2)?
+            .set_facet_point(3, 2, 3)?;
+        tetgen
+            .set_region(0, 0.1, 0.9, 0.1, 1, None)?
+            .set_region(1, 5.0, 5.0, 5.0, 2, None)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.validate_seeds()?, &[SeedIssue::RegionOutside { region: 1 }]);
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+use crate::predicates::orient2d;
+use crate::StrError;
+
+/// Holds the result of the simplification of a polyline
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimplifiedPolyline {
+    /// Holds the indices of the kept points, in the original order
+    pub kept: Vec<usize>,
+
+    /// Holds the indices of the removed points, in the original order
+    pub removed: Vec<usize>,
+}
+
+/// Simplifies polylines using the Douglas-Peucker algorithm while preserving the topology
+///
+/// This function is intended to reduce the number of points on dense (e.g., digitized) boundaries
+/// before setting the points and segments of the Planar Straight Line Graph (PSLG).
+///
+/// After the Douglas-Peucker pass, the simplified segments are checked against each other
+/// (including segments of other polylines) using exact orientation tests; if two simplified
+/// segments cross, touch, or overlap, the farthest removed point of the spans they replace is
+/// restored. The same happens if a kept point lies between a simplified segment and the span it
+/// replaces (i.e., the point would end up on the other side). This is repeated until no such
+/// conflict exists. Thus, the simplification does not introduce intersections.
+///
+/// # Input
+///
+/// * `polylines` -- the (x, y) coordinates of the points of each polyline
+/// * `closed` -- indicates that the polylines are closed (the last point connects to the first one)
+/// * `tolerance` -- the maximum distance from a removed point to the simplified polyline
+///
+/// # Output
+///
+/// Returns the kept and removed point indices of each polyline. The endpoints of open polylines
+/// are always kept; closed polylines keep at least three points.
+pub fn simplify_polylines(
+    polylines: &[Vec<(f64, f64)>],
+    closed: bool,
+    tolerance: f64,
+) -> Result<Vec<SimplifiedPolyline>, StrError> {
+    simplify_paths(polylines, &vec![closed; polylines.len()], tolerance)
+}
+
+/// Simplifies open and closed polylines (see `simplify_polylines`)
+pub(crate) fn simplify_paths(
+    polylines: &[Vec<(f64, f64)>],
+    closed: &[bool],
+    tolerance: f64,
+) -> Result<Vec<SimplifiedPolyline>, StrError> {
+    if tolerance < 0.0 {
+        return Err("the tolerance must be non-negative");
+    }
+    if polylines
+        .iter()
+        .zip(closed)
+        .any(|(p, c)| p.len() < if *c { 3 } else { 2 })
+    {
+        return Err("polylines must have at least 2 points (or 3 if closed)");
+    }
+
+    // Douglas-Peucker pass
+    let mut keep: Vec<Vec<bool>> = Vec::with_capacity(polylines.len());
+    for (points, closed) in polylines.iter().zip(closed) {
+        let n = points.len();
+        let mut flags = vec![false; n];
+        flags[0] = true;
+        if *closed {
+            // split the ring at the point farthest from the first point
+            let far = (1..n)
+                .max_by(|a, b| {
+                    dist2(points[0], points[*a])
+                        .partial_cmp(&dist2(points[0], points[*b]))
+                        .unwrap()
+                })
+                .unwrap();
+            flags[far] = true;
+            douglas_peucker(points, 0, far, tolerance, &mut flags);
+            douglas_peucker(points, far, n, tolerance, &mut flags);
+            if flags.iter().filter(|k| **k).count() < 3 {
+                let third = (1..n)
+                    .filter(|i| *i != far)
+                    .max_by(|a, b| {
+                        let da = dist_to_segment(points[*a], points[0], points[far]);
+                        let db = dist_to_segment(points[*b], points[0], points[far]);
+                        da.partial_cmp(&db).unwrap()
+                    })
+                    .unwrap();
+                flags[third] = true;
+            }
+        } else {
+            flags[n - 1] = true;
+            douglas_peucker(points, 0, n - 1, tolerance, &mut flags);
+        }
+        keep.push(flags);
+    }
+
+    // restore points until the simplified segments neither touch nor cross each other
+    // and no kept point ends up on the other side of a simplified segment
+    loop {
+        let segments = simplified_segments(polylines, &keep, closed);
+        let mut conflict = vec![false; segments.len()];
+        for i in 0..segments.len() {
+            let (pi, ai, bi) = segments[i];
+            let (a, b) = (polylines[pi][ai], polylines[pi][bi % polylines[pi].len()]);
+            for j in (i + 1)..segments.len() {
+                let (pj, aj, bj) = segments[j];
+                let (c, d) = (polylines[pj][aj], polylines[pj][bj % polylines[pj].len()]);
+                if segments_intersect(a, b, c, d) {
+                    conflict[i] = true;
+                    conflict[j] = true;
+                }
+            }
+            if bi > ai + 1 && !conflict[i] {
+                let swept = &polylines[pi][ai..bi];
+                let closing = polylines[pi][bi % polylines[pi].len()];
+                conflict[i] = segments.iter().any(|(p, first, last)| {
+                    [*first, *last % polylines[*p].len()].iter().any(|k| {
+                        let x = polylines[*p][*k];
+                        x != a && x != b && in_swept_area(swept, closing, x)
+                    })
+                });
+            }
+        }
+        let mut restored = false;
+        for (i, (p, first, last)) in segments.iter().enumerate() {
+            if conflict[i] {
+                restored |= restore_farthest(&polylines[*p], *p, *first, *last, &mut keep);
+            }
+        }
+        if !restored {
+            break;
+        }
+    }
+
+    Ok(keep
+        .iter()
+        .map(|flags| SimplifiedPolyline {
+            kept: (0..flags.len()).filter(|i| flags[*i]).collect(),
+            removed: (0..flags.len()).filter(|i| !flags[*i]).collect(),
+        })
+        .collect())
+}
+
+/// Runs the Douglas-Peucker algorithm between `first` and `last` (which may equal `points.len()`, meaning 0)
+fn douglas_peucker(points: &[(f64, f64)], first: usize, last: usize, tolerance: f64, keep: &mut [bool]) {
+    let n = points.len();
+    let mut stack = vec![(first, last)];
+    while let Some((i, j)) = stack.pop() {
+        if j <= i + 1 {
+            continue;
+        }
+        let (a, b) = (points[i], points[j % n]);
+        let mut max_dist = -1.0;
+        let mut index = i;
+        for (k, point) in points.iter().enumerate().take(j).skip(i + 1) {
+            let dist = dist_to_segment(*point, a, b);
+            if dist > max_dist {
+                max_dist = dist;
+                index = k;
+            }
+        }
+        if max_dist > tolerance {
+            keep[index] = true;
+            stack.push((i, index));
+            stack.push((index, j));
+        }
+    }
+}
+
+/// Returns the simplified segments as (polyline, start, end) where end may equal the number of points (closed)
+fn simplified_segments(
+    polylines: &[Vec<(f64, f64)>],
+    keep: &[Vec<bool>],
+    closed: &[bool],
+) -> Vec<(usize, usize, usize)> {
+    let mut segments = Vec::new();
+    for (p, points) in polylines.iter().enumerate() {
+        let kept: Vec<usize> = (0..points.len()).filter(|i| keep[p][*i]).collect();
+        for w in kept.windows(2) {
+            segments.push((p, w[0], w[1]));
+        }
+        if closed[p] {
+            segments.push((p, *kept.last().unwrap(), points.len()));
+        }
+    }
+    segments
+}
+
+/// Restores the removed point farthest from the simplified segment; returns false if there is none
+fn restore_farthest(points: &[(f64, f64)], p: usize, first: usize, last: usize, keep: &mut [Vec<bool>]) -> bool {
+    if last <= first + 1 {
+        return false;
+    }
+    let (a, b) = (points[first], points[last % points.len()]);
+    let index = ((first + 1)..last)
+        .max_by(|i, j| {
+            let di = dist_to_segment(points[*i], a, b);
+            let dj = dist_to_segment(points[*j], a, b);
+            di.partial_cmp(&dj).unwrap()
+        })
+        .unwrap();
+    keep[p][index] = true;
+    true
+}
+
+/// Returns the squared distance between two points
+fn dist2(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (b.0 - a.0) + (b.1 - a.1) * (b.1 - a.1)
+}
+
+/// Returns the distance from point p to the segment (a, b)
+fn dist_to_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (ex, ey) = (b.0 - a.0, b.1 - a.1);
+    let len2 = ex * ex + ey * ey;
+    if len2 == 0.0 {
+        return f64::sqrt(dist2(p, a));
+    }
+    let t = (((p.0 - a.0) * ex + (p.1 - a.1) * ey) / len2).clamp(0.0, 1.0);
+    f64::sqrt(dist2(p, (a.0 + t * ex, a.1 + t * ey)))
+}
+
+/// Indicates whether two segments intersect each other (sharing an endpoint only is allowed)
+///
+/// Touching (e.g., T-junctions) and collinear overlaps count as intersections.
+fn segments_intersect(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> bool {
+    if a == c || a == d || b == c || b == d {
+        if (a == c && b == d) || (a == d && b == c) {
+            return true;
+        }
+        // only a collinear overlap beyond the shared endpoint p intersects
+        let (p, q) = if a == c || a == d { (a, b) } else { (b, a) };
+        let r = if p == c { d } else { c };
+        return orient2d(p, q, r) == 0.0 && same_direction(p, q, r);
+    }
+    let o1 = orient2d(a, b, c);
+    let o2 = orient2d(a, b, d);
+    let o3 = orient2d(c, d, a);
+    let o4 = orient2d(c, d, b);
+    if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+        return true;
+    }
+    (o1 == 0.0 && on_segment(a, b, c))
+        || (o2 == 0.0 && on_segment(a, b, d))
+        || (o3 == 0.0 && on_segment(c, d, a))
+        || (o4 == 0.0 && on_segment(c, d, b))
+}
+
+/// Indicates whether q and r, collinear with p, are on the same side of p
+fn same_direction(p: (f64, f64), q: (f64, f64), r: (f64, f64)) -> bool {
+    (q.0 - p.0) * (r.0 - p.0) + (q.1 - p.1) * (r.1 - p.1) > 0.0
+}
+
+/// Indicates whether the point p, collinear with (a, b), lies on the segment (a, b)
+fn on_segment(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> bool {
+    f64::min(a.0, b.0) <= p.0 && p.0 <= f64::max(a.0, b.0) && f64::min(a.1, b.1) <= p.1 && p.1 <= f64::max(a.1, b.1)
+}
+
+/// Indicates whether a point is inside or on the boundary of the area between a span of points and
+/// the simplified segment from the first point of the span to `closing`
+fn in_swept_area(span: &[(f64, f64)], closing: (f64, f64), x: (f64, f64)) -> bool {
+    let n = span.len() + 1;
+    let vertex = |i: usize| if i < span.len() { span[i] } else { closing };
+    let mut winding = 0;
+    for i in 0..n {
+        let (p, q) = (vertex(i), vertex((i + 1) % n));
+        let o = orient2d(p, q, x);
+        if o == 0.0 && on_segment(p, q, x) {
+            return true;
+        }
+        if p.1 <= x.1 && x.1 < q.1 && o > 0.0 {
+            winding += 1;
+        } else if q.1 <= x.1 && x.1 < p.1 && o < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding != 0
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{segments_intersect, simplify_paths, simplify_polylines, SimplifiedPolyline};
+
+    #[test]
+    fn simplify_polylines_captures_errors() {
+        assert_eq!(
+            simplify_polylines(&[vec![(0.0, 0.0), (1.0, 0.0)]], false, -1.0).err(),
+            Some("the tolerance must be non-negative")
+        );
+        assert_eq!(
+            simplify_polylines(&[vec![(0.0, 0.0), (1.0, 0.0)]], true, 0.1).err(),
+            Some("polylines must have at least 2 points (or 3 if closed)")
+        );
+    }
+
+    #[test]
+    fn simplify_open_polyline_works() {
+        let line = vec![(0.0, 0.0), (1.0, 0.01), (2.0, -0.01), (3.0, 0.0), (4.0, 1.0)];
+        let res = simplify_polylines(&[line], false, 0.1).unwrap();
+        assert_eq!(
+            res,
+            &[SimplifiedPolyline {
+                kept: vec![0, 3, 4],
+                removed: vec![1, 2],
+            }]
+        );
+        let clone = res[0].clone();
+        assert_eq!(
+            format!("{:?}", clone),
+            "SimplifiedPolyline { kept: [0, 3, 4], removed: [1, 2] }"
+        );
+    }
+
+    #[test]
+    fn simplify_closed_polyline_works() {
+        // square with points along the sides
+        let ring = vec![
+            (0.0, 0.0),
+            (0.5, 0.001),
+            (1.0, 0.0),
+            (0.999, 0.5),
+            (1.0, 1.0),
+            (0.5, 1.0),
+            (0.0, 1.0),
+            (0.001, 0.5),
+        ];
+        let polylines = vec![ring];
+        let res = simplify_polylines(&polylines, true, 0.01).unwrap();
+        assert_eq!(res[0].kept, &[0, 2, 4, 6]);
+        assert_eq!(res[0].removed, &[1, 3, 5, 7]);
+
+        // large tolerance keeps at least three points
+        let res = simplify_polylines(&polylines, true, 10.0).unwrap();
+        assert_eq!(res[0].kept.len(), 3);
+    }
+
+    #[test]
+    fn simplify_polylines_preserves_topology() {
+        // polyline A dips below polyline B
+        let a = vec![(0.0, 0.0), (2.0, -1.5), (4.0, 0.0)];
+        let b = vec![(1.8, -0.2), (2.2, -0.2), (2.2, 0.2), (1.8, 0.2)];
+
+        // without the topology check, A would become the segment from (0,0) to (4,0) and cross B
+        let alone = vec![a.clone()];
+        let res = simplify_polylines(&alone, false, 2.0).unwrap();
+        assert_eq!(res[0].kept, &[0, 2]);
+
+        // with B, the point 1 of A is restored
+        let res = simplify_polylines(&[a, b], false, 2.0).unwrap();
+        assert_eq!(res[0].kept, &[0, 1, 2]);
+        assert_eq!(res[1].kept, &[0, 2, 3]);
+        assert_eq!(res[1].removed, &[1]);
+    }
+
+    #[test]
+    fn segments_intersect_works() {
+        let (a, b) = ((0.0, 0.0), (2.0, 0.0));
+        // proper crossing and disjoint segments
+        assert!(segments_intersect(a, b, (1.0, -1.0), (1.0, 1.0)));
+        assert!(!segments_intersect(a, b, (1.0, 0.5), (1.0, 1.0)));
+        assert!(!segments_intersect(a, b, (3.0, 0.0), (4.0, 0.0)));
+        // T-junction
+        assert!(segments_intersect(a, b, (1.0, 0.0), (1.0, 1.0)));
+        assert!(segments_intersect((1.0, 0.0), (1.0, 1.0), a, b));
+        // collinear overlap
+        assert!(segments_intersect(a, b, (1.0, 0.0), (3.0, 0.0)));
+        // shared endpoint
+        assert!(!segments_intersect(a, b, b, (3.0, 1.0)));
+        assert!(!segments_intersect(a, b, b, (3.0, 0.0)));
+        assert!(segments_intersect(a, b, a, (1.0, 0.0)));
+        assert!(segments_intersect(a, b, b, a));
+        // nearly collinear points (the naive orientation test would be inexact)
+        let x = 0.5 + f64::EPSILON / 2.0;
+        assert!(!segments_intersect((0.5, 0.5), (12.0, 12.0), (x, 0.5), (1.0, 0.0)));
+        assert!(segments_intersect((0.5, 0.5), (12.0, 12.0), (3.0, 3.0), (4.0, 0.0)));
+    }
+
+    #[test]
+    fn simplify_polylines_handles_touching_and_enclosed_points() {
+        let a = vec![(0.0, 0.0), (2.0, -1.5), (4.0, 0.0)];
+
+        // the end of B touches the simplified A (T-junction)
+        let b = vec![(2.0, 0.0), (2.0, 1.0)];
+        let res = simplify_polylines(&[a.clone(), b], false, 2.0).unwrap();
+        assert_eq!(res[0].kept, &[0, 1, 2]);
+
+        // B would be moved to the other side of A without crossing it
+        let b = vec![(1.9, -0.5), (2.1, -0.5), (2.0, -0.3)];
+        let res = simplify_paths(&[a, b], &[false, true], 2.0).unwrap();
+        assert_eq!(res[0].kept, &[0, 1, 2]);
+        assert_eq!(res[1].kept, &[0, 1, 2]);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{StrError, Tetgen, TetgenOptions, Triangle, TriangleOptions};
+
+/// Holds the maximum number of refinement steps of the sizing loop
+const SIZING_MAX_ITERATIONS: usize = 50;
+
+impl Triangle {
+    /// Generates a conforming constrained Delaunay triangulation graded by a sizing function
+    ///
+    /// The mesh is generated with `generate_mesh_with` and then refined (see `refine_mesh`)
+    /// until the area of each triangle is not greater than the area of the equilateral triangle
+    /// with edge equal to `h(x, y)` evaluated at the centroid of the triangle. Only the
+    /// triangles violating the sizing are refined at each step.
+    ///
+    /// # Input
+    ///
+    /// * `options` -- The options of the mesh generation and refinement
+    /// * `h` -- The sizing function returning the desired edge length at (x, y); must be positive
+    ///
+    /// # Output
+    ///
+    /// Returns the number of refinement steps.
+    ///
+    /// **Note:** The coordinates given to `h` are mapped by the output transform, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle, TriangleOptions};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, Some(4), None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?;
+    ///
+    ///     // small triangles near the origin
+    ///     let h = |x: f64, y: f64| 0.02 + 0.2 * f64::sqrt(x * x + y * y);
+    ///     triangle.generate_mesh_with_sizing(&TriangleOptions::new(), h)?;
+    ///     assert!(triangle.ntriangle() > 100);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_mesh_with_sizing<F>(&self, options: &TriangleOptions, h: F) -> Result<usize, StrError>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        self.generate_mesh_with(options)?;
+        let factor = f64::sqrt(3.0) / 4.0;
+        for step in 0..SIZING_MAX_ITERATIONS {
+            let mut done = true;
+            let mut areas = vec![0.0; self.ntriangle()];
+            for (t, area) in areas.iter_mut().enumerate() {
+                let mut centroid = [0.0, 0.0];
+                for m in 0..3 {
+                    let p = self.triangle_node(t, m);
+                    centroid[0] += self.point(p, 0) / 3.0;
+                    centroid[1] += self.point(p, 1) / 3.0;
+                }
+                let size = h(centroid[0], centroid[1]);
+                if size <= 0.0 {
+                    return Err("the sizing function must return positive values");
+                }
+                let target = factor * size * size;
+                if self.triangle_quality(t).map_or(0.0, |q| q.area) > target {
+                    *area = target;
+                    done = false;
+                }
+            }
+            if done {
+                return Ok(step);
+            }
+            self.refine_mesh(&areas, options)?;
+        }
+        Err("the sizing loop did not converge")
+    }
+}
+
+impl Tetgen {
+    /// Generates a conforming constrained Delaunay tetrahedralization graded by a sizing function
+    ///
+    /// The mesh is generated with `generate_mesh_with` and then refined (see `refine_mesh`)
+    /// until the volume of each tetrahedron is not greater than the volume of the regular
+    /// tetrahedron with edge equal to `h(x, y, z)` evaluated at the centroid of the tetrahedron.
+    /// Only the tetrahedra violating the sizing are refined at each step.
+    ///
+    /// # Input
+    ///
+    /// * `options` -- The options of the mesh generation and refinement
+    /// * `h` -- The sizing function returning the desired edge length at (x, y, z); must be positive
+    ///
+    /// # Output
+    ///
+    /// Returns the number of refinement steps.
+    ///
+    /// **Note:** The coordinates given to `h` are mapped by the output transform, if any.
+    pub fn generate_mesh_with_sizing<F>(&self, options: &TetgenOptions, h: F) -> Result<usize, StrError>
+    where
+        F: Fn(f64, f64, f64) -> f64,
+    {
+        self.generate_mesh_with(options)?;
+        let factor = 1.0 / (6.0 * f64::sqrt(2.0));
+        for step in 0..SIZING_MAX_ITERATIONS {
+            let mut done = true;
+            let mut volumes = vec![0.0; self.ntet()];
+            for (t, volume) in volumes.iter_mut().enumerate() {
+                let mut centroid = [0.0, 0.0, 0.0];
+                for m in 0..4 {
+                    let p = self.tet_node(t, m);
+                    for (dim, c) in centroid.iter_mut().enumerate() {
+                        *c += self.point(p, dim) / 4.0;
+                    }
+                }
+                let size = h(centroid[0], centroid[1], centroid[2]);
+                if size <= 0.0 {
+                    return Err("the sizing function must return positive values");
+                }
+                let target = factor * size * size * size;
+                if self.tet_quality(t).map_or(0.0, |q| q.volume) > target {
+                    *volume = target;
+                    done = false;
+                }
+            }
+            if done {
+                return Ok(step);
+            }
+            self.refine_mesh(&volumes, options)?;
+        }
+        Err("the sizing loop did not converge")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{cube, square};
+    use crate::{StrError, TetgenOptions, TriangleOptions};
+
+    #[test]
+    fn triangle_generate_mesh_with_sizing_captures_errors() -> Result<(), StrError> {
+        let triangle = square()?;
+        assert_eq!(
+            triangle
+                .generate_mesh_with_sizing(&TriangleOptions::new(), |_, _| 0.0)
+                .err(),
+            Some("the sizing function must return positive values")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_generate_mesh_with_sizing_works() -> Result<(), StrError> {
+        // uniform sizing
+        let triangle = square()?;
+        let steps = triangle.generate_mesh_with_sizing(&TriangleOptions::new(), |_, _| 0.1)?;
+        assert!(steps > 0);
+        let max_area = f64::sqrt(3.0) / 4.0 * 0.01;
+        for t in 0..triangle.ntriangle() {
+            assert!(triangle.triangle_quality(t).unwrap().area <= max_area);
+        }
+
+        // graded sizing: the triangles are smaller near the left side
+        let h = |x: f64, _: f64| 0.02 + 0.3 * x;
+        triangle.generate_mesh_with_sizing(&TriangleOptions::new(), h)?;
+        let (mut left, mut right) = (0, 0);
+        for t in 0..triangle.ntriangle() {
+            let x = (0..3)
+                .map(|m| triangle.point(triangle.triangle_node(t, m), 0))
+                .sum::<f64>()
+                / 3.0;
+            if x < 0.5 {
+                left += 1;
+            } else {
+                right += 1;
+            }
+        }
+        assert!(left > 4 * right);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_generate_mesh_with_sizing_works() -> Result<(), StrError> {
+        let tetgen = cube()?;
+        assert_eq!(
+            tetgen
+                .generate_mesh_with_sizing(&TetgenOptions::new(), |_, _, _| -1.0)
+                .err(),
+            Some("the sizing function must return positive values")
+        );
+
+        // graded sizing: the tetrahedra are smaller near the bottom side
+        let h = |_: f64, _: f64, z: f64| 0.1 + 0.5 * z;
+        let steps = tetgen.generate_mesh_with_sizing(&TetgenOptions::new(), h)?;
+        assert!(steps > 0);
+        let (mut bottom, mut top) = (0, 0);
+        for t in 0..tetgen.ntet() {
+            let z = (0..4).map(|m| tetgen.point(tetgen.tet_node(t, m), 2)).sum::<f64>() / 4.0;
+            let target = f64::powi(h(0.0, 0.0, z), 3) / (6.0 * f64::sqrt(2.0));
+            assert!(tetgen.tet_quality(t).unwrap().volume <= target);
+            if z < 0.5 {
+                bottom += 1;
+            } else {
+                top += 1;
+            }
+        }
+        assert!(bottom > 2 * top);
+        Ok(())
+    }
+}
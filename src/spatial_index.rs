@@ -0,0 +1,451 @@
+use crate::box_grid::BoxGrid;
+use crate::vector::{cross, dot, norm, sub};
+use crate::{StrError, Tetgen, Triangle};
+
+/// Implements a spatial index over the cells of a generated mesh
+///
+/// The index holds a copy of the coordinates and connectivity of the mesh (only the corner
+/// nodes are considered). Thus, it remains valid after the generator is dropped or re-run.
+/// The cells are registered in a uniform grid, enabling raycasting (e.g., for picking in
+/// graphical interfaces or line-of-sight calculations) and nearest-cell queries.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{SpatialIndex, StrError, Triangle};
+///
+/// fn main() -> Result<(), StrError> {
+///     let mut triangle = Triangle::new(4, None, None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 1.0, 1.0)?
+///         .set_point(3, 0.0, 1.0)?;
+///     triangle.generate_delaunay(false)?;
+///     let index = SpatialIndex::from_triangle(&triangle)?;
+///
+///     // a ray crossing the square horizontally hits both triangles
+///     let hits = index.raycast(&[-1.0, 0.25], &[1.0, 0.0])?;
+///     assert_eq!(hits.len(), 2);
+///     assert_eq!(hits[0].1, 1.0);
+///
+///     // the point is outside the square, at a distance of 0.5 from it
+///     let (_, distance) = index.nearest_cell(&[1.5, 0.5])?;
+///     assert_eq!(distance, 0.5);
+///     Ok(())
+/// }
+/// ```
+pub struct SpatialIndex {
+    ndim: usize,            // space dimension
+    points: Vec<[f64; 3]>,  // coordinates of the points (z = 0 in 2D)
+    cells: Vec<Vec<usize>>, // corner nodes of each cell
+    grid: BoxGrid,          // grid over the bounding boxes of the cells
+}
+
+impl SpatialIndex {
+    /// Allocates a new instance from the triangles generated by Triangle
+    pub fn from_triangle(triangle: &Triangle) -> Result<Self, StrError> {
+        let points = (0..triangle.npoint())
+            .map(|p| [triangle.point(p, 0), triangle.point(p, 1), 0.0])
+            .collect();
+        let cells = (0..triangle.ntriangle())
+            .map(|cell| (0..3).map(|m| triangle.triangle_node(cell, m)).collect())
+            .collect();
+        SpatialIndex::new(2, points, cells)
+    }
+
+    /// Allocates a new instance from the tetrahedra generated by Tetgen
+    pub fn from_tetgen(tetgen: &Tetgen) -> Result<Self, StrError> {
+        let points = (0..tetgen.npoint())
+            .map(|p| [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)])
+            .collect();
+        let cells = (0..tetgen.ntet())
+            .map(|cell| (0..4).map(|m| tetgen.tet_node(cell, m)).collect())
+            .collect();
+        SpatialIndex::new(3, points, cells)
+    }
+
+    /// Allocates a new instance given the points and the corners of each cell
+    fn new(ndim: usize, points: Vec<[f64; 3]>, cells: Vec<Vec<usize>>) -> Result<Self, StrError> {
+        if cells.is_empty() {
+            return Err("cannot build the spatial index because there are no cells");
+        }
+        let boxes: Vec<_> = cells
+            .iter()
+            .map(|corners| {
+                let mut lo = [f64::MAX; 3];
+                let mut hi = [f64::MIN; 3];
+                for p in corners {
+                    for i in 0..3 {
+                        lo[i] = f64::min(lo[i], points[*p][i]);
+                        hi[i] = f64::max(hi[i], points[*p][i]);
+                    }
+                }
+                (lo, hi)
+            })
+            .collect();
+        Ok(SpatialIndex {
+            ndim,
+            points,
+            cells,
+            grid: BoxGrid::new(&boxes),
+        })
+    }
+
+    /// Returns the cells intersected by a ray
+    ///
+    /// # Input
+    ///
+    /// * `origin` -- the origin of the ray (ndim)
+    /// * `direction` -- the direction of the ray (ndim); it does not need to be normalized
+    ///
+    /// # Output
+    ///
+    /// Returns a list of `(cell, t_in, t_out)` sorted by `t_in`, where `origin + t * direction`
+    /// gives the entry (t_in) and exit (t_out) points of the ray. Cells containing the origin
+    /// have t_in = 0. Cells just touched by the ray (at a corner or edge) are included.
+    pub fn raycast(&self, origin: &[f64], direction: &[f64]) -> Result<Vec<(usize, f64, f64)>, StrError> {
+        let o = self.to_point(origin)?;
+        let d = self.to_point(direction)?;
+        if d.iter().all(|v| *v == 0.0) {
+            return Err("the direction of the ray must not be zero");
+        }
+
+        // clip the ray against the bounds of the grid
+        let (lo, hi) = self.grid.bounds();
+        let (mut t0, mut t1) = (0.0, f64::MAX);
+        for i in 0..3 {
+            if d[i] == 0.0 {
+                if o[i] < lo[i] || o[i] > hi[i] {
+                    return Ok(Vec::new());
+                }
+            } else {
+                let ta = (lo[i] - o[i]) / d[i];
+                let tb = (hi[i] - o[i]) / d[i];
+                t0 = f64::max(t0, f64::min(ta, tb));
+                t1 = f64::min(t1, f64::max(ta, tb));
+            }
+        }
+        if t0 > t1 {
+            return Ok(Vec::new());
+        }
+
+        // march along the clipped ray collecting the candidate cells
+        let size = self.grid.cell_size();
+        let step = (0..self.ndim).map(|i| size[i]).fold(f64::MAX, f64::min);
+        let length = (t1 - t0) * norm(&d);
+        let nstep = f64::ceil(length / step) as usize + 1;
+        let mut candidates = Vec::new();
+        for k in 0..nstep {
+            let ta = t0 + (t1 - t0) * (k as f64) / (nstep as f64);
+            let tb = t0 + (t1 - t0) * ((k + 1) as f64) / (nstep as f64);
+            let mut min = [0.0; 3];
+            let mut max = [0.0; 3];
+            for i in 0..3 {
+                let (a, b) = (o[i] + ta * d[i], o[i] + tb * d[i]);
+                min[i] = f64::min(a, b);
+                max[i] = f64::max(a, b);
+            }
+            candidates.extend(self.grid.candidates(&min, &max));
+        }
+        candidates.sort();
+        candidates.dedup();
+
+        // intersect the ray with each candidate cell
+        let mut hits: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|cell| self.clip_ray(cell, &o, &d).map(|(t_in, t_out)| (cell, t_in, t_out)))
+            .collect();
+        hits.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+        Ok(hits)
+    }
+
+    /// Returns the cell nearest to a point and the distance from the point to this cell
+    ///
+    /// The distance is zero if the point is inside (or on the boundary of) the cell.
+    /// If more than one cell contains the point, the one with the smallest index is returned.
+    pub fn nearest_cell(&self, x: &[f64]) -> Result<(usize, f64), StrError> {
+        let p = self.to_point(x)?;
+        let (lo, hi) = self.grid.bounds();
+        let size = self.grid.cell_size();
+        let diagonal = norm(&[hi[0] - lo[0], hi[1] - lo[1], hi[2] - lo[2]]);
+        let outside = norm(&[
+            f64::max(0.0, f64::max(lo[0] - p[0], p[0] - hi[0])),
+            f64::max(0.0, f64::max(lo[1] - p[1], p[1] - hi[1])),
+            f64::max(0.0, f64::max(lo[2] - p[2], p[2] - hi[2])),
+        ]);
+        let mut radius = outside + (0..self.ndim).map(|i| size[i]).fold(f64::MAX, f64::min);
+        loop {
+            let min = [p[0] - radius, p[1] - radius, p[2] - radius];
+            let max = [p[0] + radius, p[1] + radius, p[2] + radius];
+            let mut best: Option<(usize, f64)> = None;
+            for cell in self.grid.candidates(&min, &max) {
+                let dist = self.distance(cell, &p);
+                if best.is_none_or(|(_, d)| dist < d) {
+                    best = Some((cell, dist));
+                }
+            }
+            // any closer cell would overlap the search box
+            if let Some((cell, dist)) = best {
+                if dist <= radius {
+                    return Ok((cell, dist));
+                }
+            }
+            if radius > outside + diagonal {
+                // all cells have been visited
+                return Ok(best.unwrap());
+            }
+            radius *= 2.0;
+        }
+    }
+
+    /// Converts the input coordinates to a 3D point
+    fn to_point(&self, x: &[f64]) -> Result<[f64; 3], StrError> {
+        if x.len() != self.ndim {
+            return Err("the length of the coordinates must be equal to ndim");
+        }
+        let mut p = [0.0; 3];
+        p[..self.ndim].copy_from_slice(x);
+        Ok(p)
+    }
+
+    /// Returns the facets of a cell as (point on facet, outward normal)
+    fn facets(&self, cell: usize) -> Vec<([f64; 3], [f64; 3])> {
+        let x: Vec<[f64; 3]> = self.cells[cell].iter().map(|p| self.points[*p]).collect();
+        let n = x.len();
+        (0..n)
+            .map(|opposite| {
+                let f: Vec<[f64; 3]> = (0..n).filter(|m| *m != opposite).map(|m| x[m]).collect();
+                let mut normal = if self.ndim == 2 {
+                    [-(f[1][1] - f[0][1]), f[1][0] - f[0][0], 0.0]
+                } else {
+                    cross(&sub(&f[1], &f[0]), &sub(&f[2], &f[0]))
+                };
+                if dot(&normal, &sub(&x[opposite], &f[0])) > 0.0 {
+                    normal = [-normal[0], -normal[1], -normal[2]];
+                }
+                (f[0], normal)
+            })
+            .collect()
+    }
+
+    /// Clips the ray (t ≥ 0) against a cell using the Cyrus-Beck algorithm
+    fn clip_ray(&self, cell: usize, o: &[f64; 3], d: &[f64; 3]) -> Option<(f64, f64)> {
+        let (mut t_in, mut t_out) = (0.0, f64::MAX);
+        for (p, normal) in self.facets(cell) {
+            let num = dot(&normal, &sub(o, &p));
+            let den = dot(&normal, d);
+            if den == 0.0 {
+                if num > 0.0 {
+                    return None;
+                }
+            } else {
+                let t = -num / den;
+                if den < 0.0 {
+                    t_in = f64::max(t_in, t);
+                } else {
+                    t_out = f64::min(t_out, t);
+                }
+                if t_in > t_out {
+                    return None;
+                }
+            }
+        }
+        Some((t_in, t_out))
+    }
+
+    /// Returns the distance from a point to a cell (zero if inside)
+    fn distance(&self, cell: usize, p: &[f64; 3]) -> f64 {
+        if self
+            .facets(cell)
+            .iter()
+            .all(|(q, normal)| dot(normal, &sub(p, q)) <= 0.0)
+        {
+            return 0.0;
+        }
+        let x: Vec<[f64; 3]> = self.cells[cell].iter().map(|q| self.points[*q]).collect();
+        let mut dist = f64::MAX;
+        if self.ndim == 2 {
+            for m in 0..3 {
+                let c = closest_on_segment(p, &x[m], &x[(m + 1) % 3]);
+                dist = f64::min(dist, norm(&sub(p, &c)));
+            }
+        } else {
+            for opposite in 0..4 {
+                let f: Vec<usize> = (0..4).filter(|m| *m != opposite).collect();
+                let c = closest_on_triangle(p, &x[f[0]], &x[f[1]], &x[f[2]]);
+                dist = f64::min(dist, norm(&sub(p, &c)));
+            }
+        }
+        dist
+    }
+}
+
+/// Returns the point on the segment (a, b) closest to p
+fn closest_on_segment(p: &[f64; 3], a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    let ab = sub(b, a);
+    let len2 = dot(&ab, &ab);
+    if len2 == 0.0 {
+        return *a;
+    }
+    let t = (dot(&sub(p, a), &ab) / len2).clamp(0.0, 1.0);
+    [a[0] + t * ab[0], a[1] + t * ab[1], a[2] + t * ab[2]]
+}
+
+/// Returns the point on the triangle (a, b, c) closest to p
+///
+/// Reference: Ericson C (2005) Real-Time Collision Detection, Section 5.1.5
+fn closest_on_triangle(p: &[f64; 3], a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> [f64; 3] {
+    let ab = sub(b, a);
+    let ac = sub(c, a);
+    let normal = cross(&ab, &ac);
+    let area2 = dot(&normal, &normal);
+    if area2 == 0.0 {
+        // degenerate triangle
+        let candidates = [
+            closest_on_segment(p, a, b),
+            closest_on_segment(p, b, c),
+            closest_on_segment(p, c, a),
+        ];
+        return *candidates
+            .iter()
+            .min_by(|x, y| norm(&sub(p, x)).partial_cmp(&norm(&sub(p, y))).unwrap())
+            .unwrap();
+    }
+    // barycentric coordinates of the projection of p onto the plane of the triangle
+    let ap = sub(p, a);
+    let v = dot(&cross(&ap, &ac), &normal) / area2;
+    let w = dot(&cross(&ab, &ap), &normal) / area2;
+    if v >= 0.0 && w >= 0.0 && v + w <= 1.0 {
+        return [
+            a[0] + v * ab[0] + w * ac[0],
+            a[1] + v * ab[1] + w * ac[1],
+            a[2] + v * ab[2] + w * ac[2],
+        ];
+    }
+    // the closest point is on the boundary
+    let candidates = [
+        closest_on_segment(p, a, b),
+        closest_on_segment(p, b, c),
+        closest_on_segment(p, c, a),
+    ];
+    *candidates
+        .iter()
+        .min_by(|x, y| norm(&sub(p, x)).partial_cmp(&norm(&sub(p, y))).unwrap())
+        .unwrap()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_on_triangle, SpatialIndex};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn new_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            SpatialIndex::from_triangle(&triangle).err(),
+            Some("cannot build the spatial index because there are no cells")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn closest_on_triangle_works() {
+        let (a, b, c) = ([0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+        assert_eq!(closest_on_triangle(&[0.2, 0.2, 5.0], &a, &b, &c), [0.2, 0.2, 0.0]);
+        assert_eq!(closest_on_triangle(&[-1.0, -1.0, 1.0], &a, &b, &c), [0.0, 0.0, 0.0]);
+        assert_eq!(closest_on_triangle(&[0.5, -1.0, 0.0], &a, &b, &c), [0.5, 0.0, 0.0]);
+        assert_eq!(closest_on_triangle(&[1.0, 1.0, 0.0], &a, &b, &c), [0.5, 0.5, 0.0]);
+        assert_eq!(closest_on_triangle(&[3.0, 0.0, 0.0], &a, &b, &b), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn triangle_spatial_index_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle.generate_mesh(false, false, Some(0.01), None)?;
+        let index = SpatialIndex::from_triangle(&triangle)?;
+
+        // errors
+        assert_eq!(
+            index.raycast(&[0.0, 0.0, 0.0], &[1.0, 0.0]).err(),
+            Some("the length of the coordinates must be equal to ndim")
+        );
+        assert_eq!(
+            index.raycast(&[0.0, 0.0], &[0.0, 0.0]).err(),
+            Some("the direction of the ray must not be zero")
+        );
+
+        // the ray misses the mesh
+        assert_eq!(index.raycast(&[-1.0, 0.5], &[-1.0, 0.0])?.len(), 0);
+        assert_eq!(index.raycast(&[-1.0, 2.0], &[1.0, 0.0])?.len(), 0);
+
+        // the ray crosses the mesh: the segments inside the cells cover the whole path
+        let hits = index.raycast(&[-1.0, 0.43], &[2.0, 0.0])?;
+        assert!(hits.len() > 2);
+        assert_eq!(hits[0].1, 0.5);
+        assert_eq!(hits.last().unwrap().2, 1.0);
+        for w in hits.windows(2) {
+            assert!(w[0].1 <= w[1].1);
+        }
+        let total: f64 = hits.iter().map(|(_, t_in, t_out)| t_out - t_in).sum();
+        assert!(f64::abs(total - 0.5) < 1e-14);
+
+        // the origin is inside the mesh
+        let hits = index.raycast(&[0.43, 0.37], &[0.0, 1.0])?;
+        assert_eq!(hits[0].1, 0.0);
+        assert_eq!(index.nearest_cell(&[0.43, 0.37])?, (hits[0].0, 0.0));
+
+        // nearest cell from outside
+        let (cell, dist) = index.nearest_cell(&[3.0, 0.5])?;
+        assert!(f64::abs(dist - 2.0) < 1e-15);
+        assert!((0..3).any(|m| triangle.point(triangle.triangle_node(cell, m), 0) == 1.0));
+        let (_, dist) = index.nearest_cell(&[-3.0, -4.0])?;
+        assert!(f64::abs(dist - 5.0) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_spatial_index_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(8, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 0.0)?
+            .set_point(4, 0.0, 0.0, 1.0)?
+            .set_point(5, 1.0, 0.0, 1.0)?
+            .set_point(6, 1.0, 1.0, 1.0)?
+            .set_point(7, 0.0, 1.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let index = SpatialIndex::from_tetgen(&tetgen)?;
+
+        // vertical ray through the cube
+        let hits = index.raycast(&[0.3, 0.6, 3.0], &[0.0, 0.0, -1.0])?;
+        assert!(!hits.is_empty());
+        assert_eq!(hits[0].1, 2.0);
+        assert_eq!(hits.last().unwrap().2, 3.0);
+        let total: f64 = hits.iter().map(|(_, t_in, t_out)| t_out - t_in).sum();
+        assert!(f64::abs(total - 1.0) < 1e-14);
+
+        // nearest cell
+        assert_eq!(index.nearest_cell(&[0.3, 0.6, 0.2])?.1, 0.0);
+        let (_, dist) = index.nearest_cell(&[0.5, 0.5, 3.0])?;
+        assert!(f64::abs(dist - 2.0) < 1e-15);
+        let (_, dist) = index.nearest_cell(&[2.0, 2.0, 2.0])?;
+        assert!(f64::abs(dist - f64::sqrt(3.0)) < 1e-15);
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{StrError, Triangle};
+
+/// Holds a point created at the crossing of segments (see `split_intersecting_segments`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntersectionPoint {
+    /// Holds the index of the new point (in `SplitSegments::points`)
+    pub point: usize,
+
+    /// Holds the indices of the original segments crossing at this point (sorted)
+    pub segments: Vec<usize>,
+}
+
+/// Holds the result of the splitting of intersecting segments
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitSegments {
+    /// Holds the (x, y) coordinates of the original points followed by the new points
+    pub points: Vec<(f64, f64)>,
+
+    /// Holds the point indices of the segments after splitting
+    pub segments: Vec<(usize, usize)>,
+
+    /// Holds the index of the original segment of each segment after splitting
+    pub parents: Vec<usize>,
+
+    /// Holds the new points and the original segments crossing at them
+    pub intersections: Vec<IntersectionPoint>,
+}
+
+/// Splits the segments of a Planar Straight Line Graph (PSLG) where they cross each other
+///
+/// This function is intended to repair the segments before calling `Triangle::set_segment`, so
+/// the points created by the repair are known (e.g., to trace the modifications of the geometry).
+///
+/// Two segments cross if they intersect at a single point that is not an endpoint of either
+/// segment; touching at endpoints and collinear overlaps are not modified. The crossing
+/// points closer than 1e-12 times the diagonal of the bounding box are merged; thus, a point
+/// where more than two segments cross is created only once. The cost is O(n²) with n segments.
+///
+/// # Input
+///
+/// * `points` -- the (x, y) coordinates of the points
+/// * `segments` -- the indices of the two points of each segment
+///
+/// # Output
+///
+/// Returns the points with the new points appended, the split segments (in the order of the
+/// original segments and along each one from the first to the second point), the map to the
+/// original segments, and the list of new points.
+pub fn split_intersecting_segments(
+    points: &[(f64, f64)],
+    segments: &[(usize, usize)],
+) -> Result<SplitSegments, StrError> {
+    if segments.iter().any(|(a, b)| *a >= points.len() || *b >= points.len()) {
+        return Err("id of segment point is out of bounds");
+    }
+    let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+    for p in points {
+        min = (f64::min(min.0, p.0), f64::min(min.1, p.1));
+        max = (f64::max(max.0, p.0), f64::max(max.1, p.1));
+    }
+    let tol = 1e-12 * f64::sqrt((max.0 - min.0).powi(2) + (max.1 - min.1).powi(2));
+
+    // find the crossings and the parametric coordinates along each segment
+    let mut result = SplitSegments {
+        points: points.to_vec(),
+        segments: Vec::new(),
+        parents: Vec::new(),
+        intersections: Vec::new(),
+    };
+    let mut splits: Vec<Vec<(f64, usize)>> = vec![Vec::new(); segments.len()];
+    for i in 0..segments.len() {
+        for j in (i + 1)..segments.len() {
+            let (a, b) = (points[segments[i].0], points[segments[i].1]);
+            let (c, d) = (points[segments[j].0], points[segments[j].1]);
+            let (ti, tj) = match crossing(a, b, c, d) {
+                Some(t) => t,
+                None => continue,
+            };
+            let x = (a.0 + ti * (b.0 - a.0), a.1 + ti * (b.1 - a.1));
+            let found = result.intersections.iter_mut().find(|inter| {
+                let y = result.points[inter.point];
+                f64::abs(x.0 - y.0) <= tol && f64::abs(x.1 - y.1) <= tol
+            });
+            let point = match found {
+                Some(inter) => {
+                    inter.segments.extend([i, j]);
+                    inter.point
+                }
+                None => {
+                    result.points.push(x);
+                    let point = result.points.len() - 1;
+                    result.intersections.push(IntersectionPoint {
+                        point,
+                        segments: vec![i, j],
+                    });
+                    point
+                }
+            };
+            splits[i].push((ti, point));
+            splits[j].push((tj, point));
+        }
+    }
+    for inter in result.intersections.iter_mut() {
+        inter.segments.sort();
+        inter.segments.dedup();
+    }
+
+    // split the segments
+    for (s, (a, b)) in segments.iter().enumerate() {
+        splits[s].sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+        let mut first = *a;
+        for (_, p) in &splits[s] {
+            if *p != first {
+                result.segments.push((first, *p));
+                result.parents.push(s);
+                first = *p;
+            }
+        }
+        result.segments.push((first, *b));
+        result.parents.push(s);
+    }
+    Ok(result)
+}
+
+impl Triangle {
+    /// Returns the output points created by Triangle at the crossings of the input segments
+    ///
+    /// Triangle splits the crossing segments automatically (using exact arithmetic) when
+    /// generating a mesh with segments; thus, a PSLG may have crossing segments on purpose (e.g.,
+    /// road networks), although `validate_input` rejects them. This function finds the crossings
+    /// of the input segments as in `split_intersecting_segments` and the output points at them.
+    /// See also `point_origins`.
+    ///
+    /// # Output
+    ///
+    /// Returns the output point ID (in `IntersectionPoint::point`) and the IDs of the input
+    /// segments crossing at each new point, sorted by point ID. Returns an empty list if not
+    /// all points or segments have been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{PointOrigin, StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // a square with crossing diagonals
+    ///     let mut triangle = Triangle::new(4, Some(6), None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 2.0, 0.0)?
+    ///         .set_point(2, 2.0, 2.0)?
+    ///         .set_point(3, 0.0, 2.0)?
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?
+    ///         .set_segment(4, 0, 2)?
+    ///         .set_segment(5, 1, 3)?;
+    ///     triangle.generate_mesh(false, false, None, None)?;
+    ///     let intersections = triangle.segment_intersections();
+    ///     assert_eq!(intersections.len(), 1);
+    ///     let p = intersections[0].point;
+    ///     assert_eq!((triangle.point(p, 0), triangle.point(p, 1)), (1.0, 1.0));
+    ///     assert_eq!(intersections[0].segments, &[4, 5]);
+    ///     assert_eq!(triangle.point_origins()[p], PointOrigin::SegmentIntersection);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn segment_intersections(&self) -> Vec<IntersectionPoint> {
+        let (npoint, all_points_set) = self.input_npoint();
+        let (nsegment, all_segments_set) = self.input_nsegment();
+        if !all_points_set || !all_segments_set || nsegment < 2 {
+            return Vec::new();
+        }
+        let points: Vec<(f64, f64)> = (0..npoint)
+            .map(|p| {
+                let x = self.input_point(p);
+                (x[0], x[1])
+            })
+            .collect();
+        let segments: Vec<(usize, usize)> = (0..nsegment).map(|s| self.input_segment(s)).collect();
+        let split = match split_intersecting_segments(&points, &segments) {
+            Ok(split) => split,
+            Err(_) => return Vec::new(),
+        };
+        if split.intersections.is_empty() {
+            return Vec::new();
+        }
+
+        // match the crossings with the output points generated by Triangle
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for p in &points {
+            min = (f64::min(min.0, p.0), f64::min(min.1, p.1));
+            max = (f64::max(max.0, p.0), f64::max(max.1, p.1));
+        }
+        let tol = 1e-9 * f64::sqrt((max.0 - min.0).powi(2) + (max.1 - min.1).powi(2));
+        let generated: Vec<(usize, [f64; 2])> = (0..self.npoint())
+            .filter(|p| self.point_original_index(*p).is_none())
+            .map(|p| (p, self.raw_point(p)))
+            .collect();
+        let mut result: Vec<IntersectionPoint> = split
+            .intersections
+            .into_iter()
+            .filter_map(|inter| {
+                let x = split.points[inter.point];
+                generated
+                    .iter()
+                    .find(|(_, y)| f64::abs(x.0 - y[0]) <= tol && f64::abs(x.1 - y[1]) <= tol)
+                    .map(|(p, _)| IntersectionPoint {
+                        point: *p,
+                        segments: inter.segments,
+                    })
+            })
+            .collect();
+        result.sort_by_key(|inter| inter.point);
+        result
+    }
+}
+
+/// Returns the parametric coordinates along (a, b) and (c, d) of their crossing (if any)
+fn crossing(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> Option<(f64, f64)> {
+    if a == c || a == d || b == c || b == d {
+        return None;
+    }
+    let orient = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0);
+    let d1 = orient(c, d, a);
+    let d2 = orient(c, d, b);
+    let d3 = orient(a, b, c);
+    let d4 = orient(a, b, d);
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        Some((d1 / (d1 - d2), d3 / (d3 - d4)))
+    } else {
+        None
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{split_intersecting_segments, IntersectionPoint};
+    use crate::{PointOrigin, StrError, Triangle};
+
+    #[test]
+    fn split_intersecting_segments_captures_errors() {
+        assert_eq!(
+            split_intersecting_segments(&[(0.0, 0.0), (1.0, 0.0)], &[(0, 2)]).err(),
+            Some("id of segment point is out of bounds")
+        );
+    }
+
+    #[test]
+    fn split_intersecting_segments_works() -> Result<(), StrError> {
+        // an "X" and a horizontal segment crossing at the same point, plus a segment touching at an endpoint
+        let points = [
+            (0.0, 0.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+            (2.0, 0.0),
+            (0.0, 1.0),
+            (2.0, 1.0),
+            (3.0, 3.0),
+        ];
+        let segments = [(0, 1), (2, 3), (4, 5), (1, 6)];
+        let res = split_intersecting_segments(&points, &segments)?;
+        assert_eq!(res.points.len(), 8);
+        assert_eq!(res.points[7], (1.0, 1.0));
+        assert_eq!(
+            res.intersections,
+            &[IntersectionPoint {
+                point: 7,
+                segments: vec![0, 1, 2],
+            }]
+        );
+        assert_eq!(res.segments, &[(0, 7), (7, 1), (2, 7), (7, 3), (4, 7), (7, 5), (1, 6)]);
+        assert_eq!(res.parents, &[0, 0, 1, 1, 2, 2, 3]);
+
+        // no crossings
+        let res = split_intersecting_segments(&points[..4], &[(0, 3), (3, 1), (1, 2), (2, 0)])?;
+        assert_eq!(res.points.len(), 4);
+        assert_eq!(res.segments, &[(0, 3), (3, 1), (1, 2), (2, 0)]);
+        assert_eq!(res.intersections, &[]);
+        Ok(())
+    }
+
+    #[test]
+    fn split_segments_can_be_meshed() -> Result<(), StrError> {
+        // square with two crossing diagonals
+        let points = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let segments = [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 3)];
+        let res = split_intersecting_segments(&points, &segments)?;
+        assert_eq!(res.intersections.len(), 1);
+        let mut triangle = Triangle::new(res.points.len(), Some(res.segments.len()), None, None)?;
+        for (i, x) in res.points.iter().enumerate() {
+            triangle.set_point(i, x.0, x.1)?;
+        }
+        for (i, (a, b)) in res.segments.iter().enumerate() {
+            triangle.set_segment(i, *a, *b)?;
+        }
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.npoint(), 5);
+        assert_eq!(triangle.ntriangle(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn segment_intersections_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(8, Some(9), None, None)?;
+        assert_eq!(triangle.segment_intersections(), &[]);
+        // a square with a star of three crossing lines at (1, 1) and a line crossing one of them
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 2.0, 2.0)?
+            .set_point(3, 0.0, 2.0)?
+            .set_point(4, 1.0, 0.0)?
+            .set_point(5, 1.0, 2.0)?
+            .set_point(6, 0.0, 0.5)?
+            .set_point(7, 0.5, 0.0)?;
+        triangle
+            .set_segment(0, 0, 4)?
+            .set_segment(1, 4, 1)?
+            .set_segment(2, 1, 2)?
+            .set_segment(3, 2, 3)?
+            .set_segment(4, 3, 0)?
+            .set_segment(5, 0, 2)?
+            .set_segment(6, 1, 3)?
+            .set_segment(7, 4, 5)?
+            .set_segment(8, 6, 7)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let intersections = triangle.segment_intersections();
+        assert_eq!(intersections.len(), 2);
+        let origins = triangle.point_origins();
+        let mut found = Vec::new();
+        for inter in &intersections {
+            let p = inter.point;
+            assert!(p >= 8);
+            assert_eq!(origins[p], PointOrigin::SegmentIntersection);
+            found.push(((triangle.point(p, 0), triangle.point(p, 1)), inter.segments.clone()));
+        }
+        found.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        assert_eq!(found[0], ((0.25, 0.25), vec![5, 8]));
+        assert_eq!(found[1], ((1.0, 1.0), vec![5, 6, 7]));
+        Ok(())
+    }
+}
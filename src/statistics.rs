@@ -0,0 +1,365 @@
+use crate::{Tetgen, Triangle};
+use std::collections::HashMap;
+
+/// Holds the percentiles (0 to 100) reported by `MeshStatistics`
+pub const STATISTICS_PERCENTILES: [f64; 5] = [0.0, 25.0, 50.0, 75.0, 100.0];
+
+/// Holds statistics of a mesh (similar to Tetgen's `-V` report)
+///
+/// Only the corners of the cells are considered. The percentiles correspond to
+/// `STATISTICS_PERCENTILES` and are computed with the nearest-rank method; thus, the first
+/// and last values are the minimum and maximum values.
+///
+/// For triangles, the angles are the interior angles and the ratio is the aspect ratio.
+/// For tetrahedra, the angles are the dihedral angles and the ratio is the radius-edge ratio.
+/// See `TriQuality` and `TetQuality`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshStatistics<const DIM: usize> {
+    /// Holds the number of points (including middle nodes)
+    pub npoint: usize,
+
+    /// Holds the number of cells
+    pub ncell: usize,
+
+    /// Holds the number of edges (corner to corner)
+    pub nedge: usize,
+
+    /// Holds the number of boundary facets (edges in 2D or faces in 3D shared by only one cell)
+    pub nboundary_facet: usize,
+
+    /// Holds the minimum coordinates of the points
+    pub min: [f64; DIM],
+
+    /// Holds the maximum coordinates of the points
+    pub max: [f64; DIM],
+
+    /// Holds the total area (2D) or volume (3D)
+    pub total_size: f64,
+
+    /// Holds the length of the shortest edge
+    pub min_edge_length: f64,
+
+    /// Holds the length of the longest edge
+    pub max_edge_length: f64,
+
+    /// Holds the percentiles of the smallest angle (degrees) of each cell
+    pub min_angle_percentiles: [f64; 5],
+
+    /// Holds the percentiles of the largest angle (degrees) of each cell
+    pub max_angle_percentiles: [f64; 5],
+
+    /// Holds the percentiles of the ratio (aspect or radius-edge) of each cell
+    pub ratio_percentiles: [f64; 5],
+}
+
+/// Holds a connected component of a 2D mesh and its statistics (see `Triangle::component_statistics`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentStatistics {
+    /// Holds the IDs of the triangles of the component (sorted)
+    pub cells: Vec<usize>,
+
+    /// Holds the attributes (regions) of the triangles of the component (sorted, without repetitions)
+    pub attributes: Vec<i32>,
+
+    /// Holds the statistics of the component (the points are only those used by its triangles)
+    pub statistics: MeshStatistics<2>,
+}
+
+impl Triangle {
+    /// Returns statistics of the current mesh
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if there are no triangles.
+    pub fn statistics(&self) -> Option<MeshStatistics<2>> {
+        let points: Vec<[f64; 2]> = (0..self.npoint())
+            .map(|p| [self.point(p, 0), self.point(p, 1)])
+            .collect();
+        let corners: Vec<Vec<usize>> = (0..self.ntriangle())
+            .map(|t| (0..3).map(|m| self.triangle_node(t, m)).collect())
+            .collect();
+        let qualities: Vec<_> = (0..self.ntriangle())
+            .filter_map(|t| self.triangle_quality(t))
+            .map(|q| (q.area, q.min_angle, q.max_angle, q.aspect_ratio))
+            .collect();
+        mesh_statistics(&points, &corners, &qualities)
+    }
+
+    /// Returns the triangles of each connected component of the mesh
+    ///
+    /// This function groups the output of `cell_components`; e.g., a PSLG with several disjoint
+    /// domains yields one component per domain. The components are numbered in the order of
+    /// their smallest triangle ID and the triangle IDs are sorted.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let (ncomponent, component) = self.cell_components();
+        let mut cells = vec![Vec::new(); ncomponent];
+        for (cell, c) in component.iter().enumerate() {
+            cells[*c].push(cell);
+        }
+        cells
+    }
+
+    /// Returns the attributes and statistics of each connected component (see `connected_components`)
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // two disjoint squares
+    ///     let mut triangle = Triangle::new(8, Some(8), Some(2), None)?;
+    ///     for (offset, attribute) in [(0, 1), (4, 2)] {
+    ///         let x = offset as f64;
+    ///         triangle
+    ///             .set_point(offset, x, 0.0)?
+    ///             .set_point(offset + 1, x + 1.0, 0.0)?
+    ///             .set_point(offset + 2, x + 1.0, 1.0)?
+    ///             .set_point(offset + 3, x, 1.0)?
+    ///             .set_region(offset / 4, x + 0.5, 0.5, attribute, None)?;
+    ///         for m in 0..4 {
+    ///             triangle.set_segment(offset + m, offset + m, offset + (m + 1) % 4)?;
+    ///         }
+    ///     }
+    ///     triangle.generate_mesh(false, false, Some(0.1), None)?;
+    ///     let components = triangle.component_statistics();
+    ///     assert_eq!(components.len(), 2);
+    ///     assert_eq!(components[0].attributes, &[1]);
+    ///     assert_eq!(components[1].attributes, &[2]);
+    ///     assert!((components[1].statistics.total_size - 1.0).abs() < 1e-14);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn component_statistics(&self) -> Vec<ComponentStatistics> {
+        let mesh = self.to_mesh();
+        self.connected_components()
+            .into_iter()
+            .map(|cells| {
+                let (part, _) = mesh.extract_cells(cells.clone());
+                let corners: Vec<Vec<usize>> = part.cells.iter().map(|nodes| nodes[..3].to_vec()).collect();
+                let qualities: Vec<_> = cells
+                    .iter()
+                    .filter_map(|t| self.triangle_quality(*t))
+                    .map(|q| (q.area, q.min_angle, q.max_angle, q.aspect_ratio))
+                    .collect();
+                let mut attributes = part.attributes.clone();
+                attributes.sort();
+                attributes.dedup();
+                ComponentStatistics {
+                    cells,
+                    attributes,
+                    statistics: mesh_statistics(&part.points, &corners, &qualities).unwrap(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Tetgen {
+    /// Returns statistics of the current mesh
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any.
+    ///
+    /// Returns None if there are no tetrahedra.
+    pub fn statistics(&self) -> Option<MeshStatistics<3>> {
+        let points: Vec<[f64; 3]> = (0..self.npoint())
+            .map(|p| [self.point(p, 0), self.point(p, 1), self.point(p, 2)])
+            .collect();
+        let corners: Vec<Vec<usize>> = (0..self.ntet())
+            .map(|t| (0..4).map(|m| self.tet_node(t, m)).collect())
+            .collect();
+        let qualities: Vec<_> = (0..self.ntet())
+            .filter_map(|t| self.tet_quality(t))
+            .map(|q| (q.volume, q.min_dihedral, q.max_dihedral, q.radius_edge_ratio))
+            .collect();
+        mesh_statistics(&points, &corners, &qualities)
+    }
+}
+
+/// Computes the statistics given the corners and the (size, min angle, max angle, ratio) of each cell
+fn mesh_statistics<const DIM: usize>(
+    points: &[[f64; DIM]],
+    corners: &[Vec<usize>],
+    qualities: &[(f64, f64, f64, f64)],
+) -> Option<MeshStatistics<DIM>> {
+    if corners.is_empty() || points.is_empty() {
+        return None;
+    }
+
+    // bounding box
+    let mut min = points[0];
+    let mut max = points[0];
+    for x in points {
+        for i in 0..DIM {
+            min[i] = f64::min(min[i], x[i]);
+            max[i] = f64::max(max[i], x[i]);
+        }
+    }
+
+    // edges and boundary facets (the facets have DIM corners)
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let mut facets: HashMap<Vec<usize>, usize> = HashMap::new();
+    for nodes in corners {
+        for i in 0..nodes.len() {
+            for j in (i + 1)..nodes.len() {
+                edges.push((usize::min(nodes[i], nodes[j]), usize::max(nodes[i], nodes[j])));
+            }
+            let mut key: Vec<usize> = (0..nodes.len()).filter(|m| *m != i).map(|m| nodes[m]).collect();
+            key.sort();
+            *facets.entry(key).or_default() += 1;
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    let lengths = edges.iter().map(|(a, b)| {
+        let (xa, xb) = (points[*a], points[*b]);
+        (0..DIM).map(|i| (xb[i] - xa[i]) * (xb[i] - xa[i])).sum::<f64>().sqrt()
+    });
+    let min_edge_length = lengths.clone().fold(f64::MAX, f64::min);
+    let max_edge_length = lengths.fold(0.0, f64::max);
+
+    Some(MeshStatistics {
+        npoint: points.len(),
+        ncell: corners.len(),
+        nedge: edges.len(),
+        nboundary_facet: facets.values().filter(|count| **count == 1).count(),
+        min,
+        max,
+        total_size: qualities.iter().map(|q| q.0).sum(),
+        min_edge_length,
+        max_edge_length,
+        min_angle_percentiles: percentiles(qualities.iter().map(|q| q.1).collect()),
+        max_angle_percentiles: percentiles(qualities.iter().map(|q| q.2).collect()),
+        ratio_percentiles: percentiles(qualities.iter().map(|q| q.3).collect()),
+    })
+}
+
+/// Returns the values at `STATISTICS_PERCENTILES` using the nearest-rank method
+fn percentiles(mut values: Vec<f64>) -> [f64; 5] {
+    if values.is_empty() {
+        return [0.0; 5];
+    }
+    values.sort_by(f64::total_cmp);
+    let n = values.len();
+    STATISTICS_PERCENTILES.map(|p| values[f64::round(p / 100.0 * ((n - 1) as f64)) as usize])
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::percentiles;
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn percentiles_works() {
+        assert_eq!(percentiles(Vec::new()), [0.0; 5]);
+        assert_eq!(percentiles(vec![3.0]), [3.0; 5]);
+        assert_eq!(percentiles(vec![5.0, 1.0, 4.0, 2.0, 3.0]), [1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn triangle_statistics_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        assert_eq!(triangle.statistics(), None);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 2.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let stats = triangle.statistics().unwrap();
+        assert_eq!(stats.npoint, 4);
+        assert_eq!(stats.ncell, 2);
+        assert_eq!(stats.nedge, 5);
+        assert_eq!(stats.nboundary_facet, 4);
+        assert_eq!(stats.min, [0.0, 0.0]);
+        assert_eq!(stats.max, [2.0, 1.0]);
+        assert_eq!(stats.total_size, 2.0);
+        assert_eq!(stats.min_edge_length, 1.0);
+        assert_eq!(stats.max_edge_length, f64::sqrt(5.0));
+        let alpha = f64::atan(0.5).to_degrees();
+        assert!(stats.min_angle_percentiles.iter().all(|a| (a - alpha).abs() < 1e-12));
+        assert!(stats.max_angle_percentiles.iter().all(|a| (a - 90.0).abs() < 1e-12));
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_statistics_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        assert_eq!(tetgen.statistics(), None);
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 2.0, 2.0, 2.0)?;
+        tetgen.generate_delaunay(false)?;
+        let stats = tetgen.statistics().unwrap();
+        assert_eq!(stats.npoint, 5);
+        assert_eq!(stats.ncell, 2);
+        assert_eq!(stats.nedge, 9);
+        assert_eq!(stats.nboundary_facet, 6);
+        assert_eq!(stats.min, [0.0, 0.0, 0.0]);
+        assert_eq!(stats.max, [2.0, 2.0, 2.0]);
+        assert!((stats.total_size - 1.0).abs() < 1e-15);
+        assert_eq!(stats.min_edge_length, 1.0);
+        assert_eq!(stats.max_edge_length, 3.0);
+        let summary = tetgen.quality_summary().unwrap();
+        assert_eq!(stats.min_angle_percentiles[0], summary.min_angle);
+        assert_eq!(stats.max_angle_percentiles[4], summary.max_angle);
+        assert_eq!(stats.ratio_percentiles[4], summary.max_ratio);
+        Ok(())
+    }
+
+    #[test]
+    fn component_statistics_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(9, Some(10), Some(3), None)?;
+        assert_eq!(triangle.connected_components().len(), 0);
+        assert_eq!(triangle.component_statistics().len(), 0);
+        // a square with two regions and a disjoint triangle
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?
+            .set_point(3, 2.0, 1.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 0.0, 1.0)?
+            .set_point(6, 3.0, 0.0)?
+            .set_point(7, 4.0, 0.0)?
+            .set_point(8, 3.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 4)?
+            .set_segment(4, 4, 5)?
+            .set_segment(5, 5, 0)?
+            .set_segment(6, 1, 4)?
+            .set_segment(7, 6, 7)?
+            .set_segment(8, 7, 8)?
+            .set_segment(9, 8, 6)?
+            .set_region(0, 0.5, 0.5, 1, None)?
+            .set_region(1, 1.5, 0.5, 2, None)?
+            .set_region(2, 3.2, 0.2, 3, None)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let components = triangle.connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components.iter().map(|c| c.len()).sum::<usize>(), triangle.ntriangle());
+        let stats = triangle.component_statistics();
+        assert_eq!(stats[0].cells, components[0]);
+        assert_eq!(stats[0].attributes, &[1, 2]);
+        assert_eq!(stats[0].statistics.npoint, 6);
+        assert_eq!(stats[0].statistics.min, [0.0, 0.0]);
+        assert_eq!(stats[0].statistics.max, [2.0, 1.0]);
+        assert_eq!(stats[0].statistics.total_size, 2.0);
+        assert_eq!(stats[1].cells, &[components[1][0]]);
+        assert_eq!(stats[1].attributes, &[3]);
+        assert_eq!(stats[1].statistics.npoint, 3);
+        assert_eq!(stats[1].statistics.ncell, 1);
+        assert_eq!(stats[1].statistics.nboundary_facet, 3);
+        assert_eq!(stats[1].statistics.total_size, 0.5);
+        Ok(())
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{StrError, Tetgen, Triangle};
+
+impl Triangle {
+    /// Generates a structured mesh of a rectangle
+    ///
+    /// The rectangle is divided into `nx × ny` cells and each cell is split into two triangles
+    /// along the diagonal from its lower-left to its upper-right corner. The mesh is given to
+    /// Triangle as a previously generated mesh; thus, the output data (e.g., `ntriangle`,
+    /// `triangle_node`, `edge_marker`) is available as after `generate_mesh`.
+    ///
+    /// # Input
+    ///
+    /// * `nx` -- is the number of divisions along x (must be at least 1)
+    /// * `ny` -- is the number of divisions along y (must be at least 1)
+    /// * `min` -- is the lower-left corner `(x, y)`
+    /// * `max` -- is the upper-right corner `(x, y)`
+    /// * `markers` -- are the markers of the sides at `[xmin, xmax, ymin, ymax]`
+    /// * `quadratic` -- Generates the middle nodes; e.g., nnode = 6
+    ///
+    /// The points are numbered from left to right and then from bottom to top, i.e.,
+    /// `p = i + j * (nx + 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let triangle = Triangle::generate_structured_rectangle(3, 2, (0.0, 0.0), (3.0, 2.0), [-1, -2, -3, -4], false)?;
+    ///     assert_eq!(triangle.npoint(), 12);
+    ///     assert_eq!(triangle.ntriangle(), 12);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_structured_rectangle(
+        nx: usize,
+        ny: usize,
+        min: (f64, f64),
+        max: (f64, f64),
+        markers: [i32; 4],
+        quadratic: bool,
+    ) -> Result<Triangle, StrError> {
+        if nx < 1 || ny < 1 {
+            return Err("the number of divisions must be at least 1");
+        }
+        if max.0 <= min.0 || max.1 <= min.1 {
+            return Err("the max corner must be greater than the min corner");
+        }
+        let point = |i: usize, j: usize| i + j * (nx + 1);
+        let npoint = (nx + 1) * (ny + 1);
+        let nsegment = 2 * (nx + ny);
+        let mut triangle = Triangle::new(npoint, Some(nsegment), None, None)?;
+        let (dx, dy) = ((max.0 - min.0) / (nx as f64), (max.1 - min.1) / (ny as f64));
+        for j in 0..(ny + 1) {
+            for i in 0..(nx + 1) {
+                triangle.set_point(point(i, j), min.0 + (i as f64) * dx, min.1 + (j as f64) * dy)?;
+            }
+        }
+        // boundary segments in the order ymin, xmax, ymax, xmin
+        let [xmin, xmax, ymin, ymax] = markers;
+        let mut s = 0;
+        for i in 0..nx {
+            triangle
+                .set_segment(s, point(i, 0), point(i + 1, 0))?
+                .set_segment_marker(s, ymin)?;
+            s += 1;
+        }
+        for j in 0..ny {
+            triangle
+                .set_segment(s, point(nx, j), point(nx, j + 1))?
+                .set_segment_marker(s, xmax)?;
+            s += 1;
+        }
+        for i in 0..nx {
+            triangle
+                .set_segment(s, point(i, ny), point(i + 1, ny))?
+                .set_segment_marker(s, ymax)?;
+            s += 1;
+        }
+        for j in 0..ny {
+            triangle
+                .set_segment(s, point(0, j), point(0, j + 1))?
+                .set_segment_marker(s, xmin)?;
+            s += 1;
+        }
+        // two counterclockwise triangles per cell
+        let mut corners = Vec::with_capacity(2 * nx * ny);
+        for j in 0..ny {
+            for i in 0..nx {
+                let (p00, p10, p11, p01) = (point(i, j), point(i + 1, j), point(i + 1, j + 1), point(i, j + 1));
+                corners.push([p00, p10, p11]);
+                corners.push([p00, p11, p01]);
+            }
+        }
+        triangle.generate_given_mesh(false, quadratic, &corners)?;
+        Ok(triangle)
+    }
+}
+
+impl Tetgen {
+    /// Generates a structured mesh of a box
+    ///
+    /// The box is divided into `nx × ny × nz` cells and each cell is split into six tetrahedra
+    /// sharing the diagonal from its min corner to its max corner (Kuhn subdivision). The boundary
+    /// faces of each cell are split along the diagonal from their min corner to their max corner;
+    /// thus, the mesh is conforming. The mesh is given to Tetgen as a previously generated mesh;
+    /// thus, the output data (e.g., `ntet`, `tet_node`, `marked_face_marker`) is available
+    /// as after `generate_mesh`.
+    ///
+    /// # Input
+    ///
+    /// * `nx` -- is the number of divisions along x (must be at least 1)
+    /// * `ny` -- is the number of divisions along y (must be at least 1)
+    /// * `nz` -- is the number of divisions along z (must be at least 1)
+    /// * `min` -- is the min corner `(x, y, z)`
+    /// * `max` -- is the max corner `(x, y, z)`
+    /// * `markers` -- are the markers of the sides at `[xmin, xmax, ymin, ymax, zmin, zmax]`
+    /// * `o2` -- Generates the middle nodes; e.g., nnode = 10
+    ///
+    /// The points are numbered along x, then y, then z, i.e.,
+    /// `p = i + j * (nx + 1) + k * (nx + 1) * (ny + 1)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Tetgen};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let tetgen = Tetgen::generate_structured_box(2, 1, 1, (0.0, 0.0, 0.0), (2.0, 1.0, 1.0), [-1, -2, -3, -4, -5, -6], false)?;
+    ///     assert_eq!(tetgen.npoint(), 12);
+    ///     assert_eq!(tetgen.ntet(), 12);
+    ///     assert_eq!(tetgen.nmarked_face(), 20);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn generate_structured_box(
+        nx: usize,
+        ny: usize,
+        nz: usize,
+        min: (f64, f64, f64),
+        max: (f64, f64, f64),
+        markers: [i32; 6],
+        o2: bool,
+    ) -> Result<Tetgen, StrError> {
+        if nx < 1 || ny < 1 || nz < 1 {
+            return Err("the number of divisions must be at least 1");
+        }
+        if max.0 <= min.0 || max.1 <= min.1 || max.2 <= min.2 {
+            return Err("the max corner must be greater than the min corner");
+        }
+        let n = [nx, ny, nz];
+        let point = |ijk: [usize; 3]| ijk[0] + ijk[1] * (nx + 1) + ijk[2] * (nx + 1) * (ny + 1);
+        let npoint = (nx + 1) * (ny + 1) * (nz + 1);
+        let mut tetgen = Tetgen::new(npoint, None, None, None)?;
+        let (dx, dy, dz) = (
+            (max.0 - min.0) / (nx as f64),
+            (max.1 - min.1) / (ny as f64),
+            (max.2 - min.2) / (nz as f64),
+        );
+        for k in 0..(nz + 1) {
+            for j in 0..(ny + 1) {
+                for i in 0..(nx + 1) {
+                    let (x, y, z) = (
+                        min.0 + (i as f64) * dx,
+                        min.1 + (j as f64) * dy,
+                        min.2 + (k as f64) * dz,
+                    );
+                    tetgen.set_point(point([i, j, k]), x, y, z)?;
+                }
+            }
+        }
+        // six tetrahedra per cell, following the paths from the min to the max corner;
+        // the even permutations of the axes give positive tetrahedra and the odd ones are swapped
+        const PATHS: [([usize; 3], bool); 6] = [
+            ([0, 1, 2], true),
+            ([1, 2, 0], true),
+            ([2, 0, 1], true),
+            ([0, 2, 1], false),
+            ([2, 1, 0], false),
+            ([1, 0, 2], false),
+        ];
+        let mut corners = Vec::with_capacity(6 * nx * ny * nz);
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    for (axes, even) in PATHS {
+                        let mut ijk = [i, j, k];
+                        let a = point(ijk);
+                        ijk[axes[0]] += 1;
+                        let b = point(ijk);
+                        ijk[axes[1]] += 1;
+                        let c = point(ijk);
+                        ijk[axes[2]] += 1;
+                        let d = point(ijk);
+                        corners.push(if even { [a, b, c, d] } else { [a, c, b, d] });
+                    }
+                }
+            }
+        }
+        // two triangles per boundary quad; the side normal to `axis` at the min (max) position
+        // gets the marker 2 * axis (2 * axis + 1)
+        let mut faces = Vec::new();
+        let mut face_markers = Vec::new();
+        for axis in 0..3 {
+            let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+            for (side, position) in [0, n[axis]].into_iter().enumerate() {
+                for iv in 0..n[v] {
+                    for iu in 0..n[u] {
+                        let mut ijk = [0; 3];
+                        ijk[axis] = position;
+                        ijk[u] = iu;
+                        ijk[v] = iv;
+                        let a = point(ijk);
+                        ijk[u] += 1;
+                        let b = point(ijk);
+                        ijk[v] += 1;
+                        let c = point(ijk);
+                        ijk[u] -= 1;
+                        let d = point(ijk);
+                        faces.push([a, b, c]);
+                        faces.push([a, c, d]);
+                        face_markers.push(markers[2 * axis + side]);
+                        face_markers.push(markers[2 * axis + side]);
+                    }
+                }
+            }
+        }
+        tetgen.generate_given_mesh(false, o2, &corners, &faces, &face_markers)?;
+        Ok(tetgen)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Tetgen, Triangle};
+
+    fn triangle_area(triangle: &Triangle, index: usize) -> f64 {
+        let x: Vec<(f64, f64)> = (0..3)
+            .map(|m| {
+                let p = triangle.triangle_node(index, m);
+                (triangle.point(p, 0), triangle.point(p, 1))
+            })
+            .collect();
+        ((x[1].0 - x[0].0) * (x[2].1 - x[0].1) - (x[2].0 - x[0].0) * (x[1].1 - x[0].1)) / 2.0
+    }
+
+    fn tet_volume(tetgen: &Tetgen, index: usize) -> f64 {
+        let x: Vec<[f64; 3]> = (0..4)
+            .map(|m| {
+                let p = tetgen.tet_node(index, m);
+                [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)]
+            })
+            .collect();
+        let a = [x[1][0] - x[0][0], x[1][1] - x[0][1], x[1][2] - x[0][2]];
+        let b = [x[2][0] - x[0][0], x[2][1] - x[0][1], x[2][2] - x[0][2]];
+        let c = [x[3][0] - x[0][0], x[3][1] - x[0][1], x[3][2] - x[0][2]];
+        (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0]))
+            / 6.0
+    }
+
+    #[test]
+    fn generate_structured_rectangle_captures_errors() {
+        assert_eq!(
+            Triangle::generate_structured_rectangle(0, 1, (0.0, 0.0), (1.0, 1.0), [0; 4], false).err(),
+            Some("the number of divisions must be at least 1")
+        );
+        assert_eq!(
+            Triangle::generate_structured_rectangle(1, 1, (0.0, 0.0), (1.0, 0.0), [0; 4], false).err(),
+            Some("the max corner must be greater than the min corner")
+        );
+    }
+
+    #[test]
+    fn generate_structured_rectangle_works() -> Result<(), StrError> {
+        let (nx, ny) = (4, 3);
+        let triangle =
+            Triangle::generate_structured_rectangle(nx, ny, (1.0, 2.0), (3.0, 5.0), [-1, -2, -3, -4], false)?;
+        assert_eq!(triangle.npoint(), (nx + 1) * (ny + 1));
+        assert_eq!(triangle.ntriangle(), 2 * nx * ny);
+        assert_eq!(triangle.nnode(), 3);
+        assert_eq!(triangle.point(nx + 1, 0), 1.0);
+        assert_eq!(triangle.point(nx + 1, 1), 3.0);
+        let mut area = 0.0;
+        for index in 0..triangle.ntriangle() {
+            let a = triangle_area(&triangle, index);
+            assert!(a > 0.0);
+            area += a;
+        }
+        assert!(f64::abs(area - 6.0) < 1e-14);
+        let mut count = [0; 4];
+        for index in 0..triangle.nedge() {
+            let (a, b) = (triangle.edge_point(index, 0), triangle.edge_point(index, 1));
+            let (xa, ya) = (triangle.point(a, 0), triangle.point(a, 1));
+            let (xb, yb) = (triangle.point(b, 0), triangle.point(b, 1));
+            let marker = triangle.edge_marker(index);
+            if xa == 1.0 && xb == 1.0 {
+                assert_eq!(marker, -1);
+            } else if xa == 3.0 && xb == 3.0 {
+                assert_eq!(marker, -2);
+            } else if ya == 2.0 && yb == 2.0 {
+                assert_eq!(marker, -3);
+            } else if ya == 5.0 && yb == 5.0 {
+                assert_eq!(marker, -4);
+            } else {
+                assert_eq!(marker, 0);
+                continue;
+            }
+            count[(-marker - 1) as usize] += 1;
+        }
+        assert_eq!(count, [ny, ny, nx, nx]);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_structured_rectangle_quadratic_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 2, (0.0, 0.0), (1.0, 1.0), [0; 4], true)?;
+        assert_eq!(triangle.ntriangle(), 8);
+        assert_eq!(triangle.nnode(), 6);
+        assert_eq!(triangle.npoint(), 25);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_structured_box_captures_errors() {
+        assert_eq!(
+            Tetgen::generate_structured_box(1, 0, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], false).err(),
+            Some("the number of divisions must be at least 1")
+        );
+        assert_eq!(
+            Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, -1.0), [0; 6], false).err(),
+            Some("the max corner must be greater than the min corner")
+        );
+    }
+
+    #[test]
+    fn generate_structured_box_works() -> Result<(), StrError> {
+        let (nx, ny, nz) = (3, 2, 2);
+        let markers = [-1, -2, -3, -4, -5, -6];
+        let tetgen = Tetgen::generate_structured_box(nx, ny, nz, (0.0, 0.0, 0.0), (3.0, 1.0, 2.0), markers, false)?;
+        assert_eq!(tetgen.npoint(), (nx + 1) * (ny + 1) * (nz + 1));
+        assert_eq!(tetgen.ntet(), 6 * nx * ny * nz);
+        assert_eq!(tetgen.nnode(), 4);
+        let mut volume = 0.0;
+        for index in 0..tetgen.ntet() {
+            let v = tet_volume(&tetgen, index);
+            assert!(v.abs() > 0.0);
+            volume += v.abs();
+        }
+        assert!(f64::abs(volume - 6.0) < 1e-14);
+        assert_eq!(tetgen.nmarked_face(), 4 * (nx * ny + ny * nz + nz * nx));
+        let max = [3.0, 1.0, 2.0];
+        let mut count = [0; 6];
+        for index in 0..tetgen.nmarked_face() {
+            let marker = tetgen.marked_face_marker(index);
+            let side = (-marker - 1) as usize;
+            let (axis, position) = (side / 2, [0.0, max[side / 2]][side % 2]);
+            for m in 0..3 {
+                let p = tetgen.marked_face_node(index, m);
+                assert_eq!(tetgen.point(p, axis), position);
+            }
+            count[side] += 1;
+        }
+        assert_eq!(count, [8, 8, 12, 12, 12, 12]);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_structured_box_orients_tetrahedra_consistently() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(2, 2, 2, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], false)?;
+        let mut delaunay = Tetgen::new(4, None, None, None)?;
+        delaunay
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        delaunay.generate_delaunay(false)?;
+        let sign = f64::signum(tet_volume(&delaunay, 0));
+        for index in 0..tetgen.ntet() {
+            assert_eq!(f64::signum(tet_volume(&tetgen, index)), sign);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generate_structured_box_o2_works() -> Result<(), StrError> {
+        let tetgen = Tetgen::generate_structured_box(1, 1, 1, (0.0, 0.0, 0.0), (1.0, 1.0, 1.0), [0; 6], true)?;
+        assert_eq!(tetgen.ntet(), 6);
+        assert_eq!(tetgen.nnode(), 10);
+        // 8 corners + 12 cube edges + 6 face diagonals + 1 main diagonal
+        assert_eq!(tetgen.npoint(), 27);
+        Ok(())
+    }
+}
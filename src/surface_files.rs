@@ -0,0 +1,711 @@
+use crate::poly_file::{lines, next_line, parse};
+use crate::StrError;
+use crate::Tetgen;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+/// Holds the type of a scalar property of a PLY file
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PlyType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+/// Holds a property of an element of a PLY file
+struct PlyProperty {
+    name: String,                // name of the property (e.g., "x" or "vertex_indices")
+    count_type: Option<PlyType>, // type of the number of items (if the property is a list)
+    value_type: PlyType,         // type of the values
+}
+
+/// Holds an element of a PLY file
+struct PlyElement {
+    name: String,                 // name of the element (e.g., "vertex" or "face")
+    count: usize,                 // number of items
+    properties: Vec<PlyProperty>, // properties of each item
+}
+
+/// Holds the body of a PLY file
+enum PlyBody<'a> {
+    Ascii(Box<dyn Iterator<Item = &'a str> + 'a>),
+    Binary(&'a [u8], usize, bool), // bytes, position, and big endian flag
+}
+
+/// Holds the points and polygons of a surface mesh
+struct Surface {
+    points: Vec<[f64; 3]>,     // coordinates of the points
+    polygons: Vec<Vec<usize>>, // indices of the points of each polygon
+}
+
+impl Tetgen {
+    /// Allocates a new instance with the polygons of an OFF file as facets
+    ///
+    /// The format is described in [Geomview's manual](http://www.geomview.org/docs/html/OFF.html).
+    /// Each polygon becomes a facet of the Piecewise Linear Complex (PLC); thus, the polygons
+    /// must be planar and the surface must be closed. The header may be OFF, COFF, NOFF, or
+    /// similar; the colors, normals, and texture coordinates are ignored.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_off<P>(full_path: &P) -> Result<Tetgen, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let contents = fs::read_to_string(Path::new(full_path)).map_err(|_| "cannot read file")?;
+        surface_to_tetgen(&parse_off(&contents)?)
+    }
+
+    /// Allocates a new instance with the polygons of a PLY file as facets
+    ///
+    /// The format is described in [Paul Bourke's website](http://paulbourke.net/dataformats/ply/).
+    /// The ASCII and binary (little and big endian) formats are supported. The points are read
+    /// from the x, y, and z properties of the "vertex" element and the polygons from the
+    /// "vertex_indices" (or "vertex_index") list of the "face" element; the other elements and
+    /// properties are ignored. Each polygon becomes a facet of the Piecewise Linear Complex
+    /// (PLC); thus, the polygons must be planar and the surface must be closed.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_ply<P>(full_path: &P) -> Result<Tetgen, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let bytes = fs::read(Path::new(full_path)).map_err(|_| "cannot read file")?;
+        surface_to_tetgen(&parse_ply(&bytes)?)
+    }
+
+    /// Allocates a new instance with the triangles of an STL file as facets
+    ///
+    /// The ASCII and binary formats are supported (a file is binary if its size matches the
+    /// number of triangles in its header). The coinciding vertices of the triangles are merged
+    /// (they must have exactly the same coordinates) and the normals are ignored. Each triangle
+    /// becomes a facet of the Piecewise Linear Complex (PLC); thus, the surface must be closed.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_stl<P>(full_path: &P) -> Result<Tetgen, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let bytes = fs::read(Path::new(full_path)).map_err(|_| "cannot read file")?;
+        surface_to_tetgen(&parse_stl(&bytes)?)
+    }
+}
+
+/// Allocates a new instance with the polygons of a surface as facets
+fn surface_to_tetgen(surface: &Surface) -> Result<Tetgen, StrError> {
+    let facet_npoint = surface.polygons.iter().map(|polygon| polygon.len()).collect();
+    let mut tetgen = Tetgen::new(surface.points.len(), Some(facet_npoint), None, None)?;
+    tetgen.set_points(&surface.points)?;
+    for (index, polygon) in surface.polygons.iter().enumerate() {
+        for (m, p) in polygon.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+    }
+    Ok(tetgen)
+}
+
+/// Parses an OFF file
+fn parse_off(contents: &str) -> Result<Surface, StrError> {
+    let mut lines = lines(contents);
+    let mut words = next_line(&mut lines, 1)?;
+    let keyword = words[0];
+    if !keyword.ends_with("OFF") || keyword.contains('4') || keyword.starts_with('n') {
+        return Err("the OFF file must start with OFF");
+    }
+    if words.len() == 1 {
+        words = next_line(&mut lines, 2)?;
+    } else {
+        words.remove(0);
+        if words.len() < 2 {
+            return Err("the file has a line with missing values");
+        }
+    }
+    let npoint: usize = parse(words[0])?;
+    let npolygon: usize = parse(words[1])?;
+    let mut points = Vec::new();
+    for _ in 0..npoint {
+        let words = next_line(&mut lines, 3)?;
+        points.push([parse(words[0])?, parse(words[1])?, parse(words[2])?]);
+    }
+    let mut polygons = Vec::new();
+    for _ in 0..npolygon {
+        let words = next_line(&mut lines, 1)?;
+        let n: usize = parse(words[0])?;
+        if n >= words.len() {
+            return Err("the file has a line with missing values");
+        }
+        let polygon = words[1..(1 + n)]
+            .iter()
+            .map(|word| parse(word))
+            .collect::<Result<Vec<usize>, _>>()?;
+        polygons.push(polygon);
+    }
+    check_polygons(&points, &polygons)?;
+    Ok(Surface { points, polygons })
+}
+
+/// Parses a PLY file
+fn parse_ply(bytes: &[u8]) -> Result<Surface, StrError> {
+    // header
+    let marker = b"end_header";
+    let end = bytes
+        .windows(marker.len())
+        .position(|window| window == marker)
+        .ok_or("the PLY file must have the end_header line")?;
+    let header = std::str::from_utf8(&bytes[..end]).map_err(|_| "the PLY header is invalid")?;
+    let mut start = end + marker.len();
+    while start < bytes.len() && bytes[start] != b'\n' {
+        start += 1;
+    }
+    start += 1;
+    let mut header_lines = header.lines().map(|line| line.split_whitespace().collect::<Vec<_>>());
+    if header_lines.next().map(|words| words == ["ply"]) != Some(true) {
+        return Err("the PLY file must start with ply");
+    }
+    let mut format = "";
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for words in header_lines {
+        match words.first() {
+            Some(&"format") if words.len() >= 2 => format = words[1],
+            Some(&"element") if words.len() >= 3 => elements.push(PlyElement {
+                name: words[1].to_string(),
+                count: words[2].parse().map_err(|_| "the PLY header is invalid")?,
+                properties: Vec::new(),
+            }),
+            Some(&"property") => {
+                let element = elements.last_mut().ok_or("the PLY header is invalid")?;
+                let property = if words.len() == 5 && words[1] == "list" {
+                    PlyProperty {
+                        name: words[4].to_string(),
+                        count_type: Some(ply_type(words[2])?),
+                        value_type: ply_type(words[3])?,
+                    }
+                } else if words.len() == 3 {
+                    PlyProperty {
+                        name: words[2].to_string(),
+                        count_type: None,
+                        value_type: ply_type(words[1])?,
+                    }
+                } else {
+                    return Err("the PLY header is invalid");
+                };
+                element.properties.push(property);
+            }
+            Some(&"comment") | Some(&"obj_info") | None => (),
+            _ => return Err("the PLY header is invalid"),
+        }
+    }
+
+    // body
+    let body = bytes.get(start..).unwrap_or(&[]);
+    let mut body = match format {
+        "ascii" => {
+            let text = std::str::from_utf8(body).map_err(|_| "the PLY body is invalid")?;
+            PlyBody::Ascii(Box::new(text.split_whitespace()))
+        }
+        "binary_little_endian" => PlyBody::Binary(body, 0, false),
+        "binary_big_endian" => PlyBody::Binary(body, 0, true),
+        _ => return Err("the PLY format must be ascii, binary_little_endian, or binary_big_endian"),
+    };
+    let mut points = Vec::new();
+    let mut polygons = Vec::new();
+    for element in &elements {
+        if element.properties.is_empty() && element.count > 0 {
+            return Err("the PLY header is invalid"); // nothing would be read from the body
+        }
+        for _ in 0..element.count {
+            let mut x = [0.0; 3];
+            for property in &element.properties {
+                match property.count_type {
+                    Some(count_type) => {
+                        let n = body.read(count_type)? as usize;
+                        let mut values = Vec::new();
+                        for _ in 0..n {
+                            values.push(body.read(property.value_type)? as usize);
+                        }
+                        let name = property.name.as_str();
+                        if element.name == "face" && (name == "vertex_indices" || name == "vertex_index") {
+                            polygons.push(values);
+                        }
+                    }
+                    None => {
+                        let value = body.read(property.value_type)?;
+                        if element.name == "vertex" {
+                            match property.name.as_str() {
+                                "x" => x[0] = value,
+                                "y" => x[1] = value,
+                                "z" => x[2] = value,
+                                _ => (),
+                            }
+                        }
+                    }
+                }
+            }
+            if element.name == "vertex" {
+                points.push(x);
+            }
+        }
+    }
+    check_polygons(&points, &polygons)?;
+    Ok(Surface { points, polygons })
+}
+
+/// Parses an STL file (ASCII or binary)
+fn parse_stl(bytes: &[u8]) -> Result<Surface, StrError> {
+    let mut triangles: Vec<[[f64; 3]; 3]> = Vec::new();
+    let binary_count = bytes
+        .get(80..84)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+    match binary_count {
+        Some(n) if bytes.len() == 84 + 50 * n => {
+            let value = |k: usize| f32::from_le_bytes([bytes[k], bytes[k + 1], bytes[k + 2], bytes[k + 3]]) as f64;
+            for t in 0..n {
+                // skip the normal (12 bytes) and read the vertices (36 bytes)
+                let start = 84 + 50 * t + 12;
+                let mut triangle = [[0.0; 3]; 3];
+                for (m, x) in triangle.iter_mut().enumerate() {
+                    for (i, xi) in x.iter_mut().enumerate() {
+                        *xi = value(start + 12 * m + 4 * i);
+                    }
+                }
+                triangles.push(triangle);
+            }
+        }
+        _ => {
+            let contents = std::str::from_utf8(bytes).map_err(|_| "the STL file must start with solid")?;
+            let mut lines = lines(contents);
+            match lines.next() {
+                Some(words) if words[0] == "solid" => (),
+                _ => return Err("the STL file must start with solid"),
+            }
+            let mut vertices = Vec::new();
+            for words in lines {
+                if words[0] == "vertex" {
+                    if words.len() < 4 {
+                        return Err("the file has a line with missing values");
+                    }
+                    vertices.push([parse(words[1])?, parse(words[2])?, parse(words[3])?]);
+                } else if words[0] == "endloop" {
+                    if vertices.len() != 3 {
+                        return Err("the STL facets must have three vertices");
+                    }
+                    triangles.push([vertices[0], vertices[1], vertices[2]]);
+                    vertices.clear();
+                }
+            }
+        }
+    }
+    if triangles.is_empty() {
+        return Err("the STL file has no triangles");
+    }
+
+    // merge the coinciding vertices
+    let mut ids: HashMap<[u64; 3], usize> = HashMap::new();
+    let mut points = Vec::new();
+    let mut polygons = Vec::with_capacity(triangles.len());
+    for triangle in &triangles {
+        let polygon = triangle
+            .iter()
+            .map(|x| {
+                *ids.entry(x.map(|v| (v + 0.0).to_bits())).or_insert_with(|| {
+                    points.push(*x);
+                    points.len() - 1
+                })
+            })
+            .collect();
+        polygons.push(polygon);
+    }
+    Ok(Surface { points, polygons })
+}
+
+/// Returns the type of a PLY property
+fn ply_type(word: &str) -> Result<PlyType, StrError> {
+    match word {
+        "char" | "int8" => Ok(PlyType::Int8),
+        "uchar" | "uint8" => Ok(PlyType::UInt8),
+        "short" | "int16" => Ok(PlyType::Int16),
+        "ushort" | "uint16" => Ok(PlyType::UInt16),
+        "int" | "int32" => Ok(PlyType::Int32),
+        "uint" | "uint32" => Ok(PlyType::UInt32),
+        "float" | "float32" => Ok(PlyType::Float32),
+        "double" | "float64" => Ok(PlyType::Float64),
+        _ => Err("the PLY property type is invalid"),
+    }
+}
+
+impl<'a> PlyBody<'a> {
+    /// Reads the next value
+    fn read(&mut self, value_type: PlyType) -> Result<f64, StrError> {
+        match self {
+            PlyBody::Ascii(words) => parse(words.next().ok_or("the file ended unexpectedly")?),
+            PlyBody::Binary(bytes, position, big_endian) => {
+                let size = match value_type {
+                    PlyType::Int8 | PlyType::UInt8 => 1,
+                    PlyType::Int16 | PlyType::UInt16 => 2,
+                    PlyType::Int32 | PlyType::UInt32 | PlyType::Float32 => 4,
+                    PlyType::Float64 => 8,
+                };
+                let chunk = bytes
+                    .get(*position..(*position + size))
+                    .ok_or("the file ended unexpectedly")?;
+                *position += size;
+                let mut b = [0; 8];
+                b[..size].copy_from_slice(chunk);
+                if *big_endian {
+                    b[..size].reverse();
+                }
+                Ok(match value_type {
+                    PlyType::Int8 => b[0] as i8 as f64,
+                    PlyType::UInt8 => b[0] as f64,
+                    PlyType::Int16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::UInt16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+                    PlyType::Int32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::UInt32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::Float32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+                    PlyType::Float64 => f64::from_le_bytes(b),
+                })
+            }
+        }
+    }
+}
+
+/// Checks the indices of the points of the polygons
+fn check_polygons(points: &[[f64; 3]], polygons: &[Vec<usize>]) -> Result<(), StrError> {
+    if polygons.iter().flatten().any(|p| *p >= points.len()) {
+        return Err("the index of a polygon point is out of bounds");
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_off, parse_ply, parse_stl};
+    use crate::{StrError, Tetgen};
+    use std::fs;
+
+    /// Holds the points of the unit cube
+    const CUBE_POINTS: [[f64; 3]; 8] = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, 1.0, 1.0],
+    ];
+
+    /// Holds the faces of the unit cube
+    const CUBE_FACES: [[usize; 4]; 6] = [
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+    ];
+
+    #[test]
+    fn parse_off_captures_errors() {
+        assert_eq!(parse_off("").err(), Some("the file ended unexpectedly"));
+        assert_eq!(parse_off("PLY\n").err(), Some("the OFF file must start with OFF"));
+        assert_eq!(parse_off("4OFF\n").err(), Some("the OFF file must start with OFF"));
+        assert_eq!(
+            parse_off("OFF 8\n").err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_off("OFF\n1 0 0\n0 0\n").err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_off("OFF\n1 1 0\n0 0 0\n3 0 1\n").err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_off("OFF\n1 1 0\n0 0 0\n3 0 1 2\n").err(),
+            Some("the index of a polygon point is out of bounds")
+        );
+
+        // the counts in the header and polygons are not trusted to allocate memory
+        assert_eq!(
+            parse_off("OFF\n99999999999999999 99999999999999999 0\n").err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_off("OFF\n1 99999999999999999 0\n0 0 0\n").err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_off("OFF\n1 1 0\n0 0 0\n18446744073709551615 0\n").err(),
+            Some("the file has a line with missing values")
+        );
+    }
+
+    #[test]
+    fn parse_ply_captures_errors() {
+        assert_eq!(
+            parse_ply(b"ply\n").err(),
+            Some("the PLY file must have the end_header line")
+        );
+        assert_eq!(
+            parse_ply(b"off\nend_header\n").err(),
+            Some("the PLY file must start with ply")
+        );
+        assert_eq!(
+            parse_ply(b"ply\nformat ascii 1.0\nproperty float x\nend_header\n").err(),
+            Some("the PLY header is invalid")
+        );
+        assert_eq!(
+            parse_ply(b"ply\nformat ascii 1.0\nelement vertex 1\nproperty real x\nend_header\n").err(),
+            Some("the PLY property type is invalid")
+        );
+        assert_eq!(
+            parse_ply(b"ply\nformat binary 1.0\nend_header\n").err(),
+            Some("the PLY format must be ascii, binary_little_endian, or binary_big_endian")
+        );
+        assert_eq!(
+            parse_ply(b"ply\nformat ascii 1.0\nelement vertex 1\nproperty float x\nend_header\n").err(),
+            Some("the file ended unexpectedly")
+        );
+
+        // the counts in the header and lists are not trusted to allocate memory
+        assert_eq!(
+            parse_ply(b"ply\nformat ascii 1.0\nelement vertex 99999999999999999\nend_header\n").err(),
+            Some("the PLY header is invalid")
+        );
+        assert_eq!(
+            parse_ply(b"ply\nformat ascii 1.0\nelement vertex 99999999999999999\nproperty float x\nend_header\n0\n")
+                .err(),
+            Some("the file ended unexpectedly")
+        );
+        let mut bytes =
+            b"ply\nformat binary_little_endian 1.0\nelement face 1\nproperty list uint int vertex_indices\nend_header\n"
+                .to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&0_i32.to_le_bytes());
+        assert_eq!(parse_ply(&bytes).err(), Some("the file ended unexpectedly"));
+    }
+
+    #[test]
+    fn parse_off_works() -> Result<(), StrError> {
+        let mut contents = String::from("COFF # with colors\n8 6 12\n");
+        for x in &CUBE_POINTS {
+            contents.push_str(&format!("{} {} {} 255 0 0 255\n", x[0], x[1], x[2]));
+        }
+        for face in &CUBE_FACES {
+            contents.push_str(&format!(
+                "4 {} {} {} {} 0.5 0.5 0.5\n",
+                face[0], face[1], face[2], face[3]
+            ));
+        }
+        let surface = parse_off(&contents)?;
+        assert_eq!(surface.points, &CUBE_POINTS);
+        assert_eq!(surface.polygons, &CUBE_FACES);
+
+        // counts on the first line
+        let surface = parse_off("OFF 3 1 3\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n")?;
+        assert_eq!(surface.polygons, &[[0, 1, 2]]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_ply_works() -> Result<(), StrError> {
+        // ascii with an extra property and an extra element
+        let mut contents = String::from(
+            "ply\nformat ascii 1.0\ncomment unit cube\n\
+             element vertex 8\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\n\
+             element face 6\nproperty list uchar int vertex_indices\n\
+             element edge 1\nproperty int vertex1\nproperty int vertex2\n\
+             end_header\n",
+        );
+        for x in &CUBE_POINTS {
+            contents.push_str(&format!("{} {} {} 255\n", x[0], x[1], x[2]));
+        }
+        for face in &CUBE_FACES {
+            contents.push_str(&format!("4 {} {} {} {}\n", face[0], face[1], face[2], face[3]));
+        }
+        contents.push_str("0 1\n");
+        let surface = parse_ply(contents.as_bytes())?;
+        assert_eq!(surface.points, &CUBE_POINTS);
+        assert_eq!(surface.polygons, &CUBE_FACES);
+
+        // binary (little and big endian)
+        for big_endian in [false, true] {
+            let format = if big_endian {
+                "binary_big_endian"
+            } else {
+                "binary_little_endian"
+            };
+            let mut bytes = format!(
+                "ply\nformat {} 1.0\n\
+                 element vertex 8\nproperty double x\nproperty double y\nproperty float z\n\
+                 element face 6\nproperty list uchar uint vertex_index\n\
+                 end_header\n",
+                format
+            )
+            .into_bytes();
+            for x in &CUBE_POINTS {
+                if big_endian {
+                    bytes.extend(x[0].to_be_bytes());
+                    bytes.extend(x[1].to_be_bytes());
+                    bytes.extend((x[2] as f32).to_be_bytes());
+                } else {
+                    bytes.extend(x[0].to_le_bytes());
+                    bytes.extend(x[1].to_le_bytes());
+                    bytes.extend((x[2] as f32).to_le_bytes());
+                }
+            }
+            for face in &CUBE_FACES {
+                bytes.push(4);
+                for p in face {
+                    if big_endian {
+                        bytes.extend((*p as u32).to_be_bytes());
+                    } else {
+                        bytes.extend((*p as u32).to_le_bytes());
+                    }
+                }
+            }
+            let surface = parse_ply(&bytes)?;
+            assert_eq!(surface.points, &CUBE_POINTS);
+            assert_eq!(surface.polygons, &CUBE_FACES);
+            assert_eq!(
+                parse_ply(&bytes[..(bytes.len() - 1)]).err(),
+                Some("the file ended unexpectedly")
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the triangles of the unit cube (two per face)
+    fn cube_triangles() -> Vec<[[f64; 3]; 3]> {
+        CUBE_FACES
+            .iter()
+            .flat_map(|f| [[f[0], f[1], f[2]], [f[0], f[2], f[3]]])
+            .map(|t| t.map(|p| CUBE_POINTS[p]))
+            .collect()
+    }
+
+    /// Returns the cube as an ASCII STL file
+    fn cube_stl_ascii() -> String {
+        let mut stl = String::from("solid cube\n");
+        for triangle in cube_triangles() {
+            stl.push_str("  facet normal 0 0 0\n    outer loop\n");
+            for x in triangle {
+                stl.push_str(&format!("      vertex {} {} {}\n", x[0], x[1], x[2]));
+            }
+            stl.push_str("    endloop\n  endfacet\n");
+        }
+        stl.push_str("endsolid cube\n");
+        stl
+    }
+
+    /// Returns the cube as a binary STL file (the header starts with solid on purpose)
+    fn cube_stl_binary() -> Vec<u8> {
+        let mut stl = vec![0; 80];
+        stl[..5].copy_from_slice(b"solid");
+        stl.extend_from_slice(&12_u32.to_le_bytes());
+        for triangle in cube_triangles() {
+            stl.extend_from_slice(&[0; 12]);
+            for x in triangle {
+                for v in x {
+                    stl.extend_from_slice(&(v as f32).to_le_bytes());
+                }
+            }
+            stl.extend_from_slice(&[0; 2]);
+        }
+        stl
+    }
+
+    #[test]
+    fn parse_stl_captures_errors() {
+        assert_eq!(parse_stl(b"").err(), Some("the STL file must start with solid"));
+        assert_eq!(parse_stl(b"ply\n").err(), Some("the STL file must start with solid"));
+        assert_eq!(
+            parse_stl(b"solid\nendsolid\n").err(),
+            Some("the STL file has no triangles")
+        );
+        assert_eq!(
+            parse_stl(b"solid\nouter loop\nvertex 0 0\n").err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_stl(b"solid\nouter loop\nvertex 0 0 0\nvertex 1 0 0\nendloop\n").err(),
+            Some("the STL facets must have three vertices")
+        );
+    }
+
+    #[test]
+    fn parse_stl_works() -> Result<(), StrError> {
+        for surface in [parse_stl(cube_stl_ascii().as_bytes())?, parse_stl(&cube_stl_binary())?] {
+            assert_eq!(surface.points.len(), 8);
+            assert_eq!(surface.polygons.len(), 12);
+            for (polygon, triangle) in surface.polygons.iter().zip(cube_triangles()) {
+                let x: Vec<[f64; 3]> = polygon.iter().map(|p| surface.points[*p]).collect();
+                assert_eq!(x, triangle);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn from_off_ply_and_stl_work() -> Result<(), StrError> {
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        let mut off = String::from("OFF\n8 6 0\n");
+        let mut ply = String::from(
+            "ply\nformat ascii 1.0\n\
+             element vertex 8\nproperty float x\nproperty float y\nproperty float z\n\
+             element face 6\nproperty list uchar int vertex_indices\n\
+             end_header\n",
+        );
+        for x in &CUBE_POINTS {
+            off.push_str(&format!("{} {} {}\n", x[0], x[1], x[2]));
+            ply.push_str(&format!("{} {} {}\n", x[0], x[1], x[2]));
+        }
+        for face in &CUBE_FACES {
+            off.push_str(&format!("4 {} {} {} {}\n", face[0], face[1], face[2], face[3]));
+            ply.push_str(&format!("4 {} {} {} {}\n", face[0], face[1], face[2], face[3]));
+        }
+        fs::write("/tmp/tritet/test_from_off.off", off).map_err(|_| "cannot write file")?;
+        fs::write("/tmp/tritet/test_from_ply.ply", ply).map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Tetgen::from_off("/tmp/tritet/__does_not_exist__.off").err(),
+            Some("cannot read file")
+        );
+        assert_eq!(
+            Tetgen::from_ply("/tmp/tritet/__does_not_exist__.ply").err(),
+            Some("cannot read file")
+        );
+        fs::write("/tmp/tritet/test_from_stl_ascii.stl", cube_stl_ascii()).map_err(|_| "cannot write file")?;
+        fs::write("/tmp/tritet/test_from_stl_binary.stl", cube_stl_binary()).map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Tetgen::from_stl("/tmp/tritet/__does_not_exist__.stl").err(),
+            Some("cannot read file")
+        );
+        for tetgen in [
+            Tetgen::from_off("/tmp/tritet/test_from_off.off")?,
+            Tetgen::from_ply("/tmp/tritet/test_from_ply.ply")?,
+            Tetgen::from_stl("/tmp/tritet/test_from_stl_ascii.stl")?,
+            Tetgen::from_stl("/tmp/tritet/test_from_stl_binary.stl")?,
+        ] {
+            tetgen.generate_mesh(false, false, Some(0.1), None)?;
+            let volume: f64 = tetgen.to_mesh().measures().iter().sum();
+            assert!(f64::abs(volume - 1.0) < 1e-14);
+        }
+        Ok(())
+    }
+}
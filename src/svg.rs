@@ -0,0 +1,355 @@
+use crate::{ColorMap, StrError, Triangle};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs::{self, File};
+use std::io::Write as IoWrite;
+use std::path::Path;
+
+/// Holds the options of `Triangle::to_svg` and `Triangle::write_svg`
+///
+/// # Examples
+///
+/// ```
+/// use tritet::{StrError, SvgOptions, Triangle};
+///
+/// fn main() -> Result<(), StrError> {
+///     let triangle = Triangle::generate_structured_rectangle(2, 1, (0.0, 0.0), (2.0, 1.0), [-1, -2, -3, -4], false)?;
+///     let mut options = SvgOptions::new();
+///     options.set_width(300.0).set_point_ids(true).set_markers(true);
+///     let svg = triangle.to_svg(&options)?;
+///     assert!(svg.starts_with("<svg"));
+///     assert!(svg.contains(">5</text>"));
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The width of the figure in pixels (the height follows from the aspect ratio of the mesh)
+    pub width: f64,
+
+    /// The margin around the mesh in pixels
+    pub margin: f64,
+
+    /// The font size of the labels in pixels
+    pub fontsize: f64,
+
+    /// Draws the IDs of the points
+    pub point_ids: bool,
+
+    /// Draws the IDs of the triangles
+    pub triangle_ids: bool,
+
+    /// Draws the attributes of the triangles (in brackets)
+    pub attribute_ids: bool,
+
+    /// Draws the edges with non-zero markers with the dark colors of the markers and their labels
+    pub markers: bool,
+}
+
+impl SvgOptions {
+    /// Allocates a new instance with the default options
+    pub fn new() -> Self {
+        SvgOptions {
+            width: 600.0,
+            margin: 20.0,
+            fontsize: 10.0,
+            point_ids: false,
+            triangle_ids: false,
+            attribute_ids: false,
+            markers: false,
+        }
+    }
+
+    /// Sets the width of the figure in pixels
+    pub fn set_width(&mut self, value: f64) -> &mut Self {
+        self.width = value;
+        self
+    }
+
+    /// Sets the margin around the mesh in pixels
+    pub fn set_margin(&mut self, value: f64) -> &mut Self {
+        self.margin = value;
+        self
+    }
+
+    /// Sets the font size of the labels in pixels
+    pub fn set_fontsize(&mut self, value: f64) -> &mut Self {
+        self.fontsize = value;
+        self
+    }
+
+    /// Draws the IDs of the points
+    pub fn set_point_ids(&mut self, flag: bool) -> &mut Self {
+        self.point_ids = flag;
+        self
+    }
+
+    /// Draws the IDs of the triangles
+    pub fn set_triangle_ids(&mut self, flag: bool) -> &mut Self {
+        self.triangle_ids = flag;
+        self
+    }
+
+    /// Draws the attributes of the triangles
+    pub fn set_attribute_ids(&mut self, flag: bool) -> &mut Self {
+        self.attribute_ids = flag;
+        self
+    }
+
+    /// Draws the edges with non-zero markers
+    pub fn set_markers(&mut self, flag: bool) -> &mut Self {
+        self.markers = flag;
+        self
+    }
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Triangle {
+    /// Renders the triangles as an SVG document
+    ///
+    /// This function does not require Python; it is an alternative to `draw_triangles` for
+    /// environments without plotpy and matplotlib. The triangles are filled with the colors of
+    /// their attributes (see `set_colormap`); the middle nodes of quadratic triangles are not
+    /// connected, but their IDs are drawn with the other points.
+    pub fn to_svg(&self, options: &SvgOptions) -> Result<String, StrError> {
+        if self.ntriangle() < 1 {
+            return Err("there are no triangles to draw");
+        }
+        if options.width <= 2.0 * options.margin || options.margin < 0.0 || options.fontsize <= 0.0 {
+            return Err("the width must be greater than twice the margin and the font size must be positive");
+        }
+
+        // the y axis of SVG points downwards
+        let (mut min, mut max) = ([f64::MAX; 2], [f64::MIN; 2]);
+        for p in 0..self.npoint() {
+            for dim in 0..2 {
+                min[dim] = f64::min(min[dim], self.point(p, dim));
+                max[dim] = f64::max(max[dim], self.point(p, dim));
+            }
+        }
+        let range = f64::max(max[0] - min[0], max[1] - min[1]);
+        let scale = if range > 0.0 {
+            (options.width - 2.0 * options.margin) / range
+        } else {
+            1.0
+        };
+        let height = (max[1] - min[1]) * scale + 2.0 * options.margin;
+        let xy = |p: usize| {
+            (
+                options.margin + (self.point(p, 0) - min[0]) * scale,
+                height - options.margin - (self.point(p, 1) - min[1]) * scale,
+            )
+        };
+
+        // header
+        let mut buffer = String::new();
+        write!(
+            &mut buffer,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n\
+             <g stroke=\"black\" stroke-width=\"1\" stroke-linejoin=\"round\">\n",
+            options.width, height, options.width, height
+        )
+        .unwrap();
+
+        // triangles
+        for tri in 0..self.ntriangle() {
+            let color = self.colormap().color(self.triangle_attribute(tri) as i64);
+            let corners: Vec<String> = (0..3)
+                .map(|m| {
+                    let (x, y) = xy(self.triangle_node(tri, m));
+                    format!("{:.3},{:.3}", x, y)
+                })
+                .collect();
+            writeln!(
+                &mut buffer,
+                "<polygon points=\"{}\" fill=\"{}\"/>",
+                corners.join(" "),
+                color
+            )
+            .unwrap();
+        }
+        writeln!(&mut buffer, "</g>").unwrap();
+
+        // edges with markers
+        let mut labels = String::new();
+        if options.markers {
+            let colormap = ColorMap::dark();
+            writeln!(&mut buffer, "<g stroke-width=\"3\" stroke-linecap=\"round\">").unwrap();
+            for e in 0..self.nedge() {
+                let marker = self.edge_marker(e);
+                if marker == 0 {
+                    continue;
+                }
+                let color = colormap.color(marker as i64);
+                let (xa, ya) = xy(self.edge_point(e, 0));
+                let (xb, yb) = xy(self.edge_point(e, 1));
+                writeln!(
+                    &mut buffer,
+                    "<line x1=\"{:.3}\" y1=\"{:.3}\" x2=\"{:.3}\" y2=\"{:.3}\" stroke=\"{}\"/>",
+                    xa, ya, xb, yb, color
+                )
+                .unwrap();
+                label(
+                    &mut labels,
+                    (xa + xb) / 2.0,
+                    (ya + yb) / 2.0,
+                    color,
+                    &format!("({})", marker),
+                );
+            }
+            writeln!(&mut buffer, "</g>").unwrap();
+        }
+
+        // labels of the triangles
+        if options.triangle_ids || options.attribute_ids {
+            for tri in 0..self.ntriangle() {
+                let (mut xc, mut yc) = (0.0, 0.0);
+                for m in 0..3 {
+                    let (x, y) = xy(self.triangle_node(tri, m));
+                    xc += x / 3.0;
+                    yc += y / 3.0;
+                }
+                if options.triangle_ids {
+                    label(&mut labels, xc, yc, "blue", &format!("{}", tri));
+                }
+                if options.attribute_ids {
+                    // halfway between the centroid and the first corner (as in draw_triangles)
+                    let (x, y) = xy(self.triangle_node(tri, 0));
+                    let text = format!("[{}]", self.triangle_attribute(tri));
+                    label(&mut labels, (x + xc) / 2.0, (y + yc) / 2.0, "black", &text);
+                }
+            }
+        }
+
+        // labels of the points
+        if options.point_ids {
+            for p in 0..self.npoint() {
+                let (x, y) = xy(p);
+                label(&mut labels, x, y, "red", &format!("{}", p));
+            }
+        }
+
+        // footer
+        if !labels.is_empty() {
+            write!(
+                &mut buffer,
+                "<g font-family=\"sans-serif\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">\n{}</g>\n",
+                options.fontsize, labels
+            )
+            .unwrap();
+        }
+        writeln!(&mut buffer, "</svg>").unwrap();
+        Ok(buffer)
+    }
+
+    /// Writes the triangles to an SVG file
+    ///
+    /// See `to_svg` for the details.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    /// * `options` -- the drawing options
+    pub fn write_svg<P>(&self, full_path: &P, options: &SvgOptions) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let buffer = self.to_svg(options)?;
+
+        // create directory
+        let path = Path::new(full_path);
+        if let Some(p) = path.parent() {
+            fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+        }
+
+        // write file
+        let mut file = File::create(path).map_err(|_| "cannot create file")?;
+        file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+
+        // force sync
+        file.sync_all().map_err(|_| "cannot sync file")?;
+        Ok(())
+    }
+}
+
+/// Writes a text element centered at (x, y)
+fn label(buffer: &mut String, x: f64, y: f64, color: &str, text: &str) {
+    writeln!(
+        buffer,
+        "<text x=\"{:.3}\" y=\"{:.3}\" fill=\"{}\">{}</text>",
+        x, y, color, text
+    )
+    .unwrap();
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::SvgOptions;
+    use crate::{StrError, Triangle};
+    use std::fs;
+
+    #[test]
+    fn to_svg_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.to_svg(&SvgOptions::new()).err(),
+            Some("there are no triangles to draw")
+        );
+        let triangle = Triangle::generate_structured_rectangle(1, 1, (0.0, 0.0), (1.0, 1.0), [0; 4], false)?;
+        let mut options = SvgOptions::new();
+        options.set_width(40.0);
+        assert_eq!(
+            triangle.to_svg(&options).err(),
+            Some("the width must be greater than twice the margin and the font size must be positive")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_svg_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(2, 1, (0.0, 0.0), (2.0, 1.0), [-1, -2, -3, -4], false)?;
+        let mut options = SvgOptions::new();
+        options.set_width(240.0).set_margin(20.0);
+        let svg = triangle.to_svg(&options)?;
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"240\" height=\"140\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polygon").count(), 4);
+        assert_eq!(svg.matches("<line").count(), 0);
+        assert_eq!(svg.matches("<text").count(), 0);
+        // the point 0 at (0,0) maps to the lower-left corner
+        assert!(svg.contains("20.000,120.000"));
+        // the point 5 at (2,1) maps to the upper-right corner
+        assert!(svg.contains("220.000,20.000"));
+
+        options
+            .set_point_ids(true)
+            .set_triangle_ids(true)
+            .set_attribute_ids(true)
+            .set_markers(true);
+        let svg = triangle.to_svg(&options)?;
+        assert_eq!(svg.matches("<line").count(), 6);
+        assert_eq!(svg.matches("<text").count(), 6 + 4 + 4 + 6);
+        assert!(svg.contains(">(-3)</text>"));
+        assert!(svg.contains(">[0]</text>"));
+        Ok(())
+    }
+
+    #[test]
+    fn write_svg_works() -> Result<(), StrError> {
+        let triangle = Triangle::generate_structured_rectangle(3, 2, (0.0, 0.0), (3.0, 2.0), [-1, -2, -3, -4], true)?;
+        let mut options = SvgOptions::new();
+        options.set_point_ids(true).set_markers(true);
+        let full_path = "/tmp/tritet/triangle_write_svg_works.svg";
+        triangle.write_svg(full_path, &options)?;
+        let contents = fs::read_to_string(full_path).map_err(|_| "cannot read file")?;
+        assert_eq!(contents, triangle.to_svg(&options)?);
+        Ok(())
+    }
+}
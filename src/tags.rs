@@ -0,0 +1,181 @@
+use crate::{Mesh, StrError};
+
+/// Holds named integer tags of the cells of a mesh (e.g., material, phase, partition)
+///
+/// Each tag has one value per cell. The tags are kept by `Mesh::extract_cells_in` and
+/// `Mesh::append` and are written by `write_mesh_vtu`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CellTags {
+    names: Vec<String>,    // names of the tags
+    defaults: Vec<i64>,    // values of new cells (or missing in merged meshes)
+    values: Vec<Vec<i64>>, // values of each tag (ntag) for each cell (ncell)
+}
+
+impl CellTags {
+    /// Returns the names of the tags in the order they were added
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the values of a tag (one per cell) or None if the tag does not exist
+    pub fn values(&self, name: &str) -> Option<&[i64]> {
+        self.position(name).map(|t| self.values[t].as_slice())
+    }
+
+    /// Returns the index of a tag
+    fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Returns the tags of some cells
+    pub(crate) fn select(&self, cells: &[usize]) -> CellTags {
+        CellTags {
+            names: self.names.clone(),
+            defaults: self.defaults.clone(),
+            values: self
+                .values
+                .iter()
+                .enumerate()
+                .map(|(t, column)| {
+                    cells
+                        .iter()
+                        .map(|c| column.get(*c).copied().unwrap_or(self.defaults[t]))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Appends the tags of other cells; the tags are matched by name
+    pub(crate) fn append(&mut self, ncell: usize, other: &CellTags, other_ncell: usize) {
+        for (t, name) in other.names.iter().enumerate() {
+            if self.position(name).is_none() {
+                self.names.push(name.clone());
+                self.defaults.push(other.defaults[t]);
+                self.values.push(Vec::new());
+            }
+        }
+        for t in 0..self.names.len() {
+            let default = self.defaults[t];
+            self.values[t].resize(ncell, default);
+            let other_t = other.position(&self.names[t]);
+            for c in 0..other_ncell {
+                let value = match other_t {
+                    Some(ot) => other.values[ot].get(c).copied().unwrap_or(other.defaults[ot]),
+                    None => default,
+                };
+                self.values[t].push(value);
+            }
+        }
+    }
+}
+
+impl<const DIM: usize> Mesh<DIM> {
+    /// Adds a tag with a default value for all cells
+    pub fn add_tag(&mut self, name: &str, default: i64) -> Result<&mut Self, StrError> {
+        if self.tags.position(name).is_some() {
+            return Err("the tag already exists");
+        }
+        self.tags.names.push(name.to_string());
+        self.tags.defaults.push(default);
+        self.tags.values.push(vec![default; self.cells.len()]);
+        Ok(self)
+    }
+
+    /// Sets the value of a tag of a cell
+    pub fn set_tag(&mut self, cell: usize, name: &str, value: i64) -> Result<&mut Self, StrError> {
+        if cell >= self.cells.len() {
+            return Err("index of cell is out of bounds");
+        }
+        let t = self.tags.position(name).ok_or("the tag does not exist")?;
+        let column = &mut self.tags.values[t];
+        if column.len() < self.cells.len() {
+            column.resize(self.cells.len(), self.tags.defaults[t]);
+        }
+        column[cell] = value;
+        Ok(self)
+    }
+
+    /// Returns the value of a tag of a cell or None if the tag does not exist
+    pub fn tag(&self, cell: usize, name: &str) -> Option<i64> {
+        let t = self.tags.position(name)?;
+        if cell >= self.cells.len() {
+            return None;
+        }
+        Some(self.tags.values[t].get(cell).copied().unwrap_or(self.tags.defaults[t]))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::CellTags;
+    use crate::{Mesh, Region, TriMesh};
+
+    fn two_triangles() -> TriMesh {
+        Mesh {
+            points: vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            cells: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            attributes: vec![1, 2],
+            facets: Vec::new(),
+            facet_markers: Vec::new(),
+            tags: CellTags::default(),
+        }
+    }
+
+    #[test]
+    fn tags_capture_errors() {
+        let mut mesh = two_triangles();
+        mesh.add_tag("material", 0).unwrap();
+        assert_eq!(mesh.add_tag("material", 0).err(), Some("the tag already exists"));
+        assert_eq!(
+            mesh.set_tag(2, "material", 1).err(),
+            Some("index of cell is out of bounds")
+        );
+        assert_eq!(mesh.set_tag(0, "phase", 1).err(), Some("the tag does not exist"));
+        assert_eq!(mesh.tag(0, "phase"), None);
+        assert_eq!(mesh.tag(2, "material"), None);
+    }
+
+    #[test]
+    fn tags_work() {
+        let mut mesh = two_triangles();
+        mesh.add_tag("material", 7).unwrap().add_tag("partition", -1).unwrap();
+        mesh.set_tag(1, "material", 8).unwrap();
+        assert_eq!(mesh.tags.names(), &["material", "partition"]);
+        assert_eq!(mesh.tags.values("material"), Some(&[7, 8][..]));
+        assert_eq!(mesh.tag(0, "partition"), Some(-1));
+
+        // extraction
+        let region = Region::BBox {
+            min: [0.0, 0.5, 0.0],
+            max: [1.0, 1.0, 0.0],
+        };
+        let (sub, _) = mesh.extract_cells_in(&region);
+        assert_eq!(sub.tag(0, "material"), Some(8));
+        assert_eq!(sub.tags.values("partition"), Some(&[-1][..]));
+
+        // merging
+        let mut other = two_triangles();
+        other.add_tag("phase", 3).unwrap().add_tag("material", 0).unwrap();
+        other.set_tag(0, "phase", 4).unwrap();
+        mesh.append(&other);
+        assert_eq!(mesh.cells.len(), 4);
+        assert_eq!(mesh.tags.names(), &["material", "partition", "phase"]);
+        assert_eq!(mesh.tags.values("material"), Some(&[7, 8, 0, 0][..]));
+        assert_eq!(mesh.tags.values("partition"), Some(&[-1, -1, -1, -1][..]));
+        assert_eq!(mesh.tags.values("phase"), Some(&[3, 3, 4, 3][..]));
+    }
+
+    #[test]
+    fn tags_handle_cells_added_later() {
+        let mut mesh = two_triangles();
+        mesh.add_tag("material", 5).unwrap();
+        mesh.cells.push(vec![1, 2, 3]);
+        mesh.attributes.push(0);
+        assert_eq!(mesh.tag(2, "material"), Some(5));
+        mesh.set_tag(2, "material", 6).unwrap();
+        assert_eq!(mesh.tags.values("material"), Some(&[5, 5, 6][..]));
+    }
+}
@@ -1,8 +1,20 @@
+use crate::box_grid::BoxGrid;
+use crate::cancellation::run_with_cancellation;
+use crate::console::run_with_console;
 use crate::constants;
 use crate::conversion::to_i32;
-use crate::StrError;
-use plotpy::{Canvas, Plot, Text};
+use crate::jitter::{find_degenerate_points, jitter_offset, JITTER_MAX_ATTEMPTS};
+use crate::progress::run_with_progress;
+use crate::recorder::{option_to_string, Recorder};
+use crate::{
+    AffineMap, CancellationToken, ColorMap, ConsoleHandler, CsrGraph, ProgressHandler, SteinerOptions, StrError,
+    TetgenOptions,
+};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[repr(C)]
 pub(crate) struct ExtTetgen {
@@ -13,25 +25,57 @@ pub(crate) struct ExtTetgen {
 extern "C" {
     fn new_tetgen(npoint: i32, nfacet: i32, facet_npoint: *const i32, nregion: i32, nhole: i32) -> *mut ExtTetgen;
     fn drop_tetgen(tetgen: *mut ExtTetgen);
+    fn tet_clear_outputs(tetgen: *mut ExtTetgen);
     fn tet_set_point(tetgen: *mut ExtTetgen, index: i32, x: f64, y: f64, z: f64) -> i32;
+    fn tet_set_points(tetgen: *mut ExtTetgen, npoint: i32, coordinates: *const f64) -> i32;
+    fn tet_set_additional_points(tetgen: *mut ExtTetgen, npoint: i32, coordinates: *const f64) -> i32;
     fn tet_set_facet_point(tetgen: *mut ExtTetgen, index: i32, m: i32, p: i32) -> i32;
+    fn tet_set_facet_polygon(tetgen: *mut ExtTetgen, index: i32, polygon: i32, npoint: i32, points: *const i32) -> i32;
+    fn tet_set_facet_hole(tetgen: *mut ExtTetgen, index: i32, hole: i32, x: f64, y: f64, z: f64) -> i32;
+    fn tet_set_facet_marker(tetgen: *mut ExtTetgen, index: i32, marker: i32) -> i32;
     fn tet_set_region(
         tetgen: *mut ExtTetgen,
         index: i32,
         x: f64,
         y: f64,
         z: f64,
-        attribute: i32,
+        attribute: f64,
         max_volume: f64,
     ) -> i32;
     fn tet_set_hole(tetgen: *mut ExtTetgen, index: i32, x: f64, y: f64, z: f64) -> i32;
     fn tet_run_delaunay(tetgen: *mut ExtTetgen, verbose: i32) -> i32;
+    fn tet_run_cdt(tetgen: *mut ExtTetgen, verbose: i32) -> i32;
+    fn tet_run_voronoi(tetgen: *mut ExtTetgen, verbose: i32) -> i32;
     fn tet_run_tetrahedralize(
         tetgen: *mut ExtTetgen,
         verbose: i32,
         o2: i32,
         global_max_volume: f64,
-        global_min_angle: f64,
+        max_radius_edge_ratio: f64,
+        min_dihedral_angle: f64,
+        max_steiner_points: i32,
+        no_bisect: i32,
+    ) -> i32;
+    fn tet_run_refine(
+        tetgen: *mut ExtTetgen,
+        verbose: i32,
+        o2: i32,
+        max_volumes: *const f64,
+        global_max_volume: f64,
+        max_radius_edge_ratio: f64,
+        min_dihedral_angle: f64,
+        max_steiner_points: i32,
+        no_bisect: i32,
+    ) -> i32;
+    fn tet_run_given_mesh(
+        tetgen: *mut ExtTetgen,
+        verbose: i32,
+        o2: i32,
+        ntet: i32,
+        corners: *const i32,
+        nface: i32,
+        faces: *const i32,
+        face_markers: *const i32,
     ) -> i32;
     fn tet_get_npoint(tetgen: *mut ExtTetgen) -> i32;
     fn tet_get_ntetrahedron(tetgen: *mut ExtTetgen) -> i32;
@@ -39,6 +83,40 @@ extern "C" {
     fn tet_get_point(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
     fn tet_get_tetrahedron_corner(tetgen: *mut ExtTetgen, index: i32, corner: i32) -> i32;
     fn tet_get_tetrahedron_attribute(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_tetrahedron_attribute_f64(tetgen: *mut ExtTetgen, index: i32) -> f64;
+    fn tet_get_nmarked_face(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_marked_face_corner(tetgen: *mut ExtTetgen, index: i32, corner: i32) -> i32;
+    fn tet_get_point_original_index(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_marked_face_marker(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_voronoi_npoint(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_voronoi_point(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
+    fn tet_get_voronoi_nedge(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_voronoi_edge_point(tetgen: *mut ExtTetgen, index: i32, side: i32) -> i32;
+    fn tet_get_voronoi_edge_point_b_direction(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
+    fn tet_get_voronoi_nface(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_voronoi_face_cell(tetgen: *mut ExtTetgen, index: i32, side: i32) -> i32;
+    fn tet_get_voronoi_face_nedge(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_voronoi_face_edge(tetgen: *mut ExtTetgen, index: i32, m: i32) -> i32;
+    fn tet_get_voronoi_ncell(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_voronoi_cell_nface(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_voronoi_cell_face(tetgen: *mut ExtTetgen, index: i32, m: i32) -> i32;
+    fn tet_get_input_point(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
+    fn tet_get_input_hole(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
+    fn tet_get_input_region(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
+    fn tet_get_input_region_attribute(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_input_facet_npolygon(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_input_facet_polygon_npoint(tetgen: *mut ExtTetgen, index: i32, polygon: i32) -> i32;
+    fn tet_get_input_facet_polygon_point(tetgen: *mut ExtTetgen, index: i32, polygon: i32, m: i32) -> i32;
+}
+
+/// Holds the index of an endpoint on a Voronoi edge or the direction of the Voronoi edge (3D)
+#[derive(Clone, Debug)]
+pub enum VoronoiEdgePoint3D {
+    /// The index of the endpoint
+    Index(usize),
+
+    /// The direction of the infinite ray
+    Direction(f64, f64, f64),
 }
 
 /// Implements high-level functions to call Si's Tetgen Cpp-Code
@@ -50,7 +128,6 @@ extern "C" {
 /// ## Delaunay triangulation
 ///
 /// ```
-/// use plotpy::Plot;
 /// use tritet::{StrError, Tetgen};
 ///
 /// fn main() -> Result<(), StrError> {
@@ -70,8 +147,8 @@ extern "C" {
 ///     assert_eq!(tetgen.ntet(), 3);
 ///     assert_eq!(tetgen.npoint(), 5);
 ///
-///     // draw edges of tetrahedra
-///     let mut plot = Plot::new();
+///     // draw edges of tetrahedra (requires the "plot" feature)
+///     // let mut plot = plotpy::Plot::new();
 ///     // tetgen.draw_wireframe(&mut plot, true, true, true, false, None, None, None);
 ///     // plot.set_equal_axes(true)
 ///     //    .set_figure_size_points(600.0, 600.0)
@@ -85,7 +162,6 @@ extern "C" {
 /// ## Mesh generation
 ///
 /// ```
-/// use plotpy::Plot;
 /// use tritet::{StrError, Tetgen};
 ///
 /// fn main() -> Result<(), StrError> {
@@ -125,8 +201,8 @@ extern "C" {
 ///     assert_eq!(tetgen.ntet(), 12);
 ///     assert_eq!(tetgen.npoint(), 11);
 ///
-///     // draw edges of tetrahedra
-///     let mut plot = Plot::new();
+///     // draw edges of tetrahedra (requires the "plot" feature)
+///     // let mut plot = plotpy::Plot::new();
 ///     // tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
 ///     // plot.set_equal_axes(true)
 ///     //     .set_figure_size_points(600.0, 600.0)
@@ -136,20 +212,56 @@ extern "C" {
 /// ```
 ///
 /// ![doc_tetgen_mesh_1.svg](https://raw.githubusercontent.com/cpmech/tritet/main/data/figures/doc_tetgen_mesh_1.svg)
+///
+/// # Reuse
+///
+/// The generate methods (and `refine_mesh`) may be called multiple times on the same instance;
+/// e.g., to tune the maximum volume or the quality constraints. The input data (points, facets,
+/// regions, and holes) is kept and only the output of the previous call is released by the c-code.
+///
+/// # Threads
+///
+/// Tetgen is `Send`; thus, independent instances may be created and used in different threads
+/// (e.g., one instance per task in a thread pool). The instance is not `Sync` and cannot be
+/// shared by threads.
+///
+/// The generations run concurrently without any lock because the c-code keeps no shared mutable
+/// state: the data is owned by each instance and the global variables of the c-code (e.g., the
+/// random seed, the constants of the exact arithmetic, and the callbacks) are thread-local.
 pub struct Tetgen {
-    ext_tetgen: *mut ExtTetgen,       // data allocate by the c-code
-    npoint: usize,                    // number of points
-    facet_npoint: Option<Vec<usize>>, // number of points on each facet
-    total_facet_npoint: usize,        // total number of facet points
-    facet_point_set_count: usize,     // counts the number of facet point already set
-    nregion: Option<usize>,           // number of regions
-    nhole: Option<usize>,             // number of holes
-    all_points_set: bool,             // indicates that all points have been set
-    all_facets_set: bool,             // indicates that all facets have been set
-    all_regions_set: bool,            // indicates that all regions have been set
-    all_holes_set: bool,              // indicates that all holes have been set
+    ext_tetgen: *mut ExtTetgen,                              // data allocate by the c-code
+    npoint: usize,                                           // number of points
+    facet_npoint: Option<Vec<usize>>,                        // number of points on each facet
+    total_facet_npoint: usize,                               // total number of facet points
+    facet_point_set_count: usize,                            // counts the number of facet point already set
+    nregion: Option<usize>,                                  // number of regions
+    nhole: Option<usize>,                                    // number of holes
+    all_points_set: bool,                                    // indicates that all points have been set
+    all_facets_set: bool,                                    // indicates that all facets have been set
+    all_regions_set: bool,                                   // indicates that all regions have been set
+    all_holes_set: bool,                                     // indicates that all holes have been set
+    colormap: ColorMap,                                      // colors of the attributes used in drawings
+    marked_face_grid: RefCell<Option<BoxGrid>>,              // spatial index over marked faces (built on demand)
+    tet_neighbors: RefCell<Option<Vec<[Option<usize>; 4]>>>, // neighbors of the tetrahedra (built on demand)
+    vertex_to_cell: RefCell<Option<Arc<CsrGraph>>>,          // point-to-cell map (built on demand)
+    cell_to_cell: RefCell<Option<Arc<CsrGraph>>>,            // cell-to-cell adjacency (built on demand)
+    recorder: Option<Recorder>,                              // writes the calls to a script (see start_recording)
+    steiner: SteinerOptions, // constraints on the Steiner points inserted by generate_mesh
+    transform: Option<AffineMap<3>>, // maps the output coordinates to the global frame
+    jitter: Option<f64>,     // perturbation applied to the degenerate points (see set_jitter_on_degeneracy)
+    jittered: RefCell<Vec<usize>>, // points moved by the last call to a generate method
+    point_tags: Vec<u64>,    // user-supplied tags of the input points (see set_point_tag)
+    inserted_points: Vec<[f64; 3]>, // additional points inserted by generate_mesh (see insert_points)
+    progress: RefCell<Option<Box<dyn ProgressHandler>>>, // receives the phases of the generation (see set_progress_handler)
+    console: RefCell<Option<Box<dyn ConsoleHandler>>>,   // receives the console output (see set_console_handler)
+    cancellation: Option<CancellationToken>, // stops the generation if cancelled (see set_cancellation_token)
+    deadline: Cell<Option<Instant>>,         // stops the generation if reached (see generate_mesh_with_timeout)
 }
 
+// The data allocated by the c-code is owned by this instance only, thus the instance can be moved
+// to another thread. The instance is not Sync because the generate methods modify the c-data.
+unsafe impl Send for Tetgen {}
+
 impl Drop for Tetgen {
     /// Tells the c-code to release memory
     fn drop(&mut self) {
@@ -218,12 +330,29 @@ impl Tetgen {
                 all_facets_set: false,
                 all_regions_set: false,
                 all_holes_set: false,
+                colormap: ColorMap::dark(),
+                marked_face_grid: RefCell::new(None),
+                tet_neighbors: RefCell::new(None),
+                vertex_to_cell: RefCell::new(None),
+                cell_to_cell: RefCell::new(None),
+                recorder: None,
+                steiner: SteinerOptions::default(),
+                transform: None,
+                jitter: None,
+                jittered: RefCell::new(Vec::new()),
+                point_tags: vec![0; npoint],
+                inserted_points: Vec::new(),
+                progress: RefCell::new(None),
+                console: RefCell::new(None),
+                cancellation: None,
+                deadline: Cell::new(None),
             })
         }
     }
 
     /// Sets the point coordinates
     pub fn set_point(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_point {} {} {} {}", index, x, y, z))?;
         unsafe {
             let status = tet_set_point(self.ext_tetgen, to_i32(index), x, y, z);
             if status != constants::TRITET_SUCCESS {
@@ -247,6 +376,54 @@ impl Tetgen {
         Ok(self)
     }
 
+    /// Sets the coordinates of all points at once
+    ///
+    /// This function copies all coordinates to the c-code in a single call; thus, it is
+    /// much faster than calling `set_point` for each point of large point sets.
+    ///
+    /// # Input
+    ///
+    /// * `coordinates` -- the (x, y, z) coordinates of each point; the length must be equal to `npoint` (passed down to `new`)
+    pub fn set_points(&mut self, coordinates: &[[f64; 3]]) -> Result<&mut Self, StrError> {
+        if coordinates.len() != self.npoint {
+            return Err("the number of coordinates must be equal to npoint");
+        }
+        for (index, x) in coordinates.iter().enumerate() {
+            self.record(|| format!("set_point {} {} {} {}", index, x[0], x[1], x[2]))?;
+        }
+        unsafe {
+            let status = tet_set_points(self.ext_tetgen, to_i32(self.npoint), coordinates.as_ptr() as *const f64);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                    return Err("INTERNAL ERROR: found NULL point list");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        self.all_points_set = true;
+        Ok(self)
+    }
+
+    /// Sets a user-supplied tag of a point (e.g., a stable ID in an external database)
+    ///
+    /// The tags are carried to the output points (see `point_tag`). The default tag is zero.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the point and goes from 0 to `npoint` (passed down to `new`)
+    /// * `tag` -- is the tag of the point
+    pub fn set_point_tag(&mut self, index: usize, tag: u64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_point_tag {} {}", index, tag))?;
+        if index >= self.npoint {
+            return Err("index of point is out of bounds");
+        }
+        self.point_tags[index] = tag;
+        Ok(self)
+    }
+
     /// Sets the facet's point IDs
     ///
     /// # Input
@@ -255,6 +432,13 @@ impl Tetgen {
     /// * `m` -- is the local index of the point on the facet and goes from 0 to `facet_npoint`
     /// * `p` -- is the ID (index) of the point on the facet
     pub fn set_facet_point(&mut self, index: usize, m: usize, p: usize) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_facet_point {} {} {}", index, m, p))?;
+        self.put_facet_point(index, m, p)?;
+        Ok(self)
+    }
+
+    /// Sets the facet's point ID without recording the call (see `set_facet_point`)
+    fn put_facet_point(&mut self, index: usize, m: usize, p: usize) -> Result<(), StrError> {
         match &self.facet_npoint {
             Some(n) => n,
             None => return Err("cannot set facet point because facet_npoint is None"),
@@ -293,6 +477,154 @@ impl Tetgen {
         if self.facet_point_set_count == self.total_facet_npoint {
             self.all_facets_set = true;
         }
+        Ok(())
+    }
+
+    /// Sets a polygon of a facet (e.g., the outer boundary or the boundary of an opening)
+    ///
+    /// A facet may have several polygons lying on the same plane; e.g., the outer boundary and
+    /// the boundaries of openings. The first polygon (0) always exists and its number of points
+    /// is given by `facet_npoint` (see `new`); thus, setting polygon 0 is equivalent to calling
+    /// `set_facet_point` for all its points. The additional polygons must be set in order,
+    /// starting at 1. Setting an existing polygon replaces it.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new`)
+    /// * `polygon` -- is the index of the polygon and goes from 0 to the number of polygons of the facet
+    /// * `points` -- are the IDs of the points of the polygon
+    pub fn set_facet_polygon(&mut self, index: usize, polygon: usize, points: &[usize]) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            let ids: Vec<String> = points.iter().map(|p| format!("{}", p)).collect();
+            format!("set_facet_polygon {} {} {}", index, polygon, ids.join(","))
+        })?;
+        if self.facet_npoint.is_none() {
+            return Err("cannot set facet polygon because facet_npoint is None");
+        }
+        if points.is_empty() {
+            return Err("the facet polygon must have at least one point");
+        }
+        if polygon == 0 {
+            let npoint = match self.facet_npoint.as_ref().and_then(|n| n.get(index)) {
+                Some(n) => *n,
+                None => return Err("index of facet is out of bounds"),
+            };
+            if points.len() != npoint {
+                return Err("the first facet polygon must have facet_npoint points");
+            }
+            for (m, p) in points.iter().enumerate() {
+                self.put_facet_point(index, m, *p)?;
+            }
+            return Ok(self);
+        }
+        let points: Vec<i32> = points.iter().map(|p| to_i32(*p)).collect();
+        unsafe {
+            let status = tet_set_facet_polygon(
+                self.ext_tetgen,
+                to_i32(index),
+                to_i32(polygon),
+                to_i32(points.len()),
+                points.as_ptr(),
+            );
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
+                    return Err("INTERNAL ERROR: found NULL facet list");
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
+                    return Err("index of facet is out of bounds");
+                }
+                if status == constants::TRITET_ERROR_NULL_FACET_POLYGON_LIST {
+                    return Err("INTERNAL ERROR: found NULL facet polygon list");
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_NUM_POLYGON {
+                    return Err("the facet polygons must be set in order");
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_POINT_ID {
+                    return Err("id of facet point is out of bounds");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets a point inside an opening of a facet (a facet hole)
+    ///
+    /// The opening must be bounded by an additional polygon (see `set_facet_polygon`). The
+    /// holes must be set in order, starting at 0. Setting an existing hole replaces it.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new`)
+    /// * `hole` -- is the index of the hole of the facet
+    /// * `x` -- is the x-coordinate of the hole
+    /// * `y` -- is the y-coordinate of the hole
+    /// * `z` -- is the z-coordinate of the hole
+    pub fn set_facet_hole(&mut self, index: usize, hole: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_facet_hole {} {} {} {} {}", index, hole, x, y, z))?;
+        if self.facet_npoint.is_none() {
+            return Err("cannot set facet hole because facet_npoint is None");
+        }
+        unsafe {
+            let status = tet_set_facet_hole(self.ext_tetgen, to_i32(index), to_i32(hole), x, y, z);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
+                    return Err("INTERNAL ERROR: found NULL facet list");
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
+                    return Err("index of facet is out of bounds");
+                }
+                if status == constants::TRITET_ERROR_INVALID_HOLE_INDEX {
+                    return Err("the facet holes must be set in order (starting at 0)");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets the marker of a facet
+    ///
+    /// The marker is transferred to the boundary faces (marked faces) generated on the facet.
+    /// The default marker is zero.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new`)
+    /// * `marker` -- is the marker of the facet
+    pub fn set_facet_marker(&mut self, index: usize, marker: i32) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_facet_marker {} {}", index, marker))?;
+        match &self.facet_npoint {
+            Some(n) => n,
+            None => return Err("cannot set facet marker because facet_npoint is None"),
+        };
+        unsafe {
+            let status = tet_set_facet_marker(self.ext_tetgen, to_i32(index), marker);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
+                    return Err("INTERNAL ERROR: found NULL facet list");
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
+                    return Err("index of facet is out of bounds");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
         Ok(self)
     }
 
@@ -304,7 +636,7 @@ impl Tetgen {
     /// * `x` -- is the x-coordinate of the region
     /// * `y` -- is the y-coordinate of the region
     /// * `z` -- is the z-coordinate of the region
-    /// * `attribute` -- is the attribute ID to group the tetrahedra belonging to this region
+    /// * `attribute` -- is the attribute ID to group the tetrahedra belonging to this region (may be zero or negative)
     /// * `max_volume` -- is the maximum volume constraint for the tetrahedra belonging to this region
     pub fn set_region(
         &mut self,
@@ -312,7 +644,67 @@ impl Tetgen {
         x: f64,
         y: f64,
         z: f64,
-        attribute: usize,
+        attribute: i32,
+        max_volume: Option<f64>,
+    ) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            format!(
+                "set_region {} {} {} {} {} {}",
+                index,
+                x,
+                y,
+                z,
+                attribute,
+                option_to_string(&max_volume)
+            )
+        })?;
+        self.update_region(index, x, y, z, attribute as f64, max_volume)
+    }
+
+    /// Marks a region within the Piecewise Linear Complexes (PLCs) with a floating-point attribute
+    ///
+    /// This function is similar to `set_region`, but keeps fractional and negative attributes
+    /// (see `tet_attribute_f64`). Only one attribute per region is supported.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the region and goes from 0 to `nregion` (passed down to `new`)
+    /// * `x` -- is the x-coordinate of the region
+    /// * `y` -- is the y-coordinate of the region
+    /// * `z` -- is the z-coordinate of the region
+    /// * `attribute` -- is the attribute to group the tetrahedra belonging to this region
+    /// * `max_volume` -- is the maximum volume constraint for the tetrahedra belonging to this region
+    pub fn set_region_f64(
+        &mut self,
+        index: usize,
+        x: f64,
+        y: f64,
+        z: f64,
+        attribute: f64,
+        max_volume: Option<f64>,
+    ) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            format!(
+                "set_region_f64 {} {} {} {} {} {}",
+                index,
+                x,
+                y,
+                z,
+                attribute,
+                option_to_string(&max_volume)
+            )
+        })?;
+        self.update_region(index, x, y, z, attribute, max_volume)
+    }
+
+    /// Sets the region data in the C structure (see `set_region` and `set_region_f64`)
+    fn update_region(
+        &mut self,
+        index: usize,
+        x: f64,
+        y: f64,
+        z: f64,
+        attribute: f64,
         max_volume: Option<f64>,
     ) -> Result<&mut Self, StrError> {
         let nregion = match self.nregion {
@@ -324,15 +716,7 @@ impl Tetgen {
             None => -1.0,
         };
         unsafe {
-            let status = tet_set_region(
-                self.ext_tetgen,
-                to_i32(index),
-                x,
-                y,
-                z,
-                to_i32(attribute),
-                volume_constraint,
-            );
+            let status = tet_set_region(self.ext_tetgen, to_i32(index), x, y, z, attribute, volume_constraint);
             if status != constants::TRITET_SUCCESS {
                 if status == constants::TRITET_ERROR_NULL_DATA {
                     return Err("INTERNAL ERROR: found NULL data");
@@ -363,6 +747,7 @@ impl Tetgen {
     /// * `y` -- is the y-coordinate of the hole
     /// * `z` -- is the z-coordinate of the hole
     pub fn set_hole(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_hole {} {} {} {}", index, x, y, z))?;
         let nhole = match self.nhole {
             Some(n) => n,
             None => return Err("cannot set hole because the number of holes is None"),
@@ -390,17 +775,160 @@ impl Tetgen {
         Ok(self)
     }
 
+    /// Releases the output data (mesh and Voronoi tessellation) but keeps the input data
+    pub fn clear_outputs(&mut self) -> Result<&mut Self, StrError> {
+        self.record(|| "clear_outputs".to_string())?;
+        unsafe {
+            tet_clear_outputs(self.ext_tetgen);
+        }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        self.jittered.borrow_mut().clear();
+        Ok(self)
+    }
+
+    /// Inserts additional points into the mesh (e.g., sensor locations)
+    ///
+    /// The points are appended to the list of additional points, which are inserted by Tetgen
+    /// (switch `-i`) into the mesh generated by `generate_mesh` and `generate_mesh_with`. The
+    /// constraints (facets, regions, and holes) are preserved; thus, to add points to an existing
+    /// mesh, call this function and then generate the mesh again. The points outside the domain
+    /// (or coinciding with existing points) are ignored by Tetgen. Tetgen may also add Steiner
+    /// points on the boundary to recover the facets. The other generate methods and `refine_mesh`
+    /// disregard the additional points.
+    ///
+    /// # Input
+    ///
+    /// * `points` -- the (x, y, z) coordinates of the points to be inserted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Tetgen};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+    ///     tetgen
+    ///         .set_point(0, 0.0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0, 0.0)?
+    ///         .set_point(3, 0.0, 0.0, 1.0)?;
+    ///     for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+    ///         for (m, p) in face.iter().enumerate() {
+    ///             tetgen.set_facet_point(index, m, *p)?;
+    ///         }
+    ///     }
+    ///     tetgen.generate_mesh(false, false, None, None)?;
+    ///     assert_eq!(tetgen.ntet(), 1);
+    ///
+    ///     // insert a point in the interior and regenerate
+    ///     tetgen.insert_points(&[(0.2, 0.2, 0.2)])?;
+    ///     tetgen.generate_mesh(false, false, None, None)?;
+    ///     assert_eq!(tetgen.npoint(), 5);
+    ///     assert_eq!(tetgen.ntet(), 4);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn insert_points(&mut self, points: &[(f64, f64, f64)]) -> Result<&mut Self, StrError> {
+        for (x, y, z) in points {
+            self.record(|| format!("insert_point {} {} {}", x, y, z))?;
+        }
+        self.inserted_points.extend(points.iter().map(|(x, y, z)| [*x, *y, *z]));
+        self.update_inserted_points()?;
+        Ok(self)
+    }
+
+    /// Clears the additional points (see `insert_points`)
+    pub fn clear_inserted_points(&mut self) -> Result<&mut Self, StrError> {
+        self.record(|| "clear_inserted_points".to_string())?;
+        self.inserted_points.clear();
+        self.update_inserted_points()?;
+        Ok(self)
+    }
+
+    /// Returns the additional points (see `insert_points`)
+    pub fn inserted_points(&self) -> &[[f64; 3]] {
+        &self.inserted_points
+    }
+
+    /// Copies the additional points to the c-code
+    fn update_inserted_points(&mut self) -> Result<(), StrError> {
+        unsafe {
+            let status = tet_set_additional_points(
+                self.ext_tetgen,
+                to_i32(self.inserted_points.len()),
+                self.inserted_points.as_ptr() as *const f64,
+            );
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        Ok(())
+    }
+
     /// Generates a Delaunay triangulation
     ///
     /// # Input
     ///
     /// * `verbose` -- Prints Tetgen's messages to the console
     pub fn generate_delaunay(&self, verbose: bool) -> Result<(), StrError> {
+        self.record(|| format!("generate_delaunay {}", verbose))?;
         if !self.all_points_set {
             return Err("cannot generate Delaunay tetrahedralization because not all points are set");
         }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        self.run_with_jitter(|| {
+            unsafe {
+                let status = self.with_handlers(|| tet_run_delaunay(self.ext_tetgen, if verbose { 1 } else { 0 }))?;
+                if status != constants::TRITET_SUCCESS {
+                    if status == constants::TRITET_ERROR_NULL_DATA {
+                        return Err("INTERNAL ERROR: found NULL data");
+                    }
+                    if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                        return Err("INTERNAL ERROR: found NULL point list");
+                    }
+                    return Err("INTERNAL ERROR: some error occurred");
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Generates a constrained Delaunay tetrahedralization of the PLC without refinement
+    ///
+    /// Only the facets are recovered (switch `p` without `q` and `a`); thus, the input points are
+    /// kept exactly and no quality or volume constraints are applied. Tetgen may still insert
+    /// Steiner points when the facets cannot be recovered otherwise (e.g., in non-convex PLCs).
+    /// The regions (attributes) and holes are considered.
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Tetgen's messages to the console
+    pub fn generate_cdt(&self, verbose: bool) -> Result<(), StrError> {
+        self.record(|| format!("generate_cdt {}", verbose))?;
+        if !self.all_points_set {
+            return Err("cannot generate mesh of tetrahedra because not all points are set");
+        }
+        if !self.all_facets_set {
+            return Err("cannot generate mesh of tetrahedra because not all facets are set");
+        }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
         unsafe {
-            let status = tet_run_delaunay(self.ext_tetgen, if verbose { 1 } else { 0 });
+            let status = self.with_handlers(|| tet_run_cdt(self.ext_tetgen, if verbose { 1 } else { 0 }))?;
             if status != constants::TRITET_SUCCESS {
                 if status == constants::TRITET_ERROR_NULL_DATA {
                     return Err("INTERNAL ERROR: found NULL data");
@@ -408,12 +936,46 @@ impl Tetgen {
                 if status == constants::TRITET_ERROR_NULL_POINT_LIST {
                     return Err("INTERNAL ERROR: found NULL point list");
                 }
+                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
+                    return Err("INTERNAL ERROR: list of facets must be defined first");
+                }
                 return Err("INTERNAL ERROR: some error occurred");
             }
         }
         Ok(())
     }
 
+    /// Generates a Voronoi tessellation and Delaunay triangulation
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Tetgen's messages to the console
+    pub fn generate_voronoi(&self, verbose: bool) -> Result<(), StrError> {
+        self.record(|| format!("generate_voronoi {}", verbose))?;
+        if !self.all_points_set {
+            return Err("cannot generate Voronoi tessellation because not all points are set");
+        }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        self.run_with_jitter(|| {
+            unsafe {
+                let status = self.with_handlers(|| tet_run_voronoi(self.ext_tetgen, if verbose { 1 } else { 0 }))?;
+                if status != constants::TRITET_SUCCESS {
+                    if status == constants::TRITET_ERROR_NULL_DATA {
+                        return Err("INTERNAL ERROR: found NULL data");
+                    }
+                    if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                        return Err("INTERNAL ERROR: found NULL point list");
+                    }
+                    return Err("INTERNAL ERROR: some error occurred");
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Generates a conforming constrained Delaunay triangulation with some quality constraints
     ///
     /// # Input
@@ -421,7 +983,9 @@ impl Tetgen {
     /// * `verbose` -- Prints Tetgen's messages to the console
     /// * `o2` -- Generates the middle nodes; e.g., nnode = 10
     /// * `global_max_volume` -- The maximum volume constraint for all generated tetrahedra
-    /// * `global_min_angle` -- The minimum angle constraint is given in degrees (the default minimum angle is TODO degrees)
+    /// * `global_min_angle` -- The minimum dihedral angle constraint is given in degrees (the default minimum angle is five degrees)
+    ///
+    /// **Note:** Use `generate_mesh_with` to also constrain the radius-edge ratio of the tetrahedra.
     pub fn generate_mesh(
         &self,
         verbose: bool,
@@ -429,28 +993,79 @@ impl Tetgen {
         global_volume_area: Option<f64>,
         global_min_angle: Option<f64>,
     ) -> Result<(), StrError> {
+        self.record(|| {
+            format!(
+                "generate_mesh {} {} {} {}",
+                verbose,
+                o2,
+                option_to_string(&global_volume_area),
+                option_to_string(&global_min_angle)
+            )
+        })?;
+        self.run_generate_mesh(&TetgenOptions {
+            verbose,
+            quadratic: o2,
+            max_volume: global_volume_area,
+            max_radius_edge_ratio: None,
+            min_dihedral_angle: global_min_angle,
+            steiner: self.steiner,
+            enforce_min_dihedral_angle: false,
+        })
+    }
+
+    /// Generates a conforming constrained Delaunay triangulation with the given options
+    ///
+    /// **Note:** The Steiner options given to `set_steiner_options` are ignored; `options.steiner` is used instead.
+    pub fn generate_mesh_with(&self, options: &TetgenOptions) -> Result<(), StrError> {
+        self.record(|| {
+            format!(
+                "generate_mesh_with {} {} {} {} {} {} {} {} {}",
+                options.verbose,
+                options.quadratic,
+                option_to_string(&options.max_volume),
+                option_to_string(&options.max_radius_edge_ratio),
+                option_to_string(&options.min_dihedral_angle),
+                option_to_string(&options.steiner.max_steiner_points),
+                options.steiner.prohibit_steiner_on_boundary,
+                options.steiner.prohibit_steiner_in_interior,
+                options.enforce_min_dihedral_angle
+            )
+        })?;
+        self.run_generate_mesh(options)
+    }
+
+    /// Runs Tetgen to generate a mesh
+    fn run_generate_mesh(&self, options: &TetgenOptions) -> Result<(), StrError> {
         if !self.all_points_set {
             return Err("cannot generate mesh of tetrahedra because not all points are set");
         }
         if !self.all_facets_set {
             return Err("cannot generate mesh of tetrahedra because not all facets are set");
         }
-        let max_volume = match global_volume_area {
-            Some(v) => v,
-            None => 0.0,
-        };
-        let min_angle = match global_min_angle {
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let max_volume = match options.max_volume {
             Some(v) => v,
             None => 0.0,
         };
+        let max_radius_edge_ratio = options.max_radius_edge_ratio.unwrap_or(0.0);
+        let min_dihedral_angle = options.min_dihedral_angle.unwrap_or(0.0);
+        let (max_steiner_points, no_bisect) = options.steiner.to_c();
         unsafe {
-            let status = tet_run_tetrahedralize(
-                self.ext_tetgen,
-                if verbose { 1 } else { 0 },
-                if o2 { 1 } else { 0 },
-                max_volume,
-                min_angle,
-            );
+            let status = self.with_handlers(|| {
+                tet_run_tetrahedralize(
+                    self.ext_tetgen,
+                    if options.verbose { 1 } else { 0 },
+                    if options.quadratic { 1 } else { 0 },
+                    max_volume,
+                    max_radius_edge_ratio,
+                    min_dihedral_angle,
+                    max_steiner_points,
+                    no_bisect,
+                )
+            })?;
             if status != constants::TRITET_SUCCESS {
                 if status == constants::TRITET_ERROR_NULL_DATA {
                     return Err("INTERNAL ERROR: found NULL data");
@@ -467,6 +1082,89 @@ impl Tetgen {
                 return Err("INTERNAL ERROR: some error occurred");
             }
         }
+        if options.enforce_min_dihedral_angle {
+            self.check_min_dihedral_angle(options.min_dihedral_angle)?;
+        }
+        Ok(())
+    }
+
+    /// Refines the current mesh with a volume constraint for each tetrahedron
+    ///
+    /// The output of the previous call to `generate_delaunay`, `generate_mesh` (or `refine_mesh`)
+    /// is fed back to Tetgen (switch `-r`); thus, the refined mesh conforms to the previous one.
+    /// The attributes and marked faces of the previous mesh are kept. The middle nodes of
+    /// quadratic tetrahedra are discarded and regenerated if `options.quadratic` is true.
+    ///
+    /// # Input
+    ///
+    /// * `per_cell_max_volume` -- The maximum volume of the tetrahedra generated from each
+    ///   tetrahedron of the current mesh (len = ntet); zero or negative values mean no constraint
+    /// * `options` -- The options of the refinement; `options.max_volume` is also applied to all tetrahedra
+    pub fn refine_mesh(&self, per_cell_max_volume: &[f64], options: &TetgenOptions) -> Result<(), StrError> {
+        self.record(|| {
+            let volumes: Vec<String> = per_cell_max_volume.iter().map(|v| format!("{}", v)).collect();
+            format!(
+                "refine_mesh {} {} {} {} {} {} {} {} {} {}",
+                volumes.join(","),
+                options.verbose,
+                options.quadratic,
+                option_to_string(&options.max_volume),
+                option_to_string(&options.max_radius_edge_ratio),
+                option_to_string(&options.min_dihedral_angle),
+                option_to_string(&options.steiner.max_steiner_points),
+                options.steiner.prohibit_steiner_on_boundary,
+                options.steiner.prohibit_steiner_in_interior,
+                options.enforce_min_dihedral_angle
+            )
+        })?;
+        let ntet = self.ntet();
+        if ntet == 0 {
+            return Err("cannot refine mesh because there is no mesh yet");
+        }
+        if per_cell_max_volume.len() != ntet {
+            return Err("per_cell_max_volume.len() must be equal to ntet");
+        }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let max_volume = options.max_volume.unwrap_or(0.0);
+        let max_radius_edge_ratio = options.max_radius_edge_ratio.unwrap_or(0.0);
+        let min_dihedral_angle = options.min_dihedral_angle.unwrap_or(0.0);
+        let (max_steiner_points, no_bisect) = options.steiner.to_c();
+        unsafe {
+            let status = self.with_handlers(|| {
+                tet_run_refine(
+                    self.ext_tetgen,
+                    if options.verbose { 1 } else { 0 },
+                    if options.quadratic { 1 } else { 0 },
+                    per_cell_max_volume.as_ptr(),
+                    max_volume,
+                    max_radius_edge_ratio,
+                    min_dihedral_angle,
+                    max_steiner_points,
+                    no_bisect,
+                )
+            })?;
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_MESH {
+                    return Err("INTERNAL ERROR: found NULL mesh");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                if status == constants::TRITET_ERROR_STRING_CONCAT {
+                    return Err("INTERNAL ERROR: cannot write string with commands for Tetgen");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        if options.enforce_min_dihedral_angle {
+            self.check_min_dihedral_angle(options.min_dihedral_angle)?;
+        }
         Ok(())
     }
 
@@ -495,8 +1193,60 @@ impl Tetgen {
     /// # Warning
     ///
     /// This function will return 0.0 if either `index` or `dim` are out of range.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any (see `set_output_transform`).
     pub fn point(&self, index: usize, dim: usize) -> f64 {
-        unsafe { tet_get_point(self.ext_tetgen, to_i32(index), to_i32(dim)) }
+        match &self.transform {
+            None => unsafe { tet_get_point(self.ext_tetgen, to_i32(index), to_i32(dim)) },
+            Some(map) => {
+                if index >= self.npoint() || dim > 2 {
+                    return 0.0;
+                }
+                let x = unsafe {
+                    [
+                        tet_get_point(self.ext_tetgen, to_i32(index), 0),
+                        tet_get_point(self.ext_tetgen, to_i32(index), 1),
+                        tet_get_point(self.ext_tetgen, to_i32(index), 2),
+                    ]
+                };
+                map.apply(&x)[dim]
+            }
+        }
+    }
+
+    /// Returns the tag of an output point (see `set_point_tag`)
+    ///
+    /// The output points corresponding to input points have the tag given to `set_point_tag`.
+    /// The points generated by Tetgen (e.g., Steiner and middle points) have the tag zero.
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if `index` is out of range.
+    pub fn point_tag(&self, index: usize) -> u64 {
+        match self.point_original_index(index) {
+            Some(p) => self.point_tags[p],
+            None => 0,
+        }
+    }
+
+    /// Returns the index of the input point corresponding to an output point
+    ///
+    /// The correspondence is recorded by the generate methods using Tetgen's own numbering; thus,
+    /// it is exact even if the coordinates coincide or are perturbed (see
+    /// `set_jitter_on_degeneracy`). Tetgen keeps the input points as the first output points but
+    /// removes the duplicated and unused ones, renumbering the remaining points; the correspondence
+    /// follows this renumbering, also after `refine_mesh`. The points generated by Tetgen (e.g.,
+    /// Steiner and middle points) and the additional points (see `insert_points`) have no
+    /// corresponding input point.
+    ///
+    /// Returns None if `index` is out of range or the point has no corresponding input point.
+    pub fn point_original_index(&self, index: usize) -> Option<usize> {
+        let original = unsafe { tet_get_point_original_index(self.ext_tetgen, to_i32(index)) };
+        if original < 0 {
+            None
+        } else {
+            Some(original as usize)
+        }
     }
 
     /// Returns the ID of a tetrahedron's node
@@ -551,140 +1301,816 @@ impl Tetgen {
     /// # Warning
     ///
     /// This function will return 0 if either `index` is out of range.
-    pub fn tet_attribute(&self, index: usize) -> usize {
-        unsafe { tet_get_tetrahedron_attribute(self.ext_tetgen, to_i32(index)) as usize }
+    pub fn tet_attribute(&self, index: usize) -> i32 {
+        unsafe { tet_get_tetrahedron_attribute(self.ext_tetgen, to_i32(index)) }
     }
 
-    /// Draws wireframe representing the edges of tetrahedra
-    pub fn draw_wireframe(
-        &self,
-        plot: &mut Plot,
-        set_range: bool,
-        with_point_ids: bool,
-        with_triangle_ids: bool,
-        with_attribute_ids: bool,
-        fontsize_point_ids: Option<f64>,
-        fontsize_triangle_ids: Option<f64>,
-        fontsize_attribute_ids: Option<f64>,
-    ) {
-        let ntet = self.ntet();
-        if ntet < 1 {
-            return;
-        }
-        let mut canvas = Canvas::new();
-        let mut point_ids = Text::new();
-        let mut tetrahedron_ids = Text::new();
-        let mut attribute_ids = Text::new();
-        if with_point_ids {
-            point_ids
-                .set_color("red")
-                .set_align_horizontal("center")
-                .set_align_vertical("center")
-                .set_bbox(true)
-                .set_bbox_facecolor("white")
-                .set_bbox_alpha(0.8)
-                .set_bbox_style("circle");
-            if let Some(fsz) = fontsize_point_ids {
-                point_ids.set_fontsize(fsz);
-            }
-        }
-        if with_triangle_ids {
-            tetrahedron_ids
-                .set_color("blue")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_triangle_ids {
-                tetrahedron_ids.set_fontsize(fsz);
-            }
-        }
-        if with_attribute_ids {
-            attribute_ids
-                .set_color("black")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_attribute_ids {
-                attribute_ids.set_fontsize(fsz);
-            }
-        }
-        const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
-        let mut x = vec![0.0; 3];
-        let mut xa = vec![0.0; 3];
-        let mut xb = vec![0.0; 3];
-        let mut xcen = vec![0.0; 3];
-        let mut xatt = vec![0.0; 3];
-        let mut min = vec![f64::MAX; 3];
-        let mut max = vec![f64::MIN; 3];
-        let mut colors: HashMap<usize, &'static str> = HashMap::new();
-        let mut index_color = 0;
-        let clr = constants::DARK_COLORS;
-        for tet in 0..ntet {
-            let attribute = self.tet_attribute(tet);
-            let color = match colors.get(&attribute) {
-                Some(c) => c,
-                None => {
-                    let c = clr[index_color % clr.len()];
-                    colors.insert(attribute, c);
-                    index_color += 1;
-                    c
+    /// Returns the attribute of a tetrahedron as given to `set_region_f64` (or `set_region`)
+    ///
+    /// **Note:** Tetgen stores a single regional attribute per region; thus, each tetrahedron has
+    /// exactly one attribute (the one of the region containing it).
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if either `index` is out of range.
+    pub fn tet_attribute_f64(&self, index: usize) -> f64 {
+        unsafe { tet_get_tetrahedron_attribute_f64(self.ext_tetgen, to_i32(index)) }
+    }
+
+    /// Returns the tetrahedron sharing a face with a tetrahedron
+    ///
+    /// The face `m` is opposite to the node `m` of the tetrahedron (see `tet_node`). The
+    /// neighbors are found from the corners of the tetrahedra on the first call after the
+    /// mesh is generated; thus, subsequent calls are fast.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the tetrahedron and goes from 0 to `ntet`
+    /// * `m` -- is the local index of the face: 0, 1, 2, or 3
+    ///
+    /// # Output
+    ///
+    /// Returns the index of the neighbor or None if the face is on the boundary
+    /// (or if either `index` or `m` are out of range).
+    pub fn tet_neighbor(&self, index: usize, m: usize) -> Option<usize> {
+        let mut neighbors = self.tet_neighbors.borrow_mut();
+        let neighbors = neighbors.get_or_insert_with(|| {
+            let ntet = self.ntet();
+            let mut faces: HashMap<[usize; 3], (usize, usize)> = HashMap::new();
+            let mut neighbors = vec![[None; 4]; ntet];
+            for tet in 0..ntet {
+                let v: Vec<usize> = (0..4).map(|k| self.tet_node(tet, k)).collect();
+                for opposite in 0..4 {
+                    let mut key = [0; 3];
+                    for (k, n) in (0..4).filter(|n| *n != opposite).enumerate() {
+                        key[k] = v[n];
+                    }
+                    key.sort();
+                    if let Some((other, other_opposite)) = faces.remove(&key) {
+                        neighbors[tet][opposite] = Some(other);
+                        neighbors[other][other_opposite] = Some(tet);
+                    } else {
+                        faces.insert(key, (tet, opposite));
+                    }
                 }
-            };
-            canvas.set_edge_color(color);
-            for dim in 0..3 {
-                xcen[dim] = 0.0;
             }
-            for m in 0..4 {
-                let p = self.tet_node(tet, m);
-                for dim in 0..3 {
-                    x[dim] = self.point(p, dim);
-                    min[dim] = f64::min(min[dim], x[dim]);
-                    max[dim] = f64::max(max[dim], x[dim]);
-                    xcen[dim] += x[dim] / 4.0;
+            neighbors
+        });
+        if index < neighbors.len() && m < 4 {
+            neighbors[index][m]
+        } else {
+            None
+        }
+    }
+
+    /// Returns the point-to-cell map in compressed sparse row (CSR) format
+    ///
+    /// The tetrahedra sharing the point `p` are `adjncy[xadj[p]..xadj[p + 1]]` (sorted); i.e.,
+    /// this is `node_to_cells` in CSR format, as required by the assembly of finite element
+    /// matrices. The map is computed once and cached until the mesh is generated again; thus,
+    /// the returned pointer shares the cached map (cloning it is cheap).
+    pub fn vertex_to_cell_map(&self) -> Arc<CsrGraph> {
+        self.vertex_to_cell
+            .borrow_mut()
+            .get_or_insert_with(|| Arc::new(CsrGraph::from_lists(self.node_to_cells())))
+            .clone()
+    }
+
+    /// Returns the cell-to-cell adjacency (the tetrahedra sharing a face) in CSR format
+    ///
+    /// This is `cell_adjacency_csr` computed once and cached until the mesh is generated again;
+    /// thus, the returned pointer shares the cached adjacency (cloning it is cheap).
+    pub fn cell_to_cell_adjacency(&self) -> Arc<CsrGraph> {
+        self.cell_to_cell
+            .borrow_mut()
+            .get_or_insert_with(|| Arc::new(self.cell_adjacency_csr()))
+            .clone()
+    }
+
+    /// Returns the number of marked faces (boundary faces) generated by `generate_mesh`
+    pub fn nmarked_face(&self) -> usize {
+        unsafe { tet_get_nmarked_face(self.ext_tetgen) as usize }
+    }
+
+    /// Returns the ID of a marked face's node
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the marked face and goes from 0 to `nmarked_face`
+    /// * `m` -- is the local index of the node and goes from 0 to 3 (the middle nodes are not available)
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` or `m` are out of range.
+    pub fn marked_face_node(&self, index: usize, m: usize) -> usize {
+        unsafe { tet_get_marked_face_corner(self.ext_tetgen, to_i32(index), to_i32(m)) as usize }
+    }
+
+    /// Returns the marker of a marked face (given by the facet marker)
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if `index` is out of range.
+    pub fn marked_face_marker(&self, index: usize) -> i32 {
+        unsafe { tet_get_marked_face_marker(self.ext_tetgen, to_i32(index)) }
+    }
+
+    /// Returns the number of points of the Voronoi tessellation
+    pub fn voronoi_npoint(&self) -> usize {
+        unsafe { tet_get_voronoi_npoint(self.ext_tetgen) as usize }
+    }
+
+    /// Returns the x-y-z coordinates of a point on the Voronoi tessellation
+    ///
+    /// The Voronoi points are the circumcenters of the tetrahedra; i.e., the Voronoi point `index`
+    /// corresponds to the tetrahedron `index`.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the point and goes from 0 to `voronoi_npoint`
+    /// * `dim` -- is the space dimension index: 0, 1, or 2
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if either `index` or `dim` are out of range.
+    pub fn voronoi_point(&self, index: usize, dim: usize) -> f64 {
+        match &self.transform {
+            None => unsafe { tet_get_voronoi_point(self.ext_tetgen, to_i32(index), to_i32(dim)) },
+            Some(_) => {
+                if index >= self.voronoi_npoint() || dim > 2 {
+                    return 0.0;
                 }
+                let x = unsafe {
+                    [
+                        tet_get_voronoi_point(self.ext_tetgen, to_i32(index), 0),
+                        tet_get_voronoi_point(self.ext_tetgen, to_i32(index), 1),
+                        tet_get_voronoi_point(self.ext_tetgen, to_i32(index), 2),
+                    ]
+                };
+                self.transform(x)[dim]
+            }
+        }
+    }
+
+    /// Returns the number of edges on the Voronoi tessellation
+    pub fn voronoi_nedge(&self) -> usize {
+        unsafe { tet_get_voronoi_nedge(self.ext_tetgen) as usize }
+    }
+
+    /// Returns the index of the first endpoint on a Voronoi edge
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `voronoi_nedge`
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` is out of range.
+    pub fn voronoi_edge_point_a(&self, index: usize) -> usize {
+        unsafe { tet_get_voronoi_edge_point(self.ext_tetgen, to_i32(index), 0) as usize }
+    }
+
+    /// Returns the index of the second endpoint on a Voronoi edge or the direction of the Voronoi edge
+    ///
+    /// The direction of an infinite ray is a unit vector.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `voronoi_nedge`
+    ///
+    /// # Warning
+    ///
+    /// This function will return Index(0) if either `index` is out of range.
+    pub fn voronoi_edge_point_b(&self, index: usize) -> VoronoiEdgePoint3D {
+        unsafe {
+            let index_i32 = to_i32(index);
+            let id = tet_get_voronoi_edge_point(self.ext_tetgen, index_i32, 1);
+            if id == -1 {
+                let v = [
+                    tet_get_voronoi_edge_point_b_direction(self.ext_tetgen, index_i32, 0),
+                    tet_get_voronoi_edge_point_b_direction(self.ext_tetgen, index_i32, 1),
+                    tet_get_voronoi_edge_point_b_direction(self.ext_tetgen, index_i32, 2),
+                ];
+                let [x, y, z] = self.transform_linear(v);
+                VoronoiEdgePoint3D::Direction(x, y, z)
+            } else {
+                VoronoiEdgePoint3D::Index(id as usize)
             }
-            for (ma, mb) in &EDGES {
-                let a = self.tet_node(tet, *ma);
-                let b = self.tet_node(tet, *mb);
-                for dim in 0..3 {
-                    xa[dim] = self.point(a, dim);
-                    xb[dim] = self.point(b, dim);
+        }
+    }
+
+    /// Returns the number of faces on the Voronoi tessellation
+    ///
+    /// Each Voronoi face corresponds to an edge of the Delaunay triangulation.
+    pub fn voronoi_nface(&self) -> usize {
+        unsafe { tet_get_voronoi_nface(self.ext_tetgen) as usize }
+    }
+
+    /// Returns one of the two Voronoi cells sharing a Voronoi face
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the face and goes from 0 to `voronoi_nface`
+    /// * `side` -- is 0 or 1
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` or `side` are out of range.
+    pub fn voronoi_face_cell(&self, index: usize, side: usize) -> usize {
+        unsafe { tet_get_voronoi_face_cell(self.ext_tetgen, to_i32(index), to_i32(side)) as usize }
+    }
+
+    /// Returns the indices of the Voronoi edges (including the rays) of a Voronoi face
+    ///
+    /// The faces corresponding to Delaunay edges on the convex hull are unbounded (not closed).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the face and goes from 0 to `voronoi_nface`
+    ///
+    /// # Warning
+    ///
+    /// This function will return an empty list if `index` is out of range.
+    pub fn voronoi_face_edges(&self, index: usize) -> Vec<usize> {
+        unsafe {
+            let index_i32 = to_i32(index);
+            let nedge = tet_get_voronoi_face_nedge(self.ext_tetgen, index_i32);
+            (0..nedge)
+                .map(|m| tet_get_voronoi_face_edge(self.ext_tetgen, index_i32, m) as usize)
+                .collect()
+        }
+    }
+
+    /// Returns the number of cells on the Voronoi tessellation
+    ///
+    /// Each Voronoi cell corresponds to a point of the Delaunay triangulation.
+    pub fn voronoi_ncell(&self) -> usize {
+        unsafe { tet_get_voronoi_ncell(self.ext_tetgen) as usize }
+    }
+
+    /// Returns the indices of the Voronoi faces of a Voronoi cell
+    ///
+    /// The cells corresponding to points on the convex hull are unbounded.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the cell (and point) and goes from 0 to `voronoi_ncell`
+    ///
+    /// # Warning
+    ///
+    /// This function will return an empty list if `index` is out of range.
+    pub fn voronoi_cell_faces(&self, index: usize) -> Vec<usize> {
+        unsafe {
+            let index_i32 = to_i32(index);
+            let nface = tet_get_voronoi_cell_nface(self.ext_tetgen, index_i32);
+            (0..nface)
+                .map(|m| tet_get_voronoi_cell_face(self.ext_tetgen, index_i32, m) as usize)
+                .collect()
+        }
+    }
+
+    /// Returns the marked faces located within an axis-aligned bounding box
+    ///
+    /// A face is selected if all its corners are inside (or on the boundary of) the box.
+    /// An internal spatial index over the marked faces is built on the first call; thus,
+    /// subsequent calls only visit the faces near the box.
+    ///
+    /// # Input
+    ///
+    /// * `min` -- the (x, y, z) coordinates of the minimum corner of the box
+    /// * `max` -- the (x, y, z) coordinates of the maximum corner of the box
+    ///
+    /// # Output
+    ///
+    /// Returns the sorted indices of the marked faces (see `marked_face_node`).
+    pub fn marked_faces_in_bbox(&self, min: &[f64; 3], max: &[f64; 3]) -> Vec<usize> {
+        let nface = self.nmarked_face();
+        let coords = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let mut grid = self.marked_face_grid.borrow_mut();
+        let grid = grid.get_or_insert_with(|| {
+            let boxes = (0..nface)
+                .map(|face| {
+                    let mut lo = [f64::MAX; 3];
+                    let mut hi = [f64::MIN; 3];
+                    for m in 0..3 {
+                        let x = coords(self.marked_face_node(face, m));
+                        for i in 0..3 {
+                            lo[i] = f64::min(lo[i], x[i]);
+                            hi[i] = f64::max(hi[i], x[i]);
+                        }
+                    }
+                    (lo, hi)
+                })
+                .collect::<Vec<_>>();
+            BoxGrid::new(&boxes)
+        });
+        grid.candidates(min, max)
+            .into_iter()
+            .filter(|face| {
+                (0..3).all(|m| {
+                    let x = coords(self.marked_face_node(*face, m));
+                    (0..3).all(|i| x[i] >= min[i] && x[i] <= max[i])
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the number of points given to `new` and whether all of them have been set
+    pub(crate) fn input_npoint(&self) -> (usize, bool) {
+        (self.npoint, self.all_points_set)
+    }
+
+    /// Returns the x-y-z coordinates of a point given to `set_point` (disregarding the output transform)
+    pub(crate) fn input_point(&self, index: usize) -> [f64; 3] {
+        unsafe {
+            [
+                tet_get_input_point(self.ext_tetgen, to_i32(index), 0),
+                tet_get_input_point(self.ext_tetgen, to_i32(index), 1),
+                tet_get_input_point(self.ext_tetgen, to_i32(index), 2),
+            ]
+        }
+    }
+
+    /// Returns the number of facets given to `new` and whether all of them have been set
+    pub(crate) fn input_nfacet(&self) -> (usize, bool) {
+        match &self.facet_npoint {
+            Some(facets) => (facets.len(), self.all_facets_set),
+            None => (0, true),
+        }
+    }
+
+    /// Returns the point IDs of the polygons of a facet (see `set_facet_point` and `set_facet_polygon`)
+    pub(crate) fn input_facet_polygons(&self, index: usize) -> Vec<Vec<usize>> {
+        unsafe {
+            let index = to_i32(index);
+            let npolygon = tet_get_input_facet_npolygon(self.ext_tetgen, index);
+            (0..npolygon)
+                .map(|polygon| {
+                    let npoint = tet_get_input_facet_polygon_npoint(self.ext_tetgen, index, polygon);
+                    (0..npoint)
+                        .map(|m| tet_get_input_facet_polygon_point(self.ext_tetgen, index, polygon, m) as usize)
+                        .collect()
+                })
+                .collect()
+        }
+    }
+
+    /// Returns the number of holes given to `new`
+    pub(crate) fn input_nhole(&self) -> usize {
+        self.nhole.unwrap_or(0)
+    }
+
+    /// Returns the number of regions given to `new`
+    pub(crate) fn input_nregion(&self) -> usize {
+        self.nregion.unwrap_or(0)
+    }
+
+    /// Returns the x-y-z coordinates of a hole seed given to `set_hole`
+    pub(crate) fn input_hole(&self, index: usize) -> (f64, f64, f64) {
+        unsafe {
+            let x = tet_get_input_hole(self.ext_tetgen, to_i32(index), 0);
+            let y = tet_get_input_hole(self.ext_tetgen, to_i32(index), 1);
+            let z = tet_get_input_hole(self.ext_tetgen, to_i32(index), 2);
+            let [x, y, z] = self.transform([x, y, z]);
+            (x, y, z)
+        }
+    }
+
+    /// Generates the output from the given tetrahedra and marked faces (without refinement)
+    ///
+    /// The tetrahedra must be positively oriented and the faces must be on the boundary.
+    pub(crate) fn generate_given_mesh(
+        &self,
+        verbose: bool,
+        o2: bool,
+        corners: &[[usize; 4]],
+        faces: &[[usize; 3]],
+        face_markers: &[i32],
+    ) -> Result<(), StrError> {
+        if !self.all_points_set {
+            return Err("cannot generate mesh of tetrahedra because not all points are set");
+        }
+        self.marked_face_grid.replace(None);
+        self.tet_neighbors.replace(None);
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        self.jittered.borrow_mut().clear();
+        let corners_i32: Vec<i32> = corners.iter().flatten().map(|p| to_i32(*p)).collect();
+        let faces_i32: Vec<i32> = faces.iter().flatten().map(|p| to_i32(*p)).collect();
+        unsafe {
+            let status = self.with_handlers(|| {
+                tet_run_given_mesh(
+                    self.ext_tetgen,
+                    if verbose { 1 } else { 0 },
+                    if o2 { 1 } else { 0 },
+                    to_i32(corners.len()),
+                    corners_i32.as_ptr(),
+                    to_i32(faces.len()),
+                    faces_i32.as_ptr(),
+                    face_markers.as_ptr(),
+                )
+            })?;
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                    return Err("INTERNAL ERROR: found NULL point list");
                 }
-                canvas.polyline_3d_begin();
-                canvas.polyline_3d_add(xa[0], xa[1], xa[2]);
-                canvas.polyline_3d_add(xb[0], xb[1], xb[2]);
-                canvas.polyline_3d_end();
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
             }
-            if with_triangle_ids {
-                tetrahedron_ids.draw_3d(xcen[0], xcen[1], xcen[2], format!("{}", tet).as_str());
+        }
+        Ok(())
+    }
+
+    /// Returns the x-y-z coordinates and the attribute of a region seed given to `set_region`
+    pub(crate) fn input_region(&self, index: usize) -> (f64, f64, f64, i32) {
+        unsafe {
+            let x = tet_get_input_region(self.ext_tetgen, to_i32(index), 0);
+            let y = tet_get_input_region(self.ext_tetgen, to_i32(index), 1);
+            let z = tet_get_input_region(self.ext_tetgen, to_i32(index), 2);
+            let attribute = tet_get_input_region_attribute(self.ext_tetgen, to_i32(index));
+            let [x, y, z] = self.transform([x, y, z]);
+            (x, y, z, attribute)
+        }
+    }
+
+    /// Sets an affine map applied to the output coordinates (e.g., to place a part in a global frame)
+    ///
+    /// The map is applied lazily by `point` and the functions using it (e.g., drawing and
+    /// writing functions). The coordinates stored by Tetgen are not modified.
+    pub fn set_output_transform(&mut self, map: AffineMap<3>) -> &mut Self {
+        self.transform = Some(map);
+        self.marked_face_grid.replace(None);
+        self
+    }
+
+    /// Removes the affine map applied to the output coordinates
+    pub fn clear_output_transform(&mut self) -> &mut Self {
+        self.transform = None;
+        self.marked_face_grid.replace(None);
+        self
+    }
+
+    /// Applies the output transform to a point
+    fn transform(&self, x: [f64; 3]) -> [f64; 3] {
+        match &self.transform {
+            Some(map) => map.apply(&x),
+            None => x,
+        }
+    }
+
+    /// Applies the linear part of the output transform to a direction
+    fn transform_linear(&self, v: [f64; 3]) -> [f64; 3] {
+        match &self.transform {
+            Some(map) => map.apply_linear(&v),
+            None => v,
+        }
+    }
+
+    /// Starts writing the calls to the set and generate functions to a script file
+    ///
+    /// The script can be replayed with `replay` (e.g., to reproduce a crash in the c-code).
+    /// This function should be called right after `new` because previous calls are not recorded.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn start_recording<P>(&mut self, full_path: &P) -> Result<&mut Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let facet_npoint = match &self.facet_npoint {
+            Some(list) => list.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+            None => "None".to_string(),
+        };
+        let first_line = format!(
+            "new_tetgen {} {} {} {}",
+            self.npoint,
+            facet_npoint,
+            option_to_string(&self.nregion),
+            option_to_string(&self.nhole)
+        );
+        self.recorder = Some(Recorder::new(full_path, first_line)?);
+        Ok(self)
+    }
+
+    /// Writes a call to the script file if recording (see `start_recording`)
+    fn record<F>(&self, call: F) -> Result<(), StrError>
+    where
+        F: FnOnce() -> String,
+    {
+        match &self.recorder {
+            Some(recorder) => recorder.write(call()),
+            None => Ok(()),
+        }
+    }
+
+    /// Enables the perturbation of the degenerate points by `generate_delaunay`
+    ///
+    /// If the output misses some input points (e.g., duplicated points) or has no cells at all (e.g.,
+    /// coplanar points), the offending points are moved by at most `eps` along each direction
+    /// and the generation is repeated (up to five times). The perturbation is deterministic.
+    /// The moved points are reported by `jittered_points`. Use `None` to disable (the default).
+    ///
+    /// The input is not modified: the coordinates of the moved points are restored after the
+    /// generation; thus, only the output coordinates of these points differ from the input.
+    pub fn set_jitter_on_degeneracy(&mut self, eps: Option<f64>) -> &mut Self {
+        if let Some(recorder) = &self.recorder {
+            // errors are ignored here and will be reported by the next recorded call
+            let _ = recorder.write(format!("set_jitter_on_degeneracy {}", option_to_string(&eps)));
+        }
+        self.jitter = eps;
+        self
+    }
+
+    /// Sets a handler receiving the phases of the next generations and their elapsed times
+    ///
+    /// The handler is called at the end of each phase of the generate methods (and `refine_mesh`)
+    /// with the wall-clock time spent in the phase and since the beginning of the generation (see
+    /// `GenerationPhase`). The phases not executed are not reported; e.g., `generate_delaunay`
+    /// reports the input conversion, Delaunay, and output conversion phases only. The handler is
+    /// called by the thread running the generation.
+    ///
+    /// **Note:** A panic of the handler does not unwind through the c-code; it is caught, the
+    /// handler is not called again, and the panic is resumed when the generation returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{Progress, StrError, Tetgen};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut tetgen = Tetgen::new(4, None, None, None)?;
+    ///     tetgen
+    ///         .set_point(0, 0.0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0, 0.0)?
+    ///         .set_point(3, 0.0, 0.0, 1.0)?;
+    ///     tetgen.set_progress_handler(|p: &Progress| {
+    ///         println!("{:?} finished in {:?} (total = {:?})", p.phase, p.phase_time, p.total_time)
+    ///     });
+    ///     tetgen.generate_delaunay(false)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_progress_handler<H>(&mut self, handler: H) -> &mut Self
+    where
+        H: ProgressHandler + 'static,
+    {
+        self.progress.replace(Some(Box::new(handler)));
+        self
+    }
+
+    /// Removes the handler set by `set_progress_handler`
+    pub fn clear_progress_handler(&mut self) -> &mut Self {
+        self.progress.replace(None);
+        self
+    }
+
+    /// Sets a handler receiving the console output of Tetgen instead of stdout
+    ///
+    /// The text printed by the c-code during the next generations (e.g., the messages enabled by
+    /// `verbose` and the warnings) is passed to the handler line by line, without the newline
+    /// character. The handler may forward the lines to a logger or a writer; e.g.,
+    /// `|line: &str| log::info!("{}", line)`. The handler is called by the thread running the
+    /// generation; thus, other instances are not affected.
+    ///
+    /// **Note:** A panic of the handler does not unwind through the c-code; it is caught, the
+    /// handler is not called again, and the panic is resumed when the generation returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Tetgen};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut tetgen = Tetgen::new(4, None, None, None)?;
+    ///     tetgen
+    ///         .set_point(0, 0.0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0, 0.0)?
+    ///         .set_point(3, 0.0, 0.0, 1.0)?;
+    ///     tetgen.set_console_handler(|line: &str| eprintln!("tetgen: {}", line));
+    ///     tetgen.generate_delaunay(true)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_console_handler<H>(&mut self, handler: H) -> &mut Self
+    where
+        H: ConsoleHandler + 'static,
+    {
+        self.console.replace(Some(Box::new(handler)));
+        self
+    }
+
+    /// Removes the handler set by `set_console_handler` (the output goes to stdout again)
+    pub fn clear_console_handler(&mut self) -> &mut Self {
+        self.console.replace(None);
+        self
+    }
+
+    /// Sets a token to cancel the next generations from another thread
+    ///
+    /// The token is checked periodically by Tetgen (e.g., while inserting points or creating
+    /// tetrahedra). If the token is cancelled, the generate methods (and `refine_mesh`) stop and
+    /// return the error "mesh generation cancelled"; the output is empty but the input is kept.
+    /// Thus, the instance may be used again after resetting the token. Use `None` to remove the token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{CancellationToken, StrError, Tetgen};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut tetgen = Tetgen::new(4, None, None, None)?;
+    ///     tetgen
+    ///         .set_point(0, 0.0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0, 0.0)?
+    ///         .set_point(3, 0.0, 0.0, 1.0)?;
+    ///     let token = CancellationToken::new();
+    ///     tetgen.set_cancellation_token(Some(token.clone()));
+    ///     let handle = std::thread::spawn(move || tetgen.generate_delaunay(false));
+    ///     token.cancel(); // e.g., when the user presses a button
+    ///     let result = handle.join().unwrap();
+    ///     assert!(result.is_ok() || result == Err("mesh generation cancelled"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) -> &mut Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Generates a mesh with the given options and stops if it takes longer than `timeout`
+    ///
+    /// This function is equivalent to `generate_mesh_with` but returns the error "mesh generation
+    /// timed out" if the time limit is reached. The time is checked periodically by Tetgen; thus,
+    /// the function may return slightly after the time limit. See also `set_cancellation_token`.
+    pub fn generate_mesh_with_timeout(&self, options: &TetgenOptions, timeout: Duration) -> Result<(), StrError> {
+        self.deadline.set(Some(Instant::now() + timeout));
+        let result = self.generate_mesh_with(options);
+        self.deadline.set(None);
+        result
+    }
+
+    /// Runs a call to the c-code passing the phases and the console output to the handlers (if any)
+    ///
+    /// Returns an error if the generation is cancelled, timed out, or fails in Tetgen; otherwise,
+    /// returns the status.
+    fn with_handlers<F>(&self, run: F) -> Result<i32, StrError>
+    where
+        F: FnOnce() -> i32,
+    {
+        let mut progress = self.progress.borrow_mut();
+        let mut console = self.console.borrow_mut();
+        let progress_handler: Option<&mut dyn ProgressHandler> = match progress.as_mut() {
+            Some(h) => Some(h.as_mut()),
+            None => None,
+        };
+        let console_handler: Option<&mut dyn ConsoleHandler> = match console.as_mut() {
+            Some(h) => Some(h.as_mut()),
+            None => None,
+        };
+        let status = run_with_cancellation(self.cancellation.as_ref(), self.deadline.get(), || {
+            run_with_console(console_handler, || run_with_progress(progress_handler, run))
+        })?;
+        if status == constants::TRITET_ERROR_CANCELLED {
+            return Err("mesh generation cancelled");
+        }
+        if status == constants::TRITET_ERROR_ALLOC {
+            return Err("Tetgen ran out of memory");
+        }
+        if status == constants::TRITET_ERROR_TETGEN_FAILED {
+            return Err("Tetgen failed to generate the mesh (e.g., the facets intersect each other)");
+        }
+        Ok(status)
+    }
+
+    /// Returns the (sorted) IDs of the points moved by the last generation (see `set_jitter_on_degeneracy`)
+    pub fn jittered_points(&self) -> Vec<usize> {
+        self.jittered.borrow().clone()
+    }
+
+    /// Runs a generator and removes the degeneracies by jittering the points (if enabled)
+    fn run_with_jitter<F>(&self, run: F) -> Result<(), StrError>
+    where
+        F: Fn() -> Result<(), StrError>,
+    {
+        self.jittered.borrow_mut().clear();
+        let eps = match self.jitter {
+            Some(eps) => eps,
+            None => return run(),
+        };
+        // the original coordinates of the moved points (restored after the generation)
+        let mut originals: Vec<(usize, [f64; 3])> = Vec::new();
+        let mut result = Err("cannot remove the degeneracy by jittering the points");
+        for attempt in 0..=JITTER_MAX_ATTEMPTS {
+            if let Err(e) = run() {
+                result = Err(e);
+                break;
+            }
+            let points = self.degenerate_points();
+            if points.is_empty() {
+                let mut jittered = self.jittered.borrow_mut();
+                jittered.sort();
+                jittered.dedup();
+                result = Ok(());
+                break;
             }
-            if with_attribute_ids {
-                for dim in 0..3 {
-                    x[dim] = self.point(self.tet_node(tet, 0), dim);
-                    xatt[dim] = (x[dim] + xcen[dim]) / 2.0;
+            if attempt == JITTER_MAX_ATTEMPTS {
+                break;
+            }
+            for p in &points {
+                let index = to_i32(*p);
+                unsafe {
+                    let x = [
+                        tet_get_input_point(self.ext_tetgen, index, 0),
+                        tet_get_input_point(self.ext_tetgen, index, 1),
+                        tet_get_input_point(self.ext_tetgen, index, 2),
+                    ];
+                    if originals.iter().all(|(q, _)| q != p) {
+                        originals.push((*p, x));
+                    }
+                    tet_set_point(
+                        self.ext_tetgen,
+                        index,
+                        x[0] + eps * jitter_offset(*p, 0, attempt),
+                        x[1] + eps * jitter_offset(*p, 1, attempt),
+                        x[2] + eps * jitter_offset(*p, 2, attempt),
+                    );
                 }
-                attribute_ids.draw_3d(xatt[0], xatt[1], xatt[2], format!("[{}]", attribute).as_str());
             }
+            self.jittered.borrow_mut().extend(points);
         }
-        if with_point_ids {
-            for p in 0..self.npoint() {
-                let x = self.point(p, 0);
-                let y = self.point(p, 1);
-                let z = self.point(p, 2);
-                point_ids.draw_3d(x, y, z, format!("{}", p).as_str());
+        for (p, x) in originals {
+            unsafe {
+                tet_set_point(self.ext_tetgen, to_i32(p), x[0], x[1], x[2]);
             }
         }
-        plot.add(&canvas);
-        if with_triangle_ids {
-            plot.add(&tetrahedron_ids);
-        }
-        if with_point_ids {
-            plot.add(&point_ids);
-        }
-        if with_attribute_ids {
-            plot.add(&attribute_ids);
+        result
+    }
+
+    /// Returns the input points missing from the output tetrahedra (the coordinates are not transformed)
+    fn degenerate_points(&self) -> Vec<usize> {
+        unsafe {
+            let input: Vec<[f64; 3]> = (0..to_i32(self.npoint))
+                .map(|index| {
+                    [
+                        tet_get_input_point(self.ext_tetgen, index, 0),
+                        tet_get_input_point(self.ext_tetgen, index, 1),
+                        tet_get_input_point(self.ext_tetgen, index, 2),
+                    ]
+                })
+                .collect();
+            let mut used = vec![false; tet_get_npoint(self.ext_tetgen) as usize];
+            for cell in 0..tet_get_ntetrahedron(self.ext_tetgen) {
+                for m in 0..4 {
+                    used[tet_get_tetrahedron_corner(self.ext_tetgen, cell, m) as usize] = true;
+                }
+            }
+            let used_output: Vec<[f64; 3]> = (0..to_i32(used.len()))
+                .filter(|index| used[*index as usize])
+                .map(|index| {
+                    [
+                        tet_get_point(self.ext_tetgen, index, 0),
+                        tet_get_point(self.ext_tetgen, index, 1),
+                        tet_get_point(self.ext_tetgen, index, 2),
+                    ]
+                })
+                .collect();
+            find_degenerate_points(&input, &used_output)
         }
-        if set_range {
-            plot.set_range_3d(min[0], max[0], min[1], max[1], min[2], max[2]);
+    }
+
+    /// Sets the constraints on the Steiner points inserted by `generate_mesh` (the default is no constraint)
+    pub fn set_steiner_options(&mut self, options: SteinerOptions) -> &mut Self {
+        if let Some(recorder) = &self.recorder {
+            // errors are ignored here and will be reported by the next recorded call
+            let _ = recorder.write(format!(
+                "set_steiner_options {} {} {}",
+                option_to_string(&options.max_steiner_points),
+                options.prohibit_steiner_on_boundary,
+                options.prohibit_steiner_in_interior
+            ));
         }
+        self.steiner = options;
+        self
+    }
+
+    /// Sets the colors of the attributes used by `draw_wireframe` (the default is `ColorMap::dark()`)
+    pub fn set_colormap(&mut self, colormap: ColorMap) -> &mut Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Returns the colors of the attributes used by `draw_wireframe`
+    pub fn colormap(&self) -> &ColorMap {
+        &self.colormap
     }
 }
 
@@ -693,8 +2119,8 @@ impl Tetgen {
 #[cfg(test)]
 mod tests {
     use super::Tetgen;
-    use crate::{write_tet_vtu, StrError};
-    use plotpy::Plot;
+    use crate::fixtures::{cube, CUBE_FACETS};
+    use crate::{StrError, TetgenOptions};
 
     #[test]
     fn new_captures_some_errors() {
@@ -734,6 +2160,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_points_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.set_points(&[[0.0, 0.0, 0.0]]).err(),
+            Some("the number of coordinates must be equal to npoint")
+        );
+        tetgen.set_points(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.point(1, 0), 1.0);
+        assert_eq!(tetgen.point(3, 2), 1.0);
+        Ok(())
+    }
+
     #[test]
     fn set_facet_point_captures_some_errors() -> Result<(), StrError> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
@@ -757,6 +2198,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_facet_marker_captures_some_errors() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.set_facet_marker(0, -1).err(),
+            Some("cannot set facet marker because facet_npoint is None")
+        );
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        assert_eq!(
+            tetgen.set_facet_marker(4, -1).err(),
+            Some("index of facet is out of bounds")
+        );
+        Ok(())
+    }
+
     #[test]
     fn set_region_captures_some_errors() -> Result<(), StrError> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
@@ -810,38 +2266,108 @@ mod tests {
         Ok(())
     }
 
-    #[test]
-    fn generate_delaunay_works() -> Result<(), StrError> {
-        let mut tetgen = Tetgen::new(4, None, None, None)?;
-        tetgen
-            .set_point(0, 0.0, 0.0, 0.0)?
-            .set_point(1, 1.0, 0.0, 0.0)?
-            .set_point(2, 0.0, 1.0, 0.0)?
-            .set_point(3, 0.0, 0.0, 1.0)?;
-        tetgen.generate_delaunay(false)?;
-        assert_eq!(tetgen.ntet(), 1);
-        assert_eq!(tetgen.npoint(), 4);
+    #[test]
+    fn generate_delaunay_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.npoint(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_cdt_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(8, Some(vec![4, 4, 4, 4, 4, 4]), None, None)?;
+        assert_eq!(
+            tetgen.generate_cdt(false).err(),
+            Some("cannot generate mesh of tetrahedra because not all points are set")
+        );
+        let points = [
+            [0.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [2.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [2.0, 0.0, 1.0],
+            [2.0, 1.0, 1.0],
+            [0.0, 1.0, 1.0],
+        ];
+        tetgen.set_points(&points)?;
+        assert_eq!(
+            tetgen.generate_cdt(false).err(),
+            Some("cannot generate mesh of tetrahedra because not all facets are set")
+        );
+        for (index, face) in CUBE_FACETS.iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, -(index as i32) - 1)?;
+        }
+        tetgen.generate_cdt(false)?;
+
+        // no Steiner points and the input points are kept
+        assert_eq!(tetgen.npoint(), 8);
+        for (p, x) in points.iter().enumerate() {
+            assert_eq!([tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)], *x);
+        }
+        let volume: f64 = tetgen.to_mesh().measures().iter().sum();
+        assert!((volume - 2.0).abs() < 1e-14);
+        assert_eq!(tetgen.nmarked_face(), 12);
+
+        // the quality mesh adds Steiner points
+        tetgen.generate_mesh(false, false, Some(0.1), None)?;
+        assert!(tetgen.npoint() > 8);
         Ok(())
     }
 
     #[test]
-    fn draw_wireframe_works() -> Result<(), StrError> {
+    fn generate_voronoi_works() -> Result<(), StrError> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.generate_voronoi(false).err(),
+            Some("cannot generate Voronoi tessellation because not all points are set")
+        );
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
             .set_point(1, 1.0, 0.0, 0.0)?
             .set_point(2, 0.0, 1.0, 0.0)?
             .set_point(3, 0.0, 0.0, 1.0)?;
-        tetgen.generate_delaunay(false)?;
+        tetgen.generate_voronoi(false)?;
         assert_eq!(tetgen.ntet(), 1);
-        assert_eq!(tetgen.npoint(), 4);
-        let mut plot = Plot::new();
-        tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/tetgen_draw_wireframe_works.svg")?;
-        }
+        assert_eq!(tetgen.voronoi_npoint(), 1);
+        assert_eq!(tetgen.voronoi_point(0, 0), 0.5);
+        assert_eq!(tetgen.voronoi_point(0, 1), 0.5);
+        assert_eq!(tetgen.voronoi_point(0, 2), 0.5);
+        assert_eq!(tetgen.voronoi_nedge(), 4);
+        assert_eq!(tetgen.voronoi_edge_point_a(0), 0);
+        assert_eq!(
+            format!("{:?}", tetgen.voronoi_edge_point_b(0)),
+            "Direction(0.0, -1.0, 0.0)"
+        );
+        assert_eq!(
+            format!("{:?}", tetgen.voronoi_edge_point_b(3)),
+            "Direction(0.5773502691896258, 0.5773502691896258, 0.5773502691896258)"
+        );
+        assert_eq!(tetgen.voronoi_nface(), 6);
+        assert_eq!(tetgen.voronoi_face_cell(0, 0), 0);
+        assert_eq!(tetgen.voronoi_face_cell(0, 1), 1);
+        assert_eq!(tetgen.voronoi_face_edges(0), &[1, 0]);
+        assert_eq!(tetgen.voronoi_face_edges(5), &[3, 2]);
+        assert_eq!(tetgen.voronoi_face_edges(6), &[] as &[usize]);
+        assert_eq!(tetgen.voronoi_ncell(), 4);
+        assert_eq!(tetgen.voronoi_cell_faces(0), &[1, 4, 0]);
+        assert_eq!(tetgen.voronoi_cell_faces(3), &[5, 1, 2]);
+        assert_eq!(tetgen.voronoi_cell_faces(4), &[] as &[usize]);
+
+        // generate again (the previous output is released)
+        tetgen.generate_voronoi(false)?;
+        assert_eq!(tetgen.voronoi_npoint(), 1);
+        assert_eq!(tetgen.voronoi_nface(), 6);
         Ok(())
     }
 
@@ -860,13 +2386,119 @@ mod tests {
         tetgen.generate_delaunay(false)?;
         assert_eq!(tetgen.ntet(), 6);
         assert_eq!(tetgen.npoint(), 8);
-        let mut plot = Plot::new();
-        tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/tetgen_test_delaunay_1.svg")?;
+        #[cfg(feature = "plot")]
+        {
+            let mut plot = plotpy::Plot::new();
+            tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
+            if false {
+                plot.set_equal_axes(true)
+                    .set_figure_size_points(600.0, 600.0)
+                    .save("/tmp/tritet/tetgen_test_delaunay_1.svg")?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn marked_faces_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 1.0, 0.0)?
+            .set_point(1, 0.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 1.0)?;
+        tetgen
+            .set_facet_point(0, 0, 0)?
+            .set_facet_point(0, 1, 2)?
+            .set_facet_point(0, 2, 1)?
+            .set_facet_marker(0, -10)?;
+        tetgen
+            .set_facet_point(1, 0, 0)?
+            .set_facet_point(1, 1, 1)?
+            .set_facet_point(1, 2, 3)?
+            .set_facet_marker(1, -20)?;
+        tetgen
+            .set_facet_point(2, 0, 0)?
+            .set_facet_point(2, 1, 3)?
+            .set_facet_point(2, 2, 2)?
+            .set_facet_marker(2, -30)?;
+        tetgen
+            .set_facet_point(3, 0, 1)?
+            .set_facet_point(3, 1, 2)?
+            .set_facet_point(3, 2, 3)?
+            .set_facet_marker(3, -40)?;
+        assert_eq!(tetgen.nmarked_face(), 0);
+        assert_eq!(tetgen.marked_faces_in_bbox(&[-1.0; 3], &[2.0; 3]), &[] as &[usize]);
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        let nface = tetgen.nmarked_face();
+        assert!(nface > 4);
+        let mut markers: Vec<_> = (0..nface).map(|f| tetgen.marked_face_marker(f)).collect();
+        markers.sort();
+        markers.dedup();
+        assert_eq!(markers, &[-40, -30, -20, -10]);
+        assert_eq!(tetgen.marked_face_marker(nface), 0);
+        assert_eq!(tetgen.marked_face_node(nface, 0), 0);
+
+        // all faces
+        let all = tetgen.marked_faces_in_bbox(&[-1.0; 3], &[2.0; 3]);
+        assert_eq!(all, (0..nface).collect::<Vec<_>>());
+
+        // faces on the z = 0 plane
+        let bottom = tetgen.marked_faces_in_bbox(&[-1.0, -1.0, -0.1], &[2.0, 2.0, 0.1]);
+        assert!(!bottom.is_empty());
+        for face in &bottom {
+            assert_eq!(tetgen.marked_face_marker(*face), -10);
+        }
+        let nbottom = (0..nface).filter(|f| tetgen.marked_face_marker(*f) == -10).count();
+        assert_eq!(bottom.len(), nbottom);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_can_be_called_multiple_times() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+            tetgen.set_facet_marker(index, -10)?;
         }
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        let (npoint, ntet, nface) = (tetgen.npoint(), tetgen.ntet(), tetgen.nmarked_face());
+        assert!((0..nface).all(|f| tetgen.marked_face_marker(f) == -10));
+
+        // tune the parameters without setting the input again
+        tetgen.generate_mesh(false, true, Some(0.001), None)?;
+        assert!(tetgen.ntet() > ntet);
+        assert_eq!(tetgen.nnode(), 10);
+
+        // the convex hull faces of the Delaunay triangulation have no markers
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.nnode(), 4);
+        assert_eq!(tetgen.nmarked_face(), 4);
+        assert!((0..4).all(|f| tetgen.marked_face_marker(f) == 0));
+
+        // the same output is obtained again
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert_eq!(tetgen.npoint(), npoint);
+        assert_eq!(tetgen.ntet(), ntet);
+        assert_eq!(tetgen.nmarked_face(), nface);
+        assert!(tetgen.tet_neighbor(0, 0).is_some() || tetgen.tet_neighbor(0, 1).is_some());
+
+        // the output may be released
+        tetgen.clear_outputs()?;
+        assert_eq!(tetgen.npoint(), 0);
+        assert_eq!(tetgen.ntet(), 0);
+        assert_eq!(tetgen.nmarked_face(), 0);
+        assert_eq!(tetgen.tet_neighbor(0, 0), None);
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert_eq!(tetgen.ntet(), ntet);
         Ok(())
     }
 
@@ -968,14 +2600,440 @@ mod tests {
         tetgen.generate_mesh(false, false, None, None)?;
         assert_eq!(tetgen.ntet(), 116);
         assert_eq!(tetgen.npoint(), 50);
-        let mut plot = Plot::new();
-        tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
-        if false {
-            write_tet_vtu(&tetgen, "/tmp/tritet/tetgen_test_mesh_1.vtu")?;
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/tetgen_test_mesh_1.svg")?;
+        #[cfg(feature = "plot")]
+        {
+            let mut plot = plotpy::Plot::new();
+            tetgen.draw_wireframe(&mut plot, true, true, true, true, None, None, None);
+            if false {
+                crate::write_tet_vtu(&tetgen, "/tmp/tritet/tetgen_test_mesh_1.vtu")?;
+                plot.set_equal_axes(true)
+                    .set_figure_size_points(600.0, 600.0)
+                    .save("/tmp/tritet/tetgen_test_mesh_1.svg")?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_captures_some_errors() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        let options = TetgenOptions::new();
+        assert_eq!(
+            tetgen.refine_mesh(&[], &options).err(),
+            Some("cannot refine mesh because there is no mesh yet")
+        );
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(
+            tetgen.refine_mesh(&[0.1, 0.1], &options).err(),
+            Some("per_cell_max_volume.len() must be equal to ntet")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_facet_marker(0, -10)?;
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.nnode(), 10);
+
+        // refine (the middle nodes are discarded)
+        tetgen.refine_mesh(&[0.001], &TetgenOptions::new())?;
+        let ntet = tetgen.ntet();
+        assert!(ntet > 50);
+        assert_eq!(tetgen.nnode(), 4);
+        let volume: f64 = tetgen.to_mesh().measures().iter().map(|v| v.abs()).sum();
+        assert!(f64::abs(volume - 1.0 / 6.0) < 1e-14);
+        assert!(tetgen.marked_faces().any(|f| f.marker == -10));
+
+        // refine again with quadratic tetrahedra
+        let volumes = vec![0.0005; ntet];
+        let mut options = TetgenOptions::new();
+        options.set_quadratic(true);
+        tetgen.refine_mesh(&volumes, &options)?;
+        assert!(tetgen.ntet() > ntet);
+        assert_eq!(tetgen.nnode(), 10);
+        Ok(())
+    }
+
+    #[test]
+    fn point_tags_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        assert_eq!(
+            tetgen.set_point_tag(4, 1).err(),
+            Some("index of point is out of bounds")
+        );
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_point_tag(1, 11)?.set_point_tag(3, 33)?;
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert!(tetgen.npoint() > 4);
+        assert_eq!(tetgen.point_tag(0), 0);
+        assert_eq!(tetgen.point_tag(1), 11);
+        assert_eq!(tetgen.point_tag(3), 33);
+        assert!((4..tetgen.npoint()).all(|p| tetgen.point_tag(p) == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn point_original_index_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        assert_eq!(tetgen.point_original_index(0), None); // no output yet
+        tetgen.generate_mesh(false, true, Some(0.01), None)?;
+        let check = |tetgen: &Tetgen| {
+            assert!(tetgen.npoint() > 4);
+            assert!((0..4).all(|p| tetgen.point_original_index(p) == Some(p)));
+            assert!((4..tetgen.npoint()).all(|p| tetgen.point_original_index(p).is_none()));
+            assert_eq!(tetgen.point(3, 2), 1.0);
+        };
+        check(&tetgen);
+        let volumes = vec![0.001; tetgen.ntet()];
+        tetgen.refine_mesh(&volumes, &TetgenOptions::new())?;
+        check(&tetgen);
+
+        // duplicated input point
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 0.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 0.0)?
+            .set_point(4, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.npoint(), 4);
+        assert_eq!(tetgen.point_original_index(0), Some(0));
+        assert_eq!(tetgen.point_original_index(1), Some(2)); // Tetgen keeps the second coinciding point
+        assert_eq!(tetgen.point_original_index(2), Some(3));
+        assert_eq!(tetgen.point_original_index(3), Some(4));
+
+        // unused input point (not on any facet) followed by a refinement
+        let mut tetgen = Tetgen::new(5, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 5.0, 5.0, 5.0)?
+            .set_point(1, 0.0, 0.0, 0.0)?
+            .set_point(2, 1.0, 0.0, 0.0)?
+            .set_point(3, 0.0, 1.0, 0.0)?
+            .set_point(4, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[1, 3, 2], [1, 2, 4], [1, 4, 3], [2, 3, 4]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        let check = |tetgen: &Tetgen| {
+            for p in 0..tetgen.npoint() {
+                match tetgen.point_original_index(p) {
+                    Some(q) => assert!((0..3).all(|dim| tetgen.point(p, dim) == tetgen.input_point(q)[dim])),
+                    None => assert!(p >= 4),
+                }
+            }
+            let originals: Vec<_> = (0..4).map(|p| tetgen.point_original_index(p)).collect();
+            assert_eq!(originals, &[Some(1), Some(2), Some(3), Some(4)]);
+        };
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        check(&tetgen);
+        let volumes = vec![0.001; tetgen.ntet()];
+        tetgen.refine_mesh(&volumes, &TetgenOptions::new())?;
+        check(&tetgen);
+        Ok(())
+    }
+
+    #[test]
+    fn quality_options_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        let mut options = TetgenOptions::new();
+        options.set_max_volume(0.001);
+        tetgen.generate_mesh_with(&options)?;
+        let npoint_default = tetgen.npoint();
+
+        options.set_max_radius_edge_ratio(1.2);
+        tetgen.generate_mesh_with(&options)?;
+        let npoint_ratio = tetgen.npoint();
+        assert!(npoint_ratio > npoint_default);
+
+        options.set_min_dihedral_angle(15.0);
+        tetgen.generate_mesh_with(&options)?;
+        assert!(tetgen.ntet() > 1);
+        let volume: f64 = tetgen.to_mesh().measures().iter().map(|v| v.abs()).sum();
+        assert!(f64::abs(volume - 1.0 / 6.0) < 1e-14);
+        Ok(())
+    }
+
+    #[test]
+    fn set_facet_polygon_and_hole_capture_some_errors() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 1, &[0, 1, 2]).err(),
+            Some("cannot set facet polygon because facet_npoint is None")
+        );
+        assert_eq!(
+            tetgen.set_facet_hole(0, 0, 0.0, 0.0, 0.0).err(),
+            Some("cannot set facet hole because facet_npoint is None")
+        );
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        assert_eq!(
+            tetgen.set_facet_polygon(4, 1, &[0, 1, 2]).err(),
+            Some("index of facet is out of bounds")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(4, 0, &[0, 1, 2]).err(),
+            Some("index of facet is out of bounds")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 0, &[0, 1]).err(),
+            Some("the first facet polygon must have facet_npoint points")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 0, &[0, 1, 4]).err(),
+            Some("id of facet point is out of bounds")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 2, &[0, 1, 2]).err(),
+            Some("the facet polygons must be set in order")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 1, &[]).err(),
+            Some("the facet polygon must have at least one point")
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon(0, 1, &[0, 1, 4]).err(),
+            Some("id of facet point is out of bounds")
+        );
+        assert_eq!(
+            tetgen.set_facet_hole(0, 1, 0.0, 0.0, 0.0).err(),
+            Some("the facet holes must be set in order (starting at 0)")
+        );
+        assert_eq!(
+            tetgen.set_facet_hole(4, 0, 0.0, 0.0, 0.0).err(),
+            Some("index of facet is out of bounds")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn facet_polygons_and_holes_work() -> Result<(), StrError> {
+        // plate [0,2]×[0,2]×[0,1] with a square opening [0.5,1.5]×[0.5,1.5]
+        let mut tetgen = Tetgen::new(16, Some(vec![4; 10]), None, None)?;
+        let square = [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let opening = [[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]];
+        for z in 0..2 {
+            for i in 0..4 {
+                tetgen.set_point(z * 4 + i, square[i][0], square[i][1], z as f64)?;
+                tetgen.set_point(8 + z * 4 + i, opening[i][0], opening[i][1], z as f64)?;
+            }
+        }
+        // bottom and top with openings (the outer boundary is the first polygon)
+        for (facet, offset) in [(0, 0), (1, 4)] {
+            let outer: Vec<usize> = (0..4).map(|m| offset + m).collect();
+            tetgen.set_facet_polygon(facet, 0, &outer)?;
+            let inner: Vec<usize> = (0..4).map(|m| 8 + offset + m).collect();
+            tetgen
+                .set_facet_polygon(facet, 1, &[0, 1, 2])? // replaced below
+                .set_facet_polygon(facet, 1, &inner)?
+                .set_facet_hole(facet, 0, 0.0, 0.0, 0.0)? // replaced below
+                .set_facet_hole(facet, 0, 1.0, 1.0, offset as f64 / 4.0)?;
+        }
+        // outer and inner sides
+        for (first, facet0) in [(0, 2), (8, 6)] {
+            for i in 0..4 {
+                let j = (i + 1) % 4;
+                let points = [first + i, first + j, first + 4 + j, first + 4 + i];
+                for (m, p) in points.iter().enumerate() {
+                    tetgen.set_facet_point(facet0 + i, m, *p)?;
+                }
+            }
+        }
+        tetgen.generate_mesh(false, false, None, None)?;
+        let volume: f64 = tetgen.to_mesh().measures().iter().map(|v| v.abs()).sum();
+        assert!(f64::abs(volume - 3.0) < 1e-13);
+        for tet in 0..tetgen.ntet() {
+            let mut c = [0.0; 2];
+            for m in 0..4 {
+                c[0] += tetgen.point(tetgen.tet_node(tet, m), 0) / 4.0;
+                c[1] += tetgen.point(tetgen.tet_node(tet, m), 1) / 4.0;
+            }
+            assert!(!(c[0] > 0.5 && c[0] < 1.5 && c[1] > 0.5 && c[1] < 1.5));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn instances_can_be_moved_to_threads() -> Result<(), StrError> {
+        let max_volumes = [0.1, 0.05, 0.01, 0.005, 0.001];
+        let mut correct = Vec::new();
+        for max_volume in max_volumes {
+            let tetgen = cube()?;
+            tetgen.generate_mesh(false, false, Some(max_volume), None)?;
+            correct.push(tetgen.ntet());
+        }
+        let mut handles = Vec::new();
+        for max_volume in max_volumes {
+            let tetgen = cube()?;
+            handles.push(std::thread::spawn(move || {
+                tetgen.generate_mesh(false, false, Some(max_volume), None).unwrap();
+                tetgen
+            }));
+        }
+        for (handle, ntet) in handles.into_iter().zip(correct) {
+            let tetgen = handle.join().map_err(|_| "thread failed")?;
+            assert_eq!(tetgen.ntet(), ntet);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn insert_points_works() -> Result<(), StrError> {
+        let mut tetgen = cube()?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.npoint(), 8);
+
+        // the point outside the cube is ignored
+        let sensors = [(0.5, 0.5, 0.5), (0.25, 0.75, 0.3), (2.0, 2.0, 2.0)];
+        tetgen.insert_points(&sensors[..1])?.insert_points(&sensors[1..])?;
+        assert_eq!(tetgen.inserted_points().len(), 3);
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(tetgen.npoint() >= 10); // Tetgen may add Steiner points to recover the boundary
+        for (x, y, z) in &sensors[..2] {
+            let found = (0..tetgen.npoint())
+                .any(|p| tetgen.point(p, 0) == *x && tetgen.point(p, 1) == *y && tetgen.point(p, 2) == *z);
+            assert!(found);
+        }
+        let volume: f64 = (0..tetgen.ntet()).map(|t| tetgen.tet_quality(t).unwrap().volume).sum();
+        assert!((volume - 1.0).abs() < 1e-14);
+
+        // the other generate methods disregard the additional points
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.npoint(), 8);
+
+        tetgen.clear_inserted_points()?;
+        assert_eq!(tetgen.inserted_points().len(), 0);
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.npoint(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn set_region_f64_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_region_f64(0, 0.1, 0.1, 0.1, -0.25, None)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!(tetgen.ntet() > 0);
+        assert!((0..tetgen.ntet()).all(|t| tetgen.tet_attribute_f64(t) == -0.25));
+        assert_eq!(tetgen.tet_attribute_f64(1000), 0.0);
+
+        // the attributes are kept when generating the mesh again
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!((0..tetgen.ntet()).all(|t| tetgen.tet_attribute_f64(t) == -0.25));
+
+        // the integer attributes of set_region are also available as f64
+        tetgen.set_region(0, 0.1, 0.1, 0.1, 2, None)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert!((0..tetgen.ntet()).all(|t| tetgen.tet_attribute(t) == 2 && tetgen.tet_attribute_f64(t) == 2.0));
+        Ok(())
+    }
+
+    #[test]
+    fn set_region_handles_negative_attributes() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]].iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.set_region(0, 0.1, 0.1, 0.1, -7, None)?;
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert!(tetgen.ntet() > 1);
+        assert!((0..tetgen.ntet()).all(|t| tetgen.tet_attribute(t) == -7));
+        assert_eq!(tetgen.input_region(0), (0.1, 0.1, 0.1, -7));
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_reports_tetgen_failures() -> Result<(), StrError> {
+        // a triangle crossing the bottom face of a tetrahedron
+        let mut tetgen = Tetgen::new(7, Some(vec![3, 3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, 0.2, 0.2, -0.5)?
+            .set_point(5, 0.2, 0.2, 0.5)?
+            .set_point(6, 0.3, 0.1, 0.2)?;
+        for (index, face) in [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3], [4, 5, 6]]
+            .iter()
+            .enumerate()
+        {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
         }
+        let error = Some("Tetgen failed to generate the mesh (e.g., the facets intersect each other)");
+        assert_eq!(tetgen.generate_mesh(false, false, None, None).err(), error);
+        assert_eq!(tetgen.generate_cdt(false).err(), error);
+
+        // the input is restored after the failure
+        assert_eq!(tetgen.generate_mesh(false, false, None, None).err(), error);
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.npoint(), 7);
         Ok(())
     }
 }
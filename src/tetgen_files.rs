@@ -0,0 +1,497 @@
+use crate::poly_file::{lines, next_line, parse, parse_node, write_file, NodeData};
+use crate::StrError;
+use crate::Tetgen;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Holds a facet of a .poly or .smesh file
+struct FacetData {
+    polygons: Vec<Vec<usize>>, // vertex numbers of the polygons (as in the file)
+    holes: Vec<[f64; 3]>,      // coordinates of the facet holes
+    marker: i32,               // boundary marker
+}
+
+/// Holds the contents of a TetGen's .poly or .smesh file
+struct TetPolyData {
+    nodes: Option<NodeData<3>>, // vertices (None if they are in a separate .node file)
+    facets: Vec<FacetData>,     // facets
+    holes: Vec<[f64; 3]>,       // coordinates of the volume holes
+    regions: Vec<([f64; 3], i32, Option<f64>)>, // coordinates, attribute, and maximum volume of the regions
+}
+
+impl Tetgen {
+    /// Allocates a new instance with the points, facets, holes, and regions of a .poly file
+    ///
+    /// The format is described in [TetGen's manual](https://wias-berlin.de/software/tetgen/1.5/doc/manual/manual006.html).
+    /// If the .poly file has no vertices, they are read from the .node file with the same
+    /// name (e.g., `box.node` for `box.poly`). The vertices may be numbered from 0 or 1.
+    /// The attributes and boundary markers of the vertices are ignored; the boundary markers
+    /// of the facets are transferred to the marked faces. The regional attributes must be
+    /// integers and the negative maximum volumes mean no constraint.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_poly_file<P>(full_path: &P) -> Result<Tetgen, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        from_tet_poly_file(Path::new(full_path), false)
+    }
+
+    /// Allocates a new instance with the points, facets, holes, and regions of a .smesh file
+    ///
+    /// The .smesh format is a simpler version of the .poly format where each facet is a single
+    /// polygon given in one line (see `from_poly_file`).
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn from_smesh_file<P>(full_path: &P) -> Result<Tetgen, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        from_tet_poly_file(Path::new(full_path), true)
+    }
+
+    /// Writes the generated mesh as .node, .ele, and .face files
+    ///
+    /// The formats are described in [TetGen's manual](https://wias-berlin.de/software/tetgen/1.5/doc/manual/manual006.html)
+    /// and the files can be read by TetView and other tools based on TetGen. The points and
+    /// tetrahedra are numbered from 0. The .ele file has the attribute of each tetrahedron and
+    /// the .face file has the marked faces with their markers.
+    ///
+    /// # Input
+    ///
+    /// * `prefix` -- the path without extension (e.g., "/tmp/tritet/mesh" for "/tmp/tritet/mesh.node")
+    pub fn write_tetgen_files<P>(&self, prefix: &P) -> Result<(), StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let ntet = self.ntet();
+        if ntet < 1 {
+            return Err("there are no tetrahedra to write");
+        }
+        let nnode = self.nnode();
+        let npoint = self.npoint();
+        let path = |extension: &str| {
+            let mut path = prefix.as_ref().to_os_string();
+            path.push(extension);
+            path
+        };
+
+        // points
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} 3 0 0", npoint).unwrap();
+        for p in 0..npoint {
+            writeln!(
+                &mut buffer,
+                "{} {} {} {}",
+                p,
+                self.point(p, 0),
+                self.point(p, 1),
+                self.point(p, 2)
+            )
+            .unwrap();
+        }
+        write_file(&buffer, &path(".node"))?;
+
+        // tetrahedra
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} {} 1", ntet, nnode).unwrap();
+        for tet in 0..ntet {
+            write!(&mut buffer, "{}", tet).unwrap();
+            for m in 0..nnode {
+                write!(&mut buffer, " {}", self.tet_node(tet, m)).unwrap();
+            }
+            writeln!(&mut buffer, " {}", self.tet_attribute(tet)).unwrap();
+        }
+        write_file(&buffer, &path(".ele"))?;
+
+        // marked faces
+        let mut buffer = String::new();
+        writeln!(&mut buffer, "{} 1", self.nmarked_face()).unwrap();
+        for face in 0..self.nmarked_face() {
+            writeln!(
+                &mut buffer,
+                "{} {} {} {} {}",
+                face,
+                self.marked_face_node(face, 0),
+                self.marked_face_node(face, 1),
+                self.marked_face_node(face, 2),
+                self.marked_face_marker(face)
+            )
+            .unwrap();
+        }
+        write_file(&buffer, &path(".face"))
+    }
+}
+
+/// Allocates a new instance with the contents of a .poly or .smesh file
+fn from_tet_poly_file(path: &Path, smesh: bool) -> Result<Tetgen, StrError> {
+    let contents = fs::read_to_string(path).map_err(|_| "cannot read file")?;
+    let poly = parse_tet_poly(&contents, smesh)?;
+    let nodes = match poly.nodes {
+        Some(nodes) => nodes,
+        None => {
+            let node_path = path.with_extension("node");
+            let contents = fs::read_to_string(node_path).map_err(|_| "cannot read the corresponding .node file")?;
+            let nodes: NodeData<3> = parse_node(&mut lines(&contents))?;
+            nodes
+        }
+    };
+    let npoint = nodes.coordinates.len();
+    let to_ids = |numbers: &[usize]| -> Result<Vec<usize>, StrError> {
+        numbers
+            .iter()
+            .map(|number| {
+                if *number < nodes.first || *number - nodes.first >= npoint {
+                    Err("the vertex number of a facet is out of bounds")
+                } else {
+                    Ok(number - nodes.first)
+                }
+            })
+            .collect()
+    };
+    let option = |n: usize| if n > 0 { Some(n) } else { None };
+    let facet_npoint = if poly.facets.is_empty() {
+        None
+    } else {
+        Some(poly.facets.iter().map(|f| f.polygons[0].len()).collect())
+    };
+    let mut tetgen = Tetgen::new(
+        npoint,
+        facet_npoint,
+        option(poly.regions.len()),
+        option(poly.holes.len()),
+    )?;
+    tetgen.set_points(&nodes.coordinates)?;
+    for (index, facet) in poly.facets.iter().enumerate() {
+        for (polygon, numbers) in facet.polygons.iter().enumerate() {
+            let ids = to_ids(numbers)?;
+            if polygon == 0 {
+                for (m, p) in ids.iter().enumerate() {
+                    tetgen.set_facet_point(index, m, *p)?;
+                }
+            } else {
+                tetgen.set_facet_polygon(index, polygon, &ids)?;
+            }
+        }
+        for (hole, x) in facet.holes.iter().enumerate() {
+            tetgen.set_facet_hole(index, hole, x[0], x[1], x[2])?;
+        }
+        tetgen.set_facet_marker(index, facet.marker)?;
+    }
+    for (index, x) in poly.holes.iter().enumerate() {
+        tetgen.set_hole(index, x[0], x[1], x[2])?;
+    }
+    for (index, (x, attribute, max_volume)) in poly.regions.iter().enumerate() {
+        tetgen.set_region(index, x[0], x[1], x[2], *attribute, *max_volume)?;
+    }
+    Ok(tetgen)
+}
+
+/// Parses the coordinates following the number in a line
+fn parse_point(words: &[&str]) -> Result<[f64; 3], StrError> {
+    Ok([parse(words[1])?, parse(words[2])?, parse(words[3])?])
+}
+
+/// Parses a polygon given by the number of vertices followed by the vertex numbers
+fn parse_polygon(words: &[&str]) -> Result<Vec<usize>, StrError> {
+    let n: usize = parse(words[0])?;
+    if n >= words.len() {
+        return Err("the file has a line with missing values");
+    }
+    words[1..(1 + n)].iter().map(|word| parse(word)).collect()
+}
+
+/// Parses a TetGen's .poly file (or a .smesh file)
+fn parse_tet_poly(contents: &str, smesh: bool) -> Result<TetPolyData, StrError> {
+    let mut lines = lines(contents).peekable();
+    let nodes: NodeData<3> = parse_node(&mut lines)?;
+    let nodes = if nodes.coordinates.is_empty() {
+        None
+    } else {
+        Some(nodes)
+    };
+
+    // facets
+    let header = next_line(&mut lines, 1)?;
+    let nfacet: usize = parse(header[0])?;
+    let with_markers = match header.get(1) {
+        Some(word) => parse::<usize>(word)? != 0,
+        None => false,
+    };
+    let mut facets = Vec::new();
+    for _ in 0..nfacet {
+        let mut facet = FacetData {
+            polygons: Vec::new(),
+            holes: Vec::new(),
+            marker: 0,
+        };
+        if smesh {
+            let words = next_line(&mut lines, 1)?;
+            facet.polygons.push(parse_polygon(&words)?);
+            if with_markers {
+                if let Some(word) = words.get(1 + facet.polygons[0].len()) {
+                    facet.marker = parse(word)?;
+                }
+            }
+        } else {
+            let words = next_line(&mut lines, 1)?;
+            let npolygon: usize = parse(words[0])?;
+            let nhole: usize = match words.get(1) {
+                Some(word) => parse(word)?,
+                None => 0,
+            };
+            if with_markers {
+                if let Some(word) = words.get(2) {
+                    facet.marker = parse(word)?;
+                }
+            }
+            for _ in 0..npolygon {
+                facet.polygons.push(parse_polygon(&next_line(&mut lines, 1)?)?);
+            }
+            for _ in 0..nhole {
+                facet.holes.push(parse_point(&next_line(&mut lines, 4)?)?);
+            }
+        }
+        if facet.polygons.is_empty() {
+            return Err("the facet must have at least one polygon");
+        }
+        facets.push(facet);
+    }
+
+    // holes (optional)
+    let mut holes = Vec::new();
+    if lines.peek().is_some() {
+        let nhole: usize = parse(next_line(&mut lines, 1)?[0])?;
+        for _ in 0..nhole {
+            holes.push(parse_point(&next_line(&mut lines, 4)?)?);
+        }
+    }
+
+    // regional attributes and volume constraints (optional)
+    let mut regions = Vec::new();
+    if lines.peek().is_some() {
+        let nregion: usize = parse(next_line(&mut lines, 1)?[0])?;
+        for _ in 0..nregion {
+            let words = next_line(&mut lines, 5)?;
+            let attribute: f64 = parse(words[4])?;
+            if attribute.fract() != 0.0 || attribute.abs() > i32::MAX as f64 {
+                return Err("the regional attribute must be an integer");
+            }
+            let max_volume = match words.get(5) {
+                Some(word) => Some(parse::<f64>(word)?).filter(|volume| *volume > 0.0),
+                None => None,
+            };
+            regions.push((parse_point(&words)?, attribute as i32, max_volume));
+        }
+    }
+    Ok(TetPolyData {
+        nodes,
+        facets,
+        holes,
+        regions,
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::parse_tet_poly;
+    use crate::{StrError, Tetgen};
+    use std::fs;
+
+    /// Returns the vertices of the unit cube (numbered from 1)
+    const CUBE_NODES: &str = "8 3 0 0\n\
+         1 0.0 0.0 0.0\n2 1.0 0.0 0.0\n3 1.0 1.0 0.0\n4 0.0 1.0 0.0\n\
+         5 0.0 0.0 1.0\n6 1.0 0.0 1.0\n7 1.0 1.0 1.0\n8 0.0 1.0 1.0\n";
+
+    #[test]
+    fn parse_tet_poly_captures_errors() {
+        assert_eq!(parse_tet_poly("", true).err(), Some("the file ended unexpectedly"));
+        assert_eq!(parse_tet_poly("3 2 0 0", true).err(), Some("the dimension must be 3"));
+        assert_eq!(
+            parse_tet_poly("1 3 0 0\n0 0.0 0.0", true).err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_tet_poly("0 3 0 0\n1 0\n4 1 2 3", true).err(),
+            Some("the file has a line with missing values")
+        );
+        assert_eq!(
+            parse_tet_poly("0 3 0 0\n1 0\n0 0", false).err(),
+            Some("the facet must have at least one polygon")
+        );
+        assert_eq!(
+            parse_tet_poly("0 3 0 0\n0 0\n0\n1\n1 0.5 0.5 0.5 -1.5 0.1", true).err(),
+            Some("the regional attribute must be an integer")
+        );
+
+        // the counts in the headers are not trusted to allocate memory
+        assert_eq!(
+            parse_tet_poly("99999999999999999 3 0 0", true).err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_tet_poly("0 3 0 0\n99999999999999999 0", false).err(),
+            Some("the file ended unexpectedly")
+        );
+        assert_eq!(
+            parse_tet_poly("0 3 0 0\n1 0\n18446744073709551615 1 2 3", true).err(),
+            Some("the file has a line with missing values")
+        );
+    }
+
+    #[test]
+    fn parse_tet_poly_works() -> Result<(), StrError> {
+        let smesh = parse_tet_poly(
+            "0 3 0 0 # vertices are in another file\n\
+             2 1\n\
+             3 1 2 3 -10\n\
+             4 1 2 3 4\n\
+             1\n\
+             1 0.5 0.5 0.5\n\
+             1\n\
+             1 0.25 0.25 0.25 2 0.1\n",
+            true,
+        )?;
+        assert!(smesh.nodes.is_none());
+        assert_eq!(smesh.facets[0].polygons, &[vec![1, 2, 3]]);
+        assert_eq!(smesh.facets[0].marker, -10);
+        assert_eq!(smesh.facets[1].polygons, &[vec![1, 2, 3, 4]]);
+        assert_eq!(smesh.facets[1].marker, 0);
+        assert_eq!(smesh.holes, &[[0.5, 0.5, 0.5]]);
+        assert_eq!(smesh.regions, &[([0.25, 0.25, 0.25], 2, Some(0.1))]);
+
+        let poly = parse_tet_poly(
+            "0 3 0 0\n\
+             1 1\n\
+             2 1 -5\n\
+             4 1 2 3 4\n\
+             4 5 6 7 8\n\
+             1 0.5 0.5 0.0\n",
+            false,
+        )?;
+        assert_eq!(poly.facets[0].polygons, &[vec![1, 2, 3, 4], vec![5, 6, 7, 8]]);
+        assert_eq!(poly.facets[0].holes, &[[0.5, 0.5, 0.0]]);
+        assert_eq!(poly.facets[0].marker, -5);
+        assert_eq!(poly.holes.len(), 0);
+        assert_eq!(poly.regions.len(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn from_smesh_file_works() -> Result<(), StrError> {
+        // unit cube with vertices in the .node file
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        fs::write("/tmp/tritet/test_from_smesh_file.node", CUBE_NODES).map_err(|_| "cannot write file")?;
+        let full_path = "/tmp/tritet/test_from_smesh_file.smesh";
+        fs::write(
+            full_path,
+            "0 3 0 0\n\
+             6 1\n\
+             4 1 2 3 4 -1\n4 5 6 7 8 -2\n4 1 2 6 5 -3\n\
+             4 2 3 7 6 -4\n4 3 4 8 7 -5\n4 4 1 5 8 -6\n\
+             0\n\
+             1\n\
+             1 0.5 0.5 0.5 3 -1\n",
+        )
+        .map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Tetgen::from_smesh_file("/tmp/tritet/__does_not_exist__.smesh").err(),
+            Some("cannot read file")
+        );
+        let tetgen = Tetgen::from_smesh_file(full_path)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let volume: f64 = tetgen.to_mesh().measures().iter().sum();
+        assert!(f64::abs(volume - 1.0) < 1e-14);
+        for tet in 0..tetgen.ntet() {
+            assert_eq!(tetgen.tet_attribute(tet), 3);
+        }
+        let mut markers: Vec<_> = (0..tetgen.nmarked_face())
+            .map(|f| tetgen.marked_face_marker(f))
+            .collect();
+        markers.sort();
+        markers.dedup();
+        assert_eq!(markers, &[-6, -5, -4, -3, -2, -1]);
+
+        // out-of-bounds vertex
+        fs::write(full_path, "0 3 0 0\n4 0\n3 1 2 3\n3 1 2 4\n3 1 3 9\n3 2 3 4\n").map_err(|_| "cannot write file")?;
+        assert_eq!(
+            Tetgen::from_smesh_file(full_path).err(),
+            Some("the vertex number of a facet is out of bounds")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_poly_file_works() -> Result<(), StrError> {
+        // unit cube with vertices in the .poly file and one polygon per facet
+        let full_path = "/tmp/tritet/test_tetgen_from_poly_file.poly";
+        fs::create_dir_all("/tmp/tritet").map_err(|_| "cannot create directory")?;
+        fs::write(
+            full_path,
+            format!(
+                "{}\
+                 6 1\n\
+                 1 0 -1\n4 1 2 3 4\n\
+                 1 0 -2\n4 5 6 7 8\n\
+                 1 0 -3\n4 1 2 6 5\n\
+                 1 0 -4\n4 2 3 7 6\n\
+                 1 0 -5\n4 3 4 8 7\n\
+                 1 0 -6\n4 4 1 5 8\n\
+                 0\n",
+                CUBE_NODES
+            ),
+        )
+        .map_err(|_| "cannot write file")?;
+        let tetgen = Tetgen::from_poly_file(full_path)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let volume: f64 = tetgen.to_mesh().measures().iter().sum();
+        assert!(f64::abs(volume - 1.0) < 1e-14);
+        Ok(())
+    }
+
+    #[test]
+    fn write_tetgen_files_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen
+                .write_tetgen_files("/tmp/tritet/test_write_tetgen_files_empty")
+                .err(),
+            Some("there are no tetrahedra to write")
+        );
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let prefix = "/tmp/tritet/test_write_tetgen_files";
+        tetgen.write_tetgen_files(prefix)?;
+        let read =
+            |extension: &str| fs::read_to_string(format!("{}{}", prefix, extension)).map_err(|_| "cannot read file");
+        assert_eq!(
+            read(".node")?,
+            "4 3 0 0\n\
+             0 0 0 0\n\
+             1 1 0 0\n\
+             2 0 1 0\n\
+             3 0 0 1\n"
+        );
+        let ele = read(".ele")?;
+        let words: Vec<usize> = ele.split_whitespace().map(|w| w.parse().unwrap()).collect();
+        assert_eq!(&words[..3], &[1, 4, 1]);
+        let mut nodes = words[4..8].to_vec();
+        nodes.sort();
+        assert_eq!(nodes, &[0, 1, 2, 3]);
+        assert_eq!(words[8], 0);
+        assert_eq!(read(".face")?.lines().count(), 5);
+        Ok(())
+    }
+}
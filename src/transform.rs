@@ -0,0 +1,160 @@
+/// Holds an affine map x ↦ A x + b
+///
+/// Used by `Triangle::set_output_transform` and `Tetgen::set_output_transform` to report the
+/// coordinates in a global frame without modifying the generated mesh.
+///
+/// # Examples
+///
+/// ```
+/// use tritet::AffineMap;
+///
+/// let mut map = AffineMap::rotation_z(std::f64::consts::PI / 2.0);
+/// map.translation = [10.0, 0.0, 0.0];
+/// let x = map.apply(&[1.0, 0.0, 5.0]);
+/// assert!((x[0] - 10.0).abs() < 1e-15);
+/// assert!((x[1] - 1.0).abs() < 1e-15);
+/// assert_eq!(x[2], 5.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AffineMap<const DIM: usize> {
+    /// Holds the linear part A (row by row)
+    pub matrix: [[f64; DIM]; DIM],
+
+    /// Holds the translation b
+    pub translation: [f64; DIM],
+}
+
+impl<const DIM: usize> AffineMap<DIM> {
+    /// Allocates the identity map
+    pub fn identity() -> Self {
+        let mut matrix = [[0.0; DIM]; DIM];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        AffineMap {
+            matrix,
+            translation: [0.0; DIM],
+        }
+    }
+
+    /// Allocates a translation
+    pub fn translation(translation: [f64; DIM]) -> Self {
+        AffineMap {
+            translation,
+            ..AffineMap::identity()
+        }
+    }
+
+    /// Allocates a uniform scaling about the origin
+    pub fn scaling(factor: f64) -> Self {
+        let mut map = AffineMap::identity();
+        for (i, row) in map.matrix.iter_mut().enumerate() {
+            row[i] = factor;
+        }
+        map
+    }
+
+    /// Applies the map to a point
+    pub fn apply(&self, x: &[f64; DIM]) -> [f64; DIM] {
+        let mut y = self.translation;
+        for (yi, row) in y.iter_mut().zip(self.matrix.iter()) {
+            *yi += row.iter().zip(x.iter()).map(|(a, b)| a * b).sum::<f64>();
+        }
+        y
+    }
+
+    /// Applies the linear part only (e.g., to a direction)
+    pub fn apply_linear(&self, v: &[f64; DIM]) -> [f64; DIM] {
+        let mut y = [0.0; DIM];
+        for (yi, row) in y.iter_mut().zip(self.matrix.iter()) {
+            *yi = row.iter().zip(v.iter()).map(|(a, b)| a * b).sum::<f64>();
+        }
+        y
+    }
+}
+
+impl AffineMap<2> {
+    /// Allocates a counterclockwise rotation about the origin
+    pub fn rotation(angle: f64) -> Self {
+        let (s, c) = f64::sin_cos(angle);
+        AffineMap {
+            matrix: [[c, -s], [s, c]],
+            translation: [0.0; 2],
+        }
+    }
+}
+
+impl AffineMap<3> {
+    /// Allocates a counterclockwise rotation about the z-axis
+    pub fn rotation_z(angle: f64) -> Self {
+        let (s, c) = f64::sin_cos(angle);
+        AffineMap {
+            matrix: [[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0; 3],
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::AffineMap;
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn affine_map_works() {
+        let map = AffineMap::<3>::identity();
+        assert_eq!(map.apply(&[1.0, 2.0, 3.0]), [1.0, 2.0, 3.0]);
+        let map = AffineMap::translation([1.0, -1.0]);
+        assert_eq!(map.apply(&[1.0, 2.0]), [2.0, 1.0]);
+        assert_eq!(map.apply_linear(&[1.0, 2.0]), [1.0, 2.0]);
+        let map = AffineMap::<2>::scaling(2.0);
+        assert_eq!(map.apply(&[1.0, 2.0]), [2.0, 4.0]);
+        let map = AffineMap::rotation(std::f64::consts::PI);
+        let x = map.apply(&[1.0, 0.0]);
+        assert!(f64::abs(x[0] + 1.0) < 1e-15 && f64::abs(x[1]) < 1e-15);
+    }
+
+    #[test]
+    fn triangle_output_transform_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, Some(1), Some(1))?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_region(0, 0.1, 0.1, 1, None)?
+            .set_hole(0, 0.2, 0.2)?;
+        triangle.generate_delaunay(false)?;
+        triangle.set_output_transform(AffineMap::translation([10.0, 20.0]));
+        assert_eq!(triangle.point(1, 0), 11.0);
+        assert_eq!(triangle.point(1, 1), 20.0);
+        assert_eq!(triangle.point(1, 2), 0.0);
+        assert_eq!(triangle.point(100, 0), 0.0);
+        assert_eq!(triangle.input_hole(0), (10.2, 20.2));
+        assert_eq!(triangle.input_region(0), (10.1, 20.1, 1));
+        triangle.clear_output_transform();
+        assert_eq!(triangle.point(1, 0), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_output_transform_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        tetgen.set_output_transform(AffineMap::<3>::scaling(2.0));
+        let x: Vec<f64> = (0..3).map(|dim| tetgen.point(3, dim)).collect();
+        assert_eq!(x, &[0.0, 0.0, 2.0]);
+        assert_eq!(tetgen.point(3, 3), 0.0);
+        assert_eq!(tetgen.point(100, 0), 0.0);
+        assert!(f64::abs(tetgen.to_mesh().measure(0).abs() - 8.0 / 6.0) < 1e-15);
+        tetgen.clear_output_transform();
+        assert_eq!(tetgen.point(3, 2), 1.0);
+        Ok(())
+    }
+}
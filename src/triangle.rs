@@ -1,8 +1,13 @@
+use crate::console::run_with_console;
 use crate::constants;
 use crate::conversion::to_i32;
-use crate::StrError;
-use plotpy::{Canvas, Curve, Plot, PolyCode, Text};
+use crate::jitter::{find_degenerate_points, jitter_offset, JITTER_MAX_ATTEMPTS};
+use crate::recorder::{option_to_string, Recorder};
+use crate::{AffineMap, ColorMap, ConsoleHandler, CsrGraph, SteinerOptions, StrError, TriangleOptions};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::sync::Arc;
 
 #[repr(C)]
 pub(crate) struct ExtTriangle {
@@ -13,9 +18,13 @@ pub(crate) struct ExtTriangle {
 extern "C" {
     fn new_triangle(npoint: i32, nsegment: i32, nregion: i32, nhole: i32) -> *mut ExtTriangle;
     fn drop_triangle(triangle: *mut ExtTriangle);
+    fn reset_triangle(triangle: *mut ExtTriangle, npoint: i32, nsegment: i32, nregion: i32, nhole: i32) -> i32;
+    fn clear_triangle_outputs(triangle: *mut ExtTriangle);
     fn set_point(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64) -> i32;
+    fn set_points(triangle: *mut ExtTriangle, npoint: i32, coordinates: *const f64) -> i32;
     fn set_segment(triangle: *mut ExtTriangle, index: i32, a: i32, b: i32) -> i32;
-    fn set_region(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64, attribute: i32, max_area: f64) -> i32;
+    fn set_segment_marker(triangle: *mut ExtTriangle, index: i32, marker: i32) -> i32;
+    fn set_region(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64, attribute: f64, max_area: f64) -> i32;
     fn set_hole(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64) -> i32;
     fn run_delaunay(triangle: *mut ExtTriangle, verbose: i32) -> i32;
     fn run_voronoi(triangle: *mut ExtTriangle, verbose: i32) -> i32;
@@ -25,6 +34,27 @@ extern "C" {
         quadratic: i32,
         global_max_area: f64,
         global_min_angle: f64,
+        max_steiner_points: i32,
+        no_bisect: i32,
+        conforming_delaunay: i32,
+    ) -> i32;
+    fn run_refine(
+        triangle: *mut ExtTriangle,
+        verbose: i32,
+        quadratic: i32,
+        max_areas: *const f64,
+        global_max_area: f64,
+        global_min_angle: f64,
+        max_steiner_points: i32,
+        no_bisect: i32,
+        conforming_delaunay: i32,
+    ) -> i32;
+    fn run_given_mesh(
+        triangle: *mut ExtTriangle,
+        verbose: i32,
+        quadratic: i32,
+        ntriangle: i32,
+        corners: *const i32,
     ) -> i32;
     fn get_npoint(triangle: *mut ExtTriangle) -> i32;
     fn get_ntriangle(triangle: *mut ExtTriangle) -> i32;
@@ -32,11 +62,23 @@ extern "C" {
     fn get_point(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
     fn get_triangle_corner(triangle: *mut ExtTriangle, index: i32, corner: i32) -> i32;
     fn get_triangle_attribute(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_triangle_attribute_f64(triangle: *mut ExtTriangle, index: i32) -> f64;
+    fn get_triangle_neighbor(triangle: *mut ExtTriangle, index: i32, corner: i32) -> i32;
+    fn get_nedge(triangle: *mut ExtTriangle) -> i32;
+    fn get_edge_point(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
+    fn get_point_marker(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_point_original_index(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_edge_marker(triangle: *mut ExtTriangle, index: i32) -> i32;
     fn get_voronoi_npoint(triangle: *mut ExtTriangle) -> i32;
     fn get_voronoi_point(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
     fn get_voronoi_nedge(triangle: *mut ExtTriangle) -> i32;
     fn get_voronoi_edge_point(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
     fn get_voronoi_edge_point_b_direction(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
+    fn get_input_point(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
+    fn get_input_segment(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
+    fn get_input_hole(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
+    fn get_input_region(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
+    fn get_input_region_attribute(triangle: *mut ExtTriangle, index: i32) -> i32;
 }
 
 /// Holds the index of an endpoint on a Voronoi edge or the direction of the Voronoi edge
@@ -58,7 +100,6 @@ pub enum VoronoiEdgePoint {
 /// ## Delaunay triangulation
 ///
 /// ```
-/// use plotpy::Plot;
 /// use tritet::{StrError, Triangle};
 ///
 /// fn main() -> Result<(), StrError> {
@@ -81,8 +122,8 @@ pub enum VoronoiEdgePoint {
 ///     // generate Delaunay triangulation
 ///     triangle.generate_delaunay(false)?;
 ///
-///     // draw triangles
-///     let mut plot = Plot::new();
+///     // draw triangles (requires the "plot" feature)
+///     // let mut plot = plotpy::Plot::new();
 ///     // triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
 ///     // plot.set_equal_axes(true)
 ///     //     .set_figure_size_points(600.0, 600.0)
@@ -96,7 +137,6 @@ pub enum VoronoiEdgePoint {
 /// ## Voronoi tessellation
 ///
 /// ```
-/// use plotpy::Plot;
 /// use tritet::{StrError, Triangle};
 ///
 /// fn main() -> Result<(), StrError> {
@@ -119,8 +159,8 @@ pub enum VoronoiEdgePoint {
 ///     // generate Voronoi tessellation
 ///     triangle.generate_voronoi(false)?;
 ///
-///     // draw Voronoi diagram
-///     let mut plot = Plot::new();
+///     // draw Voronoi diagram (requires the "plot" feature)
+///     // let mut plot = plotpy::Plot::new();
 ///     // triangle.draw_voronoi(&mut plot);
 ///     // plot.set_equal_axes(true)
 ///     //     .set_figure_size_points(600.0, 600.0)
@@ -134,7 +174,6 @@ pub enum VoronoiEdgePoint {
 /// ## Mesh generation
 ///
 /// ```
-/// use plotpy::Plot;
 /// use tritet::{StrError, Triangle};
 ///
 /// fn main() -> Result<(), StrError> {
@@ -181,8 +220,8 @@ pub enum VoronoiEdgePoint {
 ///     triangle.generate_mesh(false, true, None, None)?;
 ///     assert_eq!(triangle.ntriangle(), 14);
 ///
-///     // draw mesh
-///     let mut plot = Plot::new();
+///     // draw mesh (requires the "plot" feature)
+///     // let mut plot = plotpy::Plot::new();
 ///     // triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
 ///     // plot.set_equal_axes(true)
 ///     //     .set_figure_size_points(600.0, 600.0)
@@ -193,6 +232,22 @@ pub enum VoronoiEdgePoint {
 ///
 /// ![doc_triangle_mesh_1.svg](https://raw.githubusercontent.com/cpmech/tritet/main/data/figures/doc_triangle_mesh_1.svg)
 ///
+/// # Reuse
+///
+/// The generate methods (and `refine_mesh`) may be called multiple times on the same instance;
+/// e.g., to tune the maximum area or the quality constraints. The input data (points, segments,
+/// regions, and holes) is kept and only the output of the previous call is released by the c-code.
+///
+/// # Threads
+///
+/// Triangle is `Send`; thus, independent instances may be created and used in different threads
+/// (e.g., one instance per task in a thread pool). The instance is not `Sync` and cannot be
+/// shared by threads.
+///
+/// The generations run concurrently without any lock because the c-code keeps no shared mutable
+/// state: the data is owned by each instance and the global variables of the c-code (e.g., the
+/// random seed, the constants of the exact arithmetic, and the callbacks) are thread-local.
+///
 /// # Definition of geometric terms -- by J.R.Shewchuk
 ///
 /// For convenience, the following definitions are mirrored from [J. R. Shewchuk' Triangle Website](https://www.cs.cmu.edu/~quake/triangle.defs.html#ccdt).
@@ -214,17 +269,31 @@ pub enum VoronoiEdgePoint {
 /// * **Jonathan Richard Shewchuk**, Triangle: Engineering a 2D Quality Mesh Generator and Delaunay Triangulator, in Applied Computational Geometry: Towards Geometric Engineering (Ming C. Lin and Dinesh Manocha, editors), volume 1148 of Lecture Notes in Computer Science, pages 203-222, Springer-Verlag, Berlin, May 1996.
 /// * **Jonathan Richard Shewchuk**, Delaunay Refinement Algorithms for Triangular Mesh Generation, Computational Geometry: Theory and Applications 22(1-3):21-74, May 2002.
 pub struct Triangle {
-    ext_triangle: *mut ExtTriangle, // data allocated by the c-code
-    npoint: usize,                  // number of points
-    nsegment: Option<usize>,        // number of segments
-    nregion: Option<usize>,         // number of regions
-    nhole: Option<usize>,           // number of holes
-    all_points_set: bool,           // indicates that all points have been set
-    all_segments_set: bool,         // indicates that all segments have been set
-    all_regions_set: bool,          // indicates that all regions have been set
-    all_holes_set: bool,            // indicates that all holes have been set
+    ext_triangle: *mut ExtTriangle,                    // data allocated by the c-code
+    npoint: usize,                                     // number of points
+    nsegment: Option<usize>,                           // number of segments
+    nregion: Option<usize>,                            // number of regions
+    nhole: Option<usize>,                              // number of holes
+    all_points_set: bool,                              // indicates that all points have been set
+    all_segments_set: bool,                            // indicates that all segments have been set
+    all_regions_set: bool,                             // indicates that all regions have been set
+    all_holes_set: bool,                               // indicates that all holes have been set
+    colormap: ColorMap,                                // colors of the attributes used in drawings
+    recorder: Option<Recorder>,                        // writes the calls to a script (see start_recording)
+    steiner: SteinerOptions,                           // constraints on the Steiner points inserted by generate_mesh
+    transform: Option<AffineMap<2>>,                   // maps the output coordinates to the global frame
+    jitter: Option<f64>, // perturbation applied to the degenerate points (see set_jitter_on_degeneracy)
+    jittered: RefCell<Vec<usize>>, // points moved by the last call to a generate method
+    vertex_to_cell: RefCell<Option<Arc<CsrGraph>>>, // point-to-cell map (built on demand)
+    cell_to_cell: RefCell<Option<Arc<CsrGraph>>>, // cell-to-cell adjacency (built on demand)
+    point_tags: Vec<u64>, // user-supplied tags of the input points (see set_point_tag)
+    console: RefCell<Option<Box<dyn ConsoleHandler>>>, // receives the console output (see set_console_handler)
 }
 
+// The data allocated by the c-code is owned by this instance only, thus the instance can be moved
+// to another thread. The instance is not Sync because the generate methods modify the c-data.
+unsafe impl Send for Triangle {}
+
 impl Drop for Triangle {
     /// Tells the c-code to release memory
     fn drop(&mut self) {
@@ -278,12 +347,103 @@ impl Triangle {
                 all_segments_set: false,
                 all_regions_set: false,
                 all_holes_set: false,
+                colormap: ColorMap::light(),
+                recorder: None,
+                steiner: SteinerOptions::default(),
+                transform: None,
+                jitter: None,
+                jittered: RefCell::new(Vec::new()),
+                vertex_to_cell: RefCell::new(None),
+                cell_to_cell: RefCell::new(None),
+                point_tags: vec![0; npoint],
+                console: RefCell::new(None),
             })
         }
     }
 
+    /// Resets the instance to hold a new input with the given sizes
+    ///
+    /// This function is equivalent to `new` but reuses the allocations of the c-code where possible
+    /// (e.g., when generating thousands of meshes of the same size). The input data (points,
+    /// segments, regions, holes, and point tags) must be set again and the output is released.
+    /// The other settings (e.g., the output transform and the jitter) are kept.
+    ///
+    /// # Input
+    ///
+    /// * `npoint`, `nsegment`, `nregion`, `nhole` -- the same as in `new`
+    pub fn reset(
+        &mut self,
+        npoint: usize,
+        nsegment: Option<usize>,
+        nregion: Option<usize>,
+        nhole: Option<usize>,
+    ) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            format!(
+                "reset {} {} {} {}",
+                npoint,
+                option_to_string(&nsegment),
+                option_to_string(&nregion),
+                option_to_string(&nhole)
+            )
+        })?;
+        if npoint < 3 {
+            return Err("npoint must be ≥ 3");
+        }
+        if let Some(ns) = nsegment {
+            if ns < 3 {
+                return Err("nsegment must be ≥ 3");
+            }
+        }
+        unsafe {
+            let status = reset_triangle(
+                self.ext_triangle,
+                to_i32(npoint),
+                to_i32(nsegment.unwrap_or(0)),
+                to_i32(nregion.unwrap_or(0)),
+                to_i32(nhole.unwrap_or(0)),
+            );
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        self.npoint = npoint;
+        self.nsegment = nsegment;
+        self.nregion = nregion;
+        self.nhole = nhole;
+        self.all_points_set = false;
+        self.all_segments_set = false;
+        self.all_regions_set = false;
+        self.all_holes_set = false;
+        self.jittered.borrow_mut().clear();
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        self.point_tags.clear();
+        self.point_tags.resize(npoint, 0);
+        Ok(self)
+    }
+
+    /// Releases the output data (mesh and Voronoi tessellation) but keeps the input data
+    pub fn clear_outputs(&mut self) -> Result<&mut Self, StrError> {
+        self.record(|| "clear_outputs".to_string())?;
+        unsafe {
+            clear_triangle_outputs(self.ext_triangle);
+        }
+        self.jittered.borrow_mut().clear();
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        Ok(self)
+    }
+
     /// Sets the point coordinates
     pub fn set_point(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_point {} {} {}", index, x, y))?;
         unsafe {
             let status = set_point(self.ext_triangle, to_i32(index), x, y);
             if status != constants::TRITET_SUCCESS {
@@ -307,6 +467,58 @@ impl Triangle {
         Ok(self)
     }
 
+    /// Sets the coordinates of all points at once
+    ///
+    /// This function copies all coordinates to the c-code in a single call; thus, it is
+    /// much faster than calling `set_point` for each point of large point sets.
+    ///
+    /// # Input
+    ///
+    /// * `coordinates` -- the (x, y) coordinates of each point; the length must be equal to `npoint` (passed down to `new`)
+    pub fn set_points(&mut self, coordinates: &[[f64; 2]]) -> Result<&mut Self, StrError> {
+        if coordinates.len() != self.npoint {
+            return Err("the number of coordinates must be equal to npoint");
+        }
+        for (index, x) in coordinates.iter().enumerate() {
+            self.record(|| format!("set_point {} {} {}", index, x[0], x[1]))?;
+        }
+        unsafe {
+            let status = set_points(
+                self.ext_triangle,
+                to_i32(self.npoint),
+                coordinates.as_ptr() as *const f64,
+            );
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                    return Err("INTERNAL ERROR: found NULL point list");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        self.all_points_set = true;
+        Ok(self)
+    }
+
+    /// Sets a user-supplied tag of a point (e.g., a stable ID in an external database)
+    ///
+    /// The tags are carried to the output points (see `point_tag`). The default tag is zero.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the point and goes from 0 to `npoint` (passed down to `new`)
+    /// * `tag` -- is the tag of the point
+    pub fn set_point_tag(&mut self, index: usize, tag: u64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_point_tag {} {}", index, tag))?;
+        if index >= self.npoint {
+            return Err("index of point is out of bounds");
+        }
+        self.point_tags[index] = tag;
+        Ok(self)
+    }
+
     /// Sets the segment endpoint IDs
     ///
     /// # Input
@@ -315,6 +527,7 @@ impl Triangle {
     /// * `a` -- is the ID (index) of the first point on the segment
     /// * `b` -- is the ID (index) of the second point on the segment
     pub fn set_segment(&mut self, index: usize, a: usize, b: usize) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_segment {} {} {}", index, a, b))?;
         let nsegment = match self.nsegment {
             Some(n) => n,
             None => return Err("cannot set segment because the number of segments is None"),
@@ -345,6 +558,41 @@ impl Triangle {
         Ok(self)
     }
 
+    /// Sets the marker of a segment
+    ///
+    /// The marker is transferred to the edges and points generated on the segment, including
+    /// the ones on the boundary of holes. The default marker is zero, in which case Triangle
+    /// marks the boundary edges and points with 1. Use markers other than 0 and 1 to identify
+    /// the segments (e.g., to apply boundary conditions). The points shared by segments with
+    /// different markers receive the smallest marker (see `point_marker`).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the segment and goes from 0 to `nsegment` (passed down to `new`)
+    /// * `marker` -- is the marker of the segment
+    pub fn set_segment_marker(&mut self, index: usize, marker: i32) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_segment_marker {} {}", index, marker))?;
+        if self.nsegment.is_none() {
+            return Err("cannot set segment marker because the number of segments is None");
+        }
+        unsafe {
+            let status = set_segment_marker(self.ext_triangle, to_i32(index), marker);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_SEGMENT_LIST {
+                    return Err("INTERNAL ERROR: found NULL segment list");
+                }
+                if status == constants::TRITET_ERROR_INVALID_SEGMENT_INDEX {
+                    return Err("index of segment is out of bounds");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        Ok(self)
+    }
+
     /// Marks a region within the Planar Straight Line Graph (PSLG)
     ///
     /// # Input
@@ -352,14 +600,69 @@ impl Triangle {
     /// * `index` -- is the index of the region and goes from 0 to `nregion` (passed down to `new`)
     /// * `x` -- is the x-coordinate of the region
     /// * `y` -- is the y-coordinate of the region
-    /// * `attribute` -- is the attribute ID to group the triangles belonging to this region
+    /// * `attribute` -- is the attribute ID to group the triangles belonging to this region (may be zero or negative)
     /// * `max_area` -- is the maximum area constraint for the triangles belonging to this region
     pub fn set_region(
         &mut self,
         index: usize,
         x: f64,
         y: f64,
-        attribute: usize,
+        attribute: i32,
+        max_area: Option<f64>,
+    ) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            format!(
+                "set_region {} {} {} {} {}",
+                index,
+                x,
+                y,
+                attribute,
+                option_to_string(&max_area)
+            )
+        })?;
+        self.update_region(index, x, y, attribute as f64, max_area)
+    }
+
+    /// Marks a region within the Planar Straight Line Graph (PSLG) with a floating-point attribute
+    ///
+    /// This function is similar to `set_region`, but keeps fractional and negative attributes
+    /// (see `triangle_attribute_f64`). Only one attribute per region is supported.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the region and goes from 0 to `nregion` (passed down to `new`)
+    /// * `x` -- is the x-coordinate of the region
+    /// * `y` -- is the y-coordinate of the region
+    /// * `attribute` -- is the attribute to group the triangles belonging to this region
+    /// * `max_area` -- is the maximum area constraint for the triangles belonging to this region
+    pub fn set_region_f64(
+        &mut self,
+        index: usize,
+        x: f64,
+        y: f64,
+        attribute: f64,
+        max_area: Option<f64>,
+    ) -> Result<&mut Self, StrError> {
+        self.record(|| {
+            format!(
+                "set_region_f64 {} {} {} {} {}",
+                index,
+                x,
+                y,
+                attribute,
+                option_to_string(&max_area)
+            )
+        })?;
+        self.update_region(index, x, y, attribute, max_area)
+    }
+
+    /// Sets the region data in the C structure (see `set_region` and `set_region_f64`)
+    fn update_region(
+        &mut self,
+        index: usize,
+        x: f64,
+        y: f64,
+        attribute: f64,
         max_area: Option<f64>,
     ) -> Result<&mut Self, StrError> {
         let nregion = match self.nregion {
@@ -371,14 +674,7 @@ impl Triangle {
             None => -1.0,
         };
         unsafe {
-            let status = set_region(
-                self.ext_triangle,
-                to_i32(index),
-                x,
-                y,
-                to_i32(attribute),
-                area_constraint,
-            );
+            let status = set_region(self.ext_triangle, to_i32(index), x, y, attribute, area_constraint);
             if status != constants::TRITET_SUCCESS {
                 if status == constants::TRITET_ERROR_NULL_DATA {
                     return Err("INTERNAL ERROR: found NULL data");
@@ -408,6 +704,7 @@ impl Triangle {
     /// * `x` -- is the x-coordinate of the hole
     /// * `y` -- is the y-coordinate of the hole
     pub fn set_hole(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, StrError> {
+        self.record(|| format!("set_hole {} {} {}", index, x, y))?;
         let nhole = match self.nhole {
             Some(n) => n,
             None => return Err("cannot set hole because the number of holes is None"),
@@ -441,22 +738,25 @@ impl Triangle {
     ///
     /// * `verbose` -- Prints Triangle's messages to the console
     pub fn generate_delaunay(&self, verbose: bool) -> Result<(), StrError> {
+        self.record(|| format!("generate_delaunay {}", verbose))?;
         if !self.all_points_set {
             return Err("cannot generate Delaunay triangulation because not all points are set");
         }
-        unsafe {
-            let status = run_delaunay(self.ext_triangle, if verbose { 1 } else { 0 });
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
+        self.run_with_jitter(|| {
+            unsafe {
+                let status = self.with_handlers(|| run_delaunay(self.ext_triangle, if verbose { 1 } else { 0 }));
+                if status != constants::TRITET_SUCCESS {
+                    if status == constants::TRITET_ERROR_NULL_DATA {
+                        return Err("INTERNAL ERROR: found NULL data");
+                    }
+                    if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                        return Err("INTERNAL ERROR: found NULL point list");
+                    }
+                    return Err("INTERNAL ERROR: some error occurred");
                 }
-                return Err("INTERNAL ERROR: some error occurred");
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Generates a Voronoi tessellation and Delaunay triangulation
@@ -465,22 +765,25 @@ impl Triangle {
     ///
     /// * `verbose` -- Prints Triangle's messages to the console
     pub fn generate_voronoi(&self, verbose: bool) -> Result<(), StrError> {
+        self.record(|| format!("generate_voronoi {}", verbose))?;
         if !self.all_points_set {
             return Err("cannot generate Voronoi tessellation because not all points are set");
         }
-        unsafe {
-            let status = run_voronoi(self.ext_triangle, if verbose { 1 } else { 0 });
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
+        self.run_with_jitter(|| {
+            unsafe {
+                let status = self.with_handlers(|| run_voronoi(self.ext_triangle, if verbose { 1 } else { 0 }));
+                if status != constants::TRITET_SUCCESS {
+                    if status == constants::TRITET_ERROR_NULL_DATA {
+                        return Err("INTERNAL ERROR: found NULL data");
+                    }
+                    if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                        return Err("INTERNAL ERROR: found NULL point list");
+                    }
+                    return Err("INTERNAL ERROR: some error occurred");
                 }
-                return Err("INTERNAL ERROR: some error occurred");
             }
-        }
-        Ok(())
+            Ok(())
+        })
     }
 
     /// Generates a conforming constrained Delaunay triangulation with some quality constraints
@@ -498,28 +801,79 @@ impl Triangle {
         global_max_area: Option<f64>,
         global_min_angle: Option<f64>,
     ) -> Result<(), StrError> {
+        self.record(|| {
+            format!(
+                "generate_mesh {} {} {} {}",
+                verbose,
+                quadratic,
+                option_to_string(&global_max_area),
+                option_to_string(&global_min_angle)
+            )
+        })?;
+        self.run_generate_mesh(&TriangleOptions {
+            verbose,
+            quadratic,
+            max_area: global_max_area,
+            min_angle: global_min_angle,
+            steiner: self.steiner,
+            conforming_delaunay: false,
+            enforce_min_angle: false,
+        })
+    }
+
+    /// Generates a conforming constrained Delaunay triangulation with the given options
+    ///
+    /// **Note:** The Steiner options given to `set_steiner_options` are ignored; `options.steiner` is used instead.
+    pub fn generate_mesh_with(&self, options: &TriangleOptions) -> Result<(), StrError> {
+        self.record(|| {
+            format!(
+                "generate_mesh_with {} {} {} {} {} {} {} {} {}",
+                options.verbose,
+                options.quadratic,
+                option_to_string(&options.max_area),
+                option_to_string(&options.min_angle),
+                option_to_string(&options.steiner.max_steiner_points),
+                options.steiner.prohibit_steiner_on_boundary,
+                options.steiner.prohibit_steiner_in_interior,
+                options.conforming_delaunay,
+                options.enforce_min_angle
+            )
+        })?;
+        self.run_generate_mesh(options)
+    }
+
+    /// Runs Triangle to generate a mesh
+    fn run_generate_mesh(&self, options: &TriangleOptions) -> Result<(), StrError> {
         if !self.all_points_set {
             return Err("cannot generate mesh of triangles because not all points are set");
         }
         if !self.all_segments_set {
             return Err("cannot generate mesh of triangles because not all segments are set");
         }
-        let max_area = match global_max_area {
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let max_area = match options.max_area {
             Some(v) => v,
             None => 0.0,
         };
-        let min_angle = match global_min_angle {
+        let min_angle = match options.min_angle {
             Some(v) => v,
             None => 0.0,
         };
+        let (max_steiner_points, no_bisect) = options.steiner.to_c();
         unsafe {
-            let status = run_triangulate(
-                self.ext_triangle,
-                if verbose { 1 } else { 0 },
-                if quadratic { 1 } else { 0 },
-                max_area,
-                min_angle,
-            );
+            let status = self.with_handlers(|| {
+                run_triangulate(
+                    self.ext_triangle,
+                    if options.verbose { 1 } else { 0 },
+                    if options.quadratic { 1 } else { 0 },
+                    max_area,
+                    min_angle,
+                    max_steiner_points,
+                    no_bisect,
+                    if options.conforming_delaunay { 1 } else { 0 },
+                )
+            });
             if status != constants::TRITET_SUCCESS {
                 if status == constants::TRITET_ERROR_NULL_DATA {
                     return Err("INTERNAL ERROR: found NULL data");
@@ -536,9 +890,161 @@ impl Triangle {
                 return Err("INTERNAL ERROR: some error occurred");
             }
         }
+        if options.enforce_min_angle {
+            self.check_min_angle(options.min_angle)?;
+        }
+        Ok(())
+    }
+
+    /// Refines the current mesh with an area constraint for each triangle
+    ///
+    /// The output of the previous call to `generate_delaunay`, `generate_mesh` (or `refine_mesh`)
+    /// is fed back to Triangle (switch `-r`); thus, the refined mesh conforms to the previous one.
+    /// The segments and attributes of the previous mesh are kept. The middle nodes of quadratic
+    /// triangles are discarded and regenerated if `options.quadratic` is true.
+    ///
+    /// # Input
+    ///
+    /// * `per_cell_max_area` -- The maximum area of the triangles generated from each triangle of
+    ///   the current mesh (len = ntriangle); zero or negative values mean no constraint
+    ///   (see also `per_cell_max_area_by_attribute`)
+    /// * `options` -- The options of the refinement; `options.max_area` is also applied to all triangles
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle, TriangleOptions};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, Some(4), None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?;
+    ///     triangle.generate_mesh(false, false, None, None)?;
+    ///     assert_eq!(triangle.ntriangle(), 2);
+    ///
+    ///     // refine the first triangle only
+    ///     triangle.refine_mesh(&[0.01, 0.0], &TriangleOptions::new())?;
+    ///     assert!(triangle.ntriangle() > 50);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn refine_mesh(&self, per_cell_max_area: &[f64], options: &TriangleOptions) -> Result<(), StrError> {
+        self.record(|| {
+            let areas: Vec<String> = per_cell_max_area.iter().map(|a| format!("{}", a)).collect();
+            format!(
+                "refine_mesh {} {} {} {} {} {} {} {} {} {}",
+                areas.join(","),
+                options.verbose,
+                options.quadratic,
+                option_to_string(&options.max_area),
+                option_to_string(&options.min_angle),
+                option_to_string(&options.steiner.max_steiner_points),
+                options.steiner.prohibit_steiner_on_boundary,
+                options.steiner.prohibit_steiner_in_interior,
+                options.conforming_delaunay,
+                options.enforce_min_angle
+            )
+        })?;
+        let ntriangle = self.ntriangle();
+        if ntriangle == 0 {
+            return Err("cannot refine mesh because there is no mesh yet");
+        }
+        if per_cell_max_area.len() != ntriangle {
+            return Err("per_cell_max_area.len() must be equal to ntriangle");
+        }
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let max_area = options.max_area.unwrap_or(0.0);
+        let min_angle = options.min_angle.unwrap_or(0.0);
+        let (max_steiner_points, no_bisect) = options.steiner.to_c();
+        unsafe {
+            let status = self.with_handlers(|| {
+                run_refine(
+                    self.ext_triangle,
+                    if options.verbose { 1 } else { 0 },
+                    if options.quadratic { 1 } else { 0 },
+                    per_cell_max_area.as_ptr(),
+                    max_area,
+                    min_angle,
+                    max_steiner_points,
+                    no_bisect,
+                    if options.conforming_delaunay { 1 } else { 0 },
+                )
+            });
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
+                }
+                if status == constants::TRITET_ERROR_NULL_MESH {
+                    return Err("INTERNAL ERROR: found NULL mesh");
+                }
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
+                }
+                if status == constants::TRITET_ERROR_STRING_CONCAT {
+                    return Err("INTERNAL ERROR: cannot write string with commands for Triangle");
+                }
+                return Err("INTERNAL ERROR: some error occurred");
+            }
+        }
+        if options.enforce_min_angle {
+            self.check_min_angle(options.min_angle)?;
+        }
         Ok(())
     }
 
+    /// Returns the area constraints of `refine_mesh` given the maximum area of each attribute
+    ///
+    /// The attributes are those of the regions (see `set_region`); thus, the regions of the
+    /// current mesh can be refined independently, e.g., according to an error estimator that
+    /// computes one indicator per region. The triangles whose attribute is not in `max_area`
+    /// have no constraint (zero).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use tritet::{StrError, Triangle, TriangleOptions};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, Some(5), Some(2), None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?
+    ///         .set_segment(4, 0, 2)?
+    ///         .set_region(0, 0.9, 0.3, 1, None)?
+    ///         .set_region(1, 0.1, 0.7, 2, None)?;
+    ///     triangle.generate_mesh(false, false, None, None)?;
+    ///
+    ///     // refine the region with attribute 1 only
+    ///     let areas = triangle.per_cell_max_area_by_attribute(&HashMap::from([(1, 0.01)]));
+    ///     triangle.refine_mesh(&areas, &TriangleOptions::new())?;
+    ///     let n1 = (0..triangle.ntriangle()).filter(|t| triangle.triangle_attribute(*t) == 1).count();
+    ///     let n2 = (0..triangle.ntriangle()).filter(|t| triangle.triangle_attribute(*t) == 2).count();
+    ///     assert!(n1 > 50);
+    ///     assert!(n2 < n1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn per_cell_max_area_by_attribute(&self, max_area: &HashMap<i32, f64>) -> Vec<f64> {
+        (0..self.ntriangle())
+            .map(|t| *max_area.get(&self.triangle_attribute(t)).unwrap_or(&0.0))
+            .collect()
+    }
+
     /// Returns the number of points of the Delaunay triangulation (constrained or not)
     pub fn npoint(&self) -> usize {
         unsafe { get_npoint(self.ext_triangle) as usize }
@@ -564,8 +1070,57 @@ impl Triangle {
     /// # Warning
     ///
     /// This function will return 0.0 if either `index` or `dim` are out of range.
+    ///
+    /// **Note:** The coordinates are mapped by the output transform, if any (see `set_output_transform`).
     pub fn point(&self, index: usize, dim: usize) -> f64 {
-        unsafe { get_point(self.ext_triangle, to_i32(index), to_i32(dim)) }
+        match &self.transform {
+            None => unsafe { get_point(self.ext_triangle, to_i32(index), to_i32(dim)) },
+            Some(map) => {
+                if index >= self.npoint() || dim > 1 {
+                    return 0.0;
+                }
+                let x = unsafe {
+                    [
+                        get_point(self.ext_triangle, to_i32(index), 0),
+                        get_point(self.ext_triangle, to_i32(index), 1),
+                    ]
+                };
+                map.apply(&x)[dim]
+            }
+        }
+    }
+
+    /// Returns the tag of an output point (see `set_point_tag`)
+    ///
+    /// The output points corresponding to input points have the tag given to `set_point_tag`.
+    /// The points generated by Triangle (e.g., Steiner and middle points) have the tag zero.
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if `index` is out of range.
+    pub fn point_tag(&self, index: usize) -> u64 {
+        match self.point_original_index(index) {
+            Some(p) => self.point_tags[p],
+            None => 0,
+        }
+    }
+
+    /// Returns the index of the input point corresponding to an output point
+    ///
+    /// The correspondence is recorded by the generate methods; thus, it is exact even if the
+    /// coordinates coincide or are perturbed (see `set_jitter_on_degeneracy`). After `refine_mesh`,
+    /// the points unused by the previous mesh are removed and the remaining points are renumbered;
+    /// the correspondence follows this renumbering. The points generated by Triangle (e.g.,
+    /// Steiner and middle points) have no corresponding input point.
+    ///
+    /// Returns None if `index` is out of range or the point was generated by Triangle.
+    pub fn point_original_index(&self, index: usize) -> Option<usize> {
+        let original = unsafe { get_point_original_index(self.ext_triangle, to_i32(index)) };
+        if original < 0 {
+            None
+        } else {
+            Some(original as usize)
+        }
     }
 
     /// Returns the ID of a triangle's node
@@ -601,37 +1156,169 @@ impl Triangle {
     /// # Warning
     ///
     /// This function will return 0 if either `index` is out of range.
-    pub fn triangle_attribute(&self, index: usize) -> usize {
-        unsafe { get_triangle_attribute(self.ext_triangle, to_i32(index)) as usize }
+    pub fn triangle_attribute(&self, index: usize) -> i32 {
+        unsafe { get_triangle_attribute(self.ext_triangle, to_i32(index)) }
     }
 
-    /// Returns the number of points of the Voronoi tessellation
-    pub fn voronoi_npoint(&self) -> usize {
-        unsafe { get_voronoi_npoint(self.ext_triangle) as usize }
+    /// Returns the attribute of a triangle as given to `set_region_f64` (or `set_region`)
+    ///
+    /// **Note:** Triangle stores a single regional attribute per region; thus, each triangle has
+    /// exactly one attribute (the one of the region containing it).
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if either `index` is out of range.
+    pub fn triangle_attribute_f64(&self, index: usize) -> f64 {
+        unsafe { get_triangle_attribute_f64(self.ext_triangle, to_i32(index)) }
     }
 
-    /// Returns the x-y coordinates of a point on the Voronoi tessellation
+    /// Returns the triangle sharing a side with a triangle
+    ///
+    /// ```text
+    ///       2
+    ///      / \
+    ///     /   \
+    ///    2     1    The side s goes from
+    ///   /       \   node s to node (s + 1) % 3
+    ///  /         \
+    /// 0-----0-----1
+    /// ```
     ///
     /// # Input
     ///
-    /// * `index` -- is the index of the point and goes from 0 to `voronoi_npoint`
-    /// * `dim` -- is the space dimension index: 0 or 1
+    /// * `index` -- is the index of the triangle and goes from 0 to `ntriangle`
+    /// * `side` -- is the local index of the side: 0, 1, or 2
     ///
-    /// # Warning
+    /// # Output
     ///
-    /// This function will return 0.0 if either `index` or `dim` are out of range.
-    pub fn voronoi_point(&self, index: usize, dim: usize) -> f64 {
-        unsafe { get_voronoi_point(self.ext_triangle, to_i32(index), to_i32(dim)) }
+    /// Returns the index of the neighbor or None if the side is on the boundary
+    /// (or if either `index` or `side` are out of range).
+    pub fn triangle_neighbor(&self, index: usize, side: usize) -> Option<usize> {
+        if side > 2 {
+            return None;
+        }
+        // Triangle stores the neighbor opposite to each corner
+        let corner = [2, 0, 1][side];
+        let id = unsafe { get_triangle_neighbor(self.ext_triangle, to_i32(index), corner) };
+        if id < 0 {
+            None
+        } else {
+            Some(id as usize)
+        }
     }
 
-    /// Returns the number of edges on the Voronoi tessellation
-    pub fn voronoi_nedge(&self) -> usize {
-        unsafe { get_voronoi_nedge(self.ext_triangle) as usize }
+    /// Returns the point-to-cell map in compressed sparse row (CSR) format
+    ///
+    /// The triangles sharing the point `p` are `adjncy[xadj[p]..xadj[p + 1]]` (sorted); i.e.,
+    /// this is `node_to_cells` in CSR format, as required by the assembly of finite element
+    /// matrices. The map is computed once and cached until the mesh is generated again; thus,
+    /// the returned pointer shares the cached map (cloning it is cheap).
+    pub fn vertex_to_cell_map(&self) -> Arc<CsrGraph> {
+        self.vertex_to_cell
+            .borrow_mut()
+            .get_or_insert_with(|| Arc::new(CsrGraph::from_lists(self.node_to_cells())))
+            .clone()
     }
 
-    /// Returns the index of the first endpoint on a Voronoi edge
-    ///
-    /// # Input
+    /// Returns the cell-to-cell adjacency (the triangles sharing an edge) in CSR format
+    ///
+    /// This is `cell_adjacency_csr` computed once and cached until the mesh is generated again;
+    /// thus, the returned pointer shares the cached adjacency (cloning it is cheap).
+    pub fn cell_to_cell_adjacency(&self) -> Arc<CsrGraph> {
+        self.cell_to_cell
+            .borrow_mut()
+            .get_or_insert_with(|| Arc::new(self.cell_adjacency_csr()))
+            .clone()
+    }
+
+    /// Returns the marker of a point
+    ///
+    /// The points on a segment have the marker of the segment (see `set_segment_marker`); the
+    /// other points on the boundary have the marker 1 and the interior points have the marker 0.
+    /// The points shared by segments with different markers (other than 0 and 1) receive the
+    /// smallest of these markers, regardless of the order of the segments.
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` is out of range.
+    pub fn point_marker(&self, index: usize) -> i32 {
+        unsafe { get_point_marker(self.ext_triangle, to_i32(index)) }
+    }
+
+    /// Returns the number of edges of the Delaunay triangulation (constrained or not)
+    ///
+    /// **Note:** All edges of the triangulation are given, not only the segments.
+    pub fn nedge(&self) -> usize {
+        unsafe { get_nedge(self.ext_triangle) as usize }
+    }
+
+    /// Returns the ID of an endpoint of an edge
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `nedge`
+    /// * `side` -- is the local index of the endpoint: 0 or 1
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` or `side` are out of range.
+    pub fn edge_point(&self, index: usize, side: usize) -> usize {
+        unsafe { get_edge_point(self.ext_triangle, to_i32(index), to_i32(side)) as usize }
+    }
+
+    /// Returns the marker of an edge
+    ///
+    /// Triangle marks the edges on the boundary with 1 and the interior edges with 0, unless
+    /// the edge lies on a segment with another marker (see `set_segment_marker`).
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0 if either `index` is out of range.
+    pub fn edge_marker(&self, index: usize) -> i32 {
+        unsafe { get_edge_marker(self.ext_triangle, to_i32(index)) }
+    }
+
+    /// Returns the number of points of the Voronoi tessellation
+    pub fn voronoi_npoint(&self) -> usize {
+        unsafe { get_voronoi_npoint(self.ext_triangle) as usize }
+    }
+
+    /// Returns the x-y coordinates of a point on the Voronoi tessellation
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the point and goes from 0 to `voronoi_npoint`
+    /// * `dim` -- is the space dimension index: 0 or 1
+    ///
+    /// # Warning
+    ///
+    /// This function will return 0.0 if either `index` or `dim` are out of range.
+    pub fn voronoi_point(&self, index: usize, dim: usize) -> f64 {
+        match &self.transform {
+            None => unsafe { get_voronoi_point(self.ext_triangle, to_i32(index), to_i32(dim)) },
+            Some(map) => {
+                if index >= self.voronoi_npoint() || dim > 1 {
+                    return 0.0;
+                }
+                let x = unsafe {
+                    [
+                        get_voronoi_point(self.ext_triangle, to_i32(index), 0),
+                        get_voronoi_point(self.ext_triangle, to_i32(index), 1),
+                    ]
+                };
+                map.apply(&x)[dim]
+            }
+        }
+    }
+
+    /// Returns the number of edges on the Voronoi tessellation
+    pub fn voronoi_nedge(&self) -> usize {
+        unsafe { get_voronoi_nedge(self.ext_triangle) as usize }
+    }
+
+    /// Returns the index of the first endpoint on a Voronoi edge
+    ///
+    /// # Input
     ///
     /// * `index` -- is the index of the edge and goes from 0 to `voronoi_nedge`
     ///
@@ -658,6 +1345,7 @@ impl Triangle {
             if id == -1 {
                 let x = get_voronoi_edge_point_b_direction(self.ext_triangle, index_i32, 0);
                 let y = get_voronoi_edge_point_b_direction(self.ext_triangle, index_i32, 1);
+                let [x, y] = self.transform_linear([x, y]);
                 VoronoiEdgePoint::Direction(x, y)
             } else {
                 VoronoiEdgePoint::Index(id as usize)
@@ -665,205 +1353,387 @@ impl Triangle {
         }
     }
 
-    /// Draw triangles
-    pub fn draw_triangles(
-        &self,
-        plot: &mut Plot,
-        set_range: bool,
-        with_point_ids: bool,
-        with_triangle_ids: bool,
-        with_attribute_ids: bool,
-        fontsize_point_ids: Option<f64>,
-        fontsize_triangle_ids: Option<f64>,
-        fontsize_attribute_ids: Option<f64>,
-    ) {
-        let n_triangle = self.ntriangle();
-        if n_triangle < 1 {
-            return;
-        }
-        let mut canvas = Canvas::new();
-        let mut point_ids = Text::new();
-        let mut triangle_ids = Text::new();
-        let mut attribute_ids = Text::new();
-        if with_point_ids {
-            point_ids
-                .set_color("red")
-                .set_align_horizontal("center")
-                .set_align_vertical("center")
-                .set_bbox(true)
-                .set_bbox_facecolor("white")
-                .set_bbox_alpha(0.8)
-                .set_bbox_style("circle");
-            if let Some(fsz) = fontsize_point_ids {
-                point_ids.set_fontsize(fsz);
-            }
+    /// Returns the number of points given to `new` and whether all of them have been set
+    pub(crate) fn input_npoint(&self) -> (usize, bool) {
+        (self.npoint, self.all_points_set)
+    }
+
+    /// Returns the x-y coordinates of a point given to `set_point` (disregarding the output transform)
+    pub(crate) fn input_point(&self, index: usize) -> [f64; 2] {
+        unsafe {
+            [
+                get_input_point(self.ext_triangle, to_i32(index), 0),
+                get_input_point(self.ext_triangle, to_i32(index), 1),
+            ]
         }
-        if with_triangle_ids {
-            triangle_ids
-                .set_color("blue")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_triangle_ids {
-                triangle_ids.set_fontsize(fsz);
-            }
+    }
+
+    /// Returns the x-y coordinates of an output point (disregarding the output transform)
+    pub(crate) fn raw_point(&self, index: usize) -> [f64; 2] {
+        unsafe {
+            [
+                get_point(self.ext_triangle, to_i32(index), 0),
+                get_point(self.ext_triangle, to_i32(index), 1),
+            ]
         }
-        if with_attribute_ids {
-            attribute_ids
-                .set_color("black")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_attribute_ids {
-                attribute_ids.set_fontsize(fsz);
-            }
+    }
+
+    /// Returns the number of segments given to `new` and whether all of them have been set
+    pub(crate) fn input_nsegment(&self) -> (usize, bool) {
+        (self.nsegment.unwrap_or(0), self.all_segments_set)
+    }
+
+    /// Generates the output from the given triangles (without refinement)
+    ///
+    /// The triangles must be counterclockwise and conform to the segments (see `set_segment`).
+    pub(crate) fn generate_given_mesh(
+        &self,
+        verbose: bool,
+        quadratic: bool,
+        corners: &[[usize; 3]],
+    ) -> Result<(), StrError> {
+        if !self.all_points_set {
+            return Err("cannot generate mesh of triangles because not all points are set");
         }
-        canvas.set_edge_color("black");
-        let mut x = vec![0.0; 2];
-        let mut xmid = vec![0.0; 2];
-        let mut xatt = vec![0.0; 2];
-        let mut min = vec![f64::MAX; 2];
-        let mut max = vec![f64::MIN; 2];
-        let mut colors: HashMap<usize, &'static str> = HashMap::new();
-        let mut index_color = 0;
-        let clr = constants::LIGHT_COLORS;
-        for tri in 0..n_triangle {
-            let attribute = self.triangle_attribute(tri);
-            let color = match colors.get(&attribute) {
-                Some(c) => c,
-                None => {
-                    let c = clr[index_color % clr.len()];
-                    colors.insert(attribute, c);
-                    index_color += 1;
-                    c
+        if !self.all_segments_set {
+            return Err("cannot generate mesh of triangles because not all segments are set");
+        }
+        self.jittered.borrow_mut().clear();
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let corners_i32: Vec<i32> = corners.iter().flatten().map(|p| to_i32(*p)).collect();
+        unsafe {
+            let status = self.with_handlers(|| {
+                run_given_mesh(
+                    self.ext_triangle,
+                    if verbose { 1 } else { 0 },
+                    if quadratic { 1 } else { 0 },
+                    to_i32(corners.len()),
+                    corners_i32.as_ptr(),
+                )
+            });
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_NULL_DATA {
+                    return Err("INTERNAL ERROR: found NULL data");
                 }
-            };
-            canvas.set_face_color(color);
-            canvas.polycurve_begin();
-            for dim in 0..2 {
-                xmid[dim] = 0.0;
-            }
-            for m in 0..3 {
-                let p = self.triangle_node(tri, m);
-                for dim in 0..2 {
-                    x[dim] = self.point(p, dim);
-                    min[dim] = f64::min(min[dim], x[dim]);
-                    max[dim] = f64::max(max[dim], x[dim]);
-                    xmid[dim] += x[dim] / 3.0;
+                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
+                    return Err("INTERNAL ERROR: found NULL point list");
                 }
-                if m == 0 {
-                    canvas.polycurve_add(x[0], x[1], PolyCode::MoveTo);
-                } else {
-                    canvas.polycurve_add(x[0], x[1], PolyCode::LineTo);
+                if status == constants::TRITET_ERROR_NULL_SEGMENT_LIST {
+                    return Err("INTERNAL ERROR: list of segments must be defined first");
                 }
-            }
-            canvas.polycurve_end(true);
-            if with_triangle_ids {
-                triangle_ids.draw(xmid[0], xmid[1], format!("{}", tri).as_str());
-            }
-            if with_attribute_ids {
-                for dim in 0..2 {
-                    x[dim] = self.point(self.triangle_node(tri, 0), dim);
-                    xatt[dim] = (x[dim] + xmid[dim]) / 2.0;
+                if status == constants::TRITET_ERROR_ALLOC {
+                    return Err("INTERNAL ERROR: cannot allocate memory");
                 }
-                attribute_ids.draw(xatt[0], xatt[1], format!("[{}]", attribute).as_str());
+                return Err("INTERNAL ERROR: some error occurred");
             }
         }
-        if with_point_ids {
-            for p in 0..self.npoint() {
-                let x = self.point(p, 0);
-                let y = self.point(p, 1);
-                point_ids.draw(x, y, format!("{}", p).as_str());
-            }
+        Ok(())
+    }
+
+    /// Returns the point IDs of a segment given to `set_segment`
+    pub(crate) fn input_segment(&self, index: usize) -> (usize, usize) {
+        unsafe {
+            let a = get_input_segment(self.ext_triangle, to_i32(index), 0);
+            let b = get_input_segment(self.ext_triangle, to_i32(index), 1);
+            (a as usize, b as usize)
         }
-        plot.add(&canvas);
-        if with_triangle_ids {
-            plot.add(&triangle_ids);
+    }
+
+    /// Returns the number of holes given to `new`
+    pub(crate) fn input_nhole(&self) -> usize {
+        self.nhole.unwrap_or(0)
+    }
+
+    /// Returns the number of regions given to `new`
+    pub(crate) fn input_nregion(&self) -> usize {
+        self.nregion.unwrap_or(0)
+    }
+
+    /// Returns the x-y coordinates of a hole seed given to `set_hole`
+    pub(crate) fn input_hole(&self, index: usize) -> (f64, f64) {
+        unsafe {
+            let x = get_input_hole(self.ext_triangle, to_i32(index), 0);
+            let y = get_input_hole(self.ext_triangle, to_i32(index), 1);
+            let [x, y] = self.transform([x, y]);
+            (x, y)
         }
-        if with_point_ids {
-            plot.add(&point_ids);
+    }
+
+    /// Returns the x-y coordinates and the attribute of a region seed given to `set_region`
+    pub(crate) fn input_region(&self, index: usize) -> (f64, f64, i32) {
+        unsafe {
+            let x = get_input_region(self.ext_triangle, to_i32(index), 0);
+            let y = get_input_region(self.ext_triangle, to_i32(index), 1);
+            let attribute = get_input_region_attribute(self.ext_triangle, to_i32(index));
+            let [x, y] = self.transform([x, y]);
+            (x, y, attribute)
         }
-        if with_attribute_ids {
-            plot.add(&attribute_ids);
+    }
+
+    /// Sets an affine map applied to the output coordinates (e.g., to place a part in a global frame)
+    ///
+    /// The map is applied lazily by `point`, `voronoi_point`, and the functions using them (e.g.,
+    /// drawing and writing functions). The coordinates stored by Triangle are not modified.
+    pub fn set_output_transform(&mut self, map: AffineMap<2>) -> &mut Self {
+        self.transform = Some(map);
+        self
+    }
+
+    /// Removes the affine map applied to the output coordinates
+    pub fn clear_output_transform(&mut self) -> &mut Self {
+        self.transform = None;
+        self
+    }
+
+    /// Applies the output transform to a point
+    fn transform(&self, x: [f64; 2]) -> [f64; 2] {
+        match &self.transform {
+            Some(map) => map.apply(&x),
+            None => x,
         }
-        if set_range {
-            plot.set_range(min[0], max[0], min[1], max[1]);
+    }
+
+    /// Applies the linear part of the output transform to a direction
+    fn transform_linear(&self, v: [f64; 2]) -> [f64; 2] {
+        match &self.transform {
+            Some(map) => map.apply_linear(&v),
+            None => v,
         }
     }
 
-    /// Draws Voronoi diagram
-    pub fn draw_voronoi(&self, plot: &mut Plot) {
-        if self.voronoi_npoint() < 1 || self.voronoi_nedge() < 1 {
-            return;
+    /// Starts writing the calls to the set and generate functions to a script file
+    ///
+    /// The script can be replayed with `replay` (e.g., to reproduce a crash in the c-code).
+    /// This function should be called right after `new` because previous calls are not recorded.
+    ///
+    /// # Input
+    ///
+    /// * `full_path` -- may be a String, &str, or Path
+    pub fn start_recording<P>(&mut self, full_path: &P) -> Result<&mut Self, StrError>
+    where
+        P: AsRef<OsStr> + ?Sized,
+    {
+        let first_line = format!(
+            "new_triangle {} {} {} {}",
+            self.npoint,
+            option_to_string(&self.nsegment),
+            option_to_string(&self.nregion),
+            option_to_string(&self.nhole)
+        );
+        self.recorder = Some(Recorder::new(full_path, first_line)?);
+        Ok(self)
+    }
+
+    /// Writes a call to the script file if recording (see `start_recording`)
+    fn record<F>(&self, call: F) -> Result<(), StrError>
+    where
+        F: FnOnce() -> String,
+    {
+        match &self.recorder {
+            Some(recorder) => recorder.write(call()),
+            None => Ok(()),
         }
-        let mut x = vec![0.0; 2];
-        let mut min = vec![f64::MAX; 2];
-        let mut max = vec![f64::MIN; 2];
-        let mut markers = Curve::new();
-        markers
-            .set_marker_color("gold")
-            .set_marker_line_color("gold")
-            .set_marker_style("o")
-            .set_stop_clip(true);
-        for p in 0..self.npoint() {
-            for dim in 0..2 {
-                x[dim] = self.point(p, dim);
-                min[dim] = f64::min(min[dim], x[dim]);
-                max[dim] = f64::max(max[dim], x[dim]);
+    }
+
+    /// Sets a handler receiving the console output of Triangle instead of stdout
+    ///
+    /// The text printed by the c-code during the next generations (e.g., the messages enabled by
+    /// `verbose` and the warnings) is passed to the handler line by line, without the newline
+    /// character. The handler may forward the lines to a logger or a writer; e.g.,
+    /// `|line: &str| log::info!("{}", line)`. The handler is called by the thread running the
+    /// generation; thus, other instances are not affected.
+    ///
+    /// **Note:** A panic of the handler does not unwind through the c-code; it is caught, the
+    /// handler is not called again, and the panic is resumed when the generation returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(3, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0)?;
+    ///     let output = Arc::new(Mutex::new(String::new()));
+    ///     let sink = output.clone();
+    ///     triangle.set_console_handler(move |line: &str| {
+    ///         let mut text = sink.lock().unwrap();
+    ///         text.push_str(line);
+    ///         text.push('\n');
+    ///     });
+    ///     triangle.generate_delaunay(true)?;
+    ///     assert!(output.lock().unwrap().contains("Delaunay"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn set_console_handler<H>(&mut self, handler: H) -> &mut Self
+    where
+        H: ConsoleHandler + 'static,
+    {
+        self.console.replace(Some(Box::new(handler)));
+        self
+    }
+
+    /// Removes the handler set by `set_console_handler` (the output goes to stdout again)
+    pub fn clear_console_handler(&mut self) -> &mut Self {
+        self.console.replace(None);
+        self
+    }
+
+    /// Runs a call to the c-code passing the console output to the handler (if any)
+    fn with_handlers<F>(&self, run: F) -> i32
+    where
+        F: FnOnce() -> i32,
+    {
+        let mut console = self.console.borrow_mut();
+        let handler: Option<&mut dyn ConsoleHandler> = match console.as_mut() {
+            Some(h) => Some(h.as_mut()),
+            None => None,
+        };
+        run_with_console(handler, run)
+    }
+
+    /// Enables the perturbation of the degenerate points by `generate_delaunay` and `generate_voronoi`
+    ///
+    /// If the output misses some input points (e.g., duplicated points) or has no cells at all (e.g.,
+    /// collinear points), the offending points are moved by at most `eps` along each direction
+    /// and the generation is repeated (up to five times). The perturbation is deterministic.
+    /// The moved points are reported by `jittered_points`. Use `None` to disable (the default).
+    ///
+    /// The input is not modified: the coordinates of the moved points are restored after the
+    /// generation; thus, only the output coordinates of these points differ from the input.
+    pub fn set_jitter_on_degeneracy(&mut self, eps: Option<f64>) -> &mut Self {
+        if let Some(recorder) = &self.recorder {
+            // errors are ignored here and will be reported by the next recorded call
+            let _ = recorder.write(format!("set_jitter_on_degeneracy {}", option_to_string(&eps)));
+        }
+        self.jitter = eps;
+        self
+    }
+
+    /// Returns the (sorted) IDs of the points moved by the last generation (see `set_jitter_on_degeneracy`)
+    pub fn jittered_points(&self) -> Vec<usize> {
+        self.jittered.borrow().clone()
+    }
+
+    /// Runs a generator and removes the degeneracies by jittering the points (if enabled)
+    fn run_with_jitter<F>(&self, run: F) -> Result<(), StrError>
+    where
+        F: Fn() -> Result<(), StrError>,
+    {
+        self.jittered.borrow_mut().clear();
+        self.vertex_to_cell.replace(None);
+        self.cell_to_cell.replace(None);
+        let eps = match self.jitter {
+            Some(eps) => eps,
+            None => return run(),
+        };
+        // the original coordinates of the moved points (restored after the generation)
+        let mut originals: Vec<(usize, [f64; 2])> = Vec::new();
+        let mut result = Err("cannot remove the degeneracy by jittering the points");
+        for attempt in 0..=JITTER_MAX_ATTEMPTS {
+            if let Err(e) = run() {
+                result = Err(e);
+                break;
+            }
+            let points = self.degenerate_points();
+            if points.is_empty() {
+                let mut jittered = self.jittered.borrow_mut();
+                jittered.sort();
+                jittered.dedup();
+                result = Ok(());
+                break;
             }
-            markers.draw(&[x[0]], &[x[1]]);
+            if attempt == JITTER_MAX_ATTEMPTS {
+                break;
+            }
+            for p in &points {
+                let index = to_i32(*p);
+                unsafe {
+                    let x = [
+                        get_input_point(self.ext_triangle, index, 0),
+                        get_input_point(self.ext_triangle, index, 1),
+                    ];
+                    if originals.iter().all(|(q, _)| q != p) {
+                        originals.push((*p, x));
+                    }
+                    set_point(
+                        self.ext_triangle,
+                        index,
+                        x[0] + eps * jitter_offset(*p, 0, attempt),
+                        x[1] + eps * jitter_offset(*p, 1, attempt),
+                    );
+                }
+            }
+            self.jittered.borrow_mut().extend(points);
         }
-        for q in 0..self.voronoi_npoint() {
-            for dim in 0..2 {
-                x[dim] = self.voronoi_point(q, dim);
-                min[dim] = f64::min(min[dim], x[dim]);
-                max[dim] = f64::max(max[dim], x[dim]);
+        for (p, x) in originals {
+            unsafe {
+                set_point(self.ext_triangle, to_i32(p), x[0], x[1]);
             }
         }
-        let mut canvas = Canvas::new();
-        canvas.polycurve_begin();
-        for e in 0..self.voronoi_nedge() {
-            let a = self.voronoi_edge_point_a(e);
-            let xa = self.voronoi_point(a, 0);
-            let ya = self.voronoi_point(a, 1);
-            let b_or_direction = self.voronoi_edge_point_b(e);
-            match b_or_direction {
-                VoronoiEdgePoint::Index(b) => {
-                    let xb = self.voronoi_point(b, 0);
-                    let yb = self.voronoi_point(b, 1);
-                    canvas.polycurve_add(xa, ya, PolyCode::MoveTo);
-                    canvas.polycurve_add(xb, yb, PolyCode::LineTo);
-                }
-                VoronoiEdgePoint::Direction(dx, dy) => {
-                    let mx = if dx > 0.0 {
-                        (max[0] - xa) / dx
-                    } else if dx < 0.0 {
-                        (min[0] - xa) / dx
-                    } else {
-                        0.0
-                    };
-                    let my = if dy > 0.0 {
-                        (max[1] - ya) / dy
-                    } else if dy < 0.0 {
-                        (min[1] - ya) / dy
-                    } else {
-                        0.0
-                    };
-                    let m = if mx < my { mx } else { my };
-                    if m > 0.0 {
-                        let xb = xa + m * dx;
-                        let yb = ya + m * dy;
-                        min[0] = f64::min(min[0], xb);
-                        max[0] = f64::max(max[0], xb);
-                        min[1] = f64::min(min[1], yb);
-                        max[1] = f64::max(max[1], yb);
-                        canvas.polycurve_add(xa, ya, PolyCode::MoveTo);
-                        canvas.polycurve_add(xb, yb, PolyCode::LineTo);
-                    }
+        result
+    }
+
+    /// Returns the input points missing from the output triangles (the coordinates are not transformed)
+    fn degenerate_points(&self) -> Vec<usize> {
+        unsafe {
+            let input: Vec<[f64; 2]> = (0..to_i32(self.npoint))
+                .map(|index| {
+                    [
+                        get_input_point(self.ext_triangle, index, 0),
+                        get_input_point(self.ext_triangle, index, 1),
+                    ]
+                })
+                .collect();
+            let mut used = vec![false; get_npoint(self.ext_triangle) as usize];
+            for cell in 0..get_ntriangle(self.ext_triangle) {
+                for m in 0..3 {
+                    used[get_triangle_corner(self.ext_triangle, cell, m) as usize] = true;
                 }
             }
+            let used_output: Vec<[f64; 2]> = (0..to_i32(used.len()))
+                .filter(|index| used[*index as usize])
+                .map(|index| {
+                    [
+                        get_point(self.ext_triangle, index, 0),
+                        get_point(self.ext_triangle, index, 1),
+                    ]
+                })
+                .collect();
+            find_degenerate_points(&input, &used_output)
+        }
+    }
+
+    /// Sets the constraints on the Steiner points inserted by `generate_mesh` (the default is no constraint)
+    pub fn set_steiner_options(&mut self, options: SteinerOptions) -> &mut Self {
+        if let Some(recorder) = &self.recorder {
+            // errors are ignored here and will be reported by the next recorded call
+            let _ = recorder.write(format!(
+                "set_steiner_options {} {} {}",
+                option_to_string(&options.max_steiner_points),
+                options.prohibit_steiner_on_boundary,
+                options.prohibit_steiner_in_interior
+            ));
         }
-        canvas.polycurve_end(false);
-        plot.set_range(min[0], max[0], min[1], max[1]);
-        plot.add(&canvas).add(&markers);
+        self.steiner = options;
+        self
+    }
+
+    /// Sets the colors of the attributes used by `draw_triangles` and `to_svg` (the default is `ColorMap::light()`)
+    pub fn set_colormap(&mut self, colormap: ColorMap) -> &mut Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Returns the colors of the attributes used by `draw_triangles` and `to_svg`
+    pub fn colormap(&self) -> &ColorMap {
+        &self.colormap
     }
 }
 
@@ -872,8 +1742,9 @@ impl Triangle {
 #[cfg(test)]
 mod tests {
     use super::Triangle;
-    use crate::{StrError, VoronoiEdgePoint};
-    use plotpy::Plot;
+    use crate::fixtures::square;
+    use crate::{StrError, TriangleOptions, VoronoiEdgePoint};
+    use std::collections::HashMap;
 
     #[test]
     fn derive_works() {
@@ -917,6 +1788,103 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_points_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.set_points(&[[0.0, 0.0], [1.0, 0.0]]).err(),
+            Some("the number of coordinates must be equal to npoint")
+        );
+        triangle.set_points(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]])?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 1);
+        assert_eq!(triangle.point(1, 0), 1.0);
+        assert_eq!(triangle.point(2, 1), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn point_tags_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        assert_eq!(
+            triangle.set_point_tag(3, 1).err(),
+            Some("index of point is out of bounds")
+        );
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.set_point_tag(0, 100)?.set_point_tag(2, u64::MAX)?;
+        assert_eq!(triangle.point_tag(0), 0); // no output yet
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        assert!(triangle.npoint() > 3);
+        assert_eq!(triangle.point_tag(0), 100);
+        assert_eq!(triangle.point_tag(1), 0);
+        assert_eq!(triangle.point_tag(2), u64::MAX);
+        assert!((3..triangle.npoint()).all(|p| triangle.point_tag(p) == 0));
+        assert_eq!(triangle.point_tag(triangle.npoint()), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn point_original_index_works() -> Result<(), StrError> {
+        let triangle = square()?;
+        assert_eq!(triangle.point_original_index(0), None); // no output yet
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let check = |triangle: &Triangle| {
+            assert!(triangle.npoint() > 4);
+            assert_eq!(triangle.point_original_index(0), Some(0));
+            assert_eq!(triangle.point_original_index(3), Some(3));
+            assert!((4..triangle.npoint()).all(|p| triangle.point_original_index(p).is_none()));
+            assert_eq!(triangle.point_original_index(triangle.npoint()), None);
+            assert_eq!(triangle.point(2, 0), 1.0);
+            assert_eq!(triangle.point(2, 1), 1.0);
+        };
+        check(&triangle);
+        let areas = vec![0.01; triangle.ntriangle()];
+        triangle.refine_mesh(&areas, TriangleOptions::new().set_quadratic(true))?;
+        check(&triangle);
+
+        // unused input point (inside the hole) removed by the refinement
+        let mut triangle = Triangle::new(9, Some(8), None, Some(1))?;
+        triangle
+            .set_point(0, 0.5, 0.5)?
+            .set_point(1, 0.0, 0.0)?
+            .set_point(2, 1.0, 0.0)?
+            .set_point(3, 1.0, 1.0)?
+            .set_point(4, 0.0, 1.0)?
+            .set_point(5, 0.25, 0.25)?
+            .set_point(6, 0.75, 0.25)?
+            .set_point(7, 0.75, 0.75)?
+            .set_point(8, 0.25, 0.75)?;
+        for i in 0..4 {
+            triangle
+                .set_segment(i, 1 + i, 1 + (i + 1) % 4)?
+                .set_segment(4 + i, 5 + i, 5 + (i + 1) % 4)?;
+        }
+        triangle.set_hole(0, 0.5, 0.5)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let check = |triangle: &Triangle, correct: &[Option<usize>]| {
+            let originals: Vec<_> = (0..correct.len()).map(|p| triangle.point_original_index(p)).collect();
+            assert_eq!(originals, correct);
+            for p in 0..triangle.npoint() {
+                if let Some(q) = triangle.point_original_index(p) {
+                    assert!((0..2).all(|dim| triangle.point(p, dim) == triangle.input_point(q)[dim]));
+                }
+            }
+        };
+        let correct: Vec<_> = (0..9).map(Some).collect();
+        check(&triangle, &correct);
+        let areas = vec![0.1; triangle.ntriangle()];
+        triangle.refine_mesh(&areas, &TriangleOptions::new())?;
+        check(&triangle, &correct[1..]);
+        assert!((8..triangle.npoint()).all(|p| triangle.point_original_index(p).is_none()));
+        Ok(())
+    }
+
     #[test]
     fn set_segment_captures_some_errors() -> Result<(), StrError> {
         let mut triangle = Triangle::new(3, None, None, None)?;
@@ -936,6 +1904,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_segment_marker_captures_some_errors() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.set_segment_marker(0, -1).err(),
+            Some("cannot set segment marker because the number of segments is None")
+        );
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        assert_eq!(
+            triangle.set_segment_marker(3, -1).err(),
+            Some("index of segment is out of bounds")
+        );
+        Ok(())
+    }
+
     #[test]
     fn set_region_captures_some_errors() -> Result<(), StrError> {
         let mut triangle = Triangle::new(3, None, None, None)?;
@@ -1014,6 +1997,19 @@ mod tests {
         assert_eq!(triangle.triangle_node(0, 2), 2);
         assert_eq!(triangle.voronoi_npoint(), 0);
         assert_eq!(triangle.voronoi_nedge(), 0);
+        assert_eq!(triangle.nedge(), 3);
+        let mut edges: Vec<(usize, usize, i32)> = (0..3)
+            .map(|e| {
+                (
+                    triangle.edge_point(e, 0),
+                    triangle.edge_point(e, 1),
+                    triangle.edge_marker(e),
+                )
+            })
+            .map(|(a, b, marker)| (usize::min(a, b), usize::max(a, b), marker))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, &[(0, 1, 1), (0, 2, 1), (1, 2, 1)]);
         Ok(())
     }
 
@@ -1107,42 +2103,28 @@ mod tests {
     }
 
     #[test]
-    fn get_methods_work_with_wrong_indices() -> Result<(), StrError> {
-        let triangle = Triangle::new(3, None, None, None)?;
-        assert_eq!(triangle.point(100, 0), 0.0);
-        assert_eq!(triangle.point(0, 100), 0.0);
-        assert_eq!(triangle.triangle_attribute(100), 0);
-        assert_eq!(triangle.voronoi_point(100, 0), 0.0);
-        assert_eq!(triangle.voronoi_point(0, 100), 0.0);
-        assert_eq!(triangle.voronoi_edge_point_a(100), 0,);
-        assert_eq!(format!("{:?}", triangle.voronoi_edge_point_b(100)), "Index(0)");
-        Ok(())
-    }
-
-    #[test]
-    fn draw_triangles_works() -> Result<(), StrError> {
-        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+    fn edges_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(5, None, None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
             .set_point(1, 1.0, 0.0)?
-            .set_point(2, 0.0, 1.0)?;
-        triangle
-            .set_segment(0, 0, 1)?
-            .set_segment(1, 1, 2)?
-            .set_segment(2, 2, 0)?;
-        triangle.generate_mesh(false, true, Some(0.25), None)?;
-        let mut plot = Plot::new();
-        triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/triangle_draw_triangles_works.svg")?;
-        }
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_point(4, 0.5, 0.5)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.nedge(), 8);
+        let mut boundary: Vec<(usize, usize)> = (0..triangle.nedge())
+            .filter(|e| triangle.edge_marker(*e) == 1)
+            .map(|e| (triangle.edge_point(e, 0), triangle.edge_point(e, 1)))
+            .map(|(a, b)| (usize::min(a, b), usize::max(a, b)))
+            .collect();
+        boundary.sort();
+        assert_eq!(boundary, &[(0, 1), (0, 3), (1, 2), (2, 3)]);
         Ok(())
     }
 
     #[test]
-    fn draw_voronoi_works() -> Result<(), StrError> {
+    fn triangle_neighbor_works() -> Result<(), StrError> {
         let mut triangle = Triangle::new(5, None, None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1150,18 +2132,50 @@ mod tests {
             .set_point(2, 1.0, 1.0)?
             .set_point(3, 0.0, 1.0)?
             .set_point(4, 0.5, 0.5)?;
-        triangle.generate_voronoi(false)?;
-        assert_eq!(triangle.voronoi_npoint(), 4);
-        let mut plot = Plot::new();
-        triangle.draw_voronoi(&mut plot);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/triangle_draw_voronoi_works.svg")?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 4);
+        for cell in 0..4 {
+            let mut nboundary = 0;
+            for side in 0..3 {
+                let a = triangle.triangle_node(cell, side);
+                let b = triangle.triangle_node(cell, (side + 1) % 3);
+                match triangle.triangle_neighbor(cell, side) {
+                    Some(other) => {
+                        // the neighbor has the same side
+                        let nodes: Vec<usize> = (0..3).map(|m| triangle.triangle_node(other, m)).collect();
+                        assert!(nodes.contains(&a) && nodes.contains(&b));
+                        assert_ne!(other, cell);
+                    }
+                    None => {
+                        // the side is on the boundary of the square
+                        assert!(a != 4 && b != 4);
+                        nboundary += 1;
+                    }
+                }
+            }
+            assert_eq!(nboundary, 1);
         }
         Ok(())
     }
 
+    #[test]
+    fn get_methods_work_with_wrong_indices() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(triangle.point(100, 0), 0.0);
+        assert_eq!(triangle.point(0, 100), 0.0);
+        assert_eq!(triangle.triangle_attribute(100), 0);
+        assert_eq!(triangle.voronoi_point(100, 0), 0.0);
+        assert_eq!(triangle.voronoi_point(0, 100), 0.0);
+        assert_eq!(triangle.voronoi_edge_point_a(100), 0,);
+        assert_eq!(triangle.edge_point(100, 0), 0);
+        assert_eq!(triangle.edge_point(0, 100), 0);
+        assert_eq!(triangle.edge_marker(100), 0);
+        assert_eq!(triangle.triangle_neighbor(100, 0), None);
+        assert_eq!(triangle.triangle_neighbor(0, 100), None);
+        assert_eq!(format!("{:?}", triangle.voronoi_edge_point_b(100)), "Index(0)");
+        Ok(())
+    }
+
     #[test]
     fn mesh_3_works() -> Result<(), StrError> {
         let mut triangle = Triangle::new(4, Some(3), Some(1), None)?;
@@ -1179,12 +2193,15 @@ mod tests {
         assert_eq!(triangle.ntriangle(), 2);
         assert_eq!(triangle.triangle_attribute(0), 1);
         assert_eq!(triangle.triangle_attribute(1), 1);
-        let mut plot = Plot::new();
-        triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/triangle_mesh_3_works.svg")?;
+        #[cfg(feature = "plot")]
+        {
+            let mut plot = plotpy::Plot::new();
+            triangle.draw_triangles(&mut plot, true, true, true, true, None, None, None);
+            if false {
+                plot.set_equal_axes(true)
+                    .set_figure_size_points(600.0, 600.0)
+                    .save("/tmp/tritet/triangle_mesh_3_works.svg")?;
+            }
         }
         Ok(())
     }
@@ -1223,13 +2240,435 @@ mod tests {
         assert_eq!(triangle.ntriangle(), 14);
         assert_eq!(triangle.triangle_attribute(0), 1);
         assert_eq!(triangle.triangle_attribute(12), 2);
-        let mut plot = Plot::new();
-        triangle.draw_triangles(&mut plot, true, true, true, true, Some(12.0), Some(20.0), None);
-        if false {
-            plot.set_equal_axes(true)
-                .set_figure_size_points(600.0, 600.0)
-                .save("/tmp/tritet/triangle_mesh_4_works.svg")?;
+        #[cfg(feature = "plot")]
+        {
+            let mut plot = plotpy::Plot::new();
+            triangle.draw_triangles(&mut plot, true, true, true, true, Some(12.0), Some(20.0), None);
+            if false {
+                plot.set_equal_axes(true)
+                    .set_figure_size_points(600.0, 600.0)
+                    .save("/tmp/tritet/triangle_mesh_4_works.svg")?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reset_and_clear_outputs_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 1);
+        triangle.clear_outputs()?;
+        assert_eq!(triangle.npoint(), 0);
+        assert_eq!(triangle.ntriangle(), 0);
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 1);
+
+        // errors
+        assert_eq!(triangle.reset(2, None, None, None).err(), Some("npoint must be ≥ 3"));
+        assert_eq!(
+            triangle.reset(3, Some(2), None, None).err(),
+            Some("nsegment must be ≥ 3")
+        );
+
+        // larger input with segments, regions, and holes
+        triangle.reset(8, Some(8), Some(1), Some(1))?;
+        assert_eq!(triangle.ntriangle(), 0);
+        assert_eq!(
+            triangle.generate_delaunay(false).err(),
+            Some("cannot generate Delaunay triangulation because not all points are set")
+        );
+        let mut fresh = Triangle::new(8, Some(8), Some(1), Some(1))?;
+        for t in [&mut triangle, &mut fresh] {
+            t.set_point(0, 0.0, 0.0)?
+                .set_point(1, 1.0, 0.0)?
+                .set_point(2, 1.0, 1.0)?
+                .set_point(3, 0.0, 1.0)?
+                .set_point(4, 0.25, 0.25)?
+                .set_point(5, 0.75, 0.25)?
+                .set_point(6, 0.75, 0.75)?
+                .set_point(7, 0.25, 0.75)?
+                .set_segment(0, 0, 1)?
+                .set_segment(1, 1, 2)?
+                .set_segment(2, 2, 3)?
+                .set_segment(3, 3, 0)?
+                .set_segment(4, 4, 5)?
+                .set_segment(5, 5, 6)?
+                .set_segment(6, 6, 7)?
+                .set_segment(7, 7, 4)?
+                .set_region(0, 0.1, 0.1, 3, None)?
+                .set_hole(0, 0.5, 0.5)?
+                .generate_mesh(false, false, Some(0.01), None)?;
         }
+        assert_eq!(triangle.npoint(), fresh.npoint());
+        assert_eq!(triangle.ntriangle(), fresh.ntriangle());
+        assert_eq!(triangle.triangle_attribute(0), 3);
+
+        // smaller input (the allocations are reused)
+        triangle.reset(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 0.0, 2.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.npoint(), 3);
+        assert_eq!(triangle.ntriangle(), 1);
+        assert_eq!(triangle.point(1, 0), 2.0);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_can_be_called_multiple_times() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, Some(4), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?
+            .set_region(0, 0.5, 0.5, 7, None)?;
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        let (npoint, ntriangle) = (triangle.npoint(), triangle.ntriangle());
+
+        // tune the parameters without setting the input again
+        triangle.generate_mesh(false, true, Some(0.01), Some(30.0))?;
+        assert!(triangle.ntriangle() > ntriangle);
+        assert_eq!(triangle.nnode(), 6);
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        assert_eq!(triangle.nnode(), 3);
+
+        // the same output is obtained again
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        assert_eq!(triangle.npoint(), npoint);
+        assert_eq!(triangle.ntriangle(), ntriangle);
+        assert!((0..ntriangle).all(|t| triangle.triangle_attribute(t) == 7));
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_captures_some_errors() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        let options = TriangleOptions::new();
+        assert_eq!(
+            triangle.refine_mesh(&[], &options).err(),
+            Some("cannot refine mesh because there is no mesh yet")
+        );
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(
+            triangle.refine_mesh(&[0.1, 0.1], &options).err(),
+            Some("per_cell_max_area.len() must be equal to ntriangle")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn point_markers_at_junctions_are_the_smallest() -> Result<(), StrError> {
+        // square with one marker per side; the corners are shared by two sides
+        let markers = [-10, -20, 30, 40];
+        let corners = [[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0]];
+        let correct = [-10, -20, -20, 30];
+        for shift in 0..4 {
+            // the order of the segments must not change the result
+            let mut triangle = Triangle::new(4, Some(4), None, None)?;
+            for (p, x) in corners.iter().enumerate() {
+                triangle.set_point(p, x[0], x[1])?;
+            }
+            for index in 0..4 {
+                let i = (index + shift) % 4;
+                triangle
+                    .set_segment(index, i, (i + 1) % 4)?
+                    .set_segment_marker(index, markers[i])?;
+            }
+            triangle.generate_mesh(false, false, Some(0.5), None)?;
+            for p in 0..triangle.npoint() {
+                let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+                match corners.iter().position(|c| c[0] == x && c[1] == y) {
+                    Some(corner) => assert_eq!(triangle.point_marker(p), correct[corner]),
+                    None if y == 0.0 => assert_eq!(triangle.point_marker(p), -10),
+                    None if x == 3.0 => assert_eq!(triangle.point_marker(p), -20),
+                    None if y == 3.0 => assert_eq!(triangle.point_marker(p), 30),
+                    None if x == 0.0 => assert_eq!(triangle.point_marker(p), 40),
+                    None => assert_eq!(triangle.point_marker(p), 0),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn markers_on_hole_boundaries_work() -> Result<(), StrError> {
+        // square with a square hole (the outer segments are marked with -10 and the inner with -20)
+        let mut triangle = Triangle::new(8, Some(8), None, Some(1))?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 3.0, 0.0)?
+            .set_point(2, 3.0, 3.0)?
+            .set_point(3, 0.0, 3.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 2.0, 1.0)?
+            .set_point(6, 2.0, 2.0)?
+            .set_point(7, 1.0, 2.0)?
+            .set_hole(0, 1.5, 1.5)?;
+        for i in 0..4 {
+            triangle
+                .set_segment(i, i, (i + 1) % 4)?
+                .set_segment_marker(i, -10)?
+                .set_segment(4 + i, 4 + i, 4 + (i + 1) % 4)?
+                .set_segment_marker(4 + i, -20)?;
+        }
+        let check = |triangle: &Triangle| {
+            let mut counts = [0, 0];
+            for p in 0..triangle.npoint() {
+                let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+                let on_outer = x == 0.0 || x == 3.0 || y == 0.0 || y == 3.0;
+                let on_inner = (1.0..=2.0).contains(&x) && (1.0..=2.0).contains(&y);
+                let marker = triangle.point_marker(p);
+                if on_outer {
+                    assert_eq!(marker, -10);
+                    counts[0] += 1;
+                } else if on_inner {
+                    assert_eq!(marker, -20);
+                    counts[1] += 1;
+                } else {
+                    assert_eq!(marker, 0);
+                }
+            }
+            for e in 0..triangle.nedge() {
+                let (a, b) = (triangle.edge_point(e, 0), triangle.edge_point(e, 1));
+                let marker = triangle.edge_marker(e);
+                if marker != 0 {
+                    assert_eq!(triangle.point_marker(a), marker);
+                    assert_eq!(triangle.point_marker(b), marker);
+                }
+            }
+            counts
+        };
+
+        // Steiner points are added on both boundaries
+        triangle.generate_mesh(false, true, Some(0.05), None)?;
+        let counts = check(&triangle);
+        assert!(counts[0] > 4);
+        assert!(counts[1] > 4);
+
+        // the refinement keeps the markers
+        let areas = vec![0.01; triangle.ntriangle()];
+        triangle.refine_mesh(&areas, &TriangleOptions::new())?;
+        let refined = check(&triangle);
+        assert!(refined[0] > counts[0]);
+        assert_eq!(triangle.point_marker(triangle.npoint()), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, Some(5), Some(2), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?
+            .set_segment(4, 0, 2)?
+            .set_region(0, 0.9, 0.3, 1, None)?
+            .set_region(1, 0.1, 0.7, 2, None)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        assert_eq!(triangle.nnode(), 6);
+        let attributes = [triangle.triangle_attribute(0), triangle.triangle_attribute(1)];
+
+        // refine the first triangle only (the middle nodes are discarded)
+        triangle.refine_mesh(&[0.01, -1.0], &TriangleOptions::new())?;
+        let ntriangle = triangle.ntriangle();
+        assert!(ntriangle > 50);
+        assert_eq!(triangle.nnode(), 3);
+        let mut area = [0.0, 0.0];
+        for i in 0..ntriangle {
+            let x: Vec<(f64, f64)> = (0..3)
+                .map(|m| {
+                    let p = triangle.triangle_node(i, m);
+                    (triangle.point(p, 0), triangle.point(p, 1))
+                })
+                .collect();
+            let a = ((x[1].0 - x[0].0) * (x[2].1 - x[0].1) - (x[2].0 - x[0].0) * (x[1].1 - x[0].1)) / 2.0;
+            let k = if triangle.triangle_attribute(i) == attributes[0] {
+                0
+            } else {
+                1
+            };
+            area[k] += a;
+        }
+        assert!(f64::abs(area[0] - 0.5) < 1e-14);
+        assert!(f64::abs(area[1] - 0.5) < 1e-14);
+
+        // refine again everywhere with quadratic triangles
+        let areas = vec![0.01; ntriangle];
+        let mut options = TriangleOptions::new();
+        options.set_quadratic(true);
+        triangle.refine_mesh(&areas, &options)?;
+        assert!(triangle.ntriangle() > ntriangle);
+        assert_eq!(triangle.nnode(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn per_cell_max_area_by_attribute_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, Some(5), Some(2), None)?;
+        assert_eq!(triangle.per_cell_max_area_by_attribute(&HashMap::new()), &[] as &[f64]);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?
+            .set_segment(4, 0, 2)?
+            .set_region(0, 0.9, 0.3, 1, None)?
+            .set_region(1, 0.1, 0.7, 2, None)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let first = if triangle.triangle_attribute(0) == 1 { 0.1 } else { 0.2 };
+        let second = if first == 0.1 { 0.2 } else { 0.1 };
+        let max_area = HashMap::from([(1, 0.1), (2, 0.2)]);
+        assert_eq!(triangle.per_cell_max_area_by_attribute(&max_area), &[first, second]);
+        let max_area = HashMap::from([(2, 0.2), (3, 0.3)]);
+        let areas = triangle.per_cell_max_area_by_attribute(&max_area);
+        assert_eq!(areas.iter().filter(|a| **a == 0.0).count(), 1);
+        assert_eq!(areas.iter().filter(|a| **a == 0.2).count(), 1);
+
+        // the triangles of each region respect the constraint of the region
+        let max_area = HashMap::from([(1, 0.01), (2, 0.05)]);
+        triangle.refine_mesh(
+            &triangle.per_cell_max_area_by_attribute(&max_area),
+            &TriangleOptions::new(),
+        )?;
+        for t in 0..triangle.ntriangle() {
+            let area = triangle.triangle_quality(t).unwrap().area;
+            assert!(area <= max_area[&triangle.triangle_attribute(t)] + 1e-15);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_works_with_delaunay() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        triangle.refine_mesh(&[0.05], &TriangleOptions::new())?;
+        assert!(triangle.ntriangle() >= 10);
+        for p in 0..triangle.npoint() {
+            let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+            assert!(x >= 0.0 && y >= 0.0 && x + y <= 1.0 + 1e-15);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn instances_can_be_moved_to_threads() -> Result<(), StrError> {
+        let max_areas = [0.1, 0.05, 0.01, 0.005, 0.001, 0.0005, 0.0001, 0.00005];
+        let mut correct = Vec::new();
+        for max_area in max_areas {
+            let triangle = square()?;
+            triangle.generate_mesh(false, false, Some(max_area), None)?;
+            correct.push(triangle.ntriangle());
+        }
+        let mut handles = Vec::new();
+        for max_area in max_areas {
+            let triangle = square()?;
+            handles.push(std::thread::spawn(move || {
+                triangle.generate_mesh(false, false, Some(max_area), None).unwrap();
+                triangle
+            }));
+        }
+        for (handle, ntriangle) in handles.into_iter().zip(correct) {
+            let triangle = handle.join().map_err(|_| "thread failed")?;
+            assert_eq!(triangle.ntriangle(), ntriangle);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn set_region_f64_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(6, Some(7), Some(2), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?
+            .set_point(3, 2.0, 1.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 4)?
+            .set_segment(4, 4, 5)?
+            .set_segment(5, 5, 0)?
+            .set_segment(6, 1, 4)?
+            .set_region_f64(0, 0.5, 0.5, 2.75, None)?
+            .set_region_f64(1, 1.5, 0.5, -1.5, None)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.ntriangle(), 4);
+        for t in 0..triangle.ntriangle() {
+            let xc = (0..3)
+                .map(|m| triangle.point(triangle.triangle_node(t, m), 0))
+                .sum::<f64>()
+                / 3.0;
+            let correct = if xc < 1.0 { 2.75 } else { -1.5 };
+            assert_eq!(triangle.triangle_attribute_f64(t), correct);
+        }
+        assert_eq!(triangle.triangle_attribute_f64(100), 0.0);
+
+        // the integer attributes of set_region are also available as f64
+        triangle.set_region(1, 1.5, 0.5, -3, None)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        for t in 0..triangle.ntriangle() {
+            let xc = (0..3)
+                .map(|m| triangle.point(triangle.triangle_node(t, m), 0))
+                .sum::<f64>()
+                / 3.0;
+            let (correct, correct_f64) = if xc < 1.0 { (2, 2.75) } else { (-3, -3.0) };
+            assert_eq!(triangle.triangle_attribute(t), correct);
+            assert_eq!(triangle.triangle_attribute_f64(t), correct_f64);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn set_region_handles_negative_attributes() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?
+            .set_region(0, 0.2, 0.2, -3, None)?;
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        assert!(triangle.ntriangle() > 1);
+        assert!((0..triangle.ntriangle()).all(|t| triangle.triangle_attribute(t) == -3));
+        assert_eq!(triangle.input_region(0), (0.2, 0.2, -3));
         Ok(())
     }
 }
@@ -0,0 +1,283 @@
+use crate::predicates::{compare_distances, in_diametral};
+use crate::Triangle;
+use std::collections::HashMap;
+
+impl Triangle {
+    /// Returns the edges of the triangulation (Delaunay or constrained Delaunay)
+    ///
+    /// Each edge is given by the IDs of its two endpoints, with the smallest ID first.
+    /// The edges are sorted and only the corner nodes of the triangles are considered
+    /// (i.e., middle nodes of quadratic triangles are ignored).
+    pub fn delaunay_edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = self.edge_opposite_points().keys().copied().collect();
+        edges.sort();
+        edges
+    }
+
+    /// Returns the edges of the Gabriel graph
+    ///
+    /// An edge (a, b) belongs to the Gabriel graph if no other point lies strictly inside the
+    /// circle whose diameter is the segment from a to b. The Gabriel graph is a subgraph of the
+    /// Delaunay triangulation; thus, this function must be called after `generate_delaunay`.
+    ///
+    /// Each edge is given by the IDs of its two endpoints, with the smallest ID first.
+    pub fn gabriel_graph(&self) -> Vec<(usize, usize)> {
+        // For a Delaunay triangulation, it suffices to check the points opposite to the edge
+        let mut edges = Vec::new();
+        for ((a, b), opposite) in &self.edge_opposite_points() {
+            if opposite.iter().all(|c| !self.in_diametral_circle(*a, *b, *c)) {
+                edges.push((*a, *b));
+            }
+        }
+        edges.sort();
+        edges
+    }
+
+    /// Returns the edges of the relative neighborhood graph (RNG)
+    ///
+    /// An edge (a, b) belongs to the RNG if there is no other point c closer to both a and b
+    /// than they are to each other; i.e., the lune of (a, b) is empty. The RNG is a subgraph
+    /// of the Gabriel graph; thus, this function must be called after `generate_delaunay`.
+    ///
+    /// Each edge is given by the IDs of its two endpoints, with the smallest ID first.
+    ///
+    /// The lune of each edge of the Gabriel graph is tested against the Delaunay neighbors of its
+    /// endpoints only; if the lune is not empty, it contains one of these neighbors.
+    pub fn relative_neighborhood_graph(&self) -> Vec<(usize, usize)> {
+        let mut neighbors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (a, b) in self.edge_opposite_points().keys() {
+            neighbors.entry(*a).or_default().push(*b);
+            neighbors.entry(*b).or_default().push(*a);
+        }
+        self.gabriel_graph()
+            .into_iter()
+            .filter(|(a, b)| {
+                let (pa, pb) = (self.xy(*a), self.xy(*b));
+                neighbors[a].iter().chain(&neighbors[b]).all(|c| {
+                    if *c == *a || *c == *b {
+                        return true;
+                    }
+                    let pc = self.xy(*c);
+                    compare_distances(pa, pc, pa, pb) >= 0.0 || compare_distances(pb, pc, pa, pb) >= 0.0
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the edges of the Euclidean minimum spanning tree (EMST)
+    ///
+    /// The EMST is a subgraph of the Delaunay triangulation; thus, it is computed with
+    /// Kruskal's algorithm applied to the Delaunay edges. This function must be called
+    /// after `generate_delaunay`.
+    ///
+    /// Each edge is given by the IDs of its two endpoints, with the smallest ID first.
+    /// The edges are sorted by ID (not by length).
+    pub fn delaunay_mst(&self) -> Vec<(usize, usize)> {
+        let mut candidates: Vec<(f64, usize, usize)> = self
+            .delaunay_edges()
+            .into_iter()
+            .map(|(a, b)| (self.distance_squared(a, b), a, b))
+            .collect();
+        candidates.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        let mut parent: Vec<usize> = (0..self.npoint()).collect();
+        let mut edges = Vec::new();
+        for (_, a, b) in candidates {
+            let root_a = find_root(&mut parent, a);
+            let root_b = find_root(&mut parent, b);
+            if root_a != root_b {
+                parent[root_a] = root_b;
+                edges.push((a, b));
+            }
+        }
+        edges.sort();
+        edges
+    }
+
+    /// Returns the neighbors of a Voronoi site (i.e., the natural neighbors of a point)
+    ///
+    /// Two sites are neighbors if their Voronoi cells share an edge; equivalently, if they
+    /// are connected by an edge of the Delaunay triangulation. Thus, this function must be
+    /// called after `generate_voronoi` or `generate_delaunay`.
+    ///
+    /// The IDs of the neighbors are sorted. The result is empty if `site` is out of range.
+    pub fn voronoi_site_neighbors(&self, site: usize) -> Vec<usize> {
+        let mut neighbors = Vec::new();
+        for tri in 0..self.ntriangle() {
+            let v = [
+                self.triangle_node(tri, 0),
+                self.triangle_node(tri, 1),
+                self.triangle_node(tri, 2),
+            ];
+            if let Some(m) = v.iter().position(|p| *p == site) {
+                neighbors.push(v[(m + 1) % 3]);
+                neighbors.push(v[(m + 2) % 3]);
+            }
+        }
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// Maps each edge (smallest ID first) to the points opposite to it in the adjacent triangles
+    fn edge_opposite_points(&self) -> HashMap<(usize, usize), Vec<usize>> {
+        let mut map: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for tri in 0..self.ntriangle() {
+            let v = [
+                self.triangle_node(tri, 0),
+                self.triangle_node(tri, 1),
+                self.triangle_node(tri, 2),
+            ];
+            for m in 0..3 {
+                let a = v[m];
+                let b = v[(m + 1) % 3];
+                let c = v[(m + 2) % 3];
+                let key = if a < b { (a, b) } else { (b, a) };
+                map.entry(key).or_default().push(c);
+            }
+        }
+        map
+    }
+
+    /// Returns the squared distance between two points
+    fn distance_squared(&self, a: usize, b: usize) -> f64 {
+        let dx = self.point(b, 0) - self.point(a, 0);
+        let dy = self.point(b, 1) - self.point(a, 1);
+        dx * dx + dy * dy
+    }
+
+    /// Indicates whether point c is strictly inside the circle with diameter (a, b)
+    fn in_diametral_circle(&self, a: usize, b: usize, c: usize) -> bool {
+        in_diametral(self.xy(a), self.xy(b), self.xy(c)) > 0.0
+    }
+
+    /// Returns the coordinates of a point
+    fn xy(&self, p: usize) -> (f64, f64) {
+        (self.point(p, 0), self.point(p, 1))
+    }
+}
+
+/// Finds the root of a disjoint-set (union-find) tree with path halving
+pub(crate) fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Triangle};
+
+    #[test]
+    fn delaunay_edges_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.delaunay_edges(), &[(0, 1), (0, 2), (1, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn gabriel_and_rng_work() -> Result<(), StrError> {
+        // the 90° angle at point 2 leaves it on the boundary of the diametral circle of (0, 1)
+        // the obtuse angle at point 3 puts it inside the diametral circle of (1, 2)
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 2.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 1.8, 0.9)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.delaunay_edges(), &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+        assert_eq!(triangle.gabriel_graph(), &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+        // point 2 lies in the lune of (0, 1) because |02| = |12| = √2 < |01| = 2
+        assert_eq!(triangle.relative_neighborhood_graph(), &[(0, 2), (1, 3), (2, 3)]);
+        Ok(())
+    }
+
+    #[test]
+    fn voronoi_site_neighbors_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(5, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.2)?
+            .set_point(4, 0.5, 0.4)?;
+        triangle.generate_voronoi(false)?;
+        assert_eq!(triangle.voronoi_site_neighbors(4), &[0, 1, 2, 3]);
+        assert_eq!(triangle.voronoi_site_neighbors(0), &[1, 3, 4]);
+        assert_eq!(triangle.voronoi_site_neighbors(100), &[] as &[usize]);
+        Ok(())
+    }
+
+    #[test]
+    fn delaunay_mst_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(5, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.2)?
+            .set_point(4, 0.5, 0.4)?;
+        triangle.generate_delaunay(false)?;
+        let mst = triangle.delaunay_mst();
+        assert_eq!(mst.len(), 4);
+        assert_eq!(mst, &[(0, 4), (1, 4), (2, 4), (3, 4)]);
+        Ok(())
+    }
+
+    #[test]
+    fn gabriel_and_rng_handle_cocircular_points() -> Result<(), StrError> {
+        // the diagonals of the unit squares have the other two corners on their diametral circles
+        let n = 4;
+        let mut triangle = Triangle::new(n * n, None, None, None)?;
+        for i in 0..n {
+            for j in 0..n {
+                triangle.set_point(i + j * n, i as f64, j as f64)?;
+            }
+        }
+        triangle.generate_delaunay(false)?;
+        let gabriel = triangle.gabriel_graph();
+        assert_eq!(gabriel, triangle.delaunay_edges());
+        let rng = triangle.relative_neighborhood_graph();
+        assert_eq!(rng.len(), 2 * n * (n - 1));
+        assert!(rng.iter().all(|(a, b)| b - a == 1 || b - a == n));
+        Ok(())
+    }
+
+    #[test]
+    fn rng_matches_the_brute_force_search() -> Result<(), StrError> {
+        let npoint = 200;
+        let mut triangle = Triangle::new(npoint, None, None, None)?;
+        let mut seed: u64 = 12345;
+        let mut random = || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((seed >> 11) as f64) / ((1u64 << 53) as f64)
+        };
+        for p in 0..npoint {
+            triangle.set_point(p, random(), random())?;
+        }
+        triangle.generate_delaunay(false)?;
+        let brute_force: Vec<_> = triangle
+            .delaunay_edges()
+            .into_iter()
+            .filter(|(a, b)| {
+                let dab = triangle.distance_squared(*a, *b);
+                (0..npoint).all(|c| {
+                    c == *a
+                        || c == *b
+                        || f64::max(triangle.distance_squared(*a, c), triangle.distance_squared(*b, c)) >= dab
+                })
+            })
+            .collect();
+        assert_eq!(triangle.relative_neighborhood_graph(), brute_force);
+        Ok(())
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{StrError, Triangle, VoronoiEdgePoint};
+use std::collections::{BinaryHeap, HashMap};
+
+impl Triangle {
+    /// Returns the concave hull (chi-shape) of the points of a Delaunay triangulation
+    ///
+    /// The boundary edges of the triangulation longer than `length_threshold` are peeled,
+    /// longest first, by removing the triangle attached to them. A triangle is only removed
+    /// if its third point is not on the boundary yet; thus, the resulting polygon is simple
+    /// (no self-intersections) and contains all points. This function must be called after
+    /// `generate_delaunay`.
+    ///
+    /// See: Duckham M, Kulik L, Worboys M, Galton A (2008) Efficient generation of simple
+    /// polygons for characterizing the shape of a set of points in the plane,
+    /// Pattern Recognition, 41(10):3224-3236.
+    ///
+    /// # Input
+    ///
+    /// * `length_threshold` -- boundary edges shorter than or equal to this value are kept.
+    ///   A large value yields the convex hull.
+    ///
+    /// # Output
+    ///
+    /// Returns the IDs of the points on the outline, in counterclockwise order, starting
+    /// from the point with the smallest ID. The first point is not repeated at the end.
+    /// Returns an empty vector if there are no triangles.
+    pub fn concave_hull(&self, length_threshold: f64) -> Vec<usize> {
+        let ntriangle = self.ntriangle();
+        if ntriangle < 1 {
+            return Vec::new();
+        }
+
+        // triangles attached to each edge (smallest ID first)
+        let mut edge_triangles: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut corners = Vec::with_capacity(ntriangle);
+        for tri in 0..ntriangle {
+            let v = [
+                self.triangle_node(tri, 0),
+                self.triangle_node(tri, 1),
+                self.triangle_node(tri, 2),
+            ];
+            for m in 0..3 {
+                let (a, b) = (v[m], v[(m + 1) % 3]);
+                let key = if a < b { (a, b) } else { (b, a) };
+                edge_triangles.entry(key).or_default().push(tri);
+            }
+            corners.push(v);
+        }
+
+        // initial boundary: edges with a single triangle
+        let mut alive = vec![true; ntriangle];
+        let mut on_boundary = vec![false; self.npoint()];
+        let mut heap = BinaryHeap::new();
+        for (key, triangles) in &edge_triangles {
+            if triangles.len() == 1 {
+                on_boundary[key.0] = true;
+                on_boundary[key.1] = true;
+                heap.push((self.edge_length_key(key.0, key.1), key.0, key.1));
+            }
+        }
+
+        // peel the longest boundary edges
+        // (the bits of a non-negative f64 preserve the ordering of the numbers)
+        let threshold_key = f64::max(0.0, length_threshold).to_bits();
+        while let Some((length_key, a, b)) = heap.pop() {
+            if length_key <= threshold_key {
+                break;
+            }
+            let attached: Vec<usize> = edge_triangles[&(a, b)].iter().copied().filter(|t| alive[*t]).collect();
+            if attached.len() != 1 {
+                continue;
+            }
+            let tri = attached[0];
+            let c = corners[tri].iter().copied().find(|p| *p != a && *p != b).unwrap();
+            if on_boundary[c] {
+                continue;
+            }
+            alive[tri] = false;
+            on_boundary[c] = true;
+            for (p, q) in [(a, c), (b, c)] {
+                let key = if p < q { (p, q) } else { (q, p) };
+                heap.push((self.edge_length_key(key.0, key.1), key.0, key.1));
+            }
+        }
+
+        // oriented boundary edges (triangles are counterclockwise)
+        let mut next: HashMap<usize, usize> = HashMap::new();
+        for tri in 0..ntriangle {
+            if !alive[tri] {
+                continue;
+            }
+            let v = corners[tri];
+            for m in 0..3 {
+                let (a, b) = (v[m], v[(m + 1) % 3]);
+                let key = if a < b { (a, b) } else { (b, a) };
+                let nalive = edge_triangles[&key].iter().filter(|t| alive[**t]).count();
+                if nalive == 1 {
+                    next.insert(a, b);
+                }
+            }
+        }
+
+        // walk around the outline
+        let start = *next.keys().min().unwrap();
+        let mut outline = vec![start];
+        let mut current = next[&start];
+        while current != start && outline.len() < next.len() {
+            outline.push(current);
+            current = next[&current];
+        }
+        outline
+    }
+
+    /// Finds the largest empty circle centered within a bounding polygon
+    ///
+    /// The largest empty circle has no point of the triangulation in its interior and its
+    /// center lies within the bounding polygon. The center is located at a Voronoi vertex,
+    /// at the intersection of a Voronoi edge with the boundary, or at a polygon vertex.
+    /// Thus, this function must be called after `generate_voronoi`.
+    ///
+    /// # Input
+    ///
+    /// * `bounding_polygon` -- the (x, y) coordinates of the vertices of a simple polygon
+    ///
+    /// # Output
+    ///
+    /// Returns `(xc, yc, radius)`
+    ///
+    /// **Note:** The radius of each candidate is computed against all points; thus, the cost is
+    /// O(n²) in the worst case.
+    pub fn largest_empty_circle(&self, bounding_polygon: &[(f64, f64)]) -> Result<(f64, f64, f64), StrError> {
+        if bounding_polygon.len() < 3 {
+            return Err("the bounding polygon must have at least 3 vertices");
+        }
+        if self.voronoi_npoint() < 1 {
+            return Err("cannot find the largest empty circle because the Voronoi tessellation is not available");
+        }
+
+        // polygon vertices and Voronoi vertices within the polygon
+        let mut candidates: Vec<(f64, f64)> = bounding_polygon.to_vec();
+        for q in 0..self.voronoi_npoint() {
+            let (x, y) = (self.voronoi_point(q, 0), self.voronoi_point(q, 1));
+            if point_in_polygon(bounding_polygon, x, y) {
+                candidates.push((x, y));
+            }
+        }
+
+        // intersections between Voronoi edges and the polygon boundary
+        let nvertex = bounding_polygon.len();
+        for e in 0..self.voronoi_nedge() {
+            let a = self.voronoi_edge_point_a(e);
+            let (xa, ya) = (self.voronoi_point(a, 0), self.voronoi_point(a, 1));
+            let (dx, dy, t_max) = match self.voronoi_edge_point_b(e) {
+                VoronoiEdgePoint::Index(b) => (self.voronoi_point(b, 0) - xa, self.voronoi_point(b, 1) - ya, 1.0),
+                VoronoiEdgePoint::Direction(dx, dy) => (dx, dy, f64::INFINITY),
+            };
+            for i in 0..nvertex {
+                let (px, py) = bounding_polygon[i];
+                let (qx, qy) = bounding_polygon[(i + 1) % nvertex];
+                let (ex, ey) = (qx - px, qy - py);
+                let denom = dx * ey - dy * ex;
+                if denom == 0.0 {
+                    continue; // parallel
+                }
+                let t = ((px - xa) * ey - (py - ya) * ex) / denom;
+                let s = ((px - xa) * dy - (py - ya) * dx) / denom;
+                if (0.0..=t_max).contains(&t) && (0.0..=1.0).contains(&s) {
+                    candidates.push((xa + t * dx, ya + t * dy));
+                }
+            }
+        }
+
+        // select the candidate farthest from its nearest point
+        let npoint = self.npoint();
+        let mut best = (0.0, 0.0, -1.0);
+        for (x, y) in candidates {
+            let mut dist = f64::MAX;
+            for p in 0..npoint {
+                let (dx, dy) = (self.point(p, 0) - x, self.point(p, 1) - y);
+                dist = f64::min(dist, dx * dx + dy * dy);
+            }
+            if dist > best.2 {
+                best = (x, y, dist);
+            }
+        }
+        Ok((best.0, best.1, f64::sqrt(best.2)))
+    }
+
+    /// Finds (approximately) the maximum inscribed circle of a polygon
+    ///
+    /// The boundary of the polygon is sampled with points separated by at most `spacing`.
+    /// Then, the center of the circle is selected among the vertices of the Voronoi tessellation
+    /// of the samples (an approximation of the medial axis) located within the polygon.
+    /// The radius is computed exactly as the distance from the center to the polygon's boundary;
+    /// thus, the returned circle is always inscribed and the approximation error on the
+    /// radius is of the order of `spacing`.
+    ///
+    /// # Input
+    ///
+    /// * `polygon` -- the (x, y) coordinates of the vertices of a simple polygon
+    /// * `spacing` -- the maximum distance between the sample points along the boundary
+    ///
+    /// # Output
+    ///
+    /// Returns `(xc, yc, radius)`
+    pub fn max_inscribed_circle(polygon: &[(f64, f64)], spacing: f64) -> Result<(f64, f64, f64), StrError> {
+        if polygon.len() < 3 {
+            return Err("the polygon must have at least 3 vertices");
+        }
+        if spacing <= 0.0 {
+            return Err("the spacing must be positive");
+        }
+
+        // sample the boundary
+        let nvertex = polygon.len();
+        let mut samples = Vec::new();
+        for i in 0..nvertex {
+            let (px, py) = polygon[i];
+            let (qx, qy) = polygon[(i + 1) % nvertex];
+            let length = f64::sqrt((qx - px) * (qx - px) + (qy - py) * (qy - py));
+            let ndiv = f64::ceil(length / spacing) as usize;
+            for k in 0..ndiv {
+                let t = (k as f64) / (ndiv as f64);
+                samples.push((px + t * (qx - px), py + t * (qy - py)));
+            }
+        }
+        if samples.len() < 3 {
+            return Err("the polygon must have at least 3 distinct vertices");
+        }
+
+        // Voronoi tessellation of the samples
+        let mut triangle = Triangle::new(samples.len(), None, None, None)?;
+        for (i, (x, y)) in samples.iter().enumerate() {
+            triangle.set_point(i, *x, *y)?;
+        }
+        triangle.generate_voronoi(false)?;
+
+        // select the interior Voronoi vertex farthest from the boundary
+        let mut best: Option<(f64, f64, f64)> = None;
+        for q in 0..triangle.voronoi_npoint() {
+            let (x, y) = (triangle.voronoi_point(q, 0), triangle.voronoi_point(q, 1));
+            if !point_in_polygon(polygon, x, y) {
+                continue;
+            }
+            let radius = distance_to_polygon(polygon, x, y);
+            match best {
+                Some((_, _, r)) if r >= radius => (),
+                _ => best = Some((x, y, radius)),
+            }
+        }
+        best.ok_or("cannot find the maximum inscribed circle; try a smaller spacing")
+    }
+
+    /// Returns a key to sort edges by length
+    fn edge_length_key(&self, a: usize, b: usize) -> u64 {
+        let dx = self.point(b, 0) - self.point(a, 0);
+        let dy = self.point(b, 1) - self.point(a, 1);
+        f64::sqrt(dx * dx + dy * dy).to_bits()
+    }
+}
+
+/// Indicates whether a point is inside a polygon (ray casting; points on the boundary may go either way)
+pub(crate) fn point_in_polygon(polygon: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    let mut j = n - 1;
+    for i in 0..n {
+        let (xi, yi) = polygon[i];
+        let (xj, yj) = polygon[j];
+        if (yi > y) != (yj > y) && x < xi + (y - yi) * (xj - xi) / (yj - yi) {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Returns the distance from a point to the boundary of a polygon
+fn distance_to_polygon(polygon: &[(f64, f64)], x: f64, y: f64) -> f64 {
+    let n = polygon.len();
+    let mut dist = f64::MAX;
+    for i in 0..n {
+        let (px, py) = polygon[i];
+        let (qx, qy) = polygon[(i + 1) % n];
+        let (ex, ey) = (qx - px, qy - py);
+        let len2 = ex * ex + ey * ey;
+        let t = if len2 > 0.0 {
+            (((x - px) * ex + (y - py) * ey) / len2).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let (dx, dy) = (px + t * ex - x, py + t * ey - y);
+        dist = f64::min(dist, dx * dx + dy * dy);
+    }
+    f64::sqrt(dist)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{StrError, Triangle};
+
+    #[test]
+    fn concave_hull_works() -> Result<(), StrError> {
+        //  6-----5-----4
+        //  |           |
+        //  7     8     3
+        //  |           |
+        //  0-----1-----2
+        let mut triangle = Triangle::new(9, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?
+            .set_point(3, 2.0, 1.0)?
+            .set_point(4, 2.0, 2.0)?
+            .set_point(5, 1.0, 2.0)?
+            .set_point(6, 0.0, 2.0)?
+            .set_point(7, 0.0, 1.0)?
+            .set_point(8, 1.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+
+        assert_eq!(triangle.concave_hull(10.0), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        Ok(())
+    }
+
+    #[test]
+    fn concave_hull_peels_long_edges() -> Result<(), StrError> {
+        // the long edges (2, 3) and (0, 3) share point 3; thus, only one of them can be peeled
+        let mut triangle = Triangle::new(5, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, -0.1)?
+            .set_point(2, 2.0, 0.0)?
+            .set_point(3, 1.0, 1.0)?
+            .set_point(4, 1.0, 0.2)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.concave_hull(10.0), &[0, 1, 2, 3]);
+        assert_eq!(triangle.concave_hull(1.2), &[0, 1, 2, 4, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn concave_hull_handles_empty_output() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(triangle.concave_hull(1.0), &[] as &[usize]);
+        Ok(())
+    }
+
+    #[test]
+    fn largest_empty_circle_captures_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.largest_empty_circle(&[(0.0, 0.0), (1.0, 0.0)]).err(),
+            Some("the bounding polygon must have at least 3 vertices")
+        );
+        assert_eq!(
+            triangle
+                .largest_empty_circle(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)])
+                .err(),
+            Some("cannot find the largest empty circle because the Voronoi tessellation is not available")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn largest_empty_circle_works() -> Result<(), StrError> {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle.generate_voronoi(false)?;
+        let (xc, yc, radius) = triangle.largest_empty_circle(&square)?;
+        assert_eq!((xc, yc), (0.5, 0.5));
+        assert!(f64::abs(radius - f64::sqrt(0.5)) < 1e-15);
+
+        // the center is at the intersection of a Voronoi edge with the bounding polygon
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.5, 0.1)?;
+        triangle.generate_voronoi(false)?;
+        let (xc, yc, radius) = triangle.largest_empty_circle(&square)?;
+        assert!(f64::abs(xc - 0.06) < 1e-15 || f64::abs(xc - 0.94) < 1e-15);
+        assert!(f64::abs(yc - 1.0) < 1e-15);
+        assert!(f64::abs(radius - f64::sqrt(1.0036)) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn max_inscribed_circle_works() -> Result<(), StrError> {
+        assert_eq!(
+            Triangle::max_inscribed_circle(&[(0.0, 0.0), (1.0, 0.0)], 0.1).err(),
+            Some("the polygon must have at least 3 vertices")
+        );
+        assert_eq!(
+            Triangle::max_inscribed_circle(&[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)], 0.0).err(),
+            Some("the spacing must be positive")
+        );
+        let rectangle = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)];
+        let (xc, yc, radius) = Triangle::max_inscribed_circle(&rectangle, 0.1)?;
+        assert!(f64::abs(radius - 1.0) < 1e-12);
+        assert!((1.0..=3.0).contains(&xc));
+        assert!(f64::abs(yc - 1.0) < 1e-12);
+
+        // L-shaped polygon
+        let l_shape = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)];
+        let (_, _, radius) = Triangle::max_inscribed_circle(&l_shape, 0.05)?;
+        let exact = 2.0 - f64::sqrt(2.0); // touches (1,1) and the outer sides
+        assert!(radius <= exact + 1e-12);
+        assert!(radius > 0.5);
+        Ok(())
+    }
+}
@@ -0,0 +1,626 @@
+use crate::{StrError, Tetgen, Triangle};
+use std::collections::HashMap;
+
+/// Holds the point IDs of the polygons of a facet
+type FacetPolygons = Vec<Vec<usize>>;
+
+impl Triangle {
+    /// Finds the input points that coincide within a tolerance
+    ///
+    /// Two points coincide if the distance between them is not greater than `tolerance`
+    /// (use zero to find exact duplicates). The input coordinates are used; i.e., the output
+    /// transform, if any, is disregarded.
+    ///
+    /// # Output
+    ///
+    /// Returns the (sorted) pairs `(a, b)` of coinciding points with `a < b`. Returns an error if
+    /// not all points have been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     let mut triangle = Triangle::new(4, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0)?
+    ///         .set_point(3, 1.0, 1e-9)?;
+    ///     assert!(triangle.find_duplicate_points(0.0)?.is_empty());
+    ///     assert_eq!(triangle.find_duplicate_points(1e-6)?, &[(1, 3)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find_duplicate_points(&self, tolerance: f64) -> Result<Vec<(usize, usize)>, StrError> {
+        let (npoint, all_points_set) = self.input_npoint();
+        if !all_points_set {
+            return Err("cannot find duplicate points because not all points are set");
+        }
+        let points: Vec<_> = (0..npoint).map(|p| self.input_point(p)).collect();
+        Ok(find_duplicates(&points, tolerance))
+    }
+
+    /// Finds the segments referencing the same point twice
+    ///
+    /// Returns the (sorted) indices of the degenerate segments. Returns an error if not all
+    /// segments have been set.
+    pub fn find_degenerate_segments(&self) -> Result<Vec<usize>, StrError> {
+        let (nsegment, all_segments_set) = self.input_nsegment();
+        if nsegment > 0 && !all_segments_set {
+            return Err("cannot find degenerate segments because not all segments are set");
+        }
+        Ok((0..nsegment)
+            .filter(|s| {
+                let (a, b) = self.input_segment(*s);
+                a == b
+            })
+            .collect())
+    }
+
+    /// Finds the segments that cross or overlap each other
+    ///
+    /// Two segments are reported if they have any point in common other than a shared endpoint
+    /// (i.e., the same point ID); thus, crossings, overlaps, and T-junctions (an endpoint on the
+    /// interior of another segment) are reported. The input coordinates are used and the
+    /// predicates are evaluated with floating-point arithmetic (no tolerance).
+    ///
+    /// # Output
+    ///
+    /// Returns the (sorted) pairs `(i, j)` of intersecting segments with `i < j`. Returns an
+    /// error if not all points or segments have been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tritet::{StrError, Triangle};
+    ///
+    /// fn main() -> Result<(), StrError> {
+    ///     // bow-tie
+    ///     let mut triangle = Triangle::new(4, Some(4), None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0)?
+    ///         .set_point(3, 1.0, 1.0)?
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?;
+    ///     assert_eq!(triangle.find_crossing_segments()?, &[(1, 3)]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn find_crossing_segments(&self) -> Result<Vec<(usize, usize)>, StrError> {
+        let (npoint, all_points_set) = self.input_npoint();
+        if !all_points_set {
+            return Err("cannot find crossing segments because not all points are set");
+        }
+        let (nsegment, all_segments_set) = self.input_nsegment();
+        if nsegment > 0 && !all_segments_set {
+            return Err("cannot find crossing segments because not all segments are set");
+        }
+        let points: Vec<_> = (0..npoint).map(|p| self.input_point(p)).collect();
+        let segments: Vec<_> = (0..nsegment).map(|s| self.input_segment(s)).collect();
+        Ok(find_crossings(&points, &segments))
+    }
+
+    /// Validates the input points and segments before calling a generate method
+    ///
+    /// Returns an error if not all points (or segments) have been set, if there are coinciding
+    /// points, if a segment references the same point twice, or if there are crossing or
+    /// overlapping segments. See `find_duplicate_points`, `find_degenerate_segments`, and
+    /// `find_crossing_segments` to obtain the offending indices.
+    pub fn validate_input(&self, tolerance: f64) -> Result<(), StrError> {
+        if !self.find_duplicate_points(tolerance)?.is_empty() {
+            return Err("found duplicate points (see find_duplicate_points)");
+        }
+        if !self.find_degenerate_segments()?.is_empty() {
+            return Err("found segments referencing the same point twice (see find_degenerate_segments)");
+        }
+        if !self.find_crossing_segments()?.is_empty() {
+            return Err("found crossing segments (see find_crossing_segments)");
+        }
+        Ok(())
+    }
+}
+
+impl Tetgen {
+    /// Finds the input points that coincide within a tolerance
+    ///
+    /// Two points coincide if the distance between them is not greater than `tolerance`
+    /// (use zero to find exact duplicates). The input coordinates are used; i.e., the output
+    /// transform, if any, is disregarded.
+    ///
+    /// # Output
+    ///
+    /// Returns the (sorted) pairs `(a, b)` of coinciding points with `a < b`. Returns an error if
+    /// not all points have been set.
+    pub fn find_duplicate_points(&self, tolerance: f64) -> Result<Vec<(usize, usize)>, StrError> {
+        let (npoint, all_points_set) = self.input_npoint();
+        if !all_points_set {
+            return Err("cannot find duplicate points because not all points are set");
+        }
+        let points: Vec<_> = (0..npoint).map(|p| self.input_point(p)).collect();
+        Ok(find_duplicates(&points, tolerance))
+    }
+
+    /// Validates the input points before calling a generate method
+    ///
+    /// Returns an error if not all points have been set or if there are coinciding points
+    /// (see `find_duplicate_points` to obtain the offending indices).
+    pub fn validate_input(&self, tolerance: f64) -> Result<(), StrError> {
+        if self.find_duplicate_points(tolerance)?.is_empty() {
+            Ok(())
+        } else {
+            Err("found duplicate points (see find_duplicate_points)")
+        }
+    }
+
+    /// Finds the facets whose points are not coplanar within a tolerance
+    ///
+    /// The plane of each facet passes through the centroid of the first polygon with the normal
+    /// given by Newell's method. The points of all polygons of the facet must be within a
+    /// distance `tolerance` from this plane. Facets with a degenerate first polygon (zero area)
+    /// are skipped here and reported by `find_non_simple_facets`.
+    ///
+    /// Returns the (sorted) indices of the non-planar facets. Returns an error if not all points
+    /// or facets have been set.
+    pub fn find_non_planar_facets(&self, tolerance: f64) -> Result<Vec<usize>, StrError> {
+        let (points, facets) = self.input_facets()?;
+        Ok((0..facets.len())
+            .filter(|f| match facet_plane(&points, &facets[*f][0]) {
+                Some((normal, center)) => facets[*f].iter().flatten().any(|p| {
+                    let x = points[*p];
+                    let dist: f64 = (0..3).map(|i| normal[i] * (x[i] - center[i])).sum();
+                    f64::abs(dist) > tolerance
+                }),
+                None => false,
+            })
+            .collect())
+    }
+
+    /// Finds the facets with polygons that are not simple
+    ///
+    /// A polygon (with three or more points) is not simple if it references the same point twice,
+    /// has zero area, or has edges that cross or overlap each other (see
+    /// `Triangle::find_crossing_segments`). The polygons are projected onto the coordinate plane
+    /// most aligned with the facet. Edges of different polygons of the same facet are also checked.
+    ///
+    /// Returns the (sorted) indices of the non-simple facets. Returns an error if not all points
+    /// or facets have been set.
+    pub fn find_non_simple_facets(&self) -> Result<Vec<usize>, StrError> {
+        let (points, facets) = self.input_facets()?;
+        Ok((0..facets.len())
+            .filter(|f| {
+                let normal = match facet_plane(&points, &facets[*f][0]) {
+                    Some((normal, _)) => normal,
+                    None => return true,
+                };
+                let drop = (0..3)
+                    .max_by(|a, b| normal[*a].abs().total_cmp(&normal[*b].abs()))
+                    .unwrap();
+                let (i, j) = ((drop + 1) % 3, (drop + 2) % 3);
+                let mut ids = Vec::new();
+                let mut local = Vec::new();
+                let mut edges = Vec::new();
+                for polygon in &facets[*f] {
+                    let n = polygon.len();
+                    let start = local.len();
+                    for p in polygon {
+                        ids.push(*p);
+                        local.push([points[*p][i], points[*p][j]]);
+                    }
+                    let nedge = if n < 3 { n.saturating_sub(1) } else { n };
+                    edges.extend((0..nedge).map(|k| (start + k, start + (k + 1) % n)));
+                }
+                // map repeated point IDs to the same local point
+                let first: Vec<usize> = ids.iter().map(|id| ids.iter().position(|q| q == id).unwrap()).collect();
+                let edges: Vec<_> = edges.iter().map(|(a, b)| (first[*a], first[*b])).collect();
+                let repeated = facets[*f].iter().any(|polygon| {
+                    let mut sorted = polygon.clone();
+                    sorted.sort();
+                    sorted.dedup();
+                    sorted.len() != polygon.len()
+                });
+                repeated || !find_crossings(&local, &edges).is_empty()
+            })
+            .collect())
+    }
+
+    /// Finds the edges of the outer boundary of facets that are not shared by any other facet
+    ///
+    /// The surface is closed if every edge of the first polygon of each facet (the outer boundary;
+    /// see `set_facet_point`) is shared by at least one other facet, including the additional
+    /// polygons set by `set_facet_polygon` (e.g., the boundaries of openings). Edges shared by more
+    /// than two facets are allowed because internal facets may separate regions.
+    ///
+    /// Returns the (sorted) open edges `(a, b)` with `a < b`. Returns an error if not all points
+    /// or facets have been set.
+    pub fn find_open_facet_edges(&self) -> Result<Vec<(usize, usize)>, StrError> {
+        let (_, facets) = self.input_facets()?;
+        let mut count: HashMap<(usize, usize), usize> = HashMap::new();
+        for polygons in &facets {
+            let mut edges: Vec<(usize, usize)> = polygons.iter().flat_map(|polygon| polygon_edges(polygon)).collect();
+            edges.sort();
+            edges.dedup();
+            for edge in edges {
+                *count.entry(edge).or_insert(0) += 1;
+            }
+        }
+        let mut open: Vec<_> = facets
+            .iter()
+            .flat_map(|polygons| polygon_edges(&polygons[0]))
+            .filter(|edge| count[edge] < 2)
+            .collect();
+        open.sort();
+        open.dedup();
+        Ok(open)
+    }
+
+    /// Validates the facets before calling `generate_mesh`
+    ///
+    /// Returns an error if not all points or facets have been set, if the points of a facet are
+    /// not coplanar within `tolerance`, if a polygon of a facet is not simple, or if the surface
+    /// is not closed. See `find_non_planar_facets`, `find_non_simple_facets`, and
+    /// `find_open_facet_edges` to obtain the offending indices.
+    pub fn validate_facets(&self, tolerance: f64) -> Result<(), StrError> {
+        if !self.find_non_simple_facets()?.is_empty() {
+            return Err("found non-simple facets (see find_non_simple_facets)");
+        }
+        if !self.find_non_planar_facets(tolerance)?.is_empty() {
+            return Err("found non-planar facets (see find_non_planar_facets)");
+        }
+        if !self.find_open_facet_edges()?.is_empty() {
+            return Err("the surface is not closed (see find_open_facet_edges)");
+        }
+        Ok(())
+    }
+
+    /// Returns the input points and the polygons of each facet
+    fn input_facets(&self) -> Result<(Vec<[f64; 3]>, Vec<FacetPolygons>), StrError> {
+        let (npoint, all_points_set) = self.input_npoint();
+        if !all_points_set {
+            return Err("cannot check facets because not all points are set");
+        }
+        let (nfacet, all_facets_set) = self.input_nfacet();
+        if !all_facets_set {
+            return Err("cannot check facets because not all facets are set");
+        }
+        let points = (0..npoint).map(|p| self.input_point(p)).collect();
+        let facets = (0..nfacet).map(|f| self.input_facet_polygons(f)).collect();
+        Ok((points, facets))
+    }
+}
+
+/// Returns the unit normal (Newell's method) and the centroid of a polygon or None if the area is zero
+fn facet_plane(points: &[[f64; 3]], polygon: &[usize]) -> Option<([f64; 3], [f64; 3])> {
+    let n = polygon.len();
+    let mut normal = [0.0; 3];
+    let mut center = [0.0; 3];
+    for k in 0..n {
+        let (a, b) = (points[polygon[k]], points[polygon[(k + 1) % n]]);
+        normal[0] += (a[1] - b[1]) * (a[2] + b[2]);
+        normal[1] += (a[2] - b[2]) * (a[0] + b[0]);
+        normal[2] += (a[0] - b[0]) * (a[1] + b[1]);
+        for i in 0..3 {
+            center[i] += a[i] / (n as f64);
+        }
+    }
+    let norm = f64::sqrt(normal.iter().map(|v| v * v).sum());
+    if norm == 0.0 {
+        return None;
+    }
+    Some((normal.map(|v| v / norm), center))
+}
+
+/// Returns the edges (a, b) with a < b of a polygon (or a single edge if there are two points)
+fn polygon_edges(polygon: &[usize]) -> Vec<(usize, usize)> {
+    let n = polygon.len();
+    let nedge = if n < 3 { n.saturating_sub(1) } else { n };
+    (0..nedge)
+        .map(|k| {
+            let (a, b) = (polygon[k], polygon[(k + 1) % n]);
+            (usize::min(a, b), usize::max(a, b))
+        })
+        .collect()
+}
+
+/// Returns the sorted pairs of points whose distance is not greater than the tolerance
+///
+/// The points are sorted by the x-coordinate and swept; thus, only the points within the
+/// tolerance along x are compared.
+fn find_duplicates<const N: usize>(points: &[[f64; N]], tolerance: f64) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|a, b| points[*a][0].total_cmp(&points[*b][0]));
+    let tol_squared = tolerance * tolerance;
+    let mut pairs = Vec::new();
+    for (i, a) in order.iter().enumerate() {
+        for b in &order[(i + 1)..] {
+            if points[*b][0] - points[*a][0] > tolerance {
+                break;
+            }
+            let dist_squared: f64 = (0..N).map(|d| (points[*b][d] - points[*a][d]).powi(2)).sum();
+            if dist_squared <= tol_squared {
+                pairs.push((usize::min(*a, *b), usize::max(*a, *b)));
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// Returns the sorted pairs of segments having any point in common other than a shared endpoint
+///
+/// The segments are sorted by the minimum x-coordinate and swept; thus, only the segments with
+/// overlapping ranges along x are compared. The degenerate segments are skipped.
+fn find_crossings(points: &[[f64; 2]], segments: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let xmin = |s: usize| f64::min(points[segments[s].0][0], points[segments[s].1][0]);
+    let xmax = |s: usize| f64::max(points[segments[s].0][0], points[segments[s].1][0]);
+    let mut order: Vec<usize> = (0..segments.len())
+        .filter(|s| segments[*s].0 != segments[*s].1)
+        .collect();
+    order.sort_by(|a, b| xmin(*a).total_cmp(&xmin(*b)));
+    let mut pairs = Vec::new();
+    for (i, s) in order.iter().enumerate() {
+        for t in &order[(i + 1)..] {
+            if xmin(*t) > xmax(*s) {
+                break;
+            }
+            if segments_intersect(points, segments[*s], segments[*t]) {
+                pairs.push((usize::min(*s, *t), usize::max(*s, *t)));
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// Indicates whether two segments have any point in common other than a shared endpoint
+fn segments_intersect(points: &[[f64; 2]], (a, b): (usize, usize), (c, d): (usize, usize)) -> bool {
+    // shared endpoints
+    let shared = [a == c, a == d, b == c, b == d];
+    match shared.iter().filter(|s| **s).count() {
+        0 => (),
+        1 => {
+            // the segments overlap if they are collinear and point to the same direction
+            let (s, u, v) = if a == c {
+                (a, b, d)
+            } else if a == d {
+                (a, b, c)
+            } else if b == c {
+                (b, a, d)
+            } else {
+                (b, a, c)
+            };
+            let (xs, xu, xv) = (points[s], points[u], points[v]);
+            let dot = (xu[0] - xs[0]) * (xv[0] - xs[0]) + (xu[1] - xs[1]) * (xv[1] - xs[1]);
+            return orientation(&xs, &xu, &xv) == 0.0 && dot > 0.0;
+        }
+        _ => return true, // same segment
+    }
+
+    // general case
+    let (pa, pb, pc, pd) = (points[a], points[b], points[c], points[d]);
+    let d1 = orientation(&pc, &pd, &pa);
+    let d2 = orientation(&pc, &pd, &pb);
+    let d3 = orientation(&pa, &pb, &pc);
+    let d4 = orientation(&pa, &pb, &pd);
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        return true;
+    }
+    (d1 == 0.0 && in_box(&pa, &pc, &pd))
+        || (d2 == 0.0 && in_box(&pb, &pc, &pd))
+        || (d3 == 0.0 && in_box(&pc, &pa, &pb))
+        || (d4 == 0.0 && in_box(&pd, &pa, &pb))
+}
+
+/// Returns twice the signed area of the triangle (a, b, c); positive if counterclockwise
+fn orientation(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Indicates whether the point x is within the bounding box of the segment (a, b)
+fn in_box(x: &[f64; 2], a: &[f64; 2], b: &[f64; 2]) -> bool {
+    (0..2).all(|i| x[i] >= f64::min(a[i], b[i]) && x[i] <= f64::max(a[i], b[i]))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{find_crossings, find_duplicates};
+    use crate::fixtures::{cube_points, CUBE_FACETS};
+    use crate::{StrError, Tetgen, Triangle};
+
+    #[test]
+    fn find_duplicates_works() {
+        let points = [[1.0, 0.0], [0.0, 0.0], [1.0, 0.0], [0.5, 0.5], [1.0, 1e-8], [1.0, 0.0]];
+        assert_eq!(find_duplicates(&points, 0.0), &[(0, 2), (0, 5), (2, 5)]);
+        assert_eq!(
+            find_duplicates(&points, 1e-6),
+            &[(0, 2), (0, 4), (0, 5), (2, 4), (2, 5), (4, 5)]
+        );
+        assert_eq!(find_duplicates(&points[1..4], 0.5), &[] as &[(usize, usize)]);
+        assert_eq!(
+            find_duplicates(&points[..4], 1.0),
+            &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn find_crossings_works() {
+        //  3-----2
+        //  |     |
+        //  0--4--1--5
+        let points = [[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0], [1.0, 0.0], [3.0, 0.0]];
+        let square = [(0, 1), (1, 2), (2, 3), (3, 0)];
+        assert_eq!(find_crossings(&points, &square), &[] as &[(usize, usize)]);
+
+        // crossing diagonals (the degenerate segment is skipped)
+        let segments = [(0, 2), (1, 3), (4, 4)];
+        assert_eq!(find_crossings(&points, &segments), &[(0, 1)]);
+
+        // T-junction: 4 is on the interior of 0-1
+        let segments = [(0, 1), (4, 2)];
+        assert_eq!(find_crossings(&points, &segments), &[(0, 1)]);
+
+        // collinear with a shared endpoint: overlapping and non-overlapping
+        let segments = [(0, 1), (0, 4), (1, 5)];
+        assert_eq!(find_crossings(&points, &segments), &[(0, 1)]);
+
+        // collinear without shared endpoints
+        let segments = [(0, 1), (4, 5)];
+        assert_eq!(find_crossings(&points, &segments), &[(0, 1)]);
+
+        // repeated segment
+        let segments = [(0, 1), (1, 0), (1, 2)];
+        assert_eq!(find_crossings(&points, &segments), &[(0, 1)]);
+    }
+
+    #[test]
+    fn triangle_find_crossing_segments_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?;
+        assert_eq!(
+            triangle.find_crossing_segments().err(),
+            Some("cannot find crossing segments because not all segments are set")
+        );
+        assert_eq!(
+            triangle.find_degenerate_segments().err(),
+            Some("cannot find degenerate segments because not all segments are set")
+        );
+        triangle.set_segment(2, 2, 3)?.set_segment(3, 3, 0)?;
+        assert_eq!(triangle.validate_input(0.0), Ok(()));
+
+        // bow-tie
+        triangle
+            .set_segment(1, 1, 3)?
+            .set_segment(2, 3, 2)?
+            .set_segment(3, 2, 0)?;
+        assert_eq!(triangle.find_crossing_segments()?, &[(1, 3)]);
+        assert_eq!(
+            triangle.validate_input(0.0).err(),
+            Some("found crossing segments (see find_crossing_segments)")
+        );
+
+        // degenerate segment
+        triangle
+            .set_segment(0, 1, 2)?
+            .set_segment(1, 2, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 1)?;
+        assert_eq!(triangle.find_degenerate_segments()?, &[1]);
+        assert_eq!(
+            triangle.validate_input(0.0).err(),
+            Some("found segments referencing the same point twice (see find_degenerate_segments)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_validate_input_works() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        assert_eq!(
+            triangle.validate_input(0.0).err(),
+            Some("cannot find duplicate points because not all points are set")
+        );
+        triangle.set_point(3, 1.0, 1.0)?;
+        assert_eq!(triangle.validate_input(0.0), Ok(()));
+        triangle.set_point(3, 0.0, 1.0)?;
+        assert_eq!(triangle.find_duplicate_points(0.0)?, &[(2, 3)]);
+        assert_eq!(
+            triangle.validate_input(0.0).err(),
+            Some("found duplicate points (see find_duplicate_points)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_validate_input_works() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(5, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        assert_eq!(
+            tetgen.validate_input(0.0).err(),
+            Some("cannot find duplicate points because not all points are set")
+        );
+        tetgen.set_point(4, 1e-10, 1.0, 0.0)?;
+        assert_eq!(tetgen.validate_input(0.0), Ok(()));
+        assert_eq!(tetgen.find_duplicate_points(1e-9)?, &[(2, 4)]);
+        assert_eq!(
+            tetgen.validate_input(1e-9).err(),
+            Some("found duplicate points (see find_duplicate_points)")
+        );
+        Ok(())
+    }
+
+    fn set_faces(tetgen: &mut Tetgen, faces: &[[usize; 4]; 6]) -> Result<(), StrError> {
+        for (index, face) in faces.iter().enumerate() {
+            for (m, p) in face.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_validate_facets_works() -> Result<(), StrError> {
+        let mut faces = CUBE_FACETS;
+        let mut tetgen = cube_points()?;
+        assert_eq!(
+            tetgen.validate_facets(1e-10).err(),
+            Some("cannot check facets because not all facets are set")
+        );
+        set_faces(&mut tetgen, &faces)?;
+        assert_eq!(tetgen.validate_facets(1e-10), Ok(()));
+
+        // an additional polygon on the top facet does not open the surface
+        tetgen.set_facet_polygon(5, 1, &[4, 6])?;
+        assert_eq!(tetgen.validate_facets(1e-10), Ok(()));
+
+        // non-planar
+        tetgen.set_point(7, 0.0, 1.0, 1.1)?;
+        assert_eq!(tetgen.find_non_planar_facets(1e-10)?, &[5]);
+        assert_eq!(tetgen.find_non_planar_facets(0.1)?, &[] as &[usize]);
+        assert_eq!(
+            tetgen.validate_facets(1e-10).err(),
+            Some("found non-planar facets (see find_non_planar_facets)")
+        );
+
+        // non-simple (bow-tie)
+        let mut tetgen = cube_points()?;
+        faces[5] = [4, 5, 7, 6];
+        set_faces(&mut tetgen, &faces)?;
+        assert_eq!(tetgen.find_non_simple_facets()?, &[5]);
+        assert_eq!(
+            tetgen.validate_facets(1e-10).err(),
+            Some("found non-simple facets (see find_non_simple_facets)")
+        );
+
+        // open (the top facet is replaced by a copy of the bottom facet)
+        let mut tetgen = cube_points()?;
+        faces[5] = [0, 1, 2, 3];
+        set_faces(&mut tetgen, &faces)?;
+        assert_eq!(tetgen.find_open_facet_edges()?, &[(4, 5), (4, 7), (5, 6), (6, 7)]);
+        assert_eq!(
+            tetgen.validate_facets(1e-10).err(),
+            Some("the surface is not closed (see find_open_facet_edges)")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+/// Returns a - b
+pub(crate) fn sub<const N: usize>(a: &[f64; N], b: &[f64; N]) -> [f64; N] {
+    std::array::from_fn(|i| a[i] - b[i])
+}
+
+/// Returns the dot product a · b
+pub(crate) fn dot<const N: usize>(a: &[f64; N], b: &[f64; N]) -> f64 {
+    (0..N).map(|i| a[i] * b[i]).sum()
+}
+
+/// Returns the cross product a × b
+pub(crate) fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Returns the Euclidean norm of a vector
+pub(crate) fn norm<const N: usize>(a: &[f64; N]) -> f64 {
+    f64::sqrt(dot(a, a))
+}
+
+/// Returns the (non-normalized) normal vector (b - a) × (c - a) of the triangle (a, b, c)
+pub(crate) fn triangle_normal(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> [f64; 3] {
+    cross(&sub(b, a), &sub(c, a))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{cross, dot, norm, sub, triangle_normal};
+
+    #[test]
+    fn vector_functions_work() {
+        assert_eq!(sub(&[3.0, 2.0], &[1.0, 5.0]), [2.0, -3.0]);
+        assert_eq!(dot(&[1.0, 2.0, 3.0], &[4.0, -5.0, 6.0]), 12.0);
+        assert_eq!(cross(&[1.0, 0.0, 0.0], &[0.0, 1.0, 0.0]), [0.0, 0.0, 1.0]);
+        assert_eq!(norm(&[3.0, 4.0]), 5.0);
+        let normal = triangle_normal(&[1.0, 1.0, 1.0], &[3.0, 1.0, 1.0], &[1.0, 4.0, 1.0]);
+        assert_eq!(normal, [0.0, 0.0, 6.0]);
+    }
+}
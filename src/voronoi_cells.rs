@@ -0,0 +1,463 @@
+use crate::vector::{cross, dot, norm};
+use crate::StrError;
+use crate::{Tetgen, Triangle};
+
+/// Holds a convex polyhedron given by its points and faces
+///
+/// The faces are given by the indices of their points, counterclockwise when seen from outside.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Polyhedron {
+    /// Holds the (x, y, z) coordinates of the points
+    pub points: Vec<[f64; 3]>,
+
+    /// Holds the indices of the points of each face
+    pub faces: Vec<Vec<usize>>,
+}
+
+impl Polyhedron {
+    /// Returns the volume of the polyhedron
+    pub fn volume(&self) -> f64 {
+        // sum of the signed volumes of the tetrahedra connecting the origin to a fan of each face
+        let mut volume = 0.0;
+        for face in &self.faces {
+            let a = &self.points[face[0]];
+            for m in 1..(face.len() - 1) {
+                let b = &self.points[face[m]];
+                let c = &self.points[face[m + 1]];
+                volume += dot(a, &cross(b, c)) / 6.0;
+            }
+        }
+        volume
+    }
+}
+
+impl Triangle {
+    /// Returns the Voronoi cells clipped by an axis-aligned bounding box
+    ///
+    /// The Voronoi cell of each point (site) is computed by clipping the box with the bisectors
+    /// between the site and its Delaunay neighbors. Thus, this function must be called after
+    /// `generate_delaunay` or `generate_voronoi`; otherwise, the neighbors are not the natural ones.
+    ///
+    /// # Input
+    ///
+    /// * `min` -- the (x, y) coordinates of the minimum corner of the box
+    /// * `max` -- the (x, y) coordinates of the maximum corner of the box
+    ///
+    /// # Output
+    ///
+    /// Returns the counterclockwise vertices of the cell of each point, or an empty vector
+    /// if the cell does not intersect the box.
+    pub fn voronoi_cells(&self, min: &[f64; 2], max: &[f64; 2]) -> Result<Vec<Vec<[f64; 2]>>, StrError> {
+        if self.ntriangle() < 1 {
+            return Err("the Delaunay triangulation must be generated first");
+        }
+        if min[0] >= max[0] || min[1] >= max[1] {
+            return Err("the minimum corner of the box must be smaller than the maximum corner");
+        }
+        let npoint = self.npoint();
+        let site = |p: usize| [self.point(p, 0), self.point(p, 1)];
+        let mut neighbors = vec![Vec::new(); npoint];
+        for tri in 0..self.ntriangle() {
+            let v = [
+                self.triangle_node(tri, 0),
+                self.triangle_node(tri, 1),
+                self.triangle_node(tri, 2),
+            ];
+            for m in 0..3 {
+                neighbors[v[m]].push(v[(m + 1) % 3]);
+                neighbors[v[m]].push(v[(m + 2) % 3]);
+            }
+        }
+        let tol = tolerance(min, max);
+        let square = vec![[min[0], min[1]], [max[0], min[1]], [max[0], max[1]], [min[0], max[1]]];
+        let mut cells = Vec::with_capacity(npoint);
+        for (p, others) in neighbors.iter_mut().enumerate() {
+            others.sort();
+            others.dedup();
+            let mut polygon = square.clone();
+            for q in others.iter() {
+                let (normal, offset) = bisector(&site(p), &site(*q));
+                polygon = clip_polygon(&polygon, &normal, offset, tol);
+                if polygon.is_empty() {
+                    break;
+                }
+            }
+            cells.push(polygon);
+        }
+        Ok(cells)
+    }
+}
+
+impl Tetgen {
+    /// Returns the Voronoi cells clipped by an axis-aligned bounding box
+    ///
+    /// The Voronoi cell of each point (site) is computed by clipping the box with the bisectors
+    /// between the site and its Delaunay neighbors. Thus, this function must be called after
+    /// `generate_delaunay`; otherwise, the neighbors are not the natural ones.
+    ///
+    /// # Input
+    ///
+    /// * `min` -- the (x, y, z) coordinates of the minimum corner of the box
+    /// * `max` -- the (x, y, z) coordinates of the maximum corner of the box
+    ///
+    /// # Output
+    ///
+    /// Returns the cell of each point, with no faces if the cell does not intersect the box.
+    pub fn voronoi_cells(&self, min: &[f64; 3], max: &[f64; 3]) -> Result<Vec<Polyhedron>, StrError> {
+        if self.ntet() < 1 {
+            return Err("the Delaunay triangulation must be generated first");
+        }
+        if (0..3).any(|i| min[i] >= max[i]) {
+            return Err("the minimum corner of the box must be smaller than the maximum corner");
+        }
+        let npoint = self.npoint();
+        let site = |p: usize| [self.point(p, 0), self.point(p, 1), self.point(p, 2)];
+        let mut neighbors = vec![Vec::new(); npoint];
+        for tet in 0..self.ntet() {
+            let v = [
+                self.tet_node(tet, 0),
+                self.tet_node(tet, 1),
+                self.tet_node(tet, 2),
+                self.tet_node(tet, 3),
+            ];
+            for a in 0..4 {
+                for b in 0..4 {
+                    if a != b {
+                        neighbors[v[a]].push(v[b]);
+                    }
+                }
+            }
+        }
+        let tol = tolerance(min, max);
+        let cube = box_faces(min, max);
+        let mut cells = Vec::with_capacity(npoint);
+        for (p, others) in neighbors.iter_mut().enumerate() {
+            others.sort();
+            others.dedup();
+            let mut faces = cube.clone();
+            for q in others.iter() {
+                let (normal, offset) = bisector(&site(p), &site(*q));
+                faces = clip_polyhedron(&faces, &normal, offset, tol);
+                if faces.is_empty() {
+                    break;
+                }
+            }
+            cells.push(to_polyhedron(&faces, tol));
+        }
+        Ok(cells)
+    }
+}
+
+/// Returns the (normal, offset) of the half-space {x : normal · x ≤ offset} closer to a than to b
+fn bisector<const DIM: usize>(a: &[f64; DIM], b: &[f64; DIM]) -> ([f64; DIM], f64) {
+    let mut normal = [0.0; DIM];
+    let mut offset = 0.0;
+    for i in 0..DIM {
+        normal[i] = b[i] - a[i];
+        offset += normal[i] * (a[i] + b[i]) / 2.0;
+    }
+    (normal, offset)
+}
+
+/// Returns the tolerance used to compare coordinates, relative to the size of the box
+fn tolerance(min: &[f64], max: &[f64]) -> f64 {
+    let diagonal: f64 = min.iter().zip(max).map(|(a, b)| (b - a) * (b - a)).sum();
+    1e-10 * f64::sqrt(diagonal)
+}
+
+/// Clips a polygon by the half-space {x : normal · x ≤ offset} (Sutherland-Hodgman)
+fn clip_polygon<const DIM: usize>(
+    polygon: &[[f64; DIM]],
+    normal: &[f64; DIM],
+    offset: f64,
+    tol: f64,
+) -> Vec<[f64; DIM]> {
+    let size = norm(normal);
+    let distance = |x: &[f64; DIM]| (dot(normal, x) - offset) / size;
+    let mut result = Vec::new();
+    let n = polygon.len();
+    for m in 0..n {
+        let a = &polygon[m];
+        let b = &polygon[(m + 1) % n];
+        let (da, db) = (distance(a), distance(b));
+        if da <= tol {
+            result.push(*a);
+        }
+        if (da < -tol && db > tol) || (da > tol && db < -tol) {
+            let t = da / (da - db);
+            let mut x = [0.0; DIM];
+            for i in 0..DIM {
+                x[i] = a[i] + t * (b[i] - a[i]);
+            }
+            result.push(x);
+        }
+    }
+    if result.len() < 3 {
+        result.clear();
+    }
+    result
+}
+
+/// Clips a convex polyhedron (given by its faces) by the half-space {x : normal · x ≤ offset}
+///
+/// The cut is closed by a new face on the plane, oriented outwards (along the normal).
+fn clip_polyhedron(faces: &[Vec<[f64; 3]>], normal: &[f64; 3], offset: f64, tol: f64) -> Vec<Vec<[f64; 3]>> {
+    let size = norm(normal);
+    let distance = |x: &[f64; 3]| (dot(normal, x) - offset) / size;
+    if faces.iter().flatten().all(|x| distance(x) <= tol) {
+        return faces.to_vec();
+    }
+    let mut result = Vec::new();
+    let mut on_plane: Vec<[f64; 3]> = Vec::new();
+    for face in faces {
+        let clipped = clip_polygon(face, normal, offset, tol);
+        for x in &clipped {
+            if f64::abs(distance(x)) <= tol && !on_plane.iter().any(|y| same_point(x, y, tol)) {
+                on_plane.push(*x);
+            }
+        }
+        if !clipped.is_empty() {
+            result.push(clipped);
+        }
+    }
+    if result.is_empty() {
+        return result;
+    }
+    if on_plane.len() >= 3 {
+        // sort the points counterclockwise around the normal
+        let n = [normal[0] / size, normal[1] / size, normal[2] / size];
+        let k = (0..3)
+            .min_by(|i, j| n[*i].abs().partial_cmp(&n[*j].abs()).unwrap())
+            .unwrap();
+        let mut e = [0.0; 3];
+        e[k] = 1.0;
+        let u = cross(&e, &n);
+        let v = cross(&n, &u);
+        let mut center = [0.0; 3];
+        for x in &on_plane {
+            for i in 0..3 {
+                center[i] += x[i] / on_plane.len() as f64;
+            }
+        }
+        let angle = |x: &[f64; 3]| {
+            let d = [x[0] - center[0], x[1] - center[1], x[2] - center[2]];
+            f64::atan2(dot(&d, &v), dot(&d, &u))
+        };
+        on_plane.sort_by(|x, y| angle(x).partial_cmp(&angle(y)).unwrap());
+        result.push(on_plane);
+    }
+    result
+}
+
+/// Returns the faces of a box, counterclockwise when seen from outside
+fn box_faces(min: &[f64; 3], max: &[f64; 3]) -> Vec<Vec<[f64; 3]>> {
+    let corner = |i: usize| {
+        [
+            if i & 1 == 0 { min[0] } else { max[0] },
+            if i & 2 == 0 { min[1] } else { max[1] },
+            if i & 4 == 0 { min[2] } else { max[2] },
+        ]
+    };
+    [
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+    ]
+    .iter()
+    .map(|face| face.iter().map(|i| corner(*i)).collect())
+    .collect()
+}
+
+/// Converts a list of faces given by coordinates into a polyhedron with shared points
+fn to_polyhedron(faces: &[Vec<[f64; 3]>], tol: f64) -> Polyhedron {
+    let mut polyhedron = Polyhedron::default();
+    for face in faces {
+        let mut ids: Vec<usize> = Vec::new();
+        for x in face {
+            let id = match polyhedron.points.iter().position(|y| same_point(x, y, tol)) {
+                Some(id) => id,
+                None => {
+                    polyhedron.points.push(*x);
+                    polyhedron.points.len() - 1
+                }
+            };
+            if ids.last() != Some(&id) && ids.first() != Some(&id) {
+                ids.push(id);
+            }
+        }
+        if ids.len() >= 3 {
+            polyhedron.faces.push(ids);
+        }
+    }
+    polyhedron
+}
+
+/// Indicates whether two points coincide within a tolerance
+fn same_point<const DIM: usize>(a: &[f64; DIM], b: &[f64; DIM], tol: f64) -> bool {
+    (0..DIM).all(|i| f64::abs(a[i] - b[i]) <= tol)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{box_faces, to_polyhedron};
+    use crate::{StrError, Tetgen, Triangle};
+
+    /// Returns the area of a polygon
+    fn area(polygon: &[[f64; 2]]) -> f64 {
+        let n = polygon.len();
+        (0..n)
+            .map(|m| {
+                let (a, b) = (&polygon[m], &polygon[(m + 1) % n]);
+                (a[0] * b[1] - b[0] * a[1]) / 2.0
+            })
+            .sum()
+    }
+
+    /// Returns pseudo-random numbers in [0, 1)
+    fn random(n: usize) -> Vec<f64> {
+        let mut seed: u64 = 12345;
+        (0..n)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (seed >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn box_polyhedron_works() {
+        let cube = to_polyhedron(&box_faces(&[0.0, 0.0, 0.0], &[1.0, 2.0, 3.0]), 1e-10);
+        assert_eq!(cube.points.len(), 8);
+        assert_eq!(cube.faces.len(), 6);
+        assert!(f64::abs(cube.volume() - 6.0) < 1e-14);
+    }
+
+    #[test]
+    fn voronoi_cells_capture_some_errors() -> Result<(), StrError> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.voronoi_cells(&[0.0, 0.0], &[1.0, 1.0]).err(),
+            Some("the Delaunay triangulation must be generated first")
+        );
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.voronoi_cells(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0]).err(),
+            Some("the Delaunay triangulation must be generated first")
+        );
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(
+            triangle.voronoi_cells(&[0.0, 0.0], &[1.0, 0.0]).err(),
+            Some("the minimum corner of the box must be smaller than the maximum corner")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn triangle_voronoi_cells_work() -> Result<(), StrError> {
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.25, 0.25)?
+            .set_point(1, 0.75, 0.25)?
+            .set_point(2, 0.75, 0.75)?
+            .set_point(3, 0.25, 0.75)?;
+        triangle.generate_delaunay(false)?;
+        let cells = triangle.voronoi_cells(&[0.0, 0.0], &[1.0, 1.0])?;
+        assert_eq!(cells.len(), 4);
+        for cell in &cells {
+            assert_eq!(cell.len(), 4);
+            assert!(f64::abs(area(cell) - 0.25) < 1e-14);
+        }
+        assert_eq!(cells[0], &[[0.0, 0.0], [0.5, 0.0], [0.5, 0.5], [0.0, 0.5]]);
+
+        // random points
+        let npoint = 50;
+        let x = random(2 * npoint);
+        let mut triangle = Triangle::new(npoint, None, None, None)?;
+        for p in 0..npoint {
+            triangle.set_point(p, x[2 * p], x[2 * p + 1])?;
+        }
+        triangle.generate_voronoi(false)?;
+        let cells = triangle.voronoi_cells(&[-1.0, -1.0], &[2.0, 2.0])?;
+        for (p, cell) in cells.iter().enumerate() {
+            assert!(area(cell) > 0.0);
+            // the cell contains its site
+            let site = [triangle.point(p, 0), triangle.point(p, 1)];
+            for m in 0..cell.len() {
+                let (a, b) = (&cell[m], &cell[(m + 1) % cell.len()]);
+                let cross = (b[0] - a[0]) * (site[1] - a[1]) - (b[1] - a[1]) * (site[0] - a[0]);
+                assert!(cross > 0.0);
+            }
+        }
+        let total: f64 = cells.iter().map(|cell| area(cell)).sum();
+        assert!(f64::abs(total - 9.0) < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn tetgen_voronoi_cells_work() -> Result<(), StrError> {
+        let mut tetgen = Tetgen::new(8, None, None, None)?;
+        for (p, x) in [
+            [0.25, 0.25, 0.25],
+            [0.75, 0.25, 0.25],
+            [0.75, 0.75, 0.25],
+            [0.25, 0.75, 0.25],
+            [0.25, 0.25, 0.75],
+            [0.75, 0.25, 0.75],
+            [0.75, 0.75, 0.75],
+            [0.25, 0.75, 0.75],
+        ]
+        .iter()
+        .enumerate()
+        {
+            tetgen.set_point(p, x[0], x[1], x[2])?;
+        }
+        tetgen.generate_delaunay(false)?;
+        let cells = tetgen.voronoi_cells(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0])?;
+        assert_eq!(cells.len(), 8);
+        for cell in &cells {
+            assert_eq!(cell.points.len(), 8);
+            assert_eq!(cell.faces.len(), 6);
+            assert!(f64::abs(cell.volume() - 0.125) < 1e-14);
+        }
+
+        // random points
+        let npoint = 40;
+        let x = random(3 * npoint);
+        let mut tetgen = Tetgen::new(npoint, None, None, None)?;
+        for p in 0..npoint {
+            tetgen.set_point(p, x[3 * p], x[3 * p + 1], x[3 * p + 2])?;
+        }
+        tetgen.generate_delaunay(false)?;
+        let cells = tetgen.voronoi_cells(&[-1.0, -1.0, -1.0], &[2.0, 2.0, 2.0])?;
+        assert_eq!(cells.len(), tetgen.npoint());
+        for cell in &cells {
+            assert!(cell.volume() > 0.0);
+            // each edge is shared by exactly two faces (closed surface)
+            let mut edges = Vec::new();
+            for face in &cell.faces {
+                for m in 0..face.len() {
+                    let (a, b) = (face[m], face[(m + 1) % face.len()]);
+                    edges.push(if a < b { (a, b) } else { (b, a) });
+                }
+            }
+            edges.sort();
+            assert_eq!(edges.len() % 2, 0);
+            for pair in edges.chunks(2) {
+                assert_eq!(pair[0], pair[1]);
+            }
+        }
+        let total: f64 = cells.iter().map(|cell| cell.volume()).sum();
+        assert!(f64::abs(total - 27.0) < 1e-10);
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tritet::{Progress, StrError, Tetgen, Triangle};
+
+const NTHREAD: usize = 8;
+const NREPEAT: usize = 5;
+
+/// Holds the output of a generation (coordinates and cells) and the number of console lines
+#[derive(Debug, PartialEq)]
+struct Output {
+    points: Vec<Vec<f64>>,
+    cells: Vec<Vec<usize>>,
+    nline: usize,
+}
+
+/// Generates a mesh of a square with a hole (the max area depends on the seed)
+fn run_triangle(seed: usize) -> Result<Output, StrError> {
+    let mut triangle = Triangle::new(8, Some(8), Some(1), Some(1))?;
+    triangle
+        .set_point(0, 0.0, 0.0)?
+        .set_point(1, 1.0, 0.0)?
+        .set_point(2, 1.0, 1.0)?
+        .set_point(3, 0.0, 1.0)?
+        .set_point(4, 0.4, 0.4)?
+        .set_point(5, 0.6, 0.4)?
+        .set_point(6, 0.6, 0.6)?
+        .set_point(7, 0.4, 0.6)?;
+    for i in 0..4 {
+        triangle.set_segment(i, i, (i + 1) % 4)?;
+        triangle.set_segment(4 + i, 4 + i, 4 + (i + 1) % 4)?;
+    }
+    triangle.set_region(0, 0.1, 0.1, 1, None)?.set_hole(0, 0.5, 0.5)?;
+    let nline = Arc::new(Mutex::new(0));
+    let counter = nline.clone();
+    triangle.set_console_handler(move |_: &str| *counter.lock().unwrap() += 1);
+    let max_area = 0.01 / (1 + seed % 4) as f64;
+    triangle.generate_mesh(true, seed % 2 == 1, Some(max_area), Some(25.0))?;
+    let points = (0..triangle.npoint())
+        .map(|p| vec![triangle.point(p, 0), triangle.point(p, 1)])
+        .collect();
+    let cells = (0..triangle.ntriangle())
+        .map(|t| (0..triangle.nnode()).map(|m| triangle.triangle_node(t, m)).collect())
+        .collect();
+    let nline = *nline.lock().unwrap();
+    Ok(Output { points, cells, nline })
+}
+
+/// Generates a mesh of a cube (the max volume depends on the seed)
+fn run_tetgen(seed: usize) -> Result<Output, StrError> {
+    let mut tetgen = Tetgen::new(8, Some(vec![4; 6]), None, None)?;
+    let points = [
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 1.0],
+        [1.0, 1.0, 1.0],
+        [0.0, 1.0, 1.0],
+    ];
+    for (p, x) in points.iter().enumerate() {
+        tetgen.set_point(p, x[0], x[1], x[2])?;
+    }
+    let faces = [
+        [0, 4, 7, 3],
+        [1, 2, 6, 5],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+    ];
+    for (f, face) in faces.iter().enumerate() {
+        for (m, p) in face.iter().enumerate() {
+            tetgen.set_facet_point(f, m, *p)?;
+        }
+    }
+    let nline = Arc::new(Mutex::new(0));
+    let counter = nline.clone();
+    tetgen.set_console_handler(move |_: &str| *counter.lock().unwrap() += 1);
+    tetgen.set_progress_handler(|_: &Progress| ());
+    let max_volume = 0.005 / (1 + seed % 4) as f64;
+    tetgen.generate_mesh(true, seed % 2 == 1, Some(max_volume), None)?;
+    let points = (0..tetgen.npoint())
+        .map(|p| (0..3).map(|dim| tetgen.point(p, dim)).collect())
+        .collect();
+    let cells = (0..tetgen.ntet())
+        .map(|t| (0..tetgen.nnode()).map(|m| tetgen.tet_node(t, m)).collect())
+        .collect();
+    let nline = *nline.lock().unwrap();
+    Ok(Output { points, cells, nline })
+}
+
+#[test]
+fn concurrent_generation_matches_sequential_generation() -> Result<(), StrError> {
+    // sequential results (the console output contains timings; thus, only the number of lines is compared)
+    let mut correct_triangle = Vec::new();
+    let mut correct_tetgen = Vec::new();
+    for seed in 0..NTHREAD {
+        correct_triangle.push(run_triangle(seed)?);
+        correct_tetgen.push(run_tetgen(seed)?);
+    }
+
+    // Triangle and Tetgen running at the same time in all threads
+    thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for seed in 0..NTHREAD {
+            let (triangle, tetgen) = (&correct_triangle[seed], &correct_tetgen[seed]);
+            handles.push(scope.spawn(move || {
+                for repeat in 0..NREPEAT {
+                    if (seed + repeat) % 2 == 0 {
+                        assert_eq!(&run_triangle(seed).unwrap(), triangle);
+                        assert_eq!(&run_tetgen(seed).unwrap(), tetgen);
+                    } else {
+                        assert_eq!(&run_tetgen(seed).unwrap(), tetgen);
+                        assert_eq!(&run_triangle(seed).unwrap(), triangle);
+                    }
+                }
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    });
+    Ok(())
+}